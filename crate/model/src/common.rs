@@ -8,6 +8,7 @@ pub use self::{
     edges::Edges,
     graphviz_attrs::GraphvizAttrs,
     graphviz_dot_theme::GraphvizDotTheme,
+    html_src_and_styles::HtmlSrcAndStyles,
     image_id::{ImageId, ImageIdInvalidFmt},
     images::Images,
     node_descs::NodeDescs,
@@ -17,10 +18,12 @@ pub use self::{
     node_images::NodeImages,
     node_names::NodeNames,
     node_tags_set::NodeTagsSet,
+    svg_src_and_styles::SvgSrcAndStyles,
     tag_id::{TagId, TagIdInvalidFmt},
     tag_items::TagItems,
     tag_names::TagNames,
     tag_styles::TagStyles,
+    view_id::{ViewId, ViewIdInvalidFmt},
 };
 
 pub mod dot_src_and_styles;
@@ -33,6 +36,7 @@ mod edge_descs;
 mod edge_id;
 mod edge_tags_set;
 mod edges;
+mod html_src_and_styles;
 mod image_id;
 mod images;
 mod node_descs;
@@ -42,7 +46,9 @@ mod node_id;
 mod node_images;
 mod node_names;
 mod node_tags_set;
+mod svg_src_and_styles;
 mod tag_id;
 mod tag_items;
 mod tag_names;
 mod tag_styles;
+mod view_id;
@@ -2,8 +2,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::theme::ThemeWarnings;
 
-pub use self::{graphviz_image::GraphvizImage, graphviz_opts::GraphvizOpts};
+pub use self::{
+    dot_src_map::DotSrcMap, dot_src_map_entry::DotSrcMapEntry, edge_bundles::EdgeBundles,
+    graphviz_image::GraphvizImage, graphviz_opts::GraphvizOpts,
+};
 
+mod dot_src_map;
+mod dot_src_map_entry;
+mod edge_bundles;
 mod graphviz_image;
 mod graphviz_opts;
 
@@ -18,6 +24,12 @@ pub struct DotSrcAndStyles {
     pub opts: GraphvizOpts,
     /// Warnings detected while computing CSS utility classes.
     pub theme_warnings: ThemeWarnings,
+    /// Lines of `dot_src` generated for each node, edge, and tag, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src_map: Option<DotSrcMap>,
+    /// Edges that are drawn as one edge, when edge bundling is enabled.
+    #[serde(default)]
+    pub edge_bundles: EdgeBundles,
 }
 
 impl DotSrcAndStyles {
@@ -33,9 +45,23 @@ impl DotSrcAndStyles {
             styles,
             opts,
             theme_warnings,
+            src_map: None,
+            edge_bundles: EdgeBundles::new(),
         }
     }
 
+    /// Sets the map of dot source lines to the elements that produced them.
+    pub fn with_src_map(mut self, src_map: DotSrcMap) -> Self {
+        self.src_map = Some(src_map);
+        self
+    }
+
+    /// Sets the edges that are drawn as one edge.
+    pub fn with_edge_bundles(mut self, edge_bundles: EdgeBundles) -> Self {
+        self.edge_bundles = edge_bundles;
+        self
+    }
+
     /// Returns the Graphviz dot source to run in `dot`.
     pub fn dot_src(&self) -> &str {
         &self.dot_src
@@ -55,4 +81,19 @@ impl DotSrcAndStyles {
     pub fn theme_warnings(&self) -> &ThemeWarnings {
         &self.theme_warnings
     }
+
+    /// Returns the map of dot source lines to the elements that produced them,
+    /// if it was requested.
+    pub fn src_map(&self) -> Option<&DotSrcMap> {
+        self.src_map.as_ref()
+    }
+
+    /// Returns the edges that are drawn as one edge.
+    ///
+    /// This is empty unless [`GraphvizAttrs::edge_bundling`] is enabled.
+    ///
+    /// [`GraphvizAttrs::edge_bundling`]: crate::common::GraphvizAttrs::edge_bundling
+    pub fn edge_bundles(&self) -> &EdgeBundles {
+        &self.edge_bundles
+    }
 }
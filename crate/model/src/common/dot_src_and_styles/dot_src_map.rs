@@ -0,0 +1,91 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::dot_src_and_styles::DotSrcMapEntry;
+
+/// Maps lines of generated dot source to the `InfoGraph` elements that
+/// produced them. `Vec<DotSrcMapEntry>` newtype.
+///
+/// Entries for clusters span the lines of their child nodes, which have their
+/// own entries.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DotSrcMap(Vec<DotSrcMapEntry>);
+
+impl DotSrcMap {
+    /// Returns a new `DotSrcMap`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new `DotSrcMap` with the given preallocated capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Returns the underlying list.
+    pub fn into_inner(self) -> Vec<DotSrcMapEntry> {
+        self.0
+    }
+
+    /// Returns the innermost entry whose statement spans the given 1-based
+    /// dot source line.
+    ///
+    /// This is the line number that Graphviz reports in its errors.
+    pub fn entry_at_line(&self, line: u32) -> Option<&DotSrcMapEntry> {
+        self.0
+            .iter()
+            .filter(|entry| entry.line_start <= line && line <= entry.line_end)
+            .min_by_key(|entry| entry.line_end - entry.line_start)
+    }
+
+    /// Returns the innermost entry for the line mentioned in a Graphviz error,
+    /// e.g. `syntax error in line 12 near '['`.
+    ///
+    /// Returns `None` if the error doesn't mention a line.
+    pub fn entry_for_error(&self, error: &str) -> Option<&DotSrcMapEntry> {
+        let (_, line_and_rest) = error.split_once("line ")?;
+        let line = line_and_rest
+            .split(|c: char| !c.is_ascii_digit())
+            .next()
+            .and_then(|line| line.parse::<u32>().ok())?;
+
+        self.entry_at_line(line)
+    }
+
+    /// Returns the entries for the given node, edge, or tag ID.
+    pub fn entries_for_id<'f>(
+        &'f self,
+        any_id: &'f str,
+    ) -> impl Iterator<Item = &'f DotSrcMapEntry> + 'f {
+        self.0
+            .iter()
+            .filter(move |entry| entry.any_id.as_str() == any_id)
+    }
+}
+
+impl Deref for DotSrcMap {
+    type Target = Vec<DotSrcMapEntry>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for DotSrcMap {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<DotSrcMapEntry>> for DotSrcMap {
+    fn from(inner: Vec<DotSrcMapEntry>) -> Self {
+        Self(inner)
+    }
+}
+
+impl FromIterator<DotSrcMapEntry> for DotSrcMap {
+    fn from_iter<I: IntoIterator<Item = DotSrcMapEntry>>(iter: I) -> Self {
+        Self(Vec::from_iter(iter))
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::AnyId;
+
+/// Lines of dot source that were generated for a node, cluster, edge, or tag.
+///
+/// # Examples
+///
+/// For the following `InfoGraph`:
+///
+/// ```yaml
+/// hierarchy:
+///   a:
+///     b: {}
+/// edges:
+///   a_to_b: [a, b]
+/// ```
+///
+/// The entry for node `b` has the `doc_path` `hierarchy.a.b`, and the entry
+/// for the edge has the `doc_path` `edges.a_to_b`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DotSrcMapEntry {
+    /// First line of the statement in the dot source, starting from 1.
+    pub line_start: u32,
+    /// Last line of the statement in the dot source, inclusive.
+    pub line_end: u32,
+    /// ID of the node, edge, or tag that the statement was generated for.
+    pub any_id: AnyId,
+    /// Path of keys to the element in the `InfoGraph` document, separated by
+    /// `.`, e.g. `hierarchy.a.b`.
+    pub doc_path: String,
+}
+
+impl DotSrcMapEntry {
+    /// Returns a new `DotSrcMapEntry`.
+    pub fn new(line_start: u32, line_end: u32, any_id: AnyId, doc_path: String) -> Self {
+        Self {
+            line_start,
+            line_end,
+            any_id,
+            doc_path,
+        }
+    }
+
+    /// Returns the first line of the statement in the dot source, starting
+    /// from 1.
+    pub fn line_start(&self) -> u32 {
+        self.line_start
+    }
+
+    /// Returns the last line of the statement in the dot source, inclusive.
+    pub fn line_end(&self) -> u32 {
+        self.line_end
+    }
+
+    /// Returns the ID of the node, edge, or tag that the statement was
+    /// generated for.
+    pub fn any_id(&self) -> &AnyId {
+        &self.any_id
+    }
+
+    /// Returns the path of keys to the element in the `InfoGraph` document,
+    /// e.g. `hierarchy.a.b`.
+    pub fn doc_path(&self) -> &str {
+        &self.doc_path
+    }
+
+    /// Returns the 1-based line of this entry's element in the YAML source of
+    /// the `InfoGraph`.
+    ///
+    /// Keys are matched by indentation, so this works for block style maps. If
+    /// a key is written in flow style, e.g. `a: { b: {} }`, the line of the
+    /// closest ancestor is returned. Returns `None` if the first key of the
+    /// path is not found.
+    pub fn yaml_line(&self, info_graph_src: &str) -> Option<u32> {
+        let mut segments = self.doc_path.split('.');
+        let mut segment = segments.next()?;
+        let mut parent_indent = None::<usize>;
+        let mut child_indent = None::<usize>;
+        let mut line_matched = None::<u32>;
+
+        for (line_index, line) in info_graph_src.lines().enumerate() {
+            let content = line.trim_start();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - content.len();
+            if parent_indent.is_some_and(|parent_indent| indent <= parent_indent) {
+                // We have left the block of the last matched key.
+                break;
+            }
+            if indent != *child_indent.get_or_insert(indent) {
+                continue;
+            }
+
+            let key = content
+                .split_once(':')
+                .map(|(key, _)| key.trim().trim_matches(|c| c == '"' || c == '\''));
+            if key == Some(segment) {
+                line_matched = Some(line_index as u32 + 1);
+                match segments.next() {
+                    Some(segment_next) => {
+                        segment = segment_next;
+                        parent_indent = Some(indent);
+                        child_indent = None;
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        line_matched
+    }
+}
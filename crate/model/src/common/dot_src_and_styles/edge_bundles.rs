@@ -0,0 +1,79 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::common::EdgeId;
+
+/// Edges that are drawn as one edge, keyed by the ID of the drawn edge.
+/// `IndexMap<EdgeId, Vec<EdgeId>>` newtype.
+///
+/// Each bundle's edge IDs include the drawn edge's ID.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EdgeBundles(IndexMap<EdgeId, Vec<EdgeId>>);
+
+impl EdgeBundles {
+    /// Returns a new `EdgeBundles` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new `EdgeBundles` map with the given preallocated
+    /// capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<EdgeId, Vec<EdgeId>> {
+        self.0
+    }
+
+    /// Returns the SVG rendered by Graphviz with each bundled edge's element
+    /// annotated with the IDs of the edges in the bundle.
+    ///
+    /// e.g. `<g id="a__b" data-edge-ids="a__b a__c" class="edge">`.
+    pub fn svg_edge_ids_insert(&self, dot_svg: &str) -> String {
+        self.0
+            .iter()
+            .fold(dot_svg.to_string(), |dot_svg, (edge_id, edge_ids)| {
+                let data_edge_ids = edge_ids
+                    .iter()
+                    .map(EdgeId::as_str)
+                    .collect::<Vec<&str>>()
+                    .join(" ");
+
+                dot_svg.replacen(
+                    &format!("<g id=\"{edge_id}\" "),
+                    &format!("<g id=\"{edge_id}\" data-edge-ids=\"{data_edge_ids}\" "),
+                    1,
+                )
+            })
+    }
+}
+
+impl Deref for EdgeBundles {
+    type Target = IndexMap<EdgeId, Vec<EdgeId>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for EdgeBundles {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<IndexMap<EdgeId, Vec<EdgeId>>> for EdgeBundles {
+    fn from(inner: IndexMap<EdgeId, Vec<EdgeId>>) -> Self {
+        Self(inner)
+    }
+}
+
+impl FromIterator<(EdgeId, Vec<EdgeId>)> for EdgeBundles {
+    fn from_iter<I: IntoIterator<Item = (EdgeId, Vec<EdgeId>)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
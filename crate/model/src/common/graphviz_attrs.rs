@@ -1,17 +1,28 @@
 use serde::{Deserialize, Serialize};
 
 pub use self::{
-    cluster_margin::ClusterMargin, edge_constraints::EdgeConstraints, edge_dir::EdgeDir,
-    edge_dirs::EdgeDirs, edge_minlens::EdgeMinlens, fixed_size::FixedSize, margin::Margin,
-    margins::Margins, node_heights::NodeHeights, node_margin::NodeMargin, node_widths::NodeWidths,
-    pack_mode::PackMode, pack_mode_flag::PackModeFlag, splines::Splines,
+    arrow_primitive::ArrowPrimitive, arrow_shape::ArrowShape, arrow_side::ArrowSide,
+    arrow_type::ArrowType, cluster_margin::ClusterMargin, edge_arrowheads::EdgeArrowheads,
+    edge_arrowsizes::EdgeArrowsizes, edge_arrowtails::EdgeArrowtails,
+    edge_constraints::EdgeConstraints, edge_dir::EdgeDir, edge_dirs::EdgeDirs,
+    edge_minlens::EdgeMinlens, edge_penwidths::EdgePenwidths, fixed_size::FixedSize,
+    margin::Margin, margins::Margins, node_heights::NodeHeights, node_margin::NodeMargin,
+    node_widths::NodeWidths, pack_mode::PackMode, pack_mode_flag::PackModeFlag, splines::Splines,
 };
 
+mod arrow_primitive;
+mod arrow_shape;
+mod arrow_side;
+mod arrow_type;
 mod cluster_margin;
+mod edge_arrowheads;
+mod edge_arrowsizes;
+mod edge_arrowtails;
 mod edge_constraints;
 mod edge_dir;
 mod edge_dirs;
 mod edge_minlens;
+mod edge_penwidths;
 mod fixed_size;
 mod margin;
 mod margins;
@@ -74,6 +85,45 @@ pub struct GraphvizAttrs {
     ///
     /// [`minlen`]: https://graphviz.org/docs/attrs/minlen/
     pub edge_minlens: EdgeMinlens,
+    /// The default [`arrowhead`] shape for edges, defaults to `normal`.
+    ///
+    /// [`arrowhead`]: https://graphviz.org/docs/attrs/arrowhead/
+    pub edge_arrowhead_default: ArrowType,
+    /// Each edge's [`arrowhead`] shape.
+    ///
+    /// [`arrowhead`]: https://graphviz.org/docs/attrs/arrowhead/
+    pub edge_arrowheads: EdgeArrowheads,
+    /// The default [`arrowtail`] shape for edges, defaults to `normal`.
+    ///
+    /// The arrowtail is only drawn when the edge's [`dir`] is `back` or
+    /// `both`.
+    ///
+    /// [`arrowtail`]: https://graphviz.org/docs/attrs/arrowtail/
+    /// [`dir`]: https://graphviz.org/docs/attrs/dir/
+    pub edge_arrowtail_default: ArrowType,
+    /// Each edge's [`arrowtail`] shape.
+    ///
+    /// [`arrowtail`]: https://graphviz.org/docs/attrs/arrowtail/
+    pub edge_arrowtails: EdgeArrowtails,
+    /// The default [`arrowsize`] multiplier for edges, defaults to `0.7`.
+    ///
+    /// [`arrowsize`]: https://graphviz.org/docs/attrs/arrowsize/
+    pub edge_arrowsize_default: f64,
+    /// Each edge's [`arrowsize`] multiplier.
+    ///
+    /// [`arrowsize`]: https://graphviz.org/docs/attrs/arrowsize/
+    pub edge_arrowsizes: EdgeArrowsizes,
+    /// The default [`penwidth`] for edges in points, defaults to `1.0`.
+    ///
+    /// This affects the size of arrowheads that Graphviz lays out. The drawn
+    /// line width is still controlled by the theme's `stroke_width`.
+    ///
+    /// [`penwidth`]: https://graphviz.org/docs/attrs/penwidth/
+    pub edge_penwidth_default: f64,
+    /// Each edge's [`penwidth`] in points.
+    ///
+    /// [`penwidth`]: https://graphviz.org/docs/attrs/penwidth/
+    pub edge_penwidths: EdgePenwidths,
     /// The default value for each node's [`margin`], defaults to `0.11,0.055`.
     ///
     /// May be a single float, or two floats separated by a comma.
@@ -128,6 +178,24 @@ pub struct GraphvizAttrs {
     pub fixed_size: FixedSize,
     /// How closely to pack together graph components.
     pub pack_mode: PackMode,
+    /// Maximum depth of nested nodes to render, where top level nodes have
+    /// depth `0`.
+    ///
+    /// Clusters at this depth are rendered as leaf nodes with the number of
+    /// nodes within them, and edges to those nodes are drawn to the cluster.
+    /// Edges drawn between the same nodes are merged, with the number of edges
+    /// as their label.
+    ///
+    /// Defaults to `None`, which renders all nodes.
+    pub max_depth: Option<u32>,
+    /// Whether edges that are drawn between the same nodes or clusters are
+    /// drawn as one edge.
+    ///
+    /// Only edges with the same `constraint`, `dir`, `minlen`, `arrowhead`,
+    /// `arrowtail`, `arrowsize`, `penwidth`, and theme styles are bundled. The drawn edge is
+    /// labelled with the number of edges, and its tooltip lists each edge.
+    /// Defaults to `false`.
+    pub edge_bundling: bool,
 }
 
 impl GraphvizAttrs {
@@ -220,6 +288,74 @@ impl GraphvizAttrs {
         self
     }
 
+    /// Sets the default [`arrowhead`] shape for edges.
+    ///
+    /// [`arrowhead`]: https://graphviz.org/docs/attrs/arrowhead/
+    pub fn with_edge_arrowhead_default(mut self, edge_arrowhead_default: ArrowType) -> Self {
+        self.edge_arrowhead_default = edge_arrowhead_default;
+        self
+    }
+
+    /// Sets the map of edge [`arrowhead`] shapes.
+    ///
+    /// [`arrowhead`]: https://graphviz.org/docs/attrs/arrowhead/
+    pub fn with_edge_arrowheads(mut self, edge_arrowheads: EdgeArrowheads) -> Self {
+        self.edge_arrowheads = edge_arrowheads;
+        self
+    }
+
+    /// Sets the default [`arrowtail`] shape for edges.
+    ///
+    /// The arrowtail is only drawn when the edge's [`dir`] is `back` or
+    /// `both`.
+    ///
+    /// [`arrowtail`]: https://graphviz.org/docs/attrs/arrowtail/
+    /// [`dir`]: https://graphviz.org/docs/attrs/dir/
+    pub fn with_edge_arrowtail_default(mut self, edge_arrowtail_default: ArrowType) -> Self {
+        self.edge_arrowtail_default = edge_arrowtail_default;
+        self
+    }
+
+    /// Sets the map of edge [`arrowtail`] shapes.
+    ///
+    /// [`arrowtail`]: https://graphviz.org/docs/attrs/arrowtail/
+    pub fn with_edge_arrowtails(mut self, edge_arrowtails: EdgeArrowtails) -> Self {
+        self.edge_arrowtails = edge_arrowtails;
+        self
+    }
+
+    /// Sets the default [`arrowsize`] multiplier for edges.
+    ///
+    /// [`arrowsize`]: https://graphviz.org/docs/attrs/arrowsize/
+    pub fn with_edge_arrowsize_default(mut self, edge_arrowsize_default: f64) -> Self {
+        self.edge_arrowsize_default = edge_arrowsize_default;
+        self
+    }
+
+    /// Sets the map of edge [`arrowsize`] multipliers.
+    ///
+    /// [`arrowsize`]: https://graphviz.org/docs/attrs/arrowsize/
+    pub fn with_edge_arrowsizes(mut self, edge_arrowsizes: EdgeArrowsizes) -> Self {
+        self.edge_arrowsizes = edge_arrowsizes;
+        self
+    }
+
+    /// Sets the default [`penwidth`] for edges in points.
+    ///
+    /// [`penwidth`]: https://graphviz.org/docs/attrs/penwidth/
+    pub fn with_edge_penwidth_default(mut self, edge_penwidth_default: f64) -> Self {
+        self.edge_penwidth_default = edge_penwidth_default;
+        self
+    }
+
+    /// Sets the map of edge [`penwidth`]s in points.
+    ///
+    /// [`penwidth`]: https://graphviz.org/docs/attrs/penwidth/
+    pub fn with_edge_penwidths(mut self, edge_penwidths: EdgePenwidths) -> Self {
+        self.edge_penwidths = edge_penwidths;
+        self
+    }
+
     /// Sets the default value for each node's [`margin`], defaults to
     /// `0.11,0.055`.
     ///
@@ -305,6 +441,20 @@ impl GraphvizAttrs {
         self
     }
 
+    /// Sets the maximum depth of nested nodes to render, where top level
+    /// nodes have depth `0`.
+    pub fn with_max_depth(mut self, max_depth: Option<u32>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets whether edges that are drawn between the same nodes or clusters
+    /// are drawn as one edge.
+    pub fn with_edge_bundling(mut self, edge_bundling: bool) -> Self {
+        self.edge_bundling = edge_bundling;
+        self
+    }
+
     /// Returns the minimum space between two adjacent nodes in the same rank,
     /// in inches. Also controls the spacing between multiple edges between
     /// the same pair of nodes.
@@ -377,6 +527,62 @@ impl GraphvizAttrs {
         &self.edge_minlens
     }
 
+    /// Returns the default [`arrowhead`] shape for edges.
+    ///
+    /// [`arrowhead`]: https://graphviz.org/docs/attrs/arrowhead/
+    pub fn edge_arrowhead_default(&self) -> &ArrowType {
+        &self.edge_arrowhead_default
+    }
+
+    /// Returns the map of edge [`arrowhead`] shapes.
+    ///
+    /// [`arrowhead`]: https://graphviz.org/docs/attrs/arrowhead/
+    pub fn edge_arrowheads(&self) -> &EdgeArrowheads {
+        &self.edge_arrowheads
+    }
+
+    /// Returns the default [`arrowtail`] shape for edges.
+    ///
+    /// [`arrowtail`]: https://graphviz.org/docs/attrs/arrowtail/
+    pub fn edge_arrowtail_default(&self) -> &ArrowType {
+        &self.edge_arrowtail_default
+    }
+
+    /// Returns the map of edge [`arrowtail`] shapes.
+    ///
+    /// [`arrowtail`]: https://graphviz.org/docs/attrs/arrowtail/
+    pub fn edge_arrowtails(&self) -> &EdgeArrowtails {
+        &self.edge_arrowtails
+    }
+
+    /// Returns the default [`arrowsize`] multiplier for edges.
+    ///
+    /// [`arrowsize`]: https://graphviz.org/docs/attrs/arrowsize/
+    pub fn edge_arrowsize_default(&self) -> f64 {
+        self.edge_arrowsize_default
+    }
+
+    /// Returns the map of edge [`arrowsize`] multipliers.
+    ///
+    /// [`arrowsize`]: https://graphviz.org/docs/attrs/arrowsize/
+    pub fn edge_arrowsizes(&self) -> &EdgeArrowsizes {
+        &self.edge_arrowsizes
+    }
+
+    /// Returns the default [`penwidth`] for edges in points.
+    ///
+    /// [`penwidth`]: https://graphviz.org/docs/attrs/penwidth/
+    pub fn edge_penwidth_default(&self) -> f64 {
+        self.edge_penwidth_default
+    }
+
+    /// Returns the map of edge [`penwidth`]s in points.
+    ///
+    /// [`penwidth`]: https://graphviz.org/docs/attrs/penwidth/
+    pub fn edge_penwidths(&self) -> &EdgePenwidths {
+        &self.edge_penwidths
+    }
+
     /// Returns the default value for each node's [`margin`].
     ///
     /// May be a single float, or two floats separated by a comma.
@@ -452,6 +658,17 @@ impl GraphvizAttrs {
     pub fn fixed_size(&self) -> FixedSize {
         self.fixed_size
     }
+
+    /// Returns the maximum depth of nested nodes to render, if any.
+    pub fn max_depth(&self) -> Option<u32> {
+        self.max_depth
+    }
+
+    /// Returns whether edges that are drawn between the same nodes or
+    /// clusters are drawn as one edge.
+    pub fn edge_bundling(&self) -> bool {
+        self.edge_bundling
+    }
 }
 
 impl Default for GraphvizAttrs {
@@ -466,6 +683,14 @@ impl Default for GraphvizAttrs {
             edge_dirs: EdgeDirs::default(),
             edge_minlen_default: 2,
             edge_minlens: EdgeMinlens::default(),
+            edge_arrowhead_default: ArrowType::default(),
+            edge_arrowheads: EdgeArrowheads::default(),
+            edge_arrowtail_default: ArrowType::default(),
+            edge_arrowtails: EdgeArrowtails::default(),
+            edge_arrowsize_default: 0.7,
+            edge_arrowsizes: EdgeArrowsizes::default(),
+            edge_penwidth_default: 1.0,
+            edge_penwidths: EdgePenwidths::default(),
             margin_cluster_default: ClusterMargin::default(),
             margin_node_default: NodeMargin::default(),
             margins: Margins::default(),
@@ -475,6 +700,8 @@ impl Default for GraphvizAttrs {
             node_heights: NodeHeights::default(),
             fixed_size: FixedSize::default(),
             pack_mode: PackMode::default(),
+            max_depth: None,
+            edge_bundling: false,
         }
     }
 }
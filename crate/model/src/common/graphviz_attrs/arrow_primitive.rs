@@ -0,0 +1,109 @@
+use std::{fmt, fmt::Display, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Primitive shape of an arrow. See [`arrowType`].
+///
+/// [`arrowType`]: https://graphviz.org/docs/attr-types/arrowType/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ArrowPrimitive {
+    /// Rectangle, `box`.
+    Box,
+    /// Crow's foot, `crow`.
+    Crow,
+    /// Curve that bends away from the node, `curve`.
+    Curve,
+    /// Curve that bends towards the node, `icurve`.
+    ICurve,
+    /// Diamond, `diamond`.
+    Diamond,
+    /// Circle, `dot`.
+    Dot,
+    /// Triangle pointing away from the node, `inv`.
+    Inv,
+    /// No arrow, `none`.
+    None,
+    /// Triangle pointing towards the node, `normal`.
+    #[default]
+    Normal,
+    /// Line perpendicular to the edge, `tee`.
+    Tee,
+    /// Chevron, `vee`.
+    Vee,
+}
+
+impl ArrowPrimitive {
+    /// All primitive shapes.
+    pub const ALL: [Self; 11] = [
+        Self::Box,
+        Self::Crow,
+        Self::Curve,
+        Self::ICurve,
+        Self::Diamond,
+        Self::Dot,
+        Self::Inv,
+        Self::None,
+        Self::Normal,
+        Self::Tee,
+        Self::Vee,
+    ];
+
+    /// Returns the Graphviz name of this shape.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Box => "box",
+            Self::Crow => "crow",
+            Self::Curve => "curve",
+            Self::ICurve => "icurve",
+            Self::Diamond => "diamond",
+            Self::Dot => "dot",
+            Self::Inv => "inv",
+            Self::None => "none",
+            Self::Normal => "normal",
+            Self::Tee => "tee",
+            Self::Vee => "vee",
+        }
+    }
+}
+
+impl FromStr for ArrowPrimitive {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|arrow_primitive| arrow_primitive.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Unable to map `{s}` to an `ArrowPrimitive`. Valid strings are: \
+                    \"box\", \"crow\", \"curve\", \"icurve\", \"diamond\", \"dot\", \"inv\", \
+                    \"none\", \"normal\", \"tee\", \"vee\"."
+                )
+            })
+    }
+}
+
+impl Display for ArrowPrimitive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl<'de> Deserialize<'de> for ArrowPrimitive {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        FromStr::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ArrowPrimitive {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
@@ -0,0 +1,153 @@
+use std::{fmt, fmt::Display, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::common::graphviz_attrs::{ArrowPrimitive, ArrowSide};
+
+/// A primitive arrow shape with its modifiers, e.g. `odiamond`, `ltee`.
+///
+/// Modifiers are written before the primitive shape:
+///
+/// * `o`: Draws the shape without filling it, e.g. `odiamond`.
+/// * `l` / `r`: Only draws the left or right half of the shape, e.g. `ltee`.
+///
+/// See [`arrowType`].
+///
+/// [`arrowType`]: https://graphviz.org/docs/attr-types/arrowType/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ArrowShape {
+    /// Whether the shape is drawn without filling it.
+    pub open: bool,
+    /// Which half of the shape to draw, `None` draws both halves.
+    pub side: Option<ArrowSide>,
+    /// The primitive shape.
+    pub primitive: ArrowPrimitive,
+}
+
+impl ArrowShape {
+    /// Returns a new `ArrowShape` with no modifiers.
+    pub fn new(primitive: ArrowPrimitive) -> Self {
+        Self {
+            open: false,
+            side: None,
+            primitive,
+        }
+    }
+
+    /// Sets whether the shape is drawn without filling it.
+    pub fn with_open(mut self, open: bool) -> Self {
+        self.open = open;
+        self
+    }
+
+    /// Sets which half of the shape to draw.
+    pub fn with_side(mut self, side: Option<ArrowSide>) -> Self {
+        self.side = side;
+        self
+    }
+
+    /// Returns whether the shape is drawn without filling it.
+    pub fn open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns which half of the shape to draw.
+    pub fn side(&self) -> Option<ArrowSide> {
+        self.side
+    }
+
+    /// Returns the primitive shape.
+    pub fn primitive(&self) -> ArrowPrimitive {
+        self.primitive
+    }
+
+    /// Parses an `ArrowShape` from the start of `s`, returning the shape and
+    /// the remaining unparsed string.
+    pub(crate) fn parse_prefix(s: &str) -> Result<(Self, &str), String> {
+        let (open, rest) = match s.strip_prefix('o') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (side, rest) = if let Some(rest) = rest.strip_prefix('l') {
+            (Some(ArrowSide::Left), rest)
+        } else if let Some(rest) = rest.strip_prefix('r') {
+            (Some(ArrowSide::Right), rest)
+        } else {
+            (None, rest)
+        };
+
+        // No primitive shape name begins with `o`, `l`, or `r`, so the modifiers
+        // are not ambiguous.
+        let primitive = ArrowPrimitive::ALL
+            .into_iter()
+            .find(|arrow_primitive| rest.starts_with(arrow_primitive.as_str()))
+            .ok_or_else(|| {
+                format!(
+                    "Unable to parse an arrow shape from `{s}`. \
+                    Expected an optional `o`, an optional `l` or `r`, followed by one of: \
+                    \"box\", \"crow\", \"curve\", \"icurve\", \"diamond\", \"dot\", \"inv\", \
+                    \"none\", \"normal\", \"tee\", \"vee\"."
+                )
+            })?;
+        let rest = &rest[primitive.as_str().len()..];
+
+        let arrow_shape = Self {
+            open,
+            side,
+            primitive,
+        };
+        Ok((arrow_shape, rest))
+    }
+}
+
+impl From<ArrowPrimitive> for ArrowShape {
+    fn from(primitive: ArrowPrimitive) -> Self {
+        Self::new(primitive)
+    }
+}
+
+impl FromStr for ArrowShape {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (arrow_shape, rest) = Self::parse_prefix(s)?;
+        if rest.is_empty() {
+            Ok(arrow_shape)
+        } else {
+            Err(format!(
+                "Unable to parse an arrow shape from `{s}`: unexpected `{rest}` after `{arrow_shape}`."
+            ))
+        }
+    }
+}
+
+impl Display for ArrowShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.open {
+            "o".fmt(f)?;
+        }
+        if let Some(side) = self.side {
+            side.fmt(f)?;
+        }
+        self.primitive.fmt(f)
+    }
+}
+
+impl<'de> Deserialize<'de> for ArrowShape {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        FromStr::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ArrowShape {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
@@ -0,0 +1,25 @@
+use std::{fmt, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+/// Which half of an arrow shape to draw.
+///
+/// The side is relative to the edge, looking from the tail of the edge
+/// towards the head.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArrowSide {
+    /// Only the left half of the shape is drawn, `l`.
+    Left,
+    /// Only the right half of the shape is drawn, `r`.
+    Right,
+}
+
+impl Display for ArrowSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrowSide::Left => "l".fmt(f),
+            ArrowSide::Right => "r".fmt(f),
+        }
+    }
+}
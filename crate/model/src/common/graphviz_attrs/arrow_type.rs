@@ -0,0 +1,111 @@
+use std::{fmt, fmt::Display, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::common::graphviz_attrs::{ArrowPrimitive, ArrowShape};
+
+/// Shape of an arrowhead or arrowtail, made of one to four [`ArrowShape`]s.
+///
+/// Shapes are drawn starting from the node, e.g.:
+///
+/// * `normal`: Filled triangle.
+/// * `odiamond`: Unfilled diamond, e.g. for aggregation.
+/// * `diamond`: Filled diamond, e.g. for composition.
+/// * `crowodot`: Crow's foot and unfilled circle, i.e. "zero or many".
+/// * `teetee`: Two perpendicular lines, i.e. "one and only one".
+///
+/// See [`arrowType`].
+///
+/// [`arrowType`]: https://graphviz.org/docs/attr-types/arrowType/
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ArrowType(Vec<ArrowShape>);
+
+impl ArrowType {
+    /// Maximum number of shapes that Graphviz supports in an arrow.
+    pub const SHAPES_MAX: usize = 4;
+
+    /// Returns a new `ArrowType` from the given shapes.
+    ///
+    /// Returns an error if there are no shapes, or more than
+    /// [`Self::SHAPES_MAX`] shapes.
+    pub fn new(arrow_shapes: Vec<ArrowShape>) -> Result<Self, String> {
+        match arrow_shapes.len() {
+            0 => Err(String::from("`ArrowType` must contain at least one shape.")),
+            1..=Self::SHAPES_MAX => Ok(Self(arrow_shapes)),
+            len => Err(format!(
+                "`ArrowType` supports at most {max} shapes, but {len} were given.",
+                max = Self::SHAPES_MAX
+            )),
+        }
+    }
+
+    /// Returns the shapes in this arrow, starting from the node.
+    pub fn shapes(&self) -> &[ArrowShape] {
+        &self.0
+    }
+
+    /// Returns the underlying shapes.
+    pub fn into_inner(self) -> Vec<ArrowShape> {
+        self.0
+    }
+}
+
+impl Default for ArrowType {
+    fn default() -> Self {
+        Self(vec![ArrowShape::new(ArrowPrimitive::Normal)])
+    }
+}
+
+impl From<ArrowShape> for ArrowType {
+    fn from(arrow_shape: ArrowShape) -> Self {
+        Self(vec![arrow_shape])
+    }
+}
+
+impl From<ArrowPrimitive> for ArrowType {
+    fn from(arrow_primitive: ArrowPrimitive) -> Self {
+        Self(vec![ArrowShape::new(arrow_primitive)])
+    }
+}
+
+impl FromStr for ArrowType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut arrow_shapes = Vec::new();
+        let mut rest = s;
+        while !rest.is_empty() {
+            let (arrow_shape, rest_next) = ArrowShape::parse_prefix(rest)
+                .map_err(|e| format!("Failed to parse `ArrowType` from `{s}`. {e}"))?;
+            arrow_shapes.push(arrow_shape);
+            rest = rest_next;
+        }
+
+        Self::new(arrow_shapes).map_err(|e| format!("Failed to parse `ArrowType` from `{s}`. {e}"))
+    }
+}
+
+impl Display for ArrowType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|arrow_shape| arrow_shape.fmt(f))
+    }
+}
+
+impl<'de> Deserialize<'de> for ArrowType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        FromStr::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ArrowType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
@@ -0,0 +1,59 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::common::{graphviz_attrs::ArrowType, EdgeId};
+
+/// GraphViz edge arrowhead. `IndexMap<EdgeId, ArrowType>` newtype.
+///
+/// This is only used for GraphViz dot graphs, which sets the [`arrowhead`]
+/// attribute for the edge.
+///
+/// [`arrowhead`]: https://graphviz.org/docs/attrs/arrowhead/
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EdgeArrowheads(IndexMap<EdgeId, ArrowType>);
+
+impl EdgeArrowheads {
+    /// Returns a new `EdgeArrowheads` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new `EdgeArrowheads` map with the given preallocated
+    /// capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<EdgeId, ArrowType> {
+        self.0
+    }
+}
+
+impl Deref for EdgeArrowheads {
+    type Target = IndexMap<EdgeId, ArrowType>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for EdgeArrowheads {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<IndexMap<EdgeId, ArrowType>> for EdgeArrowheads {
+    fn from(inner: IndexMap<EdgeId, ArrowType>) -> Self {
+        Self(inner)
+    }
+}
+
+impl FromIterator<(EdgeId, ArrowType)> for EdgeArrowheads {
+    fn from_iter<I: IntoIterator<Item = (EdgeId, ArrowType)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
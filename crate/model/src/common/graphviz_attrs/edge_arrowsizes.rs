@@ -0,0 +1,59 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::common::EdgeId;
+
+/// GraphViz edge arrowsize. `IndexMap<EdgeId, f64>` newtype.
+///
+/// This is only used for GraphViz dot graphs, which sets the [`arrowsize`]
+/// attribute for the edge.
+///
+/// [`arrowsize`]: https://graphviz.org/docs/attrs/arrowsize/
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct EdgeArrowsizes(IndexMap<EdgeId, f64>);
+
+impl EdgeArrowsizes {
+    /// Returns a new `EdgeArrowsizes` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new `EdgeArrowsizes` map with the given preallocated
+    /// capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<EdgeId, f64> {
+        self.0
+    }
+}
+
+impl Deref for EdgeArrowsizes {
+    type Target = IndexMap<EdgeId, f64>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for EdgeArrowsizes {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<IndexMap<EdgeId, f64>> for EdgeArrowsizes {
+    fn from(inner: IndexMap<EdgeId, f64>) -> Self {
+        Self(inner)
+    }
+}
+
+impl FromIterator<(EdgeId, f64)> for EdgeArrowsizes {
+    fn from_iter<I: IntoIterator<Item = (EdgeId, f64)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
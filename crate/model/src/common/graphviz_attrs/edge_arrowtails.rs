@@ -0,0 +1,59 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::common::{graphviz_attrs::ArrowType, EdgeId};
+
+/// GraphViz edge arrowtail. `IndexMap<EdgeId, ArrowType>` newtype.
+///
+/// This is only used for GraphViz dot graphs, which sets the [`arrowtail`]
+/// attribute for the edge.
+///
+/// [`arrowtail`]: https://graphviz.org/docs/attrs/arrowtail/
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EdgeArrowtails(IndexMap<EdgeId, ArrowType>);
+
+impl EdgeArrowtails {
+    /// Returns a new `EdgeArrowtails` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new `EdgeArrowtails` map with the given preallocated
+    /// capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<EdgeId, ArrowType> {
+        self.0
+    }
+}
+
+impl Deref for EdgeArrowtails {
+    type Target = IndexMap<EdgeId, ArrowType>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for EdgeArrowtails {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<IndexMap<EdgeId, ArrowType>> for EdgeArrowtails {
+    fn from(inner: IndexMap<EdgeId, ArrowType>) -> Self {
+        Self(inner)
+    }
+}
+
+impl FromIterator<(EdgeId, ArrowType)> for EdgeArrowtails {
+    fn from_iter<I: IntoIterator<Item = (EdgeId, ArrowType)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
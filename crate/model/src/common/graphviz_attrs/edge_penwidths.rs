@@ -0,0 +1,59 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::common::EdgeId;
+
+/// GraphViz edge penwidth. `IndexMap<EdgeId, f64>` newtype.
+///
+/// This is only used for GraphViz dot graphs, which sets the [`penwidth`]
+/// attribute for the edge.
+///
+/// [`penwidth`]: https://graphviz.org/docs/attrs/penwidth/
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct EdgePenwidths(IndexMap<EdgeId, f64>);
+
+impl EdgePenwidths {
+    /// Returns a new `EdgePenwidths` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new `EdgePenwidths` map with the given preallocated
+    /// capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<EdgeId, f64> {
+        self.0
+    }
+}
+
+impl Deref for EdgePenwidths {
+    type Target = IndexMap<EdgeId, f64>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for EdgePenwidths {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<IndexMap<EdgeId, f64>> for EdgePenwidths {
+    fn from(inner: IndexMap<EdgeId, f64>) -> Self {
+        Self(inner)
+    }
+}
+
+impl FromIterator<(EdgeId, f64)> for EdgePenwidths {
+    fn from_iter<I: IntoIterator<Item = (EdgeId, f64)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
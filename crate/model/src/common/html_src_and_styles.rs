@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use crate::theme::ThemeWarnings;
+
+/// HTML source and CSS styles for a diagram rendered without Graphviz.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HtmlSrcAndStyles {
+    /// HTML for the diagram's nodes and edges.
+    pub html_src: String,
+    /// CSS styles to put into `<styles>..</styles>`.
+    pub styles: String,
+    /// Warnings detected while computing CSS utility classes.
+    pub theme_warnings: ThemeWarnings,
+}
+
+impl HtmlSrcAndStyles {
+    /// Returns a new `HtmlSrcAndStyles` object.
+    pub fn new(html_src: String, styles: String, theme_warnings: ThemeWarnings) -> Self {
+        Self {
+            html_src,
+            styles,
+            theme_warnings,
+        }
+    }
+
+    /// Returns the HTML for the diagram's nodes and edges.
+    pub fn html_src(&self) -> &str {
+        &self.html_src
+    }
+
+    /// Returns the CSS styles to put into `<styles>..</styles>`.
+    pub fn styles(&self) -> &str {
+        &self.styles
+    }
+
+    /// Returns the warnings detected while computing CSS utility classes.
+    pub fn theme_warnings(&self) -> &ThemeWarnings {
+        &self.theme_warnings
+    }
+}
@@ -32,3 +32,32 @@ impl From<AnyId> for NodeId {
         Self(id)
     }
 }
+
+impl NodeId {
+    /// Returns the node ID without its compass point, e.g. `"a"` for `"a:n"`.
+    ///
+    /// Edge ends may specify a compass point to attach to a side of the node.
+    pub fn node_id_plain(&self) -> &str {
+        self.node_id_and_port().0
+    }
+
+    /// Splits the node ID from its compass point, e.g. `"a:n"` into `("a",
+    /// Some("n"))`.
+    pub fn node_id_and_port(&self) -> (&str, Option<&str>) {
+        match self.split_once(':') {
+            Some((node_id_plain, compass_point)) => (node_id_plain, Some(compass_point)),
+            None => (self.as_str(), None),
+        }
+    }
+
+    /// Returns this node ID with the given port, e.g. `"a:n"` for `"a"` and
+    /// `"n"`.
+    ///
+    /// Returns `None` if the port is empty or contains characters other than
+    /// letters, numbers, and underscores.
+    pub fn with_port(&self, port: &str) -> Option<NodeId> {
+        let port_valid =
+            !port.is_empty() && port.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        port_valid.then(|| NodeId(Cow::Owned(format!("{}:{port}", self.node_id_plain()))))
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::theme::ThemeWarnings;
+
+/// SVG source and CSS styles for a diagram laid out without Graphviz.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SvgSrcAndStyles {
+    /// SVG for the diagram's nodes and edges.
+    pub svg_src: String,
+    /// CSS styles to put into `<styles>..</styles>`.
+    pub styles: String,
+    /// Warnings detected while computing CSS utility classes.
+    pub theme_warnings: ThemeWarnings,
+}
+
+impl SvgSrcAndStyles {
+    /// Returns a new `SvgSrcAndStyles` object.
+    pub fn new(svg_src: String, styles: String, theme_warnings: ThemeWarnings) -> Self {
+        Self {
+            svg_src,
+            styles,
+            theme_warnings,
+        }
+    }
+
+    /// Returns the SVG for the diagram's nodes and edges.
+    pub fn svg_src(&self) -> &str {
+        &self.svg_src
+    }
+
+    /// Returns the CSS styles to put into `<styles>..</styles>`.
+    pub fn styles(&self) -> &str {
+        &self.styles
+    }
+
+    /// Returns the warnings detected while computing CSS utility classes.
+    pub fn theme_warnings(&self) -> &ThemeWarnings {
+        &self.theme_warnings
+    }
+
+    /// Returns the SVG with the styles embedded in a `<style>` element, so
+    /// that it can be written to a file or served on its own.
+    pub fn svg_with_styles(&self) -> String {
+        let svg_tag_end = self.svg_src.find("<svg").and_then(|svg_tag_start| {
+            self.svg_src[svg_tag_start..]
+                .find('>')
+                .map(|svg_tag_len| svg_tag_start + svg_tag_len + 1)
+        });
+        let Some(svg_tag_end) = svg_tag_end else {
+            return self.svg_src.clone();
+        };
+
+        let (svg_tag, svg_body) = self.svg_src.split_at(svg_tag_end);
+        let styles = &self.styles;
+        format!("{svg_tag}\n<style>\n{styles}\n</style>{svg_body}")
+    }
+}
@@ -0,0 +1,24 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+/// Unique identifier for a view, `Cow<'static, str>` newtype.
+///
+/// Must begin with a letter or underscore, and contain only letters, numbers,
+/// and underscores.
+///
+/// # Examples
+///
+/// The following are all examples of valid `ViewId`s:
+///
+/// ```rust
+/// # use dot_ix::model::common::ViewId;
+/// #
+/// let _snake = ViewId::new("snake_case").unwrap();
+/// let _camel = ViewId::new("camelCase").unwrap();
+/// let _pascal = ViewId::new("PascalCase").unwrap();
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ViewId(Cow<'static, str>);
+
+id_newtype::id_newtype!(ViewId, ViewIdInvalidFmt);
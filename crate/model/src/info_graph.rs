@@ -2,20 +2,30 @@ use std::collections::{HashMap, VecDeque};
 
 pub use indexmap::IndexMap;
 
+use indexmap::IndexSet;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
     common::{
         EdgeDescs, EdgeId, EdgeTagsSet, Edges, GraphvizAttrs, Images, NodeDescs, NodeEmojis,
-        NodeHierarchy, NodeId, NodeImages, NodeNames, NodeTagsSet, TagItems, TagNames, TagStyles,
+        NodeHierarchy, NodeId, NodeImages, NodeNames, NodeTagsSet, TagId, TagItems, TagNames,
+        TagStyles,
     },
     theme::Theme,
 };
 
-pub use self::{graph_dir::GraphDir, graph_style::GraphStyle};
+pub use self::{
+    graph_dir::GraphDir, graph_style::GraphStyle, view::View, view_theme::ViewTheme, views::Views,
+};
 
+mod depth_limited_info_graph;
 mod graph_dir;
 mod graph_style;
+mod view;
+mod view_info_graph;
+mod view_theme;
+mod views;
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
@@ -54,6 +64,8 @@ pub struct InfoGraph {
     pub css: String,
     /// Additional elements to add in the SVG.
     pub svg_extra: String,
+    /// Named views of the graph.
+    pub views: Views,
 }
 
 impl InfoGraph {
@@ -159,6 +171,12 @@ impl InfoGraph {
         self
     }
 
+    /// Sets the named views of the graph.
+    pub fn with_views(mut self, views: Views) -> Self {
+        self.views = views;
+        self
+    }
+
     /// Returns the style of graph to render.
     pub fn graph_style(&self) -> GraphStyle {
         self.graph_style
@@ -217,6 +235,43 @@ impl InfoGraph {
         node_id_to_hierarchy
     }
 
+    /// Returns a map of each nested node ID to its parent node ID.
+    ///
+    /// Top level nodes are not present in the map.
+    ///
+    /// For example, for the following hierarchy:
+    ///
+    /// ```yaml
+    /// a:
+    ///   b:
+    ///     c: {}
+    /// d: {}
+    /// ```
+    ///
+    /// This returns:
+    ///
+    /// ```yaml
+    /// b: a
+    /// c: b
+    /// ```
+    pub fn node_parents(&self) -> HashMap<&NodeId, &NodeId> {
+        let mut node_id_to_parent =
+            HashMap::<&NodeId, &NodeId>::with_capacity(self.node_names.len());
+        let mut hierarchy_queue = VecDeque::new();
+        hierarchy_queue.push_back(self.hierarchy());
+
+        while let Some(hierarchy) = hierarchy_queue.pop_front() {
+            hierarchy.iter().for_each(|(node_id, node_hierarchy)| {
+                node_hierarchy.keys().for_each(|child_node_id| {
+                    node_id_to_parent.insert(child_node_id, node_id);
+                });
+                hierarchy_queue.push_back(node_hierarchy);
+            });
+        }
+
+        node_id_to_parent
+    }
+
     /// Returns the map of node names.
     pub fn node_names(&self) -> &NodeNames {
         &self.node_names
@@ -289,6 +344,40 @@ impl InfoGraph {
         &self.svg_extra
     }
 
+    /// Returns the named views of the graph.
+    pub fn views(&self) -> &Views {
+        &self.views
+    }
+
+    /// Returns the `InfoGraph` as presented by the named view.
+    ///
+    /// The returned graph contains only the nodes, edges, and tags shown in
+    /// the view, with collapsed clusters as plain nodes, and the view's
+    /// direction and theme applied. Its `views` are kept so that other views
+    /// can be switched to.
+    ///
+    /// Returns `None` if there is no view with the given ID.
+    pub fn view(&self, view_id: &str) -> Option<InfoGraph> {
+        self.views
+            .get(view_id)
+            .map(|view| view_info_graph::view_info_graph(self, view))
+    }
+
+    /// Returns the `InfoGraph` with nodes nested deeper than `max_depth`
+    /// hidden, where top level nodes have depth `0`.
+    ///
+    /// Clusters at `max_depth` become leaf nodes whose description notes the
+    /// number of nodes within them, and edges to hidden nodes are drawn to the
+    /// cluster instead. Edges between the same nodes are merged into one edge,
+    /// whose description notes the number of edges merged.
+    ///
+    /// This is used to render [`GraphvizAttrs::max_depth`].
+    ///
+    /// [`GraphvizAttrs::max_depth`]: crate::common::GraphvizAttrs::max_depth
+    pub fn depth_limited(&self, max_depth: u32) -> InfoGraph {
+        depth_limited_info_graph::depth_limited_info_graph(self, max_depth)
+    }
+
     /// Returns the tags associated with each node.
     pub fn node_tags_set(&self) -> NodeTagsSet {
         let hierarchy_flat = self.hierarchy_flat();
@@ -336,4 +425,29 @@ impl InfoGraph {
             },
         )
     }
+
+    /// Returns the tags associated with each node or edge, keyed by the node
+    /// or edge ID.
+    ///
+    /// Unlike [`node_tags_set`] and [`edge_tags_set`], this borrows the IDs
+    /// from this `InfoGraph`, so it is cheap to compute once and look up for
+    /// every element when rendering.
+    ///
+    /// [`node_tags_set`]: Self::node_tags_set
+    /// [`edge_tags_set`]: Self::edge_tags_set
+    pub fn el_tag_ids(&self) -> HashMap<&str, IndexSet<&TagId>> {
+        let mut el_tag_ids = HashMap::<&str, IndexSet<&TagId>>::with_capacity(
+            self.node_names.len() + self.edges.len(),
+        );
+        self.tag_items.iter().for_each(|(tag_id, any_ids)| {
+            any_ids.iter().for_each(|any_id| {
+                el_tag_ids
+                    .entry(any_id.as_str())
+                    .or_default()
+                    .insert(tag_id);
+            });
+        });
+
+        el_tag_ids
+    }
 }
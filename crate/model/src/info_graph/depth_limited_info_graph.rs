@@ -0,0 +1,245 @@
+use std::collections::{HashMap, HashSet};
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+    common::{EdgeId, NodeHierarchy, NodeId, TagId, TagItems},
+    info_graph::{view_info_graph, InfoGraph, View},
+};
+
+/// Returns the `InfoGraph` with nodes nested deeper than `max_depth` hidden.
+///
+/// Clusters at `max_depth` are collapsed, and their descriptions note the
+/// number of nodes within them. Edges between the same nodes are merged into
+/// the first edge, whose description notes the number of edges merged.
+///
+/// Tags of hidden nodes and edges apply to the collapsed cluster they are
+/// within, and tags of merged edges apply to the edge that is kept.
+pub(crate) fn depth_limited_info_graph(info_graph: &InfoGraph, max_depth: u32) -> InfoGraph {
+    let mut clusters_collapsed = Vec::new();
+    clusters_at_depth_collect(
+        info_graph.hierarchy(),
+        0,
+        max_depth,
+        &mut clusters_collapsed,
+    );
+    let node_counts = clusters_collapsed
+        .iter()
+        .map(|(node_id, node_hierarchy)| ((*node_id).clone(), node_count(node_hierarchy)))
+        .collect::<Vec<(NodeId, usize)>>();
+
+    let view = View::new().with_clusters_collapsed(
+        node_counts
+            .iter()
+            .map(|(node_id, _)| node_id.clone())
+            .collect(),
+    );
+    let mut info_graph_depth_limited = view_info_graph::view_info_graph(info_graph, &view);
+
+    node_counts.into_iter().for_each(|(node_id, node_count)| {
+        let node_count_desc = match node_count {
+            1 => String::from("1 node"),
+            _ => format!("{node_count} nodes"),
+        };
+        let node_desc = match info_graph_depth_limited.node_descs.get(&node_id) {
+            Some(node_desc) => format!("{node_desc}\n{node_count_desc}"),
+            None => node_count_desc,
+        };
+        info_graph_depth_limited
+            .node_descs
+            .insert(node_id, node_desc);
+    });
+
+    let tag_ids_by_any_id = tag_ids_by_any_id(info_graph.tag_items());
+    tags_lift(
+        info_graph,
+        &mut info_graph_depth_limited,
+        &clusters_collapsed,
+        &tag_ids_by_any_id,
+    );
+    edges_merge(&mut info_graph_depth_limited, &tag_ids_by_any_id);
+
+    info_graph_depth_limited
+}
+
+/// Collects the clusters at `max_depth`, along with their hierarchy.
+fn clusters_at_depth_collect<'graph>(
+    hierarchy: &'graph NodeHierarchy,
+    depth: u32,
+    max_depth: u32,
+    clusters: &mut Vec<(&'graph NodeId, &'graph NodeHierarchy)>,
+) {
+    hierarchy
+        .iter()
+        .filter(|(_, child_hierarchy)| !child_hierarchy.is_empty())
+        .for_each(|(node_id, child_hierarchy)| {
+            if depth >= max_depth {
+                clusters.push((node_id, child_hierarchy));
+            } else {
+                clusters_at_depth_collect(child_hierarchy, depth + 1, max_depth, clusters);
+            }
+        });
+}
+
+/// Returns the number of nodes within the hierarchy, including nested nodes.
+fn node_count(hierarchy: &NodeHierarchy) -> usize {
+    hierarchy
+        .values()
+        .map(|child_hierarchy| 1 + node_count(child_hierarchy))
+        .sum()
+}
+
+/// Returns the tags of each tagged node and edge.
+fn tag_ids_by_any_id(tag_items: &TagItems) -> HashMap<&str, Vec<&TagId>> {
+    tag_items.iter().fold(
+        HashMap::<&str, Vec<&TagId>>::new(),
+        |mut tag_ids_by_any_id, (tag_id, any_ids)| {
+            any_ids.iter().for_each(|any_id| {
+                tag_ids_by_any_id
+                    .entry(any_id.as_str())
+                    .or_default()
+                    .push(tag_id);
+            });
+            tag_ids_by_any_id
+        },
+    )
+}
+
+/// Adds each collapsed cluster to the tags of the nodes and edges hidden
+/// within it.
+fn tags_lift(
+    info_graph: &InfoGraph,
+    info_graph_depth_limited: &mut InfoGraph,
+    clusters_collapsed: &[(&NodeId, &NodeHierarchy)],
+    tag_ids_by_any_id: &HashMap<&str, Vec<&TagId>>,
+) {
+    let mut node_id_to_cluster = IndexMap::<&str, &NodeId>::new();
+    clusters_collapsed
+        .iter()
+        .for_each(|(cluster_node_id, node_hierarchy)| {
+            descendants_collect(node_hierarchy, cluster_node_id, &mut node_id_to_cluster);
+        });
+
+    // Edges are only hidden when both ends are within the same collapsed
+    // cluster.
+    let edges_hidden = info_graph
+        .edges()
+        .iter()
+        .filter(|(edge_id, _)| !info_graph_depth_limited.edges.contains_key(*edge_id))
+        .filter_map(|(edge_id, [src, target])| {
+            [src, target]
+                .into_iter()
+                .find_map(|node_id| node_id_to_cluster.get(node_id.node_id_plain()))
+                .map(|cluster_node_id| (edge_id.as_str(), *cluster_node_id))
+        })
+        .collect::<Vec<(&str, &NodeId)>>();
+
+    let clusters_by_tag = node_id_to_cluster
+        .iter()
+        .map(|(node_id, cluster_node_id)| (*node_id, *cluster_node_id))
+        .chain(edges_hidden)
+        .fold(
+            IndexMap::<&TagId, IndexSet<&NodeId>>::new(),
+            |mut clusters_by_tag, (any_id, cluster_node_id)| {
+                tag_ids_by_any_id
+                    .get(any_id)
+                    .into_iter()
+                    .flatten()
+                    .for_each(|tag_id| {
+                        clusters_by_tag
+                            .entry(*tag_id)
+                            .or_default()
+                            .insert(cluster_node_id);
+                    });
+                clusters_by_tag
+            },
+        );
+
+    clusters_by_tag
+        .into_iter()
+        .for_each(|(tag_id, cluster_node_ids)| {
+            let Some(any_ids) = info_graph_depth_limited.tag_items.get_mut(tag_id) else {
+                return;
+            };
+            let any_ids_existing = any_ids
+                .iter()
+                .map(|any_id| any_id.as_str().to_owned())
+                .collect::<HashSet<String>>();
+            any_ids.extend(
+                cluster_node_ids
+                    .into_iter()
+                    .filter(|cluster_node_id| !any_ids_existing.contains(cluster_node_id.as_str()))
+                    .map(|cluster_node_id| cluster_node_id.clone().into()),
+            );
+        });
+}
+
+/// Maps each node within the hierarchy to the collapsed cluster.
+fn descendants_collect<'graph>(
+    hierarchy: &'graph NodeHierarchy,
+    cluster_node_id: &'graph NodeId,
+    node_id_to_cluster: &mut IndexMap<&'graph str, &'graph NodeId>,
+) {
+    hierarchy.iter().for_each(|(node_id, child_hierarchy)| {
+        node_id_to_cluster.insert(node_id.as_str(), cluster_node_id);
+        descendants_collect(child_hierarchy, cluster_node_id, node_id_to_cluster);
+    });
+}
+
+/// Merges edges between the same nodes into the first of those edges.
+fn edges_merge(info_graph: &mut InfoGraph, tag_ids_by_any_id: &HashMap<&str, Vec<&TagId>>) {
+    let edge_ids_by_ends = info_graph.edges.iter().fold(
+        IndexMap::<[&NodeId; 2], Vec<&EdgeId>>::new(),
+        |mut edge_ids_by_ends, (edge_id, [src, target])| {
+            edge_ids_by_ends
+                .entry([src, target])
+                .or_default()
+                .push(edge_id);
+            edge_ids_by_ends
+        },
+    );
+    let edge_ids_merged = edge_ids_by_ends
+        .into_values()
+        .filter(|edge_ids| edge_ids.len() > 1)
+        .map(|edge_ids| {
+            let edge_ids = edge_ids.into_iter().cloned().collect::<Vec<EdgeId>>();
+            (edge_ids[0].clone(), edge_ids)
+        })
+        .collect::<Vec<(EdgeId, Vec<EdgeId>)>>();
+
+    edge_ids_merged
+        .into_iter()
+        .for_each(|(edge_id_kept, edge_ids)| {
+            let InfoGraph {
+                edges,
+                edge_descs,
+                tag_items,
+                ..
+            } = &mut *info_graph;
+
+            edge_ids[1..].iter().for_each(|edge_id| {
+                edges.shift_remove(edge_id);
+                edge_descs.shift_remove(edge_id);
+            });
+            edge_descs.insert(edge_id_kept.clone(), format!("{} edges", edge_ids.len()));
+
+            // Tags of the merged edges apply to the edge that is kept.
+            let tag_ids = edge_ids
+                .iter()
+                .filter_map(|edge_id| tag_ids_by_any_id.get(edge_id.as_str()))
+                .flatten()
+                .copied()
+                .collect::<IndexSet<&TagId>>();
+            tag_ids.into_iter().for_each(|tag_id| {
+                let Some(any_ids) = tag_items.get_mut(tag_id) else {
+                    return;
+                };
+                any_ids.retain(|any_id| {
+                    !edge_ids
+                        .iter()
+                        .any(|edge_id| edge_id.as_str() == any_id.as_str())
+                });
+                any_ids.push(edge_id_kept.clone().into());
+            });
+        });
+}
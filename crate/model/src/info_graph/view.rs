@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{NodeId, TagId},
+    info_graph::{GraphDir, ViewTheme},
+};
+
+/// A named way to present an `InfoGraph`, e.g. a "network" or "data flow"
+/// view.
+///
+/// Use [`InfoGraph::view`] to get the `InfoGraph` for a view.
+///
+/// # Examples
+///
+/// ```yaml
+/// views:
+///   data_flow:
+///     name: "Data Flow"
+///     tags_include: [tag_data]
+///     clusters_collapsed: [infra]
+///     direction: vertical
+///     theme:
+///       cascade: true
+///       styles:
+///         edge_defaults:
+///           stroke_style: dashed
+/// ```
+///
+/// [`InfoGraph::view`]: crate::info_graph::InfoGraph::view
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct View {
+    /// Name of the view to display, defaults to the view's ID.
+    pub name: Option<String>,
+    /// Tags whose nodes and edges are shown.
+    ///
+    /// Edges with these tags also show the nodes they connect. If this and
+    /// [`nodes_include`] are empty, all nodes are shown.
+    ///
+    /// [`nodes_include`]: Self::nodes_include
+    pub tags_include: Vec<TagId>,
+    /// Tags whose nodes and edges are hidden.
+    pub tags_exclude: Vec<TagId>,
+    /// Nodes that are shown, along with their descendants.
+    ///
+    /// If this and [`tags_include`] are empty, all nodes are shown.
+    ///
+    /// [`tags_include`]: Self::tags_include
+    pub nodes_include: Vec<NodeId>,
+    /// Nodes that are hidden, along with their descendants.
+    pub nodes_exclude: Vec<NodeId>,
+    /// Clusters that are shown as a single node.
+    ///
+    /// Edges to nodes within a collapsed cluster are connected to the cluster
+    /// instead.
+    pub clusters_collapsed: Vec<NodeId>,
+    /// Direction of the graph in this view, defaults to the graph's direction.
+    pub direction: Option<GraphDir>,
+    /// Theme merged over the graph's theme in this view.
+    ///
+    /// `merge_with_base` and `cascade` default to the graph theme's values.
+    pub theme: ViewTheme,
+}
+
+impl View {
+    /// Returns a new `View` that shows the whole graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the view to display.
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Sets the tags whose nodes and edges are shown.
+    pub fn with_tags_include(mut self, tags_include: Vec<TagId>) -> Self {
+        self.tags_include = tags_include;
+        self
+    }
+
+    /// Sets the tags whose nodes and edges are hidden.
+    pub fn with_tags_exclude(mut self, tags_exclude: Vec<TagId>) -> Self {
+        self.tags_exclude = tags_exclude;
+        self
+    }
+
+    /// Sets the nodes that are shown, along with their descendants.
+    pub fn with_nodes_include(mut self, nodes_include: Vec<NodeId>) -> Self {
+        self.nodes_include = nodes_include;
+        self
+    }
+
+    /// Sets the nodes that are hidden, along with their descendants.
+    pub fn with_nodes_exclude(mut self, nodes_exclude: Vec<NodeId>) -> Self {
+        self.nodes_exclude = nodes_exclude;
+        self
+    }
+
+    /// Sets the clusters that are shown as a single node.
+    pub fn with_clusters_collapsed(mut self, clusters_collapsed: Vec<NodeId>) -> Self {
+        self.clusters_collapsed = clusters_collapsed;
+        self
+    }
+
+    /// Sets the direction of the graph in this view.
+    pub fn with_direction(mut self, direction: GraphDir) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Sets the theme merged over the graph's theme in this view.
+    pub fn with_theme(mut self, theme: ViewTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Returns the name of the view to display, if set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the tags whose nodes and edges are shown.
+    pub fn tags_include(&self) -> &[TagId] {
+        &self.tags_include
+    }
+
+    /// Returns the tags whose nodes and edges are hidden.
+    pub fn tags_exclude(&self) -> &[TagId] {
+        &self.tags_exclude
+    }
+
+    /// Returns the nodes that are shown, along with their descendants.
+    pub fn nodes_include(&self) -> &[NodeId] {
+        &self.nodes_include
+    }
+
+    /// Returns the nodes that are hidden, along with their descendants.
+    pub fn nodes_exclude(&self) -> &[NodeId] {
+        &self.nodes_exclude
+    }
+
+    /// Returns the clusters that are shown as a single node.
+    pub fn clusters_collapsed(&self) -> &[NodeId] {
+        &self.clusters_collapsed
+    }
+
+    /// Returns the direction of the graph in this view, if overridden.
+    pub fn direction(&self) -> Option<GraphDir> {
+        self.direction
+    }
+
+    /// Returns the theme merged over the graph's theme in this view.
+    pub fn theme(&self) -> &ViewTheme {
+        &self.theme
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::{AnyId, Edges, NodeHierarchy, NodeId, TagId},
+    info_graph::{InfoGraph, View},
+};
+
+/// Returns the `InfoGraph` as presented by the view.
+pub(crate) fn view_info_graph(info_graph: &InfoGraph, view: &View) -> InfoGraph {
+    let node_id_to_parent = info_graph.node_parents();
+    let node_ids = info_graph
+        .hierarchy_flat()
+        .into_keys()
+        .map(NodeId::as_str)
+        .collect::<HashSet<&str>>();
+
+    let tagged_include = tagged_any_ids(info_graph, view.tags_include());
+    let tagged_exclude = tagged_any_ids(info_graph, view.tags_exclude());
+
+    let include_all = view.nodes_include().is_empty() && view.tags_include().is_empty();
+    let mut nodes_included = view
+        .nodes_include()
+        .iter()
+        .map(NodeId::as_str)
+        .chain(
+            tagged_include
+                .iter()
+                .copied()
+                .filter(|any_id| node_ids.contains(any_id)),
+        )
+        .collect::<HashSet<&str>>();
+    // Edges with an included tag show the nodes they connect.
+    info_graph
+        .edges()
+        .iter()
+        .filter(|(edge_id, _)| tagged_include.contains(edge_id.as_str()))
+        .for_each(|(_, [src_node_id, target_node_id])| {
+            nodes_included.insert(src_node_id.node_id_plain());
+            nodes_included.insert(target_node_id.node_id_plain());
+        });
+    // Clusters of included nodes are shown so that the nodes stay nested.
+    let nodes_with_descendant_included = nodes_included
+        .iter()
+        .flat_map(|node_id| ancestors(&node_id_to_parent, node_id))
+        .collect::<HashSet<&str>>();
+    let nodes_excluded = view
+        .nodes_exclude()
+        .iter()
+        .map(NodeId::as_str)
+        .chain(
+            tagged_exclude
+                .iter()
+                .copied()
+                .filter(|any_id| node_ids.contains(any_id)),
+        )
+        .collect::<HashSet<&str>>();
+    let clusters_collapsed = view
+        .clusters_collapsed()
+        .iter()
+        .map(NodeId::as_str)
+        .collect::<HashSet<&str>>();
+
+    let node_filter = NodeFilter {
+        include_all,
+        nodes_included: &nodes_included,
+        nodes_with_descendant_included: &nodes_with_descendant_included,
+        nodes_excluded: &nodes_excluded,
+        clusters_collapsed: &clusters_collapsed,
+    };
+    let hierarchy = hierarchy_filter(info_graph.hierarchy(), false, &node_filter);
+    let nodes_visible = {
+        let mut nodes_visible = HashSet::<NodeId>::with_capacity(node_ids.len());
+        hierarchy_node_ids_collect(&hierarchy, &mut nodes_visible);
+        nodes_visible
+    };
+
+    let edges = info_graph
+        .edges()
+        .iter()
+        .filter(|(edge_id, _)| !tagged_exclude.contains(edge_id.as_str()))
+        .filter_map(|(edge_id, [src_node_id, target_node_id])| {
+            let [src, target] = [src_node_id, target_node_id].map(|node_id| {
+                edge_end_visible(
+                    &node_id_to_parent,
+                    &nodes_visible,
+                    &clusters_collapsed,
+                    node_id,
+                )
+            });
+            let (src, target) = src.zip(target)?;
+
+            // Edges within a collapsed cluster are hidden.
+            let redirected = src != *src_node_id || target != *target_node_id;
+            if redirected && src.node_id_plain() == target.node_id_plain() {
+                None
+            } else {
+                Some((edge_id.clone(), [src, target]))
+            }
+        })
+        .collect::<Edges>();
+
+    let mut info_graph_view = info_graph.clone();
+    info_graph_view.hierarchy = hierarchy;
+    info_graph_view.edges = edges;
+    if let Some(direction) = view.direction() {
+        info_graph_view.direction = direction;
+    }
+    info_graph_view.theme = view.theme().merge_over(info_graph_view.theme);
+
+    let InfoGraph {
+        node_names,
+        node_descs,
+        node_emojis,
+        node_images,
+        edges,
+        edge_descs,
+        tags,
+        tag_items,
+        tag_styles_focus,
+        ..
+    } = &mut info_graph_view;
+    node_names.retain(|node_id, _| nodes_visible.contains(node_id));
+    node_descs.retain(|node_id, _| nodes_visible.contains(node_id));
+    node_emojis.retain(|node_id, _| nodes_visible.contains(node_id));
+    node_images.retain(|node_id, _| nodes_visible.contains(node_id));
+    edge_descs.retain(|edge_id, _| edges.contains_key(edge_id));
+
+    tags.retain(|tag_id, _| !view.tags_exclude().contains(tag_id));
+    tag_styles_focus.retain(|tag_id, _| !view.tags_exclude().contains(tag_id));
+    tag_items.retain(|tag_id, any_ids| {
+        any_ids.retain(|any_id| {
+            nodes_visible.contains(any_id.as_str()) || edges.contains_key(any_id.as_str())
+        });
+        !view.tags_exclude().contains(tag_id)
+    });
+
+    info_graph_view
+}
+
+/// Which nodes are shown in a view.
+struct NodeFilter<'f> {
+    include_all: bool,
+    nodes_included: &'f HashSet<&'f str>,
+    nodes_with_descendant_included: &'f HashSet<&'f str>,
+    nodes_excluded: &'f HashSet<&'f str>,
+    clusters_collapsed: &'f HashSet<&'f str>,
+}
+
+/// Returns the hierarchy with only the nodes shown in the view.
+fn hierarchy_filter(
+    hierarchy: &NodeHierarchy,
+    ancestor_included: bool,
+    node_filter: &NodeFilter<'_>,
+) -> NodeHierarchy {
+    let NodeFilter {
+        include_all,
+        nodes_included,
+        nodes_with_descendant_included,
+        nodes_excluded,
+        clusters_collapsed,
+    } = node_filter;
+
+    hierarchy.iter().fold(
+        NodeHierarchy::new(),
+        |mut hierarchy_filtered, (node_id, child_hierarchy)| {
+            if nodes_excluded.contains(node_id.as_str()) {
+                return hierarchy_filtered;
+            }
+            let included =
+                *include_all || ancestor_included || nodes_included.contains(node_id.as_str());
+            if !included && !nodes_with_descendant_included.contains(node_id.as_str()) {
+                return hierarchy_filtered;
+            }
+
+            let child_hierarchy = if clusters_collapsed.contains(node_id.as_str()) {
+                NodeHierarchy::new()
+            } else {
+                hierarchy_filter(child_hierarchy, included, node_filter)
+            };
+            hierarchy_filtered.insert(node_id.clone(), child_hierarchy);
+
+            hierarchy_filtered
+        },
+    )
+}
+
+fn hierarchy_node_ids_collect(hierarchy: &NodeHierarchy, node_ids: &mut HashSet<NodeId>) {
+    hierarchy.iter().for_each(|(node_id, child_hierarchy)| {
+        node_ids.insert(node_id.clone());
+        hierarchy_node_ids_collect(child_hierarchy, node_ids);
+    });
+}
+
+/// Returns the node that an edge end connects to in the view.
+///
+/// If the node is within a collapsed cluster, this returns the cluster. If the
+/// node is otherwise hidden, this returns `None`.
+fn edge_end_visible(
+    node_id_to_parent: &HashMap<&NodeId, &NodeId>,
+    nodes_visible: &HashSet<NodeId>,
+    clusters_collapsed: &HashSet<&str>,
+    node_id_with_port: &NodeId,
+) -> Option<NodeId> {
+    let node_id = node_id_with_port.node_id_plain();
+    if nodes_visible.contains(node_id) {
+        return Some(node_id_with_port.clone());
+    }
+
+    // The outermost collapsed cluster is the one that is shown.
+    ancestors(node_id_to_parent, node_id)
+        .filter(|ancestor_node_id| clusters_collapsed.contains(ancestor_node_id))
+        .last()
+        .and_then(|cluster_node_id| nodes_visible.get(cluster_node_id))
+        .cloned()
+}
+
+/// Returns the IDs of nodes and edges associated with the tags.
+fn tagged_any_ids<'graph>(
+    info_graph: &'graph InfoGraph,
+    tag_ids: &[TagId],
+) -> HashSet<&'graph str> {
+    tag_ids
+        .iter()
+        .filter_map(|tag_id| info_graph.tag_items().get(tag_id))
+        .flatten()
+        .map(AnyId::as_str)
+        .collect()
+}
+
+/// Returns the ancestors of a node, from its parent to the top level cluster.
+fn ancestors<'graph>(
+    node_id_to_parent: &HashMap<&'graph NodeId, &'graph NodeId>,
+    node_id: &str,
+) -> impl Iterator<Item = &'graph str> {
+    let mut node_id_parent = node_id_to_parent.get(node_id).copied();
+    std::iter::from_fn(move || {
+        let parent_node_id = node_id_parent?;
+        node_id_parent = node_id_to_parent.get(parent_node_id.as_str()).copied();
+        Some(parent_node_id.as_str())
+    })
+}
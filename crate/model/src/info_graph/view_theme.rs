@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use crate::theme::{Theme, ThemeStyles};
+
+/// Theme that a [`View`] merges over the graph's theme.
+///
+/// This has the same fields as [`Theme`], but `merge_with_base` and `cascade`
+/// are only changed from the graph's theme when they are set.
+///
+/// # Examples
+///
+/// ```yaml
+/// theme:
+///   merge_with_base: false
+///   cascade: true
+///   styles:
+///     edge_defaults:
+///       stroke_style: dashed
+/// ```
+///
+/// [`View`]: crate::info_graph::View
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewTheme {
+    /// Whether to merge with the base styles, defaults to the graph theme's
+    /// value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_with_base: Option<bool>,
+    /// Whether styles specified for a cluster are inherited by its
+    /// descendants, defaults to the graph theme's value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cascade: Option<bool>,
+    /// CSS utility class partials for each element, merged over the graph
+    /// theme's styles.
+    pub styles: ThemeStyles,
+}
+
+impl ViewTheme {
+    /// Returns a new `ViewTheme` that doesn't change the graph's theme.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether to merge with the base styles in this view.
+    pub fn with_merge_with_base(mut self, merge_with_base: bool) -> Self {
+        self.merge_with_base = Some(merge_with_base);
+        self
+    }
+
+    /// Sets whether styles specified for a cluster are inherited by its
+    /// descendants in this view.
+    pub fn with_cascade(mut self, cascade: bool) -> Self {
+        self.cascade = Some(cascade);
+        self
+    }
+
+    /// Sets the styles merged over the graph theme's styles.
+    pub fn with_styles(mut self, styles: ThemeStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Returns whether to merge with the base styles, if overridden.
+    pub fn merge_with_base(&self) -> Option<bool> {
+        self.merge_with_base
+    }
+
+    /// Returns whether styles specified for a cluster are inherited by its
+    /// descendants, if overridden.
+    pub fn cascade(&self) -> Option<bool> {
+        self.cascade
+    }
+
+    /// Returns the styles merged over the graph theme's styles.
+    pub fn styles(&self) -> &ThemeStyles {
+        &self.styles
+    }
+
+    /// Returns the graph's theme with this theme merged over it.
+    pub fn merge_over(&self, theme: Theme) -> Theme {
+        let merge_with_base = self.merge_with_base.unwrap_or(theme.merge_with_base);
+        let cascade = self.cascade.unwrap_or(theme.cascade);
+
+        Theme {
+            merge_with_base,
+            cascade,
+            ..theme.styles_merge_overlay(&self.styles)
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::ops::{Deref, DerefMut};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{common::ViewId, info_graph::View};
+
+/// Each view and how it presents the graph. `IndexMap<ViewId, View>` newtype.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Views(IndexMap<ViewId, View>);
+
+impl Views {
+    /// Returns a new `Views` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new `Views` map with the given preallocated
+    /// capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity(capacity))
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> IndexMap<ViewId, View> {
+        self.0
+    }
+}
+
+impl Deref for Views {
+    type Target = IndexMap<ViewId, View>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Views {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<IndexMap<ViewId, View>> for Views {
+    fn from(inner: IndexMap<ViewId, View>) -> Self {
+        Self(inner)
+    }
+}
+
+impl FromIterator<(ViewId, View)> for Views {
+    fn from_iter<I: IntoIterator<Item = (ViewId, View)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
@@ -1,20 +1,30 @@
 use std::{
     borrow::Cow,
+    collections::HashSet,
     ops::{Deref, DerefMut},
 };
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
-use crate::common::{AnyId, EdgeId, NodeId, TagId};
+use crate::{
+    common::{AnyId, EdgeId, NodeId, TagId},
+    theme::{any_id_or_defaults::AnyIdStr, theme_selector_keys::ThemeSelectorKeys},
+};
 
 pub use self::{
     any_id_or_defaults::AnyIdOrDefaults, color_params::ColorParams,
     css_class_merger::CssClassMerger, css_class_partials::CssClassPartials,
     css_classes::CssClasses, css_classes_and_warnings::CssClassesAndWarnings,
-    css_classes_builder::CssClassesBuilder, el_css_classes::ElCssClasses,
-    highlight_state::HighlightState, line_params::LineParams, style_for::StyleFor,
-    theme_attr::ThemeAttr, theme_styles::ThemeStyles, theme_warnings::ThemeWarnings,
-    themeable::Themeable,
+    css_classes_builder::CssClassesBuilder, edge_flow::EdgeFlow, edge_flow_params::EdgeFlowParams,
+    el_css_classes::ElCssClasses, font_params::FontParams, highlight_state::HighlightState,
+    line_params::LineParams, opacity_params::OpacityParams, shape_effect_params::ShapeEffectParams,
+    style_for::StyleFor, theme_attr::ThemeAttr, theme_attr_conflict::ThemeAttrConflict,
+    theme_attr_explanation::ThemeAttrExplanation, theme_attr_value_invalid::ThemeAttrValueInvalid,
+    theme_attrs_partial::ThemeAttrsPartial, theme_explanation::ThemeExplanation,
+    theme_selector::ThemeSelector, theme_source::ThemeSource, theme_styles::ThemeStyles,
+    theme_warning::ThemeWarning, theme_warning_severity::ThemeWarningSeverity,
+    theme_warnings::ThemeWarnings, themeable::Themeable,
 };
 
 mod any_id_or_defaults;
@@ -24,15 +34,34 @@ mod css_class_partials;
 mod css_classes;
 mod css_classes_and_warnings;
 mod css_classes_builder;
+mod edge_flow;
+mod edge_flow_params;
 mod el_css_classes;
+mod font_params;
 mod highlight_state;
 mod line_params;
+mod opacity_params;
+mod shape_effect_params;
 mod style_for;
 mod theme_attr;
+mod theme_attr_conflict;
+mod theme_attr_explanation;
+mod theme_attr_value_invalid;
+mod theme_attrs_partial;
+mod theme_explanation;
+mod theme_selector;
+mod theme_selector_keys;
+mod theme_source;
 mod theme_styles;
+mod theme_warning;
+mod theme_warning_severity;
 mod theme_warnings;
 mod themeable;
 
+/// Value substituted for an attribute in [`Theme::explain`], to find the
+/// classes that the attribute produces.
+const EXPLAIN_MARKER: &str = "dot_ix_explain";
+
 /// Theme to style the generated diagram.
 ///
 /// This is a way to simplify what Tailwind CSS classes are provided, as the
@@ -60,11 +89,70 @@ mod themeable;
 /// 1. Theme default.
 /// 2. Colour override.
 /// 3. Node/edge specific override.
+///
+/// ## Selectors
+///
+/// Besides `node_defaults`, `edge_defaults`, and element IDs, styles may be
+/// keyed by a [`ThemeSelector`], e.g. `node:db_*`, `tag:critical`, `depth:2`,
+/// `cluster`, `leaf`, or `edge:from=api_*`.
+///
+/// For each element, styles are merged in the following order, with later
+/// layers overriding earlier ones:
+///
+/// 1. Theme default.
+/// 2. `node_defaults` / `edge_defaults`.
+/// 3. Each matching selector's styles, in order of
+///    [`ThemeSelector::precedence`], then the order they are specified.
+/// 4. Node / edge specific override.
+///
+/// An attribute in a later layer also overrides the more specific attributes
+/// from earlier layers that fall back to it, e.g. `fill_color` overrides an
+/// earlier `fill_color_normal`.
+///
+/// Warnings for an element name the selectors that were applied to it.
+///
+/// ## Cascade Merge Order
+///
+/// When [`cascade`] is `true`, styles specified for a cluster's ID are also
+/// inherited by its descendant nodes, per [`ThemeAttr`]. For each node, the
+/// attributes are merged in the following order, with later layers overriding
+/// earlier ones:
+///
+/// 1. Theme default.
+/// 2. `node_defaults`.
+/// 3. Each matching selector's styles.
+/// 4. Each ancestor cluster's styles, from the outermost cluster to the node's
+///    parent.
+/// 5. Node specific override.
+///
+/// Edges do not inherit styles from clusters.
+///
+/// Attribute fallbacks are resolved within each layer before the layers are
+/// merged, so any attribute that a node sets overrides the attributes it
+/// inherits. For example, when a cluster sets `fill_color_normal` and the node
+/// sets `fill_color`, the node's `fill_color` is used for every highlight
+/// state.
+///
+/// ## Explaining Styles
+///
+/// [`explain`] and [`tag_explain`] return which theme and key supplied each
+/// attribute of an element, and the CSS classes each attribute produced.
+///
+/// [`cascade`]: Self::cascade
+/// [`explain`]: Self::explain
+/// [`tag_explain`]: Self::tag_explain
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(default)]
 pub struct Theme {
     /// Whether to merge with the base styles.
     pub merge_with_base: bool,
+    /// Whether styles specified for a cluster are inherited by its
+    /// descendants.
+    ///
+    /// Defaults to `false`. See [Cascade Merge Order].
+    ///
+    /// [Cascade Merge Order]: #cascade-merge-order
+    pub cascade: bool,
     /// CSS utility class partials for each element.
     pub styles: ThemeStyles,
 }
@@ -73,6 +161,7 @@ impl Default for Theme {
     fn default() -> Self {
         Self {
             merge_with_base: true,
+            cascade: false,
             styles: Default::default(),
         }
     }
@@ -189,6 +278,7 @@ impl Theme {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             merge_with_base: true,
+            cascade: false,
             styles: ThemeStyles::with_capacity(capacity),
         }
     }
@@ -203,12 +293,33 @@ impl Theme {
         self.merge_with_base
     }
 
+    /// Sets whether styles specified for a cluster are inherited by its
+    /// descendants.
+    ///
+    /// See [Cascade Merge Order].
+    ///
+    /// [Cascade Merge Order]: #cascade-merge-order
+    pub fn with_cascade(mut self, cascade: bool) -> Self {
+        self.cascade = cascade;
+        self
+    }
+
+    /// Returns whether styles specified for a cluster are inherited by its
+    /// descendants.
+    pub fn cascade(&self) -> bool {
+        self.cascade
+    }
+
     /// Merges the given overlay theme over this theme.
     ///
     /// Keys in the overlay theme will override the keys from this theme.
-    pub fn merge_overlay(mut self, overlay: &Theme) -> Self {
-        overlay
-            .styles
+    pub fn merge_overlay(self, overlay: &Theme) -> Self {
+        self.styles_merge_overlay(&overlay.styles)
+    }
+
+    /// Merges the given overlay styles over this theme's styles.
+    pub(crate) fn styles_merge_overlay(mut self, overlay_styles: &ThemeStyles) -> Self {
+        overlay_styles
             .iter()
             .for_each(|(any_id_or_defaults, css_class_partials)| {
                 if let Some(existing_partials) = self.styles.get_mut(any_id_or_defaults) {
@@ -241,7 +352,7 @@ impl Theme {
         };
 
         theme
-            .node_el_css_classes(themeable)
+            .node_el_css_classes(themeable, self.cascade)
             .chain(theme.edge_el_css_classes(themeable))
             .fold(
                 (
@@ -265,37 +376,125 @@ impl Theme {
             )
     }
 
+    /// Explains where the attributes of an element's CSS classes came from.
+    ///
+    /// For each [`ThemeAttr`] that contributes to the classes computed by
+    /// [`Self::el_css_classes`], this returns the theme and key that supplied
+    /// its value -- [`Theme::base`], `node_defaults` / `edge_defaults`, a
+    /// selector, an ancestor, or the element's ID -- and the classes that it
+    /// produced.
+    ///
+    /// Returns `None` if `any_id` is not a node or edge of the themeable.
+    ///
+    /// See [`Self::tag_explain`] for the styles applied when a tag is focused.
+    pub fn explain<T>(&self, themeable: &T, any_id: &AnyId) -> Option<ThemeExplanation>
+    where
+        T: Themeable,
+    {
+        let theme_base;
+        let theme_sources = if self.merge_with_base {
+            theme_base = Theme::base();
+            vec![
+                (ThemeSource::Base, &theme_base),
+                (ThemeSource::Diagram, self),
+            ]
+        } else {
+            vec![(ThemeSource::Diagram, self)]
+        };
+
+        Self::explain_internal(
+            themeable,
+            any_id,
+            &theme_sources,
+            self.cascade,
+            StyleFor::Regular,
+        )
+    }
+
     fn node_el_css_classes<'f, T>(
         &'f self,
         themeable: &'f T,
+        cascade: bool,
     ) -> impl Iterator<Item = (AnyId, CssClassesAndWarnings)> + 'f
     where
         T: Themeable,
     {
         let node_class_partials_defaults = self.get(&AnyIdOrDefaults::NodeDefaults);
+        let theme_selector_keys = ThemeSelectorKeys::new(self);
         themeable.node_ids().map(move |node_id| {
-            let node_class_partials_specified = self.node_class_partials_specified(node_id);
+            let (node_class_partials_specified, theme_selectors) = self
+                .node_class_partials_resolved(themeable, &theme_selector_keys, node_id, cascade);
 
             let any_id = AnyId::from(node_id.clone());
             let node_classes_and_warnings = CssClassMerger::node_classes(
                 &any_id,
                 node_class_partials_defaults,
-                node_class_partials_specified,
+                node_class_partials_specified.as_deref(),
                 themeable,
             );
+            let node_classes_and_warnings =
+                Self::theme_warnings_selectors_name(&theme_selectors, node_classes_and_warnings);
+            let node_classes_and_warnings = self.theme_warnings_attr_conflicts_insert(
+                &any_id,
+                None,
+                &theme_selectors,
+                node_classes_and_warnings,
+            );
 
             (any_id, node_classes_and_warnings)
         })
     }
 
-    fn node_class_partials_specified(&self, node_id: &NodeId) -> Option<&CssClassPartials> {
-        self.iter()
-            .find_map(|(any_id_or_defaults, css_class_partials)| {
-                any_id_or_defaults
-                    .any_id()
-                    .filter(|any_id| any_id.as_str() == node_id.as_str())
-                    .map(|_| css_class_partials)
-            })
+    /// Returns the class partials for a node, and the selectors that matched
+    /// it.
+    ///
+    /// The class partials are merged in the order of [`Self::node_theme_keys`].
+    fn node_class_partials_resolved<'f, T>(
+        &'f self,
+        themeable: &T,
+        theme_selector_keys: &ThemeSelectorKeys<'f>,
+        node_id: &NodeId,
+        cascade: bool,
+    ) -> (Option<Cow<'f, CssClassPartials>>, Vec<&'f ThemeSelector>)
+    where
+        T: Themeable,
+    {
+        let theme_keys = self.node_theme_keys(themeable, theme_selector_keys, node_id, cascade);
+        self.class_partials_resolved(theme_keys)
+    }
+
+    /// Returns the keys in this theme whose styles apply to a node, ordered
+    /// from lowest to highest priority.
+    ///
+    /// 1. Matching selectors, in order of [`ThemeSelector::precedence`].
+    /// 2. Ancestors' IDs, if `cascade` is `true`.
+    /// 3. The node's ID.
+    ///
+    /// `node_defaults` is not included.
+    fn node_theme_keys<'f, T>(
+        &'f self,
+        themeable: &T,
+        theme_selector_keys: &ThemeSelectorKeys<'f>,
+        node_id: &NodeId,
+        cascade: bool,
+    ) -> Vec<&'f AnyIdOrDefaults>
+    where
+        T: Themeable,
+    {
+        let mut theme_keys = theme_selector_keys
+            .node_theme_keys(themeable, node_id)
+            .collect::<Vec<_>>();
+        if cascade {
+            theme_keys.extend(
+                themeable
+                    .node_ancestor_ids(node_id)
+                    .into_iter()
+                    .filter_map(|ancestor_id| self.id_theme_key(ancestor_id.as_str())),
+            );
+        }
+        theme_keys.extend(self.id_theme_key(node_id.as_str()));
+
+        theme_keys
     }
 
     fn edge_el_css_classes<'f, T>(
@@ -306,30 +505,232 @@ impl Theme {
         T: Themeable,
     {
         let edge_class_partials_defaults = self.get(&AnyIdOrDefaults::EdgeDefaults);
+        let theme_selector_keys = ThemeSelectorKeys::new(self);
 
         themeable.edge_ids().map(move |edge_id| {
-            let edge_class_partials_specified = self.edge_class_partials_specified(edge_id);
+            let (edge_class_partials_specified, theme_selectors) =
+                self.edge_class_partials_resolved(themeable, &theme_selector_keys, edge_id);
 
             let any_id = AnyId::from(edge_id.clone());
             let edge_classes_and_warnings = CssClassMerger::edge_classes(
                 &any_id,
                 edge_class_partials_defaults,
-                edge_class_partials_specified,
+                edge_class_partials_specified.as_deref(),
                 themeable,
             );
+            let edge_classes_and_warnings =
+                Self::theme_warnings_selectors_name(&theme_selectors, edge_classes_and_warnings);
+            let edge_classes_and_warnings = self.theme_warnings_attr_conflicts_insert(
+                &any_id,
+                None,
+                &theme_selectors,
+                edge_classes_and_warnings,
+            );
 
             (any_id, edge_classes_and_warnings)
         })
     }
 
-    fn edge_class_partials_specified(&self, edge_id: &EdgeId) -> Option<&CssClassPartials> {
-        self.iter()
-            .find_map(|(any_id_or_defaults, css_class_partials)| {
-                any_id_or_defaults
-                    .any_id()
-                    .filter(|any_id| any_id.as_str() == edge_id.as_str())
-                    .map(|_| css_class_partials)
+    /// Returns the class partials for an edge, and the selectors that matched
+    /// it.
+    ///
+    /// The class partials are merged in the order of [`Self::edge_theme_keys`].
+    fn edge_class_partials_resolved<'f, T>(
+        &'f self,
+        themeable: &T,
+        theme_selector_keys: &ThemeSelectorKeys<'f>,
+        edge_id: &EdgeId,
+    ) -> (Option<Cow<'f, CssClassPartials>>, Vec<&'f ThemeSelector>)
+    where
+        T: Themeable,
+    {
+        let theme_keys = self.edge_theme_keys(themeable, theme_selector_keys, edge_id);
+        self.class_partials_resolved(theme_keys)
+    }
+
+    /// Returns the keys in this theme whose styles apply to an edge, ordered
+    /// from lowest to highest priority.
+    ///
+    /// 1. Matching selectors, in order of [`ThemeSelector::precedence`].
+    /// 2. The edge's ID.
+    ///
+    /// `edge_defaults` is not included.
+    fn edge_theme_keys<'f, T>(
+        &'f self,
+        themeable: &T,
+        theme_selector_keys: &ThemeSelectorKeys<'f>,
+        edge_id: &EdgeId,
+    ) -> Vec<&'f AnyIdOrDefaults>
+    where
+        T: Themeable,
+    {
+        let mut theme_keys = theme_selector_keys
+            .edge_theme_keys(themeable, edge_id)
+            .collect::<Vec<_>>();
+        theme_keys.extend(self.id_theme_key(edge_id.as_str()));
+
+        theme_keys
+    }
+
+    /// Returns the merged class partials for the given keys, and the selectors
+    /// among them.
+    fn class_partials_resolved<'f>(
+        &'f self,
+        theme_keys: Vec<&'f AnyIdOrDefaults>,
+    ) -> (Option<Cow<'f, CssClassPartials>>, Vec<&'f ThemeSelector>) {
+        let theme_selectors = theme_keys
+            .iter()
+            .filter_map(|any_id_or_defaults| any_id_or_defaults.selector())
+            .collect::<Vec<_>>();
+        let css_class_partials = Self::class_partials_merge(
+            theme_keys
+                .into_iter()
+                .filter_map(|any_id_or_defaults| self.get(any_id_or_defaults)),
+        );
+
+        (css_class_partials, theme_selectors)
+    }
+
+    /// Returns the key in this theme for the given node or edge ID.
+    fn id_theme_key(&self, id: &str) -> Option<&AnyIdOrDefaults> {
+        self.get_key_value(&AnyIdStr(id))
+            .map(|(any_id_or_defaults, _)| any_id_or_defaults)
+    }
+
+    /// Merges class partials, where later partials override earlier ones.
+    ///
+    /// See [`Self::class_partials_overlay`].
+    fn class_partials_merge<'f, I>(css_class_partials_all: I) -> Option<Cow<'f, CssClassPartials>>
+    where
+        I: Iterator<Item = &'f CssClassPartials>,
+    {
+        css_class_partials_all.fold(None, |css_class_partials_acc, css_class_partials| {
+            match css_class_partials_acc {
+                None => Some(Cow::Borrowed(css_class_partials)),
+                Some(css_class_partials_acc) => {
+                    let mut css_class_partials_acc = css_class_partials_acc.into_owned();
+                    Self::class_partials_overlay(&mut css_class_partials_acc, css_class_partials);
+                    Some(Cow::Owned(css_class_partials_acc))
+                }
+            }
+        })
+    }
+
+    /// Overlays `css_class_partials` onto `css_class_partials_acc`.
+    ///
+    /// Attribute fallbacks are resolved within each layer, so an attribute in
+    /// the overlay also overrides the more specific attributes that fall back
+    /// to it, e.g. `fill_color` overrides an earlier `fill_color_normal`.
+    fn class_partials_overlay(
+        css_class_partials_acc: &mut CssClassPartials,
+        css_class_partials: &CssClassPartials,
+    ) {
+        Self::theme_attrs_overridden(css_class_partials).for_each(|theme_attr| {
+            css_class_partials_acc.shift_remove(&theme_attr);
+        });
+        css_class_partials_acc.extend(
+            css_class_partials
+                .iter()
+                .map(|(theme_attr, value)| (*theme_attr, value.clone())),
+        );
+    }
+
+    /// Returns the attributes that are not in `css_class_partials`, but fall
+    /// back to an attribute that is.
+    fn theme_attrs_overridden(
+        css_class_partials: &CssClassPartials,
+    ) -> impl Iterator<Item = ThemeAttr> + '_ {
+        css_class_partials
+            .keys()
+            .flat_map(|theme_attr| theme_attr.fallback_for().iter().copied())
+            .filter(|theme_attr| !css_class_partials.contains_key(theme_attr))
+    }
+
+    /// Records the selectors that contributed styles to an element in each of
+    /// its warnings.
+    fn theme_warnings_selectors_name(
+        theme_selectors: &[&ThemeSelector],
+        css_classes_and_warnings: CssClassesAndWarnings,
+    ) -> CssClassesAndWarnings {
+        if theme_selectors.is_empty() || css_classes_and_warnings.theme_warnings.is_empty() {
+            return css_classes_and_warnings;
+        }
+
+        let CssClassesAndWarnings {
+            css_classes,
+            theme_warnings,
+        } = css_classes_and_warnings;
+        let theme_warnings = theme_warnings
+            .into_inner()
+            .into_iter()
+            .map(|mut theme_warning| {
+                if let Some(theme_attrs_partial) = theme_warning.theme_attrs_partial_mut() {
+                    theme_attrs_partial.theme_selectors = theme_selectors
+                        .iter()
+                        .map(|theme_selector| (*theme_selector).clone())
+                        .collect();
+                }
+                theme_warning
+            })
+            .collect::<ThemeWarnings>();
+
+        CssClassesAndWarnings::new(css_classes, theme_warnings)
+    }
+
+    /// Inserts a warning for each attribute that selectors with the same
+    /// precedence specify different values for.
+    ///
+    /// `theme_selectors` must be ordered by [`ThemeSelector::precedence`].
+    fn theme_warnings_attr_conflicts_insert(
+        &self,
+        any_id: &AnyId,
+        tag_id: Option<&TagId>,
+        theme_selectors: &[&ThemeSelector],
+        mut css_classes_and_warnings: CssClassesAndWarnings,
+    ) -> CssClassesAndWarnings {
+        theme_selectors
+            .chunk_by(|theme_selector_a, theme_selector_b| {
+                theme_selector_a.precedence() == theme_selector_b.precedence()
             })
+            .filter(|theme_selectors_same_precedence| theme_selectors_same_precedence.len() > 1)
+            .for_each(|theme_selectors_same_precedence| {
+                let attr_values = theme_selectors_same_precedence.iter().fold(
+                    IndexMap::<ThemeAttr, Vec<(ThemeSelector, String)>>::new(),
+                    |mut attr_values, theme_selector| {
+                        let theme_selector = (*theme_selector).clone();
+                        let theme_key = AnyIdOrDefaults::Selector(theme_selector.clone());
+                        if let Some(css_class_partials) = self.get(&theme_key) {
+                            css_class_partials.iter().for_each(|(theme_attr, value)| {
+                                attr_values
+                                    .entry(*theme_attr)
+                                    .or_default()
+                                    .push((theme_selector.clone(), value.clone()));
+                            });
+                        }
+                        attr_values
+                    },
+                );
+
+                attr_values
+                    .into_iter()
+                    .filter(|(_theme_attr, values)| {
+                        values
+                            .iter()
+                            .any(|(_theme_selector, value)| *value != values[0].1)
+                    })
+                    .for_each(|(theme_attr, values)| {
+                        css_classes_and_warnings
+                            .theme_warnings
+                            .insert(ThemeWarning::AttrConflict(ThemeAttrConflict {
+                                any_id: any_id.clone(),
+                                tag_id: tag_id.cloned(),
+                                theme_attr,
+                                values,
+                            }));
+                    });
+            });
+
+        css_classes_and_warnings
     }
 
     /// Computes the CSS utility classes for nodes and edges.
@@ -361,7 +762,7 @@ impl Theme {
         };
 
         tag_theme
-            .node_tag_el_css_classes(themeable, tag_id)
+            .node_tag_el_css_classes(themeable, tag_id, diagram_theme.cascade)
             .chain(tag_theme.edge_tag_el_css_classes(themeable, tag_id))
             .fold(
                 (
@@ -385,26 +786,260 @@ impl Theme {
             )
     }
 
+    /// Explains where the attributes of an element's CSS classes came from,
+    /// when the given tag is focused.
+    ///
+    /// `self` is the tag's theme, and the theme sources mirror
+    /// [`Self::tag_el_css_classes`]: [`Theme::tag_base`] when `self` has
+    /// `merge_with_base`, otherwise the `diagram_theme`, overlaid with the
+    /// tag's theme.
+    ///
+    /// Returns `None` if `any_id` is not a node or edge of the themeable.
+    pub fn tag_explain<T>(
+        &self,
+        themeable: &T,
+        diagram_theme: &Theme,
+        tag_id: &TagId,
+        any_id: &AnyId,
+    ) -> Option<ThemeExplanation>
+    where
+        T: Themeable,
+    {
+        let theme_tag_base;
+        let theme_source_tag = ThemeSource::TagFocus(tag_id.clone());
+        let theme_sources = if self.merge_with_base {
+            theme_tag_base = Theme::tag_base();
+            [
+                (ThemeSource::TagBase, &theme_tag_base),
+                (theme_source_tag, self),
+            ]
+        } else {
+            [
+                (ThemeSource::Diagram, diagram_theme),
+                (theme_source_tag, self),
+            ]
+        };
+
+        Self::explain_internal(
+            themeable,
+            any_id,
+            &theme_sources,
+            diagram_theme.cascade,
+            StyleFor::TagFocus(tag_id),
+        )
+    }
+
+    /// Returns the explanation for an element, where `theme_sources` are
+    /// merged in order.
+    fn explain_internal<T>(
+        themeable: &T,
+        any_id: &AnyId,
+        theme_sources: &[(ThemeSource, &Theme)],
+        cascade: bool,
+        style_for: StyleFor<'_>,
+    ) -> Option<ThemeExplanation>
+    where
+        T: Themeable,
+    {
+        // Used to find the keys that apply to the element, the same way
+        // `el_css_classes` does.
+        let theme_merged = theme_sources
+            .iter()
+            .fold(Theme::new(), |theme_merged, (_theme_source, theme)| {
+                theme_merged.merge_overlay(theme)
+            });
+        let theme_selector_keys = ThemeSelectorKeys::new(&theme_merged);
+
+        let node_id = themeable
+            .node_ids()
+            .find(|node_id| node_id.as_str() == any_id.as_str());
+        let (is_node, theme_keys) = if let Some(node_id) = node_id {
+            let theme_keys = std::iter::once(AnyIdOrDefaults::NodeDefaults)
+                .chain(
+                    theme_merged
+                        .node_theme_keys(themeable, &theme_selector_keys, node_id, cascade)
+                        .into_iter()
+                        .cloned(),
+                )
+                .collect::<Vec<_>>();
+            (true, theme_keys)
+        } else {
+            let edge_id = themeable
+                .edge_ids()
+                .find(|edge_id| edge_id.as_str() == any_id.as_str())?;
+            let theme_keys = std::iter::once(AnyIdOrDefaults::EdgeDefaults)
+                .chain(
+                    theme_merged
+                        .edge_theme_keys(themeable, &theme_selector_keys, edge_id)
+                        .into_iter()
+                        .cloned(),
+                )
+                .collect::<Vec<_>>();
+            (false, theme_keys)
+        };
+
+        // Later keys override earlier keys, and for each key, later theme sources
+        // override earlier ones.
+        let mut attr_sources = IndexMap::<ThemeAttr, (ThemeSource, AnyIdOrDefaults, String)>::new();
+        theme_keys.iter().for_each(|theme_key| {
+            theme_sources
+                .iter()
+                .filter_map(|(theme_source, theme)| {
+                    theme
+                        .get(theme_key)
+                        .map(|css_class_partials| (theme_source, css_class_partials))
+                })
+                .for_each(|(theme_source, css_class_partials)| {
+                    // Values from `node_defaults` / `edge_defaults` are looked up
+                    // separately from the element's own layers, so they are not
+                    // overridden by less specific attributes.
+                    Self::theme_attrs_overridden(css_class_partials).for_each(|theme_attr| {
+                        let overridden = attr_sources.get(&theme_attr).is_some_and(
+                            |(_, theme_key_existing, _)| {
+                                !matches!(
+                                    theme_key_existing,
+                                    AnyIdOrDefaults::NodeDefaults | AnyIdOrDefaults::EdgeDefaults
+                                )
+                            },
+                        );
+                        if overridden {
+                            attr_sources.shift_remove(&theme_attr);
+                        }
+                    });
+                    css_class_partials.iter().for_each(|(theme_attr, value)| {
+                        attr_sources.insert(
+                            *theme_attr,
+                            (theme_source.clone(), theme_key.clone(), value.clone()),
+                        );
+                    });
+                });
+        });
+
+        let css_class_partials = attr_sources
+            .iter()
+            .map(|(theme_attr, (_theme_source, _theme_key, value))| (*theme_attr, value.clone()))
+            .collect::<CssClassPartials>();
+        let css_classes =
+            Self::explain_css_classes(themeable, any_id, is_node, &css_class_partials, style_for);
+
+        // The classes that an attribute produced are the ones that change when its
+        // value changes.
+        let theme_attr_explanations = attr_sources
+            .into_iter()
+            .filter_map(|(theme_attr, (theme_source, theme_key, value))| {
+                let mut css_class_partials_marked = css_class_partials.clone();
+                css_class_partials_marked.insert(theme_attr, String::from(EXPLAIN_MARKER));
+                let css_classes_marked = Self::explain_css_classes(
+                    themeable,
+                    any_id,
+                    is_node,
+                    &css_class_partials_marked,
+                    style_for,
+                );
+                let css_classes_marked = css_classes_marked
+                    .split_whitespace()
+                    .collect::<HashSet<&str>>();
+
+                let css_classes_produced = css_classes
+                    .split_whitespace()
+                    .filter(|css_class| !css_classes_marked.contains(css_class))
+                    .map(String::from)
+                    .collect::<Vec<String>>();
+
+                (!css_classes_produced.is_empty()).then_some(ThemeAttrExplanation {
+                    theme_attr,
+                    value,
+                    theme_source,
+                    theme_key,
+                    css_classes: css_classes_produced,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let tag_id = match style_for {
+            StyleFor::Regular => None,
+            StyleFor::TagFocus(tag_id) => Some(tag_id.clone()),
+        };
+
+        Some(ThemeExplanation {
+            any_id: any_id.clone(),
+            tag_id,
+            css_classes,
+            theme_attr_explanations,
+        })
+    }
+
+    fn explain_css_classes<T>(
+        themeable: &T,
+        any_id: &AnyId,
+        is_node: bool,
+        css_class_partials: &CssClassPartials,
+        style_for: StyleFor<'_>,
+    ) -> CssClasses
+    where
+        T: Themeable,
+    {
+        let css_class_partials = Some(css_class_partials);
+        let CssClassesAndWarnings {
+            css_classes,
+            theme_warnings: _,
+        } = match (is_node, style_for) {
+            (true, StyleFor::Regular) => {
+                CssClassMerger::node_classes(any_id, None, css_class_partials, themeable)
+            }
+            (true, StyleFor::TagFocus(tag_id)) => CssClassMerger::node_tag_classes(
+                any_id,
+                None,
+                css_class_partials,
+                themeable,
+                tag_id,
+            ),
+            (false, StyleFor::Regular) => {
+                CssClassMerger::edge_classes(any_id, None, css_class_partials, themeable)
+            }
+            (false, StyleFor::TagFocus(tag_id)) => CssClassMerger::edge_tag_classes(
+                any_id,
+                None,
+                css_class_partials,
+                themeable,
+                tag_id,
+            ),
+        };
+
+        css_classes
+    }
+
     fn node_tag_el_css_classes<'f, T>(
         &'f self,
         themeable: &'f T,
         tag_id: &'f TagId,
+        cascade: bool,
     ) -> impl Iterator<Item = (AnyId, CssClassesAndWarnings)> + 'f
     where
         T: Themeable,
     {
         let node_class_partials_defaults = self.get(&AnyIdOrDefaults::NodeDefaults);
+        let theme_selector_keys = ThemeSelectorKeys::new(self);
         themeable.node_ids().map(move |node_id| {
-            let node_class_partials_specified = self.node_class_partials_specified(node_id);
+            let (node_class_partials_specified, theme_selectors) = self
+                .node_class_partials_resolved(themeable, &theme_selector_keys, node_id, cascade);
 
             let any_id = AnyId::from(node_id.clone());
             let node_classes_and_warnings = CssClassMerger::node_tag_classes(
                 &any_id,
                 node_class_partials_defaults,
-                node_class_partials_specified,
+                node_class_partials_specified.as_deref(),
                 themeable,
                 tag_id,
             );
+            let node_classes_and_warnings =
+                Self::theme_warnings_selectors_name(&theme_selectors, node_classes_and_warnings);
+            let node_classes_and_warnings = self.theme_warnings_attr_conflicts_insert(
+                &any_id,
+                Some(tag_id),
+                &theme_selectors,
+                node_classes_and_warnings,
+            );
 
             (any_id, node_classes_and_warnings)
         })
@@ -419,18 +1054,28 @@ impl Theme {
         T: Themeable,
     {
         let edge_class_partials_defaults = self.get(&AnyIdOrDefaults::EdgeDefaults);
+        let theme_selector_keys = ThemeSelectorKeys::new(self);
 
         themeable.edge_ids().map(move |edge_id| {
-            let edge_class_partials_specified = self.edge_class_partials_specified(edge_id);
+            let (edge_class_partials_specified, theme_selectors) =
+                self.edge_class_partials_resolved(themeable, &theme_selector_keys, edge_id);
 
             let any_id = AnyId::from(edge_id.clone());
             let edge_classes_and_warnings = CssClassMerger::edge_tag_classes(
                 &any_id,
                 edge_class_partials_defaults,
-                edge_class_partials_specified,
+                edge_class_partials_specified.as_deref(),
                 themeable,
                 tag_id,
             );
+            let edge_classes_and_warnings =
+                Self::theme_warnings_selectors_name(&theme_selectors, edge_classes_and_warnings);
+            let edge_classes_and_warnings = self.theme_warnings_attr_conflicts_insert(
+                &any_id,
+                Some(tag_id),
+                &theme_selectors,
+                edge_classes_and_warnings,
+            );
 
             (any_id, edge_classes_and_warnings)
         })
@@ -455,6 +1100,7 @@ impl From<ThemeStyles> for Theme {
     fn from(styles: ThemeStyles) -> Self {
         Self {
             merge_with_base: true,
+            cascade: false,
             styles,
         }
     }
@@ -1,12 +1,19 @@
-use std::fmt;
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
 
+use indexmap::Equivalent;
 use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::common::AnyId;
+use crate::{common::AnyId, theme::ThemeSelector};
 
 /// Key for users to specify tailwind styles against individual nodes, edges, or
 /// tags.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+///
+/// Keys that are `cluster`, `leaf`, or contain a `:` are parsed as a
+/// [`ThemeSelector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AnyIdOrDefaults {
     /// Styles to apply to all nodes.
     NodeDefaults,
@@ -14,6 +21,8 @@ pub enum AnyIdOrDefaults {
     EdgeDefaults,
     /// ID of a node, edge, or tag.
     AnyId(AnyId),
+    /// Styles to apply to all nodes or edges matching the selector.
+    Selector(ThemeSelector),
 }
 
 impl AnyIdOrDefaults {
@@ -25,6 +34,51 @@ impl AnyIdOrDefaults {
             None
         }
     }
+
+    /// Returns the underlying `ThemeSelector` if this holds a selector.
+    pub fn selector(&self) -> Option<&ThemeSelector> {
+        if let Self::Selector(theme_selector) = self {
+            Some(theme_selector)
+        } else {
+            None
+        }
+    }
+}
+
+// `Hash` is implemented manually so that `AnyIdStr` hashes identically to the
+// `AnyId` variant.
+impl Hash for AnyIdOrDefaults {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::NodeDefaults => 0u8.hash(state),
+            Self::EdgeDefaults => 1u8.hash(state),
+            Self::AnyId(any_id) => AnyIdStr(any_id.as_str()).hash(state),
+            Self::Selector(theme_selector) => {
+                3u8.hash(state);
+                theme_selector.hash(state);
+            }
+        }
+    }
+}
+
+/// Borrowed form of [`AnyIdOrDefaults::AnyId`], to look up an element's
+/// styles by its ID without allocating an `AnyId`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct AnyIdStr<'s>(pub(crate) &'s str);
+
+impl Hash for AnyIdStr<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        2u8.hash(state);
+        self.0.hash(state);
+    }
+}
+
+impl Equivalent<AnyIdOrDefaults> for AnyIdStr<'_> {
+    fn equivalent(&self, any_id_or_defaults: &AnyIdOrDefaults) -> bool {
+        any_id_or_defaults
+            .any_id()
+            .is_some_and(|any_id| any_id.as_str() == self.0)
+    }
 }
 
 impl From<AnyId> for AnyIdOrDefaults {
@@ -33,6 +87,12 @@ impl From<AnyId> for AnyIdOrDefaults {
     }
 }
 
+impl From<ThemeSelector> for AnyIdOrDefaults {
+    fn from(theme_selector: ThemeSelector) -> Self {
+        Self::Selector(theme_selector)
+    }
+}
+
 impl Serialize for AnyIdOrDefaults {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -42,6 +102,9 @@ impl Serialize for AnyIdOrDefaults {
             AnyIdOrDefaults::NodeDefaults => serializer.serialize_str("node_defaults"),
             AnyIdOrDefaults::EdgeDefaults => serializer.serialize_str("edge_defaults"),
             AnyIdOrDefaults::AnyId(any_id) => serializer.serialize_str(any_id),
+            AnyIdOrDefaults::Selector(theme_selector) => {
+                serializer.serialize_str(&theme_selector.to_string())
+            }
         }
     }
 }
@@ -61,7 +124,8 @@ impl Visitor<'_> for AnyIdOrDefaultsVisitor {
     type Value = AnyIdOrDefaults;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("one of `node_defaults`, `edge_defaults`, or a node/edge/tag ID")
+        formatter
+            .write_str("one of `node_defaults`, `edge_defaults`, a selector, or a node/edge/tag ID")
     }
 
     fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
@@ -71,6 +135,16 @@ impl Visitor<'_> for AnyIdOrDefaultsVisitor {
         let any_id_or_defaults = match value {
             "node_defaults" => AnyIdOrDefaults::NodeDefaults,
             "edge_defaults" => AnyIdOrDefaults::EdgeDefaults,
+            "cluster" | "leaf" => AnyIdOrDefaults::Selector(
+                value
+                    .parse::<ThemeSelector>()
+                    .map_err(serde::de::Error::custom)?,
+            ),
+            _ if value.contains(':') => AnyIdOrDefaults::Selector(
+                value
+                    .parse::<ThemeSelector>()
+                    .map_err(serde::de::Error::custom)?,
+            ),
             _ => {
                 let any_id = AnyId::try_from(value.to_owned()).map_err(serde::de::Error::custom)?;
                 AnyIdOrDefaults::AnyId(any_id)
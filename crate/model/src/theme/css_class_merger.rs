@@ -1,8 +1,10 @@
 use crate::{
     common::{AnyId, TagId},
     theme::{
-        ColorParams, CssClassPartials, CssClassesAndWarnings, CssClassesBuilder, HighlightState,
-        LineParams, StyleFor, ThemeAttr, ThemeWarnings, Themeable,
+        ColorParams, CssClassPartials, CssClassesAndWarnings, CssClassesBuilder, EdgeFlow,
+        EdgeFlowParams, FontParams, HighlightState, LineParams, OpacityParams, ShapeEffectParams,
+        StyleFor, ThemeAttr, ThemeAttrValueInvalid, ThemeAttrsPartial, ThemeWarning, ThemeWarnings,
+        Themeable,
     },
 };
 
@@ -85,6 +87,22 @@ impl CssClassMerger {
                 themeable.node_fill_classes(node_id, css_classes_builder, params);
             };
 
+        let themeable_node_text_color_classes =
+            |node_id: &AnyId,
+             themeable: &dyn Themeable,
+             css_classes_builder: &mut CssClassesBuilder,
+             params: ColorParams<'_>| {
+                themeable.node_text_color_classes(node_id, css_classes_builder, params);
+            };
+
+        let themeable_node_opacity_classes =
+            |node_id: &AnyId,
+             themeable: &dyn Themeable,
+             css_classes_builder: &mut CssClassesBuilder,
+             params: OpacityParams<'_>| {
+                themeable.node_opacity_classes(node_id, css_classes_builder, params);
+            };
+
         Self::outline_classes_append(
             css_class_merge_params,
             &mut css_classes_builder,
@@ -106,14 +124,40 @@ impl CssClassMerger {
             themeable_node_fill_classes,
             style_for,
         );
+        Self::text_color_classes_append(
+            css_class_merge_params,
+            &mut css_classes_builder,
+            &mut warnings,
+            themeable_node_text_color_classes,
+            style_for,
+        );
+        Self::opacity_classes_append(
+            css_class_merge_params,
+            &mut css_classes_builder,
+            themeable_node_opacity_classes,
+            style_for,
+        );
 
         let CssClassMergeParams {
-            any_id: _,
+            any_id,
             defaults,
             specified,
-            themeable: _,
+            themeable,
         } = css_class_merge_params;
 
+        let font_params = Self::font_params(defaults, specified);
+        if font_params != FontParams::default() {
+            themeable.node_font_classes(any_id, &mut css_classes_builder, font_params);
+        }
+        let shape_effect_params = Self::shape_effect_params(defaults, specified);
+        if shape_effect_params != ShapeEffectParams::default() {
+            themeable.node_shape_effect_classes(
+                any_id,
+                &mut css_classes_builder,
+                shape_effect_params,
+            );
+        }
+
         [
             SpacingParamGroupings::new("px", &[ThemeAttr::PaddingX, ThemeAttr::Padding]),
             SpacingParamGroupings::new("py", &[ThemeAttr::PaddingY, ThemeAttr::Padding]),
@@ -219,6 +263,22 @@ impl CssClassMerger {
                 themeable.edge_fill_classes(edge_id, css_classes_builder, params);
             };
 
+        let themeable_edge_text_color_classes =
+            |edge_id: &AnyId,
+             themeable: &dyn Themeable,
+             css_classes_builder: &mut CssClassesBuilder,
+             params: ColorParams<'_>| {
+                themeable.edge_text_color_classes(edge_id, css_classes_builder, params);
+            };
+
+        let themeable_edge_opacity_classes =
+            |edge_id: &AnyId,
+             themeable: &dyn Themeable,
+             css_classes_builder: &mut CssClassesBuilder,
+             params: OpacityParams<'_>| {
+                themeable.edge_opacity_classes(edge_id, css_classes_builder, params);
+            };
+
         Self::outline_classes_append(
             css_class_merge_params,
             &mut css_classes_builder,
@@ -240,14 +300,45 @@ impl CssClassMerger {
             themeable_edge_fill_classes,
             style_for,
         );
+        Self::text_color_classes_append(
+            css_class_merge_params,
+            &mut css_classes_builder,
+            &mut warnings,
+            themeable_edge_text_color_classes,
+            style_for,
+        );
+        Self::opacity_classes_append(
+            css_class_merge_params,
+            &mut css_classes_builder,
+            themeable_edge_opacity_classes,
+            style_for,
+        );
 
         let CssClassMergeParams {
-            any_id: _,
+            any_id,
             defaults,
             specified,
-            themeable: _,
+            themeable,
         } = css_class_merge_params;
 
+        let font_params = Self::font_params(defaults, specified);
+        if font_params != FontParams::default() {
+            themeable.edge_font_classes(any_id, &mut css_classes_builder, font_params);
+        }
+        let shape_effect_params = Self::shape_effect_params(defaults, specified);
+        if shape_effect_params != ShapeEffectParams::default() {
+            themeable.edge_shape_effect_classes(
+                any_id,
+                &mut css_classes_builder,
+                shape_effect_params,
+            );
+        }
+        if let Some(edge_flow_params) =
+            Self::edge_flow_params(css_class_merge_params, &mut warnings, style_for)
+        {
+            themeable.edge_flow_classes(any_id, &mut css_classes_builder, edge_flow_params);
+        }
+
         Self::animation_classes(&mut css_classes_builder, defaults, specified);
         Self::visibility_classes(&mut css_classes_builder, defaults, specified);
         Self::cursor_classes(&mut css_classes_builder, defaults, specified);
@@ -303,34 +394,16 @@ impl CssClassMerger {
                 .into_iter()
                 .for_each(|line_class_append_result| {
                     if let LineClassAppendResult::NoChange {
-                        style,
-                        width,
-                        color,
-                        shade,
+                        highlight_state,
+                        attrs,
                     } = line_class_append_result
                     {
-                        warnings.insert(format!(
-                            "Outline attributes partially specified, \
-                            so outline classes will not be applied. \
-                            outline_style: `{style}`, outline_width: `{width}`, \
-                            outline_color: `{color}`, outline_shade: `{shade}`.",
-                            style = style
-                                .map(|(_attr, value)| value.to_string())
-                                .as_deref()
-                                .unwrap_or("<none>"),
-                            width = width
-                                .map(|(_attr, value)| value.to_string())
-                                .as_deref()
-                                .unwrap_or("<none>"),
-                            color = color
-                                .map(|(_attr, value)| value.to_string())
-                                .as_deref()
-                                .unwrap_or("<none>"),
-                            shade = shade
-                                .map(|(_attr, value)| value.to_string())
-                                .as_deref()
-                                .unwrap_or("<none>"),
-                        ));
+                        warnings.insert(ThemeWarning::OutlineAttrsPartial(theme_attrs_partial(
+                            css_class_merge_params.any_id,
+                            style_for,
+                            highlight_state,
+                            &attrs,
+                        )));
                     }
                 });
         }
@@ -384,34 +457,16 @@ impl CssClassMerger {
                 .into_iter()
                 .for_each(|line_class_append_result| {
                     if let LineClassAppendResult::NoChange {
-                        style,
-                        width,
-                        color,
-                        shade,
+                        highlight_state,
+                        attrs,
                     } = line_class_append_result
                     {
-                        warnings.insert(format!(
-                            "Stroke attributes partially specified, \
-                                so stroke classes will not be applied. \
-                                stroke_style: `{style}`, stroke_width: `{width}`, \
-                                stroke_color: `{color}`, stroke_shade: `{shade}`.",
-                            style = style
-                                .map(|(_attr, value)| value.to_string())
-                                .as_deref()
-                                .unwrap_or("<none>"),
-                            width = width
-                                .map(|(_attr, value)| value.to_string())
-                                .as_deref()
-                                .unwrap_or("<none>"),
-                            color = color
-                                .map(|(_attr, value)| value.to_string())
-                                .as_deref()
-                                .unwrap_or("<none>"),
-                            shade = shade
-                                .map(|(_attr, value)| value.to_string())
-                                .as_deref()
-                                .unwrap_or("<none>"),
-                        ));
+                        warnings.insert(ThemeWarning::StrokeAttrsPartial(theme_attrs_partial(
+                            css_class_merge_params.any_id,
+                            style_for,
+                            highlight_state,
+                            &attrs,
+                        )));
                     }
                 });
         }
@@ -467,10 +522,13 @@ impl CssClassMerger {
                 LineClassAppendResult::Added
             }
             (style, width, color, shade) => LineClassAppendResult::NoChange {
-                style,
-                width,
-                color,
-                shade,
+                highlight_state: *highlight_state,
+                attrs: [
+                    attr_or_fallback(style, line_style_keys),
+                    attr_or_fallback(width, line_width_keys),
+                    attr_or_fallback(color, color_keys),
+                    attr_or_fallback(shade, shade_keys),
+                ],
             },
         }
     }
@@ -494,7 +552,7 @@ impl CssClassMerger {
                     ColorParamGroupings::new_fill_active(fn_fill_classes),
                 ]
                 .into_iter();
-                Self::fill_classes_fold(
+                Self::color_classes_fold(
                     css_class_merge_params,
                     css_classes_builder,
                     param_groupings,
@@ -505,7 +563,7 @@ impl CssClassMerger {
                 let param_groupings =
                     [ColorParamGroupings::new_fill_normal(fn_fill_classes)].into_iter();
 
-                Self::fill_classes_fold(
+                Self::color_classes_fold(
                     css_class_merge_params,
                     css_classes_builder,
                     param_groupings,
@@ -516,40 +574,250 @@ impl CssClassMerger {
         if !fill_class_append_result
             .iter()
             .any(|fill_class_append_result| {
-                matches!(fill_class_append_result, FillClassAppendResult::Added)
+                matches!(fill_class_append_result, ColorClassAppendResult::Added)
             })
         {
             fill_class_append_result
                 .into_iter()
                 .for_each(|fill_class_append_result| {
-                    if let FillClassAppendResult::NoChange { color, shade } =
-                        fill_class_append_result
+                    if let ColorClassAppendResult::NoChange {
+                        highlight_state,
+                        attrs,
+                    } = fill_class_append_result
                     {
-                        warnings.insert(format!(
-                            "Fill attributes partially specified, \
-                            so fill classes will not be applied. \
-                            fill_color: `{color_value}`, fill_shade: `{shade_value}`.",
-                            color_value = color
-                                .map(|(_attr, value)| value.to_string())
-                                .as_deref()
-                                .unwrap_or("<none>"),
-                            shade_value = shade
-                                .map(|(_attr, value)| value.to_string())
-                                .as_deref()
-                                .unwrap_or("<none>"),
-                        ));
+                        warnings.insert(ThemeWarning::FillAttrsPartial(theme_attrs_partial(
+                            css_class_merge_params.any_id,
+                            style_for,
+                            highlight_state,
+                            &attrs,
+                        )));
                     }
                 });
         }
     }
 
-    /// Appends CSS classes for fill styling for a given [`HighlightState`]s to
-    /// the CSS classes builder.
-    fn fill_classes_highlight_state_append<'f1, 'f2: 'f1>(
+    /// Appends CSS classes for text colour styling for all [`HighlightState`]s
+    /// to the CSS classes builder.
+    fn text_color_classes_append(
+        css_class_merge_params: CssClassMergeParams<'_>,
+        css_classes_builder: &mut CssClassesBuilder,
+        warnings: &mut ThemeWarnings,
+        fn_text_color_classes: fn(&AnyId, &dyn Themeable, &mut CssClassesBuilder, ColorParams<'_>),
+        style_for: StyleFor<'_>,
+    ) {
+        let text_color_class_append_result = match style_for {
+            StyleFor::Regular => {
+                let param_groupings = [
+                    ColorParamGroupings::new_text_normal(fn_text_color_classes),
+                    ColorParamGroupings::new_text_focus(fn_text_color_classes),
+                    ColorParamGroupings::new_text_focus_hover(fn_text_color_classes),
+                    ColorParamGroupings::new_text_hover(fn_text_color_classes),
+                    ColorParamGroupings::new_text_active(fn_text_color_classes),
+                ]
+                .into_iter();
+                Self::color_classes_fold(
+                    css_class_merge_params,
+                    css_classes_builder,
+                    param_groupings,
+                )
+            }
+
+            StyleFor::TagFocus(_) => {
+                let param_groupings =
+                    [ColorParamGroupings::new_text_normal(fn_text_color_classes)].into_iter();
+
+                Self::color_classes_fold(
+                    css_class_merge_params,
+                    css_classes_builder,
+                    param_groupings,
+                )
+            }
+        };
+
+        if !text_color_class_append_result
+            .iter()
+            .any(|text_color_class_append_result| {
+                matches!(
+                    text_color_class_append_result,
+                    ColorClassAppendResult::Added
+                )
+            })
+        {
+            text_color_class_append_result
+                .into_iter()
+                .for_each(|text_color_class_append_result| {
+                    if let ColorClassAppendResult::NoChange {
+                        highlight_state,
+                        attrs,
+                    } = text_color_class_append_result
+                    {
+                        warnings.insert(ThemeWarning::TextAttrsPartial(theme_attrs_partial(
+                            css_class_merge_params.any_id,
+                            style_for,
+                            highlight_state,
+                            &attrs,
+                        )));
+                    }
+                });
+        }
+    }
+
+    /// Appends CSS classes for opacity for all [`HighlightState`]s to the CSS
+    /// classes builder.
+    fn opacity_classes_append(
+        css_class_merge_params: CssClassMergeParams<'_>,
+        css_classes_builder: &mut CssClassesBuilder,
+        fn_opacity_classes: fn(&AnyId, &dyn Themeable, &mut CssClassesBuilder, OpacityParams<'_>),
+        style_for: StyleFor<'_>,
+    ) {
+        let CssClassMergeParams {
+            any_id,
+            defaults,
+            specified,
+            themeable,
+        } = css_class_merge_params;
+
+        let opacity_param_groupings: &[(HighlightState, &[ThemeAttr])] = match style_for {
+            StyleFor::Regular => &[
+                (
+                    HighlightState::Normal,
+                    &[ThemeAttr::OpacityNormal, ThemeAttr::Opacity],
+                ),
+                (
+                    HighlightState::Focus,
+                    &[ThemeAttr::OpacityFocus, ThemeAttr::Opacity],
+                ),
+                (
+                    HighlightState::FocusHover,
+                    &[ThemeAttr::OpacityHover, ThemeAttr::Opacity],
+                ),
+                (
+                    HighlightState::Hover,
+                    &[ThemeAttr::OpacityHover, ThemeAttr::Opacity],
+                ),
+                (
+                    HighlightState::Active,
+                    &[ThemeAttr::OpacityActive, ThemeAttr::Opacity],
+                ),
+            ],
+            StyleFor::TagFocus(_) => &[(
+                HighlightState::Normal,
+                &[ThemeAttr::OpacityNormal, ThemeAttr::Opacity],
+            )],
+        };
+
+        opacity_param_groupings
+            .iter()
+            .for_each(|(highlight_state, opacity_keys)| {
+                if let Some((_opacity_attr, opacity)) =
+                    attr_value_find(opacity_keys, defaults, specified)
+                {
+                    let opacity_params = OpacityParams::new(*highlight_state, opacity);
+                    fn_opacity_classes(any_id, themeable, css_classes_builder, opacity_params);
+                }
+            });
+    }
+
+    /// Returns the label text font parameters.
+    fn font_params<'f>(
+        defaults: Option<&'f CssClassPartials>,
+        specified: Option<&'f CssClassPartials>,
+    ) -> FontParams<'f> {
+        let font_weight = attr_value_find(&[ThemeAttr::FontWeight], defaults, specified)
+            .map(|(_font_weight_attr, font_weight)| font_weight);
+        let font_size = attr_value_find(&[ThemeAttr::FontSize], defaults, specified)
+            .map(|(_font_size_attr, font_size)| font_size);
+
+        FontParams::new(font_weight, font_size)
+    }
+
+    /// Returns the shadow and corner radius parameters.
+    fn shape_effect_params<'f>(
+        defaults: Option<&'f CssClassPartials>,
+        specified: Option<&'f CssClassPartials>,
+    ) -> ShapeEffectParams<'f> {
+        let shadow = attr_value_find(&[ThemeAttr::Shadow], defaults, specified)
+            .map(|(_shadow_attr, shadow)| shadow);
+        let corner_radius = attr_value_find(&[ThemeAttr::CornerRadius], defaults, specified)
+            .map(|(_corner_radius_attr, corner_radius)| corner_radius);
+
+        ShapeEffectParams::new(shadow, corner_radius)
+    }
+
+    /// Returns the parameters to animate an edge, if an [`EdgeFlow`] is
+    /// specified.
+    ///
+    /// The dash pattern from the edge's normal stroke style is reused, so that
+    /// the animation moves the dashes that are already drawn.
+    ///
+    /// A warning is inserted if the [`EdgeFlow`] is not a known preset.
+    fn edge_flow_params<'f>(
+        css_class_merge_params: CssClassMergeParams<'f>,
+        warnings: &mut ThemeWarnings,
+        style_for: StyleFor<'_>,
+    ) -> Option<EdgeFlowParams<'f>> {
+        let CssClassMergeParams {
+            any_id: edge_id,
+            defaults,
+            specified,
+            themeable,
+        } = css_class_merge_params;
+
+        let (_edge_flow_attr, edge_flow) =
+            attr_value_find(&[ThemeAttr::EdgeFlow], defaults, specified)?;
+        let edge_flow = match edge_flow.parse::<EdgeFlow>() {
+            Ok(edge_flow) => edge_flow,
+            Err(reason) => {
+                let tag_id = match style_for {
+                    StyleFor::Regular => None,
+                    StyleFor::TagFocus(tag_id) => Some(tag_id.clone()),
+                };
+                warnings.insert(ThemeWarning::AttrValueInvalid(ThemeAttrValueInvalid {
+                    any_id: edge_id.clone(),
+                    tag_id,
+                    theme_attr: ThemeAttr::EdgeFlow,
+                    value: edge_flow.to_string(),
+                    reason,
+                }));
+                return None;
+            }
+        };
+        let line_style_dasharray = attr_value_find(
+            &[ThemeAttr::StrokeStyleNormal, ThemeAttr::StrokeStyle],
+            defaults,
+            specified,
+        )
+        .and_then(|(_line_style_attr, line_style)| match line_style {
+            "dashed" => Some("3"),
+            "dotted" => Some("2"),
+            line_style => line_style.strip_prefix("dasharray:"),
+        })
+        .filter(|dasharray| dash_period(dasharray).is_some());
+
+        let (dasharray, dash_period) = match line_style_dasharray {
+            Some(line_style_dasharray) => (None, dash_period(line_style_dasharray)?),
+            None => {
+                let dasharray = edge_flow.dasharray_default();
+                (Some(dasharray), dash_period(dasharray)?)
+            }
+        };
+        let edge_dir = themeable.edge_dir(edge_id);
+
+        Some(EdgeFlowParams::new(
+            edge_flow,
+            edge_dir,
+            dasharray,
+            dash_period,
+        ))
+    }
+
+    /// Appends CSS classes for fill or text colour styling for a given
+    /// [`HighlightState`]s to the CSS classes builder.
+    fn color_classes_highlight_state_append<'f1, 'f2: 'f1>(
         css_class_merge_params: CssClassMergeParams<'f2>,
         css_classes_param_groupings: ColorParamGroupings<ColorParams<'f1>>,
         css_classes_builder: &mut CssClassesBuilder,
-    ) -> FillClassAppendResult<'f2> {
+    ) -> ColorClassAppendResult<'f2> {
         let CssClassMergeParams {
             any_id,
             defaults,
@@ -568,7 +836,7 @@ impl CssClassMerger {
         let shade = attr_value_find(shade_keys, defaults, specified);
 
         match (color, shade) {
-            (None, None) => FillClassAppendResult::NoAttrsSpecified,
+            (None, None) => ColorClassAppendResult::NoAttrsSpecified,
             (Some((_color_attr, color)), Some((_shade_attr, shade))) => {
                 let params = ColorParams {
                     highlight_state,
@@ -576,9 +844,15 @@ impl CssClassMerger {
                     shade,
                 };
                 fn_css_classes(any_id, themeable, css_classes_builder, params);
-                FillClassAppendResult::Added
+                ColorClassAppendResult::Added
             }
-            (color, shade) => FillClassAppendResult::NoChange { color, shade },
+            (color, shade) => ColorClassAppendResult::NoChange {
+                highlight_state,
+                attrs: [
+                    attr_or_fallback(color, color_keys),
+                    attr_or_fallback(shade, shade_keys),
+                ],
+            },
         }
     }
 
@@ -658,18 +932,18 @@ impl CssClassMerger {
 
     /// Since the param groupings are arrays of different sizes, we need to have
     /// a generic function to cater for different concrete types.
-    fn fill_classes_fold<'f, I>(
+    fn color_classes_fold<'f, I>(
         css_class_merge_params: CssClassMergeParams<'f>,
         css_classes_builder: &mut CssClassesBuilder,
         param_groupings: I,
-    ) -> Vec<FillClassAppendResult<'f>>
+    ) -> Vec<ColorClassAppendResult<'f>>
     where
         I: Iterator<Item = ColorParamGroupings<ColorParams<'f>>> + 'f,
     {
         param_groupings.fold(
             Vec::new(),
             |mut fill_class_append_result_acc, css_classes_param_groupings| {
-                let fill_class_append_result = Self::fill_classes_highlight_state_append(
+                let fill_class_append_result = Self::color_classes_highlight_state_append(
                     css_class_merge_params,
                     css_classes_param_groupings,
                     css_classes_builder,
@@ -708,6 +982,70 @@ fn attr_value_find<'attr>(
     })
 }
 
+/// Returns the length of one repetition of a `stroke-dasharray` value.
+///
+/// Values may be separated by commas, spaces, or underscores, and an odd
+/// number of values is repeated to yield an even number of values, matching
+/// how browsers render the dash pattern.
+fn dash_period(dasharray: &str) -> Option<f64> {
+    let (count, sum) = dasharray
+        .split([',', ' ', '_'])
+        .filter(|value| !value.is_empty())
+        .try_fold((0usize, 0.0f64), |(count, sum), value| {
+            value
+                .trim_end_matches("px")
+                .parse::<f64>()
+                .ok()
+                .filter(|value| *value >= 0.0)
+                .map(|value| (count + 1, sum + value))
+        })?;
+
+    let dash_period = if count % 2 == 1 { sum * 2.0 } else { sum };
+    (dash_period > 0.0).then_some(dash_period)
+}
+
+/// Returns the found attribute and its value, or the least specific fallback
+/// attribute if no value was found.
+fn attr_or_fallback<'value>(
+    attr_value: Option<(ThemeAttr, &'value str)>,
+    attr_keys: &[ThemeAttr],
+) -> (ThemeAttr, Option<&'value str>) {
+    match attr_value {
+        Some((theme_attr, value)) => (theme_attr, Some(value)),
+        None => {
+            let theme_attr_fallback = *attr_keys
+                .last()
+                .expect("Expected attribute keys to be non-empty.");
+            (theme_attr_fallback, None)
+        }
+    }
+}
+
+/// Returns the `ThemeAttrsPartial` for a warning about partially specified
+/// attributes.
+fn theme_attrs_partial(
+    any_id: &AnyId,
+    style_for: StyleFor<'_>,
+    highlight_state: HighlightState,
+    attrs: &[(ThemeAttr, Option<&str>)],
+) -> ThemeAttrsPartial {
+    let tag_id = match style_for {
+        StyleFor::Regular => None,
+        StyleFor::TagFocus(tag_id) => Some(tag_id.clone()),
+    };
+
+    ThemeAttrsPartial {
+        any_id: any_id.clone(),
+        tag_id,
+        highlight_state,
+        attrs: attrs
+            .iter()
+            .map(|(theme_attr, value)| (*theme_attr, value.map(str::to_string)))
+            .collect(),
+        theme_selectors: Vec::new(),
+    }
+}
+
 /// Grouping of common parameters to reduce parameter count in methods.
 #[derive(Clone, Copy)]
 struct CssClassMergeParams<'params> {
@@ -822,6 +1160,63 @@ impl<Params> ColorParamGroupings<Params> {
     }
 }
 
+impl<Params> ColorParamGroupings<Params> {
+    fn new_text_normal(
+        fn_css_classes: fn(&AnyId, &dyn Themeable, &mut CssClassesBuilder, Params),
+    ) -> Self {
+        Self {
+            highlight_state: HighlightState::Normal,
+            color_keys: &[ThemeAttr::TextColorNormal, ThemeAttr::TextColor],
+            shade_keys: &[ThemeAttr::TextShadeNormal, ThemeAttr::TextShade],
+            fn_css_classes,
+        }
+    }
+
+    fn new_text_focus(
+        fn_css_classes: fn(&AnyId, &dyn Themeable, &mut CssClassesBuilder, Params),
+    ) -> Self {
+        Self {
+            highlight_state: HighlightState::Focus,
+            color_keys: &[ThemeAttr::TextColorFocus, ThemeAttr::TextColor],
+            shade_keys: &[ThemeAttr::TextShadeFocus, ThemeAttr::TextShade],
+            fn_css_classes,
+        }
+    }
+
+    fn new_text_focus_hover(
+        fn_css_classes: fn(&AnyId, &dyn Themeable, &mut CssClassesBuilder, Params),
+    ) -> Self {
+        Self {
+            highlight_state: HighlightState::FocusHover,
+            color_keys: &[ThemeAttr::TextColorHover, ThemeAttr::TextColor],
+            shade_keys: &[ThemeAttr::TextShadeHover, ThemeAttr::TextShade],
+            fn_css_classes,
+        }
+    }
+
+    fn new_text_hover(
+        fn_css_classes: fn(&AnyId, &dyn Themeable, &mut CssClassesBuilder, Params),
+    ) -> Self {
+        Self {
+            highlight_state: HighlightState::Hover,
+            color_keys: &[ThemeAttr::TextColorHover, ThemeAttr::TextColor],
+            shade_keys: &[ThemeAttr::TextShadeHover, ThemeAttr::TextShade],
+            fn_css_classes,
+        }
+    }
+
+    fn new_text_active(
+        fn_css_classes: fn(&AnyId, &dyn Themeable, &mut CssClassesBuilder, Params),
+    ) -> Self {
+        Self {
+            highlight_state: HighlightState::Active,
+            color_keys: &[ThemeAttr::TextColorActive, ThemeAttr::TextColor],
+            shade_keys: &[ThemeAttr::TextShadeActive, ThemeAttr::TextShade],
+            fn_css_classes,
+        }
+    }
+}
+
 /// Groupings of parameters to generate CSS classes for colour shades.
 #[derive(Clone, Copy)]
 struct LineParamGroupings<Params> {
@@ -980,31 +1375,31 @@ impl<Params> LineParamGroupings<Params> {
 ///
 /// "Line" applies to both `stroke-*` and `outline-*` classes.
 ///
-/// The `ThemeAttr` in each `NoChange` variant is the `ThemeAttr` that is
-/// specified, i.e. one of the fallbacks.
+/// The `attrs` in each `NoChange` variant are the style, width, colour, and
+/// shade. The `ThemeAttr` is the `ThemeAttr` that is specified, i.e. one of the
+/// fallbacks, or the least specific fallback if the value is not specified.
 #[derive(Clone, Copy)]
 enum LineClassAppendResult<'value> {
     Added,
     NoChange {
-        style: Option<(ThemeAttr, &'value str)>,
-        width: Option<(ThemeAttr, &'value str)>,
-        color: Option<(ThemeAttr, &'value str)>,
-        shade: Option<(ThemeAttr, &'value str)>,
+        highlight_state: HighlightState,
+        attrs: [(ThemeAttr, Option<&'value str>); 4],
     },
     NoAttrsSpecified,
 }
 
-/// Fill attributes that were specified but not used, due to not a complete set
-/// of attributes provided to compute a CSS class.
+/// Fill or text colour attributes that were specified but not used, due to not
+/// a complete set of attributes provided to compute a CSS class.
 ///
-/// The `ThemeAttr` in each `NoChange` variant is the `ThemeAttr` that is
-/// specified, i.e. one of the fallbacks.
+/// The `attrs` in each `NoChange` variant are the colour and shade. The
+/// `ThemeAttr` is the `ThemeAttr` that is specified, i.e. one of the fallbacks,
+/// or the least specific fallback if the value is not specified.
 #[derive(Clone, Copy)]
-enum FillClassAppendResult<'value> {
+enum ColorClassAppendResult<'value> {
     Added,
     NoChange {
-        color: Option<(ThemeAttr, &'value str)>,
-        shade: Option<(ThemeAttr, &'value str)>,
+        highlight_state: HighlightState,
+        attrs: [(ThemeAttr, Option<&'value str>); 2],
     },
     NoAttrsSpecified,
 }
@@ -0,0 +1,65 @@
+use std::{fmt, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Built-in animation that shows data moving along an edge.
+///
+/// Each preset animates the `stroke-dashoffset` of the edge's path. If the
+/// edge's [`StrokeStyle`] is `dashed`, `dotted`, or `dasharray:*`, that dash
+/// pattern is animated, otherwise the preset's own dash pattern is used.
+///
+/// [`StrokeStyle`]: crate::theme::ThemeAttr::StrokeStyle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeFlow {
+    /// Dashes march from the edge's tail to its head.
+    MarchingAnts,
+    /// Short pulses travel from the edge's tail to its head.
+    Pulse,
+    /// Dashes move in the direction of the edge's arrows.
+    ///
+    /// Edges with [`EdgeDir::Back`] flow from head to tail, [`EdgeDir::Both`]
+    /// flow back and forth, and [`EdgeDir::None`] are not animated.
+    ///
+    /// [`EdgeDir::Back`]: crate::common::graphviz_attrs::EdgeDir::Back
+    /// [`EdgeDir::Both`]: crate::common::graphviz_attrs::EdgeDir::Both
+    /// [`EdgeDir::None`]: crate::common::graphviz_attrs::EdgeDir::None
+    Flow,
+}
+
+impl EdgeFlow {
+    /// Returns the `stroke-dasharray` used when the edge is not already
+    /// dashed.
+    pub fn dasharray_default(self) -> &'static str {
+        match self {
+            Self::MarchingAnts | Self::Flow => "5,3",
+            Self::Pulse => "6,42",
+        }
+    }
+}
+
+impl FromStr for EdgeFlow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "marching_ants" => Ok(Self::MarchingAnts),
+            "pulse" => Ok(Self::Pulse),
+            "flow" => Ok(Self::Flow),
+            _ => Err(format!(
+                "Unable to map `{s}` to an `EdgeFlow`. \
+                Valid strings are: \"marching_ants\", \"pulse\", \"flow\"."
+            )),
+        }
+    }
+}
+
+impl Display for EdgeFlow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MarchingAnts => "marching_ants".fmt(f),
+            Self::Pulse => "pulse".fmt(f),
+            Self::Flow => "flow".fmt(f),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{common::graphviz_attrs::EdgeDir, theme::EdgeFlow};
+
+/// Parameters to compute edge flow animation `CssClasses`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EdgeFlowParams<'params> {
+    /// Which animation preset to use.
+    pub edge_flow: EdgeFlow,
+    /// Direction of the edge's arrows.
+    pub edge_dir: EdgeDir,
+    /// Dash pattern to apply, e.g. `"5,3"`.
+    ///
+    /// This is `None` when the edge's stroke style already provides a dash
+    /// pattern.
+    pub dasharray: Option<&'params str>,
+    /// Length of one repetition of the dash pattern, which the dash offset is
+    /// animated over so that the animation loops seamlessly.
+    pub dash_period: f64,
+}
+
+impl<'params> EdgeFlowParams<'params> {
+    /// Returns a new `EdgeFlowParams`.
+    pub fn new(
+        edge_flow: EdgeFlow,
+        edge_dir: EdgeDir,
+        dasharray: Option<&'params str>,
+        dash_period: f64,
+    ) -> Self {
+        Self {
+            edge_flow,
+            edge_dir,
+            dasharray,
+            dash_period,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+/// Parameters to compute label text font related `CssClasses`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FontParams<'params> {
+    /// Weight of the text, e.g. `"bold"`.
+    pub font_weight: Option<&'params str>,
+    /// Size of the text, e.g. `"sm"`.
+    pub font_size: Option<&'params str>,
+}
+
+impl<'params> FontParams<'params> {
+    /// Returns a new `FontParams`.
+    pub fn new(font_weight: Option<&'params str>, font_size: Option<&'params str>) -> Self {
+        Self {
+            font_weight,
+            font_size,
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Whether an element is focused, hovered, or active.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum HighlightState {
     /// The element is not focused, and the cursor is not positioned over it.
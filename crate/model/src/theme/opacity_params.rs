@@ -0,0 +1,20 @@
+use crate::theme::HighlightState;
+
+/// Parameters to compute opacity related `CssClasses`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpacityParams<'params> {
+    /// Whether the element is in the normal, focused, hovered, or active state.
+    pub highlight_state: HighlightState,
+    /// Opacity to apply, e.g. `"50"`.
+    pub opacity: &'params str,
+}
+
+impl<'params> OpacityParams<'params> {
+    /// Returns a new `OpacityParams`.
+    pub fn new(highlight_state: HighlightState, opacity: &'params str) -> Self {
+        Self {
+            highlight_state,
+            opacity,
+        }
+    }
+}
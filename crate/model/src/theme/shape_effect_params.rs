@@ -0,0 +1,18 @@
+/// Parameters to compute shadow and corner related `CssClasses`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShapeEffectParams<'params> {
+    /// Shadow to draw behind the element, e.g. `"md"`.
+    pub shadow: Option<&'params str>,
+    /// Radius of the element's corners, e.g. `"md"`.
+    pub corner_radius: Option<&'params str>,
+}
+
+impl<'params> ShapeEffectParams<'params> {
+    /// Returns a new `ShapeEffectParams`.
+    pub fn new(shadow: Option<&'params str>, corner_radius: Option<&'params str>) -> Self {
+        Self {
+            shadow,
+            corner_radius,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 /// Themeable attributes of nodes and edges.
@@ -16,6 +18,7 @@ use serde::{Deserialize, Serialize};
 ///   fill colour for edges.
 /// * `Padding*` keys are only applicable to nodes.
 /// * `Margin*` keys are only applicable to nodes.
+/// * `Text*` and `Font*` keys control the label text for nodes and edges.
 ///
 /// # Colours
 ///
@@ -337,10 +340,36 @@ pub enum ThemeAttr {
     ///
     /// [`animate-*`] https://tailwindcss.com/docs/animation
     Animate,
+    /// Radius of an element's corners, e.g. `"md"` in `"rounded-md"`.
+    ///
+    /// Valid values are `"none"`, `"sm"`, `"md"`, `"lg"`, `"xl"`, `"2xl"`,
+    /// `"3xl"`, `"full"`.
+    ///
+    /// For SVG diagrams, the shape of nodes is drawn by Graphviz, so this only
+    /// rounds the element's outline.
+    CornerRadius,
     /// The [cursor style], e.g. `"pointer"`.
     ///
     /// [cursor style]: https://tailwindcss.com/docs/cursor
     Cursor,
+    /// Built-in animation to show data moving along an edge, e.g.
+    /// `"marching_ants"`.
+    ///
+    /// Valid values are `"marching_ants"`, `"pulse"`, and `"flow"`. See
+    /// [`EdgeFlow`] for details. This is only applicable to edges.
+    ///
+    /// To animate the edges of a tag only while it is focused, specify this in
+    /// `tag_styles_focus`:
+    ///
+    /// ```yaml
+    /// tag_styles_focus:
+    ///   tag_deploy:
+    ///     edge_defaults:
+    ///       edge_flow: flow
+    /// ```
+    ///
+    /// [`EdgeFlow`]: crate::theme::EdgeFlow
+    EdgeFlow,
     /// Extra classes to attach as is.
     Extra,
     /// Colour for element background/arrow head for all states, e.g. `"slate"`.
@@ -380,6 +409,16 @@ pub enum ThemeAttr {
     /// Shade for the background when an element is being clicked /
     /// pressed, e.g. `"200"` for nodes, `"700"` for edges.
     FillShadeActive,
+    /// Size of the label text, e.g. `"sm"` in `"text-sm"`.
+    ///
+    /// Valid values are `"xs"`, `"sm"`, `"base"`, `"lg"`, `"xl"`, `"2xl"`,
+    /// and so on.
+    FontSize,
+    /// Weight of the label text, e.g. `"bold"` in `"font-bold"`.
+    ///
+    /// Valid values are `"thin"`, `"extralight"`, `"light"`, `"normal"`,
+    /// `"medium"`, `"semibold"`, `"bold"`, `"extrabold"`, `"black"`.
+    FontWeight,
     /// All padding within a node, e.g. `"1.5"` in `"p-1.5"`.
     ///
     /// This key has no effect on edges.
@@ -404,6 +443,21 @@ pub enum ThemeAttr {
     ///
     /// This key has no effect on edges.
     MarginY,
+    /// Opacity of an element for all states, e.g. `"50"` in `"opacity-50"`.
+    ///
+    /// This will be used for all [`HighlightState`]s if not overridden by one
+    /// of the more specific variants.
+    ///
+    /// [`HighlightState`]: crate::theme::HighlightState
+    Opacity,
+    /// Opacity when an element is not focused / hovered over, e.g. `"50"`.
+    OpacityNormal,
+    /// Opacity when an element is focused, e.g. `"100"`.
+    OpacityFocus,
+    /// Opacity when an element has the cursor hovering over it, e.g. `"75"`.
+    OpacityHover,
+    /// Opacity when an element is being clicked / pressed, e.g. `"100"`.
+    OpacityActive,
     /// Outline colour for elements for all states, e.g. `"blue"`. Defaults to
     /// `"blue"`.
     OutlineColor,
@@ -456,6 +510,15 @@ pub enum ThemeAttr {
     /// Outline style when an element is being clicked / pressed, e.g. `"none"`,
     /// `"solid"`, `"dashed"`, `"dotted"`.
     OutlineStyleActive,
+    /// Shadow to draw behind an element, e.g. `"md"`.
+    ///
+    /// Valid values are `"sm"`, `"md"`, `"lg"`, `"xl"`, `"2xl"`, `"none"`.
+    /// SVG elements use [`drop-shadow-*`] classes, and HTML elements use
+    /// [`shadow-*`] classes.
+    ///
+    /// [`drop-shadow-*`]: https://tailwindcss.com/docs/drop-shadow
+    /// [`shadow-*`]: https://tailwindcss.com/docs/box-shadow
+    Shadow,
     /// Base colour for shape colourable attributes, e.g. stroke/border, fill.
     ShapeColor,
     /// Line/border colour for elements for all states, e.g. `"slate"`.
@@ -507,6 +570,42 @@ pub enum ThemeAttr {
     /// Line/border style when an element is being clicked / pressed, e.g.
     /// `"none"`, `"solid"`, `"dashed"`, `"dotted"`.
     StrokeStyleActive,
+    /// Colour for label text for all states, e.g. `"slate"`.
+    ///
+    /// This will be used for all [`HighlightState`]s if not overridden by one
+    /// of the more specific variants.
+    ///
+    /// [`HighlightState`]: crate::theme::HighlightState
+    TextColor,
+    /// Colour for label text when an element is not focused / hovered over,
+    /// e.g. `"slate"`.
+    TextColorNormal,
+    /// Colour for label text when an element is focused, e.g. `"slate"`.
+    TextColorFocus,
+    /// Colour for label text when an element has the cursor hovering over it,
+    /// e.g. `"slate"`.
+    TextColorHover,
+    /// Colour for label text when an element is being clicked / pressed, e.g.
+    /// `"slate"`.
+    TextColorActive,
+    /// Shade for label text for all states, e.g. `"900"`.
+    ///
+    /// This will be used for all [`HighlightState`]s if not overridden by one
+    /// of the more specific variants.
+    ///
+    /// [`HighlightState`]: crate::theme::HighlightState
+    TextShade,
+    /// Shade for label text when an element is not focused / hovered over,
+    /// e.g. `"900"`.
+    TextShadeNormal,
+    /// Shade for label text when an element is focused, e.g. `"800"`.
+    TextShadeFocus,
+    /// Shade for label text when an element has the cursor hovering over it,
+    /// e.g. `"700"`.
+    TextShadeHover,
+    /// Shade for label text when an element is being clicked / pressed, e.g.
+    /// `"800"`.
+    TextShadeActive,
     /// Whether an element is visible.
     ///
     /// This sets the [`visibility`] class, valid values are `'visible'`,
@@ -515,3 +614,113 @@ pub enum ThemeAttr {
     /// [`visibility`] https://tailwindcss.com/docs/visibility
     Visibility,
 }
+
+impl ThemeAttr {
+    /// Returns the more specific attributes that fall back to this attribute
+    /// when they are not set.
+    ///
+    /// e.g. `FillColorNormal` falls back to `FillColor`, which falls back to
+    /// `ShapeColor`.
+    pub fn fallback_for(self) -> &'static [ThemeAttr] {
+        match self {
+            Self::FillColor => &[
+                Self::FillColorNormal,
+                Self::FillColorFocus,
+                Self::FillColorHover,
+                Self::FillColorActive,
+            ],
+            Self::FillShade => &[
+                Self::FillShadeNormal,
+                Self::FillShadeFocus,
+                Self::FillShadeHover,
+                Self::FillShadeActive,
+            ],
+            Self::Padding => &[Self::PaddingX, Self::PaddingY],
+            Self::Margin => &[Self::MarginX, Self::MarginY],
+            Self::Opacity => &[
+                Self::OpacityNormal,
+                Self::OpacityFocus,
+                Self::OpacityHover,
+                Self::OpacityActive,
+            ],
+            Self::OutlineColor => &[
+                Self::OutlineColorNormal,
+                Self::OutlineColorFocus,
+                Self::OutlineColorHover,
+                Self::OutlineColorActive,
+            ],
+            Self::OutlineShade => &[
+                Self::OutlineShadeNormal,
+                Self::OutlineShadeFocus,
+                Self::OutlineShadeHover,
+                Self::OutlineShadeActive,
+            ],
+            Self::OutlineStyle => &[
+                Self::OutlineStyleNormal,
+                Self::OutlineStyleFocus,
+                Self::OutlineStyleHover,
+                Self::OutlineStyleActive,
+            ],
+            Self::ShapeColor => &[
+                Self::FillColor,
+                Self::FillColorNormal,
+                Self::FillColorFocus,
+                Self::FillColorHover,
+                Self::FillColorActive,
+                Self::StrokeColor,
+                Self::StrokeColorNormal,
+                Self::StrokeColorFocus,
+                Self::StrokeColorHover,
+                Self::StrokeColorActive,
+            ],
+            Self::StrokeColor => &[
+                Self::StrokeColorNormal,
+                Self::StrokeColorFocus,
+                Self::StrokeColorHover,
+                Self::StrokeColorActive,
+            ],
+            Self::StrokeShade => &[
+                Self::StrokeShadeNormal,
+                Self::StrokeShadeFocus,
+                Self::StrokeShadeHover,
+                Self::StrokeShadeActive,
+            ],
+            Self::StrokeStyle => &[
+                Self::StrokeStyleNormal,
+                Self::StrokeStyleFocus,
+                Self::StrokeStyleHover,
+                Self::StrokeStyleActive,
+            ],
+            Self::TextColor => &[
+                Self::TextColorNormal,
+                Self::TextColorFocus,
+                Self::TextColorHover,
+                Self::TextColorActive,
+            ],
+            Self::TextShade => &[
+                Self::TextShadeNormal,
+                Self::TextShadeFocus,
+                Self::TextShadeHover,
+                Self::TextShadeActive,
+            ],
+            _ => &[],
+        }
+    }
+}
+
+/// Writes the attribute as it is specified in a theme, e.g. `fill_color`.
+impl fmt::Display for ThemeAttr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let variant_name = format!("{self:?}");
+        variant_name.chars().enumerate().try_for_each(|(index, c)| {
+            if c.is_ascii_uppercase() {
+                if index != 0 {
+                    write!(f, "_")?;
+                }
+                write!(f, "{}", c.to_ascii_lowercase())
+            } else {
+                write!(f, "{c}")
+            }
+        })
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{AnyId, TagId},
+    theme::{StyleFor, ThemeAttr, ThemeSelector},
+};
+
+/// Attribute that was given different values by selectors with the same
+/// precedence for an element.
+///
+/// The value from the selector specified last in the theme is applied.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ThemeAttrConflict {
+    /// ID of the node or edge that the attribute is for.
+    pub any_id: AnyId,
+    /// Tag whose focus styles the attribute is for, if any.
+    pub tag_id: Option<TagId>,
+    /// The attribute with conflicting values.
+    pub theme_attr: ThemeAttr,
+    /// Each selector that specified the attribute, and its value, in the order
+    /// they are applied.
+    pub values: Vec<(ThemeSelector, String)>,
+}
+
+impl ThemeAttrConflict {
+    /// Returns what the attribute is styling -- the element itself, or when a
+    /// tag is focused.
+    pub fn style_for(&self) -> StyleFor<'_> {
+        match self.tag_id.as_ref() {
+            Some(tag_id) => StyleFor::TagFocus(tag_id),
+            None => StyleFor::Regular,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use crate::theme::{AnyIdOrDefaults, ThemeAttr, ThemeSource};
+
+/// Where a [`ThemeAttr`]'s value came from, and the CSS classes it produced.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ThemeAttrExplanation {
+    /// The attribute.
+    pub theme_attr: ThemeAttr,
+    /// The attribute's value.
+    pub value: String,
+    /// The theme that supplied the value.
+    pub theme_source: ThemeSource,
+    /// The key within the theme that supplied the value, e.g. `node_defaults`,
+    /// a selector, an ancestor's ID, or the element's ID.
+    pub theme_key: AnyIdOrDefaults,
+    /// The CSS classes that this attribute contributed to.
+    ///
+    /// A class may be contributed to by multiple attributes, e.g. the colour
+    /// and shade of a fill.
+    pub css_classes: Vec<String>,
+}
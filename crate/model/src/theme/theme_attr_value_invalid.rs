@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{AnyId, TagId},
+    theme::{StyleFor, ThemeAttr},
+};
+
+/// Attribute whose value could not be understood, so classes for it were not
+/// applied.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ThemeAttrValueInvalid {
+    /// ID of the node or edge that the attribute is for.
+    pub any_id: AnyId,
+    /// Tag whose focus styles the attribute is for, if any.
+    pub tag_id: Option<TagId>,
+    /// The attribute with the invalid value.
+    pub theme_attr: ThemeAttr,
+    /// The invalid value.
+    pub value: String,
+    /// Why the value is invalid, including the valid values.
+    pub reason: String,
+}
+
+impl ThemeAttrValueInvalid {
+    /// Returns what the attribute is styling -- the element itself, or when a
+    /// tag is focused.
+    pub fn style_for(&self) -> StyleFor<'_> {
+        match self.tag_id.as_ref() {
+            Some(tag_id) => StyleFor::TagFocus(tag_id),
+            None => StyleFor::Regular,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{AnyId, TagId},
+    theme::{HighlightState, StyleFor, ThemeAttr, ThemeSelector},
+};
+
+/// Attributes for an element that were partially specified, so classes for
+/// them were not applied.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ThemeAttrsPartial {
+    /// ID of the node or edge that the attributes are for.
+    pub any_id: AnyId,
+    /// Tag whose focus styles the attributes are for, if any.
+    pub tag_id: Option<TagId>,
+    /// The highlight state that the attributes are for.
+    pub highlight_state: HighlightState,
+    /// Each attribute needed to compute the classes, and its value.
+    ///
+    /// When the value is `Some`, the `ThemeAttr` is the attribute that the
+    /// value was found under. When the value is `None`, the `ThemeAttr` is the
+    /// least specific attribute that could be specified to provide it.
+    pub attrs: Vec<(ThemeAttr, Option<String>)>,
+    /// Selectors that contributed styles to the element.
+    pub theme_selectors: Vec<ThemeSelector>,
+}
+
+impl ThemeAttrsPartial {
+    /// Returns what the attributes are styling -- the element itself, or when a
+    /// tag is focused.
+    pub fn style_for(&self) -> StyleFor<'_> {
+        match self.tag_id.as_ref() {
+            Some(tag_id) => StyleFor::TagFocus(tag_id),
+            None => StyleFor::Regular,
+        }
+    }
+
+    /// Returns the attributes that were not specified.
+    pub fn attrs_missing(&self) -> impl Iterator<Item = ThemeAttr> + '_ {
+        self.attrs
+            .iter()
+            .filter(|(_theme_attr, value)| value.is_none())
+            .map(|(theme_attr, _value)| *theme_attr)
+    }
+
+    /// Returns the attributes that were specified, and their values.
+    pub fn attrs_specified(&self) -> impl Iterator<Item = (ThemeAttr, &str)> + '_ {
+        self.attrs
+            .iter()
+            .filter_map(|(theme_attr, value)| value.as_deref().map(|value| (*theme_attr, value)))
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{AnyId, TagId},
+    theme::{CssClasses, ThemeAttr, ThemeAttrExplanation},
+};
+
+/// Explains how an element's CSS classes were computed from a theme.
+///
+/// This is returned by [`Theme::explain`] and [`Theme::tag_explain`].
+///
+/// [`Theme::explain`]: crate::theme::Theme::explain
+/// [`Theme::tag_explain`]: crate::theme::Theme::tag_explain
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ThemeExplanation {
+    /// ID of the node or edge.
+    pub any_id: AnyId,
+    /// The tag whose focus styles are explained, if any.
+    pub tag_id: Option<TagId>,
+    /// The element's CSS classes.
+    pub css_classes: CssClasses,
+    /// Each attribute that contributed to the CSS classes, in the order they
+    /// were resolved.
+    pub theme_attr_explanations: Vec<ThemeAttrExplanation>,
+}
+
+impl ThemeExplanation {
+    /// Returns the explanation for the given attribute, if it contributed to
+    /// the CSS classes.
+    pub fn theme_attr_explanation(&self, theme_attr: ThemeAttr) -> Option<&ThemeAttrExplanation> {
+        self.theme_attr_explanations
+            .iter()
+            .find(|theme_attr_explanation| theme_attr_explanation.theme_attr == theme_attr)
+    }
+}
@@ -0,0 +1,225 @@
+use std::{fmt, fmt::Display, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    common::{EdgeId, NodeId, TagId},
+    theme::Themeable,
+};
+
+/// Selects nodes or edges to apply styles to, without listing each ID.
+///
+/// Selectors are written as theme keys:
+///
+/// | Key               | Selects                                            |
+/// | :---------------- | :------------------------------------------------- |
+/// | `cluster`         | Nodes that contain other nodes.                    |
+/// | `leaf`            | Nodes that don't contain other nodes.              |
+/// | `depth:2`         | Nodes at the given depth, top level nodes are `0`. |
+/// | `tag:critical`    | Nodes and edges associated with the tag.           |
+/// | `node:db_*`       | Nodes whose ID matches the pattern.                |
+/// | `edge:api_*`      | Edges whose ID matches the pattern.                |
+/// | `edge:from=api_*` | Edges whose source node ID matches the pattern.    |
+/// | `edge:to=db_*`    | Edges whose target node ID matches the pattern.    |
+///
+/// Patterns support `*` to match any number of characters, and `?` to match a
+/// single character.
+///
+/// # Precedence
+///
+/// When multiple selectors match an element, styles from selectors with a
+/// higher [`precedence`] override styles from selectors with a lower
+/// precedence. Selectors with the same precedence are applied in the order
+/// they are specified in the theme.
+///
+/// Styles for an element's specific ID always override selector styles.
+///
+/// [`precedence`]: Self::precedence
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ThemeSelector {
+    /// Nodes that contain other nodes.
+    Cluster,
+    /// Nodes that don't contain other nodes.
+    Leaf,
+    /// Nodes at the given depth, where top level nodes have depth `0`.
+    Depth(usize),
+    /// Nodes and edges associated with the given tag.
+    Tag(TagId),
+    /// Nodes whose ID matches the pattern.
+    Node(String),
+    /// Edges whose ID matches the pattern.
+    Edge(String),
+    /// Edges whose source node ID matches the pattern.
+    EdgeFrom(String),
+    /// Edges whose target node ID matches the pattern.
+    EdgeTo(String),
+}
+
+impl ThemeSelector {
+    /// Returns the precedence of this selector, where higher values override
+    /// lower values.
+    ///
+    /// 1. `cluster`, `leaf`
+    /// 2. `depth:N`
+    /// 3. `tag:tag_id`
+    /// 4. `node:pattern`, `edge:pattern`, `edge:from=pattern`, `edge:to=pattern`
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Self::Cluster | Self::Leaf => 1,
+            Self::Depth(_) => 2,
+            Self::Tag(_) => 3,
+            Self::Node(_) | Self::Edge(_) | Self::EdgeFrom(_) | Self::EdgeTo(_) => 4,
+        }
+    }
+
+    /// Returns whether this selector matches the given node.
+    pub fn matches_node<T>(&self, themeable: &T, node_id: &NodeId) -> bool
+    where
+        T: Themeable,
+    {
+        match self {
+            Self::Cluster => themeable.node_is_cluster(node_id),
+            Self::Leaf => !themeable.node_is_cluster(node_id),
+            Self::Depth(depth) => themeable.node_ancestor_ids(node_id).len() == *depth,
+            Self::Tag(tag_id) => themeable.el_has_tag(node_id.as_str(), tag_id),
+            Self::Node(pattern) => glob_matches(pattern, node_id.as_str()),
+            Self::Edge(_) | Self::EdgeFrom(_) | Self::EdgeTo(_) => false,
+        }
+    }
+
+    /// Returns whether this selector matches the given edge.
+    pub fn matches_edge<T>(&self, themeable: &T, edge_id: &EdgeId) -> bool
+    where
+        T: Themeable,
+    {
+        match self {
+            Self::Cluster | Self::Leaf | Self::Depth(_) | Self::Node(_) => false,
+            Self::Tag(tag_id) => themeable.el_has_tag(edge_id.as_str(), tag_id),
+            Self::Edge(pattern) => glob_matches(pattern, edge_id.as_str()),
+            Self::EdgeFrom(pattern) => themeable
+                .edge_node_ids(edge_id)
+                .is_some_and(|(node_id_from, _)| glob_matches(pattern, node_id_from.as_str())),
+            Self::EdgeTo(pattern) => themeable
+                .edge_node_ids(edge_id)
+                .is_some_and(|(_, node_id_to)| glob_matches(pattern, node_id_to.as_str())),
+        }
+    }
+}
+
+/// Returns whether the value matches the glob pattern.
+///
+/// `*` matches any number of characters, and `?` matches a single character.
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let value = value.as_bytes();
+
+    let (mut pattern_index, mut value_index) = (0, 0);
+    // Position of the last `*` in the pattern, and the value index it was matched
+    // at, so that we can backtrack.
+    let mut star_indices = None;
+
+    while value_index < value.len() {
+        match pattern.get(pattern_index) {
+            Some(b'*') => {
+                star_indices = Some((pattern_index, value_index));
+                pattern_index += 1;
+            }
+            Some(b'?') => {
+                pattern_index += 1;
+                value_index += 1;
+            }
+            Some(c) if *c == value[value_index] => {
+                pattern_index += 1;
+                value_index += 1;
+            }
+            _ => match star_indices {
+                Some((star_pattern_index, star_value_index)) => {
+                    pattern_index = star_pattern_index + 1;
+                    value_index = star_value_index + 1;
+                    star_indices = Some((star_pattern_index, value_index));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[pattern_index..].iter().all(|c| *c == b'*')
+}
+
+impl FromStr for ThemeSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cluster" => return Ok(Self::Cluster),
+            "leaf" => return Ok(Self::Leaf),
+            _ => {}
+        }
+
+        let (kind, value) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Failed to parse `ThemeSelector` from `{s}`."))?;
+        if value.is_empty() {
+            return Err(format!(
+                "Failed to parse `ThemeSelector` from `{s}`: selector value is empty."
+            ));
+        }
+
+        match kind {
+            "depth" => value.parse::<usize>().map(Self::Depth).map_err(|_e| {
+                format!("Failed to parse `{value}` as a number for `ThemeSelector::Depth`.")
+            }),
+            "tag" => TagId::try_from(value.to_owned())
+                .map(Self::Tag)
+                .map_err(|e| e.to_string()),
+            "node" => Ok(Self::Node(value.to_owned())),
+            "edge" => {
+                if let Some(pattern) = value.strip_prefix("from=") {
+                    Ok(Self::EdgeFrom(pattern.to_owned()))
+                } else if let Some(pattern) = value.strip_prefix("to=") {
+                    Ok(Self::EdgeTo(pattern.to_owned()))
+                } else {
+                    Ok(Self::Edge(value.to_owned()))
+                }
+            }
+            _ => Err(format!(
+                "Failed to parse `ThemeSelector` from `{s}`: \
+                expected one of `cluster`, `leaf`, `depth:`, `tag:`, `node:`, or `edge:`."
+            )),
+        }
+    }
+}
+
+impl Display for ThemeSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cluster => "cluster".fmt(f),
+            Self::Leaf => "leaf".fmt(f),
+            Self::Depth(depth) => write!(f, "depth:{depth}"),
+            Self::Tag(tag_id) => write!(f, "tag:{tag_id}"),
+            Self::Node(pattern) => write!(f, "node:{pattern}"),
+            Self::Edge(pattern) => write!(f, "edge:{pattern}"),
+            Self::EdgeFrom(pattern) => write!(f, "edge:from={pattern}"),
+            Self::EdgeTo(pattern) => write!(f, "edge:to={pattern}"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeSelector {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        FromStr::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ThemeSelector {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
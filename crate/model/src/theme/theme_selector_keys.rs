@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::{EdgeId, NodeId, TagId},
+    theme::{AnyIdOrDefaults, Theme, ThemeSelector, Themeable},
+};
+
+/// A theme's selector keys, partitioned by the kind of selector.
+///
+/// This is computed once per theme, so that the keys that apply to each
+/// element are looked up without scanning and sorting every key in the theme.
+///
+/// Each partition retains the order the keys are specified in the theme, and
+/// the partitions are chained in order of [`ThemeSelector::precedence`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ThemeSelectorKeys<'theme> {
+    /// `cluster` keys.
+    cluster: Vec<&'theme AnyIdOrDefaults>,
+    /// `leaf` keys.
+    leaf: Vec<&'theme AnyIdOrDefaults>,
+    /// `depth:N` keys, by depth.
+    depth: HashMap<usize, Vec<&'theme AnyIdOrDefaults>>,
+    /// `tag:tag_id` keys.
+    tag: Vec<(&'theme TagId, &'theme AnyIdOrDefaults)>,
+    /// `node:pattern` keys.
+    node_pattern: Vec<(&'theme ThemeSelector, &'theme AnyIdOrDefaults)>,
+    /// `edge:pattern`, `edge:from=pattern`, and `edge:to=pattern` keys.
+    edge_pattern: Vec<(&'theme ThemeSelector, &'theme AnyIdOrDefaults)>,
+}
+
+impl<'theme> ThemeSelectorKeys<'theme> {
+    /// Returns the selector keys of the theme, partitioned by the kind of
+    /// selector.
+    pub(crate) fn new(theme: &'theme Theme) -> Self {
+        theme.keys().fold(
+            Self::default(),
+            |mut theme_selector_keys, any_id_or_defaults| {
+                let Some(theme_selector) = any_id_or_defaults.selector() else {
+                    return theme_selector_keys;
+                };
+                match theme_selector {
+                    ThemeSelector::Cluster => theme_selector_keys.cluster.push(any_id_or_defaults),
+                    ThemeSelector::Leaf => theme_selector_keys.leaf.push(any_id_or_defaults),
+                    ThemeSelector::Depth(depth) => theme_selector_keys
+                        .depth
+                        .entry(*depth)
+                        .or_default()
+                        .push(any_id_or_defaults),
+                    ThemeSelector::Tag(tag_id) => {
+                        theme_selector_keys.tag.push((tag_id, any_id_or_defaults))
+                    }
+                    ThemeSelector::Node(_) => theme_selector_keys
+                        .node_pattern
+                        .push((theme_selector, any_id_or_defaults)),
+                    ThemeSelector::Edge(_)
+                    | ThemeSelector::EdgeFrom(_)
+                    | ThemeSelector::EdgeTo(_) => theme_selector_keys
+                        .edge_pattern
+                        .push((theme_selector, any_id_or_defaults)),
+                }
+                theme_selector_keys
+            },
+        )
+    }
+
+    /// Returns the selector keys that match the node, ordered from lowest to
+    /// highest precedence.
+    pub(crate) fn node_theme_keys<'f, T>(
+        &'f self,
+        themeable: &'f T,
+        node_id: &'f NodeId,
+    ) -> impl Iterator<Item = &'theme AnyIdOrDefaults> + 'f
+    where
+        T: Themeable,
+    {
+        let cluster_or_leaf = if themeable.node_is_cluster(node_id) {
+            &self.cluster
+        } else {
+            &self.leaf
+        };
+        // Only compute the node's depth when there are `depth:N` keys.
+        let depth = (!self.depth.is_empty())
+            .then(|| self.depth.get(&themeable.node_ancestor_ids(node_id).len()))
+            .flatten();
+
+        cluster_or_leaf
+            .iter()
+            .chain(depth.into_iter().flatten())
+            .copied()
+            .chain(self.tag_theme_keys(themeable, node_id.as_str()))
+            .chain(
+                self.node_pattern
+                    .iter()
+                    .filter(move |(theme_selector, _)| {
+                        theme_selector.matches_node(themeable, node_id)
+                    })
+                    .map(|(_, any_id_or_defaults)| *any_id_or_defaults),
+            )
+    }
+
+    /// Returns the selector keys that match the edge, ordered from lowest to
+    /// highest precedence.
+    pub(crate) fn edge_theme_keys<'f, T>(
+        &'f self,
+        themeable: &'f T,
+        edge_id: &'f EdgeId,
+    ) -> impl Iterator<Item = &'theme AnyIdOrDefaults> + 'f
+    where
+        T: Themeable,
+    {
+        self.tag_theme_keys(themeable, edge_id.as_str()).chain(
+            self.edge_pattern
+                .iter()
+                .filter(move |(theme_selector, _)| theme_selector.matches_edge(themeable, edge_id))
+                .map(|(_, any_id_or_defaults)| *any_id_or_defaults),
+        )
+    }
+
+    /// Returns the `tag:tag_id` keys for the tags that the element has.
+    fn tag_theme_keys<'f, T>(
+        &'f self,
+        themeable: &'f T,
+        el_id: &'f str,
+    ) -> impl Iterator<Item = &'theme AnyIdOrDefaults> + 'f
+    where
+        T: Themeable,
+    {
+        self.tag
+            .iter()
+            .filter(move |(tag_id, _)| themeable.el_has_tag(el_id, tag_id))
+            .map(|(_, any_id_or_defaults)| *any_id_or_defaults)
+    }
+}
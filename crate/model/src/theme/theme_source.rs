@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::TagId;
+
+/// Which theme a style was taken from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeSource {
+    /// [`Theme::base`], used when the diagram theme has `merge_with_base`.
+    ///
+    /// [`Theme::base`]: crate::theme::Theme::base
+    Base,
+    /// The diagram's theme.
+    Diagram,
+    /// [`Theme::tag_base`], used when a tag theme has `merge_with_base`.
+    ///
+    /// [`Theme::tag_base`]: crate::theme::Theme::tag_base
+    TagBase,
+    /// The theme applied when the tag is focused.
+    TagFocus(TagId),
+}
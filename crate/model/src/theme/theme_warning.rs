@@ -0,0 +1,235 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::AnyId,
+    theme::{
+        StyleFor, ThemeAttr, ThemeAttrConflict, ThemeAttrValueInvalid, ThemeAttrsPartial,
+        ThemeWarningSeverity,
+    },
+};
+
+/// Warning detected while computing CSS utility classes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeWarning {
+    /// Outline attributes were partially specified, so outline classes will
+    /// not be applied.
+    ///
+    /// The attributes are the outline style, width, colour, and shade.
+    OutlineAttrsPartial(ThemeAttrsPartial),
+    /// Stroke attributes were partially specified, so stroke classes will not
+    /// be applied.
+    ///
+    /// The attributes are the stroke style, width, colour, and shade.
+    StrokeAttrsPartial(ThemeAttrsPartial),
+    /// Fill attributes were partially specified, so fill classes will not be
+    /// applied.
+    ///
+    /// The attributes are the fill colour and shade.
+    FillAttrsPartial(ThemeAttrsPartial),
+    /// Text colour attributes were partially specified, so text colour classes
+    /// will not be applied.
+    ///
+    /// The attributes are the text colour and shade.
+    TextAttrsPartial(ThemeAttrsPartial),
+    /// Selectors with the same precedence specified different values for an
+    /// attribute, so only the last value is applied.
+    AttrConflict(ThemeAttrConflict),
+    /// An attribute's value could not be understood, so classes for the
+    /// attribute will not be applied.
+    AttrValueInvalid(ThemeAttrValueInvalid),
+}
+
+impl ThemeWarning {
+    /// Returns the ID of the node or edge that this warning is for.
+    pub fn any_id(&self) -> &AnyId {
+        match self {
+            Self::OutlineAttrsPartial(theme_attrs_partial)
+            | Self::StrokeAttrsPartial(theme_attrs_partial)
+            | Self::FillAttrsPartial(theme_attrs_partial)
+            | Self::TextAttrsPartial(theme_attrs_partial) => &theme_attrs_partial.any_id,
+            Self::AttrConflict(theme_attr_conflict) => &theme_attr_conflict.any_id,
+            Self::AttrValueInvalid(theme_attr_value_invalid) => &theme_attr_value_invalid.any_id,
+        }
+    }
+
+    /// Returns what the styles are for -- the element itself, or when a tag is
+    /// focused.
+    pub fn style_for(&self) -> StyleFor<'_> {
+        match self {
+            Self::OutlineAttrsPartial(theme_attrs_partial)
+            | Self::StrokeAttrsPartial(theme_attrs_partial)
+            | Self::FillAttrsPartial(theme_attrs_partial)
+            | Self::TextAttrsPartial(theme_attrs_partial) => theme_attrs_partial.style_for(),
+            Self::AttrConflict(theme_attr_conflict) => theme_attr_conflict.style_for(),
+            Self::AttrValueInvalid(theme_attr_value_invalid) => {
+                theme_attr_value_invalid.style_for()
+            }
+        }
+    }
+
+    /// Returns the attributes that were not specified.
+    pub fn attrs_missing(&self) -> impl Iterator<Item = ThemeAttr> + '_ {
+        self.theme_attrs_partial()
+            .into_iter()
+            .flat_map(ThemeAttrsPartial::attrs_missing)
+    }
+
+    /// Returns the attributes that were given conflicting values.
+    pub fn attrs_conflicting(&self) -> impl Iterator<Item = ThemeAttr> + '_ {
+        match self {
+            Self::AttrConflict(theme_attr_conflict) => Some(theme_attr_conflict.theme_attr),
+            _ => None,
+        }
+        .into_iter()
+    }
+
+    /// Returns how severe this warning is.
+    pub fn severity(&self) -> ThemeWarningSeverity {
+        match self {
+            Self::OutlineAttrsPartial(_)
+            | Self::StrokeAttrsPartial(_)
+            | Self::FillAttrsPartial(_)
+            | Self::TextAttrsPartial(_)
+            | Self::AttrValueInvalid(_) => ThemeWarningSeverity::Warning,
+            Self::AttrConflict(_) => ThemeWarningSeverity::Info,
+        }
+    }
+
+    /// Returns the partially specified attributes, if this warning is for
+    /// partially specified attributes.
+    pub fn theme_attrs_partial(&self) -> Option<&ThemeAttrsPartial> {
+        match self {
+            Self::OutlineAttrsPartial(theme_attrs_partial)
+            | Self::StrokeAttrsPartial(theme_attrs_partial)
+            | Self::FillAttrsPartial(theme_attrs_partial)
+            | Self::TextAttrsPartial(theme_attrs_partial) => Some(theme_attrs_partial),
+            Self::AttrConflict(_) | Self::AttrValueInvalid(_) => None,
+        }
+    }
+
+    /// Returns a mutable reference to the partially specified attributes, if
+    /// this warning is for partially specified attributes.
+    pub fn theme_attrs_partial_mut(&mut self) -> Option<&mut ThemeAttrsPartial> {
+        match self {
+            Self::OutlineAttrsPartial(theme_attrs_partial)
+            | Self::StrokeAttrsPartial(theme_attrs_partial)
+            | Self::FillAttrsPartial(theme_attrs_partial)
+            | Self::TextAttrsPartial(theme_attrs_partial) => Some(theme_attrs_partial),
+            Self::AttrConflict(_) | Self::AttrValueInvalid(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ThemeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (attrs_name, attr_names): (&str, &[&str]) = match self {
+            Self::AttrConflict(theme_attr_conflict) => {
+                return theme_attr_conflict_fmt(f, theme_attr_conflict);
+            }
+            Self::AttrValueInvalid(ThemeAttrValueInvalid {
+                any_id: _,
+                tag_id: _,
+                theme_attr,
+                value,
+                reason,
+            }) => {
+                return write!(
+                    f,
+                    "`{theme_attr}` value `{value}` is invalid, \
+                    so {theme_attr} classes will not be applied. {reason}"
+                );
+            }
+            Self::OutlineAttrsPartial(_) => (
+                "Outline",
+                &[
+                    "outline_style",
+                    "outline_width",
+                    "outline_color",
+                    "outline_shade",
+                ],
+            ),
+            Self::StrokeAttrsPartial(_) => (
+                "Stroke",
+                &[
+                    "stroke_style",
+                    "stroke_width",
+                    "stroke_color",
+                    "stroke_shade",
+                ],
+            ),
+            Self::FillAttrsPartial(_) => ("Fill", &["fill_color", "fill_shade"]),
+            Self::TextAttrsPartial(_) => ("Text", &["text_color", "text_shade"]),
+        };
+        let Some(ThemeAttrsPartial {
+            any_id,
+            tag_id: _,
+            highlight_state: _,
+            attrs,
+            theme_selectors,
+        }) = self.theme_attrs_partial()
+        else {
+            return Ok(());
+        };
+
+        write!(
+            f,
+            "{attrs_name} attributes partially specified, \
+            so {attrs_name_lower} classes will not be applied. ",
+            attrs_name_lower = attrs_name.to_lowercase(),
+        )?;
+        attr_names
+            .iter()
+            .zip(attrs.iter())
+            .enumerate()
+            .try_for_each(|(index, (attr_name, (_theme_attr, value)))| {
+                let separator = if index == 0 { "" } else { ", " };
+                let value = value.as_deref().unwrap_or("<none>");
+                write!(f, "{separator}{attr_name}: `{value}`")
+            })?;
+        write!(f, ".")?;
+
+        if let Some((theme_selector_first, theme_selectors_remaining)) =
+            theme_selectors.split_first()
+        {
+            write!(
+                f,
+                " Selectors applied to `{any_id}`: `{theme_selector_first}`"
+            )?;
+            theme_selectors_remaining
+                .iter()
+                .try_for_each(|theme_selector| write!(f, ", `{theme_selector}`"))?;
+            write!(f, ".")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn theme_attr_conflict_fmt(
+    f: &mut fmt::Formatter<'_>,
+    theme_attr_conflict: &ThemeAttrConflict,
+) -> fmt::Result {
+    let ThemeAttrConflict {
+        any_id,
+        tag_id: _,
+        theme_attr,
+        values,
+    } = theme_attr_conflict;
+
+    write!(
+        f,
+        "`{theme_attr}` specified with different values by selectors with the same \
+        precedence for `{any_id}`, so only the last value is applied. "
+    )?;
+    values
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, (theme_selector, value))| {
+            let separator = if index == 0 { "" } else { ", " };
+            write!(f, "{separator}`{theme_selector}`: `{value}`")
+        })?;
+    write!(f, ".")
+}
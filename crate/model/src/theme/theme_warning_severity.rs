@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// How severe a [`ThemeWarning`] is.
+///
+/// [`ThemeWarning`]: crate::theme::ThemeWarning
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeWarningSeverity {
+    /// The theme is valid, but may not render as intended.
+    Info,
+    /// Some of the theme's styles will not be applied.
+    Warning,
+}
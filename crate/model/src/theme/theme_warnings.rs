@@ -3,11 +3,12 @@ use std::ops::{Deref, DerefMut};
 use indexmap::IndexSet;
 use serde::{Deserialize, Serialize};
 
-/// Warnings detected while computing CSS utility classes. `IndexSet<String>`
-/// newtype.
-// TODO: Store a list of error enum variants, rather than `String`s.
+use crate::theme::{ThemeWarning, ThemeWarningSeverity};
+
+/// Warnings detected while computing CSS utility classes.
+/// `IndexSet<ThemeWarning>` newtype.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
-pub struct ThemeWarnings(IndexSet<String>);
+pub struct ThemeWarnings(IndexSet<ThemeWarning>);
 
 impl ThemeWarnings {
     /// Returns a new `ThemeWarnings` list.
@@ -22,13 +23,23 @@ impl ThemeWarnings {
     }
 
     /// Returns the underlying list.
-    pub fn into_inner(self) -> IndexSet<String> {
+    pub fn into_inner(self) -> IndexSet<ThemeWarning> {
+        self.0
+    }
+
+    /// Returns the warnings with at least the given severity.
+    pub fn with_severity_min(
+        &self,
+        severity: ThemeWarningSeverity,
+    ) -> impl Iterator<Item = &ThemeWarning> + '_ {
         self.0
+            .iter()
+            .filter(move |theme_warning| theme_warning.severity() >= severity)
     }
 }
 
 impl Deref for ThemeWarnings {
-    type Target = IndexSet<String>;
+    type Target = IndexSet<ThemeWarning>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -41,14 +52,14 @@ impl DerefMut for ThemeWarnings {
     }
 }
 
-impl From<IndexSet<String>> for ThemeWarnings {
-    fn from(inner: IndexSet<String>) -> Self {
+impl From<IndexSet<ThemeWarning>> for ThemeWarnings {
+    fn from(inner: IndexSet<ThemeWarning>) -> Self {
         Self(inner)
     }
 }
 
-impl FromIterator<String> for ThemeWarnings {
-    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+impl FromIterator<ThemeWarning> for ThemeWarnings {
+    fn from_iter<I: IntoIterator<Item = ThemeWarning>>(iter: I) -> Self {
         Self(IndexSet::from_iter(iter))
     }
 }
@@ -1,6 +1,9 @@
 use crate::{
-    common::{AnyId, EdgeId, NodeId},
-    theme::{ColorParams, CssClassesBuilder, LineParams},
+    common::{graphviz_attrs::EdgeDir, AnyId, EdgeId, NodeId, TagId},
+    theme::{
+        ColorParams, CssClassesBuilder, EdgeFlowParams, FontParams, LineParams, OpacityParams,
+        ShapeEffectParams,
+    },
 };
 
 /// Types that can be rendered into a CSS compatible format, e.g. SVG or HTML
@@ -11,6 +14,63 @@ pub trait Themeable {
     where
         Self: Sized;
 
+    /// Returns the IDs of the given node's ancestors, from the outermost
+    /// ancestor to the node's parent.
+    ///
+    /// This is used to inherit styles from clusters when [`Theme::cascade`] is
+    /// `true`, and to match `depth:N` selectors. Defaults to returning no
+    /// ancestors.
+    ///
+    /// [`Theme::cascade`]: crate::theme::Theme::cascade
+    fn node_ancestor_ids(&self, _node_id: &NodeId) -> Vec<&NodeId>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    /// Returns whether the given node contains other nodes.
+    ///
+    /// This is used to match `cluster` and `leaf` selectors. Defaults to
+    /// `false`.
+    fn node_is_cluster(&self, _node_id: &NodeId) -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+
+    /// Returns the source and target node IDs of the given edge.
+    ///
+    /// This is used to match `edge:from=` and `edge:to=` selectors. Defaults to
+    /// `None`.
+    fn edge_node_ids(&self, _edge_id: &EdgeId) -> Option<(&NodeId, &NodeId)>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Returns the direction of the given edge's arrows.
+    ///
+    /// This is used to choose which way [`EdgeFlow::Flow`] animations move.
+    /// Defaults to [`EdgeDir::Forward`].
+    ///
+    /// [`EdgeFlow::Flow`]: crate::theme::EdgeFlow::Flow
+    fn edge_dir(&self, _edge_id: &AnyId) -> EdgeDir {
+        EdgeDir::Forward
+    }
+
+    /// Returns whether the given node or edge is associated with the tag.
+    ///
+    /// This is used to match `tag:` selectors. Defaults to `false`.
+    fn el_has_tag(&self, _el_id: &str, _tag_id: &TagId) -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+
     /// Appends the CSS classes that sets the outline colour and style.
     ///
     /// For example, if the `color_shade` is `"slate-600"`, and the
@@ -65,6 +125,62 @@ pub trait Themeable {
         color_params: ColorParams<'_>,
     );
 
+    /// Appends the CSS classes that sets the label text colour.
+    ///
+    /// For example, if the `color_shade` is `"slate-900"`, and the text
+    /// element is a `<text>` element within the one that has the CSS class,
+    /// then this should return `"[&>text]:fill-slate-900"`.
+    ///
+    /// # Parameters
+    ///
+    /// * `builder`: The builder to append CSS classes.
+    /// * `color_params`: Parameters for the CSS utility class.
+    fn node_text_color_classes(
+        &self,
+        node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        color_params: ColorParams<'_>,
+    );
+
+    /// Appends the CSS classes that sets the label text weight and size.
+    ///
+    /// # Parameters
+    ///
+    /// * `builder`: The builder to append CSS classes.
+    /// * `font_params`: Parameters for the CSS utility classes.
+    fn node_font_classes(
+        &self,
+        node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        font_params: FontParams<'_>,
+    );
+
+    /// Appends the CSS classes that sets the node's opacity.
+    ///
+    /// # Parameters
+    ///
+    /// * `builder`: The builder to append CSS classes.
+    /// * `opacity_params`: Parameters for the CSS utility class.
+    fn node_opacity_classes(
+        &self,
+        node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        opacity_params: OpacityParams<'_>,
+    );
+
+    /// Appends the CSS classes that sets the node's shadow and corner radius.
+    ///
+    /// # Parameters
+    ///
+    /// * `builder`: The builder to append CSS classes.
+    /// * `shape_effect_params`: Parameters for the CSS utility classes.
+    fn node_shape_effect_classes(
+        &self,
+        node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        shape_effect_params: ShapeEffectParams<'_>,
+    );
+
     /// Returns the IDs of all edges of this themeable type.
     fn edge_ids(&self) -> impl Iterator<Item = &EdgeId>
     where
@@ -123,4 +239,73 @@ pub trait Themeable {
         builder: &mut CssClassesBuilder,
         color_params: ColorParams<'_>,
     );
+
+    /// Appends the CSS classes that sets the label text colour.
+    ///
+    /// For example, if the `color_shade` is `"slate-900"`, and the text
+    /// element is a `<text>` element within the one that has the CSS class,
+    /// then this should return `"[&>text]:fill-slate-900"`.
+    ///
+    /// # Parameters
+    ///
+    /// * `builder`: The builder to append CSS classes.
+    /// * `color_params`: Parameters for the CSS utility class.
+    fn edge_text_color_classes(
+        &self,
+        edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        color_params: ColorParams<'_>,
+    );
+
+    /// Appends the CSS classes that sets the label text weight and size.
+    ///
+    /// # Parameters
+    ///
+    /// * `builder`: The builder to append CSS classes.
+    /// * `font_params`: Parameters for the CSS utility classes.
+    fn edge_font_classes(
+        &self,
+        edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        font_params: FontParams<'_>,
+    );
+
+    /// Appends the CSS classes that sets the edge's opacity.
+    ///
+    /// # Parameters
+    ///
+    /// * `builder`: The builder to append CSS classes.
+    /// * `opacity_params`: Parameters for the CSS utility class.
+    fn edge_opacity_classes(
+        &self,
+        edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        opacity_params: OpacityParams<'_>,
+    );
+
+    /// Appends the CSS classes that sets the edge's shadow and corner radius.
+    ///
+    /// # Parameters
+    ///
+    /// * `builder`: The builder to append CSS classes.
+    /// * `shape_effect_params`: Parameters for the CSS utility classes.
+    fn edge_shape_effect_classes(
+        &self,
+        edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        shape_effect_params: ShapeEffectParams<'_>,
+    );
+
+    /// Appends the CSS classes that animate data moving along the edge.
+    ///
+    /// # Parameters
+    ///
+    /// * `builder`: The builder to append CSS classes.
+    /// * `edge_flow_params`: Parameters for the CSS utility classes.
+    fn edge_flow_classes(
+        &self,
+        edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        edge_flow_params: EdgeFlowParams<'_>,
+    );
 }
@@ -0,0 +1,5 @@
+pub use self::diagnostic::Diagnostic;
+
+mod diagnostic;
+mod graphviz_error_explain;
+mod info_graph_check;
@@ -0,0 +1,387 @@
+use std::fmt;
+
+use dot_ix_model::{
+    common::{
+        dot_src_and_styles::{DotSrcMap, DotSrcMapEntry},
+        AnyId, EdgeId, NodeId,
+    },
+    info_graph::InfoGraph,
+};
+
+use crate::{
+    diagnostics::{graphviz_error_explain, info_graph_check},
+    RenderError,
+};
+
+/// A pattern in an `InfoGraph` that Graphviz is known to render badly, or an
+/// explanation of a Graphviz error.
+///
+/// The [`Display`] implementation explains the problem, and
+/// [`Diagnostic::suggestion`] describes how to fix it.
+///
+/// [`Display`]: std::fmt::Display
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// An edge connects to a cluster whose label is short.
+    ///
+    /// Graphviz 2.43 fails with `makeCompoundEdge: Assertion 'bez->sflag'
+    /// failed` for some of these edges.
+    ClusterLabelShort {
+        /// ID of the edge.
+        edge_id: EdgeId,
+        /// ID of the cluster.
+        cluster_node_id: NodeId,
+        /// The cluster's label -- its name, or its ID if it has no name.
+        label: String,
+    },
+    /// Graphviz failed with `makeCompoundEdge: Assertion 'bez->sflag'
+    /// failed`.
+    CompoundEdgeAssertion,
+    /// An edge connects to a cluster at a compass point.
+    ///
+    /// The `headport` / `tailport` is set together with `lhead` / `ltail`,
+    /// which Graphviz may not draw with spline curves.
+    ClusterCompassPoint {
+        /// ID of the edge.
+        edge_id: EdgeId,
+        /// ID of the cluster.
+        cluster_node_id: NodeId,
+        /// The compass point, e.g. `n`.
+        compass_point: String,
+    },
+    /// An edge connects to a node at a compass point that Graphviz doesn't
+    /// recognize.
+    CompassPointInvalid {
+        /// ID of the edge, if known.
+        edge_id: Option<EdgeId>,
+        /// ID of the node.
+        node_id: String,
+        /// The compass point.
+        compass_point: String,
+    },
+    /// An edge connects to a node that is not in the `hierarchy`.
+    ///
+    /// Graphviz renders these as plain nodes outside of any cluster.
+    EdgeNodeUnknown {
+        /// ID of the edge.
+        edge_id: EdgeId,
+        /// ID of the node.
+        node_id: String,
+    },
+    /// An edge connects a cluster to a node within it.
+    ///
+    /// Graphviz ignores the `lhead` / `ltail`, so the edge is drawn to the
+    /// innermost node instead of the cluster border.
+    EdgeWithinCluster {
+        /// ID of the edge, if known.
+        edge_id: Option<EdgeId>,
+        /// ID of the cluster.
+        cluster_node_id: String,
+        /// ID of the node within the cluster.
+        node_id: String,
+    },
+    /// `splines` is `ortho`, and edges have descriptions.
+    ///
+    /// Graphviz doesn't draw labels on orthogonal edges.
+    OrthoEdgeDescs {
+        /// IDs of the edges with descriptions.
+        edge_ids: Vec<EdgeId>,
+    },
+    /// Graphviz could not parse the generated dot source.
+    SyntaxError {
+        /// The error message from Graphviz.
+        message: String,
+        /// The element that the line with the error was generated for, if a
+        /// source map is available.
+        dot_src_map_entry: Option<DotSrcMapEntry>,
+    },
+    /// Graphviz failed to rank nodes, usually because of edges between
+    /// nested clusters.
+    InitRank {
+        /// The error message from Graphviz.
+        message: String,
+    },
+    /// Graphviz ran out of memory.
+    OutOfMemory {
+        /// The error message from Graphviz.
+        message: String,
+    },
+    /// An error from Graphviz that isn't otherwise explained.
+    GraphvizError {
+        /// The error message from Graphviz.
+        message: String,
+        /// The element that the line with the error was generated for, if the
+        /// message has a line number and a source map is available.
+        dot_src_map_entry: Option<DotSrcMapEntry>,
+    },
+}
+
+impl Diagnostic {
+    /// Returns diagnostics for patterns in the `InfoGraph` that Graphviz is
+    /// known to render badly, or fail on.
+    ///
+    /// This should be called before rendering, so that users are told why a
+    /// diagram may look wrong.
+    pub fn info_graph_check(info_graph: &InfoGraph) -> Vec<Diagnostic> {
+        info_graph_check::diagnostics(info_graph)
+    }
+
+    /// Returns explanations for the error text from Graphviz, e.g. from
+    /// `graphviz_dot_svg` or `dot`'s `stderr`.
+    ///
+    /// Errors that are not known are returned as
+    /// [`Diagnostic::GraphvizError`]. Warnings that are not known are skipped.
+    ///
+    /// If `dot_src_map` is provided, errors with a line number are traced to the
+    /// node, edge, or tag that the line was generated for.
+    pub fn graphviz_error_explain(
+        graphviz_error: &str,
+        dot_src_map: Option<&DotSrcMap>,
+    ) -> Vec<Diagnostic> {
+        graphviz_error_explain::diagnostics(graphviz_error, dot_src_map)
+    }
+
+    /// Returns explanations for a [`RenderError`] from a [`DotRenderer`].
+    ///
+    /// Errors that aren't from Graphviz itself, such as failing to spawn
+    /// `dot`, are already descriptive, and return no diagnostics.
+    ///
+    /// [`DotRenderer`]: crate::DotRenderer
+    pub fn render_error_explain(
+        render_error: &RenderError,
+        dot_src_map: Option<&DotSrcMap>,
+    ) -> Vec<Diagnostic> {
+        match render_error {
+            RenderError::DotFailed {
+                exit_code: _,
+                dot_diagnostics,
+            } => graphviz_error_explain::dot_diagnostics_explain(dot_diagnostics, dot_src_map),
+            #[cfg(feature = "graphviz_wasm")]
+            RenderError::GraphvizWasmTrap { error: _, stderr } => {
+                graphviz_error_explain::diagnostics(stderr, dot_src_map)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the ID of the node, edge, or tag that this diagnostic is for,
+    /// if known.
+    pub fn any_id(&self) -> Option<AnyId> {
+        match self {
+            Self::ClusterLabelShort { edge_id, .. }
+            | Self::ClusterCompassPoint { edge_id, .. }
+            | Self::EdgeNodeUnknown { edge_id, .. } => Some(AnyId::from(edge_id.clone())),
+            Self::CompassPointInvalid { edge_id, .. } | Self::EdgeWithinCluster { edge_id, .. } => {
+                edge_id.clone().map(AnyId::from)
+            }
+            Self::OrthoEdgeDescs { edge_ids } => edge_ids.first().cloned().map(AnyId::from),
+            Self::SyntaxError {
+                dot_src_map_entry, ..
+            }
+            | Self::GraphvizError {
+                dot_src_map_entry, ..
+            } => dot_src_map_entry
+                .as_ref()
+                .map(|dot_src_map_entry| dot_src_map_entry.any_id().clone()),
+            Self::CompoundEdgeAssertion | Self::InitRank { .. } | Self::OutOfMemory { .. } => None,
+        }
+    }
+
+    /// Returns how to fix or work around the problem.
+    pub fn suggestion(&self) -> String {
+        match self {
+            Self::ClusterLabelShort {
+                cluster_node_id, ..
+            } => format!(
+                "If rendering fails with Graphviz 2.43, give `{cluster_node_id}` a longer name \
+                in `node_names`, or upgrade Graphviz to 8.1.0 or later."
+            ),
+            Self::CompoundEdgeAssertion => String::from(
+                "Upgrade Graphviz to 8.1.0 or later. Many package managers ship Graphviz 2.43, \
+                which has this bug. Otherwise, give clusters that have edges longer names in \
+                `node_names`.",
+            ),
+            Self::ClusterCompassPoint {
+                edge_id,
+                cluster_node_id,
+                compass_point,
+            } => format!(
+                "Remove `:{compass_point}` from `{cluster_node_id}` in edge `{edge_id}` if the \
+                edge should be curved."
+            ),
+            Self::CompassPointInvalid { .. } => String::from(
+                "Use one of the compass points `n`, `ne`, `e`, `se`, `s`, `sw`, `w`, `nw`, `c`, \
+                or `_`, e.g. `a:n`.",
+            ),
+            Self::EdgeNodeUnknown { node_id, .. } => format!(
+                "Add `{node_id}` to `hierarchy`, or fix the spelling of the node ID in the edge."
+            ),
+            Self::EdgeWithinCluster {
+                cluster_node_id, ..
+            } => format!(
+                "Connect the edge to a node outside of `{cluster_node_id}`, or to a node within \
+                it instead of the cluster."
+            ),
+            Self::OrthoEdgeDescs { .. } => String::from(
+                "Set `graphviz_attrs.splines` to `polyline` or `spline`, or remove the \
+                `edge_descs`.",
+            ),
+            Self::SyntaxError {
+                dot_src_map_entry, ..
+            } => match dot_src_map_entry {
+                Some(dot_src_map_entry) => format!(
+                    "Check the name and description of `{}` at `{}` for `<`, `>`, or `&`, which \
+                    need to be written as `&lt;`, `&gt;`, and `&amp;`.",
+                    dot_src_map_entry.any_id(),
+                    dot_src_map_entry.doc_path()
+                ),
+                None => String::from(
+                    "Check node names, node descriptions, and edge descriptions for `<`, `>`, \
+                    or `&`, which need to be written as `&lt;`, `&gt;`, and `&amp;`.",
+                ),
+            },
+            Self::InitRank { .. } => String::from(
+                "Remove edges between a cluster and nodes in other clusters one at a time to \
+                find the edge that Graphviz can't rank, or set `edge_constraints` to `false` for \
+                it.",
+            ),
+            Self::OutOfMemory { .. } => String::from(
+                "Reduce the number of nodes and edges, or render the diagram in parts.",
+            ),
+            Self::GraphvizError {
+                dot_src_map_entry, ..
+            } => match dot_src_map_entry {
+                Some(dot_src_map_entry) => format!(
+                    "Check `{}` at `{}`. If it looks correct, try upgrading Graphviz.",
+                    dot_src_map_entry.any_id(),
+                    dot_src_map_entry.doc_path()
+                ),
+                None => String::from(
+                    "If the `InfoGraph` looks correct, try upgrading Graphviz to 8.1.0 or later.",
+                ),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClusterLabelShort {
+                edge_id,
+                cluster_node_id,
+                label,
+            } => write!(
+                f,
+                "Edge `{edge_id}` connects to cluster `{cluster_node_id}`, whose label `{label}` \
+                is short. Graphviz 2.43 may fail with `makeCompoundEdge: Assertion \
+                'bez->sflag' failed` for this edge."
+            ),
+            Self::CompoundEdgeAssertion => write!(
+                f,
+                "Graphviz crashed while drawing an edge to a cluster. This is a Graphviz bug \
+                that happens when cluster labels are short."
+            ),
+            Self::ClusterCompassPoint {
+                edge_id,
+                cluster_node_id,
+                compass_point,
+            } => write!(
+                f,
+                "Edge `{edge_id}` connects to cluster `{cluster_node_id}` at `{compass_point}`. \
+                Graphviz may draw this edge without spline curves."
+            ),
+            Self::CompassPointInvalid {
+                edge_id,
+                node_id,
+                compass_point,
+            } => match edge_id {
+                Some(edge_id) => write!(
+                    f,
+                    "Edge `{edge_id}` connects to `{node_id}` at `{compass_point}`, which is not \
+                    a compass point. Graphviz connects the edge to the node's center instead."
+                ),
+                None => write!(
+                    f,
+                    "An edge connects to `{node_id}` at `{compass_point}`, which is not a \
+                    compass point. Graphviz connects the edge to the node's center instead."
+                ),
+            },
+            Self::EdgeNodeUnknown { edge_id, node_id } => write!(
+                f,
+                "Edge `{edge_id}` connects to `{node_id}`, which is not in the `hierarchy`. \
+                Graphviz draws it as a separate unstyled node."
+            ),
+            Self::EdgeWithinCluster {
+                edge_id,
+                cluster_node_id,
+                node_id,
+            } => match edge_id {
+                Some(edge_id) => write!(
+                    f,
+                    "Edge `{edge_id}` connects cluster `{cluster_node_id}` to `{node_id}`, which \
+                    is inside it. Graphviz draws the edge to a node inside the cluster instead of \
+                    its border."
+                ),
+                None => write!(
+                    f,
+                    "An edge connects cluster `{cluster_node_id}` to `{node_id}`, which is inside \
+                    it. Graphviz draws the edge to a node inside the cluster instead of its \
+                    border."
+                ),
+            },
+            Self::OrthoEdgeDescs { edge_ids } => {
+                let edge_ids = edge_ids
+                    .iter()
+                    .map(|edge_id| format!("`{edge_id}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "`splines` is `ortho`, which Graphviz draws without labels, so the \
+                    descriptions of edges {edge_ids} are not shown."
+                )
+            }
+            Self::SyntaxError {
+                message,
+                dot_src_map_entry,
+            } => {
+                write!(
+                    f,
+                    "Graphviz could not read the generated dot source: {message}"
+                )?;
+                dot_src_map_entry_write(f, dot_src_map_entry.as_ref())
+            }
+            Self::InitRank { message } => write!(
+                f,
+                "Graphviz could not work out which row or column to place nodes in: {message}"
+            ),
+            Self::OutOfMemory { message } => write!(
+                f,
+                "Graphviz ran out of memory while rendering the diagram: {message}"
+            ),
+            Self::GraphvizError {
+                message,
+                dot_src_map_entry,
+            } => {
+                write!(f, "Graphviz failed to render the diagram: {message}")?;
+                dot_src_map_entry_write(f, dot_src_map_entry.as_ref())
+            }
+        }
+    }
+}
+
+fn dot_src_map_entry_write(
+    f: &mut fmt::Formatter<'_>,
+    dot_src_map_entry: Option<&DotSrcMapEntry>,
+) -> fmt::Result {
+    if let Some(dot_src_map_entry) = dot_src_map_entry {
+        write!(
+            f,
+            " (generated for `{}` at `{}`)",
+            dot_src_map_entry.any_id(),
+            dot_src_map_entry.doc_path()
+        )?;
+    }
+    Ok(())
+}
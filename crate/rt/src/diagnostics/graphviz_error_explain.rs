@@ -0,0 +1,142 @@
+use dot_ix_model::common::dot_src_and_styles::DotSrcMap;
+
+use crate::{Diagnostic, DotDiagnostic, DotDiagnosticSeverity};
+
+/// Returns explanations for the error text from Graphviz.
+pub(crate) fn diagnostics(
+    graphviz_error: &str,
+    dot_src_map: Option<&DotSrcMap>,
+) -> Vec<Diagnostic> {
+    let dot_diagnostics = DotDiagnostic::parse_stderr(graphviz_error);
+    dot_diagnostics_explain(&dot_diagnostics, dot_src_map)
+}
+
+/// Returns explanations for the errors and warnings parsed from Graphviz's
+/// output.
+pub(crate) fn dot_diagnostics_explain(
+    dot_diagnostics: &[DotDiagnostic],
+    dot_src_map: Option<&DotSrcMap>,
+) -> Vec<Diagnostic> {
+    dot_diagnostics.iter().fold(
+        Vec::<Diagnostic>::with_capacity(dot_diagnostics.len()),
+        |mut diagnostics, dot_diagnostic| {
+            let message = dot_diagnostic.message.as_str();
+
+            // Assertion failures are not prefixed with `Error: `, so they may be
+            // joined to the previous message.
+            let compound_edge_assertion = message
+                .lines()
+                .any(compound_edge_assertion_matches)
+                .then_some(Diagnostic::CompoundEdgeAssertion);
+            let diagnostic = dot_diagnostic_explain(dot_diagnostic, dot_src_map);
+
+            diagnostic
+                .into_iter()
+                .chain(compound_edge_assertion)
+                .for_each(|diagnostic| {
+                    // The assertion may be reported once per edge.
+                    if !diagnostics.contains(&diagnostic) {
+                        diagnostics.push(diagnostic);
+                    }
+                });
+
+            diagnostics
+        },
+    )
+}
+
+fn compound_edge_assertion_matches(message_line: &str) -> bool {
+    message_line.contains("makeCompoundEdge") || message_line.contains("bez->sflag")
+}
+
+/// Returns the explanation for the message, excluding the compound edge
+/// assertion.
+fn dot_diagnostic_explain(
+    dot_diagnostic: &DotDiagnostic,
+    dot_src_map: Option<&DotSrcMap>,
+) -> Option<Diagnostic> {
+    let DotDiagnostic {
+        severity,
+        line,
+        message,
+    } = dot_diagnostic;
+    let message_first_line = message.lines().next().unwrap_or_default();
+    let dot_src_map_entry = || {
+        dot_src_map
+            .zip(*line)
+            .and_then(|(dot_src_map, line)| dot_src_map.entry_at_line(line))
+            .cloned()
+    };
+
+    if compound_edge_assertion_matches(message_first_line) {
+        return None;
+    }
+    if message_first_line.contains("syntax error") {
+        return Some(Diagnostic::SyntaxError {
+            message: message.clone(),
+            dot_src_map_entry: dot_src_map_entry(),
+        });
+    }
+    if message_first_line.contains("trouble in init_rank") {
+        return Some(Diagnostic::InitRank {
+            message: message.clone(),
+        });
+    }
+    let message_lowercase = message_first_line.to_lowercase();
+    if message_lowercase.contains("out of memory")
+        || message_lowercase.contains("cannot enlarge memory")
+    {
+        return Some(Diagnostic::OutOfMemory {
+            message: message.clone(),
+        });
+    }
+    if let Some(diagnostic) = port_unrecognized_explain(message_first_line) {
+        return Some(diagnostic);
+    }
+    if let Some(diagnostic) = inside_cluster_explain(message_first_line) {
+        return Some(diagnostic);
+    }
+
+    match severity {
+        DotDiagnosticSeverity::Error => Some(Diagnostic::GraphvizError {
+            message: message.clone(),
+            dot_src_map_entry: dot_src_map_entry(),
+        }),
+        DotDiagnosticSeverity::Warning => None,
+    }
+}
+
+/// Explains `node a, port x unrecognized`.
+fn port_unrecognized_explain(message: &str) -> Option<Diagnostic> {
+    let node_and_port = message
+        .strip_prefix("node ")?
+        .strip_suffix(" unrecognized")?;
+    let (node_id, compass_point) = node_and_port.split_once(", port ")?;
+
+    Some(Diagnostic::CompassPointInvalid {
+        edge_id: None,
+        node_id: node_id.to_string(),
+        compass_point: compass_point.to_string(),
+    })
+}
+
+/// Explains `a0 -> a: tail is inside head cluster cluster_a`, and the `head`
+/// variant.
+fn inside_cluster_explain(message: &str) -> Option<Diagnostic> {
+    let (edge, reason) = message.split_once(": ")?;
+    let (tail, head) = edge.split_once(" -> ")?;
+    let (node_id, cluster) =
+        if let Some(cluster) = reason.strip_prefix("tail is inside head cluster ") {
+            (tail, cluster)
+        } else {
+            let cluster = reason.strip_prefix("head is inside tail cluster ")?;
+            (head, cluster)
+        };
+    let cluster_node_id = cluster.strip_prefix("cluster_").unwrap_or(cluster);
+
+    Some(Diagnostic::EdgeWithinCluster {
+        edge_id: None,
+        cluster_node_id: cluster_node_id.to_string(),
+        node_id: node_id.to_string(),
+    })
+}
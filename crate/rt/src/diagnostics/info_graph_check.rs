@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use dot_ix_model::{
+    common::{graphviz_attrs::Splines, EdgeId, NodeHierarchy, NodeId},
+    info_graph::InfoGraph,
+};
+
+use crate::Diagnostic;
+
+/// Cluster labels with fewer characters than this are reported as
+/// [`Diagnostic::ClusterLabelShort`].
+///
+/// This is a heuristic -- Graphviz 2.43 fails depending on the label's width
+/// relative to the edges drawn to the cluster.
+const CLUSTER_LABEL_LEN_MIN: usize = 4;
+
+/// Compass points that Graphviz recognizes as edge ports.
+const COMPASS_POINTS: [&str; 10] = ["n", "ne", "e", "se", "s", "sw", "w", "nw", "c", "_"];
+
+/// Returns diagnostics for patterns in the `InfoGraph` that Graphviz is known
+/// to render badly, or fail on.
+pub(crate) fn diagnostics(info_graph: &InfoGraph) -> Vec<Diagnostic> {
+    let node_id_to_hierarchy = info_graph.hierarchy_flat();
+    let node_id_to_parent = info_graph.node_parents();
+
+    let mut diagnostics = info_graph.edges().iter().fold(
+        Vec::<Diagnostic>::new(),
+        |mut diagnostics, (edge_id, [src_node_id, target_node_id])| {
+            let [src, target] = [src_node_id, target_node_id].map(|node_id| {
+                edge_end_diagnose(
+                    &mut diagnostics,
+                    info_graph,
+                    &node_id_to_hierarchy,
+                    edge_id,
+                    node_id,
+                )
+            });
+
+            if let Some((src, target)) = src.zip(target) {
+                edge_within_cluster_diagnose(
+                    &mut diagnostics,
+                    &node_id_to_parent,
+                    edge_id,
+                    src,
+                    target,
+                );
+            }
+
+            diagnostics
+        },
+    );
+
+    if info_graph.graphviz_attrs().splines() == Splines::Ortho {
+        let edge_ids = info_graph
+            .edges()
+            .keys()
+            .filter(|edge_id| info_graph.edge_descs().contains_key(*edge_id))
+            .cloned()
+            .collect::<Vec<EdgeId>>();
+        if !edge_ids.is_empty() {
+            diagnostics.push(Diagnostic::OrthoEdgeDescs { edge_ids });
+        }
+    }
+
+    diagnostics
+}
+
+/// An end of an edge that connects to a node in the `hierarchy`.
+#[derive(Clone, Copy)]
+struct EdgeEnd<'graph> {
+    node_id: &'graph NodeId,
+    is_cluster: bool,
+}
+
+/// Pushes diagnostics for one end of an edge, and returns the node that it
+/// connects to, if the node is in the `hierarchy`.
+fn edge_end_diagnose<'graph>(
+    diagnostics: &mut Vec<Diagnostic>,
+    info_graph: &'graph InfoGraph,
+    node_id_to_hierarchy: &HashMap<&'graph NodeId, &'graph NodeHierarchy>,
+    edge_id: &EdgeId,
+    node_id_with_port: &NodeId,
+) -> Option<EdgeEnd<'graph>> {
+    let (node_id_plain, compass_point) = match node_id_with_port.split_once(':') {
+        Some((node_id_plain, compass_point)) => (node_id_plain, Some(compass_point)),
+        None => (node_id_with_port.as_str(), None),
+    };
+
+    let Some((node_id, node_hierarchy)) = node_id_to_hierarchy.get_key_value(node_id_plain) else {
+        diagnostics.push(Diagnostic::EdgeNodeUnknown {
+            edge_id: edge_id.clone(),
+            node_id: node_id_plain.to_string(),
+        });
+        return None;
+    };
+    let is_cluster = !node_hierarchy.is_empty();
+
+    if let Some(compass_point) = compass_point {
+        if !COMPASS_POINTS.contains(&compass_point) {
+            diagnostics.push(Diagnostic::CompassPointInvalid {
+                edge_id: Some(edge_id.clone()),
+                node_id: node_id_plain.to_string(),
+                compass_point: compass_point.to_string(),
+            });
+        } else if is_cluster {
+            diagnostics.push(Diagnostic::ClusterCompassPoint {
+                edge_id: edge_id.clone(),
+                cluster_node_id: (*node_id).clone(),
+                compass_point: compass_point.to_string(),
+            });
+        }
+    }
+
+    if is_cluster {
+        let label = info_graph
+            .node_names()
+            .get(*node_id)
+            .map(String::as_str)
+            .unwrap_or(node_id.as_str());
+        if label.chars().count() < CLUSTER_LABEL_LEN_MIN {
+            diagnostics.push(Diagnostic::ClusterLabelShort {
+                edge_id: edge_id.clone(),
+                cluster_node_id: (*node_id).clone(),
+                label: label.to_string(),
+            });
+        }
+    }
+
+    Some(EdgeEnd {
+        node_id,
+        is_cluster,
+    })
+}
+
+/// Pushes a diagnostic if one end of the edge is a cluster that contains the
+/// other end.
+fn edge_within_cluster_diagnose(
+    diagnostics: &mut Vec<Diagnostic>,
+    node_id_to_parent: &HashMap<&NodeId, &NodeId>,
+    edge_id: &EdgeId,
+    src: EdgeEnd<'_>,
+    target: EdgeEnd<'_>,
+) {
+    let cluster_and_node = [(src, target), (target, src)]
+        .into_iter()
+        .find(|(cluster, node)| {
+            cluster.is_cluster && node_is_within(node_id_to_parent, node.node_id, cluster.node_id)
+        });
+
+    if let Some((cluster, node)) = cluster_and_node {
+        diagnostics.push(Diagnostic::EdgeWithinCluster {
+            edge_id: Some(edge_id.clone()),
+            cluster_node_id: cluster.node_id.to_string(),
+            node_id: node.node_id.to_string(),
+        });
+    }
+}
+
+/// Returns whether `node_id` is a descendant of `ancestor_node_id`.
+fn node_is_within(
+    node_id_to_parent: &HashMap<&NodeId, &NodeId>,
+    node_id: &NodeId,
+    ancestor_node_id: &NodeId,
+) -> bool {
+    let mut node_id_current = node_id;
+    while let Some(parent_node_id) = node_id_to_parent.get(node_id_current) {
+        if *parent_node_id == ancestor_node_id {
+            return true;
+        }
+        node_id_current = parent_node_id;
+    }
+    false
+}
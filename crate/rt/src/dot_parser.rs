@@ -0,0 +1,343 @@
+pub use self::{
+    dot_parse_error::DotParseError, dot_parse_output::DotParseOutput,
+    dot_parse_warning::DotParseWarning,
+};
+
+use self::{
+    dot_lexer::{DotToken, DotTokenKind},
+    info_graph_builder::{DotAttr, DotOperand, DotValue, InfoGraphBuilder},
+};
+
+mod dot_lexer;
+mod dot_parse_error;
+mod dot_parse_output;
+mod dot_parse_warning;
+mod info_graph_builder;
+
+/// Parses GraphViz dot source into an [`InfoGraph`], e.g. to migrate existing
+/// `.dot` files.
+///
+/// * `subgraph cluster_*` becomes the node hierarchy, with the `cluster_`
+///   prefix removed from the ID. Other subgraphs are flattened.
+/// * Node and cluster `label`s become `node_names`. Text after the first line
+///   break becomes `node_descs`.
+/// * Edges get an `EdgeId` of `{src}__{target}`, or their `id` attribute if it
+///   is valid. Edge `label`s become `edge_descs`, and `lhead` / `ltail`
+///   connect the edge to the cluster.
+/// * `rankdir`, `nodesep`, `ranksep`, `splines`, `constraint`, `minlen`,
+///   `dir`, `width`, `height`, and `margin` become [`GraphvizAttrs`].
+///   `node [..]` and `edge [..]` at the top level become the `*_default`
+///   values.
+///
+/// IDs that are not valid `NodeId`s are renamed, and attributes that are not
+/// supported are skipped. Both are reported as [`DotParseWarning`]s.
+///
+/// The imported `edge_minlen_default` is `1`, which is Graphviz's default, so
+/// that ranks are spaced the same as the original graph.
+///
+/// [`InfoGraph`]: dot_ix_model::info_graph::InfoGraph
+/// [`GraphvizAttrs`]: dot_ix_model::common::GraphvizAttrs
+#[derive(Clone, Copy, Debug)]
+pub struct DotParser;
+
+impl DotParser {
+    /// Parses a `digraph` or `graph` into an `InfoGraph`.
+    pub fn parse(dot_src: &str) -> Result<DotParseOutput, DotParseError> {
+        let dot_tokens = dot_lexer::tokenize(dot_src)?;
+        let mut dot_token_parser = DotTokenParser {
+            dot_tokens: &dot_tokens,
+            index: 0,
+            info_graph_builder: InfoGraphBuilder::new(),
+        };
+        dot_token_parser.graph_parse()?;
+
+        Ok(dot_token_parser.info_graph_builder.build())
+    }
+}
+
+/// Recursive descent parser over the tokens, following the [DOT language]
+/// grammar.
+///
+/// [DOT language]: https://graphviz.org/doc/info/lang.html
+struct DotTokenParser<'tokens> {
+    dot_tokens: &'tokens [DotToken],
+    index: usize,
+    info_graph_builder: InfoGraphBuilder,
+}
+
+impl DotTokenParser<'_> {
+    fn peek(&self) -> Option<&DotToken> {
+        self.dot_tokens.get(self.index)
+    }
+
+    fn peek_kind(&self) -> Option<&DotTokenKind> {
+        self.peek().map(|dot_token| &dot_token.kind)
+    }
+
+    fn peek_second_kind(&self) -> Option<&DotTokenKind> {
+        self.dot_tokens
+            .get(self.index + 1)
+            .map(|dot_token| &dot_token.kind)
+    }
+
+    fn next(&mut self, expected: &'static str) -> Result<&DotToken, DotParseError> {
+        let dot_token = self
+            .dot_tokens
+            .get(self.index)
+            .ok_or(DotParseError::UnexpectedEnd { expected })?;
+        self.index += 1;
+        Ok(dot_token)
+    }
+
+    fn expect(&mut self, kind: DotTokenKind, expected: &'static str) -> Result<(), DotParseError> {
+        let dot_token = self.next(expected)?;
+        if dot_token.kind == kind {
+            Ok(())
+        } else {
+            Err(unexpected(dot_token, expected))
+        }
+    }
+
+    /// Returns whether the next token is the given keyword, ignoring case.
+    fn peek_is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek_kind(), Some(DotTokenKind::Id(id)) if id.eq_ignore_ascii_case(keyword))
+    }
+
+    fn graph_parse(&mut self) -> Result<(), DotParseError> {
+        let strict = self.peek_is_keyword("strict");
+        if strict {
+            self.index += 1;
+        }
+
+        let dot_token = self.next("`digraph` or `graph`")?;
+        let directed = match &dot_token.kind {
+            DotTokenKind::Id(id) if id.eq_ignore_ascii_case("digraph") => true,
+            DotTokenKind::Id(id) if id.eq_ignore_ascii_case("graph") => false,
+            _ => return Err(unexpected(dot_token, "`digraph` or `graph`")),
+        };
+
+        let graph_name = match self.peek_kind() {
+            Some(DotTokenKind::BraceOpen) => String::new(),
+            _ => self.id_parse("a graph name or `{`")?.text,
+        };
+        self.info_graph_builder
+            .graph_begin(directed, strict, graph_name);
+
+        self.expect(DotTokenKind::BraceOpen, "`{`")?;
+        self.stmt_list_parse()?;
+        self.expect(DotTokenKind::BraceClose, "`}`")?;
+
+        match self.peek() {
+            None => Ok(()),
+            Some(dot_token)
+                if self.peek_is_keyword("digraph")
+                    || self.peek_is_keyword("graph")
+                    || self.peek_is_keyword("strict") =>
+            {
+                Err(DotParseError::GraphMultiple {
+                    line: dot_token.line,
+                })
+            }
+            Some(dot_token) => Err(unexpected(dot_token, "the end of the dot source")),
+        }
+    }
+
+    fn stmt_list_parse(&mut self) -> Result<(), DotParseError> {
+        loop {
+            match self.peek_kind() {
+                Some(DotTokenKind::BraceClose) | None => return Ok(()),
+                Some(DotTokenKind::Semicolon) => self.index += 1,
+                Some(_) => self.stmt_parse()?,
+            }
+        }
+    }
+
+    fn stmt_parse(&mut self) -> Result<(), DotParseError> {
+        let is_attr_stmt = matches!(self.peek_second_kind(), Some(DotTokenKind::BracketOpen));
+        if is_attr_stmt && self.peek_is_keyword("graph") {
+            self.index += 1;
+            let dot_attrs = self.attr_list_parse()?;
+            self.info_graph_builder.graph_attrs_apply(dot_attrs);
+            return Ok(());
+        }
+        if is_attr_stmt && self.peek_is_keyword("node") {
+            self.index += 1;
+            let dot_attrs = self.attr_list_parse()?;
+            self.info_graph_builder.node_defaults_apply(dot_attrs);
+            return Ok(());
+        }
+        if is_attr_stmt && self.peek_is_keyword("edge") {
+            self.index += 1;
+            let dot_attrs = self.attr_list_parse()?;
+            self.info_graph_builder.edge_defaults_apply(dot_attrs);
+            return Ok(());
+        }
+
+        // `ID = ID`
+        let is_id = matches!(
+            self.peek_kind(),
+            Some(DotTokenKind::Id(_) | DotTokenKind::IdQuoted(_) | DotTokenKind::IdHtml(_))
+        );
+        if is_id
+            && !self.peek_is_keyword("subgraph")
+            && matches!(self.peek_second_kind(), Some(DotTokenKind::Equals))
+        {
+            let dot_attr = self.attr_parse()?;
+            self.info_graph_builder.graph_attrs_apply(vec![dot_attr]);
+            return Ok(());
+        }
+
+        let dot_operand = self.operand_parse()?;
+        if matches!(self.peek_kind(), Some(DotTokenKind::EdgeOp { .. })) {
+            let mut dot_operands = vec![dot_operand];
+            while let Some(DotTokenKind::EdgeOp { directed }) = self.peek_kind() {
+                let directed = *directed;
+                let dot_token = &self.dot_tokens[self.index];
+                if directed != self.info_graph_builder.directed() {
+                    let expected = if self.info_graph_builder.directed() {
+                        "`->` in a `digraph`"
+                    } else {
+                        "`--` in a `graph`"
+                    };
+                    return Err(unexpected(dot_token, expected));
+                }
+                self.index += 1;
+                dot_operands.push(self.operand_parse()?);
+            }
+            let dot_attrs = self.attr_list_parse_optional()?;
+            self.info_graph_builder
+                .edge_stmt_apply(dot_operands, dot_attrs);
+        } else {
+            let dot_attrs = self.attr_list_parse_optional()?;
+            match dot_operand {
+                DotOperand::Node { dot_id, port, line } => {
+                    self.info_graph_builder
+                        .node_stmt_apply(dot_id, port, dot_attrs, line);
+                }
+                DotOperand::Subgraph { .. } => {
+                    // Attributes after a subgraph are not valid DOT.
+                    if let Some(dot_attr) = dot_attrs.first() {
+                        return Err(DotParseError::Syntax {
+                            line: dot_attr.line,
+                            column: 1,
+                            message: String::from(
+                                "Attributes cannot be applied to a subgraph statement.",
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses a node ID with an optional port, or a subgraph.
+    fn operand_parse(&mut self) -> Result<DotOperand, DotParseError> {
+        if self.peek_is_keyword("subgraph")
+            || matches!(self.peek_kind(), Some(DotTokenKind::BraceOpen))
+        {
+            return self.subgraph_parse();
+        }
+
+        let dot_value = self.id_parse("a node ID, `subgraph`, or `{`")?;
+        let line = dot_value.line;
+        let mut port = None::<String>;
+        while matches!(self.peek_kind(), Some(DotTokenKind::Colon)) {
+            self.index += 1;
+            let port_part = self.id_parse("a port or compass point")?.text;
+            port = Some(match port {
+                Some(port) => format!("{port}:{port_part}"),
+                None => port_part,
+            });
+        }
+
+        Ok(DotOperand::Node {
+            dot_id: dot_value.text,
+            port,
+            line,
+        })
+    }
+
+    fn subgraph_parse(&mut self) -> Result<DotOperand, DotParseError> {
+        let line = self.peek().map(|dot_token| dot_token.line).unwrap_or(0);
+        let subgraph_dot_id = if self.peek_is_keyword("subgraph") {
+            self.index += 1;
+            match self.peek_kind() {
+                Some(DotTokenKind::BraceOpen) => None,
+                _ => Some(self.id_parse("a subgraph ID or `{`")?.text),
+            }
+        } else {
+            None
+        };
+
+        self.info_graph_builder.scope_push(subgraph_dot_id, line);
+        self.expect(DotTokenKind::BraceOpen, "`{`")?;
+        self.stmt_list_parse()?;
+        self.expect(DotTokenKind::BraceClose, "`}`")?;
+        let dot_ids = self.info_graph_builder.scope_pop();
+
+        Ok(DotOperand::Subgraph { dot_ids })
+    }
+
+    fn attr_list_parse_optional(&mut self) -> Result<Vec<DotAttr>, DotParseError> {
+        if matches!(self.peek_kind(), Some(DotTokenKind::BracketOpen)) {
+            self.attr_list_parse()
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Parses one or more `[ name = value, .. ]` lists.
+    fn attr_list_parse(&mut self) -> Result<Vec<DotAttr>, DotParseError> {
+        let mut dot_attrs = Vec::new();
+        while matches!(self.peek_kind(), Some(DotTokenKind::BracketOpen)) {
+            self.index += 1;
+            loop {
+                match self.peek_kind() {
+                    Some(DotTokenKind::BracketClose) => {
+                        self.index += 1;
+                        break;
+                    }
+                    Some(DotTokenKind::Comma | DotTokenKind::Semicolon) => self.index += 1,
+                    _ => dot_attrs.push(self.attr_parse()?),
+                }
+            }
+        }
+        Ok(dot_attrs)
+    }
+
+    fn attr_parse(&mut self) -> Result<DotAttr, DotParseError> {
+        let name = self.id_parse("an attribute name or `]`")?;
+        self.expect(DotTokenKind::Equals, "`=`")?;
+        let value = self.id_parse("an attribute value")?;
+
+        Ok(DotAttr {
+            name: name.text,
+            line: name.line,
+            value,
+        })
+    }
+
+    fn id_parse(&mut self, expected: &'static str) -> Result<DotValue, DotParseError> {
+        let dot_token = self.next(expected)?;
+        let line = dot_token.line;
+        match &dot_token.kind {
+            DotTokenKind::Id(id) => Ok(DotValue::plain(id.clone(), line)),
+            DotTokenKind::IdQuoted(id) => Ok(DotValue::quoted(id.clone(), line)),
+            DotTokenKind::IdHtml(id) => Ok(DotValue::html(id.clone(), line)),
+            _ => Err(unexpected(dot_token, expected)),
+        }
+    }
+}
+
+fn unexpected(dot_token: &DotToken, expected: &str) -> DotParseError {
+    DotParseError::Syntax {
+        line: dot_token.line,
+        column: dot_token.column,
+        message: format!(
+            "Expected {expected}, but found {}.",
+            dot_token.kind.describe()
+        ),
+    }
+}
@@ -0,0 +1,314 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use crate::DotParseError;
+
+/// A token in dot source, and where it begins.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct DotToken {
+    pub(crate) kind: DotTokenKind,
+    /// Line number, starting from `1`.
+    pub(crate) line: u32,
+    /// Column number in characters, starting from `1`.
+    pub(crate) column: u32,
+}
+
+/// Kinds of tokens in dot source.
+///
+/// See the [DOT language] grammar.
+///
+/// [DOT language]: https://graphviz.org/doc/info/lang.html
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum DotTokenKind {
+    /// Alphanumeric ID or numeral, which may be a keyword.
+    Id(String),
+    /// Double quoted string, with `\"` unescaped and `+` concatenations
+    /// joined. Other escape sequences such as `\n` are kept.
+    IdQuoted(String),
+    /// HTML string, without the outer `<` and `>`.
+    IdHtml(String),
+    BraceOpen,
+    BraceClose,
+    BracketOpen,
+    BracketClose,
+    Equals,
+    Semicolon,
+    Comma,
+    Colon,
+    /// `->` when `directed`, or `--`.
+    EdgeOp {
+        directed: bool,
+    },
+}
+
+impl DotTokenKind {
+    /// Returns a description of the token for error messages.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Self::Id(id) => format!("`{id}`"),
+            Self::IdQuoted(id) => format!("`\"{id}\"`"),
+            Self::IdHtml(id) => format!("`<{id}>`"),
+            Self::BraceOpen => String::from("`{`"),
+            Self::BraceClose => String::from("`}`"),
+            Self::BracketOpen => String::from("`[`"),
+            Self::BracketClose => String::from("`]`"),
+            Self::Equals => String::from("`=`"),
+            Self::Semicolon => String::from("`;`"),
+            Self::Comma => String::from("`,`"),
+            Self::Colon => String::from("`:`"),
+            Self::EdgeOp { directed: true } => String::from("`->`"),
+            Self::EdgeOp { directed: false } => String::from("`--`"),
+        }
+    }
+}
+
+/// Splits dot source into tokens, skipping whitespace and comments.
+pub(crate) fn tokenize(dot_src: &str) -> Result<Vec<DotToken>, DotParseError> {
+    let mut dot_lexer = DotLexer {
+        chars: dot_src.char_indices().peekable(),
+        line: 1,
+        column: 1,
+        line_start: true,
+    };
+    let mut dot_tokens = Vec::new();
+    while let Some(dot_token) = dot_lexer.token_next()? {
+        dot_tokens.push(dot_token);
+    }
+    Ok(dot_tokens)
+}
+
+struct DotLexer<'src> {
+    chars: Peekable<CharIndices<'src>>,
+    line: u32,
+    column: u32,
+    /// Whether only whitespace has been read on the current line, for `#`
+    /// preprocessor lines.
+    line_start: bool,
+}
+
+impl DotLexer<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_index, c)| *c)
+    }
+
+    /// Returns the character after the next one.
+    fn peek_second(&self) -> Option<char> {
+        let mut chars = self.chars.clone();
+        chars.next();
+        chars.next().map(|(_index, c)| c)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let (_index, c) = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+            self.line_start = true;
+        } else {
+            self.column += 1;
+            if !c.is_whitespace() {
+                self.line_start = false;
+            }
+        }
+        Some(c)
+    }
+
+    fn syntax_error(line: u32, column: u32, message: String) -> DotParseError {
+        DotParseError::Syntax {
+            line,
+            column,
+            message,
+        }
+    }
+
+    /// Skips whitespace, comments, and `#` preprocessor lines.
+    fn trivia_skip(&mut self) -> Result<(), DotParseError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('#') if self.line_start => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.bump();
+                    }
+                }
+                Some('/') if self.peek_second() == Some('/') => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.bump();
+                    }
+                }
+                Some('/') if self.peek_second() == Some('*') => {
+                    let (line, column) = (self.line, self.column);
+                    self.bump();
+                    self.bump();
+                    loop {
+                        match self.bump() {
+                            Some('*') if self.peek() == Some('/') => {
+                                self.bump();
+                                break;
+                            }
+                            Some(_) => {}
+                            None => {
+                                return Err(Self::syntax_error(
+                                    line,
+                                    column,
+                                    String::from("Comment is not closed with `*/`."),
+                                ));
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn token_next(&mut self) -> Result<Option<DotToken>, DotParseError> {
+        self.trivia_skip()?;
+
+        let (line, column) = (self.line, self.column);
+        let Some(c) = self.peek() else {
+            return Ok(None);
+        };
+
+        let kind = match c {
+            '{' => self.bump_as(DotTokenKind::BraceOpen),
+            '}' => self.bump_as(DotTokenKind::BraceClose),
+            '[' => self.bump_as(DotTokenKind::BracketOpen),
+            ']' => self.bump_as(DotTokenKind::BracketClose),
+            '=' => self.bump_as(DotTokenKind::Equals),
+            ';' => self.bump_as(DotTokenKind::Semicolon),
+            ',' => self.bump_as(DotTokenKind::Comma),
+            ':' => self.bump_as(DotTokenKind::Colon),
+            '-' if self.peek_second() == Some('>') => {
+                self.bump();
+                self.bump_as(DotTokenKind::EdgeOp { directed: true })
+            }
+            '-' if self.peek_second() == Some('-') => {
+                self.bump();
+                self.bump_as(DotTokenKind::EdgeOp { directed: false })
+            }
+            '"' => DotTokenKind::IdQuoted(self.quoted_read()?),
+            '<' => DotTokenKind::IdHtml(self.html_read()?),
+            c if c == '-' || c == '.' || c.is_ascii_digit() => {
+                DotTokenKind::Id(self.numeral_read())
+            }
+            c if c == '_' || c.is_alphabetic() || !c.is_ascii() => {
+                let mut id = String::new();
+                while let Some(c) = self
+                    .peek()
+                    .filter(|c| *c == '_' || c.is_alphanumeric() || !c.is_ascii())
+                {
+                    id.push(c);
+                    self.bump();
+                }
+                DotTokenKind::Id(id)
+            }
+            c => {
+                return Err(Self::syntax_error(
+                    line,
+                    column,
+                    format!("Unexpected character `{c}`."),
+                ));
+            }
+        };
+
+        Ok(Some(DotToken { kind, line, column }))
+    }
+
+    fn bump_as(&mut self, kind: DotTokenKind) -> DotTokenKind {
+        self.bump();
+        kind
+    }
+
+    /// Reads a numeral, e.g. `-1.5`, `.5`, `2`.
+    fn numeral_read(&mut self) -> String {
+        let mut numeral = String::new();
+        if self.peek() == Some('-') {
+            numeral.push('-');
+            self.bump();
+        }
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_digit() || *c == '.') {
+            numeral.push(c);
+            self.bump();
+        }
+        numeral
+    }
+
+    /// Reads one or more double quoted strings joined by `+`.
+    fn quoted_read(&mut self) -> Result<String, DotParseError> {
+        let mut quoted = String::new();
+        loop {
+            let (line, column) = (self.line, self.column);
+            self.bump();
+            loop {
+                match self.bump() {
+                    Some('"') => break,
+                    Some('\\') => match self.bump() {
+                        Some('"') => quoted.push('"'),
+                        // Line continuation.
+                        Some('\n') => {}
+                        Some(c) => {
+                            quoted.push('\\');
+                            quoted.push(c);
+                        }
+                        None => {}
+                    },
+                    Some(c) => quoted.push(c),
+                    None => {
+                        return Err(Self::syntax_error(
+                            line,
+                            column,
+                            String::from("String is not closed with `\"`."),
+                        ));
+                    }
+                }
+            }
+
+            // `"a" + "b"` is the same as `"ab"`.
+            let mut lookahead = self.chars.clone();
+            let next_non_whitespace = lookahead.find(|(_index, c)| !c.is_whitespace());
+            if next_non_whitespace.map(|(_index, c)| c) != Some('+') {
+                return Ok(quoted);
+            }
+            let after_plus = lookahead.find(|(_index, c)| !c.is_whitespace());
+            if after_plus.map(|(_index, c)| c) != Some('"') {
+                return Ok(quoted);
+            }
+            while self.peek() != Some('"') {
+                self.bump();
+            }
+        }
+    }
+
+    /// Reads an HTML string, which is delimited by matching `<` and `>`.
+    fn html_read(&mut self) -> Result<String, DotParseError> {
+        let (line, column) = (self.line, self.column);
+        self.bump();
+        let mut html = String::new();
+        let mut depth = 1u32;
+        loop {
+            match self.bump() {
+                Some('<') => {
+                    depth += 1;
+                    html.push('<');
+                }
+                Some('>') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(html);
+                    }
+                    html.push('>');
+                }
+                Some(c) => html.push(c),
+                None => {
+                    return Err(Self::syntax_error(
+                        line,
+                        column,
+                        String::from("HTML string is not closed with `>`."),
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+/// Errors when parsing dot source with [`DotParser`].
+///
+/// [`DotParser`]: crate::DotParser
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum DotParseError {
+    /// The dot source is not valid DOT syntax.
+    #[error("Syntax error at line {line}, column {column}: {message}")]
+    Syntax {
+        /// Line number, starting from `1`.
+        line: u32,
+        /// Column number in characters, starting from `1`.
+        column: u32,
+        /// What was expected or found.
+        message: String,
+    },
+    /// The dot source ended before the graph was closed.
+    #[error("Dot source ended unexpectedly, expected {expected}.")]
+    UnexpectedEnd {
+        /// What was expected.
+        expected: &'static str,
+    },
+    /// The dot source contains more than one graph.
+    #[error("Line {line} begins another graph, but only one graph per file is supported.")]
+    GraphMultiple {
+        /// Line number of the second graph, starting from `1`.
+        line: u32,
+    },
+}
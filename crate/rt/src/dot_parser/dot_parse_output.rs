@@ -0,0 +1,24 @@
+use dot_ix_model::info_graph::InfoGraph;
+
+use crate::DotParseWarning;
+
+/// The `InfoGraph` parsed from dot source, and what could not be imported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DotParseOutput {
+    /// The parsed graph.
+    pub info_graph: InfoGraph,
+    /// Attributes and IDs that were not imported as is.
+    pub dot_parse_warnings: Vec<DotParseWarning>,
+}
+
+impl DotParseOutput {
+    /// Returns the parsed graph.
+    pub fn info_graph(&self) -> &InfoGraph {
+        &self.info_graph
+    }
+
+    /// Returns the attributes and IDs that were not imported as is.
+    pub fn dot_parse_warnings(&self) -> &[DotParseWarning] {
+        &self.dot_parse_warnings
+    }
+}
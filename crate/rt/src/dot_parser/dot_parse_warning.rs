@@ -0,0 +1,83 @@
+use std::fmt;
+
+/// Something in the dot source that was not imported as is.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DotParseWarning {
+    /// The attribute is not supported, and was skipped.
+    AttrUnknown {
+        /// The element that the attribute is on, e.g. ``node `a` ``.
+        el: String,
+        /// Name of the attribute.
+        attr_name: String,
+        /// Line number of the attribute, starting from `1`.
+        line: u32,
+    },
+    /// The attribute value could not be imported, or was imported
+    /// differently.
+    AttrValueInvalid {
+        /// The element that the attribute is on, e.g. ``node `a` ``.
+        el: String,
+        /// Name of the attribute.
+        attr_name: String,
+        /// Value of the attribute.
+        attr_value: String,
+        /// What was done with the value.
+        message: String,
+        /// Line number of the attribute, starting from `1`.
+        line: u32,
+    },
+    /// The DOT ID is not a valid `NodeId` or `EdgeId`, so a different ID is
+    /// used.
+    IdRenamed {
+        /// ID in the dot source.
+        dot_id: String,
+        /// ID in the `InfoGraph`.
+        id: String,
+        /// Line number where the ID is first used, starting from `1`.
+        line: u32,
+    },
+    /// Edge ports are not supported, so the edge is connected to the node.
+    PortIgnored {
+        /// ID of the node in the dot source.
+        dot_id: String,
+        /// The port, e.g. `n` or `port_1:sw`.
+        port: String,
+        /// Line number of the port, starting from `1`.
+        line: u32,
+    },
+}
+
+impl fmt::Display for DotParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AttrUnknown {
+                el,
+                attr_name,
+                line,
+            } => write!(
+                f,
+                "line {line}: `{attr_name}` on {el} is not supported, and was skipped."
+            ),
+            Self::AttrValueInvalid {
+                el,
+                attr_name,
+                attr_value,
+                message,
+                line,
+            } => write!(
+                f,
+                "line {line}: `{attr_name} = {attr_value}` on {el}: {message}"
+            ),
+            Self::IdRenamed { dot_id, id, line } => {
+                write!(
+                    f,
+                    "line {line}: `{dot_id}` is not a valid ID, and was renamed to `{id}`."
+                )
+            }
+            Self::PortIgnored { dot_id, port, line } => write!(
+                f,
+                "line {line}: port `{port}` on `{dot_id}` is not supported, and was skipped."
+            ),
+        }
+    }
+}
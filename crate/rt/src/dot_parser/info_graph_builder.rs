@@ -0,0 +1,788 @@
+use std::collections::HashMap;
+
+use dot_ix_model::{
+    common::{
+        graphviz_attrs::{EdgeDir, Margin, NodeMargin, Splines},
+        EdgeDescs, EdgeId, Edges, GraphvizAttrs, NodeDescs, NodeHierarchy, NodeId, NodeNames,
+    },
+    info_graph::{GraphDir, InfoGraph},
+};
+use indexmap::IndexMap;
+
+use crate::{id_allocator::IdAllocator, DotParseOutput, DotParseWarning};
+
+/// How an ID or attribute value was written in the dot source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DotValueKind {
+    /// Alphanumeric ID or numeral.
+    Plain,
+    /// Double quoted string, which may contain escape sequences.
+    Quoted,
+    /// HTML string.
+    Html,
+}
+
+/// An ID or attribute value.
+#[derive(Clone, Debug)]
+pub(crate) struct DotValue {
+    pub(crate) text: String,
+    pub(crate) kind: DotValueKind,
+    /// Line number, starting from `1`.
+    pub(crate) line: u32,
+}
+
+impl DotValue {
+    pub(crate) fn plain(text: String, line: u32) -> Self {
+        Self {
+            text,
+            kind: DotValueKind::Plain,
+            line,
+        }
+    }
+
+    pub(crate) fn quoted(text: String, line: u32) -> Self {
+        Self {
+            text,
+            kind: DotValueKind::Quoted,
+            line,
+        }
+    }
+
+    pub(crate) fn html(text: String, line: u32) -> Self {
+        Self {
+            text,
+            kind: DotValueKind::Html,
+            line,
+        }
+    }
+}
+
+/// A `name = value` attribute.
+#[derive(Clone, Debug)]
+pub(crate) struct DotAttr {
+    pub(crate) name: String,
+    pub(crate) value: DotValue,
+    /// Line number of the name, starting from `1`.
+    pub(crate) line: u32,
+}
+
+/// One side of an edge operator.
+#[derive(Clone, Debug)]
+pub(crate) enum DotOperand {
+    /// A node, with its port if any.
+    Node {
+        dot_id: String,
+        port: Option<String>,
+        line: u32,
+    },
+    /// A subgraph, which connects the edge to each of its nodes.
+    Subgraph { dot_ids: Vec<String> },
+}
+
+/// Node attributes that are imported.
+const NODE_ATTRS: &[&str] = &["label", "width", "height", "margin"];
+
+/// Edge attributes that are imported.
+const EDGE_ATTRS: &[&str] = &[
+    "label",
+    "constraint",
+    "minlen",
+    "dir",
+    "id",
+    "lhead",
+    "ltail",
+];
+
+/// Attributes and nodes within a graph, cluster, or subgraph.
+#[derive(Clone, Debug, Default)]
+struct Scope {
+    /// The cluster that nodes in this scope belong to.
+    cluster: Option<NodeId>,
+    /// Whether this scope is the cluster's own scope, rather than a subgraph
+    /// within the cluster.
+    is_cluster: bool,
+    /// `node [..]` attributes that apply to nodes created in this scope.
+    node_attrs: Vec<DotAttr>,
+    /// `edge [..]` attributes that apply to edges created in this scope.
+    edge_attrs: Vec<DotAttr>,
+    /// DOT IDs of nodes used in this scope, including nested scopes.
+    dot_ids: Vec<String>,
+}
+
+/// Builds an `InfoGraph` from parsed DOT statements.
+pub(crate) struct InfoGraphBuilder {
+    directed: bool,
+    strict: bool,
+    graph_name: String,
+    direction: GraphDir,
+    graphviz_attrs: GraphvizAttrs,
+    /// Each node and cluster, and the cluster it is in.
+    node_parents: IndexMap<NodeId, Option<NodeId>>,
+    dot_id_to_node_id: HashMap<String, NodeId>,
+    cluster_dot_id_to_node_id: HashMap<String, NodeId>,
+    node_id_allocator: IdAllocator,
+    edge_id_allocator: IdAllocator,
+    node_names: NodeNames,
+    node_descs: NodeDescs,
+    edges: Edges,
+    edge_descs: EdgeDescs,
+    scopes: Vec<Scope>,
+    dot_parse_warnings: Vec<DotParseWarning>,
+}
+
+impl InfoGraphBuilder {
+    pub(crate) fn new() -> Self {
+        Self {
+            directed: true,
+            strict: false,
+            graph_name: String::new(),
+            direction: GraphDir::Horizontal,
+            graphviz_attrs: GraphvizAttrs::new().with_edge_minlen_default(1),
+            node_parents: IndexMap::new(),
+            dot_id_to_node_id: HashMap::new(),
+            cluster_dot_id_to_node_id: HashMap::new(),
+            node_id_allocator: IdAllocator::new(),
+            edge_id_allocator: IdAllocator::new(),
+            node_names: NodeNames::new(),
+            node_descs: NodeDescs::new(),
+            edges: Edges::new(),
+            edge_descs: EdgeDescs::new(),
+            scopes: vec![Scope::default()],
+            dot_parse_warnings: Vec::new(),
+        }
+    }
+
+    pub(crate) fn directed(&self) -> bool {
+        self.directed
+    }
+
+    pub(crate) fn graph_begin(&mut self, directed: bool, strict: bool, graph_name: String) {
+        self.directed = directed;
+        self.strict = strict;
+        self.graph_name = graph_name;
+        // Dot's default is `TB`.
+        self.direction = GraphDir::Vertical;
+        if !directed {
+            self.graphviz_attrs.edge_dir_default = EdgeDir::None;
+        }
+    }
+
+    fn scope(&self) -> &Scope {
+        self.scopes.last().expect("Root scope is never popped.")
+    }
+
+    fn scope_mut(&mut self) -> &mut Scope {
+        self.scopes.last_mut().expect("Root scope is never popped.")
+    }
+
+    fn scope_is_root(&self) -> bool {
+        self.scopes.len() == 1
+    }
+
+    /// Begins a subgraph, which is a cluster if its ID begins with `cluster`.
+    pub(crate) fn scope_push(&mut self, subgraph_dot_id: Option<String>, line: u32) {
+        let parent = self.scope();
+        let mut scope = Scope {
+            cluster: parent.cluster.clone(),
+            is_cluster: false,
+            node_attrs: parent.node_attrs.clone(),
+            edge_attrs: parent.edge_attrs.clone(),
+            dot_ids: Vec::new(),
+        };
+
+        if let Some(subgraph_dot_id) = subgraph_dot_id.filter(|id| id.starts_with("cluster")) {
+            let cluster_id = match self.cluster_dot_id_to_node_id.get(&subgraph_dot_id) {
+                Some(cluster_id) => cluster_id.clone(),
+                None => {
+                    let id_proposed = subgraph_dot_id
+                        .strip_prefix("cluster_")
+                        .or_else(|| subgraph_dot_id.strip_prefix("cluster"))
+                        .filter(|id| !id.is_empty())
+                        .unwrap_or("cluster");
+                    let cluster_id = self.node_id_alloc(&subgraph_dot_id, id_proposed, line);
+                    self.node_parents
+                        .insert(cluster_id.clone(), scope.cluster.clone());
+                    self.cluster_dot_id_to_node_id
+                        .insert(subgraph_dot_id, cluster_id.clone());
+                    cluster_id
+                }
+            };
+            scope.cluster = Some(cluster_id);
+            scope.is_cluster = true;
+        }
+
+        self.scopes.push(scope);
+    }
+
+    /// Ends a subgraph, returning the DOT IDs of the nodes used within it.
+    pub(crate) fn scope_pop(&mut self) -> Vec<String> {
+        let scope = self.scopes.pop().expect("Subgraph scope to be pushed.");
+        self.scope_mut()
+            .dot_ids
+            .extend(scope.dot_ids.iter().cloned());
+        scope.dot_ids
+    }
+
+    /// Applies `graph [..]` or `name = value` attributes to the graph or
+    /// current cluster.
+    pub(crate) fn graph_attrs_apply(&mut self, dot_attrs: Vec<DotAttr>) {
+        let scope = self.scope();
+        let cluster_id = scope.cluster.clone().filter(|_| scope.is_cluster);
+        let scope_is_root = self.scope_is_root();
+
+        dot_attrs
+            .into_iter()
+            .for_each(|dot_attr| match (&cluster_id, scope_is_root) {
+                (_, true) => self.graph_attr_apply(dot_attr),
+                (Some(cluster_id), false) => self.cluster_attr_apply(cluster_id, dot_attr),
+                (None, false) => self.warn_attr_unknown(String::from("subgraph"), &dot_attr),
+            });
+    }
+
+    fn graph_attr_apply(&mut self, dot_attr: DotAttr) {
+        let el = "graph";
+        let value = dot_attr.value.text.as_str();
+        match dot_attr.name.as_str() {
+            "rankdir" => match value {
+                "LR" => self.direction = GraphDir::Horizontal,
+                "TB" => self.direction = GraphDir::Vertical,
+                "RL" => {
+                    self.direction = GraphDir::Horizontal;
+                    self.warn_attr_value(el, &dot_attr, "Imported as `LR`.");
+                }
+                "BT" => {
+                    self.direction = GraphDir::Vertical;
+                    self.warn_attr_value(el, &dot_attr, "Imported as `TB`.");
+                }
+                _ => self.warn_attr_value(el, &dot_attr, "Expected `TB`, `LR`, `BT`, or `RL`."),
+            },
+            "nodesep" => match value.parse::<f64>() {
+                Ok(nodesep) => self.graphviz_attrs.nodesep = nodesep,
+                Err(_) => self.warn_attr_value(el, &dot_attr, "Expected a number."),
+            },
+            "ranksep" => {
+                let mut parts = value.split_whitespace();
+                match parts.next().map(str::parse::<f64>) {
+                    Some(Ok(ranksep)) => {
+                        self.graphviz_attrs.ranksep = ranksep;
+                        if parts.next().is_some() {
+                            self.warn_attr_value(el, &dot_attr, "`equally` is not supported.");
+                        }
+                    }
+                    _ => self.warn_attr_value(el, &dot_attr, "Expected a number."),
+                }
+            }
+            "splines" => {
+                let splines = match value {
+                    "true" => Ok(Splines::Spline),
+                    "false" => Ok(Splines::Line),
+                    _ => value.parse::<Splines>(),
+                };
+                match splines {
+                    Ok(splines) => self.graphviz_attrs.splines = splines,
+                    Err(message) => self.warn_attr_value(el, &dot_attr, &message),
+                }
+            }
+            // `dot_ix` always renders with `compound = true`.
+            "compound" => {}
+            _ => self.warn_attr_unknown(String::from(el), &dot_attr),
+        }
+    }
+
+    fn cluster_attr_apply(&mut self, cluster_id: &NodeId, dot_attr: DotAttr) {
+        let el = format!("cluster `{cluster_id}`");
+        match dot_attr.name.as_str() {
+            "label" => {
+                let dot_id = cluster_id.to_string();
+                self.label_apply(cluster_id, &dot_id, &dot_attr.value);
+            }
+            "margin" => match dot_attr.value.text.parse::<Margin>() {
+                Ok(margin) => {
+                    self.graphviz_attrs
+                        .margins
+                        .insert(cluster_id.clone(), margin);
+                }
+                Err(message) => self.warn_attr_value(&el, &dot_attr, &message),
+            },
+            _ => self.warn_attr_unknown(el, &dot_attr),
+        }
+    }
+
+    /// Applies `node [..]` attributes.
+    ///
+    /// At the top level, `width`, `height`, and `margin` become the
+    /// `GraphvizAttrs` defaults. Other attributes apply to each node created
+    /// afterwards in the same scope.
+    pub(crate) fn node_defaults_apply(&mut self, dot_attrs: Vec<DotAttr>) {
+        let el = "node defaults";
+        let scope_is_root = self.scope_is_root();
+        dot_attrs.into_iter().for_each(|dot_attr| {
+            if !NODE_ATTRS.contains(&dot_attr.name.as_str()) {
+                self.warn_attr_unknown(String::from(el), &dot_attr);
+                return;
+            }
+
+            let value = dot_attr.value.text.as_str();
+            match (dot_attr.name.as_str(), scope_is_root) {
+                ("width", true) => match value.parse::<f64>() {
+                    Ok(width) => self.graphviz_attrs.node_width_default = width,
+                    Err(_) => self.warn_attr_value(el, &dot_attr, "Expected a number."),
+                },
+                ("height", true) => match value.parse::<f64>() {
+                    Ok(height) => self.graphviz_attrs.node_height_default = height,
+                    Err(_) => self.warn_attr_value(el, &dot_attr, "Expected a number."),
+                },
+                ("margin", true) => match value.parse::<Margin>() {
+                    Ok(margin) => self.graphviz_attrs.margin_node_default = NodeMargin(margin),
+                    Err(message) => self.warn_attr_value(el, &dot_attr, &message),
+                },
+                _ => self.scope_mut().node_attrs.push(dot_attr),
+            }
+        });
+    }
+
+    /// Applies `edge [..]` attributes.
+    ///
+    /// At the top level, `constraint`, `minlen`, and `dir` become the
+    /// `GraphvizAttrs` defaults. Other attributes apply to each edge created
+    /// afterwards in the same scope.
+    pub(crate) fn edge_defaults_apply(&mut self, dot_attrs: Vec<DotAttr>) {
+        let el = "edge defaults";
+        let scope_is_root = self.scope_is_root();
+        dot_attrs.into_iter().for_each(|dot_attr| {
+            if !EDGE_ATTRS.contains(&dot_attr.name.as_str()) {
+                self.warn_attr_unknown(String::from(el), &dot_attr);
+                return;
+            }
+
+            let value = dot_attr.value.text.as_str();
+            match (dot_attr.name.as_str(), scope_is_root) {
+                ("constraint", true) => match bool_parse(value) {
+                    Some(constraint) => self.graphviz_attrs.edge_constraint_default = constraint,
+                    None => self.warn_attr_value(el, &dot_attr, "Expected `true` or `false`."),
+                },
+                ("minlen", true) => match value.parse::<u32>() {
+                    Ok(minlen) => self.graphviz_attrs.edge_minlen_default = minlen,
+                    Err(_) => self.warn_attr_value(el, &dot_attr, "Expected an integer."),
+                },
+                ("dir", true) => match edge_dir_parse(value) {
+                    Some(edge_dir) => self.graphviz_attrs.edge_dir_default = edge_dir,
+                    None => self.warn_attr_value(
+                        el,
+                        &dot_attr,
+                        "Expected `forward`, `back`, `both`, or `none`.",
+                    ),
+                },
+                _ => self.scope_mut().edge_attrs.push(dot_attr),
+            }
+        });
+    }
+
+    pub(crate) fn node_stmt_apply(
+        &mut self,
+        dot_id: String,
+        port: Option<String>,
+        dot_attrs: Vec<DotAttr>,
+        line: u32,
+    ) {
+        if let Some(port) = port {
+            self.warn_port(&dot_id, port, line);
+        }
+        let node_id = self.node_reference(&dot_id, line);
+        dot_attrs
+            .into_iter()
+            .for_each(|dot_attr| self.node_attr_apply(&node_id, &dot_id, dot_attr));
+    }
+
+    /// Returns the `NodeId` for a DOT ID, creating the node in the current
+    /// cluster if it doesn't exist.
+    fn node_reference(&mut self, dot_id: &str, line: u32) -> NodeId {
+        self.scope_mut().dot_ids.push(dot_id.to_string());
+        let cluster_id = self.scope().cluster.clone();
+
+        if let Some(node_id) = self.dot_id_to_node_id.get(dot_id).cloned() {
+            // Like Graphviz, a node used at the top level and then in a cluster
+            // belongs to the cluster.
+            let node_parent = self.node_parents.get_mut(&node_id);
+            if let (Some(node_parent @ None), Some(cluster_id)) = (node_parent, cluster_id) {
+                *node_parent = Some(cluster_id);
+            }
+            return node_id;
+        }
+
+        let node_id = self.node_id_alloc(dot_id, dot_id, line);
+        self.node_parents.insert(node_id.clone(), cluster_id);
+        self.dot_id_to_node_id
+            .insert(dot_id.to_string(), node_id.clone());
+        if node_id.as_str() != dot_id {
+            self.node_names.insert(node_id.clone(), dot_id.to_string());
+        }
+
+        let node_attrs = self.scope().node_attrs.clone();
+        node_attrs
+            .into_iter()
+            .for_each(|dot_attr| self.node_attr_apply(&node_id, dot_id, dot_attr));
+
+        node_id
+    }
+
+    fn node_attr_apply(&mut self, node_id: &NodeId, dot_id: &str, dot_attr: DotAttr) {
+        let el = format!("node `{dot_id}`");
+        let value = dot_attr.value.text.as_str();
+        match dot_attr.name.as_str() {
+            "label" => self.label_apply(node_id, dot_id, &dot_attr.value),
+            "width" => match value.parse::<f64>() {
+                Ok(width) => {
+                    self.graphviz_attrs
+                        .node_widths
+                        .insert(node_id.clone(), width);
+                }
+                Err(_) => self.warn_attr_value(&el, &dot_attr, "Expected a number."),
+            },
+            "height" => match value.parse::<f64>() {
+                Ok(height) => {
+                    self.graphviz_attrs
+                        .node_heights
+                        .insert(node_id.clone(), height);
+                }
+                Err(_) => self.warn_attr_value(&el, &dot_attr, "Expected a number."),
+            },
+            "margin" => match value.parse::<Margin>() {
+                Ok(margin) => {
+                    self.graphviz_attrs.margins.insert(node_id.clone(), margin);
+                }
+                Err(message) => self.warn_attr_value(&el, &dot_attr, &message),
+            },
+            _ => self.warn_attr_unknown(el, &dot_attr),
+        }
+    }
+
+    /// Sets the node or cluster's name to the label's first line, and its
+    /// description to the remaining lines.
+    fn label_apply(&mut self, node_id: &NodeId, dot_id: &str, label: &DotValue) {
+        let label_text = match label.kind {
+            DotValueKind::Quoted => label_unescape(
+                &label.text,
+                &[('N', dot_id), ('G', self.graph_name.as_str())],
+            ),
+            DotValueKind::Plain | DotValueKind::Html => label.text.clone(),
+        };
+        let label_text = label_text.trim_end_matches('\n');
+        let (node_name, node_desc) = match label.kind {
+            DotValueKind::Html => (label_text, None),
+            DotValueKind::Plain | DotValueKind::Quoted => match label_text.split_once('\n') {
+                Some((node_name, node_desc)) => (node_name, Some(node_desc)),
+                None => (label_text, None),
+            },
+        };
+
+        self.node_names
+            .insert(node_id.clone(), node_name.to_string());
+        match node_desc {
+            Some(node_desc) => {
+                self.node_descs
+                    .insert(node_id.clone(), node_desc.to_string());
+            }
+            None => {
+                self.node_descs.shift_remove(node_id);
+            }
+        }
+    }
+
+    /// Creates an edge between each pair of consecutive operands.
+    pub(crate) fn edge_stmt_apply(
+        &mut self,
+        dot_operands: Vec<DotOperand>,
+        dot_attrs: Vec<DotAttr>,
+    ) {
+        let operand_dot_ids = dot_operands
+            .into_iter()
+            .map(|dot_operand| match dot_operand {
+                DotOperand::Node { dot_id, port, line } => {
+                    if let Some(port) = port {
+                        self.warn_port(&dot_id, port, line);
+                    }
+                    self.node_reference(&dot_id, line);
+                    vec![dot_id]
+                }
+                DotOperand::Subgraph { dot_ids } => {
+                    let mut dot_ids_unique = Vec::with_capacity(dot_ids.len());
+                    dot_ids.into_iter().for_each(|dot_id| {
+                        if !dot_ids_unique.contains(&dot_id) {
+                            dot_ids_unique.push(dot_id);
+                        }
+                    });
+                    dot_ids_unique
+                }
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        let mut edge_attrs = self.scope().edge_attrs.clone();
+        edge_attrs.extend(dot_attrs);
+
+        operand_dot_ids.windows(2).for_each(|operand_pair| {
+            operand_pair[0].iter().for_each(|src_dot_id| {
+                operand_pair[1].iter().for_each(|target_dot_id| {
+                    self.edge_create(src_dot_id, target_dot_id, &edge_attrs);
+                });
+            });
+        });
+    }
+
+    fn edge_create(&mut self, src_dot_id: &str, target_dot_id: &str, edge_attrs: &[DotAttr]) {
+        let edge_op = if self.directed { "->" } else { "--" };
+        let el = format!("edge `{src_dot_id} {edge_op} {target_dot_id}`");
+        let (Some(mut src_node_id), Some(mut target_node_id)) = (
+            self.dot_id_to_node_id.get(src_dot_id).cloned(),
+            self.dot_id_to_node_id.get(target_dot_id).cloned(),
+        ) else {
+            return;
+        };
+
+        // `ltail` and `lhead` clip the edge at a cluster, which is the same as
+        // connecting the edge to the cluster in `dot_ix`.
+        let mut edge_id_attr = None::<&DotAttr>;
+        edge_attrs
+            .iter()
+            .for_each(|dot_attr| match dot_attr.name.as_str() {
+                "id" => edge_id_attr = Some(dot_attr),
+                "ltail" | "lhead" => {
+                    match self.cluster_dot_id_to_node_id.get(&dot_attr.value.text) {
+                        Some(cluster_id) if dot_attr.name == "ltail" => {
+                            src_node_id = cluster_id.clone()
+                        }
+                        Some(cluster_id) => target_node_id = cluster_id.clone(),
+                        None => self.warn_attr_value(
+                            &el,
+                            dot_attr,
+                            "Cluster does not exist, so the edge is connected to the node.",
+                        ),
+                    }
+                }
+                _ => {}
+            });
+
+        if self.strict
+            && self
+                .edges
+                .values()
+                .any(|[src, target]| *src == src_node_id && *target == target_node_id)
+        {
+            return;
+        }
+
+        let edge_id = edge_id_attr
+            .and_then(|dot_attr| EdgeId::try_from(dot_attr.value.text.clone()).ok())
+            .filter(|edge_id| self.edge_id_allocator.claim(edge_id));
+        let edge_id = match edge_id {
+            Some(edge_id) => edge_id,
+            None => {
+                if let Some(dot_attr) = edge_id_attr {
+                    self.warn_attr_value(
+                        &el,
+                        dot_attr,
+                        "Not a valid or unique `EdgeId`, so a generated ID is used.",
+                    );
+                }
+                let edge_id = self
+                    .edge_id_allocator
+                    .alloc(&format!("{src_node_id}__{target_node_id}"));
+                EdgeId::try_from(edge_id).expect("Generated `EdgeId` to be valid.")
+            }
+        };
+
+        self.edges
+            .insert(edge_id.clone(), [src_node_id, target_node_id]);
+
+        edge_attrs.iter().for_each(|dot_attr| {
+            let value = dot_attr.value.text.as_str();
+            match dot_attr.name.as_str() {
+                "label" => {
+                    let edge_desc = match dot_attr.value.kind {
+                        DotValueKind::Quoted => label_unescape(
+                            value,
+                            &[
+                                ('T', src_dot_id),
+                                ('H', target_dot_id),
+                                ('G', self.graph_name.as_str()),
+                            ],
+                        ),
+                        DotValueKind::Plain | DotValueKind::Html => value.to_string(),
+                    };
+                    self.edge_descs.insert(
+                        edge_id.clone(),
+                        edge_desc.trim_end_matches('\n').to_string(),
+                    );
+                }
+                "constraint" => match bool_parse(value) {
+                    Some(constraint) => {
+                        self.graphviz_attrs
+                            .edge_constraints
+                            .insert(edge_id.clone(), constraint);
+                    }
+                    None => self.warn_attr_value(&el, dot_attr, "Expected `true` or `false`."),
+                },
+                "minlen" => match value.parse::<u32>() {
+                    Ok(minlen) => {
+                        self.graphviz_attrs
+                            .edge_minlens
+                            .insert(edge_id.clone(), minlen);
+                    }
+                    Err(_) => self.warn_attr_value(&el, dot_attr, "Expected an integer."),
+                },
+                "dir" => match edge_dir_parse(value) {
+                    Some(edge_dir) => {
+                        self.graphviz_attrs
+                            .edge_dirs
+                            .insert(edge_id.clone(), edge_dir);
+                    }
+                    None => self.warn_attr_value(
+                        &el,
+                        dot_attr,
+                        "Expected `forward`, `back`, `both`, or `none`.",
+                    ),
+                },
+                "id" | "ltail" | "lhead" => {}
+                _ => self.warn_attr_unknown(el.clone(), dot_attr),
+            }
+        });
+    }
+
+    /// Returns a valid and unused `NodeId` for the proposed ID, warning if it
+    /// differs.
+    fn node_id_alloc(&mut self, dot_id: &str, id_proposed: &str, line: u32) -> NodeId {
+        let id = self.node_id_allocator.alloc(id_proposed);
+        if id != id_proposed {
+            self.dot_parse_warnings.push(DotParseWarning::IdRenamed {
+                dot_id: dot_id.to_string(),
+                id: id.clone(),
+                line,
+            });
+        }
+
+        NodeId::try_from(id).expect("Sanitized `NodeId` to be valid.")
+    }
+
+    fn warn_attr_unknown(&mut self, el: String, dot_attr: &DotAttr) {
+        self.dot_parse_warnings.push(DotParseWarning::AttrUnknown {
+            el,
+            attr_name: dot_attr.name.clone(),
+            line: dot_attr.line,
+        });
+    }
+
+    fn warn_attr_value(&mut self, el: &str, dot_attr: &DotAttr, message: &str) {
+        self.dot_parse_warnings
+            .push(DotParseWarning::AttrValueInvalid {
+                el: el.to_string(),
+                attr_name: dot_attr.name.clone(),
+                attr_value: dot_attr.value.text.clone(),
+                message: message.to_string(),
+                line: dot_attr.line,
+            });
+    }
+
+    fn warn_port(&mut self, dot_id: &str, port: String, line: u32) {
+        self.dot_parse_warnings.push(DotParseWarning::PortIgnored {
+            dot_id: dot_id.to_string(),
+            port,
+            line,
+        });
+    }
+
+    pub(crate) fn build(self) -> DotParseOutput {
+        let InfoGraphBuilder {
+            direction,
+            graphviz_attrs,
+            node_parents,
+            node_names,
+            node_descs,
+            edges,
+            edge_descs,
+            dot_parse_warnings,
+            ..
+        } = self;
+
+        let mut parent_to_children = IndexMap::<Option<&NodeId>, Vec<&NodeId>>::new();
+        node_parents.iter().for_each(|(node_id, node_parent)| {
+            parent_to_children
+                .entry(node_parent.as_ref())
+                .or_default()
+                .push(node_id);
+        });
+        let hierarchy = node_hierarchy_build(&parent_to_children, None);
+
+        let info_graph = InfoGraph::default()
+            .with_direction(direction)
+            .with_hierarchy(hierarchy)
+            .with_node_names(node_names)
+            .with_node_descs(node_descs)
+            .with_edges(edges)
+            .with_edge_descs(edge_descs)
+            .with_graphviz_attrs(graphviz_attrs);
+
+        DotParseOutput {
+            info_graph,
+            dot_parse_warnings,
+        }
+    }
+}
+
+fn node_hierarchy_build(
+    parent_to_children: &IndexMap<Option<&NodeId>, Vec<&NodeId>>,
+    parent: Option<&NodeId>,
+) -> NodeHierarchy {
+    let mut node_hierarchy = NodeHierarchy::new();
+    parent_to_children
+        .get(&parent)
+        .into_iter()
+        .flatten()
+        .for_each(|node_id| {
+            let child_hierarchy = node_hierarchy_build(parent_to_children, Some(node_id));
+            node_hierarchy.insert((*node_id).clone(), child_hierarchy);
+        });
+    node_hierarchy
+}
+
+/// Replaces escape sequences in a quoted label.
+///
+/// `\n`, `\l`, and `\r` become line breaks, and `\N`, `\G`, etc. are replaced
+/// with the given values.
+fn label_unescape(text: &str, substitutions: &[(char, &str)]) -> String {
+    let mut label = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            label.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'l' | 'r') => label.push('\n'),
+            Some('\\') => label.push('\\'),
+            Some(c) => match substitutions.iter().find(|(escape, _)| *escape == c) {
+                Some((_, substitution)) => label.push_str(substitution),
+                None => label.push(c),
+            },
+            None => label.push('\\'),
+        }
+    }
+    label
+}
+
+fn bool_parse(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" => Some(true),
+        "false" | "no" => Some(false),
+        value => value.parse::<i64>().ok().map(|value| value != 0),
+    }
+}
+
+fn edge_dir_parse(value: &str) -> Option<EdgeDir> {
+    match value {
+        "forward" => Some(EdgeDir::Forward),
+        "back" => Some(EdgeDir::Back),
+        "both" => Some(EdgeDir::Both),
+        "none" => Some(EdgeDir::None),
+        _ => None,
+    }
+}
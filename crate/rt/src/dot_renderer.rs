@@ -0,0 +1,46 @@
+use dot_ix_model::common::DotSrcAndStyles;
+
+pub use self::{
+    dot_diagnostic::DotDiagnostic, dot_diagnostic_severity::DotDiagnosticSeverity,
+    fake_dot_renderer::FakeDotRenderer, render_error::RenderError, svg::Svg,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::dot_process_renderer::DotProcessRenderer;
+
+mod dot_diagnostic;
+mod dot_diagnostic_severity;
+mod fake_dot_renderer;
+mod render_error;
+mod svg;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod dot_process_renderer;
+
+/// Renders dot source into SVG outside the browser, e.g. for server side
+/// rendering.
+///
+/// The returned SVG is what Graphviz generates, and does not include
+/// [`DotSrcAndStyles::styles`].
+pub trait DotRenderer {
+    /// Renders the dot source as SVG.
+    fn render(&self, dot_src_and_styles: &DotSrcAndStyles) -> Result<Svg, RenderError>;
+}
+
+impl<T> DotRenderer for &T
+where
+    T: DotRenderer + ?Sized,
+{
+    fn render(&self, dot_src_and_styles: &DotSrcAndStyles) -> Result<Svg, RenderError> {
+        (**self).render(dot_src_and_styles)
+    }
+}
+
+impl<T> DotRenderer for std::sync::Arc<T>
+where
+    T: DotRenderer + ?Sized,
+{
+    fn render(&self, dot_src_and_styles: &DotSrcAndStyles) -> Result<Svg, RenderError> {
+        (**self).render(dot_src_and_styles)
+    }
+}
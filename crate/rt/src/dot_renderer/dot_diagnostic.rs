@@ -0,0 +1,87 @@
+use std::fmt;
+
+use crate::DotDiagnosticSeverity;
+
+/// An error or warning that `dot` wrote to `stderr`.
+///
+/// # Examples
+///
+/// ```text
+/// Error: <stdin>: syntax error in line 3 near '->'
+/// Warning: node a, port x unrecognized
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DotDiagnostic {
+    /// Whether this is an error or a warning.
+    pub severity: DotDiagnosticSeverity,
+    /// Line in the dot source that the message refers to, if any.
+    pub line: Option<u32>,
+    /// The message, without the `Error: ` or `Warning: ` prefix.
+    ///
+    /// Lines that follow the first line without a prefix, such as the syntax
+    /// error context, are included.
+    pub message: String,
+}
+
+impl DotDiagnostic {
+    /// Parses the messages that `dot` writes to `stderr`.
+    ///
+    /// Output that doesn't begin with `Error: ` or `Warning: ` is treated as an
+    /// error, so that unexpected failures are not lost.
+    pub fn parse_stderr(stderr: &str) -> Vec<DotDiagnostic> {
+        stderr.lines().filter(|line| !line.trim().is_empty()).fold(
+            Vec::<DotDiagnostic>::new(),
+            |mut dot_diagnostics, line| {
+                let severity_and_message = line
+                    .strip_prefix("Error: ")
+                    .map(|message| (DotDiagnosticSeverity::Error, message))
+                    .or_else(|| {
+                        line.strip_prefix("Warning: ")
+                            .map(|message| (DotDiagnosticSeverity::Warning, message))
+                    });
+
+                match (severity_and_message, dot_diagnostics.last_mut()) {
+                    (Some((severity, message)), _) => {
+                        let message = message.strip_prefix("<stdin>: ").unwrap_or(message);
+                        dot_diagnostics.push(DotDiagnostic {
+                            severity,
+                            line: line_number(message),
+                            message: message.trim().to_string(),
+                        });
+                    }
+                    (None, Some(dot_diagnostic)) => {
+                        dot_diagnostic.message.push('\n');
+                        dot_diagnostic.message.push_str(line.trim_end());
+                    }
+                    (None, None) => dot_diagnostics.push(DotDiagnostic {
+                        severity: DotDiagnosticSeverity::Error,
+                        line: line_number(line),
+                        message: line.trim().to_string(),
+                    }),
+                }
+
+                dot_diagnostics
+            },
+        )
+    }
+}
+
+/// Returns the number after `"line "` in a message.
+fn line_number(message: &str) -> Option<u32> {
+    let (_, after_line) = message.split_once("line ")?;
+    let digits_len = after_line
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(after_line.len());
+    after_line[..digits_len].parse().ok()
+}
+
+impl fmt::Display for DotDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            severity,
+            line: _,
+            message,
+        } = self;
+        write!(f, "{severity}: {message}")
+    }
+}
@@ -0,0 +1,21 @@
+use std::fmt;
+
+/// Whether a [`DotDiagnostic`] is an error or a warning.
+///
+/// [`DotDiagnostic`]: crate::DotDiagnostic
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DotDiagnosticSeverity {
+    /// `dot` could not render the graph.
+    Error,
+    /// `dot` rendered the graph, but something in it was ignored.
+    Warning,
+}
+
+impl fmt::Display for DotDiagnosticSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => "error".fmt(f),
+            Self::Warning => "warning".fmt(f),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use dot_ix_model::common::DotSrcAndStyles;
+
+use crate::{DotDiagnostic, DotDiagnosticSeverity, DotRenderer, RenderError, Svg};
+
+/// How often to check whether `dot` has exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// [`DotRenderer`] that runs a local Graphviz `dot` executable.
+///
+/// The dot source is written to `dot -Tsvg`'s `stdin`, and the SVG is read
+/// from its `stdout`. If `dot` exits unsuccessfully, or writes an error to
+/// `stderr`, its messages are returned in [`RenderError::DotFailed`]. Warnings
+/// are ignored when the graph is rendered.
+///
+/// Images in [`DotSrcAndStyles::opts`] are not passed to `dot`, so image paths
+/// must be readable by the `dot` process.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DotProcessRenderer {
+    /// Path to the `dot` executable, defaults to `"dot"` on the `PATH`.
+    dot_path: PathBuf,
+    /// How long to wait for `dot` before killing it, defaults to 10 seconds.
+    timeout: Duration,
+}
+
+impl DotProcessRenderer {
+    /// Returns a new `DotProcessRenderer` with the default `dot` path and
+    /// timeout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the path to the `dot` executable.
+    pub fn with_dot_path(mut self, dot_path: PathBuf) -> Self {
+        self.dot_path = dot_path;
+        self
+    }
+
+    /// Sets how long to wait for `dot` before killing it.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the path to the `dot` executable.
+    pub fn dot_path(&self) -> &Path {
+        &self.dot_path
+    }
+
+    /// Returns how long to wait for `dot` before killing it.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl Default for DotProcessRenderer {
+    fn default() -> Self {
+        Self {
+            dot_path: PathBuf::from("dot"),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl DotRenderer for DotProcessRenderer {
+    fn render(&self, dot_src_and_styles: &DotSrcAndStyles) -> Result<Svg, RenderError> {
+        let dot_path = &self.dot_path;
+        let mut child = Command::new(dot_path)
+            .arg("-Tsvg")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| RenderError::DotSpawn {
+                dot_path: dot_path.clone(),
+                error,
+            })?;
+
+        // stdin, stdout, and stderr are handled on separate threads, so that
+        // `dot` doesn't block on a full pipe while we wait on another.
+        let stdin_writer = child.stdin.take().map(|mut stdin| {
+            let dot_src = dot_src_and_styles.dot_src().to_string();
+            thread::spawn(move || {
+                // A write error means `dot` exited early, which is reported
+                // through its exit status and `stderr`.
+                let _ = stdin.write_all(dot_src.as_bytes());
+            })
+        });
+        let stdout_reader = child.stdout.take().map(|mut stdout| {
+            thread::spawn(move || {
+                let mut bytes = Vec::new();
+                stdout.read_to_end(&mut bytes).map(|_| bytes)
+            })
+        });
+        let stderr_reader = child.stderr.take().map(|mut stderr| {
+            thread::spawn(move || {
+                let mut bytes = Vec::new();
+                stderr.read_to_end(&mut bytes).map(|_| bytes)
+            })
+        });
+
+        let deadline = Instant::now() + self.timeout;
+        let exit_status = loop {
+            let exit_status = child.try_wait().map_err(|error| RenderError::DotWait {
+                dot_path: dot_path.clone(),
+                error,
+            })?;
+            match exit_status {
+                Some(exit_status) => break exit_status,
+                None if Instant::now() >= deadline => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(RenderError::DotTimeout {
+                        dot_path: dot_path.clone(),
+                        timeout: self.timeout,
+                    });
+                }
+                None => thread::sleep(POLL_INTERVAL),
+            }
+        };
+
+        if let Some(stdin_writer) = stdin_writer {
+            let _ = stdin_writer.join();
+        }
+        let stdout = stdout_reader
+            .and_then(|stdout_reader| stdout_reader.join().ok())
+            .and_then(Result::ok)
+            .unwrap_or_default();
+        let stderr = stderr_reader
+            .and_then(|stderr_reader| stderr_reader.join().ok())
+            .and_then(Result::ok)
+            .unwrap_or_default();
+
+        let dot_diagnostics = DotDiagnostic::parse_stderr(&String::from_utf8_lossy(&stderr));
+        let has_error = dot_diagnostics
+            .iter()
+            .any(|dot_diagnostic| dot_diagnostic.severity == DotDiagnosticSeverity::Error);
+        if !exit_status.success() || has_error {
+            return Err(RenderError::DotFailed {
+                exit_code: exit_status.code(),
+                dot_diagnostics,
+            });
+        }
+
+        String::from_utf8(stdout)
+            .map(Svg::new)
+            .map_err(RenderError::SvgNotUtf8)
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::Mutex;
+
+use dot_ix_model::common::DotSrcAndStyles;
+
+use crate::{DotDiagnostic, DotRenderer, RenderError, Svg};
+
+/// SVG that [`FakeDotRenderer`] returns when no SVG is set.
+const SVG_EMPTY: &str = r#"<svg width="8pt" height="8pt" viewBox="0.00 0.00 8.00 8.00" xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+<g id="graph0" class="graph" transform="scale(1 1) rotate(0) translate(4 4)">
+<title>G</title>
+</g>
+</svg>
+"#;
+
+/// [`DotRenderer`] for tests, which returns a preset SVG or error instead of
+/// running Graphviz.
+///
+/// The dot sources it is asked to render are recorded, and can be inspected
+/// with [`FakeDotRenderer::dot_srcs`].
+#[derive(Debug, Default)]
+pub struct FakeDotRenderer {
+    /// SVG to return, defaults to an empty graph.
+    svg: Option<Svg>,
+    /// `stderr` to fail with, parsed the same way as `dot`'s output.
+    stderr: Option<String>,
+    /// Dot sources that were rendered.
+    dot_srcs: Mutex<Vec<String>>,
+}
+
+impl FakeDotRenderer {
+    /// Returns a new `FakeDotRenderer` that renders an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the SVG to return from [`render`].
+    ///
+    /// [`render`]: DotRenderer::render
+    pub fn with_svg(mut self, svg: Svg) -> Self {
+        self.svg = Some(svg);
+        self
+    }
+
+    /// Sets the `stderr` to fail with, as if `dot` exited with code `1`.
+    pub fn with_stderr(mut self, stderr: String) -> Self {
+        self.stderr = Some(stderr);
+        self
+    }
+
+    /// Returns the dot sources that have been rendered, in order.
+    pub fn dot_srcs(&self) -> Vec<String> {
+        self.dot_srcs
+            .lock()
+            .map(|dot_srcs| dot_srcs.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl DotRenderer for FakeDotRenderer {
+    fn render(&self, dot_src_and_styles: &DotSrcAndStyles) -> Result<Svg, RenderError> {
+        if let Ok(mut dot_srcs) = self.dot_srcs.lock() {
+            dot_srcs.push(dot_src_and_styles.dot_src().to_string());
+        }
+
+        match self.stderr.as_deref() {
+            Some(stderr) => Err(RenderError::DotFailed {
+                exit_code: Some(1),
+                dot_diagnostics: DotDiagnostic::parse_stderr(stderr),
+            }),
+            None => Ok(self
+                .svg
+                .clone()
+                .unwrap_or_else(|| Svg::new(SVG_EMPTY.to_string()))),
+        }
+    }
+}
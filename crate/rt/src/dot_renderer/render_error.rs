@@ -0,0 +1,129 @@
+use std::{path::PathBuf, string::FromUtf8Error, time::Duration};
+
+use crate::{DotDiagnostic, DotDiagnosticSeverity};
+
+/// Errors when rendering dot source with a [`DotRenderer`].
+///
+/// [`DotRenderer`]: crate::DotRenderer
+#[derive(Debug, thiserror::Error)]
+pub enum RenderError {
+    /// Failed to run the `dot` executable.
+    #[error("Failed to run `{}`. Is Graphviz installed?", dot_path.display())]
+    DotSpawn {
+        /// Path to the `dot` executable.
+        dot_path: PathBuf,
+        /// The underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+    /// Failed to wait for the `dot` process to exit.
+    #[error("Failed to wait for `{}` to exit.", dot_path.display())]
+    DotWait {
+        /// Path to the `dot` executable.
+        dot_path: PathBuf,
+        /// The underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+    /// `dot` did not exit before the timeout, and was killed.
+    #[error("`{}` did not finish rendering within {timeout:?}.", dot_path.display())]
+    DotTimeout {
+        /// Path to the `dot` executable.
+        dot_path: PathBuf,
+        /// How long `dot` was given to render.
+        timeout: Duration,
+    },
+    /// `dot` failed to render the graph.
+    #[error("{}", dot_failed_display(*exit_code, dot_diagnostics))]
+    DotFailed {
+        /// Exit code of the `dot` process, if it exited normally.
+        exit_code: Option<i32>,
+        /// Errors and warnings parsed from `stderr`.
+        dot_diagnostics: Vec<DotDiagnostic>,
+    },
+    /// The rendered SVG is not valid UTF-8.
+    #[error("The SVG rendered by `dot` is not valid UTF-8.")]
+    SvgNotUtf8(#[source] FromUtf8Error),
+    /// Failed to read the Graphviz wasm module.
+    #[cfg(feature = "graphviz_wasm")]
+    #[error("Failed to read the Graphviz wasm module from `{}`.", wasm_path.display())]
+    GraphvizWasmRead {
+        /// Path to the Graphviz wasm module.
+        wasm_path: PathBuf,
+        /// The underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+    /// Failed to read the minification map for the Graphviz wasm module.
+    #[cfg(feature = "graphviz_wasm")]
+    #[error(
+        "Failed to read the Graphviz wasm minification map from `{}`.",
+        minification_map_path.display()
+    )]
+    GraphvizWasmMinificationMapRead {
+        /// Path to the minification map.
+        minification_map_path: PathBuf,
+        /// The underlying error.
+        #[source]
+        error: std::io::Error,
+    },
+    /// A line in the Graphviz wasm minification map is not a
+    /// `minified:original` pair.
+    #[cfg(feature = "graphviz_wasm")]
+    #[error(
+        "Line {line_number} of the Graphviz wasm minification map is not a `minified:original` pair."
+    )]
+    GraphvizWasmMinificationMapInvalid {
+        /// 1-based line number of the invalid line.
+        line_number: usize,
+    },
+    /// Failed to compile the Graphviz wasm module.
+    #[cfg(feature = "graphviz_wasm")]
+    #[error("Failed to compile the Graphviz wasm module.")]
+    GraphvizWasmCompile(#[source] wasmi::Error),
+    /// The Graphviz wasm module does not export a function that is needed to
+    /// render.
+    ///
+    /// The module needs to be a build of `@hpcc-js/wasm` whose exports are not
+    /// minified, or be loaded with the minification map for its exports.
+    #[cfg(feature = "graphviz_wasm")]
+    #[error("The Graphviz wasm module does not export `{export_name}`.")]
+    GraphvizWasmExportMissing {
+        /// Name of the missing export.
+        export_name: String,
+    },
+    /// Failed to instantiate the Graphviz wasm module.
+    #[cfg(feature = "graphviz_wasm")]
+    #[error("Failed to instantiate the Graphviz wasm module.")]
+    GraphvizWasmInstantiate(#[source] wasmi::Error),
+    /// The Graphviz wasm module trapped while rendering.
+    #[cfg(feature = "graphviz_wasm")]
+    #[error("Graphviz wasm module failed while rendering.")]
+    GraphvizWasmTrap {
+        /// The trap.
+        #[source]
+        error: wasmi::Error,
+        /// Output that Graphviz wrote to `stderr` before the trap.
+        stderr: String,
+    },
+}
+
+fn dot_failed_display(exit_code: Option<i32>, dot_diagnostics: &[DotDiagnostic]) -> String {
+    let exit_code = exit_code
+        .map(|exit_code| exit_code.to_string())
+        .unwrap_or_else(|| String::from("none"));
+    let dot_diagnostic_errors = dot_diagnostics
+        .iter()
+        .filter(|dot_diagnostic| dot_diagnostic.severity == DotDiagnosticSeverity::Error)
+        .map(|dot_diagnostic| dot_diagnostic.message.as_str())
+        .collect::<Vec<_>>();
+
+    if dot_diagnostic_errors.is_empty() {
+        format!("`dot` failed to render the graph (exit code: {exit_code}).")
+    } else {
+        let dot_diagnostic_errors = dot_diagnostic_errors.join("\n");
+        format!(
+            "`dot` failed to render the graph (exit code: {exit_code}):\n{dot_diagnostic_errors}"
+        )
+    }
+}
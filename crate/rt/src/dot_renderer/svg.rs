@@ -0,0 +1,48 @@
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+/// SVG rendered by a [`DotRenderer`]. `String` newtype.
+///
+/// [`DotRenderer`]: crate::DotRenderer
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Svg(String);
+
+impl Svg {
+    /// Returns a new `Svg`.
+    pub fn new(svg: String) -> Self {
+        Self(svg)
+    }
+
+    /// Returns the underlying string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for Svg {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Svg {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<String> for Svg {
+    fn from(inner: String) -> Self {
+        Self(inner)
+    }
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -0,0 +1,364 @@
+use std::{path::Path, sync::Arc};
+
+use dot_ix_model::common::{dot_src_and_styles::GraphvizImage, DotSrcAndStyles};
+use wasmi::{Engine, Extern, Func, Instance, Linker, Module, Store, Val};
+
+use crate::{DotDiagnostic, DotRenderer, RenderError, Svg};
+
+use self::{emscripten_env::EmscriptenState, minification_map::MinificationMap};
+
+mod emscripten_env;
+mod minification_map;
+
+/// Export that constructs a `CGraphviz` object.
+const EXPORT_CGRAPHVIZ_NEW: &str = "emscripten_bind_CGraphviz_CGraphviz_2";
+/// Export that renders dot source with a `CGraphviz` object.
+const EXPORT_CGRAPHVIZ_LAYOUT: &str = "emscripten_bind_CGraphviz_layout_3";
+/// Export that returns the last error message from Graphviz.
+const EXPORT_CGRAPHVIZ_LAST_ERROR: &str = "emscripten_bind_CGraphviz_lastError_0";
+/// Export that destroys a `CGraphviz` object.
+const EXPORT_CGRAPHVIZ_DESTROY: &str = "emscripten_bind_CGraphviz___destroy___0";
+
+/// [`DotRenderer`] that runs the Graphviz wasm module from [`@hpcc-js/wasm`]
+/// in an embedded wasm runtime.
+///
+/// This is the same Graphviz build that renders diagrams in the browser, so
+/// the SVG is identical to what `graphviz_dot_svg.js` renders, and does not
+/// need Graphviz to be installed.
+///
+/// # Vendoring
+///
+/// The wasm module is not bundled with `dot_ix`, and needs to be vendored
+/// from the `@hpcc-js/wasm` version used by `graphviz_dot_svg.js`, which is
+/// `2.20.0`, published at
+/// <https://cdn.jsdelivr.net/npm/@hpcc-js/wasm@2.20.0/dist/graphviz.js>.
+///
+/// `scripts/graphviz_wasm_vendor.mjs` loads that file in `node`, writes the
+/// wasm module that it instantiates to `graphviz.wasm` and its minification
+/// map to `graphviz.wasm.map`, prints the module's SHA-256 checksum to record
+/// alongside the vendored files, and writes the SVG that `graphviz.js` renders
+/// for the dot files in `workspace_tests/graphviz_wasm`, which the renderer's
+/// output is compared against.
+///
+/// # Minified Builds
+///
+/// The published module is an optimized emscripten build, whose import and
+/// export names are minified, e.g. `emscripten_bind_CGraphviz_layout_3` is
+/// exported under a one or two letter name, and the names are only mapped
+/// back in the minified JavaScript glue. Such a module needs a minification
+/// map, passed to [`from_path_with_minification_map`] or
+/// [`from_bytes_with_minification_map`]. `@hpcc-js/wasm` does not publish the
+/// map that `emcc --emit-minification-map` writes, so the vendor script
+/// recovers the names from the glue in `graphviz.js`.
+///
+/// Modules that are built without minifying names can be loaded with
+/// [`from_path`] or [`from_bytes`].
+///
+/// [`@hpcc-js/wasm`]: https://github.com/hpcc-systems/hpcc-js-wasm
+/// [`from_bytes`]: Self::from_bytes
+/// [`from_bytes_with_minification_map`]: Self::from_bytes_with_minification_map
+/// [`from_path`]: Self::from_path
+/// [`from_path_with_minification_map`]: Self::from_path_with_minification_map
+#[derive(Debug)]
+pub struct GraphvizWasmRenderer {
+    /// Engine that the module is compiled for.
+    engine: Engine,
+    /// The compiled Graphviz wasm module.
+    module: Module,
+    /// Original names of the module's minified imports and exports.
+    minification_map: Arc<MinificationMap>,
+}
+
+impl GraphvizWasmRenderer {
+    /// Returns a new `GraphvizWasmRenderer`, reading the Graphviz wasm module
+    /// from the given path.
+    pub fn from_path(wasm_path: &Path) -> Result<Self, RenderError> {
+        let wasm_bytes =
+            std::fs::read(wasm_path).map_err(|error| RenderError::GraphvizWasmRead {
+                wasm_path: wasm_path.to_path_buf(),
+                error,
+            })?;
+        Self::from_bytes(&wasm_bytes)
+    }
+
+    /// Returns a new `GraphvizWasmRenderer`, reading the Graphviz wasm module
+    /// and the emscripten minification map for its import and export names
+    /// from the given paths.
+    pub fn from_path_with_minification_map(
+        wasm_path: &Path,
+        minification_map_path: &Path,
+    ) -> Result<Self, RenderError> {
+        let wasm_bytes =
+            std::fs::read(wasm_path).map_err(|error| RenderError::GraphvizWasmRead {
+                wasm_path: wasm_path.to_path_buf(),
+                error,
+            })?;
+        let minification_map_src =
+            std::fs::read_to_string(minification_map_path).map_err(|error| {
+                RenderError::GraphvizWasmMinificationMapRead {
+                    minification_map_path: minification_map_path.to_path_buf(),
+                    error,
+                }
+            })?;
+        Self::from_bytes_with_minification_map(&wasm_bytes, &minification_map_src)
+    }
+
+    /// Returns a new `GraphvizWasmRenderer` from the bytes of the Graphviz
+    /// wasm module.
+    pub fn from_bytes(wasm_bytes: &[u8]) -> Result<Self, RenderError> {
+        Self::from_bytes_and_map(wasm_bytes, MinificationMap::default())
+    }
+
+    /// Returns a new `GraphvizWasmRenderer` from the bytes of the Graphviz
+    /// wasm module, and the emscripten minification map for its import and
+    /// export names.
+    ///
+    /// The map has one `minified:original` pair per line, as written by `emcc
+    /// --emit-minification-map`.
+    pub fn from_bytes_with_minification_map(
+        wasm_bytes: &[u8],
+        minification_map_src: &str,
+    ) -> Result<Self, RenderError> {
+        let minification_map =
+            MinificationMap::parse(minification_map_src).map_err(|line_number| {
+                RenderError::GraphvizWasmMinificationMapInvalid { line_number }
+            })?;
+        Self::from_bytes_and_map(wasm_bytes, minification_map)
+    }
+
+    fn from_bytes_and_map(
+        wasm_bytes: &[u8],
+        minification_map: MinificationMap,
+    ) -> Result<Self, RenderError> {
+        let engine = Engine::default();
+        let module = Module::new(&engine, wasm_bytes).map_err(RenderError::GraphvizWasmCompile)?;
+
+        [
+            EXPORT_CGRAPHVIZ_NEW,
+            EXPORT_CGRAPHVIZ_LAYOUT,
+            EXPORT_CGRAPHVIZ_DESTROY,
+            "malloc",
+        ]
+        .into_iter()
+        .try_for_each(|export_name| {
+            if module
+                .get_export(minification_map.minified(export_name))
+                .is_some()
+            {
+                Ok(())
+            } else {
+                Err(RenderError::GraphvizWasmExportMissing {
+                    export_name: export_name.to_string(),
+                })
+            }
+        })?;
+
+        Ok(Self {
+            engine,
+            module,
+            minification_map: Arc::new(minification_map),
+        })
+    }
+
+    /// Instantiates the module in a new store.
+    ///
+    /// Each render uses a new instance, so that memory from a previous render,
+    /// including after a trap, does not affect later renders.
+    fn instantiate(&self) -> Result<(Store<EmscriptenState>, Instance), RenderError> {
+        let mut store = Store::new(
+            &self.engine,
+            EmscriptenState::new(Arc::clone(&self.minification_map)),
+        );
+        let linker: Linker<EmscriptenState> =
+            emscripten_env::linker_build(&mut store, &self.module)
+                .map_err(RenderError::GraphvizWasmInstantiate)?;
+        let instance = linker
+            .instantiate(&mut store, &self.module)
+            .and_then(|instance_pre| instance_pre.start(&mut store))
+            .map_err(RenderError::GraphvizWasmInstantiate)?;
+
+        let minification_map = &self.minification_map;
+        if let Some(memory) = instance.get_memory(&store, minification_map.minified("memory")) {
+            store.data_mut().memory = Some(memory);
+        }
+        if let Some(wasm_call_ctors) =
+            instance.get_func(&store, minification_map.minified("__wasm_call_ctors"))
+        {
+            wasm_call_ctors
+                .call(&mut store, &[], &mut [])
+                .map_err(|error| trap_error(&store, error))?;
+        }
+
+        Ok((store, instance))
+    }
+}
+
+impl DotRenderer for GraphvizWasmRenderer {
+    fn render(&self, dot_src_and_styles: &DotSrcAndStyles) -> Result<Svg, RenderError> {
+        let dot_src = dot_src_and_styles.dot_src();
+        if dot_src.is_empty() {
+            return Ok(Svg::default());
+        }
+
+        let (mut store, instance) = self.instantiate()?;
+
+        // Same as `imageToFile` in `@hpcc-js/wasm`: Graphviz only needs the
+        // image size, so each image is written as an empty SVG of that size.
+        dot_src_and_styles
+            .opts()
+            .images()
+            .iter()
+            .for_each(|graphviz_image| {
+                store.data_mut().file_write(
+                    graphviz_image.path(),
+                    image_svg(graphviz_image).into_bytes(),
+                );
+            });
+
+        let layout_result = layout(&mut store, &instance, dot_src);
+        let (svg, last_error) = layout_result.map_err(|error| trap_error(&store, error))?;
+
+        match (svg.is_empty(), last_error) {
+            (true, Some(last_error)) if !last_error.is_empty() => Err(RenderError::DotFailed {
+                exit_code: None,
+                dot_diagnostics: DotDiagnostic::parse_stderr(&last_error),
+            }),
+            _ => Ok(Svg::new(svg)),
+        }
+    }
+}
+
+/// Runs `CGraphviz::layout`, returning the SVG and the last error message.
+fn layout(
+    store: &mut Store<EmscriptenState>,
+    instance: &Instance,
+    dot_src: &str,
+) -> Result<(String, Option<String>), wasmi::Error> {
+    let cgraphviz_new = export_func(store, instance, EXPORT_CGRAPHVIZ_NEW)?;
+    let cgraphviz_layout = export_func(store, instance, EXPORT_CGRAPHVIZ_LAYOUT)?;
+    let cgraphviz_destroy = export_func(store, instance, EXPORT_CGRAPHVIZ_DESTROY)?;
+
+    // `yInvert` and `nop` are both `0`, which is the default in `@hpcc-js/wasm`.
+    let cgraphviz = func_call_i32(store, cgraphviz_new, &[Val::I32(0), Val::I32(0)])?;
+
+    let dot_src_ptr = cstr_alloc(store, instance, dot_src)?;
+    let format_ptr = cstr_alloc(store, instance, "svg")?;
+    let engine_ptr = cstr_alloc(store, instance, "dot")?;
+    let svg_ptr = func_call_i32(
+        store,
+        cgraphviz_layout,
+        &[
+            Val::I32(cgraphviz),
+            Val::I32(dot_src_ptr),
+            Val::I32(format_ptr),
+            Val::I32(engine_ptr),
+        ],
+    )?;
+    let svg = cstr_read(store, instance, svg_ptr)?;
+
+    // `lastError` is static in some versions of `@hpcc-js/wasm`, and takes no
+    // parameters.
+    let export_name = store
+        .data()
+        .minification_map
+        .minified(EXPORT_CGRAPHVIZ_LAST_ERROR)
+        .to_string();
+    let last_error = match instance.get_func(&*store, &export_name) {
+        Some(cgraphviz_last_error) => {
+            let params = if cgraphviz_last_error.ty(&*store).params().is_empty() {
+                Vec::new()
+            } else {
+                vec![Val::I32(cgraphviz)]
+            };
+            let last_error_ptr = func_call_i32(store, cgraphviz_last_error, &params)?;
+            Some(cstr_read(store, instance, last_error_ptr)?)
+        }
+        None => None,
+    };
+
+    cgraphviz_destroy.call(&mut *store, &[Val::I32(cgraphviz)], &mut [])?;
+
+    Ok((svg, last_error))
+}
+
+/// Returns the SVG that Graphviz reads to find the size of an image.
+fn image_svg(graphviz_image: &GraphvizImage) -> String {
+    let width = graphviz_image.width();
+    let height = graphviz_image.height();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n\
+        <svg width=\"{width}\" height=\"{height}\"></svg>"
+    )
+}
+
+fn export_func(
+    store: &Store<EmscriptenState>,
+    instance: &Instance,
+    export_name: &str,
+) -> Result<Func, wasmi::Error> {
+    instance
+        .get_export(store, store.data().minification_map.minified(export_name))
+        .and_then(Extern::into_func)
+        .ok_or_else(|| wasmi::Error::new(format!("`{export_name}` is not exported.")))
+}
+
+fn func_call_i32(
+    store: &mut Store<EmscriptenState>,
+    func: Func,
+    params: &[Val],
+) -> Result<i32, wasmi::Error> {
+    let mut results = [Val::I32(0)];
+    func.call(&mut *store, params, &mut results)?;
+    results[0]
+        .i32()
+        .ok_or_else(|| wasmi::Error::new("Expected an `i32` result."))
+}
+
+/// Copies a string into memory allocated with `malloc`, with a NUL terminator.
+///
+/// The memory is not freed, as the instance is dropped after rendering.
+fn cstr_alloc(
+    store: &mut Store<EmscriptenState>,
+    instance: &Instance,
+    s: &str,
+) -> Result<i32, wasmi::Error> {
+    let malloc = export_func(store, instance, "malloc")?;
+    let mut bytes = Vec::with_capacity(s.len() + 1);
+    bytes.extend_from_slice(s.as_bytes());
+    bytes.push(0);
+
+    let ptr = func_call_i32(store, malloc, &[Val::I32(bytes.len() as i32)])?;
+    if ptr == 0 {
+        return Err(wasmi::Error::new("`malloc` returned a null pointer."));
+    }
+    let memory = store
+        .data()
+        .memory
+        .ok_or_else(|| wasmi::Error::new("Graphviz wasm module has no memory."))?;
+    memory
+        .write(&mut *store, ptr as u32 as usize, &bytes)
+        .map_err(wasmi::Error::from)?;
+    Ok(ptr)
+}
+
+fn cstr_read(
+    store: &Store<EmscriptenState>,
+    instance: &Instance,
+    ptr: i32,
+) -> Result<String, wasmi::Error> {
+    if ptr == 0 {
+        return Ok(String::new());
+    }
+    let memory = instance
+        .get_memory(store, store.data().minification_map.minified("memory"))
+        .or(store.data().memory)
+        .ok_or_else(|| wasmi::Error::new("Graphviz wasm module has no memory."))?;
+    emscripten_env::cstr_read(memory.data(store), ptr)
+}
+
+fn trap_error(store: &Store<EmscriptenState>, error: wasmi::Error) -> RenderError {
+    RenderError::GraphvizWasmTrap {
+        error,
+        stderr: String::from_utf8_lossy(&store.data().stderr).into_owned(),
+    }
+}
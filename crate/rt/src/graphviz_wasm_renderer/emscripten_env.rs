@@ -0,0 +1,727 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use wasmi::{
+    core::{HostError, Pages, ValType},
+    Caller, Error, Extern, ExternType, Global, Linker, Memory, Module, Store, Table, Val,
+};
+
+use super::minification_map::MinificationMap;
+
+/// WASI `errno` for a file that doesn't exist, `ENOENT`.
+const ERRNO_NOENT: i32 = 44;
+/// WASI `errno` for a bad file descriptor, `EBADF`.
+const ERRNO_BADF: i32 = 8;
+/// WASI `errno` for a file descriptor that is not a terminal, `ENOTTY`.
+const ERRNO_NOTTY: i32 = 59;
+/// WASI `errno` for an invalid argument, `EINVAL`.
+const ERRNO_INVAL: i32 = 28;
+
+/// First file descriptor used for files, after `stdin`, `stdout`, and `stderr`.
+const FD_FILE_FIRST: i32 = 3;
+
+/// Size of a wasm memory page.
+const PAGE_SIZE: u64 = 65_536;
+
+/// Largest heap size that emscripten allows, 2 GiB.
+const HEAP_MAX: u64 = 2 * 1024 * 1024 * 1024;
+
+/// State for the host functions that the emscripten build of Graphviz
+/// imports, which are normally provided by the emscripten JavaScript glue.
+///
+/// Files are held in memory, similar to emscripten's `MEMFS`.
+#[derive(Debug, Default)]
+pub(crate) struct EmscriptenState {
+    /// The instance's linear memory.
+    pub(crate) memory: Option<Memory>,
+    /// Original names of the module's minified imports and exports.
+    pub(crate) minification_map: Arc<MinificationMap>,
+    /// Bytes written to `stderr`.
+    pub(crate) stderr: Vec<u8>,
+    /// Contents of each file, keyed by the path without a leading `/`.
+    pub(crate) files: HashMap<String, Vec<u8>>,
+    /// Open files, keyed by file descriptor.
+    open_files: HashMap<i32, OpenFile>,
+    /// Next file descriptor to return from `open`.
+    fd_next: i32,
+}
+
+/// A file opened by the wasm module.
+#[derive(Debug)]
+struct OpenFile {
+    /// Path of the file, used to look up its contents.
+    path: String,
+    /// Offset of the next read or write.
+    position: usize,
+}
+
+impl EmscriptenState {
+    /// Returns a new `EmscriptenState` for a module with the given
+    /// minification map.
+    pub(crate) fn new(minification_map: Arc<MinificationMap>) -> Self {
+        Self {
+            minification_map,
+            ..Self::default()
+        }
+    }
+
+    /// Writes a file that the wasm module can read.
+    pub(crate) fn file_write(&mut self, path: &str, contents: Vec<u8>) {
+        self.files
+            .insert(path_normalize(path).to_string(), contents);
+    }
+}
+
+/// Thrown by `longjmp` and C++ `throw`, and caught by `invoke_*` functions.
+///
+/// Emscripten implements `setjmp` / `longjmp` and C++ exceptions by throwing a
+/// JavaScript exception, which is caught by the `invoke_*` import that called
+/// the function containing `setjmp` or `try`.
+#[derive(Debug)]
+struct EmscriptenThrow;
+
+impl fmt::Display for EmscriptenThrow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "`longjmp` or C++ exception was not caught".fmt(f)
+    }
+}
+
+impl HostError for EmscriptenThrow {}
+
+type HostFn = fn(&mut Caller<'_, EmscriptenState>, &[Val], &mut [Val]) -> Result<(), Error>;
+
+/// Returns a linker that defines every import of the module.
+///
+/// Imports that are not used by Graphviz trap when called, so that a newer
+/// Graphviz build still instantiates, and fails with the import's name if it
+/// needs it.
+pub(crate) fn linker_build(
+    store: &mut Store<EmscriptenState>,
+    module: &Module,
+) -> Result<Linker<EmscriptenState>, Error> {
+    let mut linker = Linker::<EmscriptenState>::new(module.engine());
+
+    module.imports().try_for_each(|import| {
+        let (import_module, import_name) = (import.module(), import.name());
+        match import.ty() {
+            ExternType::Func(func_type) => {
+                let result_types = func_type.results().to_vec();
+                let import_name_original = store.data().minification_map.original(import_name);
+                let host_fn = host_fn(import_name_original);
+                let import_path = if import_name_original == import_name {
+                    format!("`{import_module}.{import_name}`")
+                } else {
+                    format!("`{import_module}.{import_name}` (`{import_name_original}`)")
+                };
+                linker.func_new(
+                    import_module,
+                    import_name,
+                    func_type.clone(),
+                    move |mut caller, params, results| {
+                        results
+                            .iter_mut()
+                            .zip(result_types.iter())
+                            .for_each(|(result, result_type)| *result = Val::default(*result_type));
+                        match host_fn {
+                            Some(host_fn) => host_fn(&mut caller, params, results),
+                            None => Err(Error::new(format!(
+                                "Graphviz called {import_path}, which is not supported."
+                            ))),
+                        }
+                    },
+                )?;
+            }
+            ExternType::Memory(memory_type) => {
+                let memory = Memory::new(&mut *store, *memory_type)?;
+                store.data_mut().memory = Some(memory);
+                linker.define(import_module, import_name, memory)?;
+            }
+            ExternType::Table(table_type) => {
+                let table =
+                    Table::new(&mut *store, *table_type, Val::default(table_type.element()))?;
+                linker.define(import_module, import_name, table)?;
+            }
+            ExternType::Global(global_type) => {
+                let global = Global::new(
+                    &mut *store,
+                    Val::default(global_type.content()),
+                    global_type.mutability(),
+                );
+                linker.define(import_module, import_name, global)?;
+            }
+        }
+        Ok::<_, Error>(())
+    })?;
+
+    Ok(linker)
+}
+
+/// Returns the host function for an import, or `None` if it is not supported.
+fn host_fn(import_name: &str) -> Option<HostFn> {
+    let host_fn: HostFn = match import_name {
+        "fd_write" => fd_write,
+        "fd_read" => fd_read,
+        "fd_seek" => fd_seek,
+        "fd_close" => fd_close,
+        "fd_fdstat_get" => fd_fdstat_get,
+        "__syscall_openat" => syscall_openat,
+        "__syscall_fstat64" => syscall_fstat64,
+        "__syscall_stat64" | "__syscall_lstat64" => syscall_stat64,
+        "__syscall_newfstatat" => syscall_newfstatat,
+        "__syscall_faccessat" => syscall_faccessat,
+        "__syscall_getcwd" => syscall_getcwd,
+        "__syscall_ioctl" => |_, _, results| result_i32(results, -ERRNO_NOTTY),
+        "__syscall_fcntl64" => |_, _, _| Ok(()),
+        "emscripten_resize_heap" => emscripten_resize_heap,
+        "emscripten_get_heap_max" => |_, _, results| result_i32(results, HEAP_MAX as i32),
+        "emscripten_memcpy_big" | "emscripten_memcpy_js" | "_emscripten_memcpy_js" => {
+            emscripten_memcpy
+        }
+        "_emscripten_get_now_is_monotonic" => |_, _, results| result_i32(results, 1),
+        "random_get" | "getentropy" => random_get,
+        "proc_exit" | "exit" => |_, params, _| Err(Error::i32_exit(param_i32(params, 0)?)),
+        "__assert_fail" => assert_fail,
+        "abort" | "_abort_js" => |_, _, _| Err(Error::new("Graphviz aborted.")),
+        "__cxa_throw" | "_emscripten_throw_longjmp" | "emscripten_longjmp" => {
+            |_, _, _| Err(Error::host(EmscriptenThrow))
+        }
+        // Time is fixed so that the output is deterministic.
+        "clock_time_get" => clock_time_get,
+        "environ_sizes_get" => environ_sizes_get,
+        "emscripten_date_now"
+        | "emscripten_get_now"
+        | "_emscripten_date_now"
+        | "_tzset_js"
+        | "_localtime_js"
+        | "_gmtime_js"
+        | "_mktime_js"
+        | "_timegm_js"
+        | "environ_get"
+        | "emscripten_notify_memory_growth" => |_, _, _| Ok(()),
+        import_name if import_name.starts_with("invoke_") => invoke,
+        _ => return None,
+    };
+
+    Some(host_fn)
+}
+
+fn clock_time_get(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    // The time pointer is the last parameter, whether or not `precision` is
+    // split into two `i32`s.
+    let time_ptr = param_i32(params, params.len().saturating_sub(1))?;
+    memory_write(caller, time_ptr, &0u64.to_le_bytes())?;
+    result_i32(results, 0)
+}
+
+fn environ_sizes_get(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let (environ_count_ptr, environ_buf_size_ptr) = (param_i32(params, 0)?, param_i32(params, 1)?);
+    memory_write(caller, environ_count_ptr, &0u32.to_le_bytes())?;
+    memory_write(caller, environ_buf_size_ptr, &0u32.to_le_bytes())?;
+    result_i32(results, 0)
+}
+
+fn fd_write(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let (fd, iovs_ptr, iovs_len, written_ptr) = (
+        param_i32(params, 0)?,
+        param_i32(params, 1)?,
+        param_i32(params, 2)?,
+        param_i32(params, 3)?,
+    );
+    let bytes = iovs_read(caller, iovs_ptr, iovs_len)?;
+    let written = bytes.len();
+
+    let state = caller.data_mut();
+    match fd {
+        // `stdout` is not used when rendering to a string.
+        1 => {}
+        2 => state.stderr.extend_from_slice(&bytes),
+        fd => {
+            let Some(open_file) = state.open_files.get_mut(&fd) else {
+                return result_i32(results, ERRNO_BADF);
+            };
+            let contents = state.files.entry(open_file.path.clone()).or_default();
+            let end = open_file.position + bytes.len();
+            if contents.len() < end {
+                contents.resize(end, 0);
+            }
+            contents[open_file.position..end].copy_from_slice(&bytes);
+            open_file.position = end;
+        }
+    }
+
+    memory_write(caller, written_ptr, &(written as u32).to_le_bytes())?;
+    result_i32(results, 0)
+}
+
+fn fd_read(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let (fd, iovs_ptr, iovs_len, read_ptr) = (
+        param_i32(params, 0)?,
+        param_i32(params, 1)?,
+        param_i32(params, 2)?,
+        param_i32(params, 3)?,
+    );
+
+    let mut read_total = 0u32;
+    for iov_index in 0..iovs_len {
+        let (buf_ptr, buf_len) = iov_read(caller, iovs_ptr, iov_index)?;
+        let state = caller.data_mut();
+        let Some(open_file) = state.open_files.get_mut(&fd) else {
+            return result_i32(results, ERRNO_BADF);
+        };
+        let contents = state
+            .files
+            .get(&open_file.path)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let start = open_file.position.min(contents.len());
+        let end = (start + buf_len as usize).min(contents.len());
+        let bytes = contents[start..end].to_vec();
+        open_file.position = end;
+
+        memory_write(caller, buf_ptr, &bytes)?;
+        read_total += bytes.len() as u32;
+        if bytes.len() < buf_len as usize {
+            break;
+        }
+    }
+
+    memory_write(caller, read_ptr, &read_total.to_le_bytes())?;
+    result_i32(results, 0)
+}
+
+fn fd_seek(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    // `offset` is an `i64`, or split into two `i32`s when the module was built
+    // without `WASM_BIGINT`.
+    let fd = param_i32(params, 0)?;
+    let (offset, whence, position_ptr) = match params.get(1) {
+        Some(Val::I64(offset)) => (*offset, param_i32(params, 2)?, param_i32(params, 3)?),
+        _ => {
+            let offset_low = param_i32(params, 1)? as u32 as i64;
+            let offset_high = param_i32(params, 2)? as i64;
+            (
+                offset_low | (offset_high << 32),
+                param_i32(params, 3)?,
+                param_i32(params, 4)?,
+            )
+        }
+    };
+
+    let state = caller.data_mut();
+    let Some(open_file) = state.open_files.get_mut(&fd) else {
+        return result_i32(results, ERRNO_BADF);
+    };
+    let file_len = state
+        .files
+        .get(&open_file.path)
+        .map(Vec::len)
+        .unwrap_or_default();
+    let base = match whence {
+        0 => 0,
+        1 => open_file.position as i64,
+        2 => file_len as i64,
+        _ => return result_i32(results, ERRNO_INVAL),
+    };
+    let Ok(position) = usize::try_from(base + offset) else {
+        return result_i32(results, ERRNO_INVAL);
+    };
+    open_file.position = position;
+
+    memory_write(caller, position_ptr, &(position as u64).to_le_bytes())?;
+    result_i32(results, 0)
+}
+
+fn fd_close(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let fd = param_i32(params, 0)?;
+    caller.data_mut().open_files.remove(&fd);
+    result_i32(results, 0)
+}
+
+fn fd_fdstat_get(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let (fd, fdstat_ptr) = (param_i32(params, 0)?, param_i32(params, 1)?);
+    // `__WASI_FILETYPE_CHARACTER_DEVICE` for stdio, and
+    // `__WASI_FILETYPE_REGULAR_FILE` for files.
+    let file_type = if fd < FD_FILE_FIRST { 2u8 } else { 4u8 };
+    let mut fdstat = [0u8; 24];
+    fdstat[0] = file_type;
+    memory_write(caller, fdstat_ptr, &fdstat)?;
+    result_i32(results, 0)
+}
+
+fn syscall_openat(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    /// `O_CREAT` in emscripten's musl.
+    const O_CREAT: i32 = 0o100;
+
+    let (path_ptr, flags) = (param_i32(params, 1)?, param_i32(params, 2)?);
+    let path = path_normalize(&memory_read_cstr(caller, path_ptr)?).to_string();
+
+    let state = caller.data_mut();
+    if !state.files.contains_key(&path) {
+        if flags & O_CREAT == 0 {
+            return result_i32(results, -ERRNO_NOENT);
+        }
+        state.files.insert(path.clone(), Vec::new());
+    }
+
+    let fd = state.fd_next.max(FD_FILE_FIRST);
+    state.fd_next = fd + 1;
+    state.open_files.insert(fd, OpenFile { path, position: 0 });
+    result_i32(results, fd)
+}
+
+fn syscall_fstat64(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let (fd, stat_ptr) = (param_i32(params, 0)?, param_i32(params, 1)?);
+    let state = caller.data();
+    let file_len = match state.open_files.get(&fd) {
+        Some(open_file) => state.files.get(&open_file.path).map(Vec::len),
+        None if fd < FD_FILE_FIRST => Some(0),
+        None => None,
+    };
+    stat_write(caller, stat_ptr, file_len, results)
+}
+
+fn syscall_stat64(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let (path_ptr, stat_ptr) = (param_i32(params, 0)?, param_i32(params, 1)?);
+    let path = memory_read_cstr(caller, path_ptr)?;
+    let file_len = caller.data().files.get(path_normalize(&path)).map(Vec::len);
+    stat_write(caller, stat_ptr, file_len, results)
+}
+
+fn syscall_newfstatat(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let (path_ptr, stat_ptr) = (param_i32(params, 1)?, param_i32(params, 2)?);
+    let path = memory_read_cstr(caller, path_ptr)?;
+    let file_len = caller.data().files.get(path_normalize(&path)).map(Vec::len);
+    stat_write(caller, stat_ptr, file_len, results)
+}
+
+fn syscall_faccessat(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let path_ptr = param_i32(params, 1)?;
+    let path = memory_read_cstr(caller, path_ptr)?;
+    if caller.data().files.contains_key(path_normalize(&path)) {
+        result_i32(results, 0)
+    } else {
+        result_i32(results, -ERRNO_NOENT)
+    }
+}
+
+fn syscall_getcwd(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let (buf_ptr, buf_len) = (param_i32(params, 0)?, param_i32(params, 1)?);
+    if buf_len < 2 {
+        return result_i32(results, -ERRNO_INVAL);
+    }
+    memory_write(caller, buf_ptr, b"/\0")?;
+    result_i32(results, 2)
+}
+
+/// Writes the size and mode of a file into a `struct stat`, using emscripten's
+/// layout.
+fn stat_write(
+    caller: &mut Caller<'_, EmscriptenState>,
+    stat_ptr: i32,
+    file_len: Option<usize>,
+    results: &mut [Val],
+) -> Result<(), Error> {
+    /// `S_IFREG | 0o644`.
+    const MODE_FILE: u32 = 0o100_644;
+
+    let Some(file_len) = file_len else {
+        return result_i32(results, -ERRNO_NOENT);
+    };
+    let mut stat = [0u8; 96];
+    stat[4..8].copy_from_slice(&MODE_FILE.to_le_bytes());
+    stat[8..12].copy_from_slice(&1u32.to_le_bytes());
+    stat[24..32].copy_from_slice(&(file_len as u64).to_le_bytes());
+    stat[32..36].copy_from_slice(&4096u32.to_le_bytes());
+    memory_write(caller, stat_ptr, &stat)?;
+    result_i32(results, 0)
+}
+
+fn emscripten_resize_heap(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let requested_size = u64::from(param_i32(params, 0)? as u32);
+    let memory = memory(caller)?;
+    let current_size = memory.data(&*caller).len() as u64;
+    if requested_size > HEAP_MAX {
+        return result_i32(results, 0);
+    }
+    if requested_size <= current_size {
+        return result_i32(results, 1);
+    }
+
+    // Grow by at least 20% like emscripten, to reduce how often memory grows.
+    let size_target = requested_size
+        .max(current_size + current_size / 5)
+        .min(HEAP_MAX);
+    let pages_additional = (size_target - current_size).div_ceil(PAGE_SIZE);
+    let grown = Pages::new(pages_additional as u32)
+        .map(|pages| memory.grow(&mut *caller, pages).is_ok())
+        .unwrap_or(false);
+    result_i32(results, i32::from(grown))
+}
+
+fn emscripten_memcpy(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    _results: &mut [Val],
+) -> Result<(), Error> {
+    let (dest, src, len) = (
+        param_i32(params, 0)? as u32 as usize,
+        param_i32(params, 1)? as u32 as usize,
+        param_i32(params, 2)? as u32 as usize,
+    );
+    let memory = memory(caller)?;
+    let data = memory.data_mut(&mut *caller);
+    if src.checked_add(len).is_none_or(|end| end > data.len())
+        || dest.checked_add(len).is_none_or(|end| end > data.len())
+    {
+        return Err(Error::new("`memcpy` out of bounds."));
+    }
+    data.copy_within(src..src + len, dest);
+    Ok(())
+}
+
+fn random_get(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    // Zeroes keep the output deterministic.
+    let (buf_ptr, buf_len) = (param_i32(params, 0)?, param_i32(params, 1)?);
+    memory_write(caller, buf_ptr, &vec![0u8; buf_len as u32 as usize])?;
+    result_i32(results, 0)
+}
+
+fn assert_fail(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    _results: &mut [Val],
+) -> Result<(), Error> {
+    let condition = memory_read_cstr(caller, param_i32(params, 0)?)?;
+    let file_name = memory_read_cstr(caller, param_i32(params, 1)?)?;
+    let line = param_i32(params, 2)?;
+    let func_name = memory_read_cstr(caller, param_i32(params, 3)?)?;
+    Err(Error::new(format!(
+        "{file_name}:{line}: {func_name}: Assertion `{condition}` failed."
+    )))
+}
+
+/// Calls a function in the indirect function table, catching `longjmp`s and
+/// C++ exceptions.
+///
+/// This is the same as emscripten's `invoke_*` JavaScript functions.
+fn invoke(
+    caller: &mut Caller<'_, EmscriptenState>,
+    params: &[Val],
+    results: &mut [Val],
+) -> Result<(), Error> {
+    let func_index = param_i32(params, 0)? as u32;
+    let func = export_get(caller, "__indirect_function_table")
+        .and_then(Extern::into_table)
+        .and_then(|table| table.get(&*caller, func_index))
+        .and_then(|val| match val {
+            Val::FuncRef(func_ref) => func_ref.func().copied(),
+            _ => None,
+        })
+        .ok_or_else(|| Error::new(format!("No function at table index {func_index}.")))?;
+
+    let stack_pointer = export_call(caller, &["stackSave", "emscripten_stack_get_current"], &[])?;
+    match func.call(&mut *caller, &params[1..], results) {
+        Ok(()) => Ok(()),
+        Err(error) if error.downcast_ref::<EmscriptenThrow>().is_some() => {
+            if let Some(stack_pointer) = stack_pointer {
+                export_call(
+                    caller,
+                    &["stackRestore", "_emscripten_stack_restore"],
+                    &[stack_pointer],
+                )?;
+            }
+            export_call(caller, &["setThrew"], &[Val::I32(1), Val::I32(0)])?;
+            results.iter_mut().for_each(|result| {
+                *result = Val::default(result.ty());
+            });
+            Ok(())
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Calls the first of the named exports that exists, returning its first
+/// result.
+fn export_call(
+    caller: &mut Caller<'_, EmscriptenState>,
+    export_names: &[&str],
+    params: &[Val],
+) -> Result<Option<Val>, Error> {
+    let Some(func) = export_names
+        .iter()
+        .find_map(|export_name| export_get(caller, export_name))
+        .and_then(Extern::into_func)
+    else {
+        return Ok(None);
+    };
+
+    let mut results = func
+        .ty(&*caller)
+        .results()
+        .iter()
+        .map(|result_type| Val::default(*result_type))
+        .collect::<Vec<_>>();
+    func.call(&mut *caller, params, &mut results)?;
+    Ok(results.into_iter().next())
+}
+
+/// Returns the export with the given original name.
+fn export_get(caller: &Caller<'_, EmscriptenState>, export_name: &str) -> Option<Extern> {
+    let export_name = caller.data().minification_map.minified(export_name);
+    caller.get_export(export_name)
+}
+
+fn memory(caller: &Caller<'_, EmscriptenState>) -> Result<Memory, Error> {
+    export_get(caller, "memory")
+        .and_then(Extern::into_memory)
+        .or(caller.data().memory)
+        .ok_or_else(|| Error::new("Graphviz wasm module has no memory."))
+}
+
+fn memory_write(
+    caller: &mut Caller<'_, EmscriptenState>,
+    ptr: i32,
+    bytes: &[u8],
+) -> Result<(), Error> {
+    let memory = memory(caller)?;
+    memory
+        .write(&mut *caller, ptr as u32 as usize, bytes)
+        .map_err(Error::from)
+}
+
+fn memory_read_cstr(caller: &Caller<'_, EmscriptenState>, ptr: i32) -> Result<String, Error> {
+    let memory = memory(caller)?;
+    cstr_read(memory.data(caller), ptr)
+}
+
+/// Reads a NUL terminated UTF-8 string from memory.
+pub(crate) fn cstr_read(data: &[u8], ptr: i32) -> Result<String, Error> {
+    let start = ptr as u32 as usize;
+    let bytes = data
+        .get(start..)
+        .ok_or_else(|| Error::new("String pointer out of bounds."))?;
+    let len = bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(bytes.len());
+    Ok(String::from_utf8_lossy(&bytes[..len]).into_owned())
+}
+
+/// Reads the pointer and length of the `iov_index`th `iovec`.
+fn iov_read(
+    caller: &Caller<'_, EmscriptenState>,
+    iovs_ptr: i32,
+    iov_index: i32,
+) -> Result<(i32, u32), Error> {
+    let memory = memory(caller)?;
+    let mut iov = [0u8; 8];
+    memory
+        .read(
+            caller,
+            (iovs_ptr as u32 + 8 * iov_index as u32) as usize,
+            &mut iov,
+        )
+        .map_err(Error::from)?;
+    let buf_ptr = i32::from_le_bytes([iov[0], iov[1], iov[2], iov[3]]);
+    let buf_len = u32::from_le_bytes([iov[4], iov[5], iov[6], iov[7]]);
+    Ok((buf_ptr, buf_len))
+}
+
+/// Reads the bytes of all `iovec`s.
+fn iovs_read(
+    caller: &Caller<'_, EmscriptenState>,
+    iovs_ptr: i32,
+    iovs_len: i32,
+) -> Result<Vec<u8>, Error> {
+    let memory = memory(caller)?;
+    let data = memory.data(caller);
+    (0..iovs_len).try_fold(Vec::new(), |mut bytes, iov_index| {
+        let (buf_ptr, buf_len) = iov_read(caller, iovs_ptr, iov_index)?;
+        let start = buf_ptr as u32 as usize;
+        let buf = data
+            .get(start..start + buf_len as usize)
+            .ok_or_else(|| Error::new("`iovec` out of bounds."))?;
+        bytes.extend_from_slice(buf);
+        Ok(bytes)
+    })
+}
+
+fn param_i32(params: &[Val], index: usize) -> Result<i32, Error> {
+    params
+        .get(index)
+        .and_then(Val::i32)
+        .ok_or_else(|| Error::new(format!("Expected an `i32` parameter at index {index}.")))
+}
+
+fn result_i32(results: &mut [Val], value: i32) -> Result<(), Error> {
+    if let Some(result) = results
+        .first_mut()
+        .filter(|result| result.ty() == ValType::I32)
+    {
+        *result = Val::I32(value);
+    }
+    Ok(())
+}
+
+/// Returns the path relative to the root directory.
+fn path_normalize(path: &str) -> &str {
+    path.trim_start_matches("./").trim_start_matches('/')
+}
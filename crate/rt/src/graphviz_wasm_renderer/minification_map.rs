@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+/// Maps the minified import and export names of an emscripten build to their
+/// original names.
+///
+/// Emscripten minifies import and export names in optimized builds, e.g.
+/// `emscripten_bind_CGraphviz_layout_3` is exported as `Ea`. The map is
+/// written by `emcc --emit-minification-map <path>`, with one
+/// `minified:original` pair per line.
+///
+/// An empty map is used for builds that are not minified, and returns names
+/// unchanged.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct MinificationMap {
+    /// Original name for each minified name.
+    minified_to_original: HashMap<String, String>,
+    /// Minified name for each original name.
+    original_to_minified: HashMap<String, String>,
+}
+
+impl MinificationMap {
+    /// Parses a minification map written by emscripten.
+    ///
+    /// Returns the 1-based line number of the first line that is not a
+    /// `minified:original` pair on error.
+    pub(crate) fn parse(minification_map_src: &str) -> Result<Self, usize> {
+        minification_map_src
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .try_fold(
+                Self::default(),
+                |mut minification_map, (line_index, line)| {
+                    let (minified, original) = line
+                        .trim()
+                        .split_once(':')
+                        .filter(|(minified, original)| !minified.is_empty() && !original.is_empty())
+                        .ok_or(line_index + 1)?;
+
+                    minification_map
+                        .minified_to_original
+                        .insert(minified.to_string(), original.to_string());
+                    minification_map
+                        .original_to_minified
+                        .insert(original.to_string(), minified.to_string());
+                    Ok(minification_map)
+                },
+            )
+    }
+
+    /// Returns the original name of an import or export, or the name itself
+    /// if it is not minified.
+    pub(crate) fn original<'f>(&'f self, name: &'f str) -> &'f str {
+        self.minified_to_original
+            .get(name)
+            .map(String::as_str)
+            .unwrap_or(name)
+    }
+
+    /// Returns the name that an import or export is given in the module, or
+    /// the original name if it is not minified.
+    pub(crate) fn minified<'f>(&'f self, original: &'f str) -> &'f str {
+        self.original_to_minified
+            .get(original)
+            .map(String::as_str)
+            .unwrap_or(original)
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+
+/// Allocates unique IDs that are valid for `NodeId`, `EdgeId`, and `TagId`,
+/// when importing diagrams whose IDs may contain other characters.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct IdAllocator {
+    ids_used: HashSet<String>,
+}
+
+impl IdAllocator {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a valid and unused ID based on the proposed ID, and marks it as
+    /// used.
+    ///
+    /// Characters that are not valid are replaced with `_`, and `_2`, `_3`,
+    /// etc. are appended if the ID is already used.
+    pub(crate) fn alloc(&mut self, id_proposed: &str) -> String {
+        let id_base = Self::sanitize(id_proposed);
+        let id = if self.ids_used.contains(&id_base) {
+            (2..)
+                .map(|n| format!("{id_base}_{n}"))
+                .find(|id| !self.ids_used.contains(id))
+                .expect("Unused ID to be found.")
+        } else {
+            id_base
+        };
+
+        self.ids_used.insert(id.clone());
+        id
+    }
+
+    /// Marks the ID as used, returning `false` if it was already used.
+    pub(crate) fn claim(&mut self, id: &str) -> bool {
+        self.ids_used.insert(id.to_string())
+    }
+
+    /// Returns the ID with characters that are not valid replaced with `_`,
+    /// and a leading `_` if it doesn't begin with a letter or `_`.
+    pub(crate) fn sanitize(id: &str) -> String {
+        let mut id_sanitized = String::with_capacity(id.len() + 1);
+        if !id.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            id_sanitized.push('_');
+        }
+        id.chars().for_each(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                id_sanitized.push(c);
+            } else {
+                id_sanitized.push('_');
+            }
+        });
+        id_sanitized
+    }
+}
@@ -0,0 +1,180 @@
+use std::fmt;
+
+use dot_ix_model::{
+    common::{GraphvizDotTheme, TagId},
+    info_graph::InfoGraph,
+    theme::{ElCssClasses, Theme, ThemeWarnings},
+};
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{InfoGraphDot, TailwindCssGen};
+
+/// Hack to get Chrome/Edge to not display black box around focused nodes.
+pub(crate) const OUTLINE_NONE: &str = "outline-none";
+
+/// Theme classes for each element of an `InfoGraph`, including the classes
+/// applied when a tag is focused.
+///
+/// This is shared by renderers that produce the same elements as Graphviz.
+pub(crate) struct InfoGraphClasses<'graph> {
+    /// Classes for each node, edge, and tag from the diagram theme.
+    pub(crate) el_css_classes: ElCssClasses,
+    /// Classes for each element when a tag is focused.
+    pub(crate) tag_el_css_classes_map: IndexMap<&'graph TagId, ElCssClasses>,
+    /// Warnings detected while computing the classes.
+    pub(crate) theme_warnings: ThemeWarnings,
+}
+
+impl<'graph> InfoGraphClasses<'graph> {
+    /// Computes the diagram theme and tag focus classes for each element.
+    pub(crate) fn new(info_graph: &'graph InfoGraph, info_graph_dot: &InfoGraphDot<'_>) -> Self {
+        let diagram_theme = info_graph.theme();
+        let (el_css_classes, diagram_theme_warnings) = diagram_theme.el_css_classes(info_graph_dot);
+
+        // tag styles per tag
+        let tag_styles_focus = info_graph.tag_styles_focus();
+        let (tag_el_css_classes_map, theme_warnings) = info_graph.tags().keys().fold(
+            (
+                IndexMap::<&TagId, ElCssClasses>::new(),
+                diagram_theme_warnings,
+            ),
+            |(mut tag_el_css_classes_map_acc, mut theme_warnings_acc), tag_id| {
+                let tag_theme = tag_styles_focus
+                    .get(tag_id)
+                    .cloned()
+                    .map(Theme::from)
+                    .unwrap_or_else(Theme::tag_base);
+                let (tag_el_css_classes, tag_theme_warnings) =
+                    tag_theme.tag_el_css_classes(info_graph_dot, diagram_theme, tag_id);
+
+                tag_el_css_classes_map_acc.insert(tag_id, tag_el_css_classes);
+                theme_warnings_acc.extend(tag_theme_warnings.into_inner());
+
+                (tag_el_css_classes_map_acc, theme_warnings_acc)
+            },
+        );
+
+        Self {
+            el_css_classes,
+            tag_el_css_classes_map,
+            theme_warnings,
+        }
+    }
+
+    /// Returns the classes of the element, followed by its classes for each of
+    /// the given tags.
+    #[cfg(feature = "info_graph_svg")]
+    pub(crate) fn el_classes(&self, el_id: &str, tag_ids: Option<&IndexSet<&TagId>>) -> String {
+        let mut el_classes = String::new();
+        self.el_classes_write(&mut el_classes, el_id, tag_ids)
+            .expect("Failed to write `el_classes` string.");
+        el_classes
+    }
+
+    /// Writes the classes of the element, followed by its classes for each of
+    /// the given tags.
+    pub(crate) fn el_classes_write<W>(
+        &self,
+        buffer: &mut W,
+        el_id: &str,
+        tag_ids: Option<&IndexSet<&TagId>>,
+    ) -> fmt::Result
+    where
+        W: fmt::Write + ?Sized,
+    {
+        if let Some(el_tailwind_classes) = self.el_css_classes.get(el_id) {
+            buffer.write_str(el_tailwind_classes.as_ref())?;
+        }
+        match tag_ids {
+            Some(tag_ids) => self.el_tag_classes_write(buffer, el_id, tag_ids),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes an element's classes for each of the given tags, without the
+    /// element's own classes.
+    pub(crate) fn el_tag_classes_write<W>(
+        &self,
+        buffer: &mut W,
+        el_id: &str,
+        tag_ids: &IndexSet<&TagId>,
+    ) -> fmt::Result
+    where
+        W: fmt::Write + ?Sized,
+    {
+        tag_ids
+            .iter()
+            .filter_map(|tag_id| self.tag_el_css_classes_map.get(*tag_id))
+            .filter_map(|el_css_classes| el_css_classes.get(el_id))
+            .try_for_each(|el_tag_classes| buffer.write_str(el_tag_classes.as_ref()))
+    }
+
+    /// Returns the CSS for all classes, followed by the user provided `css`.
+    pub(crate) fn styles(&self, theme: &GraphvizDotTheme, css: &str) -> String {
+        // Many elements share the same classes, so we only split each distinct
+        // string of classes once.
+        let tailwind_css_gen = [OUTLINE_NONE, theme.tag_classes()]
+            .into_iter()
+            .chain(self.el_css_classes.values().map(AsRef::<str>::as_ref))
+            .chain(
+                self.tag_el_css_classes_map
+                    .values()
+                    .flat_map(|tag_el_css_classes| tag_el_css_classes.values())
+                    .map(AsRef::<str>::as_ref),
+            )
+            .collect::<IndexSet<&str>>()
+            .into_iter()
+            .collect::<TailwindCssGen>();
+
+        let mut styles = tailwind_css_gen.build();
+        styles.push_str(css);
+        styles
+    }
+
+    /// Returns the `fill`, `stroke`, `stroke-width`, and `stroke-dasharray` of
+    /// an element's shape, and its text `color`, from its diagram theme
+    /// classes.
+    ///
+    /// Only classes without variants, or with one of the given shape variants,
+    /// are used. `[&>text]:fill-*` classes become the text `color`.
+    pub(crate) fn shape_properties(
+        &self,
+        el_id: &str,
+        shape_variants: &[&str],
+    ) -> IndexMap<&'static str, String> {
+        // Later classes override earlier ones, the same as in CSS.
+        let mut properties = IndexMap::<&'static str, String>::new();
+
+        self.el_css_classes
+            .get(el_id)
+            .map(AsRef::<str>::as_ref)
+            .unwrap_or_default()
+            .split_whitespace()
+            .filter_map(TailwindCssGen::class_declarations)
+            .for_each(|(variants, declarations)| {
+                let is_text = match variants.as_slice() {
+                    [] => false,
+                    [variant] if shape_variants.contains(variant) => false,
+                    ["[&>text]"] => true,
+                    _ => return,
+                };
+
+                declarations
+                    .split(';')
+                    .filter_map(|declaration| declaration.split_once(':'))
+                    .for_each(|(property, value)| {
+                        let property = match (property.trim(), is_text) {
+                            ("fill", false) => "fill",
+                            ("stroke", false) => "stroke",
+                            ("stroke-dasharray", false) => "stroke-dasharray",
+                            ("stroke-width", false) => "stroke-width",
+                            ("fill", true) => "color",
+                            _ => return,
+                        };
+                        properties.insert(property, value.trim().to_string());
+                    });
+            });
+
+        properties
+    }
+}
@@ -1,15 +1,25 @@
 use std::collections::HashMap;
 
 use dot_ix_model::{
-    common::{AnyId, EdgeId, NodeHierarchy, NodeId},
+    common::{
+        graphviz_attrs::EdgeDir, AnyId, EdgeId, Edges, GraphvizAttrs, NodeHierarchy, NodeId, TagId,
+    },
     info_graph::GraphStyle,
-    theme::{ColorParams, CssClassesBuilder, HighlightState, LineParams, Themeable},
+    theme::{
+        ColorParams, CssClassesBuilder, EdgeFlow, EdgeFlowParams, FontParams, HighlightState,
+        LineParams, OpacityParams, ShapeEffectParams, Themeable,
+    },
 };
+use indexmap::IndexSet;
 
 #[derive(Clone)]
 pub struct InfoGraphDot<'graph> {
     pub graph_style: GraphStyle,
     pub node_id_to_hierarchy: &'graph HashMap<&'graph NodeId, &'graph NodeHierarchy>,
+    pub node_id_to_parent: &'graph HashMap<&'graph NodeId, &'graph NodeId>,
+    pub edges: &'graph Edges,
+    pub graphviz_attrs: &'graph GraphvizAttrs,
+    pub el_tag_ids: &'graph HashMap<&'graph str, IndexSet<&'graph TagId>>,
     pub node_ids: Vec<&'graph NodeId>,
     pub edge_ids: Vec<&'graph EdgeId>,
 }
@@ -41,6 +51,55 @@ impl Themeable for InfoGraphDot<'_> {
         self.node_ids.iter().copied()
     }
 
+    fn node_ancestor_ids(&self, node_id: &NodeId) -> Vec<&NodeId>
+    where
+        Self: Sized,
+    {
+        let mut ancestor_ids = Vec::new();
+        let mut node_id = node_id;
+        while let Some(parent_id) = self.node_id_to_parent.get(node_id) {
+            ancestor_ids.push(*parent_id);
+            node_id = parent_id;
+        }
+        ancestor_ids.reverse();
+        ancestor_ids
+    }
+
+    fn node_is_cluster(&self, node_id: &NodeId) -> bool
+    where
+        Self: Sized,
+    {
+        self.node_id_to_hierarchy
+            .get(node_id)
+            .is_some_and(|hierarchy| !hierarchy.is_empty())
+    }
+
+    fn edge_node_ids(&self, edge_id: &EdgeId) -> Option<(&NodeId, &NodeId)>
+    where
+        Self: Sized,
+    {
+        self.edges
+            .get(edge_id)
+            .map(|[node_id_from, node_id_to]| (node_id_from, node_id_to))
+    }
+
+    fn edge_dir(&self, edge_id: &AnyId) -> EdgeDir {
+        self.graphviz_attrs
+            .edge_dirs()
+            .get(edge_id.as_str())
+            .copied()
+            .unwrap_or_else(|| self.graphviz_attrs.edge_dir_default())
+    }
+
+    fn el_has_tag(&self, el_id: &str, tag_id: &TagId) -> bool
+    where
+        Self: Sized,
+    {
+        self.el_tag_ids
+            .get(el_id)
+            .is_some_and(|tag_ids| tag_ids.contains(tag_id))
+    }
+
     fn node_outline_classes(
         &self,
         node_id: &AnyId,
@@ -94,6 +153,42 @@ impl Themeable for InfoGraphDot<'_> {
         path_color_classes(builder, color_params, "fill", self.el_prefix(node_id));
     }
 
+    fn node_text_color_classes(
+        &self,
+        _node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        color_params: ColorParams<'_>,
+    ) {
+        el_color_classes(builder, color_params, "fill", "[&>text]:");
+    }
+
+    fn node_font_classes(
+        &self,
+        _node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        font_params: FontParams<'_>,
+    ) {
+        font_classes(builder, font_params);
+    }
+
+    fn node_opacity_classes(
+        &self,
+        _node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        opacity_params: OpacityParams<'_>,
+    ) {
+        opacity_classes(builder, opacity_params);
+    }
+
+    fn node_shape_effect_classes(
+        &self,
+        _node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        shape_effect_params: ShapeEffectParams<'_>,
+    ) {
+        shape_effect_classes(builder, shape_effect_params);
+    }
+
     fn edge_ids(&self) -> impl Iterator<Item = &EdgeId>
     where
         Self: Sized,
@@ -156,6 +251,127 @@ impl Themeable for InfoGraphDot<'_> {
         // when the path is styled as dashed
         polygon_color_classes(builder, color_params, "fill");
     }
+
+    fn edge_text_color_classes(
+        &self,
+        _edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        color_params: ColorParams<'_>,
+    ) {
+        el_color_classes(builder, color_params, "fill", "[&>text]:");
+    }
+
+    fn edge_font_classes(
+        &self,
+        _edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        font_params: FontParams<'_>,
+    ) {
+        font_classes(builder, font_params);
+    }
+
+    fn edge_opacity_classes(
+        &self,
+        _edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        opacity_params: OpacityParams<'_>,
+    ) {
+        opacity_classes(builder, opacity_params);
+    }
+
+    fn edge_shape_effect_classes(
+        &self,
+        _edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        shape_effect_params: ShapeEffectParams<'_>,
+    ) {
+        shape_effect_classes(builder, shape_effect_params);
+    }
+
+    fn edge_flow_classes(
+        &self,
+        _edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        edge_flow_params: EdgeFlowParams<'_>,
+    ) {
+        edge_flow_classes(builder, edge_flow_params);
+    }
+}
+
+/// Appends classes for the weight and size of `<text>` elements.
+fn font_classes(builder: &mut CssClassesBuilder, font_params: FontParams<'_>) {
+    let FontParams {
+        font_weight,
+        font_size,
+    } = font_params;
+
+    if let Some(font_weight) = font_weight {
+        builder.append(&format!("[&>text]:font-{font_weight}"));
+    }
+    if let Some(font_size) = font_size {
+        builder.append(&format!("[&>text]:text-{font_size}"));
+    }
+}
+
+fn opacity_classes(builder: &mut CssClassesBuilder, opacity_params: OpacityParams<'_>) {
+    let OpacityParams {
+        highlight_state,
+        opacity,
+    } = opacity_params;
+
+    let highlight_prefix = highlight_prefix(highlight_state);
+    builder.append(&format!("{highlight_prefix}opacity-{opacity}"));
+}
+
+/// Appends classes for shadows and rounded corners.
+///
+/// SVG elements don't support `box-shadow`, so `drop-shadow-*` is used
+/// instead. The shape of the node is drawn by Graphviz, so the corner radius
+/// only applies to the element's outline.
+fn shape_effect_classes(
+    builder: &mut CssClassesBuilder,
+    shape_effect_params: ShapeEffectParams<'_>,
+) {
+    let ShapeEffectParams {
+        shadow,
+        corner_radius,
+    } = shape_effect_params;
+
+    if let Some(shadow) = shadow {
+        builder.append(&format!("drop-shadow-{shadow}"));
+    }
+    if let Some(corner_radius) = corner_radius {
+        builder.append(&format!("rounded-{corner_radius}"));
+    }
+}
+
+/// Appends classes to animate the `<path>` of an edge.
+///
+/// The dash offset is animated over one repetition of the dash pattern, which
+/// is passed to the keyframes through the `--dot-ix-dash-period` variable.
+fn edge_flow_classes(builder: &mut CssClassesBuilder, edge_flow_params: EdgeFlowParams<'_>) {
+    let EdgeFlowParams {
+        edge_flow,
+        edge_dir,
+        dasharray,
+        dash_period,
+    } = edge_flow_params;
+
+    let animation = match (edge_flow, edge_dir) {
+        (EdgeFlow::MarchingAnts, _) => "edge-march",
+        (EdgeFlow::Pulse, _) => "edge-pulse",
+        (EdgeFlow::Flow, EdgeDir::Forward) => "edge-flow",
+        (EdgeFlow::Flow, EdgeDir::Back) => "edge-flow-reverse",
+        (EdgeFlow::Flow, EdgeDir::Both) => "edge-flow-alternate",
+        (EdgeFlow::Flow, EdgeDir::None) => return,
+    };
+
+    if let Some(dasharray) = dasharray {
+        builder.append(&format!("[&>path]:[stroke-dasharray:{dasharray}]"));
+    }
+    builder
+        .append(&format!("[&>path]:[--dot-ix-dash-period:{dash_period}]"))
+        .append(&format!("[&>path]:animate-{animation}"));
 }
 
 fn outline_style_classes(
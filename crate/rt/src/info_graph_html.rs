@@ -1,17 +1,35 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap};
 
 use dot_ix_model::{
-    common::{AnyId, EdgeId, NodeId},
-    theme::{ColorParams, CssClassesBuilder, HighlightState, LineParams, Themeable},
+    common::{
+        graphviz_attrs::EdgeDir, AnyId, EdgeId, Edges, GraphvizAttrs, NodeHierarchy, NodeId, TagId,
+    },
+    theme::{
+        ColorParams, CssClassesBuilder, EdgeFlow, EdgeFlowParams, FontParams, HighlightState,
+        LineParams, OpacityParams, ShapeEffectParams, Themeable,
+    },
 };
+use indexmap::IndexSet;
 
+/// Computes classes for an `InfoGraph` rendered as HTML.
+///
+/// Nodes are `div`s, so they are styled with `border`, `bg`, and `text`
+/// classes. Edges are drawn in an SVG overlay, so they are styled with the
+/// same `[&>path]`, `[&>polygon]`, and `[&>text]` classes as [`InfoGraphDot`].
+///
+/// [`InfoGraphDot`]: crate::InfoGraphDot
 #[derive(Clone)]
 pub struct InfoGraphHtml<'graph> {
+    pub node_id_to_hierarchy: &'graph HashMap<&'graph NodeId, &'graph NodeHierarchy>,
+    pub node_id_to_parent: &'graph HashMap<&'graph NodeId, &'graph NodeId>,
+    pub edges: &'graph Edges,
+    pub graphviz_attrs: &'graph GraphvizAttrs,
+    pub el_tag_ids: &'graph HashMap<&'graph str, IndexSet<&'graph TagId>>,
     pub node_ids: Vec<&'graph NodeId>,
     pub edge_ids: Vec<&'graph EdgeId>,
 }
 
-impl<'graph> Themeable for InfoGraphHtml<'graph> {
+impl Themeable for InfoGraphHtml<'_> {
     fn node_ids(&self) -> impl Iterator<Item = &NodeId>
     where
         Self: Sized,
@@ -19,6 +37,55 @@ impl<'graph> Themeable for InfoGraphHtml<'graph> {
         self.node_ids.iter().copied()
     }
 
+    fn node_ancestor_ids(&self, node_id: &NodeId) -> Vec<&NodeId>
+    where
+        Self: Sized,
+    {
+        let mut ancestor_ids = Vec::new();
+        let mut node_id = node_id;
+        while let Some(parent_id) = self.node_id_to_parent.get(node_id) {
+            ancestor_ids.push(*parent_id);
+            node_id = parent_id;
+        }
+        ancestor_ids.reverse();
+        ancestor_ids
+    }
+
+    fn node_is_cluster(&self, node_id: &NodeId) -> bool
+    where
+        Self: Sized,
+    {
+        self.node_id_to_hierarchy
+            .get(node_id)
+            .is_some_and(|hierarchy| !hierarchy.is_empty())
+    }
+
+    fn edge_node_ids(&self, edge_id: &EdgeId) -> Option<(&NodeId, &NodeId)>
+    where
+        Self: Sized,
+    {
+        self.edges
+            .get(edge_id)
+            .map(|[node_id_from, node_id_to]| (node_id_from, node_id_to))
+    }
+
+    fn edge_dir(&self, edge_id: &AnyId) -> EdgeDir {
+        self.graphviz_attrs
+            .edge_dirs()
+            .get(edge_id.as_str())
+            .copied()
+            .unwrap_or_else(|| self.graphviz_attrs.edge_dir_default())
+    }
+
+    fn el_has_tag(&self, el_id: &str, tag_id: &TagId) -> bool
+    where
+        Self: Sized,
+    {
+        self.el_tag_ids
+            .get(el_id)
+            .is_some_and(|tag_ids| tag_ids.contains(tag_id))
+    }
+
     fn node_outline_classes(
         &self,
         _node_id: &AnyId,
@@ -72,6 +139,42 @@ impl<'graph> Themeable for InfoGraphHtml<'graph> {
         el_color_classes(builder, color_params, "bg");
     }
 
+    fn node_text_color_classes(
+        &self,
+        _node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        color_params: ColorParams<'_>,
+    ) {
+        el_color_classes(builder, color_params, "text");
+    }
+
+    fn node_font_classes(
+        &self,
+        _node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        font_params: FontParams<'_>,
+    ) {
+        font_classes(builder, font_params);
+    }
+
+    fn node_opacity_classes(
+        &self,
+        _node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        opacity_params: OpacityParams<'_>,
+    ) {
+        opacity_classes(builder, opacity_params);
+    }
+
+    fn node_shape_effect_classes(
+        &self,
+        _node_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        shape_effect_params: ShapeEffectParams<'_>,
+    ) {
+        shape_effect_classes(builder, shape_effect_params);
+    }
+
     fn edge_ids(&self) -> impl Iterator<Item = &EdgeId>
     where
         Self: Sized,
@@ -113,13 +216,14 @@ impl<'graph> Themeable for InfoGraphHtml<'graph> {
             line_style,
         } = line_params;
 
-        el_color_classes(builder, color_params, "border");
-        line_style_classes(
+        svg_color_classes(builder, color_params, "stroke", "[&>path]:");
+        svg_color_classes(builder, color_params, "stroke", "[&>polygon]:");
+        svg_line_style_classes(
             builder,
             color_params.highlight_state,
             line_width,
             line_style,
-            "border",
+            "[&>path]:",
         );
     }
 
@@ -129,10 +233,140 @@ impl<'graph> Themeable for InfoGraphHtml<'graph> {
         builder: &mut CssClassesBuilder,
         color_params: ColorParams<'_>,
     ) {
-        el_color_classes(builder, color_params, "bg");
+        // Only the arrowhead is filled, filling the `path` fills the area under the
+        // curve.
+        svg_color_classes(builder, color_params, "fill", "[&>polygon]:");
+    }
+
+    fn edge_text_color_classes(
+        &self,
+        _edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        color_params: ColorParams<'_>,
+    ) {
+        svg_color_classes(builder, color_params, "fill", "[&>text]:");
+    }
+
+    fn edge_font_classes(
+        &self,
+        _edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        font_params: FontParams<'_>,
+    ) {
+        let FontParams {
+            font_weight,
+            font_size,
+        } = font_params;
+
+        if let Some(font_weight) = font_weight {
+            builder.append(&format!("[&>text]:font-{font_weight}"));
+        }
+        if let Some(font_size) = font_size {
+            builder.append(&format!("[&>text]:text-{font_size}"));
+        }
+    }
+
+    fn edge_opacity_classes(
+        &self,
+        _edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        opacity_params: OpacityParams<'_>,
+    ) {
+        opacity_classes(builder, opacity_params);
+    }
+
+    fn edge_shape_effect_classes(
+        &self,
+        _edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        shape_effect_params: ShapeEffectParams<'_>,
+    ) {
+        // SVG elements don't support `box-shadow` or `border-radius`.
+        if let Some(shadow) = shape_effect_params.shadow {
+            builder.append(&format!("drop-shadow-{shadow}"));
+        }
+    }
+
+    fn edge_flow_classes(
+        &self,
+        _edge_id: &AnyId,
+        builder: &mut CssClassesBuilder,
+        edge_flow_params: EdgeFlowParams<'_>,
+    ) {
+        edge_flow_classes(builder, edge_flow_params);
+    }
+}
+
+fn font_classes(builder: &mut CssClassesBuilder, font_params: FontParams<'_>) {
+    let FontParams {
+        font_weight,
+        font_size,
+    } = font_params;
+
+    if let Some(font_weight) = font_weight {
+        builder.append(&format!("font-{font_weight}"));
+    }
+    if let Some(font_size) = font_size {
+        builder.append(&format!("text-{font_size}"));
+    }
+}
+
+fn opacity_classes(builder: &mut CssClassesBuilder, opacity_params: OpacityParams<'_>) {
+    let OpacityParams {
+        highlight_state,
+        opacity,
+    } = opacity_params;
+
+    let highlight_prefix = highlight_prefix(highlight_state);
+    builder.append(&format!("{highlight_prefix}opacity-{opacity}"));
+}
+
+fn shape_effect_classes(
+    builder: &mut CssClassesBuilder,
+    shape_effect_params: ShapeEffectParams<'_>,
+) {
+    let ShapeEffectParams {
+        shadow,
+        corner_radius,
+    } = shape_effect_params;
+
+    if let Some(shadow) = shadow {
+        builder.append(&format!("shadow-{shadow}"));
+    }
+    if let Some(corner_radius) = corner_radius {
+        builder.append(&format!("rounded-{corner_radius}"));
     }
 }
 
+/// Appends classes to animate the `<path>` of an edge.
+///
+/// The dash offset is animated over one repetition of the dash pattern, which
+/// is passed to the keyframes through the `--dot-ix-dash-period` variable.
+fn edge_flow_classes(builder: &mut CssClassesBuilder, edge_flow_params: EdgeFlowParams<'_>) {
+    let EdgeFlowParams {
+        edge_flow,
+        edge_dir,
+        dasharray,
+        dash_period,
+    } = edge_flow_params;
+
+    let animation = match (edge_flow, edge_dir) {
+        (EdgeFlow::MarchingAnts, _) => "edge-march",
+        (EdgeFlow::Pulse, _) => "edge-pulse",
+        (EdgeFlow::Flow, EdgeDir::Forward) => "edge-flow",
+        (EdgeFlow::Flow, EdgeDir::Back) => "edge-flow-reverse",
+        (EdgeFlow::Flow, EdgeDir::Both) => "edge-flow-alternate",
+        (EdgeFlow::Flow, EdgeDir::None) => return,
+    };
+
+    if let Some(dasharray) = dasharray {
+        builder.append(&format!("[&>path]:[stroke-dasharray:{dasharray}]"));
+    }
+    builder
+        .append(&format!("[&>path]:[--dot-ix-dash-period:{dash_period}]"))
+        .append(&format!("[&>path]:animate-{animation}"));
+}
+
 fn line_style_classes(
     builder: &mut CssClassesBuilder,
     highlight_state: HighlightState,
@@ -168,6 +402,53 @@ fn line_width_increment(line_width: &str) -> Cow<'_, str> {
     line_width
 }
 
+/// Appends SVG stroke classes that emulate HTML border styles.
+fn svg_line_style_classes(
+    builder: &mut CssClassesBuilder,
+    highlight_state: HighlightState,
+    line_width: &str,
+    line_style: &str,
+    el_prefix: &str,
+) {
+    let highlight_prefix = highlight_prefix(highlight_state);
+    let dasharray = match line_style {
+        "none" => return,
+        "solid" => None,
+        "dashed" => Some("dasharray:3"),
+        "dotted" => Some("dasharray:2"),
+        line_style if line_style.starts_with("dasharray:") => Some(line_style),
+        _ => {
+            // TODO #29: warn user of unknown value.
+            return;
+        }
+    };
+
+    builder.append(&format!("{el_prefix}{highlight_prefix}stroke-{line_width}"));
+    if let Some(dasharray) = dasharray {
+        builder.append(&format!(
+            "{el_prefix}{highlight_prefix}[stroke-{dasharray}]"
+        ));
+    }
+}
+
+fn svg_color_classes(
+    builder: &mut CssClassesBuilder,
+    color_params: ColorParams<'_>,
+    stroke_or_fill: &str,
+    el_prefix: &str,
+) {
+    let ColorParams {
+        highlight_state,
+        color,
+        shade,
+    } = color_params;
+
+    let highlight_prefix = highlight_prefix(highlight_state);
+    builder.append(&format!(
+        "{el_prefix}{highlight_prefix}{stroke_or_fill}-{color}-{shade}"
+    ));
+}
+
 fn el_color_classes(
     builder: &mut CssClassesBuilder,
     color_params: ColorParams<'_>,
@@ -0,0 +1,89 @@
+//! Converters between `InfoGraph` and graph interchange formats, so that
+//! diagrams can be analysed in other tools and imported back.
+//!
+//! Each format carries:
+//!
+//! * The node hierarchy.
+//! * Node names, descriptions, and emojis.
+//! * Edges and edge descriptions.
+//! * Tags and which nodes and edges they are on.
+//! * The graph direction, except in GEXF which has no graph level data.
+//!
+//! Themes, images, and Graphviz attributes are not included.
+
+use std::collections::HashMap;
+
+use dot_ix_model::{
+    common::{AnyId, TagId},
+    info_graph::{GraphDir, InfoGraph},
+};
+
+pub use self::{
+    cytoscape_json::CytoscapeJson, gexf::Gexf, graphml::Graphml,
+    interchange_error::InterchangeError,
+};
+
+mod cytoscape_json;
+mod gexf;
+mod graphml;
+mod info_graph_assembler;
+mod interchange_error;
+mod xml;
+
+/// What a GraphML key or GEXF attribute is imported as.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DataKind {
+    Direction,
+    Name,
+    Desc,
+    Emoji,
+    /// Port of the source node that an edge attaches to.
+    SourcePort,
+    /// Port of the target node that an edge attaches to.
+    TargetPort,
+    /// Whether the node or edge has the tag.
+    Tag(String),
+    /// Data that is not imported.
+    Other,
+}
+
+impl DataKind {
+    /// Returns the kind of data for a key or attribute name.
+    ///
+    /// `label` and `description`, which other tools use, are read as names
+    /// and descriptions, and `tag:<tag_id>` is read as the tag.
+    fn from_name(name: &str) -> Self {
+        match name {
+            "direction" => Self::Direction,
+            "name" | "label" => Self::Name,
+            "desc" | "description" => Self::Desc,
+            "emoji" => Self::Emoji,
+            "source_port" => Self::SourcePort,
+            "target_port" => Self::TargetPort,
+            _ => name
+                .strip_prefix("tag:")
+                .map(|tag_id| Self::Tag(tag_id.to_string()))
+                .unwrap_or(Self::Other),
+        }
+    }
+}
+
+/// Returns the tags on each node and edge.
+fn any_id_to_tag_ids(info_graph: &InfoGraph) -> HashMap<&AnyId, Vec<&TagId>> {
+    let mut any_id_to_tag_ids = HashMap::<&AnyId, Vec<&TagId>>::new();
+    info_graph.tag_items().iter().for_each(|(tag_id, any_ids)| {
+        any_ids.iter().for_each(|any_id| {
+            any_id_to_tag_ids.entry(any_id).or_default().push(tag_id);
+        });
+    });
+    any_id_to_tag_ids
+}
+
+/// Returns the direction for `"horizontal"` or `"vertical"`.
+fn graph_dir_parse(direction: &str) -> Option<GraphDir> {
+    match direction.trim() {
+        "horizontal" => Some(GraphDir::Horizontal),
+        "vertical" => Some(GraphDir::Vertical),
+        _ => None,
+    }
+}
@@ -0,0 +1,256 @@
+use dot_ix_model::{
+    common::{AnyId, NodeHierarchy, NodeId},
+    info_graph::InfoGraph,
+};
+use serde_json::{json, Map, Value};
+
+use crate::interchange::{
+    any_id_to_tag_ids, graph_dir_parse,
+    info_graph_assembler::{EdgeData, InfoGraphAssembler, NodeData},
+    InterchangeError,
+};
+
+/// Converts between `InfoGraph` and [Cytoscape.js] JSON.
+///
+/// * The node hierarchy becomes compound nodes, with each child's `parent`
+///   set to its parent node's ID.
+/// * Names, descriptions, emojis, and tags are `name`, `desc`, `emoji`, and
+///   `tags` in each element's `data`.
+/// * Edge ports, e.g. `n` in `a:n`, are `source_port` and `target_port` in
+///   the edge's `data`.
+/// * The direction and tag names are in the top level `data`.
+///
+/// When importing, `elements` may be an object with `nodes` and `edges`, or
+/// an array of elements with a `group`. `label` and `description` are read as
+/// names and descriptions.
+///
+/// [Cytoscape.js]: https://js.cytoscape.org/#notation/elements-json
+#[derive(Clone, Copy, Debug)]
+pub struct CytoscapeJson;
+
+impl CytoscapeJson {
+    /// Returns the `InfoGraph` as Cytoscape.js JSON.
+    pub fn export(info_graph: &InfoGraph) -> String {
+        let any_id_to_tag_ids = any_id_to_tag_ids(info_graph);
+        let node_id_to_parent = info_graph.node_parents();
+        let tags_of = |any_id: &AnyId| any_id_to_tag_ids.get(any_id).map(|tag_ids| json!(tag_ids));
+
+        let mut node_ids = Vec::with_capacity(info_graph.node_names().len());
+        node_ids_collect(&mut node_ids, info_graph.hierarchy());
+        let nodes = node_ids
+            .into_iter()
+            .map(|node_id| {
+                let mut data = Map::new();
+                data.insert(String::from("id"), json!(node_id));
+                [
+                    (
+                        "parent",
+                        node_id_to_parent.get(node_id).map(|parent| json!(parent)),
+                    ),
+                    (
+                        "name",
+                        info_graph.node_names().get(node_id).map(|name| json!(name)),
+                    ),
+                    (
+                        "desc",
+                        info_graph.node_descs().get(node_id).map(|desc| json!(desc)),
+                    ),
+                    (
+                        "emoji",
+                        info_graph
+                            .node_emojis()
+                            .get(node_id)
+                            .map(|emoji| json!(emoji)),
+                    ),
+                    ("tags", tags_of(&AnyId::from(node_id.clone()))),
+                ]
+                .into_iter()
+                .for_each(|(key, value)| {
+                    if let Some(value) = value {
+                        data.insert(String::from(key), value);
+                    }
+                });
+                json!({ "data": data })
+            })
+            .collect::<Vec<Value>>();
+
+        let edges = info_graph
+            .edges()
+            .iter()
+            .map(|(edge_id, [src_node_id, target_node_id])| {
+                let mut data = Map::new();
+                data.insert(String::from("id"), json!(edge_id));
+                let (src_node_id, src_port) = src_node_id.node_id_and_port();
+                let (target_node_id, target_port) = target_node_id.node_id_and_port();
+                data.insert(String::from("source"), json!(src_node_id));
+                data.insert(String::from("target"), json!(target_node_id));
+                [("source_port", src_port), ("target_port", target_port)]
+                    .into_iter()
+                    .for_each(|(key, port)| {
+                        if let Some(port) = port {
+                            data.insert(String::from(key), json!(port));
+                        }
+                    });
+                if let Some(edge_desc) = info_graph.edge_descs().get(edge_id) {
+                    data.insert(String::from("desc"), json!(edge_desc));
+                }
+                if let Some(tags) = tags_of(&AnyId::from(edge_id.clone())) {
+                    data.insert(String::from("tags"), tags);
+                }
+                json!({ "data": data })
+            })
+            .collect::<Vec<Value>>();
+
+        let tags = info_graph
+            .tags()
+            .iter()
+            .map(|(tag_id, tag_name)| json!({ "id": tag_id, "name": tag_name }))
+            .collect::<Vec<Value>>();
+
+        let cytoscape_json = json!({
+            "data": {
+                "direction": info_graph.direction().to_string(),
+                "tags": tags,
+            },
+            "elements": {
+                "nodes": nodes,
+                "edges": edges,
+            },
+        });
+        serde_json::to_string_pretty(&cytoscape_json)
+            .expect("Failed to serialize Cytoscape.js JSON.")
+    }
+
+    /// Returns the `InfoGraph` in Cytoscape.js JSON.
+    pub fn import(cytoscape_json: &str) -> Result<InfoGraph, InterchangeError> {
+        let cytoscape_json = serde_json::from_str::<Value>(cytoscape_json).map_err(|error| {
+            InterchangeError::JsonParse {
+                line: error.line(),
+                column: error.column(),
+                message: error.to_string(),
+            }
+        })?;
+
+        let mut assembler = InfoGraphAssembler::new();
+        if let Some(data) = cytoscape_json.get("data") {
+            if let Some(direction) = data
+                .get("direction")
+                .and_then(Value::as_str)
+                .and_then(graph_dir_parse)
+            {
+                assembler.direction_set(direction);
+            }
+            data.get("tags")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|tag| {
+                    let tag_id = str_value(tag, &["id"])?;
+                    Some((tag_id, str_value(tag, &["name", "label"])))
+                })
+                .for_each(|(tag_id, tag_name)| assembler.tag_add(tag_id, tag_name));
+        }
+
+        let elements = if cytoscape_json.is_array() {
+            &cytoscape_json
+        } else {
+            cytoscape_json
+                .get("elements")
+                .ok_or(InterchangeError::GraphMissing {
+                    element: "elements",
+                })?
+        };
+        let (nodes, edges) = match elements {
+            Value::Array(elements) => elements.iter().partition::<Vec<&Value>, _>(|element| {
+                match element.get("group").and_then(Value::as_str) {
+                    Some(group) => group == "nodes",
+                    None => element
+                        .get("data")
+                        .is_none_or(|data| data.get("source").is_none()),
+                }
+            }),
+            _ => {
+                let group = |group: &str| {
+                    elements
+                        .get(group)
+                        .and_then(Value::as_array)
+                        .map(|elements| elements.iter().collect::<Vec<&Value>>())
+                        .unwrap_or_default()
+                };
+                (group("nodes"), group("edges"))
+            }
+        };
+
+        nodes.into_iter().try_for_each(|node| {
+            let data = node.get("data").unwrap_or(&Value::Null);
+            let node_id = str_value(data, &["id"]).ok_or(InterchangeError::AttributeMissing {
+                element: "node",
+                attribute: "id",
+            })?;
+            let node_data = NodeData {
+                parent: str_value(data, &["parent"]),
+                name: str_value(data, &["name", "label"]),
+                desc: str_value(data, &["desc", "description"]),
+                emoji: str_value(data, &["emoji"]),
+                tags: tags_value(data),
+            };
+            assembler.node_add(node_id, node_data)
+        })?;
+        edges.into_iter().try_for_each(|edge| {
+            let data = edge.get("data").unwrap_or(&Value::Null);
+            let required = |attribute: &'static str| {
+                str_value(data, &[attribute]).ok_or(InterchangeError::AttributeMissing {
+                    element: "edge",
+                    attribute,
+                })
+            };
+            let edge_data = EdgeData {
+                id: str_value(data, &["id"]),
+                source: required("source")?,
+                source_port: str_value(data, &["source_port"]),
+                target: required("target")?,
+                target_port: str_value(data, &["target_port"]),
+                desc: str_value(data, &["desc", "description", "label", "name"]),
+                tags: tags_value(data),
+            };
+            assembler.edge_add(edge_data);
+            Ok::<_, InterchangeError>(())
+        })?;
+
+        assembler.build()
+    }
+}
+
+/// Collects the node IDs with each parent before its children, which is the
+/// order Cytoscape.js adds compound nodes in.
+fn node_ids_collect<'f>(node_ids: &mut Vec<&'f NodeId>, node_hierarchy: &'f NodeHierarchy) {
+    node_hierarchy
+        .iter()
+        .for_each(|(node_id, child_hierarchy)| {
+            node_ids.push(node_id);
+            node_ids_collect(node_ids, child_hierarchy);
+        });
+}
+
+/// Returns the first of the keys that has a string or number value.
+fn str_value(data: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| match data.get(key)? {
+        Value::String(value) => Some(value.clone()),
+        Value::Number(value) => Some(value.to_string()),
+        _ => None,
+    })
+}
+
+/// Returns the tag IDs in `tags`, which may be an array or a space separated
+/// string.
+fn tags_value(data: &Value) -> Vec<String> {
+    match data.get("tags") {
+        Some(Value::Array(tags)) => tags
+            .iter()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect(),
+        Some(Value::String(tags)) => tags.split_whitespace().map(String::from).collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,449 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use dot_ix_model::{
+    common::{AnyId, NodeHierarchy, NodeId, TagId},
+    info_graph::InfoGraph,
+};
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+
+use crate::{
+    interchange::{
+        any_id_to_tag_ids,
+        info_graph_assembler::{EdgeData, InfoGraphAssembler, NodeData},
+        xml, DataKind, InterchangeError,
+    },
+    xml_escape::xml_escape,
+};
+
+/// Converts between `InfoGraph` and [GEXF], which Gephi uses.
+///
+/// * The node hierarchy becomes nested `nodes` within `node`s.
+/// * Node names and edge descriptions are `label`s.
+/// * Node descriptions and emojis are `desc` and `emoji` attributes.
+/// * Each tag is a boolean attribute with the ID `tag:<tag_id>`, and the tag
+///   name as its title.
+/// * Edge ports, e.g. `n` in `a:n`, are `source_port` and `target_port`
+///   attributes.
+///
+/// GEXF has no graph level data, so the direction is not included.
+///
+/// When importing, parent nodes may also be given with `pid`.
+///
+/// [GEXF]: https://gexf.net/
+#[derive(Clone, Copy, Debug)]
+pub struct Gexf;
+
+impl Gexf {
+    /// Returns the `InfoGraph` as a GEXF document.
+    pub fn export(info_graph: &InfoGraph) -> String {
+        let mut gexf =
+            String::with_capacity(128 * (info_graph.node_names().len() + info_graph.edges().len()));
+        gexf_write(&mut gexf, info_graph).expect("Failed to write `gexf` string.");
+        gexf
+    }
+
+    /// Returns the `InfoGraph` in a GEXF document.
+    pub fn import(gexf: &str) -> Result<InfoGraph, InterchangeError> {
+        GexfReader::new(gexf).read()
+    }
+}
+
+fn gexf_write(gexf: &mut String, info_graph: &InfoGraph) -> fmt::Result {
+    writeln!(gexf, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(gexf, r#"<gexf xmlns="http://gexf.net/1.3" version="1.3">"#)?;
+    writeln!(
+        gexf,
+        r#"  <graph defaultedgetype="directed" mode="static">"#
+    )?;
+
+    ["node", "edge"].into_iter().try_for_each(|class| {
+        writeln!(gexf, r#"    <attributes class="{class}">"#)?;
+        if class == "node" {
+            writeln!(
+                gexf,
+                r#"      <attribute id="desc" title="desc" type="string"/>"#
+            )?;
+            writeln!(
+                gexf,
+                r#"      <attribute id="emoji" title="emoji" type="string"/>"#
+            )?;
+        } else {
+            writeln!(
+                gexf,
+                r#"      <attribute id="source_port" title="source_port" type="string"/>"#
+            )?;
+            writeln!(
+                gexf,
+                r#"      <attribute id="target_port" title="target_port" type="string"/>"#
+            )?;
+        }
+        info_graph
+            .tags()
+            .iter()
+            .try_for_each(|(tag_id, tag_name)| {
+                writeln!(
+                    gexf,
+                    r#"      <attribute id="tag:{tag_id}" title="{}" type="boolean"/>"#,
+                    xml_escape(tag_name)
+                )
+            })?;
+        writeln!(gexf, "    </attributes>")
+    })?;
+
+    let any_id_to_tag_ids = any_id_to_tag_ids(info_graph);
+    nodes_write(
+        gexf,
+        info_graph,
+        &any_id_to_tag_ids,
+        info_graph.hierarchy(),
+        2,
+    )?;
+
+    writeln!(gexf, "    <edges>")?;
+    info_graph
+        .edges()
+        .iter()
+        .try_for_each(|(edge_id, [src_node_id, target_node_id])| {
+            let (src_node_id, src_port) = src_node_id.node_id_and_port();
+            let (target_node_id, target_port) = target_node_id.node_id_and_port();
+            write!(
+                gexf,
+                r#"      <edge id="{edge_id}" source="{src_node_id}" target="{target_node_id}""#
+            )?;
+            if let Some(edge_desc) = info_graph.edge_descs().get(edge_id) {
+                write!(gexf, r#" label="{}""#, xml_escape(edge_desc))?;
+            }
+            let any_id = AnyId::from(edge_id.clone());
+            let attrs = [("source_port", src_port), ("target_port", target_port)]
+                .into_iter()
+                .filter_map(|(attr_id, value)| value.map(|value| (attr_id, value)))
+                .collect::<Vec<_>>();
+            if !attrs.is_empty() || any_id_to_tag_ids.contains_key(&any_id) {
+                writeln!(gexf, ">")?;
+                attvalues_write(gexf, &any_id_to_tag_ids, &any_id, &attrs, 4)?;
+                writeln!(gexf, "      </edge>")
+            } else {
+                writeln!(gexf, "/>")
+            }
+        })?;
+    writeln!(gexf, "    </edges>")?;
+
+    writeln!(gexf, "  </graph>")?;
+    writeln!(gexf, "</gexf>")
+}
+
+fn nodes_write(
+    gexf: &mut String,
+    info_graph: &InfoGraph,
+    any_id_to_tag_ids: &HashMap<&AnyId, Vec<&TagId>>,
+    node_hierarchy: &NodeHierarchy,
+    depth: usize,
+) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    writeln!(gexf, "{indent}<nodes>")?;
+    node_hierarchy
+        .iter()
+        .try_for_each(|(node_id, child_hierarchy)| {
+            node_write(
+                gexf,
+                info_graph,
+                any_id_to_tag_ids,
+                node_id,
+                child_hierarchy,
+                depth + 1,
+            )
+        })?;
+    writeln!(gexf, "{indent}</nodes>")
+}
+
+fn node_write(
+    gexf: &mut String,
+    info_graph: &InfoGraph,
+    any_id_to_tag_ids: &HashMap<&AnyId, Vec<&TagId>>,
+    node_id: &NodeId,
+    node_hierarchy: &NodeHierarchy,
+    depth: usize,
+) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    write!(gexf, r#"{indent}<node id="{node_id}""#)?;
+    if let Some(node_name) = info_graph.node_names().get(node_id) {
+        write!(gexf, r#" label="{}""#, xml_escape(node_name))?;
+    }
+    writeln!(gexf, ">")?;
+
+    let attrs = [
+        ("desc", info_graph.node_descs().get(node_id)),
+        ("emoji", info_graph.node_emojis().get(node_id)),
+    ]
+    .into_iter()
+    .filter_map(|(attr_id, value)| value.map(|value| (attr_id, value.as_str())))
+    .collect::<Vec<_>>();
+    attvalues_write(
+        gexf,
+        any_id_to_tag_ids,
+        &AnyId::from(node_id.clone()),
+        &attrs,
+        depth + 1,
+    )?;
+    if !node_hierarchy.is_empty() {
+        nodes_write(
+            gexf,
+            info_graph,
+            any_id_to_tag_ids,
+            node_hierarchy,
+            depth + 1,
+        )?;
+    }
+    writeln!(gexf, "{indent}</node>")
+}
+
+fn attvalues_write(
+    gexf: &mut String,
+    any_id_to_tag_ids: &HashMap<&AnyId, Vec<&TagId>>,
+    any_id: &AnyId,
+    attrs: &[(&str, &str)],
+    depth: usize,
+) -> fmt::Result {
+    let tag_ids = any_id_to_tag_ids
+        .get(any_id)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    if attrs.is_empty() && tag_ids.is_empty() {
+        return Ok(());
+    }
+
+    let indent = "  ".repeat(depth);
+    writeln!(gexf, "{indent}<attvalues>")?;
+    attrs.iter().try_for_each(|(attr_id, value)| {
+        writeln!(
+            gexf,
+            r#"{indent}  <attvalue for="{attr_id}" value="{}"/>"#,
+            xml_escape(value)
+        )
+    })?;
+    tag_ids.iter().try_for_each(|tag_id| {
+        writeln!(
+            gexf,
+            r#"{indent}  <attvalue for="tag:{tag_id}" value="true"/>"#
+        )
+    })?;
+    writeln!(gexf, "{indent}</attvalues>")
+}
+
+/// Element whose children are being read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Scope {
+    /// `attributes` for nodes.
+    NodeAttributes,
+    /// `attributes` for edges.
+    EdgeAttributes,
+    /// Index of the node in `GexfReader::nodes`.
+    Node(usize),
+    /// Index of the edge in `GexfReader::edges`.
+    Edge(usize),
+    /// Any other element.
+    Other,
+}
+
+struct GexfReader<'s> {
+    reader: Reader<&'s [u8]>,
+    node_attrs: HashMap<String, DataKind>,
+    edge_attrs: HashMap<String, DataKind>,
+    scopes: Vec<Scope>,
+    nodes: Vec<(String, NodeData)>,
+    edges: Vec<EdgeData>,
+    assembler: InfoGraphAssembler,
+    gexf_found: bool,
+    graph_found: bool,
+}
+
+impl<'s> GexfReader<'s> {
+    fn new(gexf: &'s str) -> Self {
+        Self {
+            reader: Reader::from_str(gexf),
+            node_attrs: HashMap::new(),
+            edge_attrs: HashMap::new(),
+            scopes: Vec::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            assembler: InfoGraphAssembler::new(),
+            gexf_found: false,
+            graph_found: false,
+        }
+    }
+
+    fn read(mut self) -> Result<InfoGraph, InterchangeError> {
+        loop {
+            let event = self
+                .reader
+                .read_event()
+                .map_err(|error| xml::xml_parse_error(&self.reader, error))?;
+            match event {
+                Event::Start(bytes_start) => {
+                    let scope = self.element_start(&bytes_start)?;
+                    self.scopes.push(scope);
+                }
+                Event::Empty(bytes_start) => {
+                    self.element_start(&bytes_start)?;
+                }
+                Event::End(_) => {
+                    self.scopes.pop();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if !self.gexf_found {
+            return Err(InterchangeError::GraphMissing { element: "gexf" });
+        }
+        if !self.graph_found {
+            return Err(InterchangeError::GraphMissing { element: "graph" });
+        }
+
+        let Self {
+            nodes,
+            edges,
+            mut assembler,
+            ..
+        } = self;
+        nodes
+            .into_iter()
+            .try_for_each(|(node_id, node_data)| assembler.node_add(node_id, node_data))?;
+        edges
+            .into_iter()
+            .for_each(|edge_data| assembler.edge_add(edge_data));
+        assembler.build()
+    }
+
+    fn element_start(&mut self, bytes_start: &BytesStart<'_>) -> Result<Scope, InterchangeError> {
+        let reader = &self.reader;
+        let scope = match bytes_start.local_name().as_ref() {
+            b"gexf" => {
+                self.gexf_found = true;
+                Scope::Other
+            }
+            b"graph" => {
+                self.graph_found = true;
+                Scope::Other
+            }
+            b"attributes" => match xml::attr_value(reader, bytes_start, "class")?.as_deref() {
+                Some("node") => Scope::NodeAttributes,
+                Some("edge") => Scope::EdgeAttributes,
+                _ => Scope::Other,
+            },
+            b"attribute" => {
+                let attr_id = xml::attr_value_required(reader, bytes_start, "attribute", "id")?;
+                let title = xml::attr_value(reader, bytes_start, "title")?;
+                let data_kind = match DataKind::from_name(&attr_id) {
+                    DataKind::Tag(tag_id) => {
+                        self.assembler.tag_add(tag_id.clone(), title);
+                        DataKind::Tag(tag_id)
+                    }
+                    DataKind::Other => title
+                        .map(|title| DataKind::from_name(&title))
+                        .unwrap_or(DataKind::Other),
+                    data_kind => data_kind,
+                };
+                match self.scopes.last() {
+                    Some(Scope::NodeAttributes) => {
+                        self.node_attrs.insert(attr_id, data_kind);
+                    }
+                    Some(Scope::EdgeAttributes) => {
+                        self.edge_attrs.insert(attr_id, data_kind);
+                    }
+                    _ => {}
+                }
+                Scope::Other
+            }
+            b"node" => {
+                let node_id = xml::attr_value_required(reader, bytes_start, "node", "id")?;
+                let parent = match xml::attr_value(reader, bytes_start, "pid")? {
+                    Some(pid) => Some(pid),
+                    None => self.scopes.iter().rev().find_map(|scope| match scope {
+                        Scope::Node(node_index) => Some(self.nodes[*node_index].0.clone()),
+                        _ => None,
+                    }),
+                };
+                let node_data = NodeData {
+                    parent,
+                    name: xml::attr_value(reader, bytes_start, "label")?,
+                    ..Default::default()
+                };
+                self.nodes.push((node_id, node_data));
+                Scope::Node(self.nodes.len() - 1)
+            }
+            b"edge" => {
+                let edge_data = EdgeData {
+                    id: xml::attr_value(reader, bytes_start, "id")?,
+                    source: xml::attr_value_required(reader, bytes_start, "edge", "source")?,
+                    target: xml::attr_value_required(reader, bytes_start, "edge", "target")?,
+                    desc: xml::attr_value(reader, bytes_start, "label")?,
+                    ..Default::default()
+                };
+                self.edges.push(edge_data);
+                Scope::Edge(self.edges.len() - 1)
+            }
+            b"attvalue" => {
+                // GEXF 1.0 uses `id` instead of `for`.
+                let attr_id = match xml::attr_value(reader, bytes_start, "for")? {
+                    Some(attr_id) => attr_id,
+                    None => xml::attr_value_required(reader, bytes_start, "attvalue", "id")?,
+                };
+                let value = xml::attr_value_required(reader, bytes_start, "attvalue", "value")?;
+                self.attvalue_apply(&attr_id, value);
+                Scope::Other
+            }
+            _ => Scope::Other,
+        };
+        Ok(scope)
+    }
+
+    fn attvalue_apply(&mut self, attr_id: &str, value: String) {
+        let tag_is_set = || matches!(value.trim(), "true" | "1");
+        let scope = self
+            .scopes
+            .iter()
+            .rev()
+            .find(|scope| matches!(scope, Scope::Node(_) | Scope::Edge(_)));
+        match scope {
+            Some(Scope::Node(node_index)) => {
+                let Some(data_kind) = self.node_attrs.get(attr_id) else {
+                    return;
+                };
+                let node_data = &mut self.nodes[*node_index].1;
+                match data_kind {
+                    DataKind::Name => node_data.name = Some(value),
+                    DataKind::Desc => node_data.desc = Some(value),
+                    DataKind::Emoji => node_data.emoji = Some(value),
+                    DataKind::Tag(tag_id) if tag_is_set() => node_data.tags.push(tag_id.clone()),
+                    DataKind::Tag(_)
+                    | DataKind::SourcePort
+                    | DataKind::TargetPort
+                    | DataKind::Direction
+                    | DataKind::Other => {}
+                }
+            }
+            Some(Scope::Edge(edge_index)) => {
+                let Some(data_kind) = self.edge_attrs.get(attr_id) else {
+                    return;
+                };
+                let edge_data = &mut self.edges[*edge_index];
+                match data_kind {
+                    DataKind::Name | DataKind::Desc => edge_data.desc = Some(value),
+                    DataKind::Tag(tag_id) if tag_is_set() => edge_data.tags.push(tag_id.clone()),
+                    DataKind::SourcePort => edge_data.source_port = Some(value),
+                    DataKind::TargetPort => edge_data.target_port = Some(value),
+                    DataKind::Tag(_) | DataKind::Emoji | DataKind::Direction | DataKind::Other => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
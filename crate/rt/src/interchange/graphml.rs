@@ -0,0 +1,462 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use dot_ix_model::{
+    common::{AnyId, NodeHierarchy, NodeId, TagId},
+    info_graph::InfoGraph,
+};
+use quick_xml::{events::Event, Reader};
+
+use crate::{
+    interchange::{
+        any_id_to_tag_ids, graph_dir_parse,
+        info_graph_assembler::{EdgeData, InfoGraphAssembler, NodeData},
+        xml, DataKind, InterchangeError,
+    },
+    xml_escape::xml_escape,
+};
+
+/// Converts between `InfoGraph` and [GraphML].
+///
+/// * The node hierarchy becomes nested `graph`s within `node`s.
+/// * Names, descriptions, and emojis are `name`, `desc`, and `emoji` data.
+/// * Each tag is a boolean key named `tag:<tag_id>`, with the tag name as the
+///   key's `desc`.
+/// * The direction is `direction` data on the top level graph.
+/// * Edge ports, e.g. `n` in `a:n`, are the edge's `sourceport` and
+///   `targetport`.
+///
+/// When importing, keys are matched by their `attr.name`, so `label` and
+/// `description` keys from other tools are read as names and descriptions.
+///
+/// [GraphML]: http://graphml.graphdrawing.org/
+#[derive(Clone, Copy, Debug)]
+pub struct Graphml;
+
+impl Graphml {
+    /// Returns the `InfoGraph` as a GraphML document.
+    pub fn export(info_graph: &InfoGraph) -> String {
+        let mut graphml =
+            String::with_capacity(128 * (info_graph.node_names().len() + info_graph.edges().len()));
+        graphml_write(&mut graphml, info_graph).expect("Failed to write `graphml` string.");
+        graphml
+    }
+
+    /// Returns the `InfoGraph` in a GraphML document.
+    pub fn import(graphml: &str) -> Result<InfoGraph, InterchangeError> {
+        GraphmlReader::new(graphml).read()
+    }
+}
+
+fn graphml_write(graphml: &mut String, info_graph: &InfoGraph) -> fmt::Result {
+    writeln!(graphml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        graphml,
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+    )?;
+    [
+        ("direction", "graph", "direction"),
+        ("name", "node", "name"),
+        ("desc", "node", "desc"),
+        ("emoji", "node", "emoji"),
+        ("edge_desc", "edge", "desc"),
+    ]
+    .into_iter()
+    .try_for_each(|(key_id, key_for, attr_name)| {
+        writeln!(
+            graphml,
+            r#"  <key id="{key_id}" for="{key_for}" attr.name="{attr_name}" attr.type="string"/>"#
+        )
+    })?;
+    info_graph.tags().iter().try_for_each(|(tag_id, tag_name)| {
+        writeln!(
+            graphml,
+            r#"  <key id="tag_{tag_id}" for="all" attr.name="tag:{tag_id}" attr.type="boolean">"#
+        )?;
+        writeln!(graphml, "    <desc>{}</desc>", xml_escape(tag_name))?;
+        writeln!(graphml, "  </key>")
+    })?;
+
+    let any_id_to_tag_ids = any_id_to_tag_ids(info_graph);
+    writeln!(graphml, r#"  <graph id="G" edgedefault="directed">"#)?;
+    writeln!(
+        graphml,
+        r#"    <data key="direction">{}</data>"#,
+        info_graph.direction()
+    )?;
+    info_graph
+        .hierarchy()
+        .iter()
+        .try_for_each(|(node_id, node_hierarchy)| {
+            node_write(
+                graphml,
+                info_graph,
+                &any_id_to_tag_ids,
+                node_id,
+                node_hierarchy,
+                2,
+            )
+        })?;
+    info_graph
+        .edges()
+        .iter()
+        .try_for_each(|(edge_id, [src_node_id, target_node_id])| {
+            let (src_node_id, src_port) = src_node_id.node_id_and_port();
+            let (target_node_id, target_port) = target_node_id.node_id_and_port();
+            write!(
+                graphml,
+                r#"    <edge id="{edge_id}" source="{src_node_id}" target="{target_node_id}""#
+            )?;
+            if let Some(src_port) = src_port {
+                write!(graphml, r#" sourceport="{}""#, xml_escape(src_port))?;
+            }
+            if let Some(target_port) = target_port {
+                write!(graphml, r#" targetport="{}""#, xml_escape(target_port))?;
+            }
+            writeln!(graphml, ">")?;
+            if let Some(edge_desc) = info_graph.edge_descs().get(edge_id) {
+                writeln!(
+                    graphml,
+                    r#"      <data key="edge_desc">{}</data>"#,
+                    xml_escape(edge_desc)
+                )?;
+            }
+            tags_write(
+                graphml,
+                &any_id_to_tag_ids,
+                &AnyId::from(edge_id.clone()),
+                6,
+            )?;
+            writeln!(graphml, "    </edge>")
+        })?;
+    writeln!(graphml, "  </graph>")?;
+    writeln!(graphml, "</graphml>")
+}
+
+fn node_write(
+    graphml: &mut String,
+    info_graph: &InfoGraph,
+    any_id_to_tag_ids: &HashMap<&AnyId, Vec<&TagId>>,
+    node_id: &NodeId,
+    node_hierarchy: &NodeHierarchy,
+    depth: usize,
+) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    writeln!(graphml, r#"{indent}<node id="{node_id}">"#)?;
+    [
+        ("name", info_graph.node_names().get(node_id)),
+        ("desc", info_graph.node_descs().get(node_id)),
+        ("emoji", info_graph.node_emojis().get(node_id)),
+    ]
+    .into_iter()
+    .filter_map(|(key_id, value)| value.map(|value| (key_id, value)))
+    .try_for_each(|(key_id, value)| {
+        writeln!(
+            graphml,
+            r#"{indent}  <data key="{key_id}">{}</data>"#,
+            xml_escape(value)
+        )
+    })?;
+    tags_write(
+        graphml,
+        any_id_to_tag_ids,
+        &AnyId::from(node_id.clone()),
+        depth + 1,
+    )?;
+
+    if !node_hierarchy.is_empty() {
+        writeln!(
+            graphml,
+            r#"{indent}  <graph id="{node_id}:" edgedefault="directed">"#
+        )?;
+        node_hierarchy
+            .iter()
+            .try_for_each(|(child_node_id, child_hierarchy)| {
+                node_write(
+                    graphml,
+                    info_graph,
+                    any_id_to_tag_ids,
+                    child_node_id,
+                    child_hierarchy,
+                    depth + 2,
+                )
+            })?;
+        writeln!(graphml, "{indent}  </graph>")?;
+    }
+    writeln!(graphml, "{indent}</node>")
+}
+
+fn tags_write(
+    graphml: &mut String,
+    any_id_to_tag_ids: &HashMap<&AnyId, Vec<&TagId>>,
+    any_id: &AnyId,
+    depth: usize,
+) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    any_id_to_tag_ids
+        .get(any_id)
+        .into_iter()
+        .flatten()
+        .try_for_each(|tag_id| writeln!(graphml, r#"{indent}<data key="tag_{tag_id}">true</data>"#))
+}
+
+/// Element whose children are being read.
+#[derive(Clone, Debug)]
+enum Scope {
+    /// A `graph`, and the node that it is within.
+    Graph { parent: Option<String> },
+    /// Index of the node in `GraphmlReader::nodes`.
+    Node(usize),
+    /// Index of the edge in `GraphmlReader::edges`.
+    Edge(usize),
+    /// A `key`, and the tag it is for, if any.
+    Key { tag_id: Option<String> },
+    /// The `desc` of a `key`.
+    KeyDesc,
+    /// A `data` element, and the key it is for.
+    Data { key_id: String },
+    /// Any other element.
+    Other,
+}
+
+struct GraphmlReader<'s> {
+    reader: Reader<&'s [u8]>,
+    keys: HashMap<String, DataKind>,
+    scopes: Vec<Scope>,
+    nodes: Vec<(String, NodeData)>,
+    edges: Vec<EdgeData>,
+    assembler: InfoGraphAssembler,
+    /// Text of the `data` element or `key` `desc` being read.
+    text: String,
+    graphml_found: bool,
+    graph_found: bool,
+}
+
+impl<'s> GraphmlReader<'s> {
+    fn new(graphml: &'s str) -> Self {
+        Self {
+            reader: Reader::from_str(graphml),
+            keys: HashMap::new(),
+            scopes: Vec::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            assembler: InfoGraphAssembler::new(),
+            text: String::new(),
+            graphml_found: false,
+            graph_found: false,
+        }
+    }
+
+    fn read(mut self) -> Result<InfoGraph, InterchangeError> {
+        loop {
+            let event = self
+                .reader
+                .read_event()
+                .map_err(|error| xml::xml_parse_error(&self.reader, error))?;
+            match event {
+                Event::Start(bytes_start) => {
+                    let scope = self.element_start(&bytes_start)?;
+                    self.scopes.push(scope);
+                }
+                Event::Empty(bytes_start) => {
+                    let scope = self.element_start(&bytes_start)?;
+                    self.element_end(scope);
+                }
+                Event::End(_) => {
+                    if let Some(scope) = self.scopes.pop() {
+                        self.element_end(scope);
+                    }
+                }
+                Event::Text(bytes_text) if self.text_is_read() => {
+                    let text = bytes_text
+                        .unescape()
+                        .map_err(|error| xml::xml_parse_error(&self.reader, error))?;
+                    self.text.push_str(&text);
+                }
+                Event::CData(bytes_cdata) if self.text_is_read() => {
+                    let text = bytes_cdata
+                        .decode()
+                        .map_err(|error| xml::xml_parse_error(&self.reader, error))?;
+                    self.text.push_str(&text);
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if !self.graphml_found {
+            return Err(InterchangeError::GraphMissing { element: "graphml" });
+        }
+        if !self.graph_found {
+            return Err(InterchangeError::GraphMissing { element: "graph" });
+        }
+
+        let Self {
+            nodes,
+            edges,
+            mut assembler,
+            ..
+        } = self;
+        nodes
+            .into_iter()
+            .try_for_each(|(node_id, node_data)| assembler.node_add(node_id, node_data))?;
+        edges
+            .into_iter()
+            .for_each(|edge_data| assembler.edge_add(edge_data));
+        assembler.build()
+    }
+
+    fn element_start(
+        &mut self,
+        bytes_start: &quick_xml::events::BytesStart<'_>,
+    ) -> Result<Scope, InterchangeError> {
+        let reader = &self.reader;
+        let scope = match bytes_start.local_name().as_ref() {
+            b"graphml" => {
+                self.graphml_found = true;
+                Scope::Other
+            }
+            b"key" => {
+                let key_id = xml::attr_value_required(reader, bytes_start, "key", "id")?;
+                let key_kind = xml::attr_value(reader, bytes_start, "attr.name")?
+                    .map(|attr_name| DataKind::from_name(&attr_name))
+                    .unwrap_or_else(|| DataKind::from_name(&key_id));
+                let tag_id = if let DataKind::Tag(tag_id) = &key_kind {
+                    self.assembler.tag_add(tag_id.clone(), None);
+                    Some(tag_id.clone())
+                } else {
+                    None
+                };
+                self.keys.insert(key_id, key_kind);
+                Scope::Key { tag_id }
+            }
+            b"desc" if matches!(self.scopes.last(), Some(Scope::Key { .. })) => {
+                self.text.clear();
+                Scope::KeyDesc
+            }
+            b"graph" => {
+                self.graph_found = true;
+                let parent = self.scopes.iter().rev().find_map(|scope| match scope {
+                    Scope::Node(node_index) => Some(Some(self.nodes[*node_index].0.clone())),
+                    Scope::Graph { .. } => Some(None),
+                    _ => None,
+                });
+                Scope::Graph {
+                    parent: parent.flatten(),
+                }
+            }
+            b"node" => {
+                let node_id = xml::attr_value_required(reader, bytes_start, "node", "id")?;
+                let parent = self.graph_parent();
+                self.nodes.push((
+                    node_id,
+                    NodeData {
+                        parent,
+                        ..Default::default()
+                    },
+                ));
+                Scope::Node(self.nodes.len() - 1)
+            }
+            b"edge" => {
+                let edge_data = EdgeData {
+                    id: xml::attr_value(reader, bytes_start, "id")?,
+                    source: xml::attr_value_required(reader, bytes_start, "edge", "source")?,
+                    source_port: xml::attr_value(reader, bytes_start, "sourceport")?,
+                    target: xml::attr_value_required(reader, bytes_start, "edge", "target")?,
+                    target_port: xml::attr_value(reader, bytes_start, "targetport")?,
+                    ..Default::default()
+                };
+                self.edges.push(edge_data);
+                Scope::Edge(self.edges.len() - 1)
+            }
+            b"data" => {
+                let key_id = xml::attr_value_required(reader, bytes_start, "data", "key")?;
+                self.text.clear();
+                Scope::Data { key_id }
+            }
+            _ => Scope::Other,
+        };
+        Ok(scope)
+    }
+
+    fn element_end(&mut self, scope: Scope) {
+        match scope {
+            Scope::Data { key_id } => self.data_apply(&key_id),
+            Scope::KeyDesc => {
+                // The `desc` of a tag's `key` is the tag name.
+                if let Some(Scope::Key {
+                    tag_id: Some(tag_id),
+                }) = self.scopes.last()
+                    && !self.text.is_empty()
+                {
+                    let tag_name = std::mem::take(&mut self.text);
+                    self.assembler.tag_add(tag_id.clone(), Some(tag_name));
+                }
+            }
+            Scope::Graph { .. }
+            | Scope::Node(_)
+            | Scope::Edge(_)
+            | Scope::Key { .. }
+            | Scope::Other => {}
+        }
+    }
+
+    /// Returns whether the text being read is a `data` value or tag name.
+    fn text_is_read(&self) -> bool {
+        matches!(
+            self.scopes.last(),
+            Some(Scope::Data { .. } | Scope::KeyDesc)
+        )
+    }
+
+    /// Returns the node that contains the `graph` being read.
+    fn graph_parent(&self) -> Option<String> {
+        self.scopes.iter().rev().find_map(|scope| match scope {
+            Scope::Graph { parent } => Some(parent.clone()),
+            _ => None,
+        })?
+    }
+
+    fn data_apply(&mut self, key_id: &str) {
+        let value = std::mem::take(&mut self.text);
+        let Some(key_kind) = self.keys.get(key_id) else {
+            return;
+        };
+        let tag_is_set = || matches!(value.trim(), "true" | "1");
+        match self.scopes.last() {
+            Some(Scope::Graph { parent: None }) => {
+                if let (DataKind::Direction, Some(direction)) = (key_kind, graph_dir_parse(&value))
+                {
+                    self.assembler.direction_set(direction);
+                }
+            }
+            Some(Scope::Node(node_index)) => {
+                let node_data = &mut self.nodes[*node_index].1;
+                match key_kind {
+                    DataKind::Name => node_data.name = Some(value),
+                    DataKind::Desc => node_data.desc = Some(value),
+                    DataKind::Emoji => node_data.emoji = Some(value),
+                    DataKind::Tag(tag_id) if tag_is_set() => node_data.tags.push(tag_id.clone()),
+                    DataKind::Tag(_)
+                    | DataKind::SourcePort
+                    | DataKind::TargetPort
+                    | DataKind::Direction
+                    | DataKind::Other => {}
+                }
+            }
+            Some(Scope::Edge(edge_index)) => {
+                let edge_data = &mut self.edges[*edge_index];
+                match key_kind {
+                    DataKind::Name | DataKind::Desc => edge_data.desc = Some(value),
+                    DataKind::Tag(tag_id) if tag_is_set() => edge_data.tags.push(tag_id.clone()),
+                    DataKind::SourcePort => edge_data.source_port = Some(value),
+                    DataKind::TargetPort => edge_data.target_port = Some(value),
+                    DataKind::Tag(_) | DataKind::Emoji | DataKind::Direction | DataKind::Other => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use dot_ix_model::{
+    common::{
+        AnyId, EdgeDescs, EdgeId, Edges, NodeDescs, NodeEmojis, NodeHierarchy, NodeId, NodeNames,
+        TagId, TagItems, TagNames,
+    },
+    info_graph::{GraphDir, InfoGraph},
+};
+use indexmap::IndexMap;
+
+use crate::{id_allocator::IdAllocator, interchange::InterchangeError};
+
+/// Node read from an interchange document, with IDs as they are in the
+/// document.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct NodeData {
+    pub(crate) parent: Option<String>,
+    pub(crate) name: Option<String>,
+    pub(crate) desc: Option<String>,
+    pub(crate) emoji: Option<String>,
+    pub(crate) tags: Vec<String>,
+}
+
+/// Edge read from an interchange document, with IDs as they are in the
+/// document.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EdgeData {
+    pub(crate) id: Option<String>,
+    pub(crate) source: String,
+    /// Port of the source node that the edge attaches to, e.g. `n`.
+    pub(crate) source_port: Option<String>,
+    pub(crate) target: String,
+    /// Port of the target node that the edge attaches to, e.g. `s`.
+    pub(crate) target_port: Option<String>,
+    pub(crate) desc: Option<String>,
+    pub(crate) tags: Vec<String>,
+}
+
+/// Collects the nodes, edges, and tags read from an interchange document, and
+/// assembles them into an `InfoGraph`.
+///
+/// IDs that are not valid are renamed, and renamed nodes without a name use
+/// their original ID as their name.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct InfoGraphAssembler {
+    direction: Option<GraphDir>,
+    /// Tag IDs and their names.
+    tags: IndexMap<String, Option<String>>,
+    nodes: IndexMap<String, NodeData>,
+    edges: Vec<EdgeData>,
+}
+
+impl InfoGraphAssembler {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn direction_set(&mut self, direction: GraphDir) {
+        self.direction = Some(direction);
+    }
+
+    pub(crate) fn tag_add(&mut self, tag_id: String, name: Option<String>) {
+        let tag_name = self.tags.entry(tag_id).or_default();
+        if name.is_some() {
+            *tag_name = name;
+        }
+    }
+
+    pub(crate) fn node_add(
+        &mut self,
+        node_id: String,
+        node_data: NodeData,
+    ) -> Result<(), InterchangeError> {
+        if self.nodes.contains_key(&node_id) {
+            return Err(InterchangeError::NodeDuplicate { node_id });
+        }
+        self.nodes.insert(node_id, node_data);
+        Ok(())
+    }
+
+    pub(crate) fn edge_add(&mut self, edge_data: EdgeData) {
+        self.edges.push(edge_data);
+    }
+
+    pub(crate) fn build(self) -> Result<InfoGraph, InterchangeError> {
+        self.parents_validate()?;
+
+        let Self {
+            direction,
+            mut tags,
+            nodes,
+            edges,
+        } = self;
+
+        nodes
+            .values()
+            .flat_map(|node_data| node_data.tags.iter())
+            .chain(edges.iter().flat_map(|edge_data| edge_data.tags.iter()))
+            .for_each(|tag_id| {
+                if !tags.contains_key(tag_id) {
+                    tags.insert(tag_id.clone(), None);
+                }
+            });
+
+        // Valid IDs are claimed first, so that they are kept when other IDs
+        // are renamed to the same ID.
+        let mut id_allocator = IdAllocator::new();
+        let node_ids_valid = nodes
+            .keys()
+            .map(|node_id| id_is_valid(node_id) && id_allocator.claim(node_id))
+            .collect::<Vec<bool>>();
+        let edge_ids_valid = edges
+            .iter()
+            .map(|edge_data| {
+                edge_data
+                    .id
+                    .as_deref()
+                    .is_some_and(|edge_id| id_is_valid(edge_id) && id_allocator.claim(edge_id))
+            })
+            .collect::<Vec<bool>>();
+        let mut tag_id_allocator = IdAllocator::new();
+        let tag_ids_valid = tags
+            .keys()
+            .map(|tag_id| id_is_valid(tag_id) && tag_id_allocator.claim(tag_id))
+            .collect::<Vec<bool>>();
+
+        let mut node_names = NodeNames::with_capacity(nodes.len());
+        let mut node_descs = NodeDescs::new();
+        let mut node_emojis = NodeEmojis::new();
+        let node_ids = nodes
+            .iter()
+            .zip(node_ids_valid)
+            .map(|((node_id_src, node_data), is_valid)| {
+                let node_id = if is_valid {
+                    node_id_src.clone()
+                } else {
+                    id_allocator.alloc(node_id_src)
+                };
+                let node_id = NodeId::try_from(node_id).expect("Allocated `NodeId` to be valid.");
+
+                let name = node_data
+                    .name
+                    .clone()
+                    .or_else(|| (!is_valid).then(|| node_id_src.clone()));
+                if let Some(name) = name {
+                    node_names.insert(node_id.clone(), name);
+                }
+                if let Some(desc) = node_data.desc.clone() {
+                    node_descs.insert(node_id.clone(), desc);
+                }
+                if let Some(emoji) = node_data.emoji.clone() {
+                    node_emojis.insert(node_id.clone(), emoji);
+                }
+
+                (node_id_src.as_str(), node_id)
+            })
+            .collect::<IndexMap<&str, NodeId>>();
+
+        let tag_ids = tags
+            .iter()
+            .zip(tag_ids_valid)
+            .map(|((tag_id_src, _), is_valid)| {
+                let tag_id = if is_valid {
+                    tag_id_src.clone()
+                } else {
+                    tag_id_allocator.alloc(tag_id_src)
+                };
+                let tag_id = TagId::try_from(tag_id).expect("Allocated `TagId` to be valid.");
+                (tag_id_src.as_str(), tag_id)
+            })
+            .collect::<IndexMap<&str, TagId>>();
+        let tag_names = tags
+            .iter()
+            .map(|(tag_id_src, name)| {
+                let name = name.clone().unwrap_or_else(|| tag_id_src.clone());
+                (tag_ids[tag_id_src.as_str()].clone(), name)
+            })
+            .collect::<TagNames>();
+        let mut tag_items = TagItems::new();
+        let mut tag_items_add = |tag_ids_src: &[String], any_id: AnyId| {
+            tag_ids_src.iter().for_each(|tag_id_src| {
+                tag_items
+                    .entry(tag_ids[tag_id_src.as_str()].clone())
+                    .or_default()
+                    .push(any_id.clone());
+            });
+        };
+        nodes.iter().for_each(|(node_id_src, node_data)| {
+            let node_id = node_ids[node_id_src.as_str()].clone();
+            tag_items_add(&node_data.tags, AnyId::from(node_id));
+        });
+
+        let mut edges_out = Edges::with_capacity(edges.len());
+        let mut edge_descs = EdgeDescs::new();
+        edges
+            .iter()
+            .zip(edge_ids_valid)
+            .try_for_each(|(edge_data, is_valid)| {
+                let referrer = || {
+                    edge_data
+                        .id
+                        .clone()
+                        .unwrap_or_else(|| format!("{} -> {}", edge_data.source, edge_data.target))
+                };
+                let node_id_lookup =
+                    |node_id_src: &str, port: Option<&str>| {
+                        let node_id = node_ids.get(node_id_src).ok_or_else(|| {
+                            InterchangeError::NodeUnknown {
+                                referrer: referrer(),
+                                node_id: node_id_src.to_string(),
+                            }
+                        })?;
+                        match port {
+                            Some(port) => node_id.with_port(port).ok_or_else(|| {
+                                InterchangeError::PortInvalid {
+                                    referrer: referrer(),
+                                    node_id: node_id_src.to_string(),
+                                    port: port.to_string(),
+                                }
+                            }),
+                            None => Ok(node_id.clone()),
+                        }
+                    };
+                let source = node_id_lookup(&edge_data.source, edge_data.source_port.as_deref())?;
+                let target = node_id_lookup(&edge_data.target, edge_data.target_port.as_deref())?;
+
+                let edge_id = match edge_data.id.as_deref() {
+                    Some(edge_id) if is_valid => edge_id.to_string(),
+                    Some(edge_id) => id_allocator.alloc(edge_id),
+                    None => id_allocator.alloc(&format!(
+                        "{}__{}",
+                        source.node_id_plain(),
+                        target.node_id_plain()
+                    )),
+                };
+                let edge_id = EdgeId::try_from(edge_id).expect("Allocated `EdgeId` to be valid.");
+
+                if let Some(desc) = edge_data.desc.clone() {
+                    edge_descs.insert(edge_id.clone(), desc);
+                }
+                tag_items_add(&edge_data.tags, AnyId::from(edge_id.clone()));
+                edges_out.insert(edge_id, [source, target]);
+
+                Ok::<_, InterchangeError>(())
+            })?;
+
+        let mut parent_to_children = IndexMap::<Option<&str>, Vec<&str>>::new();
+        nodes.iter().try_for_each(|(node_id_src, node_data)| {
+            let parent = node_data.parent.as_deref();
+            if let Some(parent) = parent
+                && !node_ids.contains_key(parent)
+            {
+                return Err(InterchangeError::NodeUnknown {
+                    referrer: node_id_src.clone(),
+                    node_id: parent.to_string(),
+                });
+            }
+            parent_to_children
+                .entry(parent)
+                .or_default()
+                .push(node_id_src.as_str());
+            Ok(())
+        })?;
+        let hierarchy = node_hierarchy_build(&parent_to_children, &node_ids, None);
+
+        let info_graph = InfoGraph::default()
+            .with_direction(direction.unwrap_or_default())
+            .with_hierarchy(hierarchy)
+            .with_node_names(node_names)
+            .with_node_descs(node_descs)
+            .with_node_emojis(node_emojis)
+            .with_edges(edges_out)
+            .with_edge_descs(edge_descs)
+            .with_tags(tag_names)
+            .with_tag_items(tag_items);
+
+        Ok(info_graph)
+    }
+
+    /// Returns an error if a node is its own ancestor, as it would not be
+    /// reachable from the top of the hierarchy.
+    fn parents_validate(&self) -> Result<(), InterchangeError> {
+        self.nodes.iter().try_for_each(|(node_id, node_data)| {
+            let mut parent = node_data.parent.as_deref();
+            let mut depth = 0;
+            while let Some(parent_id) = parent {
+                depth += 1;
+                if parent_id == node_id || depth > self.nodes.len() {
+                    return Err(InterchangeError::NodeParentCycle {
+                        node_id: node_id.clone(),
+                    });
+                }
+                parent = self
+                    .nodes
+                    .get(parent_id)
+                    .and_then(|node_data| node_data.parent.as_deref());
+            }
+            Ok(())
+        })
+    }
+}
+
+fn id_is_valid(id: &str) -> bool {
+    IdAllocator::sanitize(id) == id
+}
+
+fn node_hierarchy_build(
+    parent_to_children: &IndexMap<Option<&str>, Vec<&str>>,
+    node_ids: &IndexMap<&str, NodeId>,
+    parent: Option<&str>,
+) -> NodeHierarchy {
+    let mut node_hierarchy = NodeHierarchy::new();
+    parent_to_children
+        .get(&parent)
+        .into_iter()
+        .flatten()
+        .for_each(|node_id_src| {
+            let child_hierarchy =
+                node_hierarchy_build(parent_to_children, node_ids, Some(node_id_src));
+            node_hierarchy.insert(node_ids[node_id_src].clone(), child_hierarchy);
+        });
+    node_hierarchy
+}
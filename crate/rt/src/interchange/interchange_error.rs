@@ -0,0 +1,68 @@
+/// Errors when importing a graph interchange format into an `InfoGraph`.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum InterchangeError {
+    /// The XML is not well formed.
+    #[error("XML error at byte {position}: {message}")]
+    XmlParse {
+        /// Byte offset of the error.
+        position: u64,
+        /// What was expected or found.
+        message: String,
+    },
+    /// The JSON is not well formed, or does not have the expected structure.
+    #[error("JSON error at line {line}, column {column}: {message}")]
+    JsonParse {
+        /// Line number, starting from `1`.
+        line: usize,
+        /// Column number, starting from `1`.
+        column: usize,
+        /// What was expected or found.
+        message: String,
+    },
+    /// The document does not contain the element that holds the graph.
+    #[error("Expected a `{element}` element that holds the graph.")]
+    GraphMissing {
+        /// Name of the element, e.g. `graphml`.
+        element: &'static str,
+    },
+    /// A node or edge is missing a required attribute.
+    #[error("A `{element}` is missing its `{attribute}`.")]
+    AttributeMissing {
+        /// Name of the element, e.g. `edge`.
+        element: &'static str,
+        /// Name of the attribute, e.g. `source`.
+        attribute: &'static str,
+    },
+    /// More than one node has the same ID.
+    #[error("Node `{node_id}` is defined more than once.")]
+    NodeDuplicate {
+        /// ID of the node in the imported document.
+        node_id: String,
+    },
+    /// An edge or node refers to a node that does not exist.
+    #[error("`{referrer}` refers to node `{node_id}`, which does not exist.")]
+    NodeUnknown {
+        /// ID of the edge or node that refers to the node.
+        referrer: String,
+        /// ID of the node that does not exist.
+        node_id: String,
+    },
+    /// An edge refers to a port that is not a valid port name.
+    #[error(
+        "`{referrer}` refers to port `{port}` of node `{node_id}`, which is not a valid port."
+    )]
+    PortInvalid {
+        /// ID of the edge that refers to the port.
+        referrer: String,
+        /// ID of the node in the imported document.
+        node_id: String,
+        /// The port name.
+        port: String,
+    },
+    /// A node is its own ancestor.
+    #[error("Node `{node_id}` is its own ancestor.")]
+    NodeParentCycle {
+        /// ID of the node in the imported document.
+        node_id: String,
+    },
+}
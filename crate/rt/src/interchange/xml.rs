@@ -0,0 +1,44 @@
+use std::borrow::Cow;
+
+use quick_xml::{events::BytesStart, Reader};
+
+use crate::interchange::InterchangeError;
+
+/// Returns the unescaped value of the attribute, if it is present.
+pub(crate) fn attr_value(
+    reader: &Reader<&[u8]>,
+    bytes_start: &BytesStart<'_>,
+    attr_name: &str,
+) -> Result<Option<String>, InterchangeError> {
+    bytes_start
+        .try_get_attribute(attr_name)
+        .map_err(|error| xml_parse_error(reader, error))?
+        .map(|attr| {
+            attr.unescape_value()
+                .map(Cow::into_owned)
+                .map_err(|error| xml_parse_error(reader, error))
+        })
+        .transpose()
+}
+
+/// Returns the unescaped value of the attribute, or an error if it is
+/// missing.
+pub(crate) fn attr_value_required(
+    reader: &Reader<&[u8]>,
+    bytes_start: &BytesStart<'_>,
+    element: &'static str,
+    attribute: &'static str,
+) -> Result<String, InterchangeError> {
+    attr_value(reader, bytes_start, attribute)?
+        .ok_or(InterchangeError::AttributeMissing { element, attribute })
+}
+
+pub(crate) fn xml_parse_error(
+    reader: &Reader<&[u8]>,
+    error: impl Into<quick_xml::Error>,
+) -> InterchangeError {
+    InterchangeError::XmlParse {
+        position: reader.error_position(),
+        message: error.into().to_string(),
+    }
+}
@@ -0,0 +1,8 @@
+use dot_ix_model::common::GraphvizDotTheme;
+
+mod info_graph;
+
+/// Generates a draw.io (diagrams.net) file, which can be edited by hand.
+pub trait IntoDrawioSrc {
+    fn into(self, theme: &GraphvizDotTheme) -> String;
+}
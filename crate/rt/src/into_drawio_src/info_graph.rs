@@ -0,0 +1,637 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use dot_ix_model::{
+    common::{
+        dot_src_and_styles::GraphvizImage,
+        graphviz_attrs::{EdgeDir, FixedSize, Margin},
+        EdgeId, GraphvizDotTheme, NodeHierarchy, NodeId,
+    },
+    info_graph::{GraphStyle, InfoGraph},
+};
+
+use crate::{
+    info_graph_classes::InfoGraphClasses,
+    layered_layout::{LayeredLayout, LayoutEdge, LayoutItem, LayoutPadding, LayoutRect},
+    xml_escape::xml_escape,
+    InfoGraphDot, IntoDrawioSrc,
+};
+
+/// Graphviz measures `width`, `height`, and node `margin`s in inches.
+const POINTS_PER_INCH: f64 = 72.0;
+
+/// Width of a character relative to the font size.
+const CHAR_WIDTH_RATIO: f64 = 0.6;
+
+/// Height of a line of text relative to the font size.
+const LINE_HEIGHT_RATIO: f64 = 1.2;
+
+/// Space between a node's image and its label.
+const IMAGE_SPACING: f64 = 10.0;
+
+/// ID of the root cell, which holds the layers.
+const CELL_ID_ROOT: &str = "0";
+
+/// ID of the default layer, which holds the top level cells.
+const CELL_ID_LAYER: &str = "1";
+
+/// Converts an `InfoGraph` into a draw.io (diagrams.net) file, in mxGraph XML.
+///
+/// * Nodes with children become containers, and other nodes become cells
+///   with their emoji, name, and description as the label.
+/// * Edges become connectors with their description as the label, and
+///   arrowheads from the edge direction. Compass points, e.g. `a:n`, become
+///   the connector's exit and entry points.
+/// * Nodes are positioned using the same layered layout as `IntoSvgSrc`, and
+///   edges are given the layout's waypoints between ranks.
+/// * Fill, stroke, stroke width, dash, and text colours from the diagram
+///   theme become cell styles, and node images become label images.
+///
+/// # Limitations
+///
+/// * Text is measured assuming a monospace font, so labels in draw.io's
+///   default font may have extra space around them.
+/// * Tags, `hover` and `focus` styles, and tag focus styles are not
+///   included.
+/// * Arrowhead shapes are drawn as draw.io's `classic` arrow.
+impl IntoDrawioSrc for &InfoGraph {
+    fn into(self, theme: &GraphvizDotTheme) -> String {
+        let node_id_to_hierarchy = self.hierarchy_flat();
+        let node_id_to_hierarchy = &node_id_to_hierarchy;
+        let node_id_to_parent = self.node_parents();
+        let node_id_to_parent = &node_id_to_parent;
+        let el_tag_ids = self.el_tag_ids();
+        let el_tag_ids = &el_tag_ids;
+
+        let info_graph_dot = InfoGraphDot {
+            graph_style: self.graph_style(),
+            node_id_to_hierarchy,
+            node_id_to_parent,
+            edges: self.edges(),
+            graphviz_attrs: self.graphviz_attrs(),
+            el_tag_ids,
+            node_ids: node_id_to_hierarchy.keys().copied().collect::<Vec<_>>(),
+            edge_ids: self.edges().keys().collect::<Vec<_>>(),
+        };
+        let info_graph_classes = InfoGraphClasses::new(self, &info_graph_dot);
+
+        let drawio_layout = DrawioLayout::new(self, theme);
+
+        let mut drawio_src = String::with_capacity(
+            384 * (drawio_layout.drawio_nodes.len() + self.edges().len()) + 512,
+        );
+        drawio_write(
+            &mut drawio_src,
+            self,
+            theme,
+            &info_graph_classes,
+            drawio_layout,
+        )
+        .expect("Failed to write `drawio_src` string.");
+
+        drawio_src
+    }
+}
+
+/// A node to write, which is a container if it has children.
+struct DrawioNode<'graph> {
+    node_id: &'graph NodeId,
+    /// Item index of the cluster that the node is within.
+    parent_item: Option<usize>,
+    is_cluster: bool,
+    label: String,
+    /// Image path, and its width and height.
+    image: Option<(&'graph str, f64, f64)>,
+}
+
+/// An edge to write, and the layout edge it corresponds to.
+struct DrawioEdge<'graph> {
+    edge_id: &'graph EdgeId,
+    src_item: usize,
+    target_item: usize,
+    src_compass_point: Option<&'graph str>,
+    target_compass_point: Option<&'graph str>,
+    layout_edge_index: usize,
+}
+
+/// Layout input, and the node and edge that each item and edge is written as.
+struct DrawioLayout<'graph> {
+    layered_layout: LayeredLayout,
+    drawio_nodes: Vec<DrawioNode<'graph>>,
+    drawio_edges: Vec<DrawioEdge<'graph>>,
+}
+
+impl<'graph> DrawioLayout<'graph> {
+    fn new(info_graph: &'graph InfoGraph, theme: &GraphvizDotTheme) -> Self {
+        let graphviz_attrs = info_graph.graphviz_attrs();
+        let mut drawio_layout = DrawioLayout {
+            layered_layout: LayeredLayout {
+                graph_dir: info_graph.direction(),
+                nodesep: graphviz_attrs.nodesep() * POINTS_PER_INCH,
+                ranksep: graphviz_attrs.ranksep() * POINTS_PER_INCH,
+                items: Vec::new(),
+                roots: Vec::new(),
+                edges: Vec::new(),
+            },
+            drawio_nodes: Vec::new(),
+            drawio_edges: Vec::new(),
+        };
+
+        let mut node_id_to_item = HashMap::<&str, usize>::new();
+        let root_indices = info_graph
+            .hierarchy()
+            .iter()
+            .map(|(node_id, node_hierarchy)| {
+                drawio_layout.node_items_push(
+                    info_graph,
+                    theme,
+                    &mut node_id_to_item,
+                    None,
+                    node_id,
+                    node_hierarchy,
+                )
+            })
+            .collect::<Vec<usize>>();
+        drawio_layout.layered_layout.roots = root_indices;
+
+        info_graph
+            .edges()
+            .iter()
+            .for_each(|(edge_id, [src_node_id, target_node_id])| {
+                let (src_node_id_plain, src_compass_point) = src_node_id.node_id_and_port();
+                let (target_node_id_plain, target_compass_point) =
+                    target_node_id.node_id_and_port();
+                let (Some(src_item), Some(target_item)) = (
+                    node_id_to_item.get(src_node_id_plain).copied(),
+                    node_id_to_item.get(target_node_id_plain).copied(),
+                ) else {
+                    return;
+                };
+
+                let layout_edge_index = drawio_layout.layered_layout.edges.len();
+                drawio_layout.layered_layout.edges.push(LayoutEdge {
+                    src: src_item,
+                    target: target_item,
+                    minlen: graphviz_attrs
+                        .edge_minlens()
+                        .get(edge_id)
+                        .copied()
+                        .unwrap_or_else(|| graphviz_attrs.edge_minlen_default()),
+                    constraint: graphviz_attrs
+                        .edge_constraints()
+                        .get(edge_id)
+                        .copied()
+                        .unwrap_or_else(|| graphviz_attrs.edge_constraint_default()),
+                });
+                drawio_layout.drawio_edges.push(DrawioEdge {
+                    edge_id,
+                    src_item,
+                    target_item,
+                    src_compass_point,
+                    target_compass_point,
+                    layout_edge_index,
+                });
+            });
+
+        drawio_layout
+    }
+
+    /// Pushes the items for a node and its descendants, returning the node's
+    /// item index.
+    fn node_items_push(
+        &mut self,
+        info_graph: &'graph InfoGraph,
+        theme: &GraphvizDotTheme,
+        node_id_to_item: &mut HashMap<&'graph str, usize>,
+        parent_item: Option<usize>,
+        node_id: &'graph NodeId,
+        node_hierarchy: &'graph NodeHierarchy,
+    ) -> usize {
+        let graphviz_attrs = info_graph.graphviz_attrs();
+        let is_cluster = !node_hierarchy.is_empty();
+        let label = node_label(info_graph, node_id, !is_cluster);
+        let image = info_graph
+            .node_images()
+            .get(node_id)
+            .and_then(|image_id| info_graph.images().get(image_id))
+            .map(|image| {
+                let GraphvizImage {
+                    path,
+                    width,
+                    height,
+                } = image;
+                (path.as_str(), length_px(width), length_px(height))
+            });
+
+        let font_size = f64::from(theme.node_point_size());
+        let label_width = label
+            .split('\n')
+            .map(|line| text_width(line, font_size))
+            .fold(0.0f64, f64::max)
+            + image
+                .map(|(_path, width, _height)| width + IMAGE_SPACING)
+                .unwrap_or_default();
+        let label_height = (label.split('\n').count() as f64 * font_size * LINE_HEIGHT_RATIO).max(
+            image
+                .map(|(_path, _width, height)| height)
+                .unwrap_or_default(),
+        );
+
+        let layout_item = if is_cluster {
+            let (margin_x, margin_y) = margin_xy(
+                graphviz_attrs
+                    .margins()
+                    .get(node_id)
+                    .copied()
+                    .unwrap_or_else(|| graphviz_attrs.margin_cluster_default().into_inner()),
+            );
+
+            LayoutItem {
+                width: label_width + 2.0 * margin_x,
+                height: label_height + 2.0 * margin_y,
+                padding: LayoutPadding {
+                    left: margin_x,
+                    right: margin_x,
+                    top: margin_y + label_height,
+                    bottom: margin_y,
+                },
+                children: Vec::new(),
+            }
+        } else {
+            let (margin_x, margin_y) = margin_xy(
+                graphviz_attrs
+                    .margins()
+                    .get(node_id)
+                    .copied()
+                    .unwrap_or_else(|| graphviz_attrs.margin_node_default().into_inner()),
+            );
+            let (margin_x, margin_y) = (margin_x * POINTS_PER_INCH, margin_y * POINTS_PER_INCH);
+            let width_min = graphviz_attrs
+                .node_widths()
+                .get(node_id)
+                .copied()
+                .unwrap_or_else(|| graphviz_attrs.node_width_default())
+                * POINTS_PER_INCH;
+            let height_min = graphviz_attrs
+                .node_heights()
+                .get(node_id)
+                .copied()
+                .unwrap_or_else(|| graphviz_attrs.node_height_default())
+                * POINTS_PER_INCH;
+
+            let (width, height) = (label_width + 2.0 * margin_x, label_height + 2.0 * margin_y);
+            let (width, height) = match graphviz_attrs.fixed_size() {
+                FixedSize::False => (width.max(width_min), height.max(height_min)),
+                FixedSize::True | FixedSize::Shape => (width_min, height_min),
+            };
+
+            LayoutItem {
+                width,
+                height,
+                ..LayoutItem::default()
+            }
+        };
+
+        let item_index = self.layered_layout.items.len();
+        self.layered_layout.items.push(layout_item);
+        self.drawio_nodes.push(DrawioNode {
+            node_id,
+            parent_item,
+            is_cluster,
+            label,
+            image,
+        });
+        node_id_to_item.insert(node_id.as_str(), item_index);
+
+        if is_cluster {
+            let child_indices = node_hierarchy
+                .iter()
+                .map(|(child_node_id, child_node_hierarchy)| {
+                    self.node_items_push(
+                        info_graph,
+                        theme,
+                        node_id_to_item,
+                        Some(item_index),
+                        child_node_id,
+                        child_node_hierarchy,
+                    )
+                })
+                .collect::<Vec<usize>>();
+            self.layered_layout.items[item_index].children = child_indices;
+        }
+
+        item_index
+    }
+}
+
+fn drawio_write(
+    drawio_src: &mut String,
+    info_graph: &InfoGraph,
+    theme: &GraphvizDotTheme,
+    info_graph_classes: &InfoGraphClasses<'_>,
+    drawio_layout: DrawioLayout<'_>,
+) -> fmt::Result {
+    let layout_output = drawio_layout.layered_layout.layout();
+    let DrawioLayout {
+        layered_layout: _,
+        drawio_nodes,
+        drawio_edges,
+    } = drawio_layout;
+
+    writeln!(drawio_src, "<mxfile host=\"dot_ix\">")?;
+    writeln!(drawio_src, "  <diagram id=\"dot_ix\" name=\"Page-1\">")?;
+    writeln!(
+        drawio_src,
+        "    <mxGraphModel grid=\"1\" gridSize=\"10\" page=\"0\" \
+        pageWidth=\"{width}\" pageHeight=\"{height}\">",
+        width = layout_output.width.ceil(),
+        height = layout_output.height.ceil(),
+    )?;
+    writeln!(drawio_src, "      <root>")?;
+    writeln!(drawio_src, "        <mxCell id=\"{CELL_ID_ROOT}\"/>")?;
+    writeln!(
+        drawio_src,
+        "        <mxCell id=\"{CELL_ID_LAYER}\" parent=\"{CELL_ID_ROOT}\"/>"
+    )?;
+
+    // Cells within a container are positioned relative to the container.
+    drawio_nodes
+        .iter()
+        .zip(layout_output.item_rects.iter())
+        .try_for_each(|(drawio_node, rect)| {
+            let (parent_id, parent_rect) = match drawio_node.parent_item {
+                Some(parent_item) => (
+                    drawio_nodes[parent_item].node_id.as_str(),
+                    layout_output.item_rects[parent_item],
+                ),
+                None => (CELL_ID_LAYER, LayoutRect::default()),
+            };
+            let style = node_style(info_graph, theme, info_graph_classes, drawio_node);
+
+            writeln!(
+                drawio_src,
+                "        <mxCell id=\"{id}\" value=\"{value}\" style=\"{style}\" \
+                vertex=\"1\" parent=\"{parent_id}\">",
+                id = drawio_node.node_id,
+                value = xml_escape(&drawio_node.label),
+                style = xml_escape(&style),
+            )?;
+            writeln!(
+                drawio_src,
+                "          <mxGeometry x=\"{x}\" y=\"{y}\" width=\"{width}\" height=\"{height}\" \
+                as=\"geometry\"/>",
+                x = (rect.x - parent_rect.x).round(),
+                y = (rect.y - parent_rect.y).round(),
+                width = rect.width.round(),
+                height = rect.height.round(),
+            )?;
+            writeln!(drawio_src, "        </mxCell>")
+        })?;
+
+    // Edges are in the default layer, so their waypoints are not relative to
+    // any container.
+    drawio_edges.iter().try_for_each(|drawio_edge| {
+        let edge_id = drawio_edge.edge_id;
+        let style = edge_style(info_graph, info_graph_classes, drawio_edge);
+        let value = info_graph
+            .edge_descs()
+            .get(edge_id)
+            .map(|edge_desc| xml_escape(edge_desc))
+            .unwrap_or_default();
+
+        writeln!(
+            drawio_src,
+            "        <mxCell id=\"{edge_id}\" value=\"{value}\" style=\"{style}\" \
+            edge=\"1\" parent=\"{CELL_ID_LAYER}\" source=\"{src}\" target=\"{target}\">",
+            style = xml_escape(&style),
+            src = drawio_nodes[drawio_edge.src_item].node_id,
+            target = drawio_nodes[drawio_edge.target_item].node_id,
+        )?;
+        let waypoints = &layout_output.edge_waypoints[drawio_edge.layout_edge_index];
+        if waypoints.is_empty() {
+            writeln!(
+                drawio_src,
+                "          <mxGeometry relative=\"1\" as=\"geometry\"/>"
+            )?;
+        } else {
+            writeln!(
+                drawio_src,
+                "          <mxGeometry relative=\"1\" as=\"geometry\">"
+            )?;
+            writeln!(drawio_src, "            <Array as=\"points\">")?;
+            waypoints.iter().try_for_each(|waypoint| {
+                writeln!(
+                    drawio_src,
+                    "              <mxPoint x=\"{x}\" y=\"{y}\"/>",
+                    x = waypoint.x.round(),
+                    y = waypoint.y.round(),
+                )
+            })?;
+            writeln!(drawio_src, "            </Array>")?;
+            writeln!(drawio_src, "          </mxGeometry>")?;
+        }
+        writeln!(drawio_src, "        </mxCell>")
+    })?;
+
+    writeln!(drawio_src, "      </root>")?;
+    writeln!(drawio_src, "    </mxGraphModel>")?;
+    writeln!(drawio_src, "  </diagram>")?;
+    writeln!(drawio_src, "</mxfile>")?;
+
+    Ok(())
+}
+
+/// Returns the mxGraph style for a node.
+fn node_style(
+    info_graph: &InfoGraph,
+    theme: &GraphvizDotTheme,
+    info_graph_classes: &InfoGraphClasses<'_>,
+    drawio_node: &DrawioNode<'_>,
+) -> String {
+    let mut style = if drawio_node.is_cluster {
+        String::from(
+            "rounded=1;arcSize=4;absoluteArcSize=1;whiteSpace=wrap;html=0;\
+            container=1;collapsible=0;verticalAlign=top;align=left;spacingLeft=8;",
+        )
+    } else {
+        match info_graph.graph_style() {
+            GraphStyle::Box => {
+                String::from("rounded=1;arcSize=4;absoluteArcSize=1;whiteSpace=wrap;html=0;")
+            }
+            GraphStyle::Circle => String::from("ellipse;whiteSpace=wrap;html=0;"),
+        }
+    };
+    write!(style, "fontSize={};", theme.node_point_size()).expect("Failed to write style.");
+
+    if let Some((path, width, height)) = drawio_node.image {
+        // `;` separates style properties, so data URLs are written without
+        // `;base64`, which draw.io still reads as base64 data.
+        let path = path.replacen(";base64,", ",", 1);
+        write!(
+            style,
+            "shape=label;image={path};imageWidth={width};imageHeight={height};\
+            imageAlign=left;imageVerticalAlign=middle;",
+            width = width.round(),
+            height = height.round(),
+        )
+        .expect("Failed to write style.");
+    }
+
+    theme_style_push(
+        &mut style,
+        info_graph_classes,
+        drawio_node.node_id.as_str(),
+        &["[&>path]", "[&>ellipse]"],
+    );
+    style
+}
+
+/// Returns the mxGraph style for an edge.
+fn edge_style(
+    info_graph: &InfoGraph,
+    info_graph_classes: &InfoGraphClasses<'_>,
+    drawio_edge: &DrawioEdge<'_>,
+) -> String {
+    let graphviz_attrs = info_graph.graphviz_attrs();
+    let edge_dir = graphviz_attrs
+        .edge_dirs()
+        .get(drawio_edge.edge_id)
+        .copied()
+        .unwrap_or_else(|| graphviz_attrs.edge_dir_default());
+    let (start_arrow, end_arrow) = match edge_dir {
+        EdgeDir::Forward => ("none", "classic"),
+        EdgeDir::Back => ("classic", "none"),
+        EdgeDir::Both => ("classic", "classic"),
+        EdgeDir::None => ("none", "none"),
+    };
+
+    let mut style =
+        format!("rounded=1;curved=1;html=0;startArrow={start_arrow};endArrow={end_arrow};");
+    if let Some((x, y)) = drawio_edge.src_compass_point.and_then(compass_point_xy) {
+        write!(style, "exitX={x};exitY={y};exitPerimeter=0;").expect("Failed to write style.");
+    }
+    if let Some((x, y)) = drawio_edge.target_compass_point.and_then(compass_point_xy) {
+        write!(style, "entryX={x};entryY={y};entryPerimeter=0;").expect("Failed to write style.");
+    }
+
+    theme_style_push(
+        &mut style,
+        info_graph_classes,
+        drawio_edge.edge_id.as_str(),
+        &["[&>path]"],
+    );
+    style
+}
+
+/// Appends the mxGraph style properties for an element's theme colours, line
+/// width, and dashes.
+fn theme_style_push(
+    style: &mut String,
+    info_graph_classes: &InfoGraphClasses<'_>,
+    el_id: &str,
+    shape_variants: &[&str],
+) {
+    info_graph_classes
+        .shape_properties(el_id, shape_variants)
+        .iter()
+        .for_each(|(property, value)| {
+            let style_property = match *property {
+                "fill" => Some(format!("fillColor={value};")),
+                "stroke" => Some(format!("strokeColor={value};")),
+                "stroke-width" => {
+                    let value = value.trim_end_matches("px");
+                    value
+                        .parse::<f64>()
+                        .is_ok()
+                        .then(|| format!("strokeWidth={value};"))
+                }
+                "stroke-dasharray" => {
+                    let dash_pattern = value
+                        .split([',', ' '])
+                        .filter(|length| !length.is_empty())
+                        .collect::<Vec<&str>>()
+                        .join(" ");
+                    match dash_pattern.as_str() {
+                        "" | "0" | "none" => Some(String::from("dashed=0;")),
+                        _ => Some(format!("dashed=1;dashPattern={dash_pattern};")),
+                    }
+                }
+                "color" => Some(format!("fontColor={value};")),
+                _ => None,
+            };
+            if let Some(style_property) = style_property {
+                style.push_str(&style_property);
+            }
+        });
+}
+
+/// Returns the label for a node, which is its emoji and name, followed by its
+/// description for nodes that are not clusters.
+fn node_label(info_graph: &InfoGraph, node_id: &NodeId, with_desc: bool) -> String {
+    let node_name = info_graph
+        .node_names()
+        .get(node_id)
+        .map(String::as_str)
+        .unwrap_or(node_id.as_str());
+    let mut node_label = match info_graph.node_emojis().get(node_id) {
+        Some(node_emoji) => format!("{node_emoji} {node_name}"),
+        None => node_name.to_string(),
+    };
+
+    if let Some(node_desc) = info_graph.node_descs().get(node_id).filter(|_| with_desc) {
+        node_label.push('\n');
+        node_label.push_str(node_desc);
+    }
+
+    node_label
+}
+
+/// Returns the position of a compass point relative to a cell's size, e.g.
+/// `(1, 0)` for `"ne"`.
+fn compass_point_xy(compass_point: &str) -> Option<(f64, f64)> {
+    let xy = match compass_point {
+        "n" => (0.5, 0.0),
+        "ne" => (1.0, 0.0),
+        "e" => (1.0, 0.5),
+        "se" => (1.0, 1.0),
+        "s" => (0.5, 1.0),
+        "sw" => (0.0, 1.0),
+        "w" => (0.0, 0.5),
+        "nw" => (0.0, 0.0),
+        "c" => (0.5, 0.5),
+        _ => return None,
+    };
+    Some(xy)
+}
+
+/// Returns the horizontal and vertical margins.
+fn margin_xy(margin: Margin) -> (f64, f64) {
+    match margin {
+        Margin::Same(margin) => (margin, margin),
+        Margin::Different(margin_x, margin_y) => (margin_x, margin_y),
+    }
+}
+
+/// Parses a length such as `"50px"` into points.
+fn length_px(length: &str) -> f64 {
+    length
+        .trim()
+        .trim_end_matches("px")
+        .parse::<f64>()
+        .unwrap_or_default()
+}
+
+/// Returns the width of the text, assuming a monospace font.
+///
+/// Wide characters such as emojis and CJK characters are counted as two
+/// characters.
+fn text_width(text: &str, font_size: f64) -> f64 {
+    let char_count = text
+        .chars()
+        .map(|c| if u32::from(c) >= 0x2E80 { 2.0 } else { 1.0 })
+        .sum::<f64>();
+
+    char_count * font_size * CHAR_WIDTH_RATIO
+}
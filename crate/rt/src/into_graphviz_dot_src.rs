@@ -1,8 +1,29 @@
 use dot_ix_model::common::{DotSrcAndStyles, GraphvizDotTheme};
 
+pub use self::info_graph::write_dot;
+
 mod info_graph;
+mod src_map_writer;
 
 /// Generates GraphViz Dot source that can be rendered using `dot`.
 pub trait IntoGraphvizDotSrc {
     fn into(self, theme: &GraphvizDotTheme) -> DotSrcAndStyles;
+
+    /// Generates GraphViz Dot source, along with a [`DotSrcMap`] of the lines
+    /// generated for each node, edge, and tag.
+    ///
+    /// The source map can be used to find which element a Graphviz error's line
+    /// number refers to.
+    ///
+    /// The default implementation calls [`into`] and does not record a source
+    /// map.
+    ///
+    /// [`DotSrcMap`]: dot_ix_model::common::dot_src_and_styles::DotSrcMap
+    /// [`into`]: Self::into
+    fn into_with_src_map(self, theme: &GraphvizDotTheme) -> DotSrcAndStyles
+    where
+        Self: Sized,
+    {
+        self.into(theme)
+    }
 }
@@ -1,25 +1,27 @@
 use std::{
     borrow::Cow,
+    collections::HashMap,
     fmt::{self, Write},
 };
 
 use dot_ix_model::{
     common::{
-        dot_src_and_styles::{GraphvizImage, GraphvizOpts},
-        graphviz_attrs::{EdgeDir, FixedSize, NodeHeights, NodeWidths, Splines},
+        dot_src_and_styles::{EdgeBundles, GraphvizImage, GraphvizOpts},
+        graphviz_attrs::{ArrowType, EdgeDir, FixedSize, NodeHeights, NodeWidths, Splines},
         AnyId, DotSrcAndStyles, EdgeId, GraphvizAttrs, GraphvizDotTheme, ImageId, Images,
         NodeHierarchy, NodeId, TagId, TagNames,
     },
     info_graph::{GraphDir, GraphStyle, InfoGraph},
-    theme::{ElCssClasses, Theme},
+    theme::ElCssClasses,
 };
 use indexmap::{IndexMap, IndexSet};
-use indoc::{formatdoc, writedoc};
+use indoc::writedoc;
 
-use crate::{InfoGraphDot, IntoGraphvizDotSrc};
-
-/// Hack to get Chrome/Edge to not display black box around focused nodes.
-const OUTLINE_NONE: &str = "outline-none";
+use crate::{
+    info_graph_classes::{InfoGraphClasses, OUTLINE_NONE},
+    into_graphviz_dot_src::src_map_writer::SrcMapWriter,
+    InfoGraphDot, IntoGraphvizDotSrc,
+};
 
 /// Renders a GraphViz Dot diagram with interactive styling.
 ///
@@ -47,8 +49,14 @@ const OUTLINE_NONE: &str = "outline-none";
 /// This can be avoided by handling requests using an up-to-date version of
 /// GraphViz -- 8.1.0 at the time of writing.
 ///
+/// [`Diagnostic::info_graph_check`] reports this and other patterns that
+/// Graphviz renders badly, and [`Diagnostic::graphviz_error_explain`] explains
+/// errors like the above to users.
+///
 /// [graphviz#1879]: https://gitlab.com/graphviz/graphviz/-/issues/1879
 /// [graphviz#1949]: https://gitlab.com/graphviz/graphviz/-/issues/1949
+/// [`Diagnostic::info_graph_check`]: crate::Diagnostic::info_graph_check
+/// [`Diagnostic::graphviz_error_explain`]: crate::Diagnostic::graphviz_error_explain
 ///
 /// ## Font Metrics
 ///
@@ -79,151 +87,248 @@ const OUTLINE_NONE: &str = "outline-none";
 ///
 /// ## Tailwind CSS Generation
 ///
-/// Currently tailwind compilation is done on `.rs` source. This means the
-/// classes must be fully spelt out in source, and cannot be computed string
-/// formats.
+/// Tailwind compilation for the playground is done on `.rs` source. This means
+/// the classes must be fully spelt out in source, and cannot be computed
+/// string formats.
 ///
-/// If we were to support runtime tailwind CSS generation, e.g. the user
-/// specifying a colour which is plugged into a `format!("fill-{colour}")`, then
-/// we need to run tailwind on the generated dot source.
+/// Since theme classes are computed at runtime, e.g. the user specifying a
+/// colour which is plugged into a `format!("fill-{colour}")`, the classes
+/// used in the generated dot source are translated into plain CSS by
+/// [`TailwindCssGen`], and prepended to [`DotSrcAndStyles::styles`]. This
+/// means the rendered SVG does not need Tailwind to be run.
 ///
-/// The most complete crate for this at the tim of writing is [`tailwind-css`],
-/// however it uses the MPL-2.0 license which is copyleft, requiring a binary
-/// compiled with it to be open source, which is not something users of `dot_ix`
-/// may be able to work with.
+/// The most complete crate for full Tailwind generation at the time of writing
+/// is [`tailwind-css`], however it uses the MPL-2.0 license which is copyleft,
+/// requiring a binary compiled with it to be open source, which is not
+/// something users of `dot_ix` may be able to work with.
 ///
+/// [`TailwindCssGen`]: crate::TailwindCssGen
 /// [`tailwind-css`]: https://github.com/oovm/tailwind-rs
 impl IntoGraphvizDotSrc for &InfoGraph {
     fn into(self, theme: &GraphvizDotTheme) -> DotSrcAndStyles {
-        let graph_style = self.graph_style();
-        let graphviz_attrs = self.graphviz_attrs();
-        let graph_attrs = graph_attrs(theme, self.direction(), graphviz_attrs);
-        let node_attrs = node_attrs(graph_style, graphviz_attrs, theme);
-        let edge_attrs = edge_attrs(graphviz_attrs, theme);
-        let diagram_theme = self.theme();
-
-        // Build a map from `NodeId` to their `NodeHierarchy`, so that we don't have to
-        // search for it every time we want to create an edge.
-        let node_id_to_hierarchy = self.hierarchy_flat();
-        let node_id_to_hierarchy = &node_id_to_hierarchy;
-
-        let info_graph_dot = InfoGraphDot {
-            graph_style,
-            node_id_to_hierarchy,
-            node_ids: node_id_to_hierarchy.keys().copied().collect::<Vec<_>>(),
-            edge_ids: self.edges().keys().collect::<Vec<_>>(),
-        };
-        let info_graph_dot = &info_graph_dot;
-        let (el_css_classes, diagram_theme_warnings) = diagram_theme.el_css_classes(info_graph_dot);
-        let el_css_classes = &el_css_classes;
-
-        // tag styles per tag
-        let tag_styles_focus = self.tag_styles_focus();
-        let (tag_el_css_classes_map, theme_warnings) = self.tags().keys().fold(
-            (
-                IndexMap::<&TagId, ElCssClasses>::new(),
-                diagram_theme_warnings,
-            ),
-            |(mut tag_el_css_classes_map_acc, mut theme_warnings_acc), tag_id| {
-                let tag_theme = tag_styles_focus
-                    .get(tag_id)
-                    .cloned()
-                    .map(Theme::from)
-                    .unwrap_or_else(Theme::tag_base);
-                let (tag_el_css_classes, tag_theme_warnings) =
-                    tag_theme.tag_el_css_classes(info_graph_dot, diagram_theme, tag_id);
-
-                tag_el_css_classes_map_acc.insert(tag_id, tag_el_css_classes);
-                theme_warnings_acc.extend(tag_theme_warnings.into_inner());
-
-                (tag_el_css_classes_map_acc, theme_warnings_acc)
-            },
-        );
-        let tag_el_css_classes_map = &tag_el_css_classes_map;
+        dot_src_and_styles(self, theme, false)
+    }
 
-        let node_widths = graphviz_attrs.node_widths();
-        let node_heights = graphviz_attrs.node_heights();
-        let node_clusters = self
-            .hierarchy()
-            .iter()
-            .map(|(node_id, node_hierarchy)| {
-                let node_cluster_args = NodeClusterArgs {
-                    info_graph: self,
-                    el_css_classes,
-                    tag_el_css_classes_map,
-                    theme,
-                    node_id,
-                    node_hierarchy,
-                    node_widths,
-                    node_heights,
-                };
+    fn into_with_src_map(self, theme: &GraphvizDotTheme) -> DotSrcAndStyles {
+        dot_src_and_styles(self, theme, true)
+    }
+}
+
+fn dot_src_and_styles(
+    info_graph: &InfoGraph,
+    theme: &GraphvizDotTheme,
+    src_map_enabled: bool,
+) -> DotSrcAndStyles {
+    let info_graph = info_graph_depth_limited(info_graph);
+    let info_graph = info_graph.as_ref();
+
+    // Build a map from `NodeId` to their `NodeHierarchy`, so that we don't have to
+    // search for it every time we want to create an edge.
+    let node_id_to_hierarchy = info_graph.hierarchy_flat();
+    let node_id_to_parent = info_graph.node_parents();
+    let el_tag_ids = info_graph.el_tag_ids();
+    let info_graph_dot = info_graph_dot(
+        info_graph,
+        &node_id_to_hierarchy,
+        &node_id_to_parent,
+        &el_tag_ids,
+    );
+    let info_graph_classes = InfoGraphClasses::new(info_graph, &info_graph_dot);
+
+    let mut dot_src = String::new();
+    let mut src_map_writer = if src_map_enabled {
+        SrcMapWriter::with_src_map(&mut dot_src)
+    } else {
+        SrcMapWriter::new(&mut dot_src)
+    };
+    let edge_bundles = dot_write(
+        info_graph,
+        theme,
+        &info_graph_dot,
+        &info_graph_classes,
+        &mut src_map_writer,
+    )
+    .expect("Failed to write `dot_src` string.");
+    let src_map = src_map_writer.into_src_map();
 
-                node_cluster(node_cluster_args)
+    let opts = {
+        let images = info_graph
+            .images()
+            .iter()
+            .map(|(image_id, image)| GraphvizImage {
+                path: image_id.as_str().to_string(),
+                width: image.width().to_string(),
+                height: image.height().to_string(),
             })
-            .collect::<Vec<String>>()
-            .join("\n");
-
-        let edges = edges(
-            self,
-            node_id_to_hierarchy,
-            el_css_classes,
-            tag_el_css_classes_map,
-        );
-
-        let mut tag_legend_buffer = String::with_capacity(512 * self.tags().len() + 512);
-        tag_legend(
-            self.direction(),
-            &mut tag_legend_buffer,
-            theme,
-            el_css_classes,
-            self.tags(),
-        )
-        .expect("Failed to write `tag_legend` string.");
+            .collect();
 
-        let opts = {
-            let images = self
-                .images()
-                .iter()
-                .map(|(image_id, image)| GraphvizImage {
-                    path: image_id.as_str().to_string(),
-                    width: image.width().to_string(),
-                    height: image.height().to_string(),
-                })
-                .collect();
-
-            GraphvizOpts::new(images)
-        };
+        GraphvizOpts::new(images)
+    };
 
-        let dot_src = formatdoc!(
-            "digraph G {{
-                {graph_attrs}
-                {node_attrs}
-                {edge_attrs}
+    let styles = info_graph_classes.styles(theme, info_graph.css());
 
-                {tag_legend_buffer}
+    DotSrcAndStyles {
+        dot_src,
+        styles,
+        opts,
+        theme_warnings: info_graph_classes.theme_warnings,
+        src_map,
+        edge_bundles,
+    }
+}
 
-                {node_clusters}
+/// Writes the GraphViz dot source for an `InfoGraph` to the given writer.
+///
+/// This writes the same dot source as [`IntoGraphvizDotSrc`], without
+/// building the source for each node and edge in its own `String`, which is
+/// useful for large diagrams, or when the source is written to a file or
+/// socket.
+///
+/// The CSS styles for the rendered SVG are not written, and can be generated
+/// using [`IntoGraphvizDotSrc`].
+///
+/// [`IntoGraphvizDotSrc`]: crate::IntoGraphvizDotSrc
+pub fn write_dot<W>(info_graph: &InfoGraph, theme: &GraphvizDotTheme, writer: &mut W) -> fmt::Result
+where
+    W: Write,
+{
+    let info_graph = info_graph_depth_limited(info_graph);
+    let info_graph = info_graph.as_ref();
+
+    let node_id_to_hierarchy = info_graph.hierarchy_flat();
+    let node_id_to_parent = info_graph.node_parents();
+    let el_tag_ids = info_graph.el_tag_ids();
+    let info_graph_dot = info_graph_dot(
+        info_graph,
+        &node_id_to_hierarchy,
+        &node_id_to_parent,
+        &el_tag_ids,
+    );
+    let info_graph_classes = InfoGraphClasses::new(info_graph, &info_graph_dot);
 
-                {edges}
-            }}"
-        );
+    dot_write(
+        info_graph,
+        theme,
+        &info_graph_dot,
+        &info_graph_classes,
+        &mut SrcMapWriter::new(writer),
+    )
+    .map(|_edge_bundles| ())
+}
 
-        let styles = self.css().to_string();
+/// Returns the `InfoGraph` with [`GraphvizAttrs::max_depth`] applied.
+///
+/// [`GraphvizAttrs::max_depth`]: dot_ix_model::common::GraphvizAttrs::max_depth
+fn info_graph_depth_limited(info_graph: &InfoGraph) -> Cow<'_, InfoGraph> {
+    match info_graph.graphviz_attrs().max_depth() {
+        Some(max_depth) => Cow::Owned(info_graph.depth_limited(max_depth)),
+        None => Cow::Borrowed(info_graph),
+    }
+}
 
-        DotSrcAndStyles {
-            dot_src,
-            styles,
-            opts,
-            theme_warnings,
-        }
+/// Returns the `InfoGraphDot` for an `InfoGraph`, borrowing the maps that are
+/// computed once for all elements.
+fn info_graph_dot<'graph>(
+    info_graph: &'graph InfoGraph,
+    node_id_to_hierarchy: &'graph HashMap<&'graph NodeId, &'graph NodeHierarchy>,
+    node_id_to_parent: &'graph HashMap<&'graph NodeId, &'graph NodeId>,
+    el_tag_ids: &'graph HashMap<&'graph str, IndexSet<&'graph TagId>>,
+) -> InfoGraphDot<'graph> {
+    InfoGraphDot {
+        graph_style: info_graph.graph_style(),
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: info_graph.edges(),
+        graphviz_attrs: info_graph.graphviz_attrs(),
+        el_tag_ids,
+        node_ids: node_id_to_hierarchy.keys().copied().collect::<Vec<_>>(),
+        edge_ids: info_graph.edges().keys().collect::<Vec<_>>(),
     }
 }
 
-fn graph_attrs(
+/// Writes the dot source, and returns the edges that are drawn as one edge
+/// when edge bundling is enabled.
+fn dot_write<W>(
+    info_graph: &InfoGraph,
+    theme: &GraphvizDotTheme,
+    info_graph_dot: &InfoGraphDot<'_>,
+    info_graph_classes: &InfoGraphClasses<'_>,
+    buffer: &mut SrcMapWriter<'_, W>,
+) -> Result<EdgeBundles, fmt::Error>
+where
+    W: Write,
+{
+    let InfoGraphDot {
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        el_tag_ids,
+        ..
+    } = info_graph_dot;
+    let graph_style = info_graph.graph_style();
+    let graphviz_attrs = info_graph.graphviz_attrs();
+    let node_widths = graphviz_attrs.node_widths();
+    let node_heights = graphviz_attrs.node_heights();
+
+    // Only the first line of each section is indented.
+    buffer.write_str("digraph G {\n    ")?;
+    graph_attrs_write(buffer, theme, info_graph.direction(), graphviz_attrs)?;
+    buffer.write_str("\n    ")?;
+    node_attrs_write(buffer, graph_style, graphviz_attrs, theme)?;
+    buffer.write_str("\n    ")?;
+    edge_attrs_write(buffer, graphviz_attrs, theme)?;
+    buffer.write_str("\n\n    ")?;
+    tag_legend_write(
+        buffer,
+        info_graph.direction(),
+        theme,
+        &info_graph_classes.el_css_classes,
+        info_graph.tags(),
+    )?;
+    buffer.write_str("\n\n    ")?;
+
+    info_graph.hierarchy().iter().enumerate().try_for_each(
+        |(node_index, (node_id, node_hierarchy))| {
+            if node_index != 0 {
+                buffer.write_char('\n')?;
+            }
+            let node_cluster_args = NodeClusterArgs {
+                info_graph,
+                info_graph_classes,
+                node_id_to_parent,
+                el_tag_ids,
+                theme,
+                node_id,
+                node_hierarchy,
+                node_widths,
+                node_heights,
+            };
+
+            node_cluster_write(node_cluster_args, buffer)
+        },
+    )?;
+    buffer.write_str("\n\n    ")?;
+
+    let edge_bundles = edges_write(
+        buffer,
+        info_graph,
+        node_id_to_hierarchy,
+        el_tag_ids,
+        info_graph_classes,
+    )?;
+    buffer.write_str("\n}")?;
+
+    Ok(edge_bundles)
+}
+
+fn graph_attrs_write<W>(
+    buffer: &mut W,
     theme: &GraphvizDotTheme,
     graph_dir: GraphDir,
     graphviz_attrs: &GraphvizAttrs,
-) -> String {
+) -> fmt::Result
+where
+    W: Write,
+{
     let plain_text_color = theme.plain_text_color();
     // Note: `margin` is set to 0.1 because some text lies outside the viewport.
     // This may be due to incorrect width calculation for emoji characters, which
@@ -241,17 +346,21 @@ fn graph_attrs(
     let nodesep = graphviz_attrs.nodesep();
     let ranksep = graphviz_attrs.ranksep();
     let splines = graphviz_attrs.splines();
-    let splines = match splines {
-        Splines::Unset => Cow::Borrowed(""),
-        Splines::None
-        | Splines::Line
-        | Splines::Polyline
-        | Splines::Curved
-        | Splines::Ortho
-        | Splines::Spline => Cow::Owned(format!("splines = {splines}")),
-    };
+    let splines = AttrOpt(
+        "splines",
+        match splines {
+            Splines::Unset => None,
+            Splines::None
+            | Splines::Line
+            | Splines::Polyline
+            | Splines::Curved
+            | Splines::Ortho
+            | Splines::Spline => Some(splines),
+        },
+    );
 
-    formatdoc!(
+    writedoc!(
+        buffer,
         r#"
         compound  = true
         graph [
@@ -270,11 +379,15 @@ fn graph_attrs(
     )
 }
 
-fn node_attrs(
+fn node_attrs_write<W>(
+    buffer: &mut W,
     graph_style: GraphStyle,
     graphviz_attrs: &GraphvizAttrs,
     theme: &GraphvizDotTheme,
-) -> String {
+) -> fmt::Result
+where
+    W: Write,
+{
     let node_style_and_shape = match graph_style {
         GraphStyle::Box => {
             "shape     = \"rect\"
@@ -292,12 +405,16 @@ fn node_attrs(
     let node_width = graphviz_attrs.node_width_default();
     let node_height = graphviz_attrs.node_height_default();
     let fixed_size = graphviz_attrs.fixed_size();
-    let fixed_size = match fixed_size {
-        FixedSize::False => Cow::Borrowed(""),
-        FixedSize::True | FixedSize::Shape => Cow::Owned(format!(r#"fixedsize = {fixed_size}"#)),
-    };
+    let fixed_size = AttrOpt(
+        "fixedsize",
+        match fixed_size {
+            FixedSize::False => None,
+            FixedSize::True | FixedSize::Shape => Some(fixed_size),
+        },
+    );
 
-    formatdoc!(
+    writedoc!(
+        buffer,
         r#"
         node [
             fontcolor = "{node_text_color}"
@@ -313,15 +430,27 @@ fn node_attrs(
     )
 }
 
-fn edge_attrs(graphviz_attrs: &GraphvizAttrs, theme: &GraphvizDotTheme) -> String {
+fn edge_attrs_write<W>(
+    buffer: &mut W,
+    graphviz_attrs: &GraphvizAttrs,
+    theme: &GraphvizDotTheme,
+) -> fmt::Result
+where
+    W: Write,
+{
     let edge_color = theme.edge_color();
     let plain_text_color = theme.plain_text_color();
     let edge_point_size = theme.edge_point_size();
     let edge_constraint_default = graphviz_attrs.edge_constraint_default();
     let edge_dir_default = graphviz_attrs.edge_dir_default();
     let edge_minlen_default = graphviz_attrs.edge_minlen_default();
+    let edge_arrowhead_default = graphviz_attrs.edge_arrowhead_default();
+    let edge_arrowtail_default = graphviz_attrs.edge_arrowtail_default();
+    let edge_arrowsize_default = graphviz_attrs.edge_arrowsize_default();
+    let edge_penwidth_default = graphviz_attrs.edge_penwidth_default();
 
-    formatdoc!(
+    writedoc!(
+        buffer,
         r#"
         edge [
             constraint = {edge_constraint_default},
@@ -329,7 +458,10 @@ fn edge_attrs(graphviz_attrs: &GraphvizAttrs, theme: &GraphvizDotTheme) -> Strin
             minlen     = {edge_minlen_default},
             fontname   = "liberationmono"
             fontsize   = {edge_point_size}
-            arrowsize  = 0.7
+            arrowhead  = {edge_arrowhead_default}
+            arrowtail  = {edge_arrowtail_default}
+            arrowsize  = {edge_arrowsize_default}
+            penwidth   = {edge_penwidth_default}
             color      = "{edge_color}"
             fontcolor  = "{plain_text_color}"
         ]
@@ -337,23 +469,18 @@ fn edge_attrs(graphviz_attrs: &GraphvizAttrs, theme: &GraphvizDotTheme) -> Strin
     )
 }
 
-fn node_cluster(node_cluster_args: NodeClusterArgs<'_>) -> String {
-    let mut buffer = String::with_capacity(1024);
-
-    node_cluster_internal(node_cluster_args, &mut buffer)
-        .expect("Failed to write node_cluster string.");
-
-    buffer
-}
-
-fn node_cluster_internal(
+fn node_cluster_write<W>(
     node_cluster_args: NodeClusterArgs<'_>,
-    buffer: &mut String,
-) -> fmt::Result {
+    buffer: &mut SrcMapWriter<'_, W>,
+) -> fmt::Result
+where
+    W: Write,
+{
     let NodeClusterArgs {
         info_graph,
-        el_css_classes,
-        tag_el_css_classes_map,
+        info_graph_classes,
+        node_id_to_parent,
+        el_tag_ids,
         theme,
         node_id,
         node_hierarchy,
@@ -365,16 +492,12 @@ fn node_cluster_internal(
     let node_names = info_graph.node_names();
     let node_descs = info_graph.node_descs();
     let node_emojis = info_graph.node_emojis();
-    let node_tags_set = info_graph.node_tags_set();
     let images = info_graph.images();
     let node_images = info_graph.node_images();
     let graph_dir = info_graph.direction();
-    let node_tailwind_classes = el_css_classes
-        .get(&AnyId::from(node_id.clone()))
-        .map(AsRef::<str>::as_ref)
-        .unwrap_or_default();
     let graphviz_attrs = info_graph.graphviz_attrs();
     let margins = graphviz_attrs.margins();
+    let line_start = buffer.line();
 
     let node_point_size = theme.node_point_size();
     let node_name = node_names.get(node_id).map(String::as_str);
@@ -384,55 +507,33 @@ fn node_cluster_internal(
     // TODO: escape
     let node_label = node_name.unwrap_or(node_id);
     // TODO: escape
-    let node_desc = node_desc
-        .map(|desc| desc.replace('\n', "<br />"))
-        .map(|desc| format!("<tr><td balign=\"left\">{desc}</td></tr>"));
-    let node_desc = node_desc.as_deref();
-
-    let image = image(images, node_image, node_desc);
-    let image = image.as_deref().unwrap_or("");
-    let emoji = emoji(node_emoji, node_desc, theme, node_point_size);
-    let emoji = emoji.as_deref().unwrap_or("");
-    let node_desc = node_desc.unwrap_or("");
-
-    let node_tag_classes = node_tags_set
-        .get(node_id)
-        .map(|tag_ids| {
-            tag_ids
-                .iter()
-                .filter_map(|tag_id| tag_el_css_classes_map.get(tag_id))
-                .map(|el_css_classes| {
-                    el_css_classes
-                        .get(&AnyId::from(node_id.clone()))
-                        .map(AsRef::<str>::as_ref)
-                        .unwrap_or_default()
-                })
-                .collect::<String>()
-        })
-        .unwrap_or_default();
-
-    let node_width = node_widths
-        .get(node_id)
-        .map(|node_width| Cow::<str>::Owned(format!("width = {node_width}")))
-        .unwrap_or_default();
-    let node_height = node_heights
-        .get(node_id)
-        .map(|node_height| Cow::<str>::Owned(format!("height = {node_height}")))
-        .unwrap_or_default();
-
-    // Note: There's no space between `{node_tailwind_classes}{node_tag_classes}`
-    // because for some reason spaces before `{node_tag_classes}` are translated
-    // into the `0xa0` byte.
-    //
-    // Same thing happens for `{edge_tag_classes}`
+    let node_desc = node_desc.map(NodeDescRow);
+
+    let image = DisplayOpt(image(images, node_image, node_desc.is_some()));
+    let emoji = DisplayOpt(emoji(
+        node_emoji,
+        node_desc.is_some(),
+        theme,
+        node_point_size,
+    ));
+    let node_desc = DisplayOpt(node_desc);
+
+    let node_classes = ElClasses {
+        info_graph_classes,
+        el_id: node_id.as_str(),
+        tag_ids: el_tag_ids.get(node_id.as_str()),
+    };
+
+    let node_width = AttrOpt("width", node_widths.get(node_id));
+    let node_height = AttrOpt("height", node_heights.get(node_id));
+
+    // Note: `InfoGraphClasses::el_classes` doesn't insert a space before the tag
+    // classes, because for some reason spaces before them are translated into
+    // the `0xa0` byte.
     if node_hierarchy.is_empty() {
         match graph_style {
             GraphStyle::Box => {
-                let margin = margins
-                    .get(node_id)
-                    .copied()
-                    .map(|margin| Cow::<str>::Owned(format!(r#"margin = "{margin}""#)))
-                    .unwrap_or_default();
+                let margin = AttrOpt("margin", margins.get(node_id).map(Quoted));
 
                 writedoc!(
                     buffer,
@@ -449,7 +550,7 @@ fn node_cluster_internal(
                                 </tr>
                                 {node_desc}
                             </table>>
-                            class = "{OUTLINE_NONE} {node_tailwind_classes}{node_tag_classes}"
+                            class = "{OUTLINE_NONE} {node_classes}"
                             {node_width}
                             {node_height}
                             {margin}
@@ -485,7 +586,7 @@ fn node_cluster_internal(
 
                             {node_id} [
                                 label = ""
-                                class = "{OUTLINE_NONE} {node_tailwind_classes}{node_tag_classes}"
+                                class = "{OUTLINE_NONE} {node_classes}"
                                 {margin_inner}
                             ]
                             {node_id}_text [
@@ -532,7 +633,7 @@ fn node_cluster_internal(
                         {node_desc}
                     </table>>
                     style = "filled,rounded"
-                    class = "{OUTLINE_NONE} {node_tailwind_classes}{node_tag_classes}"
+                    class = "{OUTLINE_NONE} {node_classes}"
                     {node_width}
                     {node_height}
             "#
@@ -547,8 +648,9 @@ fn node_cluster_internal(
                 .try_for_each(|(child_node_id, child_node_hierarchy)| {
                     let node_cluster_args = NodeClusterArgs {
                         info_graph,
-                        el_css_classes,
-                        tag_el_css_classes_map,
+                        info_graph_classes,
+                        node_id_to_parent,
+                        el_tag_ids,
                         theme,
                         node_id: child_node_id,
                         node_hierarchy: child_node_hierarchy,
@@ -556,7 +658,7 @@ fn node_cluster_internal(
                         node_heights,
                     };
 
-                    node_cluster_internal(node_cluster_args, buffer)
+                    node_cluster_write(node_cluster_args, buffer)
                 })?,
             GraphDir::Vertical => {
                 node_hierarchy
@@ -564,8 +666,9 @@ fn node_cluster_internal(
                     .try_for_each(|(child_node_id, child_node_hierarchy)| {
                         let node_cluster_args = NodeClusterArgs {
                             info_graph,
-                            el_css_classes,
-                            tag_el_css_classes_map,
+                            info_graph_classes,
+                            node_id_to_parent,
+                            el_tag_ids,
                             theme,
                             node_id: child_node_id,
                             node_hierarchy: child_node_hierarchy,
@@ -573,7 +676,7 @@ fn node_cluster_internal(
                             node_heights,
                         };
 
-                        node_cluster_internal(node_cluster_args, buffer)
+                        node_cluster_write(node_cluster_args, buffer)
                     })?
             }
         }
@@ -581,58 +684,125 @@ fn node_cluster_internal(
         write!(buffer, "}}")?;
     }
 
+    buffer.entry_push(line_start, || {
+        (
+            AnyId::from(node_id.clone()),
+            node_doc_path(node_id_to_parent, node_id),
+        )
+    });
+
     Ok(())
 }
 
-fn image(images: &Images, node_image: Option<&ImageId>, node_desc: Option<&str>) -> Option<String> {
+/// Returns the path to the node in the `InfoGraph` document, e.g.
+/// `hierarchy.a.b`.
+fn node_doc_path(node_id_to_parent: &HashMap<&NodeId, &NodeId>, node_id: &NodeId) -> String {
+    let mut node_ids = vec![node_id];
+    let mut node_id_current = node_id;
+    while let Some(parent_node_id) = node_id_to_parent.get(node_id_current) {
+        node_ids.push(parent_node_id);
+        node_id_current = parent_node_id;
+    }
+
+    node_ids
+        .into_iter()
+        .rev()
+        .fold(String::from("hierarchy"), |mut doc_path, node_id| {
+            doc_path.push('.');
+            doc_path.push_str(node_id.as_str());
+            doc_path
+        })
+}
+
+fn image<'f>(
+    images: &'f Images,
+    node_image: Option<&'f ImageId>,
+    has_desc: bool,
+) -> Option<ImageCells<'f>> {
     node_image
         .and_then(|image_id| images.get(image_id).map(|image| (image_id, image)))
-        .map(|(image_id, image)| {
-            let rowspan = if node_desc.is_some() {
-                "rowspan=\"2\""
-            } else {
-                ""
-            };
-
-            let GraphvizImage {
-                path: _,
-                width,
-                height,
-            } = image;
-
-            // Extra `<td>` is for spacing
-            format!(
-                "\
-                <td \
-                    valign=\"top\" \
-                    {rowspan} \
-                    fixedsize=\"true\" \
-                    width=\"{width}\" \
-                    height=\"{height}\" \
-                >\
-                    <img src=\"{image_id}\" />\
-                </td>\
-                <td \
-                    {rowspan} \
-                    fixedsize=\"true\" \
-                    width=\"10px\" \
-                ></td>"
-            )
+        .map(|(image_id, image)| ImageCells {
+            image_id,
+            image,
+            has_desc,
         })
 }
 
-fn emoji(
-    node_emoji: Option<&str>,
-    node_desc: Option<&str>,
+/// Table cells for a node's image, and the spacing after it.
+struct ImageCells<'f> {
+    image_id: &'f ImageId,
+    image: &'f GraphvizImage,
+    has_desc: bool,
+}
+
+impl fmt::Display for ImageCells<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ImageCells {
+            image_id,
+            image,
+            has_desc,
+        } = self;
+        let rowspan = if *has_desc { "rowspan=\"2\"" } else { "" };
+
+        let GraphvizImage {
+            path: _,
+            width,
+            height,
+        } = image;
+
+        // Extra `<td>` is for spacing
+        write!(
+            f,
+            "\
+            <td \
+                valign=\"top\" \
+                {rowspan} \
+                fixedsize=\"true\" \
+                width=\"{width}\" \
+                height=\"{height}\" \
+            >\
+                <img src=\"{image_id}\" />\
+            </td>\
+            <td \
+                {rowspan} \
+                fixedsize=\"true\" \
+                width=\"10px\" \
+            ></td>"
+        )
+    }
+}
+
+fn emoji<'f>(
+    node_emoji: Option<&'f str>,
+    has_desc: bool,
     theme: &GraphvizDotTheme,
     node_point_size: u32,
-) -> Option<String> {
-    node_emoji.map(|emoji| {
-        let rowspan = if node_desc.is_some() {
-            "rowspan=\"2\""
-        } else {
-            ""
-        };
+) -> Option<EmojiCell<'f>> {
+    node_emoji.map(|emoji| EmojiCell {
+        emoji,
+        has_desc,
+        emoji_point_size: theme.emoji_point_size(),
+        node_point_size,
+    })
+}
+
+/// Table cell for a node's emoji.
+struct EmojiCell<'f> {
+    emoji: &'f str,
+    has_desc: bool,
+    emoji_point_size: u32,
+    node_point_size: u32,
+}
+
+impl fmt::Display for EmojiCell<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let EmojiCell {
+            emoji,
+            has_desc,
+            emoji_point_size,
+            node_point_size,
+        } = *self;
+        let rowspan = if has_desc { "rowspan=\"2\"" } else { "" };
 
         // Graphviz uses one space character per byte in the emoji.
         //
@@ -648,14 +818,14 @@ fn emoji(
         // * `width`
         // * `height`
         let cell_spacing = 2;
-        let emoji_point_size = theme.emoji_point_size();
         let emoji_point_size_spaced = emoji_point_size + cell_spacing;
-        let row_height = if node_desc.is_some() {
+        let row_height = if has_desc {
             node_point_size * 2
         } else {
             node_point_size
         };
-        formatdoc!(
+        writedoc!(
+            f,
             "\
             <td \
                 valign=\"top\" \
@@ -679,13 +849,90 @@ fn emoji(
                 </table>
             </td>"
         )
-    })
+    }
+}
+
+/// Table row for a node's description, with line breaks as `<br />`.
+#[derive(Clone, Copy)]
+struct NodeDescRow<'f>(&'f str);
+
+impl fmt::Display for NodeDescRow<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<tr><td balign=\"left\">")?;
+        self.0
+            .split('\n')
+            .enumerate()
+            .try_for_each(|(line_index, line)| {
+                if line_index != 0 {
+                    f.write_str("<br />")?;
+                }
+                f.write_str(line)
+            })?;
+        f.write_str("</td></tr>")
+    }
+}
+
+/// Theme classes of an element, followed by its classes for each of its tags.
+struct ElClasses<'f> {
+    info_graph_classes: &'f InfoGraphClasses<'f>,
+    el_id: &'f str,
+    tag_ids: Option<&'f IndexSet<&'f TagId>>,
+}
+
+impl fmt::Display for ElClasses<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.info_graph_classes
+            .el_classes_write(f, self.el_id, self.tag_ids)
+    }
+}
+
+/// Writes `name = value` if there is a value, and nothing otherwise.
+struct AttrOpt<'f, T>(&'f str, Option<T>);
+
+impl<T> fmt::Display for AttrOpt<'_, T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.1 {
+            Some(value) => write!(f, "{} = {value}", self.0),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes the value within double quotes.
+struct Quoted<T>(T);
+
+impl<T> fmt::Display for Quoted<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"", self.0)
+    }
+}
+
+/// Writes the value if there is one, and nothing otherwise.
+struct DisplayOpt<T>(Option<T>);
+
+impl<T> fmt::Display for DisplayOpt<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(value) => value.fmt(f),
+            None => Ok(()),
+        }
+    }
 }
 
 struct NodeClusterArgs<'args> {
     info_graph: &'args InfoGraph,
-    el_css_classes: &'args ElCssClasses,
-    tag_el_css_classes_map: &'args IndexMap<&'args TagId, ElCssClasses>,
+    info_graph_classes: &'args InfoGraphClasses<'args>,
+    node_id_to_parent: &'args HashMap<&'args NodeId, &'args NodeId>,
+    el_tag_ids: &'args HashMap<&'args str, IndexSet<&'args TagId>>,
     theme: &'args GraphvizDotTheme,
     node_id: &'args NodeId,
     node_hierarchy: &'args NodeHierarchy,
@@ -694,14 +941,20 @@ struct NodeClusterArgs<'args> {
 }
 
 struct EdgeArgs<'args> {
-    el_css_classes: &'args ElCssClasses,
-    tag_el_css_classes_map: &'args IndexMap<&'args TagId, ElCssClasses>,
+    info_graph_classes: &'args InfoGraphClasses<'args>,
     edge_id: &'args EdgeId,
     edge_desc: Option<&'args str>,
+    edge_tooltip: Option<&'args str>,
     edge_constraint: Option<bool>,
     edge_dir: Option<EdgeDir>,
     edge_minlen: Option<u32>,
-    edge_tags: Option<&'args IndexSet<TagId>>,
+    edge_arrowhead: Option<&'args ArrowType>,
+    edge_arrowtail: Option<&'args ArrowType>,
+    edge_arrowsize: Option<f64>,
+    edge_penwidth: Option<f64>,
+    edge_tags: Option<&'args IndexSet<&'args TagId>>,
+    /// Other edges drawn as this edge, and their tags, when edges are bundled.
+    edges_bundled: &'args [(&'args EdgeId, Option<&'args IndexSet<&'args TagId>>)],
     src_node_id_with_port: &'args str,
     src_node_id_plain: &'args str,
     src_node_hierarchy: Option<&'args NodeHierarchy>,
@@ -712,84 +965,305 @@ struct EdgeArgs<'args> {
     target_compass_point: Option<&'args str>,
 }
 
-fn edges(
+/// The nodes that an edge is drawn between, after edges to clusters are
+/// drawn to a node within the cluster.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct EdgeEnds<'args> {
+    edge_src_node_id: &'args str,
+    ltail: Option<ClusterLink<'args>>,
+    edge_target_node_id: &'args str,
+    lhead: Option<ClusterLink<'args>>,
+}
+
+/// Edges with the same key are drawn as one edge when edge bundling is
+/// enabled.
+///
+/// Besides the ends, the key includes the attributes and theme classes that
+/// change how the edge is drawn, so that edges are only bundled when they
+/// would be drawn the same way.
+///
+/// Tag classes are not included, as the drawn edge has the tag classes of
+/// every edge in the bundle.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct EdgeBundleKey<'args> {
+    edge_ends: EdgeEnds<'args>,
+    edge_classes: Option<&'args str>,
+    edge_constraint: Option<bool>,
+    edge_dir: Option<EdgeDir>,
+    edge_minlen: Option<u32>,
+    edge_arrowhead: Option<&'args ArrowType>,
+    edge_arrowtail: Option<&'args ArrowType>,
+    edge_arrowsize: Option<u64>,
+    edge_penwidth: Option<u64>,
+}
+
+impl<'args> EdgeBundleKey<'args> {
+    fn new(edge_args: &EdgeArgs<'args>) -> Self {
+        Self {
+            edge_ends: edge_ends(edge_args),
+            edge_classes: edge_args
+                .info_graph_classes
+                .el_css_classes
+                .get(edge_args.edge_id.as_str())
+                .map(AsRef::<str>::as_ref),
+            edge_constraint: edge_args.edge_constraint,
+            edge_dir: edge_args.edge_dir,
+            edge_minlen: edge_args.edge_minlen,
+            edge_arrowhead: edge_args.edge_arrowhead,
+            edge_arrowtail: edge_args.edge_arrowtail,
+            edge_arrowsize: edge_args.edge_arrowsize.map(f64::to_bits),
+            edge_penwidth: edge_args.edge_penwidth.map(f64::to_bits),
+        }
+    }
+}
+
+/// Writes the edges, and returns the edges that are drawn as one edge when
+/// edge bundling is enabled.
+fn edges_write<W>(
+    buffer: &mut SrcMapWriter<'_, W>,
     info_graph: &InfoGraph,
-    node_id_to_hierarchy: &std::collections::HashMap<&NodeId, &NodeHierarchy>,
-    el_css_classes: &ElCssClasses,
-    tag_el_css_classes_map: &IndexMap<&TagId, ElCssClasses>,
-) -> String {
-    let graphviz_attrs = info_graph.graphviz_attrs();
-    let edge_tags_set = info_graph.edge_tags_set();
-    let edge_tags_set = &edge_tags_set;
+    node_id_to_hierarchy: &HashMap<&NodeId, &NodeHierarchy>,
+    el_tag_ids: &HashMap<&str, IndexSet<&TagId>>,
+    info_graph_classes: &InfoGraphClasses<'_>,
+) -> Result<EdgeBundles, fmt::Error>
+where
+    W: Write,
+{
+    let edge_args_iter = edge_args_iter(
+        info_graph,
+        node_id_to_hierarchy,
+        el_tag_ids,
+        info_graph_classes,
+    );
+
+    if info_graph.graphviz_attrs().edge_bundling() {
+        let edge_args_bundles = edge_args_bundle(edge_args_iter);
+        let edge_bundles = edge_bundles(&edge_args_bundles);
+        edge_args_bundles.into_values().enumerate().try_for_each(
+            |(edge_index, edge_args_bundled)| {
+                if edge_index != 0 {
+                    buffer.write_char('\n')?;
+                }
+                edge_bundle_write(buffer, info_graph, edge_args_bundled)
+            },
+        )?;
+
+        Ok(edge_bundles)
+    } else {
+        edge_args_iter
+            .enumerate()
+            .try_for_each(|(edge_index, edge_args)| {
+                if edge_index != 0 {
+                    buffer.write_char('\n')?;
+                }
+                let edge_id = edge_args.edge_id;
+
+                let line_start = buffer.line();
+                edge_write(buffer, edge_args)?;
+                buffer.entry_push(line_start, || {
+                    (AnyId::from(edge_id.clone()), format!("edges.{edge_id}"))
+                });
+
+                Ok(())
+            })?;
+
+        Ok(EdgeBundles::new())
+    }
+}
 
+/// Returns the edges that are drawn as one edge, keyed by the ends that they
+/// are drawn between and the attributes they are drawn with.
+fn edge_args_bundle<'args, I>(
+    edge_args_iter: I,
+) -> IndexMap<EdgeBundleKey<'args>, Vec<EdgeArgs<'args>>>
+where
+    I: Iterator<Item = EdgeArgs<'args>>,
+{
+    edge_args_iter.fold(
+        IndexMap::<EdgeBundleKey<'args>, Vec<EdgeArgs<'args>>>::new(),
+        |mut edge_args_bundles, edge_args| {
+            edge_args_bundles
+                .entry(EdgeBundleKey::new(&edge_args))
+                .or_default()
+                .push(edge_args);
+            edge_args_bundles
+        },
+    )
+}
+
+/// Returns the IDs of edges that are drawn as one edge, keyed by the ID of the
+/// edge that is drawn.
+fn edge_bundles(edge_args_bundles: &IndexMap<EdgeBundleKey<'_>, Vec<EdgeArgs<'_>>>) -> EdgeBundles {
+    edge_args_bundles
+        .values()
+        .filter(|edge_args_bundled| edge_args_bundled.len() > 1)
+        .map(|edge_args_bundled| {
+            let edge_ids = edge_args_bundled
+                .iter()
+                .map(|edge_args| edge_args.edge_id.clone())
+                .collect::<Vec<EdgeId>>();
+            (edge_ids[0].clone(), edge_ids)
+        })
+        .collect::<EdgeBundles>()
+}
+
+/// Returns the `EdgeArgs` for each edge, in the order they are declared.
+fn edge_args_iter<'args>(
+    info_graph: &'args InfoGraph,
+    node_id_to_hierarchy: &'args HashMap<&'args NodeId, &'args NodeHierarchy>,
+    el_tag_ids: &'args HashMap<&'args str, IndexSet<&'args TagId>>,
+    info_graph_classes: &'args InfoGraphClasses<'args>,
+) -> impl Iterator<Item = EdgeArgs<'args>> {
     info_graph
         .edges()
         .iter()
         .map(|(edge_id, [src_node_id, target_node_id])| {
-            let edge_desc = info_graph.edge_descs().get(edge_id).map(String::as_str);
-            let edge_constraint = graphviz_attrs.edge_constraints().get(edge_id).copied();
-            let edge_dir = graphviz_attrs.edge_dirs().get(edge_id).copied();
-            let edge_minlen = graphviz_attrs.edge_minlens().get(edge_id).copied();
-            let edge_tags = edge_tags_set.get(edge_id);
-
-            // Graphviz has a bug where setting the `headport` / `tailport` attributes
-            // causes the edge to not be rendered with spline curves if the `lhead` /
-            // `ltail` is a node. So we workaround this by still passing the
-            // compass points through as the source / target node IDs.
-            let (src_node_id_plain, src_compass_point) = match src_node_id.split_once(':') {
-                Some((src_node_id_plain, src_compass_point)) => {
-                    (src_node_id_plain, Some(src_compass_point))
-                }
-                None => (src_node_id.as_str(), None),
-            };
-            let (target_node_id_plain, target_compass_point) = match target_node_id.split_once(':')
-            {
-                Some((target_node_id_plain, target_compass_point)) => {
-                    (target_node_id_plain, Some(target_compass_point))
-                }
-                None => (target_node_id.as_str(), None),
-            };
-
-            // We need to find the node_hierarchy for both the the `src_node_id` and
-            // `target_node_id`.
-            let src_node_hierarchy = node_id_to_hierarchy.get(src_node_id_plain).copied();
-            let target_node_hierarchy = node_id_to_hierarchy.get(target_node_id_plain).copied();
-
-            let edge_args = EdgeArgs {
-                el_css_classes,
-                tag_el_css_classes_map,
-                edge_tags,
+            edge_args(
+                info_graph,
+                node_id_to_hierarchy,
+                el_tag_ids,
+                info_graph_classes,
                 edge_id,
-                edge_desc,
-                edge_constraint,
-                edge_dir,
-                edge_minlen,
-                src_node_id_with_port: src_node_id.as_str(),
-                src_node_id_plain,
-                src_compass_point,
-                src_node_hierarchy,
-                target_node_id_with_port: target_node_id.as_str(),
-                target_node_id_plain,
-                target_node_hierarchy,
-                target_compass_point,
-            };
-
-            edge(edge_args)
+                src_node_id,
+                target_node_id,
+            )
         })
-        .collect::<Vec<String>>()
-        .join("\n")
 }
 
-fn edge(edge_args: EdgeArgs<'_>) -> String {
-    let EdgeArgs {
-        el_css_classes,
-        tag_el_css_classes_map,
+fn edge_args<'args>(
+    info_graph: &'args InfoGraph,
+    node_id_to_hierarchy: &'args HashMap<&'args NodeId, &'args NodeHierarchy>,
+    el_tag_ids: &'args HashMap<&'args str, IndexSet<&'args TagId>>,
+    info_graph_classes: &'args InfoGraphClasses<'args>,
+    edge_id: &'args EdgeId,
+    src_node_id: &'args NodeId,
+    target_node_id: &'args NodeId,
+) -> EdgeArgs<'args> {
+    let graphviz_attrs = info_graph.graphviz_attrs();
+    let edge_desc = info_graph.edge_descs().get(edge_id).map(String::as_str);
+    let edge_constraint = graphviz_attrs.edge_constraints().get(edge_id).copied();
+    let edge_dir = graphviz_attrs.edge_dirs().get(edge_id).copied();
+    let edge_minlen = graphviz_attrs.edge_minlens().get(edge_id).copied();
+    let edge_arrowhead = graphviz_attrs.edge_arrowheads().get(edge_id);
+    let edge_arrowtail = graphviz_attrs.edge_arrowtails().get(edge_id);
+    let edge_arrowsize = graphviz_attrs.edge_arrowsizes().get(edge_id).copied();
+    let edge_penwidth = graphviz_attrs.edge_penwidths().get(edge_id).copied();
+    let edge_tags = el_tag_ids.get(edge_id.as_str());
+
+    // Graphviz has a bug where setting the `headport` / `tailport` attributes
+    // causes the edge to not be rendered with spline curves if the `lhead` /
+    // `ltail` is a node. So we workaround this by still passing the
+    // compass points through as the source / target node IDs.
+    let (src_node_id_plain, src_compass_point) = match src_node_id.split_once(':') {
+        Some((src_node_id_plain, src_compass_point)) => {
+            (src_node_id_plain, Some(src_compass_point))
+        }
+        None => (src_node_id.as_str(), None),
+    };
+    let (target_node_id_plain, target_compass_point) = match target_node_id.split_once(':') {
+        Some((target_node_id_plain, target_compass_point)) => {
+            (target_node_id_plain, Some(target_compass_point))
+        }
+        None => (target_node_id.as_str(), None),
+    };
+
+    // We need to find the node_hierarchy for both the the `src_node_id` and
+    // `target_node_id`.
+    let src_node_hierarchy = node_id_to_hierarchy.get(src_node_id_plain).copied();
+    let target_node_hierarchy = node_id_to_hierarchy.get(target_node_id_plain).copied();
+
+    EdgeArgs {
+        info_graph_classes,
+        edge_tags,
+        edges_bundled: &[],
         edge_id,
         edge_desc,
+        edge_tooltip: None,
         edge_constraint,
         edge_dir,
         edge_minlen,
-        edge_tags,
+        edge_arrowhead,
+        edge_arrowtail,
+        edge_arrowsize,
+        edge_penwidth,
+        src_node_id_with_port: src_node_id.as_str(),
+        src_node_id_plain,
+        src_compass_point,
+        src_node_hierarchy,
+        target_node_id_with_port: target_node_id.as_str(),
+        target_node_id_plain,
+        target_node_hierarchy,
+        target_compass_point,
+    }
+}
+
+/// Writes edges that are drawn between the same ends as one edge.
+///
+/// The edge is drawn with the attributes of the first edge, labelled with the
+/// number of edges, and its tooltip lists each edge and its description.
+fn edge_bundle_write<W>(
+    buffer: &mut SrcMapWriter<'_, W>,
+    info_graph: &InfoGraph,
+    edge_args_bundled: Vec<EdgeArgs<'_>>,
+) -> fmt::Result
+where
+    W: Write,
+{
+    let edge_ids = edge_args_bundled
+        .iter()
+        .map(|edge_args| edge_args.edge_id)
+        .collect::<Vec<&EdgeId>>();
+    let line_start = buffer.line();
+
+    let mut edge_args_iter = edge_args_bundled.into_iter();
+    let Some(edge_args) = edge_args_iter.next() else {
+        return Ok(());
+    };
+    let edges_bundled = edge_args_iter
+        .map(|edge_args| (edge_args.edge_id, edge_args.edge_tags))
+        .collect::<Vec<_>>();
+
+    if edges_bundled.is_empty() {
+        edge_write(buffer, edge_args)?;
+    } else {
+        let edge_label = format!("{} edges", edge_ids.len());
+        let edge_tooltip = edge_ids
+            .iter()
+            .map(|edge_id| match info_graph.edge_descs().get(*edge_id) {
+                Some(edge_desc) => format!("{edge_id}: {edge_desc}"),
+                None => edge_id.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+
+        edge_write(
+            buffer,
+            EdgeArgs {
+                edge_desc: Some(&edge_label),
+                edge_tooltip: Some(&edge_tooltip),
+                edges_bundled: &edges_bundled,
+                ..edge_args
+            },
+        )?;
+    }
+
+    edge_ids.into_iter().for_each(|edge_id| {
+        buffer.entry_push(line_start, || {
+            (AnyId::from(edge_id.clone()), format!("edges.{edge_id}"))
+        });
+    });
+
+    Ok(())
+}
+
+/// Returns the nodes that an edge is drawn between.
+///
+/// Edges to a cluster are drawn to a node within the cluster, and clipped at
+/// the cluster's border using `ltail` / `lhead`.
+fn edge_ends<'args>(edge_args: &EdgeArgs<'args>) -> EdgeEnds<'args> {
+    let EdgeArgs {
         src_node_id_with_port,
         src_node_id_plain,
         src_node_hierarchy,
@@ -798,7 +1272,8 @@ fn edge(edge_args: EdgeArgs<'_>) -> String {
         target_node_id_plain,
         target_node_hierarchy,
         target_compass_point,
-    } = edge_args;
+        ..
+    } = *edge_args;
     let (edge_src_node_id, ltail) = if let Some((mut child_node_id, mut child_node_hierarchy)) =
         src_node_hierarchy
             .filter(|node_hierarchy| !node_hierarchy.is_empty())
@@ -811,16 +1286,17 @@ fn edge(edge_args: EdgeArgs<'_>) -> String {
         }
         let edge_src_node_id = child_node_id;
 
-        let mut ltail = format!(", ltail = cluster_{src_node_id_plain}");
-        if let Some(src_compass_point) = src_compass_point {
-            ltail.push_str(" tailport = ");
-            ltail.push_str(src_compass_point);
-        }
+        let ltail = ClusterLink {
+            attr: "ltail",
+            port_attr: "tailport",
+            cluster_node_id: src_node_id_plain,
+            compass_point: src_compass_point,
+        };
 
-        (edge_src_node_id.as_str(), Cow::Owned(ltail))
+        (edge_src_node_id.as_str(), Some(ltail))
     } else {
         // This is a node, not a cluster.
-        (src_node_id_with_port, Cow::Borrowed(""))
+        (src_node_id_with_port, None)
     };
 
     let (edge_target_node_id, lhead) = if let Some((mut child_node_id, mut child_node_hierarchy)) =
@@ -835,70 +1311,166 @@ fn edge(edge_args: EdgeArgs<'_>) -> String {
         }
         let edge_target_node_id = child_node_id;
 
-        let mut lhead = format!(", lhead = cluster_{target_node_id_plain}");
-        if let Some(target_compass_point) = target_compass_point {
-            lhead.push_str(" headport = ");
-            lhead.push_str(target_compass_point);
-        }
+        let lhead = ClusterLink {
+            attr: "lhead",
+            port_attr: "headport",
+            cluster_node_id: target_node_id_plain,
+            compass_point: target_compass_point,
+        };
 
-        (edge_target_node_id.as_str(), Cow::Owned(lhead))
+        (edge_target_node_id.as_str(), Some(lhead))
     } else {
         // This is a node, not a cluster.
-        (target_node_id_with_port, Cow::Borrowed(""))
+        (target_node_id_with_port, None)
     };
 
-    let edge_label = edge_desc
-        .map(|edge_desc| Cow::Owned(format!("label = <{edge_desc}>")))
-        .unwrap_or(Cow::Borrowed(""));
-    let edge_tailwind_classes = el_css_classes
-        .get(&AnyId::from(edge_id.clone()))
-        .map(AsRef::<str>::as_ref)
-        .unwrap_or_default();
-
-    let edge_tag_classes = edge_tags
-        .map(|tag_ids| {
-            tag_ids
-                .iter()
-                .filter_map(|tag_id| tag_el_css_classes_map.get(tag_id))
-                .map(|el_css_classes| {
-                    el_css_classes
-                        .get(&AnyId::from(edge_id.clone()))
-                        .map(AsRef::<str>::as_ref)
-                        .unwrap_or_default()
-                })
-                .collect::<String>()
-        })
-        .unwrap_or_default();
-    let edge_constraint = edge_constraint
-        .map(|edge_constraint| Cow::Owned(format!("constraint = {edge_constraint}")))
-        .unwrap_or(Cow::Borrowed(""));
-    let edge_dir = edge_dir
-        .map(|edge_dir| Cow::Owned(format!("dir = {edge_dir}")))
-        .unwrap_or(Cow::Borrowed(""));
-    let edge_minlen = edge_minlen
-        .map(|edge_minlen| Cow::Owned(format!("minlen = {edge_minlen}")))
-        .unwrap_or(Cow::Borrowed(""));
-
-    // Note: There's no space between `{edge_tailwind_classes}{edge_tag_classes}`
-    // because for some reason spaces before `{edge_tag_classes}` are translated
-    // into the `0xa0` byte.
-    //
-    // Same thing happens for `{node_tag_classes}`
-    formatdoc!(
+    EdgeEnds {
+        edge_src_node_id,
+        ltail,
+        edge_target_node_id,
+        lhead,
+    }
+}
+
+fn edge_write<W>(buffer: &mut W, edge_args: EdgeArgs<'_>) -> fmt::Result
+where
+    W: Write,
+{
+    let EdgeEnds {
+        edge_src_node_id,
+        ltail,
+        edge_target_node_id,
+        lhead,
+    } = edge_ends(&edge_args);
+    let EdgeArgs {
+        info_graph_classes,
+        edge_id,
+        edge_desc,
+        edge_tooltip,
+        edge_constraint,
+        edge_dir,
+        edge_minlen,
+        edge_arrowhead,
+        edge_arrowtail,
+        edge_arrowsize,
+        edge_penwidth,
+        edge_tags,
+        edges_bundled,
+        ..
+    } = edge_args;
+    let ltail = DisplayOpt(ltail);
+    let lhead = DisplayOpt(lhead);
+
+    let edge_label = AttrOpt("label", edge_desc.map(HtmlLabel));
+    let edge_tooltip = DisplayOpt(edge_tooltip.map(EdgeTooltip));
+    let edge_classes = EdgeClasses {
+        el_classes: ElClasses {
+            info_graph_classes,
+            el_id: edge_id.as_str(),
+            tag_ids: edge_tags,
+        },
+        edges_bundled,
+    };
+    let edge_constraint = AttrOpt("constraint", edge_constraint);
+    let edge_dir = AttrOpt("dir", edge_dir);
+    let edge_minlen = AttrOpt("minlen", edge_minlen);
+    let edge_arrowhead = AttrOpt("arrowhead", edge_arrowhead);
+    let edge_arrowtail = AttrOpt("arrowtail", edge_arrowtail);
+    let edge_arrowsize = AttrOpt("arrowsize", edge_arrowsize);
+    let edge_penwidth = AttrOpt("penwidth", edge_penwidth);
+
+    writedoc!(
+        buffer,
         r#"
         {edge_src_node_id} -> {edge_target_node_id} [
             id     = "{edge_id}"
-            {edge_label}
+            {edge_label}{edge_tooltip}
             {edge_constraint}
             {edge_dir}
             {edge_minlen}
-            class = "{edge_tailwind_classes}{edge_tag_classes}"
+            {edge_arrowhead}
+            {edge_arrowtail}
+            {edge_arrowsize}
+            {edge_penwidth}
+            class = "{edge_classes}"
             {ltail}
             {lhead}
         ]"#
     )
 }
 
+/// Classes of an edge, followed by the tag classes of the other edges drawn as
+/// this edge, so that focusing any of their tags highlights the drawn edge.
+struct EdgeClasses<'f> {
+    el_classes: ElClasses<'f>,
+    edges_bundled: &'f [(&'f EdgeId, Option<&'f IndexSet<&'f TagId>>)],
+}
+
+impl fmt::Display for EdgeClasses<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.el_classes.fmt(f)?;
+        if self.edges_bundled.is_empty() {
+            return Ok(());
+        }
+
+        let mut tag_ids_written = self.el_classes.tag_ids.cloned().unwrap_or_default();
+        self.edges_bundled
+            .iter()
+            .try_for_each(|(edge_id, edge_tags)| {
+                let edge_tags_unwritten = edge_tags
+                    .iter()
+                    .copied()
+                    .flatten()
+                    .copied()
+                    .filter(|tag_id| tag_ids_written.insert(*tag_id))
+                    .collect::<IndexSet<&TagId>>();
+                self.el_classes.info_graph_classes.el_tag_classes_write(
+                    f,
+                    edge_id.as_str(),
+                    &edge_tags_unwritten,
+                )
+            })
+    }
+}
+
+/// Writes ` tooltip = "{tooltip}"`, where the tooltip is already escaped.
+struct EdgeTooltip<'f>(&'f str);
+
+impl fmt::Display for EdgeTooltip<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " tooltip = \"{}\"", self.0)
+    }
+}
+
+/// Writes the `ltail` or `lhead` of an edge to a cluster, e.g.
+/// `, lhead = cluster_a headport = n`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct ClusterLink<'f> {
+    attr: &'static str,
+    port_attr: &'static str,
+    cluster_node_id: &'f str,
+    compass_point: Option<&'f str>,
+}
+
+impl fmt::Display for ClusterLink<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, ", {} = cluster_{}", self.attr, self.cluster_node_id)?;
+        if let Some(compass_point) = self.compass_point {
+            write!(f, " {} = {compass_point}", self.port_attr)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the text within `<` and `>`, as an HTML-like label.
+struct HtmlLabel<'f>(&'f str);
+
+impl fmt::Display for HtmlLabel<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.0)
+    }
+}
+
 /// Returns the middle node and its hierarchy for a given cluster.
 fn middle_node(node_hierarchy: &NodeHierarchy) -> Option<(&NodeId, &NodeHierarchy)> {
     let half_index = node_hierarchy.len() / 2;
@@ -914,13 +1486,16 @@ fn middle_node(node_hierarchy: &NodeHierarchy) -> Option<(&NodeId, &NodeHierarch
     node_hierarchy.get_index(node_index)
 }
 
-fn tag_legend(
+fn tag_legend_write<W>(
+    buffer: &mut SrcMapWriter<'_, W>,
     graph_dir: GraphDir,
-    buffer: &mut String,
     theme: &GraphvizDotTheme,
     el_css_classes: &ElCssClasses,
     tags: &TagNames,
-) -> fmt::Result {
+) -> fmt::Result
+where
+    W: Write,
+{
     let node_point_size = theme.node_point_size();
     writedoc!(
         buffer,
@@ -942,14 +1517,12 @@ fn tag_legend(
     tags.iter().rev().try_for_each(|(tag_id, tag_name)| {
         let tag_label = tag_name; // TODO: escape
 
-        // This is for tailwindcss to identify this peer by name.
-        let tag_peer_class = format!("peer/{tag_id}");
-
         let tag_classes = el_css_classes
-            .get(&AnyId::from(tag_id.clone()))
+            .get(tag_id.as_str())
             .map(AsRef::<str>::as_ref)
             .unwrap_or(tag_classes);
 
+        let line_start = buffer.line();
         writedoc!(
             buffer,
             r#"
@@ -960,7 +1533,7 @@ fn tag_legend(
                 margin    = "{tag_margin_x:.3},{tag_margin_y:.3}"
                 fontname  = "liberationmono"
                 fontsize  = {tag_point_size}
-                class     = "{OUTLINE_NONE} {tag_classes} {tag_peer_class}"
+                class     = "{OUTLINE_NONE} {tag_classes} peer/{tag_id}"
                 penwidth  = 1
 
                 // invisible node for cluster to appear
@@ -975,6 +1548,9 @@ fn tag_legend(
             }}
             "#
         )?;
+        buffer.entry_push(line_start, || {
+            (AnyId::from(tag_id.clone()), format!("tags.{tag_id}"))
+        });
 
         Ok(())
     })?;
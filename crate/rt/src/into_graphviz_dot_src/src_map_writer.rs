@@ -0,0 +1,94 @@
+use std::fmt::{self, Write};
+
+use dot_ix_model::common::{
+    dot_src_and_styles::{DotSrcMap, DotSrcMapEntry},
+    AnyId,
+};
+
+/// Writer that records which lines of dot source are written for each
+/// element.
+///
+/// Lines are only counted when a source map is requested, so writing without
+/// one is as cheap as writing to the inner writer.
+pub(crate) struct SrcMapWriter<'w, W> {
+    /// Writer that the dot source is written to.
+    writer: &'w mut W,
+    /// Source map being built, if requested.
+    src_map: Option<DotSrcMap>,
+    /// Current line of the dot source, starting from 1.
+    line: u32,
+    /// Whether the last character written was a newline.
+    line_ended: bool,
+}
+
+impl<'w, W> SrcMapWriter<'w, W>
+where
+    W: Write,
+{
+    /// Returns a `SrcMapWriter` that doesn't record a source map.
+    pub(crate) fn new(writer: &'w mut W) -> Self {
+        Self {
+            writer,
+            src_map: None,
+            line: 1,
+            line_ended: false,
+        }
+    }
+
+    /// Returns a `SrcMapWriter` that records a source map.
+    pub(crate) fn with_src_map(writer: &'w mut W) -> Self {
+        Self {
+            src_map: Some(DotSrcMap::new()),
+            ..Self::new(writer)
+        }
+    }
+
+    /// Returns the line that the next character is written to, for
+    /// [`Self::entry_push`].
+    pub(crate) fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Records that the lines from `line_start` to the last written line were
+    /// generated for an element.
+    ///
+    /// `fn_any_id_and_doc_path` is only called when a source map is being
+    /// recorded.
+    pub(crate) fn entry_push<F>(&mut self, line_start: u32, fn_any_id_and_doc_path: F)
+    where
+        F: FnOnce() -> (AnyId, String),
+    {
+        let line_end = if self.line_ended {
+            self.line - 1
+        } else {
+            self.line
+        };
+        if let Some(src_map) = self.src_map.as_mut() {
+            let (any_id, doc_path) = fn_any_id_and_doc_path();
+            src_map.push(DotSrcMapEntry::new(
+                line_start,
+                line_end.max(line_start),
+                any_id,
+                doc_path,
+            ));
+        }
+    }
+
+    /// Returns the source map, if one was recorded.
+    pub(crate) fn into_src_map(self) -> Option<DotSrcMap> {
+        self.src_map
+    }
+}
+
+impl<W> Write for SrcMapWriter<'_, W>
+where
+    W: Write,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.src_map.is_some() && !s.is_empty() {
+            self.line += s.bytes().filter(|byte| *byte == b'\n').count() as u32;
+            self.line_ended = s.ends_with('\n');
+        }
+        self.writer.write_str(s)
+    }
+}
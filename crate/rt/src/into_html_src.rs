@@ -0,0 +1,11 @@
+use dot_ix_model::common::HtmlSrcAndStyles;
+
+pub use self::html_edge_connector::HtmlEdgeConnector;
+
+mod html_edge_connector;
+mod info_graph;
+
+/// Generates HTML that can be rendered without Graphviz.
+pub trait IntoHtmlSrc {
+    fn into(self, edge_connector: HtmlEdgeConnector) -> HtmlSrcAndStyles;
+}
@@ -0,0 +1,20 @@
+use std::{fmt, fmt::Display};
+
+/// How edges are drawn between nodes in the HTML renderer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HtmlEdgeConnector {
+    /// A straight line between the nodes.
+    #[default]
+    Straight,
+    /// Horizontal and vertical line segments with right angle bends.
+    Orthogonal,
+}
+
+impl Display for HtmlEdgeConnector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HtmlEdgeConnector::Straight => "straight".fmt(f),
+            HtmlEdgeConnector::Orthogonal => "orthogonal".fmt(f),
+        }
+    }
+}
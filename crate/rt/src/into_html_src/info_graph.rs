@@ -0,0 +1,283 @@
+use std::fmt::{self, Write};
+
+use dot_ix_model::{
+    common::{graphviz_attrs::EdgeDir, HtmlSrcAndStyles, NodeHierarchy, NodeId},
+    info_graph::{GraphDir, InfoGraph},
+    theme::ElCssClasses,
+};
+
+use crate::{
+    info_graph_classes::OUTLINE_NONE, into_html_src::HtmlEdgeConnector, xml_escape::xml_escape,
+    InfoGraphHtml, IntoHtmlSrc, TailwindCssGen,
+};
+
+/// Layout styles for the HTML structure.
+///
+/// These are emitted before the theme styles, so that theme classes such as
+/// padding override them.
+const LAYOUT_CSS: &str = "\
+.dot_ix_html { position: relative; display: inline-block; padding: 0.5rem; font-family: monospace; }
+.dot_ix_html_nodes { display: flex; gap: 2rem; align-items: flex-start; }
+.dot_ix_html_horizontal { flex-direction: row; }
+.dot_ix_html_vertical { flex-direction: column; }
+.dot_ix_html_node { display: flex; flex-direction: column; gap: 0.5rem; padding: 0.5rem; border-style: solid; border-width: 1px; }
+.dot_ix_html_label { white-space: nowrap; }
+.dot_ix_html_desc { font-size: 0.75em; }
+.dot_ix_html_edges { position: absolute; top: 0; left: 0; width: 100%; height: 100%; overflow: visible; pointer-events: none; }
+.dot_ix_html_edge { pointer-events: visiblePainted; }
+.dot_ix_html_edge > path { fill: none; }
+";
+
+/// Renders an `InfoGraph` as nested `div`s, with edges drawn in an SVG
+/// overlay.
+///
+/// Each node is a `div`, and its child nodes are laid out within it using
+/// flexbox in the graph's [`GraphDir`] direction.
+///
+/// Edges are `<g>` elements with an empty `<path>`, and a `<polygon>` for each
+/// arrowhead. Their coordinates depend on where the browser lays out the
+/// nodes, so they are computed on the client by `info_graph_html_edges.js`,
+/// using the `data-edge-*` attributes.
+///
+/// # Limitations
+///
+/// * Tag focus styles are not applied, as they rely on `peer` selectors, which
+///   only match sibling elements.
+/// * Graphviz attributes other than edge `dir` are not used.
+/// * Images are not rendered.
+impl IntoHtmlSrc for &InfoGraph {
+    fn into(self, edge_connector: HtmlEdgeConnector) -> HtmlSrcAndStyles {
+        let node_id_to_hierarchy = self.hierarchy_flat();
+        let node_id_to_hierarchy = &node_id_to_hierarchy;
+        let node_id_to_parent = self.node_parents();
+        let node_id_to_parent = &node_id_to_parent;
+        let el_tag_ids = self.el_tag_ids();
+        let el_tag_ids = &el_tag_ids;
+
+        let info_graph_html = InfoGraphHtml {
+            node_id_to_hierarchy,
+            node_id_to_parent,
+            edges: self.edges(),
+            graphviz_attrs: self.graphviz_attrs(),
+            el_tag_ids,
+            node_ids: node_id_to_hierarchy.keys().copied().collect::<Vec<_>>(),
+            edge_ids: self.edges().keys().collect::<Vec<_>>(),
+        };
+        let (el_css_classes, theme_warnings) = self.theme().el_css_classes(&info_graph_html);
+
+        let mut html_src =
+            String::with_capacity(256 * (node_id_to_hierarchy.len() + self.edges().len()) + 512);
+        html_write(&mut html_src, self, &el_css_classes, edge_connector)
+            .expect("Failed to write `html_src` string.");
+
+        let styles = {
+            let tailwind_css_gen = [OUTLINE_NONE]
+                .into_iter()
+                .chain(el_css_classes.values().map(AsRef::<str>::as_ref))
+                .collect::<TailwindCssGen>();
+
+            let mut styles = String::from(LAYOUT_CSS);
+            styles.push_str(&tailwind_css_gen.build());
+            styles.push_str(self.css());
+            styles
+        };
+
+        HtmlSrcAndStyles {
+            html_src,
+            styles,
+            theme_warnings,
+        }
+    }
+}
+
+fn html_write(
+    buffer: &mut String,
+    info_graph: &InfoGraph,
+    el_css_classes: &ElCssClasses,
+    edge_connector: HtmlEdgeConnector,
+) -> fmt::Result {
+    let graph_dir = info_graph.direction();
+    let graph_dir_class = graph_dir_class(graph_dir);
+
+    write!(
+        buffer,
+        "<div class=\"dot_ix_html\" \
+        data-graph-dir=\"{graph_dir}\" \
+        data-edge-connector=\"{edge_connector}\">"
+    )?;
+
+    nodes_write(
+        buffer,
+        info_graph,
+        el_css_classes,
+        graph_dir_class,
+        info_graph.hierarchy(),
+    )?;
+    edges_write(buffer, info_graph, el_css_classes)?;
+
+    buffer.push_str("</div>");
+
+    Ok(())
+}
+
+fn nodes_write(
+    buffer: &mut String,
+    info_graph: &InfoGraph,
+    el_css_classes: &ElCssClasses,
+    graph_dir_class: &str,
+    node_hierarchy: &NodeHierarchy,
+) -> fmt::Result {
+    write!(
+        buffer,
+        "<div class=\"dot_ix_html_nodes {graph_dir_class}\">"
+    )?;
+
+    node_hierarchy
+        .iter()
+        .try_for_each(|(node_id, child_hierarchy)| {
+            node_write(
+                buffer,
+                info_graph,
+                el_css_classes,
+                graph_dir_class,
+                node_id,
+                child_hierarchy,
+            )
+        })?;
+
+    buffer.push_str("</div>");
+
+    Ok(())
+}
+
+fn node_write(
+    buffer: &mut String,
+    info_graph: &InfoGraph,
+    el_css_classes: &ElCssClasses,
+    graph_dir_class: &str,
+    node_id: &NodeId,
+    node_hierarchy: &NodeHierarchy,
+) -> fmt::Result {
+    let node_classes = el_css_classes
+        .get(node_id.as_str())
+        .map(AsRef::<str>::as_ref)
+        .unwrap_or_default();
+    let node_classes = xml_escape(node_classes);
+    let node_label = info_graph
+        .node_names()
+        .get(node_id)
+        .map(String::as_str)
+        .unwrap_or(node_id);
+    let node_label = xml_escape(node_label);
+    let node_id_escaped = xml_escape(node_id);
+
+    write!(
+        buffer,
+        "<div id=\"{node_id_escaped}\" tabindex=\"0\" class=\"dot_ix_html_node {OUTLINE_NONE} {node_classes}\">\
+        <div class=\"dot_ix_html_label\">"
+    )?;
+    if let Some(node_emoji) = info_graph.node_emojis().get(node_id) {
+        let node_emoji = xml_escape(node_emoji);
+        write!(
+            buffer,
+            "<span class=\"dot_ix_html_emoji\">{node_emoji}</span> "
+        )?;
+    }
+    write!(buffer, "{node_label}</div>")?;
+
+    if let Some(node_desc) = info_graph.node_descs().get(node_id) {
+        buffer.push_str("<div class=\"dot_ix_html_desc\">");
+        node_desc
+            .split('\n')
+            .enumerate()
+            .try_for_each(|(index, node_desc_line)| {
+                if index != 0 {
+                    buffer.push_str("<br />");
+                }
+                buffer.write_str(&xml_escape(node_desc_line))
+            })?;
+        buffer.push_str("</div>");
+    }
+
+    if !node_hierarchy.is_empty() {
+        nodes_write(
+            buffer,
+            info_graph,
+            el_css_classes,
+            graph_dir_class,
+            node_hierarchy,
+        )?;
+    }
+
+    buffer.push_str("</div>");
+
+    Ok(())
+}
+
+fn edges_write(
+    buffer: &mut String,
+    info_graph: &InfoGraph,
+    el_css_classes: &ElCssClasses,
+) -> fmt::Result {
+    let graphviz_attrs = info_graph.graphviz_attrs();
+    let edge_dir_default = graphviz_attrs.edge_dir_default();
+
+    buffer.push_str("<svg class=\"dot_ix_html_edges\" xmlns=\"http://www.w3.org/2000/svg\">");
+
+    info_graph
+        .edges()
+        .iter()
+        .try_for_each(|(edge_id, [src_node_id, target_node_id])| {
+            let edge_classes = el_css_classes
+                .get(edge_id.as_str())
+                .map(AsRef::<str>::as_ref)
+                .unwrap_or_default();
+            let edge_classes = xml_escape(edge_classes);
+            let edge_dir = graphviz_attrs
+                .edge_dirs()
+                .get(edge_id)
+                .copied()
+                .unwrap_or(edge_dir_default);
+
+            // Compass points are not supported, so we only use the node ID.
+            let src_node_id = xml_escape(src_node_id.node_id_plain());
+            let target_node_id = xml_escape(target_node_id.node_id_plain());
+            let edge_id_escaped = xml_escape(edge_id);
+
+            write!(
+                buffer,
+                "<g id=\"{edge_id_escaped}\" tabindex=\"0\" class=\"dot_ix_html_edge {OUTLINE_NONE} {edge_classes}\" \
+                data-edge-src=\"{src_node_id}\" \
+                data-edge-target=\"{target_node_id}\" \
+                data-edge-dir=\"{edge_dir}\">\
+                <path d=\"\" />"
+            )?;
+
+            let arrowhead_count = match edge_dir {
+                EdgeDir::Forward | EdgeDir::Back => 1,
+                EdgeDir::Both => 2,
+                EdgeDir::None => 0,
+            };
+            (0..arrowhead_count).try_for_each(|_| buffer.write_str("<polygon points=\"\" />"))?;
+
+            if let Some(edge_desc) = info_graph.edge_descs().get(edge_id) {
+                let edge_desc = xml_escape(edge_desc);
+                write!(buffer, "<text text-anchor=\"middle\">{edge_desc}</text>")?;
+            }
+
+            buffer.push_str("</g>");
+
+            Ok(())
+        })?;
+
+    buffer.push_str("</svg>");
+
+    Ok(())
+}
+
+fn graph_dir_class(graph_dir: GraphDir) -> &'static str {
+    match graph_dir {
+        GraphDir::Horizontal => "dot_ix_html_horizontal",
+        GraphDir::Vertical => "dot_ix_html_vertical",
+    }
+}
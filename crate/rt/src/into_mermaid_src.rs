@@ -0,0 +1,7 @@
+mod info_graph;
+
+/// Generates Mermaid `flowchart` source, which GitHub and GitLab render in
+/// markdown.
+pub trait IntoMermaidSrc {
+    fn into(self) -> String;
+}
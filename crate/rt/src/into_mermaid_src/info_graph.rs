@@ -0,0 +1,322 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Write},
+};
+
+use dot_ix_model::{
+    common::{graphviz_attrs::EdgeDir, EdgeId, NodeHierarchy, NodeId},
+    info_graph::{GraphDir, GraphStyle, InfoGraph},
+};
+use indexmap::IndexMap;
+
+use crate::{info_graph_classes::InfoGraphClasses, InfoGraphDot, IntoMermaidSrc};
+
+/// Words that Mermaid parses as keywords instead of node IDs.
+const MERMAID_KEYWORDS: &[&str] = &[
+    "call",
+    "class",
+    "classDef",
+    "click",
+    "default",
+    "direction",
+    "end",
+    "flowchart",
+    "graph",
+    "href",
+    "linkStyle",
+    "style",
+    "subgraph",
+];
+
+/// Converts an `InfoGraph` into a Mermaid `flowchart`.
+///
+/// * The node hierarchy becomes nested `subgraph`s.
+/// * Node names, emojis, and descriptions become node labels, and edge
+///   descriptions become edge labels.
+/// * Edge directions become arrow types. `EdgeDir::Back` edges are written
+///   from the target to the source, as Mermaid has no arrow that only points
+///   backwards.
+/// * Colours, line widths, and dash styles from the diagram theme become
+///   `classDef`s for nodes, and `linkStyle`s for edges. Styles for `hover`,
+///   `focus`, and tag focus are not included.
+///
+/// Tags and images are not included, and node IDs that are Mermaid keywords,
+/// such as `end`, have `_` appended.
+impl IntoMermaidSrc for &InfoGraph {
+    fn into(self) -> String {
+        let node_id_to_hierarchy = self.hierarchy_flat();
+        let node_id_to_hierarchy = &node_id_to_hierarchy;
+        let node_id_to_parent = self.node_parents();
+        let node_id_to_parent = &node_id_to_parent;
+        let el_tag_ids = self.el_tag_ids();
+        let el_tag_ids = &el_tag_ids;
+
+        let info_graph_dot = InfoGraphDot {
+            graph_style: self.graph_style(),
+            node_id_to_hierarchy,
+            node_id_to_parent,
+            edges: self.edges(),
+            graphviz_attrs: self.graphviz_attrs(),
+            el_tag_ids,
+            node_ids: node_id_to_hierarchy.keys().copied().collect::<Vec<_>>(),
+            edge_ids: self.edges().keys().collect::<Vec<_>>(),
+        };
+        let info_graph_classes = InfoGraphClasses::new(self, &info_graph_dot);
+
+        let mut mermaid_src =
+            String::with_capacity(128 * (node_id_to_hierarchy.len() + self.edges().len()) + 64);
+        mermaid_write(&mut mermaid_src, self, &info_graph_classes)
+            .expect("Failed to write `mermaid_src` string.");
+
+        mermaid_src
+    }
+}
+
+fn mermaid_write(
+    mermaid_src: &mut String,
+    info_graph: &InfoGraph,
+    info_graph_classes: &InfoGraphClasses<'_>,
+) -> fmt::Result {
+    let direction = match info_graph.direction() {
+        GraphDir::Horizontal => "LR",
+        GraphDir::Vertical => "TB",
+    };
+    writeln!(mermaid_src, "flowchart {direction}")?;
+
+    // Node IDs grouped by their Mermaid style, so each style is one `classDef`.
+    let mut style_to_node_ids = IndexMap::<String, Vec<Cow<'_, str>>>::new();
+
+    info_graph
+        .hierarchy()
+        .iter()
+        .try_for_each(|(node_id, node_hierarchy)| {
+            node_write(
+                mermaid_src,
+                info_graph,
+                info_graph_classes,
+                &mut style_to_node_ids,
+                node_id,
+                node_hierarchy,
+                1,
+            )
+        })?;
+
+    let mut link_styles = Vec::<(usize, String)>::new();
+    if !info_graph.edges().is_empty() {
+        writeln!(mermaid_src)?;
+    }
+    info_graph.edges().iter().enumerate().try_for_each(
+        |(edge_index, (edge_id, [src_node_id, target_node_id]))| {
+            edge_write(
+                mermaid_src,
+                info_graph,
+                edge_id,
+                src_node_id,
+                target_node_id,
+            )?;
+
+            let edge_style = mermaid_style(info_graph_classes, edge_id.as_str(), &["[&>path]"]);
+            if !edge_style.is_empty() {
+                link_styles.push((edge_index, edge_style));
+            }
+            Ok(())
+        },
+    )?;
+
+    if !style_to_node_ids.is_empty() || !link_styles.is_empty() {
+        writeln!(mermaid_src)?;
+    }
+    style_to_node_ids
+        .iter()
+        .enumerate()
+        .try_for_each(|(style_index, (style, node_ids))| {
+            let node_ids = node_ids.join(",");
+            writeln!(mermaid_src, "    classDef theme_{style_index} {style}")?;
+            writeln!(mermaid_src, "    class {node_ids} theme_{style_index}")
+        })?;
+    link_styles
+        .iter()
+        .try_for_each(|(edge_index, edge_style)| {
+            writeln!(mermaid_src, "    linkStyle {edge_index} {edge_style}")
+        })?;
+
+    Ok(())
+}
+
+fn node_write<'graph>(
+    mermaid_src: &mut String,
+    info_graph: &InfoGraph,
+    info_graph_classes: &InfoGraphClasses<'_>,
+    style_to_node_ids: &mut IndexMap<String, Vec<Cow<'graph, str>>>,
+    node_id: &'graph NodeId,
+    node_hierarchy: &'graph NodeHierarchy,
+    depth: usize,
+) -> fmt::Result {
+    let indent = "    ".repeat(depth);
+    let mermaid_node_id = mermaid_id(node_id);
+    let node_label = node_label(info_graph, node_id, node_hierarchy.is_empty());
+
+    let node_style = mermaid_style(
+        info_graph_classes,
+        node_id.as_str(),
+        &["[&>path]", "[&>ellipse]"],
+    );
+    if !node_style.is_empty() {
+        style_to_node_ids
+            .entry(node_style)
+            .or_default()
+            .push(mermaid_node_id.clone());
+    }
+
+    if node_hierarchy.is_empty() {
+        match info_graph.graph_style() {
+            GraphStyle::Box => {
+                writeln!(mermaid_src, "{indent}{mermaid_node_id}(\"{node_label}\")")
+            }
+            GraphStyle::Circle => {
+                writeln!(mermaid_src, "{indent}{mermaid_node_id}((\"{node_label}\"))")
+            }
+        }
+    } else {
+        writeln!(
+            mermaid_src,
+            "{indent}subgraph {mermaid_node_id} [\"{node_label}\"]"
+        )?;
+        node_hierarchy
+            .iter()
+            .try_for_each(|(child_node_id, child_node_hierarchy)| {
+                node_write(
+                    mermaid_src,
+                    info_graph,
+                    info_graph_classes,
+                    style_to_node_ids,
+                    child_node_id,
+                    child_node_hierarchy,
+                    depth + 1,
+                )
+            })?;
+        writeln!(mermaid_src, "{indent}end")
+    }
+}
+
+fn edge_write(
+    mermaid_src: &mut String,
+    info_graph: &InfoGraph,
+    edge_id: &EdgeId,
+    src_node_id: &NodeId,
+    target_node_id: &NodeId,
+) -> fmt::Result {
+    let graphviz_attrs = info_graph.graphviz_attrs();
+    let edge_dir = graphviz_attrs
+        .edge_dirs()
+        .get(edge_id)
+        .copied()
+        .unwrap_or_else(|| graphviz_attrs.edge_dir_default());
+
+    // Compass points such as `a:n` are not supported by Mermaid.
+    let src_node_id = mermaid_id(src_node_id.node_id_plain());
+    let target_node_id = mermaid_id(target_node_id.node_id_plain());
+    let (src_node_id, arrow, target_node_id) = match edge_dir {
+        EdgeDir::Forward => (src_node_id, "-->", target_node_id),
+        EdgeDir::Back => (target_node_id, "-->", src_node_id),
+        EdgeDir::Both => (src_node_id, "<-->", target_node_id),
+        EdgeDir::None => (src_node_id, "---", target_node_id),
+    };
+
+    match info_graph.edge_descs().get(edge_id) {
+        Some(edge_desc) => {
+            let edge_label = label_escape(edge_desc);
+            writeln!(
+                mermaid_src,
+                "    {src_node_id} {arrow}|\"{edge_label}\"| {target_node_id}"
+            )
+        }
+        None => writeln!(mermaid_src, "    {src_node_id} {arrow} {target_node_id}"),
+    }
+}
+
+/// Returns the label for a node, which is its emoji and name, followed by its
+/// description for nodes that are not clusters.
+fn node_label(info_graph: &InfoGraph, node_id: &NodeId, with_desc: bool) -> String {
+    let node_name = info_graph
+        .node_names()
+        .get(node_id)
+        .map(String::as_str)
+        .unwrap_or(node_id.as_str());
+    let mut node_label = match info_graph.node_emojis().get(node_id) {
+        Some(node_emoji) => format!("{node_emoji} {}", label_escape(node_name)),
+        None => label_escape(node_name).into_owned(),
+    };
+
+    info_graph
+        .node_descs()
+        .get(node_id)
+        .filter(|_| with_desc)
+        .into_iter()
+        .flat_map(|node_desc| node_desc.split('\n'))
+        .for_each(|node_desc_line| {
+            node_label.push_str("<br/>");
+            node_label.push_str(&label_escape(node_desc_line));
+        });
+
+    node_label
+}
+
+/// Returns the Mermaid style for the theme classes of an element.
+fn mermaid_style(
+    info_graph_classes: &InfoGraphClasses<'_>,
+    el_id: &str,
+    shape_variants: &[&str],
+) -> String {
+    info_graph_classes
+        .shape_properties(el_id, shape_variants)
+        .iter()
+        .map(|(property, value)| {
+            if *property == "stroke-width" && value.parse::<f64>().is_ok() {
+                format!("{property}:{value}px")
+            } else {
+                format!("{property}:{value}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Returns the node ID, with `_` appended if it is a Mermaid keyword.
+///
+/// IDs that are a keyword followed by `_`s also have `_` appended, so that a
+/// node named `end_` does not collide with the node named `end`.
+fn mermaid_id(node_id: &str) -> Cow<'_, str> {
+    if MERMAID_KEYWORDS.contains(&node_id.trim_end_matches('_')) {
+        Cow::Owned(format!("{node_id}_"))
+    } else {
+        Cow::Borrowed(node_id)
+    }
+}
+
+/// Replaces characters that Mermaid would otherwise treat as markup in a label
+/// with Mermaid entity codes.
+///
+/// `"` would end the label, `<`, `>`, and `&` would be rendered as HTML, and
+/// `#` would start an entity code.
+fn label_escape(text: &str) -> Cow<'_, str> {
+    if text.contains(['"', '<', '>', '&', '#']) {
+        let text_escaped = text.chars().fold(
+            String::with_capacity(text.len() + 16),
+            |mut text_escaped, c| {
+                match c {
+                    '"' => text_escaped.push_str("#quot;"),
+                    '<' => text_escaped.push_str("#lt;"),
+                    '>' => text_escaped.push_str("#gt;"),
+                    '&' => text_escaped.push_str("#amp;"),
+                    '#' => text_escaped.push_str("#35;"),
+                    c => text_escaped.push(c),
+                }
+                text_escaped
+            },
+        );
+        Cow::Owned(text_escaped)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
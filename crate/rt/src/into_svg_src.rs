@@ -0,0 +1,8 @@
+use dot_ix_model::common::{GraphvizDotTheme, SvgSrcAndStyles};
+
+mod info_graph;
+
+/// Generates SVG by laying out the diagram in Rust, without Graphviz.
+pub trait IntoSvgSrc {
+    fn into(self, theme: &GraphvizDotTheme) -> SvgSrcAndStyles;
+}
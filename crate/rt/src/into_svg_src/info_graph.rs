@@ -0,0 +1,1351 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use dot_ix_model::{
+    common::{
+        dot_src_and_styles::GraphvizImage,
+        graphviz_attrs::{ArrowPrimitive, ArrowType, EdgeDir, FixedSize, Margin, Splines},
+        EdgeId, GraphvizDotTheme, ImageId, NodeHierarchy, NodeId, SvgSrcAndStyles, TagId,
+    },
+    info_graph::{GraphDir, GraphStyle, InfoGraph},
+};
+
+use crate::{
+    info_graph_classes::{InfoGraphClasses, OUTLINE_NONE},
+    layered_layout::{
+        LayeredLayout, LayoutEdge, LayoutItem, LayoutOutput, LayoutPadding, LayoutPoint, LayoutRect,
+    },
+    xml_escape::xml_escape,
+    InfoGraphDot, IntoSvgSrc,
+};
+
+/// Graphviz measures `width`, `height`, and node `margin`s in inches.
+const POINTS_PER_INCH: f64 = 72.0;
+
+/// Space around the diagram, the same as `margin` in the generated dot source.
+const GRAPH_MARGIN: f64 = 0.1 * POINTS_PER_INCH;
+
+/// Width of a character relative to the font size, for `liberationmono`.
+const CHAR_WIDTH_RATIO: f64 = 0.6;
+
+/// Height of a line of text relative to the font size.
+const LINE_HEIGHT_RATIO: f64 = 1.2;
+
+/// Space between a node's image and its label, the same as the spacer cell in
+/// the generated dot source.
+const IMAGE_SPACING: f64 = 10.0;
+
+/// Space between a circle node's circle and its label.
+const CIRCLE_LABEL_SPACING: f64 = 4.0;
+
+/// Radius of rounded corners of nodes and clusters.
+const CORNER_RADIUS: f64 = 4.0;
+
+/// Length of an arrowhead when `arrowsize` is `1.0`.
+const ARROW_LENGTH: f64 = 10.0;
+
+/// Half of an arrowhead's width relative to its length.
+const ARROW_HALF_WIDTH_RATIO: f64 = 0.35;
+
+/// Distance between an edge's label and the edge.
+const EDGE_LABEL_OFFSET: f64 = 4.0;
+
+/// Lays out an `InfoGraph` in Rust, and renders it as SVG.
+///
+/// The layout is layered (Sugiyama style):
+///
+/// 1. Each cluster's children are ranked along the [`GraphDir`], using
+///    `edge_minlens` and `edge_constraints`.
+/// 2. Nodes within each rank are ordered to reduce edge crossings.
+/// 3. Nodes are positioned to be near their neighbours, separated by `nodesep`
+///    and `ranksep`.
+///
+/// Clusters are laid out from the innermost outwards, and each cluster is
+/// treated as a single node in its parent's layout.
+///
+/// The SVG uses the same element IDs, CSS classes, and element structure as
+/// the SVG that Graphviz renders from [`IntoGraphvizDotSrc`], so the same
+/// [`SvgSrcAndStyles::styles`] apply to it.
+///
+/// # Limitations
+///
+/// * Text is measured assuming a monospace font.
+/// * Edges are drawn with curves between ranks, and `splines = "ortho"` is
+///   drawn as straight lines.
+/// * Arrowhead shapes other than `normal`, `inv`, `vee`, `box`, `diamond`,
+///   `tee`, and `none` are drawn as `normal`, and only the first shape of an
+///   [`ArrowType`] is drawn.
+///
+/// [`IntoGraphvizDotSrc`]: crate::IntoGraphvizDotSrc
+impl IntoSvgSrc for &InfoGraph {
+    fn into(self, theme: &GraphvizDotTheme) -> SvgSrcAndStyles {
+        let node_id_to_hierarchy = self.hierarchy_flat();
+        let node_id_to_hierarchy = &node_id_to_hierarchy;
+        let node_id_to_parent = self.node_parents();
+        let node_id_to_parent = &node_id_to_parent;
+        let el_tag_ids = self.el_tag_ids();
+        let el_tag_ids = &el_tag_ids;
+
+        let info_graph_dot = InfoGraphDot {
+            graph_style: self.graph_style(),
+            node_id_to_hierarchy,
+            node_id_to_parent,
+            edges: self.edges(),
+            graphviz_attrs: self.graphviz_attrs(),
+            el_tag_ids,
+            node_ids: node_id_to_hierarchy.keys().copied().collect::<Vec<_>>(),
+            edge_ids: self.edges().keys().collect::<Vec<_>>(),
+        };
+        let info_graph_classes = InfoGraphClasses::new(self, &info_graph_dot);
+
+        let svg_layout = SvgLayout::new(self, theme);
+        let layout_output = svg_layout.layered_layout.layout();
+
+        let mut svg_src =
+            String::with_capacity(512 * (svg_layout.svg_items.len() + self.edges().len()) + 512);
+        svg_write(
+            &mut svg_src,
+            self,
+            theme,
+            &info_graph_classes,
+            &svg_layout,
+            &layout_output,
+        )
+        .expect("Failed to write `svg_src` string.");
+
+        let styles = info_graph_classes.styles(theme, self.css());
+
+        SvgSrcAndStyles {
+            svg_src,
+            styles,
+            theme_warnings: info_graph_classes.theme_warnings,
+        }
+    }
+}
+
+/// What a layout item is rendered as.
+#[derive(Clone, Copy, Debug)]
+enum SvgItem<'graph> {
+    /// The cluster around the tags.
+    Legend,
+    /// A tag within the legend.
+    Tag(&'graph TagId),
+    /// A node, which is a cluster if it has children.
+    Node {
+        node_id: &'graph NodeId,
+        is_cluster: bool,
+    },
+}
+
+/// An edge to render, and the layout edge it corresponds to.
+struct SvgEdge<'graph> {
+    edge_id: &'graph EdgeId,
+    src_node_id: &'graph str,
+    target_node_id: &'graph str,
+    src_item: usize,
+    target_item: usize,
+    src_compass_point: Option<&'graph str>,
+    target_compass_point: Option<&'graph str>,
+    layout_edge_index: usize,
+}
+
+/// Layout input, and what each item and edge is rendered as.
+struct SvgLayout<'graph> {
+    layered_layout: LayeredLayout,
+    svg_items: Vec<SvgItem<'graph>>,
+    labels: Vec<Label<'graph>>,
+    svg_edges: Vec<SvgEdge<'graph>>,
+}
+
+impl<'graph> SvgLayout<'graph> {
+    fn new(info_graph: &'graph InfoGraph, theme: &GraphvizDotTheme) -> Self {
+        let graphviz_attrs = info_graph.graphviz_attrs();
+        let graph_dir = info_graph.direction();
+        let mut svg_layout = SvgLayout {
+            layered_layout: LayeredLayout {
+                graph_dir,
+                nodesep: graphviz_attrs.nodesep() * POINTS_PER_INCH,
+                ranksep: graphviz_attrs.ranksep() * POINTS_PER_INCH,
+                items: Vec::new(),
+                roots: Vec::new(),
+                edges: Vec::new(),
+            },
+            svg_items: Vec::new(),
+            labels: Vec::new(),
+            svg_edges: Vec::new(),
+        };
+
+        let tags = info_graph.tags();
+        if !tags.is_empty() {
+            let legend_margin = f64::from(theme.node_point_size());
+            let legend_label = Label {
+                lines: vec![Cow::Borrowed("Legend")],
+                font_size: f64::from(theme.node_point_size()),
+                bold: true,
+                ..Label::default()
+            };
+            let legend_index = svg_layout.item_push(
+                SvgItem::Legend,
+                LayoutItem {
+                    width: legend_label.width() + 2.0 * legend_margin,
+                    height: legend_label.height() + 2.0 * legend_margin,
+                    padding: LayoutPadding {
+                        left: legend_margin,
+                        right: legend_margin,
+                        top: legend_margin + legend_label.height(),
+                        bottom: legend_margin,
+                    },
+                    children: Vec::new(),
+                },
+                legend_label,
+            );
+            svg_layout.layered_layout.roots.push(legend_index);
+
+            let tag_margin_x = theme.tag_margin_x() * POINTS_PER_INCH;
+            let tag_margin_y = theme.tag_margin_y() * POINTS_PER_INCH;
+            let tag_indices = tags
+                .iter()
+                .map(|(tag_id, tag_name)| {
+                    let tag_label = Label {
+                        lines: vec![label_text(tag_name)],
+                        font_size: f64::from(theme.tag_point_size()),
+                        ..Label::default()
+                    };
+                    svg_layout.item_push(
+                        SvgItem::Tag(tag_id),
+                        LayoutItem {
+                            width: (theme.tag_width() * POINTS_PER_INCH)
+                                .max(tag_label.width() + 2.0 * tag_margin_x),
+                            height: (theme.tag_height() * POINTS_PER_INCH)
+                                .max(tag_label.height() + 2.0 * tag_margin_y),
+                            ..LayoutItem::default()
+                        },
+                        tag_label,
+                    )
+                })
+                .collect::<Vec<usize>>();
+
+            // Tags are stacked the same way as the dot source: all in one rank for
+            // horizontal graphs, and joined by invisible edges for vertical graphs.
+            if graph_dir == GraphDir::Vertical {
+                tag_indices.windows(2).for_each(|tag_pair| {
+                    svg_layout.layered_layout.edges.push(LayoutEdge {
+                        src: tag_pair[0],
+                        target: tag_pair[1],
+                        minlen: 1,
+                        constraint: true,
+                    });
+                });
+            }
+            svg_layout.layered_layout.items[legend_index].children = tag_indices;
+        }
+
+        let mut node_id_to_item = HashMap::<&str, usize>::new();
+        let root_indices = info_graph
+            .hierarchy()
+            .iter()
+            .map(|(node_id, node_hierarchy)| {
+                svg_layout.node_items_push(
+                    info_graph,
+                    theme,
+                    &mut node_id_to_item,
+                    node_id,
+                    node_hierarchy,
+                )
+            })
+            .collect::<Vec<usize>>();
+        svg_layout.layered_layout.roots.extend(root_indices);
+
+        info_graph
+            .edges()
+            .iter()
+            .for_each(|(edge_id, [src_node_id, target_node_id])| {
+                let (src_node_id_plain, src_compass_point) = src_node_id.node_id_and_port();
+                let (target_node_id_plain, target_compass_point) =
+                    target_node_id.node_id_and_port();
+                let (Some(src_item), Some(target_item)) = (
+                    node_id_to_item.get(src_node_id_plain).copied(),
+                    node_id_to_item.get(target_node_id_plain).copied(),
+                ) else {
+                    return;
+                };
+
+                let layout_edge_index = svg_layout.layered_layout.edges.len();
+                svg_layout.layered_layout.edges.push(LayoutEdge {
+                    src: src_item,
+                    target: target_item,
+                    minlen: graphviz_attrs
+                        .edge_minlens()
+                        .get(edge_id)
+                        .copied()
+                        .unwrap_or_else(|| graphviz_attrs.edge_minlen_default()),
+                    constraint: graphviz_attrs
+                        .edge_constraints()
+                        .get(edge_id)
+                        .copied()
+                        .unwrap_or_else(|| graphviz_attrs.edge_constraint_default()),
+                });
+                svg_layout.svg_edges.push(SvgEdge {
+                    edge_id,
+                    src_node_id: src_node_id.as_str(),
+                    target_node_id: target_node_id.as_str(),
+                    src_item,
+                    target_item,
+                    src_compass_point,
+                    target_compass_point,
+                    layout_edge_index,
+                });
+            });
+
+        svg_layout
+    }
+
+    fn item_push(
+        &mut self,
+        svg_item: SvgItem<'graph>,
+        layout_item: LayoutItem,
+        label: Label<'graph>,
+    ) -> usize {
+        let item_index = self.layered_layout.items.len();
+        self.layered_layout.items.push(layout_item);
+        self.svg_items.push(svg_item);
+        self.labels.push(label);
+        item_index
+    }
+
+    /// Pushes the items for a node and its descendants, returning the node's
+    /// item index.
+    fn node_items_push(
+        &mut self,
+        info_graph: &'graph InfoGraph,
+        theme: &GraphvizDotTheme,
+        node_id_to_item: &mut HashMap<&'graph str, usize>,
+        node_id: &'graph NodeId,
+        node_hierarchy: &'graph NodeHierarchy,
+    ) -> usize {
+        let graphviz_attrs = info_graph.graphviz_attrs();
+        let label = Label::node(info_graph, theme, node_id);
+        let is_cluster = !node_hierarchy.is_empty();
+        let svg_item = SvgItem::Node {
+            node_id,
+            is_cluster,
+        };
+
+        let layout_item = if is_cluster {
+            let (margin_x, margin_y) = margin_xy(
+                graphviz_attrs
+                    .margins()
+                    .get(node_id)
+                    .copied()
+                    .unwrap_or_else(|| graphviz_attrs.margin_cluster_default().into_inner()),
+            );
+
+            LayoutItem {
+                width: label.width() + 2.0 * margin_x,
+                height: label.height() + 2.0 * margin_y,
+                padding: LayoutPadding {
+                    left: margin_x,
+                    right: margin_x,
+                    top: margin_y + label.height(),
+                    bottom: margin_y,
+                },
+                children: Vec::new(),
+            }
+        } else {
+            let (margin_x, margin_y) = margin_xy(
+                graphviz_attrs
+                    .margins()
+                    .get(node_id)
+                    .copied()
+                    .unwrap_or_else(|| graphviz_attrs.margin_node_default().into_inner()),
+            );
+            let (margin_x, margin_y) = (margin_x * POINTS_PER_INCH, margin_y * POINTS_PER_INCH);
+            let width_min = graphviz_attrs
+                .node_widths()
+                .get(node_id)
+                .copied()
+                .unwrap_or_else(|| graphviz_attrs.node_width_default())
+                * POINTS_PER_INCH;
+            let height_min = graphviz_attrs
+                .node_heights()
+                .get(node_id)
+                .copied()
+                .unwrap_or_else(|| graphviz_attrs.node_height_default())
+                * POINTS_PER_INCH;
+
+            let (width, height) = match info_graph.graph_style() {
+                GraphStyle::Box => (
+                    label.width() + 2.0 * margin_x,
+                    label.height() + 2.0 * margin_y,
+                ),
+                GraphStyle::Circle => {
+                    let diameter = circle_diameter(&label, margin_y, height_min);
+                    (
+                        diameter + CIRCLE_LABEL_SPACING + label.width() + 2.0 * margin_x,
+                        diameter.max(label.height() + 2.0 * margin_y),
+                    )
+                }
+            };
+            let (width, height) = match graphviz_attrs.fixed_size() {
+                FixedSize::False => (width.max(width_min), height.max(height_min)),
+                FixedSize::True | FixedSize::Shape => (width_min, height_min),
+            };
+
+            LayoutItem {
+                width,
+                height,
+                ..LayoutItem::default()
+            }
+        };
+
+        let item_index = self.item_push(svg_item, layout_item, label);
+        node_id_to_item.insert(node_id.as_str(), item_index);
+
+        if is_cluster {
+            let child_indices = node_hierarchy
+                .iter()
+                .map(|(child_node_id, child_node_hierarchy)| {
+                    self.node_items_push(
+                        info_graph,
+                        theme,
+                        node_id_to_item,
+                        child_node_id,
+                        child_node_hierarchy,
+                    )
+                })
+                .collect::<Vec<usize>>();
+            self.layered_layout.items[item_index].children = child_indices;
+        }
+
+        item_index
+    }
+}
+
+/// Text, emoji, and image within a node, cluster, or tag.
+#[derive(Clone, Debug, Default)]
+struct Label<'graph> {
+    /// Image ID and its width and height.
+    image: Option<(&'graph ImageId, f64, f64)>,
+    emoji: Option<&'graph str>,
+    /// The name followed by each line of the description.
+    lines: Vec<Cow<'graph, str>>,
+    font_size: f64,
+    emoji_size: f64,
+    bold: bool,
+}
+
+impl<'graph> Label<'graph> {
+    fn node(
+        info_graph: &'graph InfoGraph,
+        theme: &GraphvizDotTheme,
+        node_id: &'graph NodeId,
+    ) -> Self {
+        let node_name = info_graph
+            .node_names()
+            .get(node_id)
+            .map(String::as_str)
+            .unwrap_or(node_id.as_str());
+        let node_desc_lines = info_graph
+            .node_descs()
+            .get(node_id)
+            .into_iter()
+            .flat_map(|node_desc| node_desc.split('\n'))
+            .map(label_text);
+        let image = info_graph
+            .node_images()
+            .get(node_id)
+            .and_then(|image_id| info_graph.images().get_key_value(image_id))
+            .map(|(image_id, image)| {
+                let GraphvizImage {
+                    path: _,
+                    width,
+                    height,
+                } = image;
+                (image_id, length_px(width), length_px(height))
+            });
+
+        Label {
+            image,
+            emoji: info_graph.node_emojis().get(node_id).map(String::as_str),
+            lines: std::iter::once(label_text(node_name))
+                .chain(node_desc_lines)
+                .collect(),
+            font_size: f64::from(theme.node_point_size()),
+            emoji_size: f64::from(theme.emoji_point_size()),
+            bold: false,
+        }
+    }
+
+    fn line_height(&self) -> f64 {
+        self.font_size * LINE_HEIGHT_RATIO
+    }
+
+    fn image_column_width(&self) -> f64 {
+        self.image
+            .map(|(_image_id, width, _height)| width + IMAGE_SPACING)
+            .unwrap_or_default()
+    }
+
+    fn emoji_column_width(&self) -> f64 {
+        self.emoji
+            .map(|_emoji| self.emoji_size + 4.0)
+            .unwrap_or_default()
+    }
+
+    fn width(&self) -> f64 {
+        let text_width = self
+            .lines
+            .iter()
+            .map(|line| text_width(line, self.font_size))
+            .fold(0.0f64, f64::max);
+
+        self.image_column_width() + self.emoji_column_width() + text_width
+    }
+
+    fn height(&self) -> f64 {
+        let text_height = self.lines.len() as f64 * self.line_height();
+        let image_height = self
+            .image
+            .map(|(_image_id, _width, height)| height)
+            .unwrap_or_default();
+        let emoji_height = self
+            .emoji
+            .map(|_emoji| self.emoji_size * LINE_HEIGHT_RATIO)
+            .unwrap_or_default();
+
+        text_height.max(image_height).max(emoji_height)
+    }
+
+    /// Writes the label with its top left corner at the given position.
+    fn write(&self, buffer: &mut String, x: f64, y: f64, fill: &str) -> fmt::Result {
+        if let Some((image_id, width, height)) = self.image {
+            writeln!(
+                buffer,
+                "<image xlink:href=\"{image_id}\" width=\"{width:.2}px\" height=\"{height:.2}px\" \
+                preserveAspectRatio=\"xMinYMin meet\" x=\"{x:.2}\" y=\"{y:.2}\"/>"
+            )?;
+        }
+        if let Some(emoji) = self.emoji {
+            let emoji_x = x + self.image_column_width() + 2.0;
+            let emoji_y = y + self.emoji_size;
+            let emoji_size = self.emoji_size;
+            writeln!(
+                buffer,
+                "<text text-anchor=\"start\" x=\"{emoji_x:.2}\" y=\"{emoji_y:.2}\" \
+                font-family=\"liberationmono\" font-size=\"{emoji_size:.2}\" fill=\"{fill}\">\
+                {emoji}</text>"
+            )?;
+        }
+
+        let text_x = x + self.image_column_width() + self.emoji_column_width();
+        let font_size = self.font_size;
+        let font_weight = if self.bold {
+            " font-weight=\"bold\""
+        } else {
+            ""
+        };
+        self.lines.iter().enumerate().try_for_each(|(index, line)| {
+            let text_y = y + index as f64 * self.line_height() + font_size;
+            writeln!(
+                buffer,
+                "<text text-anchor=\"start\" x=\"{text_x:.2}\" y=\"{text_y:.2}\" \
+                font-family=\"liberationmono\" font-size=\"{font_size:.2}\"{font_weight} \
+                fill=\"{fill}\">{line}</text>"
+            )
+        })
+    }
+}
+
+fn svg_write(
+    buffer: &mut String,
+    info_graph: &InfoGraph,
+    theme: &GraphvizDotTheme,
+    info_graph_classes: &InfoGraphClasses<'_>,
+    svg_layout: &SvgLayout<'_>,
+    layout_output: &LayoutOutput,
+) -> fmt::Result {
+    let width = layout_output.width + 2.0 * GRAPH_MARGIN;
+    let height = layout_output.height + 2.0 * GRAPH_MARGIN;
+    let item_rect = |item_index: usize| {
+        let rect = layout_output.item_rects[item_index];
+        LayoutRect {
+            x: rect.x + GRAPH_MARGIN,
+            y: rect.y + GRAPH_MARGIN,
+            ..rect
+        }
+    };
+
+    writeln!(
+        buffer,
+        "<svg width=\"{width:.0}pt\" height=\"{height:.0}pt\" \
+        viewBox=\"0.00 0.00 {width:.2} {height:.2}\" \
+        xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\">"
+    )?;
+    writeln!(buffer, "<g id=\"graph0\" class=\"graph\">")?;
+    writeln!(buffer, "<title>G</title>")?;
+
+    // Graphviz renders all clusters before nodes and edges, which is needed for
+    // `peer-focus` classes on tags to apply to the nodes and edges after them.
+    let el_tag_ids = info_graph.el_tag_ids();
+    let graphviz_attrs = info_graph.graphviz_attrs();
+    let mut cluster_number = 0;
+    svg_layout
+        .svg_items
+        .iter()
+        .enumerate()
+        .try_for_each(|(item_index, svg_item)| {
+            let (title, classes, fill) = match svg_item {
+                SvgItem::Legend => (
+                    Cow::Borrowed("cluster_tag_legend"),
+                    Cow::Borrowed(""),
+                    "none",
+                ),
+                SvgItem::Tag(tag_id) => {
+                    let tag_classes = info_graph_classes
+                        .el_css_classes
+                        .get(tag_id.as_str())
+                        .map(AsRef::<str>::as_ref)
+                        .unwrap_or(theme.tag_classes().trim());
+                    (
+                        Cow::Owned(format!("cluster_{tag_id}")),
+                        Cow::Owned(format!(" {OUTLINE_NONE} {tag_classes} peer/{tag_id}")),
+                        "none",
+                    )
+                }
+                SvgItem::Node {
+                    node_id,
+                    is_cluster: true,
+                } => {
+                    let node_classes = info_graph_classes
+                        .el_classes(node_id.as_str(), el_tag_ids.get(node_id.as_str()));
+                    (
+                        Cow::Owned(format!("cluster_{node_id}")),
+                        Cow::Owned(format!(" {OUTLINE_NONE} {node_classes}")),
+                        "lightgrey",
+                    )
+                }
+                SvgItem::Node {
+                    is_cluster: false, ..
+                } => return Ok(()),
+            };
+
+            cluster_number += 1;
+            let rect = item_rect(item_index);
+            let (margin_x, margin_y) = match svg_item {
+                SvgItem::Legend => {
+                    let legend_margin = f64::from(theme.node_point_size());
+                    (legend_margin, legend_margin)
+                }
+                SvgItem::Tag(_) => (
+                    theme.tag_margin_x() * POINTS_PER_INCH,
+                    theme.tag_margin_y() * POINTS_PER_INCH,
+                ),
+                SvgItem::Node { node_id, .. } => margin_xy(
+                    graphviz_attrs
+                        .margins()
+                        .get(*node_id)
+                        .copied()
+                        .unwrap_or_else(|| graphviz_attrs.margin_cluster_default().into_inner()),
+                ),
+            };
+            let path = rounded_rect_path(rect);
+            let title = xml_escape(&title);
+
+            writeln!(
+                buffer,
+                "<g id=\"clust{cluster_number}\" class=\"cluster{classes}\">"
+            )?;
+            writeln!(buffer, "<title>{title}</title>")?;
+            writeln!(
+                buffer,
+                "<path fill=\"{fill}\" stroke=\"black\" d=\"{path}\"/>"
+            )?;
+            svg_layout.labels[item_index].write(
+                buffer,
+                rect.x + margin_x,
+                rect.y + margin_y,
+                theme.node_text_color(),
+            )?;
+            writeln!(buffer, "</g>")
+        })?;
+
+    let mut node_number = 0;
+    svg_layout.svg_items.iter().enumerate().try_for_each(
+        |(item_index, svg_item)| match svg_item {
+            // Invisible node for the tag's cluster to appear.
+            SvgItem::Tag(tag_id) => {
+                node_number += 1;
+                writeln!(
+                    buffer,
+                    "<g id=\"node{node_number}\" class=\"node\">\n<title>{tag_id}</title>\n</g>"
+                )
+            }
+            SvgItem::Node {
+                node_id,
+                is_cluster: false,
+            } => {
+                node_number += 1;
+                let node_classes = info_graph_classes
+                    .el_classes(node_id.as_str(), el_tag_ids.get(node_id.as_str()));
+                let rect = item_rect(item_index);
+                let label = &svg_layout.labels[item_index];
+                let title = xml_escape(node_id);
+
+                writeln!(
+                    buffer,
+                    "<g id=\"node{node_number}\" class=\"node {OUTLINE_NONE} {node_classes}\">"
+                )?;
+                writeln!(buffer, "<title>{title}</title>")?;
+                match info_graph.graph_style() {
+                    GraphStyle::Box => {
+                        let path = rounded_rect_path(rect);
+                        writeln!(
+                            buffer,
+                            "<path fill=\"lightgrey\" stroke=\"black\" d=\"{path}\"/>"
+                        )?;
+                        let center = rect.center();
+                        label.write(
+                            buffer,
+                            center.x - label.width() / 2.0,
+                            center.y - label.height() / 2.0,
+                            theme.node_text_color(),
+                        )?;
+                    }
+                    GraphStyle::Circle => {
+                        let (margin_x, margin_y) = node_margin_points(info_graph, node_id);
+                        let height_min = graphviz_attrs
+                            .node_heights()
+                            .get(*node_id)
+                            .copied()
+                            .unwrap_or_else(|| graphviz_attrs.node_height_default())
+                            * POINTS_PER_INCH;
+                        let radius = circle_diameter(label, margin_y, height_min) / 2.0;
+                        let center = rect.center();
+                        let circle_x = rect.x + margin_x + radius;
+                        let circle_y = center.y;
+                        writeln!(
+                            buffer,
+                            "<ellipse fill=\"lightgrey\" stroke=\"black\" \
+                            cx=\"{circle_x:.2}\" cy=\"{circle_y:.2}\" \
+                            rx=\"{radius:.2}\" ry=\"{radius:.2}\"/>"
+                        )?;
+                        label.write(
+                            buffer,
+                            circle_x + radius + CIRCLE_LABEL_SPACING,
+                            center.y - label.height() / 2.0,
+                            theme.node_text_color(),
+                        )?;
+                    }
+                }
+                writeln!(buffer, "</g>")
+            }
+            SvgItem::Legend
+            | SvgItem::Node {
+                is_cluster: true, ..
+            } => Ok(()),
+        },
+    )?;
+
+    svg_layout.svg_edges.iter().try_for_each(|svg_edge| {
+        let edge_id = svg_edge.edge_id.as_str();
+        let edge_classes = info_graph_classes.el_classes(edge_id, el_tag_ids.get(edge_id));
+        let waypoints = &layout_output.edge_waypoints[svg_edge.layout_edge_index];
+        let waypoints = waypoints
+            .iter()
+            .map(|waypoint| LayoutPoint {
+                x: waypoint.x + GRAPH_MARGIN,
+                y: waypoint.y + GRAPH_MARGIN,
+            })
+            .collect::<Vec<_>>();
+
+        edge_write(
+            buffer,
+            info_graph,
+            theme,
+            svg_edge,
+            &edge_classes,
+            item_rect(svg_edge.src_item),
+            item_rect(svg_edge.target_item),
+            &waypoints,
+        )
+    })?;
+
+    writeln!(buffer, "</g>")?;
+    writeln!(buffer, "</svg>")
+}
+
+#[allow(clippy::too_many_arguments)]
+fn edge_write(
+    buffer: &mut String,
+    info_graph: &InfoGraph,
+    theme: &GraphvizDotTheme,
+    svg_edge: &SvgEdge<'_>,
+    edge_classes: &str,
+    src_rect: LayoutRect,
+    target_rect: LayoutRect,
+    waypoints: &[LayoutPoint],
+) -> fmt::Result {
+    let graphviz_attrs = info_graph.graphviz_attrs();
+    let graph_dir = info_graph.direction();
+    let SvgEdge {
+        edge_id,
+        src_node_id,
+        target_node_id,
+        src_item,
+        target_item,
+        src_compass_point,
+        target_compass_point,
+        layout_edge_index: _,
+    } = svg_edge;
+    let edge_color = theme.edge_color();
+    let title = format!("{src_node_id}->{target_node_id}");
+    let title = xml_escape(&title);
+
+    writeln!(buffer, "<g id=\"{edge_id}\" class=\"edge {edge_classes}\">")?;
+    writeln!(buffer, "<title>{title}</title>")?;
+
+    let mut points = if src_item == target_item {
+        self_loop_points(src_rect, graph_dir)
+    } else {
+        let src_toward = waypoints
+            .first()
+            .copied()
+            .unwrap_or_else(|| target_rect.center());
+        let target_toward = waypoints
+            .last()
+            .copied()
+            .unwrap_or_else(|| src_rect.center());
+        let src_point = src_compass_point
+            .and_then(|compass_point| compass_point_position(src_rect, compass_point))
+            .unwrap_or_else(|| rect_clip(src_rect, src_toward));
+        let target_point = target_compass_point
+            .and_then(|compass_point| compass_point_position(target_rect, compass_point))
+            .unwrap_or_else(|| rect_clip(target_rect, target_toward));
+
+        let through_points = std::iter::once(src_point)
+            .chain(waypoints.iter().copied())
+            .chain(std::iter::once(target_point))
+            .collect::<Vec<_>>();
+        let curved = !matches!(
+            graphviz_attrs.splines(),
+            Splines::Line | Splines::Polyline | Splines::Ortho
+        );
+
+        bezier_points(&through_points, graph_dir, curved)
+    };
+
+    if graphviz_attrs.splines() != Splines::None && points.len() >= 4 {
+        let edge_dir = graphviz_attrs
+            .edge_dirs()
+            .get(*edge_id)
+            .copied()
+            .unwrap_or_else(|| graphviz_attrs.edge_dir_default());
+        let arrow_length = graphviz_attrs
+            .edge_arrowsizes()
+            .get(*edge_id)
+            .copied()
+            .unwrap_or_else(|| graphviz_attrs.edge_arrowsize_default())
+            * ARROW_LENGTH;
+        let penwidth = graphviz_attrs
+            .edge_penwidths()
+            .get(*edge_id)
+            .copied()
+            .unwrap_or_else(|| graphviz_attrs.edge_penwidth_default());
+        let stroke_width = if penwidth == 1.0 {
+            Cow::Borrowed("")
+        } else {
+            Cow::Owned(format!(" stroke-width=\"{penwidth}\""))
+        };
+        let arrowhead = graphviz_attrs
+            .edge_arrowheads()
+            .get(*edge_id)
+            .unwrap_or_else(|| graphviz_attrs.edge_arrowhead_default());
+        let arrowtail = graphviz_attrs
+            .edge_arrowtails()
+            .get(*edge_id)
+            .unwrap_or_else(|| graphviz_attrs.edge_arrowtail_default());
+
+        let head_arrow = matches!(edge_dir, EdgeDir::Forward | EdgeDir::Both)
+            .then(|| arrow_primitive(arrowhead))
+            .flatten()
+            .map(|primitive| {
+                let points_len = points.len();
+                let tip = points[points_len - 1];
+                let direction = unit_vector(points[points_len - 2], tip)
+                    .or_else(|| unit_vector(points[0], tip));
+                (primitive, tip, direction)
+            });
+        let tail_arrow = matches!(edge_dir, EdgeDir::Back | EdgeDir::Both)
+            .then(|| arrow_primitive(arrowtail))
+            .flatten()
+            .map(|primitive| {
+                let tip = points[0];
+                let direction = unit_vector(points[1], tip)
+                    .or_else(|| unit_vector(points[points.len() - 1], tip));
+                (primitive, tip, direction)
+            });
+
+        // Shorten the path so that it ends at the base of each arrow.
+        if let Some((_primitive, _tip, Some(direction))) = head_arrow {
+            let points_len = points.len();
+            [points_len - 2, points_len - 1]
+                .into_iter()
+                .for_each(|index| {
+                    points[index].x -= direction.x * arrow_length;
+                    points[index].y -= direction.y * arrow_length;
+                });
+        }
+        if let Some((_primitive, _tip, Some(direction))) = tail_arrow {
+            [0, 1].into_iter().for_each(|index| {
+                points[index].x -= direction.x * arrow_length;
+                points[index].y -= direction.y * arrow_length;
+            });
+        }
+
+        let path = bezier_path(&points);
+        writeln!(
+            buffer,
+            "<path fill=\"none\" stroke=\"{edge_color}\"{stroke_width} d=\"{path}\"/>"
+        )?;
+
+        [head_arrow, tail_arrow]
+            .into_iter()
+            .flatten()
+            .try_for_each(|(primitive, tip, direction)| {
+                let Some(direction) = direction else {
+                    return Ok(());
+                };
+                let polygon_points = arrow_polygon(primitive, tip, direction, arrow_length)
+                    .iter()
+                    .map(|point| format!("{:.2},{:.2}", point.x, point.y))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                writeln!(
+                    buffer,
+                    "<polygon fill=\"{edge_color}\" stroke=\"{edge_color}\"{stroke_width} \
+                    points=\"{polygon_points}\"/>"
+                )
+            })?;
+    }
+
+    if let Some(edge_desc) = info_graph.edge_descs().get(*edge_id) {
+        let font_size = f64::from(theme.edge_point_size());
+        let line_height = font_size * LINE_HEIGHT_RATIO;
+        let plain_text_color = theme.plain_text_color();
+        let label_position = polyline_midpoint(&points);
+        let edge_desc_lines = edge_desc.split('\n').collect::<Vec<_>>();
+        let label_y_first =
+            label_position.y - EDGE_LABEL_OFFSET - (edge_desc_lines.len() - 1) as f64 * line_height;
+        edge_desc_lines
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, line)| {
+                let label_x = label_position.x;
+                let label_y = label_y_first + index as f64 * line_height;
+                let line = label_text(line);
+                writeln!(
+                    buffer,
+                    "<text text-anchor=\"middle\" x=\"{label_x:.2}\" y=\"{label_y:.2}\" \
+                    font-family=\"liberationmono\" font-size=\"{font_size:.2}\" \
+                    fill=\"{plain_text_color}\">{line}</text>"
+                )
+            })?;
+    }
+
+    writeln!(buffer, "</g>")
+}
+
+/// Returns the horizontal and vertical margins.
+fn margin_xy(margin: Margin) -> (f64, f64) {
+    match margin {
+        Margin::Same(margin) => (margin, margin),
+        Margin::Different(margin_x, margin_y) => (margin_x, margin_y),
+    }
+}
+
+/// Returns a node's margins in points.
+fn node_margin_points(info_graph: &InfoGraph, node_id: &NodeId) -> (f64, f64) {
+    let graphviz_attrs = info_graph.graphviz_attrs();
+    let (margin_x, margin_y) = margin_xy(
+        graphviz_attrs
+            .margins()
+            .get(node_id)
+            .copied()
+            .unwrap_or_else(|| graphviz_attrs.margin_node_default().into_inner()),
+    );
+    (margin_x * POINTS_PER_INCH, margin_y * POINTS_PER_INCH)
+}
+
+/// Returns the diameter of a circle node's circle.
+fn circle_diameter(label: &Label<'_>, margin_y: f64, height_min: f64) -> f64 {
+    (label.line_height() + 2.0 * margin_y).max(height_min)
+}
+
+/// Parses a length such as `"50px"` into points.
+fn length_px(length: &str) -> f64 {
+    length
+        .trim()
+        .trim_end_matches("px")
+        .parse::<f64>()
+        .unwrap_or_default()
+}
+
+/// Returns the width of the text, assuming a monospace font.
+///
+/// Wide characters such as emojis and CJK characters are counted as two
+/// characters, and HTML entities are counted as one.
+fn text_width(text: &str, font_size: f64) -> f64 {
+    let mut char_count = 0.0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '&' {
+            let entity_len = chars
+                .clone()
+                .take(10)
+                .position(|c| c == ';')
+                .filter(|entity_len| *entity_len > 0);
+            if let Some(entity_len) = entity_len {
+                chars.nth(entity_len);
+            }
+            char_count += 1.0;
+        } else if u32::from(c) >= 0x2E80 {
+            char_count += 2.0;
+        } else {
+            char_count += 1.0;
+        }
+    }
+
+    char_count * font_size * CHAR_WIDTH_RATIO
+}
+
+/// Returns the text content of an HTML-like Graphviz label, without tags, and
+/// with stray `&`, `<`, and `>` characters escaped.
+fn label_text(text: &str) -> Cow<'_, str> {
+    if !text.contains(['<', '>', '&']) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut label_text = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '<' => match rest.find('>') {
+                Some(tag_end) => {
+                    rest = &rest[tag_end + 1..];
+                    continue;
+                }
+                None => label_text.push_str("&lt;"),
+            },
+            '>' => label_text.push_str("&gt;"),
+            '&' => {
+                let is_entity = rest[1..]
+                    .find(';')
+                    .filter(|entity_len| *entity_len > 0 && *entity_len <= 10)
+                    .is_some_and(|entity_len| {
+                        rest[1..=entity_len]
+                            .chars()
+                            .all(|c| c == '#' || c.is_ascii_alphanumeric())
+                    });
+                if is_entity {
+                    label_text.push('&');
+                } else {
+                    label_text.push_str("&amp;");
+                }
+            }
+            c => label_text.push(c),
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+
+    Cow::Owned(label_text)
+}
+
+/// Returns the path of a rectangle with rounded corners.
+fn rounded_rect_path(rect: LayoutRect) -> String {
+    let LayoutRect {
+        x,
+        y,
+        width,
+        height,
+    } = rect;
+    let r = CORNER_RADIUS.min(width / 2.0).min(height / 2.0);
+    let (x_right, y_bottom) = (x + width, y + height);
+
+    format!(
+        "M{x_r:.2},{y:.2} L{x_right_r:.2},{y:.2} Q{x_right:.2},{y:.2} {x_right:.2},{y_r:.2} \
+        L{x_right:.2},{y_bottom_r:.2} Q{x_right:.2},{y_bottom:.2} {x_right_r:.2},{y_bottom:.2} \
+        L{x_r:.2},{y_bottom:.2} Q{x:.2},{y_bottom:.2} {x:.2},{y_bottom_r:.2} \
+        L{x:.2},{y_r:.2} Q{x:.2},{y:.2} {x_r:.2},{y:.2} Z",
+        x_r = x + r,
+        x_right_r = x_right - r,
+        y_r = y + r,
+        y_bottom_r = y_bottom - r,
+    )
+}
+
+/// Returns where a line from the rectangle's centre towards the given point
+/// crosses the rectangle's border.
+fn rect_clip(rect: LayoutRect, toward: LayoutPoint) -> LayoutPoint {
+    let center = rect.center();
+    let (dx, dy) = (toward.x - center.x, toward.y - center.y);
+    let scale_x = if dx.abs() > f64::EPSILON {
+        rect.width / 2.0 / dx.abs()
+    } else {
+        f64::INFINITY
+    };
+    let scale_y = if dy.abs() > f64::EPSILON {
+        rect.height / 2.0 / dy.abs()
+    } else {
+        f64::INFINITY
+    };
+    let scale = scale_x.min(scale_y).min(1.0);
+    if !scale.is_finite() {
+        return center;
+    }
+
+    LayoutPoint {
+        x: center.x + dx * scale,
+        y: center.y + dy * scale,
+    }
+}
+
+/// Returns the position of a compass point on the rectangle's border, e.g.
+/// `"ne"` for the top right corner.
+fn compass_point_position(rect: LayoutRect, compass_point: &str) -> Option<LayoutPoint> {
+    let LayoutRect {
+        x,
+        y,
+        width,
+        height,
+    } = rect;
+    let (x_ratio, y_ratio) = match compass_point {
+        "n" => (0.5, 0.0),
+        "ne" => (1.0, 0.0),
+        "e" => (1.0, 0.5),
+        "se" => (1.0, 1.0),
+        "s" => (0.5, 1.0),
+        "sw" => (0.0, 1.0),
+        "w" => (0.0, 0.5),
+        "nw" => (0.0, 0.0),
+        "c" | "_" => (0.5, 0.5),
+        _ => return None,
+    };
+
+    Some(LayoutPoint {
+        x: x + width * x_ratio,
+        y: y + height * y_ratio,
+    })
+}
+
+/// Returns the cubic bezier points for an edge from a node to itself, drawn on
+/// the side of the node that is across the rank axis.
+fn self_loop_points(rect: LayoutRect, graph_dir: GraphDir) -> Vec<LayoutPoint> {
+    let center = rect.center();
+    let loop_size = ARROW_LENGTH * 2.0;
+    let point = |x, y| LayoutPoint { x, y };
+
+    match graph_dir {
+        GraphDir::Horizontal => {
+            let y_bottom = rect.y + rect.height;
+            vec![
+                point(center.x - rect.width / 4.0, y_bottom),
+                point(center.x - rect.width / 2.0, y_bottom + loop_size),
+                point(center.x + rect.width / 2.0, y_bottom + loop_size),
+                point(center.x + rect.width / 4.0, y_bottom),
+            ]
+        }
+        GraphDir::Vertical => {
+            let x_right = rect.x + rect.width;
+            vec![
+                point(x_right, center.y - rect.height / 4.0),
+                point(x_right + loop_size, center.y - rect.height / 2.0),
+                point(x_right + loop_size, center.y + rect.height / 2.0),
+                point(x_right, center.y + rect.height / 4.0),
+            ]
+        }
+    }
+}
+
+/// Returns cubic bezier points that pass through the given points.
+///
+/// The returned points are the start point, followed by two control points
+/// and an end point for each segment. Curved segments leave and enter each
+/// point along the rank axis.
+fn bezier_points(
+    through_points: &[LayoutPoint],
+    graph_dir: GraphDir,
+    curved: bool,
+) -> Vec<LayoutPoint> {
+    let mut points = Vec::with_capacity(through_points.len() * 3);
+    points.extend(through_points.first().copied());
+    through_points.windows(2).for_each(|segment| {
+        let (start, end) = (segment[0], segment[1]);
+        let rank_delta = match graph_dir {
+            GraphDir::Horizontal => end.x - start.x,
+            GraphDir::Vertical => end.y - start.y,
+        };
+        let (control_1, control_2) = if curved && rank_delta.abs() > 1.0 {
+            match graph_dir {
+                GraphDir::Horizontal => (
+                    LayoutPoint {
+                        x: start.x + rank_delta / 2.0,
+                        y: start.y,
+                    },
+                    LayoutPoint {
+                        x: end.x - rank_delta / 2.0,
+                        y: end.y,
+                    },
+                ),
+                GraphDir::Vertical => (
+                    LayoutPoint {
+                        x: start.x,
+                        y: start.y + rank_delta / 2.0,
+                    },
+                    LayoutPoint {
+                        x: end.x,
+                        y: end.y - rank_delta / 2.0,
+                    },
+                ),
+            }
+        } else {
+            (
+                LayoutPoint {
+                    x: start.x + (end.x - start.x) / 3.0,
+                    y: start.y + (end.y - start.y) / 3.0,
+                },
+                LayoutPoint {
+                    x: start.x + (end.x - start.x) * 2.0 / 3.0,
+                    y: start.y + (end.y - start.y) * 2.0 / 3.0,
+                },
+            )
+        };
+        points.extend([control_1, control_2, end]);
+    });
+
+    points
+}
+
+/// Returns the SVG path for cubic bezier points.
+fn bezier_path(points: &[LayoutPoint]) -> String {
+    let mut path = String::with_capacity(points.len() * 16);
+    points.iter().enumerate().for_each(|(index, point)| {
+        let command = match index {
+            0 => "M",
+            index if index % 3 == 1 => " C",
+            _ => " ",
+        };
+        let _ = write!(path, "{command}{:.2},{:.2}", point.x, point.y);
+    });
+    path
+}
+
+/// Returns the point halfway along the lines between the given points.
+fn polyline_midpoint(points: &[LayoutPoint]) -> LayoutPoint {
+    let distance = |a: LayoutPoint, b: LayoutPoint| (b.x - a.x).hypot(b.y - a.y);
+    let length_total = points
+        .windows(2)
+        .map(|segment| distance(segment[0], segment[1]))
+        .sum::<f64>();
+
+    let mut length_remaining = length_total / 2.0;
+    for segment in points.windows(2) {
+        let segment_length = distance(segment[0], segment[1]);
+        if segment_length >= length_remaining && segment_length > 0.0 {
+            let ratio = length_remaining / segment_length;
+            return LayoutPoint {
+                x: segment[0].x + (segment[1].x - segment[0].x) * ratio,
+                y: segment[0].y + (segment[1].y - segment[0].y) * ratio,
+            };
+        }
+        length_remaining -= segment_length;
+    }
+
+    points.first().copied().unwrap_or_default()
+}
+
+/// Returns the unit vector from `from` to `to`, or `None` if they are the
+/// same point.
+fn unit_vector(from: LayoutPoint, to: LayoutPoint) -> Option<LayoutPoint> {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let length = dx.hypot(dy);
+    (length > f64::EPSILON).then(|| LayoutPoint {
+        x: dx / length,
+        y: dy / length,
+    })
+}
+
+/// Returns the primitive to draw for an arrow, or `None` if no arrow is drawn.
+fn arrow_primitive(arrow_type: &ArrowType) -> Option<ArrowPrimitive> {
+    arrow_type
+        .shapes()
+        .first()
+        .map(|arrow_shape| arrow_shape.primitive())
+        .filter(|primitive| *primitive != ArrowPrimitive::None)
+}
+
+/// Returns the polygon for an arrow whose tip is at `tip`, pointing in
+/// `direction`.
+fn arrow_polygon(
+    primitive: ArrowPrimitive,
+    tip: LayoutPoint,
+    direction: LayoutPoint,
+    length: f64,
+) -> Vec<LayoutPoint> {
+    let half_width = length * ARROW_HALF_WIDTH_RATIO;
+    // Converts coordinates along and across the arrow into a point.
+    let point = |along: f64, across: f64| LayoutPoint {
+        x: tip.x - direction.x * along - direction.y * across,
+        y: tip.y - direction.y * along + direction.x * across,
+    };
+
+    match primitive {
+        ArrowPrimitive::Inv => vec![
+            point(length, 0.0),
+            point(0.0, half_width),
+            point(0.0, -half_width),
+            point(length, 0.0),
+        ],
+        ArrowPrimitive::Vee => vec![
+            point(0.0, 0.0),
+            point(length, half_width),
+            point(length * 0.75, 0.0),
+            point(length, -half_width),
+            point(0.0, 0.0),
+        ],
+        ArrowPrimitive::Box => vec![
+            point(0.0, half_width),
+            point(length, half_width),
+            point(length, -half_width),
+            point(0.0, -half_width),
+            point(0.0, half_width),
+        ],
+        ArrowPrimitive::Diamond => vec![
+            point(0.0, 0.0),
+            point(length / 2.0, half_width),
+            point(length, 0.0),
+            point(length / 2.0, -half_width),
+            point(0.0, 0.0),
+        ],
+        ArrowPrimitive::Tee => vec![
+            point(0.0, half_width * 1.5),
+            point(length * 0.25, half_width * 1.5),
+            point(length * 0.25, -half_width * 1.5),
+            point(0.0, -half_width * 1.5),
+            point(0.0, half_width * 1.5),
+        ],
+        ArrowPrimitive::Normal
+        | ArrowPrimitive::Crow
+        | ArrowPrimitive::Curve
+        | ArrowPrimitive::ICurve
+        | ArrowPrimitive::Dot
+        | ArrowPrimitive::None => vec![
+            point(0.0, 0.0),
+            point(length, half_width),
+            point(length, -half_width),
+            point(0.0, 0.0),
+        ],
+    }
+}
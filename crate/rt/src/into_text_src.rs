@@ -0,0 +1,12 @@
+pub use self::{text_charset::TextCharset, text_options::TextOptions};
+
+mod edge_router;
+mod info_graph;
+mod text_canvas;
+mod text_charset;
+mod text_options;
+
+/// Generates a text diagram for terminals, logs, and code review comments.
+pub trait IntoTextSrc {
+    fn into(self, text_options: &TextOptions) -> String;
+}
@@ -0,0 +1,266 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::into_text_src::text_canvas::{
+    direction_between, direction_opposite, TextCanvas, TextRect, DOWN, LEFT, RIGHT, UP,
+};
+
+/// Extra cost of turning, so that routes with fewer bends are preferred.
+const BEND_COST: usize = 4;
+
+/// Extra cost of crossing another line, so that routes that go around lines
+/// are preferred when they are not much longer.
+const CROSSING_COST: usize = 2;
+
+/// Directions in the order routes try them.
+const DIRECTIONS: [u8; 4] = [UP, DOWN, LEFT, RIGHT];
+
+/// Returns whether an edge can be drawn along the path without touching a box
+/// or joining lines that are already drawn.
+///
+/// The edge may only cross straight lines at right angles, so that no junction
+/// is drawn where the edge meets other lines.
+pub(crate) fn path_is_clear(
+    canvas: &TextCanvas,
+    obstacles: &[TextRect],
+    edge_path: &EdgePath,
+) -> bool {
+    edge_path
+        .cells
+        .iter()
+        .zip(path_masks(edge_path))
+        .all(|(&(x, y), mask)| {
+            !cell_is_obstacle(obstacles, (x, y))
+                && match canvas.line_mask(x, y) {
+                    Some(0) => true,
+                    Some(mask_existing) => is_crossing(mask_existing, mask),
+                    None => false,
+                }
+        })
+}
+
+/// Draws the edge's line, joining its ends to the boxes it connects.
+pub(crate) fn path_draw(canvas: &mut TextCanvas, edge_path: &EdgePath) {
+    edge_path
+        .cells
+        .iter()
+        .zip(path_masks(edge_path))
+        .for_each(|(&(x, y), mask)| canvas.line_add(x, y, mask));
+}
+
+/// Returns the directions that the edge's line connects in at each cell.
+fn path_masks(edge_path: &EdgePath) -> impl Iterator<Item = u8> + '_ {
+    let cells = &edge_path.cells;
+    (0..cells.len()).map(move |cell_index| {
+        let direction_in = match cell_index.checked_sub(1) {
+            Some(cell_index_previous) => {
+                direction_between(cells[cell_index_previous], cells[cell_index])
+            }
+            None => edge_path.src_direction,
+        };
+        let direction_out = match cells.get(cell_index + 1) {
+            Some(cell_next) => direction_between(cells[cell_index], *cell_next),
+            None => edge_path.target_direction,
+        };
+        direction_opposite(direction_in) | direction_out
+    })
+}
+
+/// Cells that an edge passes through, and the directions it leaves its source
+/// and arrives at its target in.
+#[derive(Clone, Debug)]
+pub(crate) struct EdgePath {
+    pub(crate) cells: Vec<(usize, usize)>,
+    pub(crate) src_direction: u8,
+    pub(crate) target_direction: u8,
+}
+
+/// Returns a path for an edge between the boxes that goes around boxes and
+/// lines that are already drawn, or `None` if there is no such path.
+///
+/// The edge may leave and arrive at any side of the boxes, preferring the
+/// sides facing `direction`, and may cross straight lines at right angles.
+/// Shorter paths with fewer bends are preferred.
+pub(crate) fn route(
+    canvas: &TextCanvas,
+    obstacles: &[TextRect],
+    direction: u8,
+    (src, target): (TextRect, TextRect),
+) -> Option<EdgePath> {
+    let (width, height) = canvas.size();
+    // Ends may be on lines that the edge crosses, such as the border of the
+    // cluster that a box is in.
+    let end_cost = |(x, y): (usize, usize), side: u8| {
+        if cell_is_obstacle(obstacles, (x, y)) {
+            return None;
+        }
+        match canvas.line_mask(x, y)? {
+            0 => Some(0),
+            mask_existing if is_crossing(mask_existing, side | direction_opposite(side)) => {
+                Some(CROSSING_COST)
+            }
+            _ => None,
+        }
+    };
+    let state_index = |(x, y): (usize, usize), heading: u8| {
+        (y * width + x) * DIRECTIONS.len() + heading_index(heading)
+    };
+
+    // Cells next to the target box, and the direction that arrives at the box
+    // from each cell.
+    let target_cells = side_cells(target, (width, height))
+        .filter(|(cell, side)| end_cost(*cell, *side).is_some())
+        .map(|(cell, side)| (cell, direction_opposite(side)))
+        .collect::<HashMap<(usize, usize), u8>>();
+
+    let mut costs = vec![usize::MAX; width * height * DIRECTIONS.len()];
+    let mut previous = vec![None::<((usize, usize), u8)>; costs.len()];
+    let mut queue = BinaryHeap::new();
+    side_cells(src, (width, height))
+        .filter_map(|(cell, side)| Some((cell, side, end_cost(cell, side)?)))
+        .for_each(|(cell, side, end_cost)| {
+            let cost = end_cost + if side == direction { 0 } else { BEND_COST };
+            costs[state_index(cell, side)] = cost;
+            queue.push(Reverse((cost, cell, side)));
+        });
+
+    let mut end = None;
+    while let Some(Reverse((cost, cell, heading))) = queue.pop() {
+        if cost > costs[state_index(cell, heading)] {
+            continue;
+        }
+        if target_cells.get(&cell) == Some(&heading) {
+            end = Some((cell, heading));
+            break;
+        }
+
+        // Edges go straight through lines they cross, and only turn where the
+        // cell is empty.
+        let is_crossing_cell = canvas.line_mask(cell.0, cell.1) != Some(0);
+        DIRECTIONS
+            .into_iter()
+            .filter(|heading_next| {
+                if is_crossing_cell {
+                    *heading_next == heading
+                } else {
+                    *heading_next != direction_opposite(heading)
+                }
+            })
+            .filter_map(|heading_next| {
+                let cell_next = cell_step(cell, heading_next, (width, height))?;
+                if cell_is_obstacle(obstacles, cell_next) {
+                    return None;
+                }
+                let crossing_cost = match canvas.line_mask(cell_next.0, cell_next.1)? {
+                    0 => 0,
+                    mask_existing
+                        if is_crossing(
+                            mask_existing,
+                            heading_next | direction_opposite(heading_next),
+                        ) =>
+                    {
+                        CROSSING_COST
+                    }
+                    _ => return None,
+                };
+                let bend_cost = if heading_next == heading {
+                    0
+                } else {
+                    BEND_COST
+                };
+                Some((
+                    cell_next,
+                    heading_next,
+                    cost + 1 + bend_cost + crossing_cost,
+                ))
+            })
+            .for_each(|(cell_next, heading_next, cost_next)| {
+                let state_next = state_index(cell_next, heading_next);
+                if cost_next < costs[state_next] {
+                    costs[state_next] = cost_next;
+                    previous[state_next] = Some((cell, heading));
+                    queue.push(Reverse((cost_next, cell_next, heading_next)));
+                }
+            });
+    }
+
+    let (end_cell, target_direction) = end?;
+    let mut cells = vec![end_cell];
+    let mut state = (end_cell, target_direction);
+    while let Some(state_previous) = previous[state_index(state.0, state.1)] {
+        cells.push(state_previous.0);
+        state = state_previous;
+    }
+    cells.reverse();
+
+    // The heading of the first cell is the side of the source box it is on.
+    Some(EdgePath {
+        cells,
+        src_direction: state.1,
+        target_direction,
+    })
+}
+
+/// Returns the cells next to each side of the box, excluding the corners, and
+/// the side that each cell is on.
+fn side_cells(
+    rect: TextRect,
+    (width, height): (usize, usize),
+) -> impl Iterator<Item = ((usize, usize), u8)> {
+    let columns = rect.x + 1..rect.right().saturating_sub(1);
+    let rows = rect.y + 1..rect.bottom().saturating_sub(1);
+    let above = rect.y.checked_sub(1);
+    let left = rect.x.checked_sub(1);
+
+    let up = above
+        .into_iter()
+        .flat_map(move |y| columns.clone().map(move |x| ((x, y), UP)));
+    let down =
+        (rect.x + 1..rect.right().saturating_sub(1)).map(move |x| ((x, rect.bottom()), DOWN));
+    let left = left
+        .into_iter()
+        .flat_map(move |x| rows.clone().map(move |y| ((x, y), LEFT)));
+    let right =
+        (rect.y + 1..rect.bottom().saturating_sub(1)).map(move |y| ((rect.right(), y), RIGHT));
+    up.chain(down)
+        .chain(left)
+        .chain(right)
+        .filter(move |((x, y), _)| *x < width && *y < height)
+}
+
+/// Returns whether a line with `mask` crosses the existing straight line at a
+/// right angle.
+fn is_crossing(mask_existing: u8, mask: u8) -> bool {
+    let is_straight = |mask: u8| mask == (UP | DOWN) || mask == (LEFT | RIGHT);
+    is_straight(mask_existing) && is_straight(mask) && mask_existing & mask == 0
+}
+
+fn cell_is_obstacle(obstacles: &[TextRect], (x, y): (usize, usize)) -> bool {
+    obstacles
+        .iter()
+        .any(|rect| (rect.x..rect.right()).contains(&x) && (rect.y..rect.bottom()).contains(&y))
+}
+
+/// Returns the neighbouring cell in the direction, if it is on the canvas.
+fn cell_step(
+    (x, y): (usize, usize),
+    direction: u8,
+    (width, height): (usize, usize),
+) -> Option<(usize, usize)> {
+    let (x, y) = match direction {
+        UP => (x, y.checked_sub(1)?),
+        DOWN => (x, y + 1),
+        LEFT => (x.checked_sub(1)?, y),
+        _ => (x + 1, y),
+    };
+    (x < width && y < height).then_some((x, y))
+}
+
+fn heading_index(heading: u8) -> usize {
+    DIRECTIONS
+        .iter()
+        .position(|direction| *direction == heading)
+        .unwrap_or(0)
+}
@@ -0,0 +1,823 @@
+use std::collections::HashMap;
+
+use dot_ix_model::{
+    common::{graphviz_attrs::EdgeDir, EdgeId, NodeHierarchy, NodeId},
+    info_graph::{GraphDir, InfoGraph},
+};
+
+use crate::{
+    into_text_src::{
+        edge_router::{self, EdgePath},
+        text_canvas::{
+            direction_opposite, text_width, TextCanvas, TextRect, DOWN, LEFT, RIGHT, UP,
+        },
+        TextCharset, TextOptions,
+    },
+    layered_layout::{
+        LayeredLayout, LayoutEdge, LayoutItem, LayoutPadding, LayoutPoint, LayoutRect,
+    },
+    IntoTextSrc,
+};
+
+/// Columns between nodes in the same rank, for vertical graphs.
+const NODESEP_COLUMNS: f64 = 2.0;
+
+/// Rows between nodes in the same rank, for horizontal graphs.
+const NODESEP_ROWS: f64 = 1.0;
+
+/// Rows between ranks, for vertical graphs.
+const RANKSEP_ROWS: f64 = 3.0;
+
+/// Minimum columns between ranks, for horizontal graphs.
+///
+/// This is widened to fit the longest edge label.
+const RANKSEP_COLUMNS_MIN: usize = 6;
+
+/// Space between a cluster's border and its children.
+///
+/// The top has room for the border with the cluster's name, and a blank row.
+const CLUSTER_PADDING: LayoutPadding = LayoutPadding {
+    left: 2.0,
+    right: 2.0,
+    top: 2.0,
+    bottom: 1.0,
+};
+
+/// Draws an `InfoGraph` as text, with Unicode box-drawing or ASCII
+/// characters.
+///
+/// * Nodes are boxes with their emoji and name inside, and nodes with
+///   children are boxes around their children with the name in the border.
+/// * Edges are lines with right angle bends, with arrowheads from the edge
+///   direction, and their description as a label.
+/// * Nodes are laid out in ranks along the [`GraphDir`], using the same
+///   layered layout as `IntoSvgSrc`.
+///
+/// If the diagram is wider than [`TextOptions::width_max`], the hierarchy is
+/// drawn as an indented tree followed by a list of edges, with lines that are
+/// too long cut off.
+///
+/// Edges are routed around nodes and edges drawn before them, and only cross
+/// other edges at right angles, so lines never join to form edges that don't
+/// exist. If an edge can't be routed, the tree and edge list are drawn
+/// instead.
+///
+/// # Limitations
+///
+/// * Edge labels are placed next to the middle of the edge where there is
+///   space, and may overlap other lines when there is not.
+/// * Node descriptions, images, tags, and theme styles are not drawn.
+impl IntoTextSrc for &InfoGraph {
+    fn into(self, text_options: &TextOptions) -> String {
+        diagram_draw(self, text_options).unwrap_or_else(|| tree_draw(self, text_options))
+    }
+}
+
+/// A node to draw, which is a cluster if it has children.
+#[derive(Clone, Debug)]
+struct TextItem {
+    label_lines: Vec<String>,
+    is_cluster: bool,
+}
+
+/// An edge to draw, and the layout edge it corresponds to.
+#[derive(Clone, Copy, Debug)]
+struct TextEdge<'graph> {
+    edge_id: &'graph EdgeId,
+    src_item: usize,
+    target_item: usize,
+}
+
+/// Returns the diagram, or `None` if it is wider than the width limit.
+fn diagram_draw(info_graph: &InfoGraph, text_options: &TextOptions) -> Option<String> {
+    let charset = text_options.charset();
+    let graph_dir = info_graph.direction();
+    let graphviz_attrs = info_graph.graphviz_attrs();
+
+    let mut text_items = Vec::<TextItem>::new();
+    let mut layout_items = Vec::<LayoutItem>::new();
+    let mut node_id_to_item = HashMap::<&str, usize>::new();
+    let roots = info_graph
+        .hierarchy()
+        .iter()
+        .map(|(node_id, node_hierarchy)| {
+            items_push(
+                info_graph,
+                &mut text_items,
+                &mut layout_items,
+                &mut node_id_to_item,
+                node_id,
+                node_hierarchy,
+            )
+        })
+        .collect::<Vec<usize>>();
+
+    let mut layout_edges = Vec::<LayoutEdge>::with_capacity(info_graph.edges().len());
+    let text_edges = info_graph
+        .edges()
+        .iter()
+        .filter_map(|(edge_id, [src_node_id, target_node_id])| {
+            let src_item = *node_id_to_item.get(src_node_id.node_id_plain())?;
+            let target_item = *node_id_to_item.get(target_node_id.node_id_plain())?;
+            layout_edges.push(LayoutEdge {
+                src: src_item,
+                target: target_item,
+                minlen: graphviz_attrs
+                    .edge_minlens()
+                    .get(edge_id)
+                    .copied()
+                    .unwrap_or_else(|| graphviz_attrs.edge_minlen_default()),
+                constraint: graphviz_attrs
+                    .edge_constraints()
+                    .get(edge_id)
+                    .copied()
+                    .unwrap_or_else(|| graphviz_attrs.edge_constraint_default()),
+            });
+            Some(TextEdge {
+                edge_id,
+                src_item,
+                target_item,
+            })
+        })
+        .collect::<Vec<TextEdge<'_>>>();
+
+    let edge_label_width_max = text_edges
+        .iter()
+        .filter_map(|text_edge| info_graph.edge_descs().get(text_edge.edge_id))
+        .map(|edge_desc| text_width(&edge_label(edge_desc)))
+        .max()
+        .unwrap_or(0);
+    let (nodesep, ranksep) = match graph_dir {
+        GraphDir::Vertical => (NODESEP_COLUMNS, RANKSEP_ROWS),
+        GraphDir::Horizontal => (
+            NODESEP_ROWS,
+            (edge_label_width_max + 2).max(RANKSEP_COLUMNS_MIN) as f64,
+        ),
+    };
+    let layout_output = LayeredLayout {
+        graph_dir,
+        nodesep,
+        ranksep,
+        items: layout_items,
+        roots,
+        edges: layout_edges,
+    }
+    .layout();
+
+    // Edge labels may extend past the laid out nodes, so the canvas is widened
+    // for them, and the width limit is checked after drawing. The extra rows
+    // leave room for edges to be routed below the laid out nodes.
+    let width = layout_output.width.ceil() as usize + edge_label_width_max + 3;
+    let height = layout_output.height.ceil() as usize + 3;
+    if layout_output.width.ceil() as usize > text_options.width_max() {
+        return None;
+    }
+    let item_rects = layout_output
+        .item_rects
+        .iter()
+        .map(rect_round)
+        .collect::<Vec<TextRect>>();
+    let mut canvas = TextCanvas::new(width, height);
+
+    // Cluster borders are drawn as lines, so edges that cross them are joined
+    // to them.
+    text_items
+        .iter()
+        .zip(item_rects.iter())
+        .filter(|(text_item, _)| text_item.is_cluster)
+        .for_each(|(_, rect)| canvas.rect_lines_draw(*rect));
+
+    let edge_directions = text_edges
+        .iter()
+        .map(|text_edge| {
+            edge_direction(
+                graph_dir,
+                item_rects[text_edge.src_item],
+                item_rects[text_edge.target_item],
+            )
+        })
+        .collect::<Vec<Option<u8>>>();
+    let edge_ports = edge_ports_assign(&item_rects, &text_edges, &edge_directions);
+    let node_rects = text_items
+        .iter()
+        .zip(item_rects.iter())
+        .filter(|(text_item, _)| !text_item.is_cluster)
+        .map(|(_, rect)| *rect)
+        .collect::<Vec<TextRect>>();
+    // Edges are not drawn through nodes or cluster titles.
+    let obstacles = text_items
+        .iter()
+        .zip(item_rects.iter())
+        .filter(|(text_item, _)| text_item.is_cluster)
+        .map(|(text_item, rect)| TextRect {
+            x: rect.x + 2,
+            y: rect.y,
+            width: text_width(&cluster_title(text_item)),
+            height: 1,
+        })
+        .chain(node_rects.iter().copied())
+        .collect::<Vec<TextRect>>();
+
+    // Each edge is routed around the lines drawn before it, so that edges
+    // don't join other edges. If an edge can't be routed, the tree is drawn
+    // instead.
+    let edge_paths = text_edges
+        .iter()
+        .enumerate()
+        .map(|(edge_index, text_edge)| {
+            let Some(direction) = edge_directions[edge_index] else {
+                return Some(None);
+            };
+            let edge_path = edge_path(
+                graph_dir,
+                direction,
+                (
+                    item_rects[text_edge.src_item],
+                    item_rects[text_edge.target_item],
+                ),
+                edge_ports[edge_index],
+                &layout_output.edge_waypoints[edge_index],
+                &node_rects,
+            );
+            let edge_path = if edge_router::path_is_clear(&canvas, &obstacles, &edge_path) {
+                edge_path
+            } else {
+                edge_router::route(
+                    &canvas,
+                    &obstacles,
+                    direction,
+                    (
+                        item_rects[text_edge.src_item],
+                        item_rects[text_edge.target_item],
+                    ),
+                )?
+            };
+            edge_router::path_draw(&mut canvas, &edge_path);
+            Some(Some(edge_path))
+        })
+        .collect::<Option<Vec<Option<EdgePath>>>>()?;
+
+    // Nodes are drawn over edges that pass through them.
+    text_items
+        .iter()
+        .zip(item_rects.iter())
+        .filter(|(text_item, _)| !text_item.is_cluster)
+        .for_each(|(text_item, rect)| {
+            canvas.rect_draw(*rect, charset, charset.node_corners());
+            text_item
+                .label_lines
+                .iter()
+                .enumerate()
+                .for_each(|(line_index, line)| {
+                    let x = rect.x + rect.width.saturating_sub(text_width(line)) / 2;
+                    canvas.text_write(x, rect.y + 1 + line_index, line);
+                });
+        });
+
+    text_edges
+        .iter()
+        .zip(edge_paths.iter())
+        .filter_map(|(text_edge, edge_path)| Some((text_edge, edge_path.as_ref()?)))
+        .for_each(|(text_edge, edge_path)| {
+            let edge_dir = graphviz_attrs
+                .edge_dirs()
+                .get(text_edge.edge_id)
+                .copied()
+                .unwrap_or_else(|| graphviz_attrs.edge_dir_default());
+            let (arrow_at_src, arrow_at_target) = match edge_dir {
+                EdgeDir::Forward => (false, true),
+                EdgeDir::Back => (true, false),
+                EdgeDir::Both => (true, true),
+                EdgeDir::None => (false, false),
+            };
+            let (Some((src_x, src_y)), Some((target_x, target_y))) =
+                (edge_path.cells.first(), edge_path.cells.last())
+            else {
+                return;
+            };
+            if arrow_at_target {
+                canvas.char_write(
+                    *target_x,
+                    *target_y,
+                    charset.arrow(edge_path.target_direction),
+                );
+            }
+            if arrow_at_src {
+                let arrow = charset.arrow(direction_opposite(edge_path.src_direction));
+                canvas.char_write(*src_x, *src_y, arrow);
+            }
+        });
+
+    text_edges
+        .iter()
+        .zip(edge_paths.iter())
+        .for_each(|(text_edge, edge_path)| {
+            if let (Some(edge_desc), Some(edge_path)) =
+                (info_graph.edge_descs().get(text_edge.edge_id), edge_path)
+            {
+                edge_label_write(&mut canvas, graph_dir, edge_path, &edge_label(edge_desc));
+            }
+        });
+
+    text_items
+        .iter()
+        .zip(item_rects.iter())
+        .filter(|(text_item, _)| text_item.is_cluster)
+        .for_each(|(text_item, rect)| {
+            canvas.text_write(rect.x + 2, rect.y, &cluster_title(text_item));
+        });
+
+    let diagram = canvas.render(charset);
+    diagram
+        .lines()
+        .all(|line| text_width(line) <= text_options.width_max())
+        .then_some(diagram)
+}
+
+/// Returns the title written in a cluster's border.
+fn cluster_title(text_item: &TextItem) -> String {
+    format!(" {} ", text_item.label_lines.join(" "))
+}
+
+/// Adds the layout items for the node and its children, and returns the
+/// node's item index.
+fn items_push<'graph>(
+    info_graph: &'graph InfoGraph,
+    text_items: &mut Vec<TextItem>,
+    layout_items: &mut Vec<LayoutItem>,
+    node_id_to_item: &mut HashMap<&'graph str, usize>,
+    node_id: &'graph NodeId,
+    node_hierarchy: &'graph NodeHierarchy,
+) -> usize {
+    let label_lines = node_label_lines(info_graph, node_id);
+    let is_cluster = !node_hierarchy.is_empty();
+    let layout_item = if is_cluster {
+        // `┌─ title ─┐`
+        let title_width = label_lines
+            .iter()
+            .map(|line| text_width(line))
+            .sum::<usize>()
+            + label_lines.len().saturating_sub(1);
+        LayoutItem {
+            width: (title_width + 6) as f64,
+            height: 0.0,
+            padding: CLUSTER_PADDING,
+            children: Vec::new(),
+        }
+    } else {
+        let label_width = label_lines
+            .iter()
+            .map(|line| text_width(line))
+            .max()
+            .unwrap_or(0);
+        LayoutItem {
+            width: (label_width + 4) as f64,
+            height: (label_lines.len() + 2) as f64,
+            ..LayoutItem::default()
+        }
+    };
+
+    let item_index = text_items.len();
+    text_items.push(TextItem {
+        label_lines,
+        is_cluster,
+    });
+    layout_items.push(layout_item);
+    node_id_to_item.insert(node_id.as_str(), item_index);
+
+    let children = node_hierarchy
+        .iter()
+        .map(|(child_node_id, child_hierarchy)| {
+            items_push(
+                info_graph,
+                text_items,
+                layout_items,
+                node_id_to_item,
+                child_node_id,
+                child_hierarchy,
+            )
+        })
+        .collect::<Vec<usize>>();
+    layout_items[item_index].children = children;
+
+    item_index
+}
+
+/// Returns the side of the source box that the edge leaves from, or `None`
+/// if there is no space between the boxes, such as when one box is within the
+/// other.
+///
+/// Sides along the [`GraphDir`] are preferred.
+fn edge_direction(graph_dir: GraphDir, src: TextRect, target: TextRect) -> Option<u8> {
+    let directions = match graph_dir {
+        GraphDir::Vertical => [DOWN, UP, RIGHT, LEFT],
+        GraphDir::Horizontal => [RIGHT, LEFT, DOWN, UP],
+    };
+    directions.into_iter().find(|direction| match *direction {
+        DOWN => target.y > src.bottom(),
+        UP => src.y > target.bottom(),
+        RIGHT => target.x > src.right(),
+        _ => src.x > target.right(),
+    })
+}
+
+/// Returns where each edge meets its source and target boxes, as a column for
+/// the top and bottom sides, or a row for the left and right sides.
+///
+/// Edges that meet the same side of a box are spread out along it, ordered by
+/// the position of the box at their other end. When an edge is the only edge
+/// on both sides and the sides overlap, the edge is drawn straight.
+fn edge_ports_assign(
+    item_rects: &[TextRect],
+    text_edges: &[TextEdge<'_>],
+    edge_directions: &[Option<u8>],
+) -> Vec<(usize, usize)> {
+    // Range of columns or rows that edges may meet the side at.
+    let side_range = |rect: TextRect, side: u8| {
+        let (start, len) = if matches!(side, UP | DOWN) {
+            (rect.x, rect.width)
+        } else {
+            (rect.y, rect.height)
+        };
+        (start + 1, start + len.saturating_sub(2).max(1))
+    };
+    let along = |rect: TextRect, side: u8| {
+        if matches!(side, UP | DOWN) {
+            rect.center_x()
+        } else {
+            rect.center_y()
+        }
+    };
+
+    // Edges that meet each side of each box, and whether the box is the edge's
+    // source.
+    let mut side_to_edges = HashMap::<(usize, u8), Vec<(usize, bool, usize)>>::new();
+    text_edges
+        .iter()
+        .zip(edge_directions.iter())
+        .enumerate()
+        .filter_map(|(edge_index, (text_edge, direction))| {
+            Some((edge_index, text_edge, (*direction)?))
+        })
+        .for_each(|(edge_index, text_edge, direction)| {
+            let src_rect = item_rects[text_edge.src_item];
+            let target_rect = item_rects[text_edge.target_item];
+            side_to_edges
+                .entry((text_edge.src_item, direction))
+                .or_default()
+                .push((edge_index, true, along(target_rect, direction)));
+            side_to_edges
+                .entry((text_edge.target_item, direction_opposite(direction)))
+                .or_default()
+                .push((edge_index, false, along(src_rect, direction)));
+        });
+
+    let mut edge_ports = vec![(0, 0); text_edges.len()];
+    side_to_edges
+        .iter_mut()
+        .for_each(|((item_index, side), side_edges)| {
+            side_edges.sort_by_key(|(edge_index, _, other_along)| (*other_along, *edge_index));
+            let (range_start, range_end) = side_range(item_rects[*item_index], *side);
+            let span = range_end + 1 - range_start;
+            let count = side_edges.len();
+            side_edges
+                .iter()
+                .enumerate()
+                .for_each(|(position, (edge_index, is_src, _))| {
+                    let port = range_start + (2 * position + 1) * span / (2 * count);
+                    if *is_src {
+                        edge_ports[*edge_index].0 = port;
+                    } else {
+                        edge_ports[*edge_index].1 = port;
+                    }
+                });
+        });
+
+    text_edges
+        .iter()
+        .zip(edge_directions.iter())
+        .zip(edge_ports.iter_mut())
+        .filter_map(|((text_edge, direction), edge_port)| {
+            Some((text_edge, (*direction)?, edge_port))
+        })
+        .for_each(|(text_edge, direction, edge_port)| {
+            let side_edge_count = |item_index: usize, side: u8| {
+                side_to_edges.get(&(item_index, side)).map_or(0, Vec::len)
+            };
+            if side_edge_count(text_edge.src_item, direction) != 1
+                || side_edge_count(text_edge.target_item, direction_opposite(direction)) != 1
+            {
+                return;
+            }
+            let (src_start, src_end) = side_range(item_rects[text_edge.src_item], direction);
+            let (target_start, target_end) = side_range(
+                item_rects[text_edge.target_item],
+                direction_opposite(direction),
+            );
+            let (overlap_start, overlap_end) =
+                (src_start.max(target_start), src_end.min(target_end));
+            if overlap_start <= overlap_end {
+                let port = (overlap_start + overlap_end) / 2;
+                *edge_port = (port, port);
+            }
+        });
+
+    edge_ports
+}
+
+/// Returns the cells for an edge that leaves the source box in the given
+/// direction, and meets the boxes at the given ports.
+///
+/// The edge bends at right angles half way between waypoints. When the ports
+/// line up and no node is in the way, the edge is drawn straight.
+fn edge_path(
+    graph_dir: GraphDir,
+    direction: u8,
+    (src, target): (TextRect, TextRect),
+    (src_port, target_port): (usize, usize),
+    waypoints: &[LayoutPoint],
+    node_rects: &[TextRect],
+) -> EdgePath {
+    let (start, end) = match direction {
+        DOWN => ((src_port, src.bottom()), (target_port, target.y - 1)),
+        UP => ((src_port, src.y - 1), (target_port, target.bottom())),
+        RIGHT => ((src.right(), src_port), (target.x - 1, target_port)),
+        _ => ((src.x - 1, src_port), (target.right(), target_port)),
+    };
+    let is_vertical = matches!(direction, UP | DOWN);
+    let primary = |(x, y): (usize, usize)| if is_vertical { y } else { x };
+
+    // Waypoints are between ranks, so they are only used when the edge goes
+    // across ranks.
+    let is_straight_clear = src_port == target_port && {
+        let (x_min, x_max) = (start.0.min(end.0), start.0.max(end.0));
+        let (y_min, y_max) = (start.1.min(end.1), start.1.max(end.1));
+        !node_rects.iter().any(|rect| {
+            x_min < rect.right() && rect.x <= x_max && y_min < rect.bottom() && rect.y <= y_max
+        })
+    };
+    let mut points = vec![start];
+    if !is_straight_clear && is_vertical == (graph_dir == GraphDir::Vertical) {
+        let (primary_min, primary_max) = {
+            let (a, b) = (primary(start), primary(end));
+            (a.min(b), a.max(b))
+        };
+        let mut waypoints = waypoints
+            .iter()
+            .map(|waypoint| {
+                (
+                    waypoint.x.max(0.0).round() as usize,
+                    waypoint.y.max(0.0).round() as usize,
+                )
+            })
+            .filter(|waypoint| (primary_min + 1..primary_max).contains(&primary(*waypoint)))
+            .collect::<Vec<(usize, usize)>>();
+        waypoints.sort_by_key(|waypoint| primary(*waypoint));
+        if matches!(direction, UP | LEFT) {
+            waypoints.reverse();
+        }
+        points.extend(waypoints);
+    }
+    points.push(end);
+
+    let mut corners = Vec::with_capacity(points.len() * 3);
+    points.windows(2).for_each(|point_pair| {
+        let [(x_a, y_a), (x_b, y_b)] = [point_pair[0], point_pair[1]];
+        corners.push((x_a, y_a));
+        if is_vertical && x_a != x_b {
+            let y_mid = (y_a + y_b) / 2;
+            corners.extend([(x_a, y_mid), (x_b, y_mid)]);
+        } else if !is_vertical && y_a != y_b {
+            let x_mid = (x_a + x_b) / 2;
+            corners.extend([(x_mid, y_a), (x_mid, y_b)]);
+        }
+    });
+    corners.push(end);
+
+    let mut cells = vec![start];
+    corners.iter().skip(1).for_each(|&(x_corner, y_corner)| {
+        while let Some(&(x, y)) = cells.last()
+            && (x, y) != (x_corner, y_corner)
+        {
+            let cell = if x != x_corner {
+                (if x < x_corner { x + 1 } else { x - 1 }, y)
+            } else {
+                (x, if y < y_corner { y + 1 } else { y - 1 })
+            };
+            cells.push(cell);
+        }
+    });
+
+    EdgePath {
+        cells,
+        src_direction: direction,
+        target_direction: direction,
+    }
+}
+
+/// Writes the label next to the middle of the edge, where it does not cover
+/// anything.
+///
+/// If there is no space, the label is written next to the middle of the edge
+/// anyway.
+fn edge_label_write(
+    canvas: &mut TextCanvas,
+    graph_dir: GraphDir,
+    edge_path: &EdgePath,
+    label: &str,
+) {
+    let label_width = text_width(label);
+    let candidates = |(x, y): (usize, usize)| {
+        let beside = [
+            Some((x + 2, y)),
+            x.checked_sub(label_width + 1).map(|x| (x, y)),
+        ];
+        let x_centered = x.saturating_sub(label_width / 2);
+        let around = [
+            y.checked_sub(1).map(|y| (x_centered, y)),
+            Some((x_centered, y + 1)),
+        ];
+        match graph_dir {
+            GraphDir::Vertical => [beside, around],
+            GraphDir::Horizontal => [around, beside],
+        }
+        .into_iter()
+        .flatten()
+        .flatten()
+    };
+
+    // Cells from the middle of the edge outwards.
+    let middle = edge_path.cells.len() / 2;
+    let cells_from_middle = (0..edge_path.cells.len()).map(|offset| {
+        let index = if offset % 2 == 0 {
+            middle + offset / 2
+        } else {
+            middle.wrapping_sub(offset / 2 + 1)
+        };
+        edge_path.cells.get(index).copied()
+    });
+    let position = cells_from_middle
+        .flatten()
+        .flat_map(&candidates)
+        .find(|(x, y)| canvas.text_fits(*x, *y, label))
+        .or_else(|| {
+            edge_path
+                .cells
+                .get(middle)
+                .and_then(|cell| candidates(*cell).next())
+        });
+    if let Some((x, y)) = position {
+        canvas.text_write(x, y, label);
+    }
+}
+
+/// Draws the hierarchy as an indented tree, followed by a list of edges.
+fn tree_draw(info_graph: &InfoGraph, text_options: &TextOptions) -> String {
+    let charset = text_options.charset();
+    let mut lines = Vec::<String>::new();
+    info_graph
+        .hierarchy()
+        .iter()
+        .for_each(|(node_id, node_hierarchy)| {
+            lines.push(node_label(info_graph, node_id));
+            tree_lines_push(&mut lines, info_graph, charset, node_hierarchy, "");
+        });
+
+    if !info_graph.edges().is_empty() {
+        lines.push(String::new());
+    }
+    let graphviz_attrs = info_graph.graphviz_attrs();
+    let horizontal = charset.horizontal();
+    let node_id_plain_to_node_id = info_graph
+        .hierarchy_flat()
+        .into_keys()
+        .map(|node_id| (node_id.as_str(), node_id))
+        .collect::<HashMap<&str, &NodeId>>();
+    // Edges may refer to a node's port, e.g. `a:n`.
+    let edge_node_label = |node_id: &NodeId| {
+        let node_id_plain = node_id.node_id_plain();
+        node_id_plain_to_node_id
+            .get(node_id_plain)
+            .map(|node_id| node_label(info_graph, node_id))
+            .unwrap_or_else(|| node_id_plain.to_string())
+    };
+    info_graph
+        .edges()
+        .iter()
+        .for_each(|(edge_id, [src_node_id, target_node_id])| {
+            let edge_dir = graphviz_attrs
+                .edge_dirs()
+                .get(edge_id)
+                .copied()
+                .unwrap_or_else(|| graphviz_attrs.edge_dir_default());
+            let arrow_src = match edge_dir {
+                EdgeDir::Back | EdgeDir::Both => charset.arrow(LEFT),
+                EdgeDir::Forward | EdgeDir::None => horizontal,
+            };
+            let arrow_target = match edge_dir {
+                EdgeDir::Forward | EdgeDir::Both => charset.arrow(RIGHT),
+                EdgeDir::Back | EdgeDir::None => horizontal,
+            };
+            let mut line = format!(
+                "{src} {arrow_src}{horizontal}{arrow_target} {target}",
+                src = edge_node_label(src_node_id),
+                target = edge_node_label(target_node_id),
+            );
+            if let Some(edge_desc) = info_graph.edge_descs().get(edge_id) {
+                line.push_str(": ");
+                line.push_str(&edge_label(edge_desc));
+            }
+            lines.push(line);
+        });
+
+    let mut tree = String::with_capacity(lines.iter().map(|line| line.len() + 1).sum());
+    lines.iter().for_each(|line| {
+        tree.push_str(&line_truncate(line, text_options.width_max(), charset));
+        tree.push('\n');
+    });
+    tree
+}
+
+fn tree_lines_push(
+    lines: &mut Vec<String>,
+    info_graph: &InfoGraph,
+    charset: TextCharset,
+    node_hierarchy: &NodeHierarchy,
+    prefix: &str,
+) {
+    let child_count = node_hierarchy.len();
+    node_hierarchy
+        .iter()
+        .enumerate()
+        .for_each(|(child_index, (node_id, child_hierarchy))| {
+            let (branch, indent) = charset.tree_branch(child_index + 1 == child_count);
+            lines.push(format!(
+                "{prefix}{branch}{label}",
+                label = node_label(info_graph, node_id)
+            ));
+            tree_lines_push(
+                lines,
+                info_graph,
+                charset,
+                child_hierarchy,
+                &format!("{prefix}{indent}"),
+            );
+        });
+}
+
+/// Returns the node's emoji and name, with one entry per line of the name.
+fn node_label_lines(info_graph: &InfoGraph, node_id: &NodeId) -> Vec<String> {
+    let name = info_graph
+        .node_names()
+        .get(node_id)
+        .map(String::as_str)
+        .unwrap_or(node_id.as_str());
+    let mut lines = name.lines().map(String::from).collect::<Vec<String>>();
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    if let Some(emoji) = info_graph.node_emojis().get(node_id) {
+        lines[0] = format!("{emoji} {}", lines[0]);
+    }
+    lines
+}
+
+/// Returns the node's emoji and name on one line.
+fn node_label(info_graph: &InfoGraph, node_id: &NodeId) -> String {
+    node_label_lines(info_graph, node_id).join(" ")
+}
+
+/// Returns the edge description on one line.
+fn edge_label(edge_desc: &str) -> String {
+    edge_desc.lines().collect::<Vec<&str>>().join(" ")
+}
+
+/// Returns the line cut off to the width limit, with an ellipsis.
+fn line_truncate(line: &str, width_max: usize, charset: TextCharset) -> String {
+    if text_width(line) <= width_max {
+        return line.to_string();
+    }
+
+    let ellipsis = charset.ellipsis();
+    let width_kept = width_max.saturating_sub(text_width(ellipsis));
+    let mut width = 0;
+    let mut line_truncated = line
+        .chars()
+        .take_while(|c| {
+            width += text_width(c.encode_utf8(&mut [0; 4]));
+            width <= width_kept
+        })
+        .collect::<String>();
+    line_truncated.push_str(ellipsis);
+    line_truncated
+}
+
+/// Returns the rectangle in whole columns and rows.
+fn rect_round(rect: &LayoutRect) -> TextRect {
+    TextRect {
+        x: rect.x.max(0.0).round() as usize,
+        y: rect.y.max(0.0).round() as usize,
+        width: rect.width.round() as usize,
+        height: rect.height.round() as usize,
+    }
+}
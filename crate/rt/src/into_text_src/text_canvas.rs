@@ -0,0 +1,278 @@
+use crate::into_text_src::TextCharset;
+
+/// A line in the cell connects to the cell above.
+pub(crate) const UP: u8 = 0b0001;
+/// A line in the cell connects to the cell below.
+pub(crate) const DOWN: u8 = 0b0010;
+/// A line in the cell connects to the cell on the left.
+pub(crate) const LEFT: u8 = 0b0100;
+/// A line in the cell connects to the cell on the right.
+pub(crate) const RIGHT: u8 = 0b1000;
+
+/// A character cell in a [`TextCanvas`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    /// A character, and a joiner or variation selector that follows it, such
+    /// as the one that makes `🗄️` an emoji.
+    Char(char, Option<char>),
+    /// The second column of a double width character.
+    WideTail,
+    /// Lines that connect to neighbouring cells, so that crossing lines are
+    /// drawn as junctions.
+    Line(u8),
+}
+
+/// Position and size of a box on a [`TextCanvas`], in columns and rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TextRect {
+    pub(crate) x: usize,
+    pub(crate) y: usize,
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+}
+
+impl TextRect {
+    pub(crate) fn right(&self) -> usize {
+        self.x + self.width
+    }
+
+    pub(crate) fn bottom(&self) -> usize {
+        self.y + self.height
+    }
+
+    pub(crate) fn center_x(&self) -> usize {
+        self.x + self.width / 2
+    }
+
+    pub(crate) fn center_y(&self) -> usize {
+        self.y + self.height / 2
+    }
+}
+
+/// Grid of character cells that a text diagram is drawn on.
+#[derive(Clone, Debug)]
+pub(crate) struct TextCanvas {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl TextCanvas {
+    pub(crate) fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::Empty; width * height],
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Writes the text from the given cell, and returns the number of columns
+    /// written.
+    ///
+    /// Text beyond the right edge of the canvas is not written.
+    pub(crate) fn text_write(&mut self, x: usize, y: usize, text: &str) -> usize {
+        let mut column = x;
+        let mut index_last = None;
+        text.chars().for_each(|c| {
+            let width = char_width(c);
+            if width == 0 {
+                if let Some(Cell::Char(_, mark @ None)) =
+                    index_last.map(|index: usize| &mut self.cells[index])
+                {
+                    *mark = Some(c);
+                }
+                return;
+            }
+            if column + width > self.width {
+                return;
+            }
+            if let Some(index) = self.index(column, y) {
+                self.cells[index] = Cell::Char(c, None);
+                if width == 2 {
+                    self.cells[index + 1] = Cell::WideTail;
+                }
+                index_last = Some(index);
+            }
+            column += width;
+        });
+        column - x
+    }
+
+    /// Writes the character to the cell.
+    pub(crate) fn char_write(&mut self, x: usize, y: usize, c: char) {
+        self.text_write(x, y, c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Returns whether the text can be written from the given cell without
+    /// covering anything.
+    pub(crate) fn text_fits(&self, x: usize, y: usize, text: &str) -> bool {
+        (x..x + text_width(text)).all(|column| {
+            self.index(column, y)
+                .is_some_and(|index| self.cells[index] == Cell::Empty)
+        })
+    }
+
+    /// Returns the number of columns and rows in the canvas.
+    pub(crate) fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns the directions that lines in the cell connect in, `0` if the
+    /// cell is empty, or `None` if the cell has text or is outside the canvas.
+    pub(crate) fn line_mask(&self, x: usize, y: usize) -> Option<u8> {
+        match self.cells[self.index(x, y)?] {
+            Cell::Empty => Some(0),
+            Cell::Line(mask) => Some(mask),
+            Cell::Char(..) | Cell::WideTail => None,
+        }
+    }
+
+    /// Adds lines to the cell that connect in the directions in `mask`.
+    pub(crate) fn line_add(&mut self, x: usize, y: usize, mask: u8) {
+        if let Some(index) = self.index(x, y) {
+            self.cells[index] = match self.cells[index] {
+                Cell::Line(mask_existing) => Cell::Line(mask_existing | mask),
+                _ => Cell::Line(mask),
+            };
+        }
+    }
+
+    /// Draws the border of a box with lines, so that lines that cross it are
+    /// drawn as junctions.
+    pub(crate) fn rect_lines_draw(&mut self, rect: TextRect) {
+        if rect.width < 2 || rect.height < 2 {
+            return;
+        }
+        let (right, bottom) = (rect.right() - 1, rect.bottom() - 1);
+        (rect.x..=right).for_each(|x| {
+            let mask_x = if x == rect.x {
+                RIGHT
+            } else if x == right {
+                LEFT
+            } else {
+                LEFT | RIGHT
+            };
+            self.line_add(
+                x,
+                rect.y,
+                mask_x | if x == rect.x || x == right { DOWN } else { 0 },
+            );
+            self.line_add(
+                x,
+                bottom,
+                mask_x | if x == rect.x || x == right { UP } else { 0 },
+            );
+        });
+        (rect.y + 1..bottom).for_each(|y| {
+            self.line_add(rect.x, y, UP | DOWN);
+            self.line_add(right, y, UP | DOWN);
+        });
+    }
+
+    /// Clears the box and draws its border with the given corners.
+    pub(crate) fn rect_draw(&mut self, rect: TextRect, charset: TextCharset, corners: [char; 4]) {
+        if rect.width < 2 || rect.height < 2 {
+            return;
+        }
+        let (right, bottom) = (rect.right() - 1, rect.bottom() - 1);
+        let horizontal = charset.line(LEFT | RIGHT);
+        let vertical = charset.line(UP | DOWN);
+        (rect.y..=bottom).for_each(|y| {
+            (rect.x..=right).for_each(|x| {
+                let cell = match (x, y) {
+                    (x, y) if x == rect.x && y == rect.y => Cell::Char(corners[0], None),
+                    (x, y) if x == right && y == rect.y => Cell::Char(corners[1], None),
+                    (x, y) if x == rect.x && y == bottom => Cell::Char(corners[2], None),
+                    (x, y) if x == right && y == bottom => Cell::Char(corners[3], None),
+                    (_, y) if y == rect.y || y == bottom => Cell::Char(horizontal, None),
+                    (x, _) if x == rect.x || x == right => Cell::Char(vertical, None),
+                    _ => Cell::Empty,
+                };
+                if let Some(index) = self.index(x, y) {
+                    self.cells[index] = cell;
+                }
+            });
+        });
+    }
+
+    /// Returns the canvas as lines of text, without trailing spaces or empty
+    /// lines.
+    pub(crate) fn render(&self, charset: TextCharset) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        self.cells.chunks(self.width.max(1)).for_each(|row| {
+            let line_start = text.len();
+            row.iter().for_each(|cell| match cell {
+                Cell::Empty => text.push(' '),
+                Cell::Char(c, mark) => {
+                    text.push(*c);
+                    text.extend(*mark);
+                }
+                Cell::WideTail => {}
+                Cell::Line(mask) => text.push(charset.line(*mask)),
+            });
+            let line_len = text[line_start..].trim_end().len();
+            text.truncate(line_start + line_len);
+            text.push('\n');
+        });
+        let text_len = text.trim_end().len();
+        text.truncate(text_len);
+        text.push('\n');
+        text
+    }
+}
+
+/// Returns the direction from one cell to the next, which must be in the same
+/// row or column.
+pub(crate) fn direction_between((x_a, y_a): (usize, usize), (x_b, y_b): (usize, usize)) -> u8 {
+    if y_b < y_a {
+        UP
+    } else if y_b > y_a {
+        DOWN
+    } else if x_b < x_a {
+        LEFT
+    } else {
+        RIGHT
+    }
+}
+
+pub(crate) fn direction_opposite(direction: u8) -> u8 {
+    match direction {
+        UP => DOWN,
+        DOWN => UP,
+        LEFT => RIGHT,
+        _ => LEFT,
+    }
+}
+
+/// Returns the number of columns the text takes up in a terminal.
+pub(crate) fn text_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Returns the number of columns the character takes up in a terminal.
+///
+/// Emojis and East Asian wide characters take up two columns, and joiners,
+/// variation selectors, and combining marks take up none.
+fn char_width(c: char) -> usize {
+    match u32::from(c) {
+        0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F900..=0x1F9FF
+        | 0x1FA70..=0x1FAFF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
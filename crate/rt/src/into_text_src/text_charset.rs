@@ -0,0 +1,109 @@
+use std::{fmt, fmt::Display};
+
+/// Characters used to draw text diagrams.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextCharset {
+    /// Unicode box-drawing characters and arrows, e.g. `┌─┐` and `▶`.
+    #[default]
+    Unicode,
+    /// ASCII characters, e.g. `+-+` and `>`, for terminals and fonts without
+    /// box-drawing characters.
+    Ascii,
+}
+
+impl TextCharset {
+    /// Returns the character for a line cell that connects in the directions
+    /// in `mask`.
+    ///
+    /// See the direction constants in `text_canvas`.
+    pub(crate) fn line(self, mask: u8) -> char {
+        use super::text_canvas::{DOWN, LEFT, RIGHT, UP};
+
+        let vertical = mask & (UP | DOWN);
+        let horizontal = mask & (LEFT | RIGHT);
+        match self {
+            TextCharset::Unicode => match (vertical, horizontal) {
+                (0, 0) => ' ',
+                (_, 0) => '│',
+                (0, _) => '─',
+                (DOWN, RIGHT) => '┌',
+                (DOWN, LEFT) => '┐',
+                (UP, RIGHT) => '└',
+                (UP, LEFT) => '┘',
+                (_, RIGHT) => '├',
+                (_, LEFT) => '┤',
+                (DOWN, _) => '┬',
+                (UP, _) => '┴',
+                _ => '┼',
+            },
+            TextCharset::Ascii => match (vertical, horizontal) {
+                (0, 0) => ' ',
+                (_, 0) => '|',
+                (0, _) => '-',
+                _ => '+',
+            },
+        }
+    }
+
+    /// Returns the top left, top right, bottom left, and bottom right corners
+    /// of a node's box.
+    pub(crate) fn node_corners(self) -> [char; 4] {
+        match self {
+            TextCharset::Unicode => ['╭', '╮', '╰', '╯'],
+            TextCharset::Ascii => ['+', '+', '+', '+'],
+        }
+    }
+
+    /// Returns the arrowhead that points in the direction.
+    pub(crate) fn arrow(self, direction: u8) -> char {
+        use super::text_canvas::{DOWN, LEFT, UP};
+
+        match (self, direction) {
+            (TextCharset::Unicode, UP) => '▲',
+            (TextCharset::Unicode, DOWN) => '▼',
+            (TextCharset::Unicode, LEFT) => '◀',
+            (TextCharset::Unicode, _) => '▶',
+            (TextCharset::Ascii, UP) => '^',
+            (TextCharset::Ascii, DOWN) => 'v',
+            (TextCharset::Ascii, LEFT) => '<',
+            (TextCharset::Ascii, _) => '>',
+        }
+    }
+
+    /// Returns the prefix for an item in a tree, and the prefix for its
+    /// children.
+    pub(crate) fn tree_branch(self, is_last: bool) -> (&'static str, &'static str) {
+        match (self, is_last) {
+            (TextCharset::Unicode, false) => ("├── ", "│   "),
+            (TextCharset::Unicode, true) => ("└── ", "    "),
+            (TextCharset::Ascii, false) => ("|-- ", "|   "),
+            (TextCharset::Ascii, true) => ("`-- ", "    "),
+        }
+    }
+
+    /// Returns the horizontal line used between two node names in an edge
+    /// list.
+    pub(crate) fn horizontal(self) -> char {
+        match self {
+            TextCharset::Unicode => '─',
+            TextCharset::Ascii => '-',
+        }
+    }
+
+    /// Returns the character that replaces text cut off at the width limit.
+    pub(crate) fn ellipsis(self) -> &'static str {
+        match self {
+            TextCharset::Unicode => "…",
+            TextCharset::Ascii => "...",
+        }
+    }
+}
+
+impl Display for TextCharset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextCharset::Unicode => "unicode".fmt(f),
+            TextCharset::Ascii => "ascii".fmt(f),
+        }
+    }
+}
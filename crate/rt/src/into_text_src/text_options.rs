@@ -0,0 +1,52 @@
+use crate::into_text_src::TextCharset;
+
+/// Options for drawing an `InfoGraph` as text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextOptions {
+    /// Characters to draw with.
+    charset: TextCharset,
+    /// Maximum number of columns in each line.
+    ///
+    /// If the diagram is wider than this, the hierarchy is drawn as an
+    /// indented tree followed by a list of edges.
+    width_max: usize,
+}
+
+impl TextOptions {
+    /// Returns `TextOptions` with Unicode characters and a width limit of
+    /// `100` columns.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the characters to draw with.
+    pub fn with_charset(mut self, charset: TextCharset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Sets the maximum number of columns in each line.
+    pub fn with_width_max(mut self, width_max: usize) -> Self {
+        self.width_max = width_max;
+        self
+    }
+
+    /// Returns the characters to draw with.
+    pub fn charset(&self) -> TextCharset {
+        self.charset
+    }
+
+    /// Returns the maximum number of columns in each line.
+    pub fn width_max(&self) -> usize {
+        self.width_max
+    }
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            charset: TextCharset::default(),
+            width_max: 100,
+        }
+    }
+}
@@ -0,0 +1,850 @@
+use std::collections::HashMap;
+
+use dot_ix_model::info_graph::GraphDir;
+
+/// Number of sweeps to reduce edge crossings between ranks.
+const ORDER_ITERATIONS: usize = 24;
+
+/// Number of passes to align nodes with their neighbours across ranks.
+const COORD_ITERATIONS: usize = 8;
+
+/// Weight of a node's current position when it has no neighbours in the rank
+/// being aligned with.
+const COORD_WEIGHT_UNCONNECTED: f64 = 0.05;
+
+/// Layered (Sugiyama style) layout of items that may contain other items.
+///
+/// Each cluster is laid out on its own, and then treated as a single item in
+/// its parent's layout. Edges between items in different clusters are laid out
+/// between the outermost clusters that contain them.
+///
+/// All sizes and positions are in points.
+#[derive(Clone, Debug)]
+pub(crate) struct LayeredLayout {
+    /// Whether ranks go from left to right, or top to bottom.
+    pub(crate) graph_dir: GraphDir,
+    /// Space between items in the same rank.
+    pub(crate) nodesep: f64,
+    /// Space between ranks.
+    pub(crate) ranksep: f64,
+    /// All items, including the children of clusters.
+    pub(crate) items: Vec<LayoutItem>,
+    /// Indices of the items that are not within a cluster.
+    pub(crate) roots: Vec<usize>,
+    /// Edges between items.
+    pub(crate) edges: Vec<LayoutEdge>,
+}
+
+/// A node or cluster to lay out.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LayoutItem {
+    /// Minimum width of the item.
+    pub(crate) width: f64,
+    /// Minimum height of the item.
+    pub(crate) height: f64,
+    /// Space between the cluster's border and its children.
+    pub(crate) padding: LayoutPadding,
+    /// Indices of the items within this cluster.
+    pub(crate) children: Vec<usize>,
+}
+
+/// Space between a cluster's border and its children.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct LayoutPadding {
+    pub(crate) left: f64,
+    pub(crate) right: f64,
+    pub(crate) top: f64,
+    pub(crate) bottom: f64,
+}
+
+/// An edge between two items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct LayoutEdge {
+    /// Index of the source item.
+    pub(crate) src: usize,
+    /// Index of the target item.
+    pub(crate) target: usize,
+    /// Minimum number of ranks between the source and target.
+    pub(crate) minlen: u32,
+    /// Whether the edge is used to rank the items.
+    pub(crate) constraint: bool,
+}
+
+/// Position of a point.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct LayoutPoint {
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+}
+
+/// Position and size of a laid out item.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct LayoutRect {
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+    pub(crate) width: f64,
+    pub(crate) height: f64,
+}
+
+impl LayoutRect {
+    /// Returns the centre of the rectangle.
+    #[cfg_attr(not(feature = "info_graph_svg"), allow(dead_code))]
+    pub(crate) fn center(&self) -> LayoutPoint {
+        LayoutPoint {
+            x: self.x + self.width / 2.0,
+            y: self.y + self.height / 2.0,
+        }
+    }
+}
+
+/// Result of a [`LayeredLayout`].
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LayoutOutput {
+    /// Width of the laid out diagram.
+    pub(crate) width: f64,
+    /// Height of the laid out diagram.
+    pub(crate) height: f64,
+    /// Position and size of each item, in the same order as the input items.
+    pub(crate) item_rects: Vec<LayoutRect>,
+    /// Points that each edge should pass through between its source and
+    /// target, in the same order as the input edges.
+    pub(crate) edge_waypoints: Vec<Vec<LayoutPoint>>,
+}
+
+/// An edge lifted to the items within the cluster that it is laid out in.
+#[derive(Clone, Copy, Debug)]
+struct LevelEdge {
+    src: usize,
+    target: usize,
+    minlen: u32,
+    constraint: bool,
+    edge_index: usize,
+}
+
+/// Result of laying out the items within one cluster.
+struct LevelOutput {
+    width: f64,
+    height: f64,
+    /// Centres of each member, relative to the cluster's content.
+    member_centers: Vec<LayoutPoint>,
+    /// Waypoints of each edge laid out in this cluster.
+    edge_waypoints: Vec<(usize, Vec<LayoutPoint>)>,
+}
+
+impl LayeredLayout {
+    /// Returns the position of every item and the waypoints of every edge.
+    pub(crate) fn layout(&self) -> LayoutOutput {
+        let item_count = self.items.len();
+        // The root is given the index after all items.
+        let root = item_count;
+
+        let mut parents = vec![root; item_count];
+        self.items
+            .iter()
+            .enumerate()
+            .for_each(|(item_index, item)| {
+                item.children
+                    .iter()
+                    .for_each(|child_index| parents[*child_index] = item_index);
+            });
+
+        let level_edges = self.level_edges(&parents, root);
+
+        // Lay out clusters from the innermost outwards, so that the size of each
+        // cluster is known when its parent is laid out.
+        let mut sizes = self
+            .items
+            .iter()
+            .map(|item| (item.width, item.height))
+            .collect::<Vec<_>>();
+        let mut content_offsets = vec![LayoutPoint::default(); item_count + 1];
+        let mut level_outputs = HashMap::<usize, LevelOutput>::new();
+        self.containers_post_order(root)
+            .into_iter()
+            .for_each(|container| {
+                let members = self.members(container, root);
+                let level_output = self.level_layout(members, &sizes, &level_edges[container]);
+
+                if container != root {
+                    let item = &self.items[container];
+                    let LayoutPadding {
+                        left,
+                        right,
+                        top,
+                        bottom,
+                    } = item.padding;
+                    let width = item.width.max(level_output.width + left + right);
+                    let height = item.height.max(level_output.height + top + bottom);
+                    content_offsets[container] = LayoutPoint {
+                        x: left + (width - left - right - level_output.width) / 2.0,
+                        y: top + (height - top - bottom - level_output.height) / 2.0,
+                    };
+                    sizes[container] = (width, height);
+                }
+
+                level_outputs.insert(container, level_output);
+            });
+
+        // Translate each cluster's layout to absolute positions, from the root
+        // inwards.
+        let mut item_rects = vec![LayoutRect::default(); item_count];
+        let mut edge_waypoints = vec![Vec::new(); self.edges.len()];
+        let mut container_origins = vec![(root, LayoutPoint::default())];
+        while let Some((container, origin)) = container_origins.pop() {
+            let Some(level_output) = level_outputs.remove(&container) else {
+                continue;
+            };
+            let members = self.members(container, root);
+            members
+                .iter()
+                .zip(level_output.member_centers.iter())
+                .for_each(|(member, member_center)| {
+                    let (width, height) = sizes[*member];
+                    let rect = LayoutRect {
+                        x: origin.x + member_center.x - width / 2.0,
+                        y: origin.y + member_center.y - height / 2.0,
+                        width,
+                        height,
+                    };
+                    item_rects[*member] = rect;
+
+                    if !self.items[*member].children.is_empty() {
+                        let content_offset = content_offsets[*member];
+                        container_origins.push((
+                            *member,
+                            LayoutPoint {
+                                x: rect.x + content_offset.x,
+                                y: rect.y + content_offset.y,
+                            },
+                        ));
+                    }
+                });
+            level_output
+                .edge_waypoints
+                .into_iter()
+                .for_each(|(edge_index, waypoints)| {
+                    edge_waypoints[edge_index] = waypoints
+                        .into_iter()
+                        .map(|waypoint| LayoutPoint {
+                            x: origin.x + waypoint.x,
+                            y: origin.y + waypoint.y,
+                        })
+                        .collect();
+                });
+        }
+
+        let (width, height) = item_rects
+            .iter()
+            .fold((0.0f64, 0.0f64), |(width, height), rect| {
+                (
+                    width.max(rect.x + rect.width),
+                    height.max(rect.y + rect.height),
+                )
+            });
+
+        LayoutOutput {
+            width,
+            height,
+            item_rects,
+            edge_waypoints,
+        }
+    }
+
+    /// Returns the items directly within the given container.
+    fn members(&self, container: usize, root: usize) -> &[usize] {
+        if container == root {
+            &self.roots
+        } else {
+            &self.items[container].children
+        }
+    }
+
+    /// Returns the clusters and the root, with each cluster before its parent.
+    fn containers_post_order(&self, root: usize) -> Vec<usize> {
+        let mut containers = Vec::new();
+        let mut stack = vec![root];
+        while let Some(container) = stack.pop() {
+            containers.push(container);
+            self.members(container, root)
+                .iter()
+                .filter(|member| !self.items[**member].children.is_empty())
+                .for_each(|member| stack.push(*member));
+        }
+        containers.reverse();
+        containers
+    }
+
+    /// Groups edges by the container they are laid out in.
+    ///
+    /// Each edge is laid out in the innermost container of both its source and
+    /// target, between the members of that container which contain them.
+    /// Edges between an item and its own ancestor cannot be laid out, and are
+    /// left out.
+    fn level_edges(&self, parents: &[usize], root: usize) -> Vec<Vec<LevelEdge>> {
+        let ancestry = |item_index: usize| {
+            let mut ancestry = vec![item_index];
+            let mut item_index = item_index;
+            while parents[item_index] != root {
+                item_index = parents[item_index];
+                ancestry.push(item_index);
+            }
+            ancestry.reverse();
+            ancestry
+        };
+
+        let mut level_edges = vec![Vec::new(); root + 1];
+        self.edges
+            .iter()
+            .enumerate()
+            .filter(|(_edge_index, edge)| edge.src != edge.target)
+            .for_each(|(edge_index, edge)| {
+                let src_ancestry = ancestry(edge.src);
+                let target_ancestry = ancestry(edge.target);
+                let common_len = src_ancestry
+                    .iter()
+                    .zip(target_ancestry.iter())
+                    .take_while(|(src_item, target_item)| src_item == target_item)
+                    .count();
+                if common_len == src_ancestry.len() || common_len == target_ancestry.len() {
+                    return;
+                }
+
+                let container = common_len
+                    .checked_sub(1)
+                    .map(|common_index| src_ancestry[common_index])
+                    .unwrap_or(root);
+                level_edges[container].push(LevelEdge {
+                    src: src_ancestry[common_len],
+                    target: target_ancestry[common_len],
+                    minlen: edge.minlen,
+                    constraint: edge.constraint,
+                    edge_index,
+                });
+            });
+
+        level_edges
+    }
+
+    /// Lays out the members of one container.
+    fn level_layout(
+        &self,
+        members: &[usize],
+        sizes: &[(f64, f64)],
+        level_edges: &[LevelEdge],
+    ) -> LevelOutput {
+        let member_count = members.len();
+        let member_to_local = members
+            .iter()
+            .enumerate()
+            .map(|(local_index, member)| (*member, local_index))
+            .collect::<HashMap<usize, usize>>();
+        let local_edges = level_edges
+            .iter()
+            .filter_map(|level_edge| {
+                let src = *member_to_local.get(&level_edge.src)?;
+                let target = *member_to_local.get(&level_edge.target)?;
+                Some(LevelEdge {
+                    src,
+                    target,
+                    ..*level_edge
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let ranks = ranks_assign(member_count, &local_edges);
+
+        // Split edges that span multiple ranks with dummy nodes, so that they
+        // take part in ordering and are routed around other nodes.
+        let mut vnode_ranks = ranks.clone();
+        let mut down_adj = vec![Vec::<usize>::new(); member_count];
+        let mut up_adj = vec![Vec::<usize>::new(); member_count];
+        let mut edge_chains = Vec::with_capacity(local_edges.len());
+        local_edges.iter().for_each(|local_edge| {
+            let (upper, lower, reversed) = if ranks[local_edge.src] <= ranks[local_edge.target] {
+                (local_edge.src, local_edge.target, false)
+            } else {
+                (local_edge.target, local_edge.src, true)
+            };
+            let mut chain = vec![upper];
+            if ranks[upper] != ranks[lower] {
+                ((ranks[upper] + 1)..ranks[lower]).for_each(|rank| {
+                    let dummy = vnode_ranks.len();
+                    vnode_ranks.push(rank);
+                    down_adj.push(Vec::new());
+                    up_adj.push(Vec::new());
+                    chain.push(dummy);
+                });
+                chain.push(lower);
+                chain.windows(2).for_each(|segment| {
+                    down_adj[segment[0]].push(segment[1]);
+                    up_adj[segment[1]].push(segment[0]);
+                });
+            } else {
+                chain.push(lower);
+            }
+            edge_chains.push((local_edge.edge_index, chain, reversed));
+        });
+
+        let vnode_count = vnode_ranks.len();
+        let rank_count = vnode_ranks.iter().copied().max().map_or(0, |rank| rank + 1);
+        let mut layers = vec![Vec::<usize>::new(); rank_count];
+        (0..vnode_count).for_each(|vnode| layers[vnode_ranks[vnode]].push(vnode));
+        let layers = layers_order(layers, &down_adj, &up_adj, vnode_count);
+
+        // Sizes along the rank axis and across it.
+        let vnode_sizes = (0..vnode_count)
+            .map(|vnode| {
+                if vnode < member_count {
+                    let (width, height) = sizes[members[vnode]];
+                    match self.graph_dir {
+                        GraphDir::Horizontal => (width, height),
+                        GraphDir::Vertical => (height, width),
+                    }
+                } else {
+                    (0.0, 0.0)
+                }
+            })
+            .collect::<Vec<(f64, f64)>>();
+
+        let cross_positions = cross_positions_assign(
+            &layers,
+            &vnode_sizes,
+            member_count,
+            self.nodesep,
+            &down_adj,
+            &up_adj,
+        );
+
+        let rank_extents = layers
+            .iter()
+            .map(|layer| {
+                layer
+                    .iter()
+                    .map(|vnode| vnode_sizes[*vnode].0)
+                    .fold(0.0f64, f64::max)
+            })
+            .collect::<Vec<f64>>();
+        let mut rank_positions = Vec::with_capacity(rank_count);
+        rank_extents
+            .iter()
+            .enumerate()
+            .for_each(|(rank, rank_extent)| {
+                let rank_position = match rank.checked_sub(1) {
+                    Some(rank_prev) => {
+                        rank_positions[rank_prev]
+                            + rank_extents[rank_prev] / 2.0
+                            + self.ranksep
+                            + rank_extent / 2.0
+                    }
+                    None => rank_extent / 2.0,
+                };
+                rank_positions.push(rank_position);
+            });
+
+        let vnode_point = |vnode: usize| {
+            let rank_position = rank_positions[vnode_ranks[vnode]];
+            let cross_position = cross_positions[vnode];
+            match self.graph_dir {
+                GraphDir::Horizontal => LayoutPoint {
+                    x: rank_position,
+                    y: cross_position,
+                },
+                GraphDir::Vertical => LayoutPoint {
+                    x: cross_position,
+                    y: rank_position,
+                },
+            }
+        };
+
+        // Translate everything so the top left of the content is at the origin.
+        let (mut x_min, mut y_min, mut x_max, mut y_max) = (
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        );
+        (0..vnode_count).for_each(|vnode| {
+            let point = vnode_point(vnode);
+            let (half_width, half_height) = if vnode < member_count {
+                let (width, height) = sizes[members[vnode]];
+                (width / 2.0, height / 2.0)
+            } else {
+                (0.0, 0.0)
+            };
+            x_min = x_min.min(point.x - half_width);
+            y_min = y_min.min(point.y - half_height);
+            x_max = x_max.max(point.x + half_width);
+            y_max = y_max.max(point.y + half_height);
+        });
+        if vnode_count == 0 {
+            (x_min, y_min, x_max, y_max) = (0.0, 0.0, 0.0, 0.0);
+        }
+        let translate = |point: LayoutPoint| LayoutPoint {
+            x: point.x - x_min,
+            y: point.y - y_min,
+        };
+
+        let member_centers = (0..member_count)
+            .map(|vnode| translate(vnode_point(vnode)))
+            .collect::<Vec<_>>();
+        let edge_waypoints = edge_chains
+            .into_iter()
+            .map(|(edge_index, chain, reversed)| {
+                let mut waypoints = chain[1..chain.len() - 1]
+                    .iter()
+                    .map(|vnode| translate(vnode_point(*vnode)))
+                    .collect::<Vec<_>>();
+                if reversed {
+                    waypoints.reverse();
+                }
+                (edge_index, waypoints)
+            })
+            .collect::<Vec<_>>();
+
+        LevelOutput {
+            width: x_max - x_min,
+            height: y_max - y_min,
+            member_centers,
+            edge_waypoints,
+        }
+    }
+}
+
+/// Returns the rank of each node, such that each constraint edge's target is
+/// at least `minlen` ranks after its source.
+///
+/// Cycles are broken by reversing edges that point back to a node that is
+/// still being visited in a depth first search.
+fn ranks_assign(node_count: usize, edges: &[LevelEdge]) -> Vec<usize> {
+    let constraint_edges = edges
+        .iter()
+        .filter(|edge| edge.constraint)
+        .collect::<Vec<_>>();
+    let mut out_edges = vec![Vec::<usize>::new(); node_count];
+    constraint_edges
+        .iter()
+        .enumerate()
+        .for_each(|(edge_index, edge)| out_edges[edge.src].push(edge_index));
+
+    // 0: not visited, 1: on the stack, 2: done.
+    let mut visit_states = vec![0u8; node_count];
+    let mut reversed = vec![false; constraint_edges.len()];
+    (0..node_count).for_each(|node_start| {
+        if visit_states[node_start] != 0 {
+            return;
+        }
+        visit_states[node_start] = 1;
+        let mut stack = vec![(node_start, 0usize)];
+        while let Some((node, out_edge_position)) = stack.pop() {
+            match out_edges[node].get(out_edge_position) {
+                Some(edge_index) => {
+                    stack.push((node, out_edge_position + 1));
+                    let target = constraint_edges[*edge_index].target;
+                    match visit_states[target] {
+                        0 => {
+                            visit_states[target] = 1;
+                            stack.push((target, 0));
+                        }
+                        1 => reversed[*edge_index] = true,
+                        _ => {}
+                    }
+                }
+                None => visit_states[node] = 2,
+            }
+        }
+    });
+
+    let ranked_edges = constraint_edges
+        .iter()
+        .zip(reversed.iter())
+        .map(|(edge, reversed)| {
+            if *reversed {
+                (edge.target, edge.src, edge.minlen as usize)
+            } else {
+                (edge.src, edge.target, edge.minlen as usize)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Longest path from the sources, in topological order.
+    let mut successors = vec![Vec::<(usize, usize)>::new(); node_count];
+    let mut in_degrees = vec![0usize; node_count];
+    ranked_edges.iter().for_each(|(src, target, minlen)| {
+        successors[*src].push((*target, *minlen));
+        in_degrees[*target] += 1;
+    });
+    let is_source = in_degrees
+        .iter()
+        .map(|in_degree| *in_degree == 0)
+        .collect::<Vec<bool>>();
+    let mut ranks = vec![0usize; node_count];
+    let mut queue = (0..node_count)
+        .filter(|node| in_degrees[*node] == 0)
+        .collect::<Vec<_>>();
+    let mut queue_position = 0;
+    while let Some(node) = queue.get(queue_position).copied() {
+        queue_position += 1;
+        successors[node].iter().for_each(|(target, minlen)| {
+            ranks[*target] = ranks[*target].max(ranks[node] + minlen);
+            in_degrees[*target] -= 1;
+            if in_degrees[*target] == 0 {
+                queue.push(*target);
+            }
+        });
+    }
+
+    // Move sources next to their nearest successor, so they are not all
+    // stacked in the first rank.
+    (0..node_count)
+        .filter(|node| is_source[*node])
+        .for_each(|node| {
+            if let Some(rank) = successors[node]
+                .iter()
+                .map(|(target, minlen)| ranks[*target].saturating_sub(*minlen))
+                .min()
+            {
+                ranks[node] = rank;
+            }
+        });
+
+    let rank_min = ranks.iter().copied().min().unwrap_or(0);
+    ranks.iter_mut().for_each(|rank| *rank -= rank_min);
+    ranks
+}
+
+/// Orders the nodes in each layer to reduce edge crossings, using the
+/// barycenter heuristic.
+fn layers_order(
+    mut layers: Vec<Vec<usize>>,
+    down_adj: &[Vec<usize>],
+    up_adj: &[Vec<usize>],
+    vnode_count: usize,
+) -> Vec<Vec<usize>> {
+    let mut positions = vec![0usize; vnode_count];
+    let positions_update = |layers: &[Vec<usize>], positions: &mut [usize]| {
+        layers.iter().for_each(|layer| {
+            layer
+                .iter()
+                .enumerate()
+                .for_each(|(position, vnode)| positions[*vnode] = position)
+        })
+    };
+    positions_update(&layers, &mut positions);
+
+    let mut layers_best = layers.clone();
+    let mut crossings_best = crossings_count(&layers, down_adj, &positions);
+    let layer_count = layers.len();
+
+    for iteration in 0..ORDER_ITERATIONS {
+        if crossings_best == 0 {
+            break;
+        }
+
+        let (layer_indices, adj): (Vec<usize>, _) = if iteration % 2 == 0 {
+            ((1..layer_count).collect(), up_adj)
+        } else {
+            ((0..layer_count.saturating_sub(1)).rev().collect(), down_adj)
+        };
+        layer_indices.into_iter().for_each(|layer_index| {
+            let layer = &mut layers[layer_index];
+            let mut barycenters = layer
+                .iter()
+                .map(|vnode| {
+                    let neighbours = &adj[*vnode];
+                    let barycenter = if neighbours.is_empty() {
+                        positions[*vnode] as f64
+                    } else {
+                        neighbours
+                            .iter()
+                            .map(|neighbour| positions[*neighbour] as f64)
+                            .sum::<f64>()
+                            / neighbours.len() as f64
+                    };
+                    (*vnode, barycenter)
+                })
+                .collect::<Vec<_>>();
+            barycenters.sort_by(|(_, barycenter_a), (_, barycenter_b)| {
+                barycenter_a.total_cmp(barycenter_b)
+            });
+            barycenters
+                .into_iter()
+                .enumerate()
+                .for_each(|(position, (vnode, _barycenter))| {
+                    layer[position] = vnode;
+                    positions[vnode] = position;
+                });
+        });
+
+        let crossings = crossings_count(&layers, down_adj, &positions);
+        if crossings < crossings_best {
+            crossings_best = crossings;
+            layers_best.clone_from(&layers);
+        }
+    }
+
+    layers_best
+}
+
+/// Returns the number of edge crossings between adjacent layers.
+fn crossings_count(layers: &[Vec<usize>], down_adj: &[Vec<usize>], positions: &[usize]) -> usize {
+    layers
+        .windows(2)
+        .map(|layer_pair| {
+            let lower_len = layer_pair[1].len();
+            let mut lower_positions = Vec::new();
+            layer_pair[0].iter().for_each(|vnode| {
+                let mut targets = down_adj[*vnode]
+                    .iter()
+                    .map(|target| positions[*target])
+                    .collect::<Vec<_>>();
+                targets.sort_unstable();
+                lower_positions.extend(targets);
+            });
+
+            // Count inversions with a Fenwick tree.
+            let mut tree = vec![0usize; lower_len + 1];
+            lower_positions
+                .iter()
+                .enumerate()
+                .map(|(inserted, position)| {
+                    let mut index = position + 1;
+                    let mut not_greater = 0;
+                    while index > 0 {
+                        not_greater += tree[index];
+                        index -= index & index.wrapping_neg();
+                    }
+                    let mut index = position + 1;
+                    while index <= lower_len {
+                        tree[index] += 1;
+                        index += index & index.wrapping_neg();
+                    }
+                    inserted - not_greater
+                })
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+/// Returns the position of each node across the rank axis.
+///
+/// Nodes are repeatedly moved towards the average position of their
+/// neighbours in the adjacent rank, while keeping their order and spacing.
+fn cross_positions_assign(
+    layers: &[Vec<usize>],
+    vnode_sizes: &[(f64, f64)],
+    member_count: usize,
+    nodesep: f64,
+    down_adj: &[Vec<usize>],
+    up_adj: &[Vec<usize>],
+) -> Vec<f64> {
+    let gap = |vnode_a: usize, vnode_b: usize| {
+        let sep = if vnode_a < member_count && vnode_b < member_count {
+            nodesep
+        } else {
+            nodesep / 2.0
+        };
+        vnode_sizes[vnode_a].1 / 2.0 + sep + vnode_sizes[vnode_b].1 / 2.0
+    };
+
+    let mut positions = vec![0.0f64; vnode_sizes.len()];
+    layers.iter().for_each(|layer| {
+        let mut position = 0.0;
+        layer.iter().enumerate().for_each(|(index, vnode)| {
+            if let Some(vnode_prev) = index.checked_sub(1).map(|index_prev| layer[index_prev]) {
+                position += gap(vnode_prev, *vnode);
+            }
+            positions[*vnode] = position;
+        });
+        let offset = position / 2.0;
+        layer.iter().for_each(|vnode| positions[*vnode] -= offset);
+    });
+
+    let layer_count = layers.len();
+    (0..=COORD_ITERATIONS).for_each(|iteration| {
+        let layer_indices: Vec<usize> = if iteration % 2 == 0 {
+            (0..layer_count).collect()
+        } else {
+            (0..layer_count).rev().collect()
+        };
+        layer_indices.into_iter().for_each(|layer_index| {
+            let layer = &layers[layer_index];
+            let targets = layer
+                .iter()
+                .map(|vnode| {
+                    let neighbours = if iteration == COORD_ITERATIONS {
+                        up_adj[*vnode]
+                            .iter()
+                            .chain(down_adj[*vnode].iter())
+                            .collect::<Vec<_>>()
+                    } else if iteration % 2 == 0 {
+                        up_adj[*vnode].iter().collect()
+                    } else {
+                        down_adj[*vnode].iter().collect()
+                    };
+                    if neighbours.is_empty() {
+                        (positions[*vnode], COORD_WEIGHT_UNCONNECTED)
+                    } else {
+                        let position = neighbours
+                            .iter()
+                            .map(|neighbour| positions[**neighbour])
+                            .sum::<f64>()
+                            / neighbours.len() as f64;
+                        (position, 1.0)
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let mut offsets = Vec::with_capacity(layer.len());
+            layer.iter().enumerate().for_each(|(index, vnode)| {
+                let offset = match index.checked_sub(1) {
+                    Some(index_prev) => offsets[index_prev] + gap(layer[index_prev], *vnode),
+                    None => 0.0,
+                };
+                offsets.push(offset);
+            });
+
+            let placed = isotonic_regression(
+                &targets
+                    .iter()
+                    .zip(offsets.iter())
+                    .map(|((target, weight), offset)| (target - offset, *weight))
+                    .collect::<Vec<_>>(),
+            );
+            layer
+                .iter()
+                .zip(placed.iter().zip(offsets.iter()))
+                .for_each(|(vnode, (placed, offset))| positions[*vnode] = placed + offset);
+        });
+    });
+
+    positions
+}
+
+/// Returns the non-decreasing values closest to the weighted targets, using
+/// the pool adjacent violators algorithm.
+fn isotonic_regression(targets: &[(f64, f64)]) -> Vec<f64> {
+    // Each block is `(weighted sum, total weight, count)`.
+    let mut blocks = Vec::<(f64, f64, usize)>::with_capacity(targets.len());
+    targets.iter().for_each(|(target, weight)| {
+        blocks.push((target * weight, *weight, 1));
+        while blocks.len() > 1 {
+            let (sum_last, weight_last, count_last) = blocks[blocks.len() - 1];
+            let (sum_prev, weight_prev, count_prev) = blocks[blocks.len() - 2];
+            if sum_prev / weight_prev <= sum_last / weight_last {
+                break;
+            }
+            blocks.pop();
+            blocks.pop();
+            blocks.push((
+                sum_prev + sum_last,
+                weight_prev + weight_last,
+                count_prev + count_last,
+            ));
+        }
+    });
+
+    blocks
+        .into_iter()
+        .flat_map(|(sum, weight, count)| std::iter::repeat_n(sum / weight, count))
+        .collect()
+}
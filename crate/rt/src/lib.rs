@@ -1,12 +1,83 @@
 //! Runtime logic for the `dot_ix` diagramming application.
 
-pub use crate::{info_graph_dot::InfoGraphDot, into_graphviz_dot_src::IntoGraphvizDotSrc};
+pub use crate::{
+    diagnostics::Diagnostic,
+    dot_parser::{DotParseError, DotParseOutput, DotParseWarning, DotParser},
+    dot_renderer::{
+        DotDiagnostic, DotDiagnosticSeverity, DotRenderer, FakeDotRenderer, RenderError, Svg,
+    },
+    info_graph_dot::InfoGraphDot,
+    into_graphviz_dot_src::{write_dot, IntoGraphvizDotSrc},
+    into_mermaid_src::IntoMermaidSrc,
+    mermaid_parser::{MermaidParseError, MermaidParseOutput, MermaidParseWarning, MermaidParser},
+    tailwind_css_gen::TailwindCssGen,
+};
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::dot_renderer::DotProcessRenderer;
+
+mod diagnostics;
+mod dot_parser;
+mod dot_renderer;
+mod id_allocator;
+mod info_graph_classes;
 mod info_graph_dot;
 mod into_graphviz_dot_src;
+mod into_mermaid_src;
+mod mermaid_parser;
+mod tailwind_css_gen;
 
 cfg_if::cfg_if! { if #[cfg(feature = "info_graph_html")] {
-    pub use crate::info_graph_html::InfoGraphHtml;
+    pub use crate::{
+        info_graph_html::InfoGraphHtml,
+        into_html_src::{HtmlEdgeConnector, IntoHtmlSrc},
+    };
 
     mod info_graph_html;
+    mod into_html_src;
+}}
+
+cfg_if::cfg_if! { if #[cfg(feature = "info_graph_svg")] {
+    pub use crate::into_svg_src::IntoSvgSrc;
+
+    mod into_svg_src;
+}}
+
+cfg_if::cfg_if! { if #[cfg(feature = "info_graph_text")] {
+    pub use crate::into_text_src::{IntoTextSrc, TextCharset, TextOptions};
+
+    mod into_text_src;
+}}
+
+cfg_if::cfg_if! { if #[cfg(feature = "info_graph_drawio")] {
+    pub use crate::into_drawio_src::IntoDrawioSrc;
+
+    mod into_drawio_src;
+}}
+
+#[cfg(any(
+    feature = "info_graph_svg",
+    feature = "info_graph_text",
+    feature = "info_graph_drawio"
+))]
+mod layered_layout;
+
+#[cfg(any(
+    feature = "info_graph_html",
+    feature = "info_graph_svg",
+    feature = "info_graph_drawio",
+    feature = "interchange"
+))]
+mod xml_escape;
+
+cfg_if::cfg_if! { if #[cfg(feature = "graphviz_wasm")] {
+    pub use crate::graphviz_wasm_renderer::GraphvizWasmRenderer;
+
+    mod graphviz_wasm_renderer;
+}}
+
+cfg_if::cfg_if! { if #[cfg(feature = "interchange")] {
+    pub use crate::interchange::{CytoscapeJson, Gexf, Graphml, InterchangeError};
+
+    mod interchange;
 }}
@@ -0,0 +1,745 @@
+use std::borrow::Cow;
+
+pub use self::{
+    mermaid_parse_error::MermaidParseError, mermaid_parse_output::MermaidParseOutput,
+    mermaid_parse_warning::MermaidParseWarning,
+};
+
+use self::info_graph_builder::{
+    InfoGraphBuilder, LinkStroke, LinkStyleTargets, MermaidLink, MermaidNode, MermaidShape,
+};
+
+mod info_graph_builder;
+mod mermaid_parse_error;
+mod mermaid_parse_output;
+mod mermaid_parse_warning;
+
+/// Parses a Mermaid `flowchart` into an [`InfoGraph`], e.g. to move diagrams
+/// to `dot_ix` to use tag focusing.
+///
+/// * `flowchart` and `graph` directions become the [`GraphDir`].
+/// * Nested `subgraph`s become the node hierarchy.
+/// * Node and link labels become `node_names` and `edge_descs`. Text after the
+///   first `<br>` in a node label becomes `node_descs`.
+/// * Edges get an `EdgeId` of `{src}__{target}`, or their ID if given, e.g.
+///   `e1` in `a e1@--> b`.
+/// * Dotted, thick, and invisible links become edge theme styles. Longer links
+///   such as `--->` become `edge_minlens`.
+/// * Each `classDef` and `class` becomes a tag, with the class's styles
+///   applied through a `tag:` theme selector. `classDef default` becomes the
+///   `node_defaults` styles.
+/// * `style` and `linkStyle` become theme styles for the node or edge.
+///
+/// Colours are mapped to the closest Tailwind colour. Round and stadium
+/// shaped nodes get rounded corners, and if every node is a circle, the
+/// diagram uses [`GraphStyle::Circle`].
+///
+/// IDs that are not valid `NodeId`s are renamed, and the original IDs are
+/// kept in [`MermaidParseOutput::mermaid_ids`]. Shapes, styles, and
+/// statements that are not supported are reported as
+/// [`MermaidParseWarning`]s.
+///
+/// [`InfoGraph`]: dot_ix_model::info_graph::InfoGraph
+/// [`GraphDir`]: dot_ix_model::info_graph::GraphDir
+/// [`GraphStyle::Circle`]: dot_ix_model::info_graph::GraphStyle::Circle
+#[derive(Clone, Copy, Debug)]
+pub struct MermaidParser;
+
+impl MermaidParser {
+    /// Parses a `flowchart` or `graph` into an `InfoGraph`.
+    pub fn parse(mermaid_src: &str) -> Result<MermaidParseOutput, MermaidParseError> {
+        let mut statements = statements_split(mermaid_src).into_iter();
+        let header = statements.next().ok_or(MermaidParseError::Empty)?;
+
+        let mut info_graph_builder = InfoGraphBuilder::new();
+        let mut header_words = header.text.split_whitespace();
+        match header_words.next() {
+            Some("flowchart" | "graph" | "flowchart-elk") => {}
+            diagram_type => {
+                return Err(MermaidParseError::DiagramTypeUnsupported {
+                    diagram_type: diagram_type.unwrap_or_default().to_string(),
+                    line: header.line,
+                });
+            }
+        }
+        if let Some(direction) = header_words.next() {
+            info_graph_builder
+                .direction_apply(direction, header.line)
+                .map_err(|message| MermaidParseError::Syntax {
+                    line: header.line,
+                    column: header.column,
+                    message,
+                })?;
+        }
+
+        statements.try_for_each(|statement| {
+            StatementParser::new(&statement, &mut info_graph_builder).statement_parse()
+        })?;
+
+        if let Some((subgraph_id, line)) = info_graph_builder.subgraph_open() {
+            return Err(MermaidParseError::SubgraphNotClosed { subgraph_id, line });
+        }
+
+        Ok(info_graph_builder.build())
+    }
+}
+
+/// Text of one statement, and where it begins.
+struct Statement {
+    text: String,
+    /// Line number, starting from `1`.
+    line: u32,
+    /// Column number in characters, starting from `1`.
+    column: u32,
+}
+
+/// Splits Mermaid source into statements, which are separated by line breaks
+/// or `;`.
+///
+/// Front matter, `%%` comments, and `%%{ .. }%%` directives are skipped.
+fn statements_split(mermaid_src: &str) -> Vec<Statement> {
+    let mut statements = Vec::new();
+    let mut in_front_matter = false;
+
+    mermaid_src
+        .lines()
+        .zip(1u32..)
+        .for_each(|(line_text, line)| {
+            let line_trimmed = line_text.trim();
+            if line_trimmed == "---" && (in_front_matter || statements.is_empty()) {
+                in_front_matter = !in_front_matter;
+                return;
+            }
+            if in_front_matter || line_trimmed.starts_with("%%") {
+                return;
+            }
+
+            // Split on `;`s that are not in labels.
+            let mut statement_text = String::new();
+            let mut statement_column = 1u32;
+            let mut quoted = false;
+            let mut bracket_depth = 0u32;
+            let mut statement_push = |statement_text: &mut String, column: u32| {
+                let text_trimmed = statement_text.trim_start();
+                let column = column + (statement_text.len() - text_trimmed.len()) as u32;
+                let text_trimmed = text_trimmed.trim_end();
+                if !text_trimmed.is_empty() {
+                    statements.push(Statement {
+                        text: text_trimmed.to_string(),
+                        line,
+                        column,
+                    });
+                }
+                statement_text.clear();
+            };
+            line_text.chars().zip(1u32..).for_each(|(c, column)| {
+                match c {
+                    '"' => quoted = !quoted,
+                    '[' | '(' | '{' if !quoted => bracket_depth += 1,
+                    ']' | ')' | '}' if !quoted => bracket_depth = bracket_depth.saturating_sub(1),
+                    ';' if !quoted && bracket_depth == 0 => {
+                        statement_push(&mut statement_text, statement_column);
+                        statement_column = column + 1;
+                        return;
+                    }
+                    _ => {}
+                }
+                statement_text.push(c);
+            });
+            statement_push(&mut statement_text, statement_column);
+        });
+
+    statements
+}
+
+/// Parses one statement, applying it to the `InfoGraphBuilder`.
+struct StatementParser<'b> {
+    chars: Vec<char>,
+    index: usize,
+    line: u32,
+    column: u32,
+    info_graph_builder: &'b mut InfoGraphBuilder,
+}
+
+impl<'b> StatementParser<'b> {
+    fn new(statement: &Statement, info_graph_builder: &'b mut InfoGraphBuilder) -> Self {
+        Self {
+            chars: statement.text.chars().collect(),
+            index: 0,
+            line: statement.line,
+            column: statement.column,
+            info_graph_builder,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+
+    fn rest_starts_with(&self, prefix: &str) -> bool {
+        prefix
+            .chars()
+            .enumerate()
+            .all(|(offset, c)| self.peek_at(offset) == Some(c))
+    }
+
+    /// Returns the remaining text of the statement.
+    fn rest(&self) -> String {
+        self.chars[self.index..].iter().collect()
+    }
+
+    fn whitespace_skip(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.index += 1;
+        }
+    }
+
+    fn syntax_error(&self, message: String) -> MermaidParseError {
+        MermaidParseError::Syntax {
+            line: self.line,
+            column: self.column + self.index as u32,
+            message,
+        }
+    }
+
+    fn statement_parse(mut self) -> Result<(), MermaidParseError> {
+        let keyword = self.keyword_peek();
+        let line = self.line;
+        match keyword.as_str() {
+            "subgraph" => {
+                self.index += keyword.len();
+                self.subgraph_parse()
+            }
+            "end" if self.chars.len() == keyword.len() => {
+                if self.info_graph_builder.subgraph_end() {
+                    Ok(())
+                } else {
+                    Err(self.syntax_error(String::from("`end` without a `subgraph`.")))
+                }
+            }
+            "classDef" => {
+                self.index += keyword.len();
+                let (class_names, style) = self.targets_and_rest_parse("class name")?;
+                self.info_graph_builder
+                    .class_def_apply(class_names, &style, line);
+                Ok(())
+            }
+            "class" => {
+                self.index += keyword.len();
+                let (targets, class_name) = self.targets_and_rest_parse("node ID")?;
+                self.info_graph_builder
+                    .class_apply(targets, class_name, line);
+                Ok(())
+            }
+            "style" => {
+                self.index += keyword.len();
+                let (targets, style) = self.targets_and_rest_parse("node ID")?;
+                targets
+                    .into_iter()
+                    .for_each(|target| self.info_graph_builder.style_apply(target, &style, line));
+                Ok(())
+            }
+            "linkStyle" => {
+                self.index += keyword.len();
+                let (targets, style) = self.targets_and_rest_parse("link index")?;
+                let link_style_targets = if targets.iter().any(|target| target == "default") {
+                    LinkStyleTargets::Default
+                } else {
+                    LinkStyleTargets::Indices(targets)
+                };
+                self.info_graph_builder
+                    .link_style_apply(link_style_targets, &style, line);
+                Ok(())
+            }
+            "direction" | "click" | "callback" | "linkStyleDefault" | "accTitle" | "accDescr"
+            | "title" => {
+                self.info_graph_builder.statement_unsupported(keyword, line);
+                Ok(())
+            }
+            _ => self.chain_parse(),
+        }
+    }
+
+    /// Returns the leading word, without consuming it.
+    ///
+    /// Keywords such as `accTitle:` end with `:`, which is not included.
+    fn keyword_peek(&self) -> String {
+        let keyword = self
+            .chars
+            .iter()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>();
+        match self.chars.get(keyword.len()) {
+            None => keyword,
+            Some(c) if c.is_whitespace() || *c == ':' => keyword,
+            Some(_) => String::new(),
+        }
+    }
+
+    /// Parses `subgraph id`, `subgraph id [title]`, or `subgraph title`.
+    fn subgraph_parse(&mut self) -> Result<(), MermaidParseError> {
+        self.whitespace_skip();
+        let line = self.line;
+        if self.peek() == Some('"') {
+            let title = self.quoted_parse()?;
+            self.info_graph_builder
+                .subgraph_begin(title.clone(), Some(title), line);
+            return Ok(());
+        }
+
+        let subgraph_id = self.id_parse();
+        if subgraph_id.is_empty() {
+            return Err(self.syntax_error(String::from("Expected a subgraph ID or title.")));
+        }
+        self.whitespace_skip();
+        match self.peek() {
+            None => {
+                self.info_graph_builder
+                    .subgraph_begin(subgraph_id, None, line);
+            }
+            Some('[') => {
+                self.index += 1;
+                let (title, _quoted) = self.label_parse(&["]"])?;
+                self.info_graph_builder
+                    .subgraph_begin(subgraph_id, Some(title), line);
+            }
+            Some(_) => {
+                // `subgraph A title with spaces`, the whole text is the title.
+                let title = format!("{subgraph_id} {}", self.rest().trim_end());
+                self.info_graph_builder
+                    .subgraph_begin(title.clone(), Some(title), line);
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses `a,b rest`, returning the comma separated targets and the rest
+    /// of the statement.
+    fn targets_and_rest_parse(
+        &mut self,
+        expected: &str,
+    ) -> Result<(Vec<String>, String), MermaidParseError> {
+        self.whitespace_skip();
+        let mut targets = Vec::new();
+        loop {
+            let target = self
+                .chars
+                .iter()
+                .skip(self.index)
+                .take_while(|c| !c.is_whitespace() && **c != ',')
+                .collect::<String>();
+            if target.is_empty() {
+                return Err(self.syntax_error(format!("Expected a {expected}.")));
+            }
+            self.index += target.chars().count();
+            targets.push(target);
+
+            self.whitespace_skip();
+            if self.peek() == Some(',') {
+                self.index += 1;
+                self.whitespace_skip();
+            } else {
+                break;
+            }
+        }
+
+        let rest = self.rest().trim().to_string();
+        if rest.is_empty() {
+            return Err(self.syntax_error(String::from("Expected a style or class name.")));
+        }
+        Ok((targets, rest))
+    }
+
+    /// Parses `a --> b & c -- text --> d`.
+    fn chain_parse(&mut self) -> Result<(), MermaidParseError> {
+        let line = self.line;
+        let mut mermaid_ids_prev = self.node_group_parse()?;
+        loop {
+            self.whitespace_skip();
+            if self.peek().is_none() {
+                return Ok(());
+            }
+
+            let mermaid_link = self.link_parse()?;
+            self.whitespace_skip();
+            let mermaid_ids = self.node_group_parse()?;
+            self.info_graph_builder.edges_apply(
+                &mermaid_ids_prev,
+                &mermaid_ids,
+                &mermaid_link,
+                line,
+            );
+            mermaid_ids_prev = mermaid_ids;
+        }
+    }
+
+    /// Parses `a & b:::class & c[label]`, returning the node IDs.
+    fn node_group_parse(&mut self) -> Result<Vec<String>, MermaidParseError> {
+        let mut mermaid_ids = vec![self.node_parse()?];
+        loop {
+            let index = self.index;
+            self.whitespace_skip();
+            if self.peek() == Some('&') {
+                self.index += 1;
+                self.whitespace_skip();
+                mermaid_ids.push(self.node_parse()?);
+            } else {
+                self.index = index;
+                return Ok(mermaid_ids);
+            }
+        }
+    }
+
+    fn node_parse(&mut self) -> Result<String, MermaidParseError> {
+        let line = self.line;
+        let mermaid_id = self.id_parse();
+        if mermaid_id.is_empty() {
+            let found = self
+                .peek()
+                .map(|c| format!("`{c}`"))
+                .unwrap_or_else(|| String::from("the end of the statement"));
+            return Err(self.syntax_error(format!("Expected a node ID, but found {found}.")));
+        }
+
+        let mut mermaid_node = MermaidNode {
+            mermaid_id: mermaid_id.clone(),
+            shape_and_label: None,
+            class_names: Vec::new(),
+            line,
+        };
+        self.class_shorthand_parse(&mut mermaid_node);
+
+        if self.rest_starts_with("@{") {
+            self.index += 2;
+            mermaid_node.shape_and_label = Some(self.shape_object_parse()?);
+        } else {
+            // Longer delimiters first, so that `((` is not read as `(`.
+            const SHAPE_DELIMITERS: &[(&str, &[&str], MermaidShape)] = &[
+                ("(((", &[")))"], MermaidShape::DoubleCircle),
+                ("((", &["))"], MermaidShape::Circle),
+                ("([", &["])"], MermaidShape::Stadium),
+                (
+                    "[[",
+                    &["]]"],
+                    MermaidShape::Other(Cow::Borrowed("subroutine")),
+                ),
+                (
+                    "[(",
+                    &[")]"],
+                    MermaidShape::Other(Cow::Borrowed("cylinder")),
+                ),
+                (
+                    "[/",
+                    &["/]", "\\]"],
+                    MermaidShape::Other(Cow::Borrowed("parallelogram")),
+                ),
+                (
+                    "[\\",
+                    &["\\]", "/]"],
+                    MermaidShape::Other(Cow::Borrowed("parallelogram")),
+                ),
+                ("{{", &["}}"], MermaidShape::Other(Cow::Borrowed("hexagon"))),
+                ("{", &["}"], MermaidShape::Other(Cow::Borrowed("rhombus"))),
+                (
+                    ">",
+                    &["]"],
+                    MermaidShape::Other(Cow::Borrowed("asymmetric")),
+                ),
+                ("(", &[")"], MermaidShape::Round),
+                ("[", &["]"], MermaidShape::Rect),
+            ];
+            let shape_delimiter = SHAPE_DELIMITERS
+                .iter()
+                .find(|(open, _closes, _shape)| self.rest_starts_with(open));
+            if let Some((open, closes, shape)) = shape_delimiter {
+                self.index += open.chars().count();
+                let (label, _quoted) = self.label_parse(closes)?;
+                mermaid_node.shape_and_label = Some((shape.clone(), Some(label)));
+            }
+        }
+
+        self.class_shorthand_parse(&mut mermaid_node);
+        self.info_graph_builder.node_apply(mermaid_node);
+        Ok(mermaid_id)
+    }
+
+    /// Parses `:::class_name` after a node.
+    fn class_shorthand_parse(&mut self, mermaid_node: &mut MermaidNode) {
+        while self.rest_starts_with(":::") {
+            self.index += 3;
+            let class_name = self.id_parse();
+            if !class_name.is_empty() {
+                mermaid_node.class_names.push(class_name);
+            }
+        }
+    }
+
+    /// Parses the `{ shape: rounded, label: "text" }` after `@`.
+    fn shape_object_parse(&mut self) -> Result<(MermaidShape, Option<String>), MermaidParseError> {
+        let mut shape = MermaidShape::Rect;
+        let mut label = None;
+        loop {
+            self.whitespace_skip();
+            match self.peek() {
+                Some('}') => {
+                    self.index += 1;
+                    return Ok((shape, label));
+                }
+                Some(',') => self.index += 1,
+                Some(_) => {
+                    let key = self.id_parse();
+                    self.whitespace_skip();
+                    if key.is_empty() || self.peek() != Some(':') {
+                        return Err(self.syntax_error(String::from("Expected `key: value`.")));
+                    }
+                    self.index += 1;
+                    self.whitespace_skip();
+                    let value = if self.peek() == Some('"') {
+                        self.quoted_parse()?
+                    } else {
+                        let value = self
+                            .chars
+                            .iter()
+                            .skip(self.index)
+                            .take_while(|c| **c != ',' && **c != '}')
+                            .collect::<String>();
+                        self.index += value.chars().count();
+                        value.trim().to_string()
+                    };
+                    match key.as_str() {
+                        "shape" => shape = MermaidShape::from_name(&value),
+                        "label" => label = Some(value),
+                        _ => {}
+                    }
+                }
+                None => {
+                    return Err(self.syntax_error(String::from("Expected `}`.")));
+                }
+            }
+        }
+    }
+
+    /// Parses an ID, which may contain `-` when it is followed by a letter or
+    /// digit.
+    fn id_parse(&mut self) -> String {
+        let mut id = String::new();
+        while let Some(c) = self.peek() {
+            let is_id_char = c.is_alphanumeric()
+                || c == '_'
+                || (c == '-'
+                    && !id.is_empty()
+                    && self.peek_at(1).is_some_and(|c| c.is_alphanumeric()));
+            if !is_id_char {
+                break;
+            }
+            id.push(c);
+            self.index += 1;
+        }
+        id
+    }
+
+    /// Parses a `"quoted string"`.
+    fn quoted_parse(&mut self) -> Result<String, MermaidParseError> {
+        self.index += 1;
+        let text = self
+            .chars
+            .iter()
+            .skip(self.index)
+            .take_while(|c| **c != '"')
+            .collect::<String>();
+        self.index += text.chars().count();
+        if self.peek() != Some('"') {
+            return Err(self.syntax_error(String::from("String is not closed with `\"`.")));
+        }
+        self.index += 1;
+        Ok(text)
+    }
+
+    /// Parses a label up to one of the closing delimiters, returning the
+    /// label and whether it was quoted.
+    fn label_parse(&mut self, closes: &[&str]) -> Result<(String, bool), MermaidParseError> {
+        self.whitespace_skip();
+        let (label, quoted) = if self.peek() == Some('"') {
+            let label = self.quoted_parse()?;
+            self.whitespace_skip();
+            (label, true)
+        } else {
+            let mut label = String::new();
+            while self.peek().is_some() && !closes.iter().any(|close| self.rest_starts_with(close))
+            {
+                label.extend(self.peek());
+                self.index += 1;
+            }
+            (label.trim().to_string(), false)
+        };
+
+        match closes.iter().find(|close| self.rest_starts_with(close)) {
+            Some(close) => {
+                self.index += close.chars().count();
+                Ok((label, quoted))
+            }
+            None => Err(self.syntax_error(format!("Expected `{}` to close the label.", closes[0]))),
+        }
+    }
+
+    /// Parses a link such as `-->`, `-- text -->`, `-.->|text|`, or
+    /// `e1@==>`.
+    fn link_parse(&mut self) -> Result<MermaidLink, MermaidParseError> {
+        let index_start = self.index;
+
+        // `e1@-->`
+        let edge_mermaid_id = {
+            let edge_mermaid_id = self.id_parse();
+            if !edge_mermaid_id.is_empty()
+                && self.peek() == Some('@')
+                && self.peek_at(1).is_some_and(link_char_is_start)
+            {
+                self.index += 1;
+                Some(edge_mermaid_id)
+            } else {
+                self.index = index_start;
+                None
+            }
+        };
+
+        let arrowtail = match self.peek() {
+            Some('<') => Some('<'),
+            Some(c @ ('o' | 'x')) if self.peek_at(1).is_some_and(link_char_is_body) => Some(c),
+            _ => None,
+        };
+        if arrowtail.is_some() {
+            self.index += 1;
+        }
+
+        let mut link_body = self.link_body_parse();
+        let mut label = None;
+        let label_close = match link_body.as_str() {
+            "--" => Some("--"),
+            "==" => Some("=="),
+            "-." => Some(".-"),
+            _ => None,
+        };
+        if let Some(label_close) = label_close.filter(|_| self.arrowhead_peek().is_none()) {
+            // `-- text -->`
+            let mut label_text = String::new();
+            while self.peek().is_some() && !self.rest_starts_with(label_close) {
+                label_text.extend(self.peek());
+                self.index += 1;
+            }
+            if self.peek().is_none() {
+                return Err(
+                    self.syntax_error(format!("Expected `{label_close}` to close the link text."))
+                );
+            }
+            label = Some(label_text.trim().to_string());
+            link_body = self.link_body_parse();
+        }
+
+        let is_link = link_body.chars().count() >= 2 || link_body.contains('.');
+        if !is_link {
+            self.index = index_start;
+            let found = self.peek().map(|c| format!("`{c}`")).unwrap_or_default();
+            return Err(self.syntax_error(format!(
+                "Expected a link such as `-->`, `&`, or the end of the statement, but found {found}."
+            )));
+        }
+
+        let arrowhead = self.arrowhead_peek();
+        if arrowhead.is_some() {
+            self.index += 1;
+        }
+
+        // `-->|text|`
+        self.whitespace_skip();
+        if self.peek() == Some('|') {
+            self.index += 1;
+            let label_text = self
+                .chars
+                .iter()
+                .skip(self.index)
+                .take_while(|c| **c != '|')
+                .collect::<String>();
+            self.index += label_text.chars().count();
+            if self.peek() != Some('|') {
+                return Err(self.syntax_error(String::from("Expected `|` to close the link text.")));
+            }
+            self.index += 1;
+            let label_text = label_text.trim();
+            let label_text = label_text
+                .strip_prefix('"')
+                .and_then(|label_text| label_text.strip_suffix('"'))
+                .unwrap_or(label_text);
+            label = Some(label_text.to_string());
+        }
+
+        let stroke = if link_body.contains('~') {
+            LinkStroke::Invisible
+        } else if link_body.contains('=') {
+            LinkStroke::Thick
+        } else if link_body.contains('.') {
+            LinkStroke::Dotted
+        } else {
+            LinkStroke::Normal
+        };
+        let length = match stroke {
+            LinkStroke::Dotted => link_body.matches('.').count(),
+            LinkStroke::Invisible => 1,
+            LinkStroke::Normal | LinkStroke::Thick => {
+                let line_chars = link_body.chars().count();
+                if arrowhead.is_some() {
+                    line_chars.saturating_sub(1)
+                } else {
+                    line_chars.saturating_sub(2)
+                }
+            }
+        }
+        .max(1);
+
+        Ok(MermaidLink {
+            edge_mermaid_id,
+            arrowtail,
+            arrowhead,
+            stroke,
+            length: length as u32,
+            label,
+        })
+    }
+
+    /// Parses the `-`, `=`, `.`, and `~` characters of a link.
+    fn link_body_parse(&mut self) -> String {
+        let mut link_body = String::new();
+        while let Some(c) = self.peek().filter(|c| link_char_is_body(*c)) {
+            link_body.push(c);
+            self.index += 1;
+        }
+        link_body
+    }
+
+    /// Returns the arrowhead at the end of a link, e.g. `>` in `-->`, or `o` in
+    /// `--o`.
+    fn arrowhead_peek(&self) -> Option<char> {
+        match self.peek() {
+            Some('>') => Some('>'),
+            Some(c @ ('o' | 'x'))
+                if self
+                    .peek_at(1)
+                    .is_none_or(|c_next| c_next.is_whitespace() || c_next == '|') =>
+            {
+                Some(c)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn link_char_is_body(c: char) -> bool {
+    matches!(c, '-' | '=' | '.' | '~')
+}
+
+fn link_char_is_start(c: char) -> bool {
+    link_char_is_body(c) || matches!(c, '<' | 'o' | 'x')
+}
@@ -0,0 +1,930 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
+
+use dot_ix_model::{
+    common::{
+        graphviz_attrs::{ArrowType, EdgeDir},
+        AnyId, EdgeDescs, EdgeId, Edges, GraphvizAttrs, NodeDescs, NodeHierarchy, NodeId,
+        NodeNames, TagId, TagItems, TagNames,
+    },
+    info_graph::{GraphDir, GraphStyle, InfoGraph},
+    theme::{AnyIdOrDefaults, CssClassPartials, Theme, ThemeAttr, ThemeSelector},
+};
+use indexmap::IndexMap;
+
+use crate::{id_allocator::IdAllocator, MermaidParseOutput, MermaidParseWarning, TailwindCssGen};
+
+/// Shape of a Mermaid node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum MermaidShape {
+    /// `a[text]`
+    Rect,
+    /// `a(text)`
+    Round,
+    /// `a([text])`
+    Stadium,
+    /// `a((text))`
+    Circle,
+    /// `a(((text)))`
+    DoubleCircle,
+    /// Shapes that are drawn as a box, e.g. `a{text}`.
+    Other(Cow<'static, str>),
+}
+
+impl MermaidShape {
+    /// Returns the shape for a name in `a@{ shape: name }`.
+    pub(crate) fn from_name(name: &str) -> Self {
+        match name {
+            "rect" | "rectangle" | "proc" | "process" => Self::Rect,
+            "rounded" | "event" => Self::Round,
+            "stadium" | "pill" | "terminal" => Self::Stadium,
+            "circle" | "circ" => Self::Circle,
+            "dbl-circ" | "double-circle" => Self::DoubleCircle,
+            name => Self::Other(Cow::Owned(name.to_string())),
+        }
+    }
+}
+
+/// A node reference in a statement, e.g. `a[text]:::class`.
+#[derive(Clone, Debug)]
+pub(crate) struct MermaidNode {
+    pub(crate) mermaid_id: String,
+    /// The shape and label, if the node has a shape delimiter.
+    pub(crate) shape_and_label: Option<(MermaidShape, Option<String>)>,
+    /// Classes from `:::class`.
+    pub(crate) class_names: Vec<String>,
+    /// Line number, starting from `1`.
+    pub(crate) line: u32,
+}
+
+/// How a link's line is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LinkStroke {
+    /// `-->`
+    Normal,
+    /// `==>`
+    Thick,
+    /// `-.->`
+    Dotted,
+    /// `~~~`
+    Invisible,
+}
+
+/// A link between node groups, e.g. `-- text -->`.
+#[derive(Clone, Debug)]
+pub(crate) struct MermaidLink {
+    /// ID of the link, e.g. `e1` in `e1@-->`.
+    pub(crate) edge_mermaid_id: Option<String>,
+    /// `<`, `o`, or `x` at the start of the link.
+    pub(crate) arrowtail: Option<char>,
+    /// `>`, `o`, or `x` at the end of the link.
+    pub(crate) arrowhead: Option<char>,
+    pub(crate) stroke: LinkStroke,
+    /// Number of ranks the link spans, e.g. `2` for `--->`.
+    pub(crate) length: u32,
+    pub(crate) label: Option<String>,
+}
+
+/// Links that a `linkStyle` statement applies to.
+#[derive(Clone, Debug)]
+pub(crate) enum LinkStyleTargets {
+    /// `linkStyle default`
+    Default,
+    /// `linkStyle 0,2`
+    Indices(Vec<String>),
+}
+
+/// A `subgraph` that has not been closed with `end`.
+#[derive(Clone, Debug)]
+struct SubgraphOpen {
+    node_id: NodeId,
+    mermaid_id: String,
+    line: u32,
+}
+
+/// A statement that refers to nodes or edges, which is applied after all
+/// nodes and edges are known.
+#[derive(Clone, Debug)]
+struct StyleDeferred {
+    target: String,
+    style: String,
+    line: u32,
+}
+
+/// Builds an `InfoGraph` from parsed Mermaid statements.
+pub(crate) struct InfoGraphBuilder {
+    direction: GraphDir,
+    graphviz_attrs: GraphvizAttrs,
+    /// Each node and subgraph, and the subgraph it is in.
+    node_parents: IndexMap<NodeId, Option<NodeId>>,
+    mermaid_id_to_node_id: HashMap<String, NodeId>,
+    node_id_allocator: IdAllocator,
+    node_names: NodeNames,
+    node_descs: NodeDescs,
+    node_shapes: IndexMap<NodeId, (MermaidShape, String, u32)>,
+    edges: Edges,
+    edge_descs: EdgeDescs,
+    edge_id_allocator: IdAllocator,
+    /// Edges in the order they are defined, for `linkStyle` indices.
+    edge_ids: Vec<EdgeId>,
+    edge_mermaid_id_to_edge_id: HashMap<String, EdgeId>,
+    subgraph_ids: HashSet<NodeId>,
+    subgraphs_open: Vec<SubgraphOpen>,
+    theme: Theme,
+    /// Each `classDef` name, and its style.
+    class_defs: IndexMap<String, (String, u32)>,
+    /// `class` and `:::class` assignments, as the target and class name.
+    class_assignments: Vec<StyleDeferred>,
+    styles_deferred: Vec<StyleDeferred>,
+    link_styles_deferred: Vec<(LinkStyleTargets, String, u32)>,
+    mermaid_ids: IndexMap<AnyId, String>,
+    mermaid_parse_warnings: Vec<MermaidParseWarning>,
+}
+
+impl InfoGraphBuilder {
+    pub(crate) fn new() -> Self {
+        Self {
+            // Mermaid's default is `TB`.
+            direction: GraphDir::Vertical,
+            graphviz_attrs: GraphvizAttrs::new(),
+            node_parents: IndexMap::new(),
+            mermaid_id_to_node_id: HashMap::new(),
+            node_id_allocator: IdAllocator::new(),
+            node_names: NodeNames::new(),
+            node_descs: NodeDescs::new(),
+            node_shapes: IndexMap::new(),
+            edges: Edges::new(),
+            edge_descs: EdgeDescs::new(),
+            edge_id_allocator: IdAllocator::new(),
+            edge_ids: Vec::new(),
+            edge_mermaid_id_to_edge_id: HashMap::new(),
+            subgraph_ids: HashSet::new(),
+            subgraphs_open: Vec::new(),
+            theme: Theme::new(),
+            class_defs: IndexMap::new(),
+            class_assignments: Vec::new(),
+            styles_deferred: Vec::new(),
+            link_styles_deferred: Vec::new(),
+            mermaid_ids: IndexMap::new(),
+            mermaid_parse_warnings: Vec::new(),
+        }
+    }
+
+    /// Applies the direction from `flowchart LR`.
+    pub(crate) fn direction_apply(&mut self, direction: &str, line: u32) -> Result<(), String> {
+        self.direction = match direction {
+            "TB" | "TD" => GraphDir::Vertical,
+            "LR" => GraphDir::Horizontal,
+            "BT" | "RL" => {
+                self.mermaid_parse_warnings
+                    .push(MermaidParseWarning::DirectionReversed {
+                        direction: direction.to_string(),
+                        line,
+                    });
+                if direction == "BT" {
+                    GraphDir::Vertical
+                } else {
+                    GraphDir::Horizontal
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "Expected `TB`, `TD`, `BT`, `LR`, or `RL`, but found `{direction}`."
+                ));
+            }
+        };
+        Ok(())
+    }
+
+    pub(crate) fn subgraph_begin(&mut self, mermaid_id: String, title: Option<String>, line: u32) {
+        let node_id = self.node_reference(&mermaid_id, line);
+
+        // A node that was used before its `subgraph` is a cluster, so it has
+        // no shape.
+        self.node_shapes.shift_remove(&node_id);
+        self.subgraph_ids.insert(node_id.clone());
+        if let Some(title) = title {
+            self.label_apply(&node_id, &title);
+        }
+
+        self.subgraphs_open.push(SubgraphOpen {
+            node_id,
+            mermaid_id,
+            line,
+        });
+    }
+
+    /// Ends the current `subgraph`, returning `false` if there is none.
+    pub(crate) fn subgraph_end(&mut self) -> bool {
+        self.subgraphs_open.pop().is_some()
+    }
+
+    /// Returns the ID and line of the innermost `subgraph` that is not closed.
+    pub(crate) fn subgraph_open(&self) -> Option<(String, u32)> {
+        self.subgraphs_open
+            .last()
+            .map(|subgraph_open| (subgraph_open.mermaid_id.clone(), subgraph_open.line))
+    }
+
+    pub(crate) fn node_apply(&mut self, mermaid_node: MermaidNode) {
+        let MermaidNode {
+            mermaid_id,
+            shape_and_label,
+            class_names,
+            line,
+        } = mermaid_node;
+
+        let node_id = self.node_reference(&mermaid_id, line);
+        if let Some((shape, label)) = shape_and_label {
+            if let Some(label) = label {
+                self.label_apply(&node_id, &label);
+            }
+            if !self.subgraph_ids.contains(&node_id) {
+                self.node_shapes
+                    .insert(node_id, (shape, mermaid_id.clone(), line));
+            }
+        }
+
+        class_names.into_iter().for_each(|class_name| {
+            self.class_assignments.push(StyleDeferred {
+                target: mermaid_id.clone(),
+                style: class_name,
+                line,
+            })
+        });
+    }
+
+    /// Returns the `NodeId` for a Mermaid ID, creating the node in the current
+    /// subgraph if it doesn't exist.
+    fn node_reference(&mut self, mermaid_id: &str, line: u32) -> NodeId {
+        let subgraph_id = self
+            .subgraphs_open
+            .last()
+            .map(|subgraph_open| subgraph_open.node_id.clone());
+
+        if let Some(node_id) = self.mermaid_id_to_node_id.get(mermaid_id).cloned() {
+            // Like Mermaid, a node used at the top level and then in a subgraph
+            // belongs to the subgraph.
+            let node_parent = self.node_parents.get_mut(&node_id);
+            if let (Some(node_parent @ None), Some(subgraph_id)) = (node_parent, subgraph_id)
+                && subgraph_id != node_id
+            {
+                *node_parent = Some(subgraph_id);
+            }
+            return node_id;
+        }
+
+        let id = self.id_alloc(mermaid_id, line);
+        let node_id = NodeId::try_from(id).expect("Sanitized `NodeId` to be valid.");
+        if node_id.as_str() != mermaid_id {
+            self.node_names
+                .insert(node_id.clone(), mermaid_id.to_string());
+        }
+        self.node_parents.insert(node_id.clone(), subgraph_id);
+        self.mermaid_id_to_node_id
+            .insert(mermaid_id.to_string(), node_id.clone());
+        self.mermaid_ids
+            .insert(AnyId::from(node_id.clone()), mermaid_id.to_string());
+
+        node_id
+    }
+
+    /// Sets the node's name to the label's first line, and its description to
+    /// the remaining lines.
+    fn label_apply(&mut self, node_id: &NodeId, label: &str) {
+        let label = label_text(label);
+        let (node_name, node_desc) = match label.split_once('\n') {
+            Some((node_name, node_desc)) => (node_name, Some(node_desc)),
+            None => (label.as_str(), None),
+        };
+
+        self.node_names
+            .insert(node_id.clone(), node_name.to_string());
+        match node_desc {
+            Some(node_desc) => {
+                self.node_descs
+                    .insert(node_id.clone(), node_desc.to_string());
+            }
+            None => {
+                self.node_descs.shift_remove(node_id);
+            }
+        }
+    }
+
+    /// Creates an edge from each source node to each target node.
+    pub(crate) fn edges_apply(
+        &mut self,
+        src_mermaid_ids: &[String],
+        target_mermaid_ids: &[String],
+        mermaid_link: &MermaidLink,
+        line: u32,
+    ) {
+        src_mermaid_ids.iter().for_each(|src_mermaid_id| {
+            target_mermaid_ids.iter().for_each(|target_mermaid_id| {
+                self.edge_create(src_mermaid_id, target_mermaid_id, mermaid_link, line)
+            })
+        });
+    }
+
+    fn edge_create(
+        &mut self,
+        src_mermaid_id: &str,
+        target_mermaid_id: &str,
+        mermaid_link: &MermaidLink,
+        line: u32,
+    ) {
+        let src_node_id = self.node_reference(src_mermaid_id, line);
+        let target_node_id = self.node_reference(target_mermaid_id, line);
+
+        let edge_id = match mermaid_link.edge_mermaid_id.as_deref() {
+            Some(edge_mermaid_id) => {
+                let id = self.edge_id_allocator.alloc(edge_mermaid_id);
+                if id != edge_mermaid_id {
+                    self.mermaid_parse_warnings
+                        .push(MermaidParseWarning::IdRenamed {
+                            mermaid_id: edge_mermaid_id.to_string(),
+                            id: id.clone(),
+                            line,
+                        });
+                }
+                let edge_id = EdgeId::try_from(id).expect("Sanitized `EdgeId` to be valid.");
+                self.edge_mermaid_id_to_edge_id
+                    .insert(edge_mermaid_id.to_string(), edge_id.clone());
+                self.mermaid_ids
+                    .insert(AnyId::from(edge_id.clone()), edge_mermaid_id.to_string());
+                edge_id
+            }
+            None => {
+                let id = self
+                    .edge_id_allocator
+                    .alloc(&format!("{src_node_id}__{target_node_id}"));
+                EdgeId::try_from(id).expect("Generated `EdgeId` to be valid.")
+            }
+        };
+
+        self.edges
+            .insert(edge_id.clone(), [src_node_id, target_node_id]);
+        self.edge_ids.push(edge_id.clone());
+
+        if let Some(label) = mermaid_link.label.as_deref() {
+            self.edge_descs.insert(edge_id.clone(), label_text(label));
+        }
+
+        let edge_dir = match (mermaid_link.arrowtail, mermaid_link.arrowhead) {
+            (Some(_), Some(_)) => EdgeDir::Both,
+            (None, Some(_)) => EdgeDir::Forward,
+            (Some(_), None) => EdgeDir::Back,
+            (None, None) => EdgeDir::None,
+        };
+        if edge_dir != self.graphviz_attrs.edge_dir_default {
+            self.graphviz_attrs
+                .edge_dirs
+                .insert(edge_id.clone(), edge_dir);
+        }
+
+        let el = format!("edge `{edge_id}`");
+        if let Some(arrowtail) = mermaid_link
+            .arrowtail
+            .and_then(|arrowtail| self.arrow_type(arrowtail, &el, "arrowtail", line))
+        {
+            self.graphviz_attrs
+                .edge_arrowtails
+                .insert(edge_id.clone(), arrowtail);
+        }
+        if let Some(arrowhead) = mermaid_link
+            .arrowhead
+            .and_then(|arrowhead| self.arrow_type(arrowhead, &el, "arrowhead", line))
+        {
+            self.graphviz_attrs
+                .edge_arrowheads
+                .insert(edge_id.clone(), arrowhead);
+        }
+
+        let stroke_style = match mermaid_link.stroke {
+            LinkStroke::Normal => None,
+            LinkStroke::Thick => Some((ThemeAttr::StrokeWidth, "2")),
+            LinkStroke::Dotted => Some((ThemeAttr::StrokeStyle, "dashed")),
+            LinkStroke::Invisible => Some((ThemeAttr::Visibility, "invisible")),
+        };
+        if let Some((theme_attr, value)) = stroke_style {
+            let mut css_class_partials = CssClassPartials::new();
+            css_class_partials.insert(theme_attr, value.to_string());
+            self.theme_styles_merge(
+                AnyIdOrDefaults::AnyId(AnyId::from(edge_id.clone())),
+                css_class_partials,
+            );
+        }
+
+        if mermaid_link.length > 1 {
+            let edge_minlen = self.graphviz_attrs.edge_minlen_default * mermaid_link.length;
+            self.graphviz_attrs
+                .edge_minlens
+                .insert(edge_id, edge_minlen);
+        }
+    }
+
+    /// Returns the arrow type for `o` or `x` at the end of a link, or `None`
+    /// for the usual arrow.
+    fn arrow_type(
+        &mut self,
+        arrow: char,
+        el: &str,
+        attr_name: &str,
+        line: u32,
+    ) -> Option<ArrowType> {
+        match arrow {
+            'o' => "dot".parse::<ArrowType>().ok(),
+            'x' => {
+                self.mermaid_parse_warnings
+                    .push(MermaidParseWarning::StyleUnsupported {
+                        el: el.to_string(),
+                        style: format!("{attr_name}:x"),
+                        line,
+                    });
+                None
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn class_def_apply(&mut self, class_names: Vec<String>, style: &str, line: u32) {
+        class_names.into_iter().for_each(|class_name| {
+            self.class_defs
+                .insert(class_name, (style.to_string(), line));
+        });
+    }
+
+    pub(crate) fn class_apply(&mut self, targets: Vec<String>, class_name: String, line: u32) {
+        targets.into_iter().for_each(|target| {
+            self.class_assignments.push(StyleDeferred {
+                target,
+                style: class_name.clone(),
+                line,
+            })
+        });
+    }
+
+    pub(crate) fn style_apply(&mut self, target: String, style: &str, line: u32) {
+        self.styles_deferred.push(StyleDeferred {
+            target,
+            style: style.to_string(),
+            line,
+        });
+    }
+
+    pub(crate) fn link_style_apply(
+        &mut self,
+        link_style_targets: LinkStyleTargets,
+        style: &str,
+        line: u32,
+    ) {
+        self.link_styles_deferred
+            .push((link_style_targets, style.to_string(), line));
+    }
+
+    pub(crate) fn statement_unsupported(&mut self, keyword: String, line: u32) {
+        self.mermaid_parse_warnings
+            .push(MermaidParseWarning::StatementUnsupported { keyword, line });
+    }
+
+    /// Returns the ID of the node or edge with the given Mermaid ID.
+    fn any_id_find(&self, mermaid_id: &str) -> Option<AnyId> {
+        self.mermaid_id_to_node_id
+            .get(mermaid_id)
+            .cloned()
+            .map(AnyId::from)
+            .or_else(|| {
+                self.edge_mermaid_id_to_edge_id
+                    .get(mermaid_id)
+                    .cloned()
+                    .map(AnyId::from)
+            })
+    }
+
+    /// Returns a valid and unused ID for a node or tag, warning if it differs.
+    fn id_alloc(&mut self, mermaid_id: &str, line: u32) -> String {
+        let id = self.node_id_allocator.alloc(mermaid_id);
+        if id != mermaid_id {
+            self.mermaid_parse_warnings
+                .push(MermaidParseWarning::IdRenamed {
+                    mermaid_id: mermaid_id.to_string(),
+                    id: id.clone(),
+                    line,
+                });
+        }
+        id
+    }
+
+    fn target_missing(&mut self, target: String, line: u32) {
+        self.mermaid_parse_warnings
+            .push(MermaidParseWarning::StyleTargetMissing { target, line });
+    }
+
+    /// Merges the styles into the theme's styles for the key.
+    fn theme_styles_merge(
+        &mut self,
+        any_id_or_defaults: AnyIdOrDefaults,
+        css_class_partials: CssClassPartials,
+    ) {
+        if css_class_partials.is_empty() {
+            return;
+        }
+        self.theme
+            .entry(any_id_or_defaults)
+            .or_default()
+            .extend(css_class_partials.into_inner());
+    }
+
+    /// Returns the theme attributes for Mermaid styles, e.g.
+    /// `fill:#f9f,stroke:#333,stroke-width:4px`.
+    fn css_class_partials(&mut self, style: &str, el: &str, line: u32) -> CssClassPartials {
+        let mut css_class_partials = CssClassPartials::new();
+        style_split(style).for_each(|property_value| {
+            let parsed = property_value
+                .split_once(':')
+                .map(|(property, value)| (property.trim(), value.trim()))
+                .and_then(|(property, value)| {
+                    style_theme_attrs(property, value).filter(|theme_attrs| !theme_attrs.is_empty())
+                });
+            match parsed {
+                Some(theme_attrs) => theme_attrs.into_iter().for_each(|(theme_attr, value)| {
+                    css_class_partials.insert(theme_attr, value);
+                }),
+                None => self
+                    .mermaid_parse_warnings
+                    .push(MermaidParseWarning::StyleUnsupported {
+                        el: el.to_string(),
+                        style: property_value.to_string(),
+                        line,
+                    }),
+            }
+        });
+        css_class_partials
+    }
+
+    pub(crate) fn build(mut self) -> MermaidParseOutput {
+        // `style`
+        std::mem::take(&mut self.styles_deferred)
+            .into_iter()
+            .for_each(
+                |StyleDeferred {
+                     target,
+                     style,
+                     line,
+                 }| {
+                    match self.any_id_find(&target) {
+                        Some(any_id) => {
+                            let el = format!("`{target}`");
+                            let css_class_partials = self.css_class_partials(&style, &el, line);
+                            self.theme_styles_merge(
+                                AnyIdOrDefaults::AnyId(any_id),
+                                css_class_partials,
+                            );
+                        }
+                        None => self.target_missing(target, line),
+                    }
+                },
+            );
+
+        // `linkStyle`
+        std::mem::take(&mut self.link_styles_deferred)
+            .into_iter()
+            .for_each(
+                |(link_style_targets, style, line)| match link_style_targets {
+                    LinkStyleTargets::Default => {
+                        let css_class_partials =
+                            self.css_class_partials(&style, "`linkStyle default`", line);
+                        self.theme_styles_merge(AnyIdOrDefaults::EdgeDefaults, css_class_partials);
+                    }
+                    LinkStyleTargets::Indices(indices) => indices.into_iter().for_each(|index| {
+                        let edge_id = index
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|index| self.edge_ids.get(index))
+                            .cloned();
+                        match edge_id {
+                            Some(edge_id) => {
+                                let el = format!("`linkStyle {index}`");
+                                let css_class_partials = self.css_class_partials(&style, &el, line);
+                                self.theme_styles_merge(
+                                    AnyIdOrDefaults::AnyId(AnyId::from(edge_id)),
+                                    css_class_partials,
+                                );
+                            }
+                            None => self.target_missing(index, line),
+                        }
+                    }),
+                },
+            );
+
+        // `classDef` and `class`, which become tags. These are allocated after
+        // nodes, so that nodes keep their IDs when a class has the same name.
+        let mut tag_names = TagNames::new();
+        let mut tag_items = TagItems::new();
+        let mut class_name_to_tag_id = HashMap::<String, TagId>::new();
+        let class_names =
+            self.class_defs
+                .iter()
+                .map(|(class_name, (_style, line))| (class_name.clone(), *line))
+                .chain(self.class_assignments.iter().map(|class_assignment| {
+                    (class_assignment.style.clone(), class_assignment.line)
+                }))
+                .collect::<Vec<_>>();
+        class_names
+            .into_iter()
+            .filter(|(class_name, _line)| class_name != "default")
+            .for_each(|(class_name, line)| {
+                if class_name_to_tag_id.contains_key(&class_name) {
+                    return;
+                }
+                let id = self.id_alloc(&class_name, line);
+                let tag_id = TagId::try_from(id).expect("Sanitized `TagId` to be valid.");
+                tag_names.insert(tag_id.clone(), class_name.clone());
+                tag_items.insert(tag_id.clone(), Vec::new());
+                self.mermaid_ids
+                    .insert(AnyId::from(tag_id.clone()), class_name.clone());
+                class_name_to_tag_id.insert(class_name, tag_id);
+            });
+
+        std::mem::take(&mut self.class_assignments)
+            .into_iter()
+            .for_each(
+                |StyleDeferred {
+                     target,
+                     style,
+                     line,
+                 }| {
+                    let Some(tag_id) = class_name_to_tag_id.get(&style) else {
+                        return;
+                    };
+                    match self.any_id_find(&target) {
+                        Some(any_id) => {
+                            let items = tag_items.entry(tag_id.clone()).or_default();
+                            if !items.contains(&any_id) {
+                                items.push(any_id);
+                            }
+                        }
+                        None => self.target_missing(target, line),
+                    }
+                },
+            );
+
+        std::mem::take(&mut self.class_defs)
+            .into_iter()
+            .for_each(|(class_name, (style, line))| {
+                let el = format!("classDef `{class_name}`");
+                let css_class_partials = self.css_class_partials(&style, &el, line);
+                let any_id_or_defaults = match class_name_to_tag_id.get(&class_name) {
+                    Some(tag_id) => AnyIdOrDefaults::Selector(ThemeSelector::Tag(tag_id.clone())),
+                    None => AnyIdOrDefaults::NodeDefaults,
+                };
+                self.theme_styles_merge(any_id_or_defaults, css_class_partials);
+            });
+
+        // Shapes.
+        let node_ids_leaf = self
+            .node_parents
+            .keys()
+            .filter(|node_id| !self.subgraph_ids.contains(*node_id))
+            .cloned()
+            .collect::<Vec<NodeId>>();
+        let graph_style = if !node_ids_leaf.is_empty()
+            && node_ids_leaf.iter().all(|node_id| {
+                matches!(
+                    self.node_shapes.get(node_id),
+                    Some((MermaidShape::Circle | MermaidShape::DoubleCircle, ..))
+                )
+            }) {
+            GraphStyle::Circle
+        } else {
+            GraphStyle::Box
+        };
+        std::mem::take(&mut self.node_shapes).into_iter().for_each(
+            |(node_id, (shape, mermaid_id, line))| {
+                let corner_radius = match (shape, graph_style) {
+                    (MermaidShape::Rect, _) => None,
+                    (MermaidShape::Round, _) => Some("lg"),
+                    (MermaidShape::Stadium, _) => Some("full"),
+                    (MermaidShape::Circle | MermaidShape::DoubleCircle, GraphStyle::Circle) => None,
+                    (
+                        shape @ (MermaidShape::Circle | MermaidShape::DoubleCircle),
+                        GraphStyle::Box,
+                    )
+                    | (shape @ MermaidShape::Other(_), _) => {
+                        let shape = match shape {
+                            MermaidShape::Circle => Cow::Borrowed("circle"),
+                            MermaidShape::DoubleCircle => Cow::Borrowed("double circle"),
+                            MermaidShape::Other(shape) => shape,
+                            _ => unreachable!("Other shapes are matched above."),
+                        };
+                        self.mermaid_parse_warnings
+                            .push(MermaidParseWarning::ShapeUnsupported {
+                                mermaid_id,
+                                shape: shape.into_owned(),
+                                line,
+                            });
+                        None
+                    }
+                };
+                if let Some(corner_radius) = corner_radius {
+                    let mut css_class_partials = CssClassPartials::new();
+                    css_class_partials.insert(ThemeAttr::CornerRadius, corner_radius.to_string());
+                    self.theme_styles_merge(
+                        AnyIdOrDefaults::AnyId(AnyId::from(node_id)),
+                        css_class_partials,
+                    );
+                }
+            },
+        );
+
+        let InfoGraphBuilder {
+            direction,
+            graphviz_attrs,
+            node_parents,
+            node_names,
+            node_descs,
+            edges,
+            edge_descs,
+            theme,
+            mermaid_ids,
+            mermaid_parse_warnings,
+            ..
+        } = self;
+
+        let mut parent_to_children = IndexMap::<Option<&NodeId>, Vec<&NodeId>>::new();
+        node_parents.iter().for_each(|(node_id, node_parent)| {
+            parent_to_children
+                .entry(node_parent.as_ref())
+                .or_default()
+                .push(node_id);
+        });
+        let hierarchy = node_hierarchy_build(&parent_to_children, None);
+
+        let info_graph = InfoGraph::default()
+            .with_graph_style(graph_style)
+            .with_direction(direction)
+            .with_hierarchy(hierarchy)
+            .with_node_names(node_names)
+            .with_node_descs(node_descs)
+            .with_edges(edges)
+            .with_edge_descs(edge_descs)
+            .with_tags(tag_names)
+            .with_tag_items(tag_items)
+            .with_graphviz_attrs(graphviz_attrs)
+            .with_theme(theme);
+
+        MermaidParseOutput {
+            info_graph,
+            mermaid_ids,
+            mermaid_parse_warnings,
+        }
+    }
+}
+
+fn node_hierarchy_build(
+    parent_to_children: &IndexMap<Option<&NodeId>, Vec<&NodeId>>,
+    parent: Option<&NodeId>,
+) -> NodeHierarchy {
+    let mut node_hierarchy = NodeHierarchy::new();
+    parent_to_children
+        .get(&parent)
+        .into_iter()
+        .flatten()
+        .for_each(|node_id| {
+            let child_hierarchy = node_hierarchy_build(parent_to_children, Some(node_id));
+            node_hierarchy.insert((*node_id).clone(), child_hierarchy);
+        });
+    node_hierarchy
+}
+
+/// Returns the label text with `<br>`s as line breaks, entity codes such as
+/// `#quot;` decoded, and markdown string backticks removed.
+fn label_text(label: &str) -> String {
+    let label = label
+        .strip_prefix('`')
+        .and_then(|label| label.strip_suffix('`'))
+        .unwrap_or(label);
+
+    let mut text = String::with_capacity(label.len());
+    let mut rest = label;
+    while let Some(c) = rest.chars().next() {
+        let is_br = rest
+            .get(..3)
+            .is_some_and(|tag| tag.eq_ignore_ascii_case("<br"))
+            && matches!(rest[3..].chars().next(), Some('>' | '/' | ' '));
+        if let Some(br_end) = rest.find('>').filter(|_| is_br) {
+            text.push('\n');
+            rest = &rest[br_end + 1..];
+            continue;
+        }
+        if c == '#' {
+            let entity = rest[1..]
+                .find(';')
+                .filter(|entity_end| *entity_end <= 8)
+                .and_then(|entity_end| {
+                    let entity = &rest[1..=entity_end];
+                    entity_decode(entity).map(|decoded| (decoded, entity_end + 2))
+                });
+            if let Some((decoded, entity_len)) = entity {
+                text.push(decoded);
+                rest = &rest[entity_len..];
+                continue;
+            }
+        }
+        text.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    text
+}
+
+/// Returns the character for a Mermaid entity code, e.g. `quot` or `35`.
+fn entity_decode(entity: &str) -> Option<char> {
+    match entity {
+        "quot" => Some('"'),
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        entity => entity.parse::<u32>().ok().and_then(char::from_u32),
+    }
+}
+
+/// Splits `fill:#f9f,stroke:rgb(1, 2, 3)` on commas that are not within
+/// parentheses.
+fn style_split(style: &str) -> impl Iterator<Item = &str> {
+    let style = style.trim().trim_end_matches(';');
+    let mut depth = 0u32;
+    let mut start = 0;
+    let mut parts = Vec::new();
+    style.char_indices().for_each(|(index, c)| match c {
+        '(' => depth += 1,
+        ')' => depth = depth.saturating_sub(1),
+        ',' if depth == 0 => {
+            parts.push(&style[start..index]);
+            start = index + 1;
+        }
+        _ => {}
+    });
+    parts.push(&style[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+}
+
+/// Returns the theme attributes for a Mermaid style property, or `None` if it
+/// is not supported.
+fn style_theme_attrs(property: &str, value: &str) -> Option<Vec<(ThemeAttr, String)>> {
+    let color_attrs = |color_attr: ThemeAttr, shade_attr: ThemeAttr| {
+        TailwindCssGen::color_nearest(value).map(|(color, shade)| {
+            vec![
+                (color_attr, color.to_string()),
+                (shade_attr, shade.to_string()),
+            ]
+        })
+    };
+
+    match property {
+        "fill" => color_attrs(ThemeAttr::FillColor, ThemeAttr::FillShadeNormal),
+        "stroke" => color_attrs(ThemeAttr::StrokeColor, ThemeAttr::StrokeShadeNormal),
+        "color" => color_attrs(ThemeAttr::TextColor, ThemeAttr::TextShadeNormal),
+        "stroke-width" => {
+            let width = value.trim_end_matches("px").trim().parse::<f64>().ok()?;
+            Some(vec![(
+                ThemeAttr::StrokeWidth,
+                format!("{}", width.round().max(0.0) as u32),
+            )])
+        }
+        "stroke-dasharray" => {
+            let stroke_style = match value {
+                "0" | "none" => "solid",
+                _ => "dashed",
+            };
+            Some(vec![(ThemeAttr::StrokeStyle, stroke_style.to_string())])
+        }
+        "font-weight" => {
+            let font_weight = match value {
+                "100" => "thin",
+                "200" => "extralight",
+                "300" | "lighter" => "light",
+                "400" | "normal" => "normal",
+                "500" => "medium",
+                "600" => "semibold",
+                "700" | "bold" | "bolder" => "bold",
+                "800" => "extrabold",
+                "900" => "black",
+                _ => return None,
+            };
+            Some(vec![(ThemeAttr::FontWeight, font_weight.to_string())])
+        }
+        "opacity" => {
+            let opacity = value.parse::<f64>().ok()?;
+            let percent = (opacity.clamp(0.0, 1.0) * 100.0).round() as u32;
+            Some(vec![(ThemeAttr::Opacity, percent.to_string())])
+        }
+        _ => None,
+    }
+}
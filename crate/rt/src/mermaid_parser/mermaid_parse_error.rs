@@ -0,0 +1,37 @@
+/// Errors when parsing Mermaid source with [`MermaidParser`].
+///
+/// [`MermaidParser`]: crate::MermaidParser
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum MermaidParseError {
+    /// The Mermaid source does not contain a diagram.
+    #[error("Mermaid source is empty, expected a `flowchart` or `graph`.")]
+    Empty,
+    /// The diagram is not a `flowchart` or `graph`.
+    #[error(
+        "Line {line} begins a `{diagram_type}`, but only `flowchart` and `graph` are supported."
+    )]
+    DiagramTypeUnsupported {
+        /// The diagram type, e.g. `sequenceDiagram`.
+        diagram_type: String,
+        /// Line number, starting from `1`.
+        line: u32,
+    },
+    /// The Mermaid source is not valid flowchart syntax.
+    #[error("Syntax error at line {line}, column {column}: {message}")]
+    Syntax {
+        /// Line number, starting from `1`.
+        line: u32,
+        /// Column number in characters, starting from `1`.
+        column: u32,
+        /// What was expected or found.
+        message: String,
+    },
+    /// A `subgraph` is not closed with `end`.
+    #[error("`subgraph {subgraph_id}` on line {line} is not closed with `end`.")]
+    SubgraphNotClosed {
+        /// ID of the subgraph in the Mermaid source.
+        subgraph_id: String,
+        /// Line number of the `subgraph`, starting from `1`.
+        line: u32,
+    },
+}
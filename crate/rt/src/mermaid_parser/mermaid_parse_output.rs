@@ -0,0 +1,38 @@
+use dot_ix_model::{common::AnyId, info_graph::InfoGraph};
+use indexmap::IndexMap;
+
+use crate::MermaidParseWarning;
+
+/// The `InfoGraph` parsed from Mermaid source, and what could not be imported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MermaidParseOutput {
+    /// The parsed graph.
+    pub info_graph: InfoGraph,
+    /// Each node, edge, and tag ID in the `InfoGraph`, and the Mermaid ID or
+    /// class name it was created from.
+    ///
+    /// Edges are only included when they have an ID in the Mermaid source,
+    /// e.g. `e1` in `a e1@--> b`.
+    pub mermaid_ids: IndexMap<AnyId, String>,
+    /// Shapes, styles, and statements that were not imported as is.
+    pub mermaid_parse_warnings: Vec<MermaidParseWarning>,
+}
+
+impl MermaidParseOutput {
+    /// Returns the parsed graph.
+    pub fn info_graph(&self) -> &InfoGraph {
+        &self.info_graph
+    }
+
+    /// Returns each ID in the `InfoGraph`, and the Mermaid ID or class name it
+    /// was created from.
+    pub fn mermaid_ids(&self) -> &IndexMap<AnyId, String> {
+        &self.mermaid_ids
+    }
+
+    /// Returns the shapes, styles, and statements that were not imported as
+    /// is.
+    pub fn mermaid_parse_warnings(&self) -> &[MermaidParseWarning] {
+        &self.mermaid_parse_warnings
+    }
+}
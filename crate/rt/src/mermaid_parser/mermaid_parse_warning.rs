@@ -0,0 +1,95 @@
+use std::fmt;
+
+/// Something in the Mermaid source that was not imported as is.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MermaidParseWarning {
+    /// The Mermaid ID is not a valid `NodeId`, `EdgeId`, or `TagId`, so a
+    /// different ID is used.
+    IdRenamed {
+        /// ID or class name in the Mermaid source.
+        mermaid_id: String,
+        /// ID in the `InfoGraph`.
+        id: String,
+        /// Line number where the ID is first used, starting from `1`.
+        line: u32,
+    },
+    /// `RL` and `BT` are imported as `LR` and `TB`.
+    DirectionReversed {
+        /// The direction in the Mermaid source.
+        direction: String,
+        /// Line number, starting from `1`.
+        line: u32,
+    },
+    /// The node shape is not supported, so the node is drawn as a box.
+    ShapeUnsupported {
+        /// ID of the node in the Mermaid source.
+        mermaid_id: String,
+        /// Name of the shape, e.g. `rhombus`.
+        shape: String,
+        /// Line number, starting from `1`.
+        line: u32,
+    },
+    /// The style property is not supported, and was skipped.
+    StyleUnsupported {
+        /// The element that the style is for, e.g. ``classDef `db` ``.
+        el: String,
+        /// The `property:value` pair.
+        style: String,
+        /// Line number, starting from `1`.
+        line: u32,
+    },
+    /// The node, edge, or link index that a `style`, `class`, or `linkStyle`
+    /// applies to does not exist.
+    StyleTargetMissing {
+        /// The node ID, edge ID, or link index.
+        target: String,
+        /// Line number, starting from `1`.
+        line: u32,
+    },
+    /// The statement is not supported, and was skipped.
+    StatementUnsupported {
+        /// The statement's keyword, e.g. `click`.
+        keyword: String,
+        /// Line number, starting from `1`.
+        line: u32,
+    },
+}
+
+impl fmt::Display for MermaidParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IdRenamed {
+                mermaid_id,
+                id,
+                line,
+            } => write!(
+                f,
+                "line {line}: `{mermaid_id}` is not a valid ID, and was renamed to `{id}`."
+            ),
+            Self::DirectionReversed { direction, line } => write!(
+                f,
+                "line {line}: direction `{direction}` is not supported, and is drawn in the opposite direction."
+            ),
+            Self::ShapeUnsupported {
+                mermaid_id,
+                shape,
+                line,
+            } => write!(
+                f,
+                "line {line}: shape `{shape}` on `{mermaid_id}` is not supported, and is drawn as a box."
+            ),
+            Self::StyleUnsupported { el, style, line } => write!(
+                f,
+                "line {line}: `{style}` on {el} is not supported, and was skipped."
+            ),
+            Self::StyleTargetMissing { target, line } => write!(
+                f,
+                "line {line}: `{target}` does not exist, so its style was skipped."
+            ),
+            Self::StatementUnsupported { keyword, line } => write!(
+                f,
+                "line {line}: `{keyword}` statements are not supported, and were skipped."
+            ),
+        }
+    }
+}
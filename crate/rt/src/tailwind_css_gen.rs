@@ -0,0 +1,169 @@
+use indexmap::IndexMap;
+
+use self::utility_css::UtilityCss;
+
+mod class_selector;
+mod tailwind_colors;
+mod utility_css;
+
+/// Generates plain CSS for the Tailwind CSS utility classes that `dot_ix`
+/// produces, so that a rendered diagram doesn't need Tailwind to be run.
+///
+/// This covers the subset of classes computed by [`InfoGraphDot`] and
+/// [`CssClassMerger`], e.g.:
+///
+/// * Colours: `stroke-slate-600`, `fill-lime-200`, `outline-blue-500`.
+/// * Line widths and styles: `stroke-2`, `outline-[2px]`, `outline-dashed`,
+///   `[stroke-dasharray:3]`.
+/// * Element variants: `[&>path]:`, `[&>ellipse]:`, `[&>polygon]:`.
+/// * State variants: `focus:`, `hover:`, `active:`.
+/// * Tag focus variants: `peer-focus/{tag_id}:`.
+/// * Spacing, cursor, visibility, rounding, and `animate-*` classes.
+///
+/// Classes that are not recognized are skipped.
+///
+/// [`InfoGraphDot`]: crate::InfoGraphDot
+/// [`CssClassMerger`]: dot_ix_model::theme::CssClassMerger
+#[derive(Clone, Debug, Default)]
+pub struct TailwindCssGen {
+    /// Each unique class and the CSS rule generated for it.
+    rules: IndexMap<String, Option<String>>,
+    /// `@keyframes` rules used by `animate-*` classes, keyed by name.
+    keyframes: IndexMap<&'static str, &'static str>,
+}
+
+impl TailwindCssGen {
+    /// Returns a new `TailwindCssGen`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the CSS for all classes in the given space separated class
+    /// strings.
+    pub fn generate<'c, I>(classes: I) -> String
+    where
+        I: IntoIterator<Item = &'c str>,
+    {
+        let mut tailwind_css_gen = Self::new();
+        classes
+            .into_iter()
+            .for_each(|classes| tailwind_css_gen.classes_add(classes));
+        tailwind_css_gen.build()
+    }
+
+    /// Adds space separated CSS classes to generate rules for.
+    pub fn classes_add(&mut self, classes: &str) {
+        classes
+            .split_whitespace()
+            .for_each(|class| self.class_add(class));
+    }
+
+    /// Adds a single CSS class to generate a rule for.
+    pub fn class_add(&mut self, class: &str) {
+        // Marker classes, such as `peer/{tag_id}`, are only used within other classes'
+        // selectors.
+        if self.rules.contains_key(class)
+            || class.starts_with("peer/")
+            || class.starts_with("group/")
+        {
+            return;
+        }
+
+        let rule = Self::class_rule(class).map(|(rule, keyframes)| {
+            if let Some((name, keyframes)) = keyframes {
+                self.keyframes.insert(name, keyframes);
+            }
+            rule
+        });
+        self.rules.insert(class.to_string(), rule);
+    }
+
+    /// Returns the classes that were added, but could not be translated into
+    /// CSS.
+    pub fn classes_unsupported(&self) -> impl Iterator<Item = &str> {
+        self.rules
+            .iter()
+            .filter(|(_class, rule)| rule.is_none())
+            .map(|(class, _rule)| class.as_str())
+    }
+
+    /// Returns the generated CSS.
+    ///
+    /// Rules are written in the order Tailwind CSS orders their variants, so
+    /// `hover:` rules come before `focus:` rules, whichever class was added
+    /// first.
+    pub fn build(&self) -> String {
+        let capacity = self
+            .rules
+            .values()
+            .flatten()
+            .map(|rule| rule.len() + 1)
+            .sum::<usize>()
+            + self
+                .keyframes
+                .values()
+                .map(|keyframes| keyframes.len() + 1)
+                .sum::<usize>();
+
+        let mut css = String::with_capacity(capacity);
+        self.keyframes.values().for_each(|keyframes| {
+            css.push_str(keyframes);
+            css.push('\n');
+        });
+        let mut rules = self
+            .rules
+            .iter()
+            .filter_map(|(class, rule)| Some((class, rule.as_ref()?)))
+            .map(|(class, rule)| {
+                let (variants, _utility) = class_selector::variants_and_utility(class);
+                (class_selector::variants_order(&variants), rule)
+            })
+            .collect::<Vec<(Vec<usize>, &String)>>();
+        // Stable sort, so rules with the same variants stay in the order they
+        // were added.
+        rules.sort_by(|(order_a, _), (order_b, _)| order_a.cmp(order_b));
+        rules.iter().for_each(|(_order, rule)| {
+            css.push_str(rule);
+            css.push('\n');
+        });
+
+        css
+    }
+
+    /// Returns the variants of a class, and the CSS declarations of its
+    /// utility, e.g. `["[&>path]"]` and `"fill: #cbd5e1;"` for
+    /// `"[&>path]:fill-slate-300"`.
+    pub(crate) fn class_declarations(class: &str) -> Option<(Vec<&str>, String)> {
+        let (variants, utility) = class_selector::variants_and_utility(class);
+        let UtilityCss { declarations, .. } = utility_css::utility_css(utility)?;
+
+        Some((variants, declarations))
+    }
+
+    /// Returns the Tailwind colour and shade closest to a CSS colour, e.g.
+    /// `("red", "600")` for `"#dc2626"`.
+    pub(crate) fn color_nearest(css_color: &str) -> Option<(&'static str, &'static str)> {
+        tailwind_colors::color_nearest(css_color)
+    }
+
+    /// Returns the CSS rule for a class, and the keyframes it uses.
+    fn class_rule(class: &str) -> Option<(String, Option<(&'static str, &'static str)>)> {
+        let (variants, utility) = class_selector::variants_and_utility(class);
+        let UtilityCss {
+            declarations,
+            keyframes,
+        } = utility_css::utility_css(utility)?;
+        let selector = class_selector::class_selector(class, &variants)?;
+
+        Some((format!("{selector} {{ {declarations} }}"), keyframes))
+    }
+}
+
+impl<'c> FromIterator<&'c str> for TailwindCssGen {
+    fn from_iter<I: IntoIterator<Item = &'c str>>(iter: I) -> Self {
+        let mut tailwind_css_gen = Self::new();
+        iter.into_iter()
+            .for_each(|classes| tailwind_css_gen.classes_add(classes));
+        tailwind_css_gen
+    }
+}
@@ -0,0 +1,190 @@
+use std::fmt::Write;
+
+/// Splits a CSS utility class into its variants and the utility.
+///
+/// Colons within `[..]` are not treated as separators, so
+/// `"[&>path]:focus:[stroke-dasharray:3]"` is split into
+/// `["[&>path]", "focus"]` and `"[stroke-dasharray:3]"`.
+pub(crate) fn variants_and_utility(class: &str) -> (Vec<&str>, &str) {
+    let mut variants = Vec::new();
+    let mut bracket_depth = 0u32;
+    let mut segment_start = 0;
+
+    class.char_indices().for_each(|(index, c)| match c {
+        '[' => bracket_depth += 1,
+        ']' => bracket_depth = bracket_depth.saturating_sub(1),
+        ':' if bracket_depth == 0 => {
+            variants.push(&class[segment_start..index]);
+            segment_start = index + 1;
+        }
+        _ => {}
+    });
+
+    (variants, &class[segment_start..])
+}
+
+/// Returns the CSS selector for the given class, or `None` if any variant is
+/// not supported.
+///
+/// Variants are applied from the innermost (closest to the utility) to the
+/// outermost, matching Tailwind CSS v3, so
+/// `"[&>path]:focus:fill-slate-200"` produces:
+///
+/// ```css
+/// .\[\&\>path\]\:focus\:fill-slate-200:focus>path
+/// ```
+pub(crate) fn class_selector(class: &str, variants: &[&str]) -> Option<String> {
+    let selector = variants
+        .iter()
+        .rev()
+        .try_fold(String::from("&"), |selector, variant| {
+            variant_template(variant).map(|template| template.replace('&', &selector))
+        })?;
+
+    let mut class_escaped = String::with_capacity(class.len() * 2 + 1);
+    class_escaped.push('.');
+    class_escape(class, &mut class_escaped);
+
+    Some(selector.replace('&', &class_escaped))
+}
+
+/// Returns the order of a class's rule relative to other rules, from its
+/// variants.
+///
+/// Rules are ordered the way Tailwind CSS v3 orders variants, so that when
+/// rules with the same specificity set the same property, the variant that
+/// Tailwind would apply wins regardless of the order the classes were added
+/// in:
+///
+/// 1. Classes without variants.
+/// 2. `group-*` variants, then `peer-*` variants.
+/// 3. `focus-within`, `hover`, `focus`, `focus-visible`, `active`.
+/// 4. Arbitrary variants, e.g. `[&>path]`.
+///
+/// Like Tailwind, classes are compared by their latest variant first, so
+/// `[&>path]:hover:` is ordered before `[&>path]:focus:`.
+pub(crate) fn variants_order(variants: &[&str]) -> Vec<usize> {
+    let mut variant_orders = variants
+        .iter()
+        .map(|variant| variant_order(variant))
+        .collect::<Vec<usize>>();
+    variant_orders.sort_unstable_by(|order_a, order_b| order_b.cmp(order_a));
+    variant_orders
+}
+
+/// Pseudo classes in the order that Tailwind CSS v3 registers their variants.
+const PSEUDO_CLASS_ORDER: [&str; 6] = [
+    "checked",
+    "focus-within",
+    "hover",
+    "focus",
+    "focus-visible",
+    "active",
+];
+
+fn variant_order(variant: &str) -> usize {
+    let state_order = |state: &str| {
+        PSEUDO_CLASS_ORDER
+            .iter()
+            .position(|pseudo_class| *pseudo_class == state)
+            .unwrap_or(PSEUDO_CLASS_ORDER.len())
+    };
+    let group_count = PSEUDO_CLASS_ORDER.len() + 1;
+
+    if let Some((group_state, _)) = variant
+        .strip_prefix("group-")
+        .and_then(|group_variant| group_variant.split_once('/'))
+    {
+        state_order(group_state)
+    } else if let Some((peer_state, _)) = variant
+        .strip_prefix("peer-")
+        .and_then(|peer_variant| peer_variant.split_once('/'))
+    {
+        group_count + state_order(peer_state)
+    } else if variant.starts_with('[') {
+        group_count * 3
+    } else {
+        group_count * 2 + state_order(variant)
+    }
+}
+
+/// Returns the selector template for a variant, where `&` is replaced by the
+/// selector that the variant applies to.
+fn variant_template(variant: &str) -> Option<String> {
+    let template = match variant {
+        "focus" => String::from("&:focus"),
+        "hover" => String::from("&:hover"),
+        "active" => String::from("&:active"),
+        "focus-within" => String::from("&:focus-within"),
+        "focus-visible" => String::from("&:focus-visible"),
+        _ => {
+            if let Some(arbitrary) = variant
+                .strip_prefix('[')
+                .and_then(|variant| variant.strip_suffix(']'))
+            {
+                // e.g. `[&>path]`
+                if !arbitrary.contains('&') {
+                    return None;
+                }
+                arbitrary.replace('_', " ")
+            } else if let Some((peer_state, peer_name)) = variant
+                .strip_prefix("peer-")
+                .and_then(|peer_variant| peer_variant.split_once('/'))
+            {
+                // e.g. `peer-focus/tag_id`
+                let pseudo_class = pseudo_class(peer_state)?;
+                let mut template = String::with_capacity(peer_name.len() * 2 + 24);
+                template.push_str(".peer\\/");
+                class_escape(peer_name, &mut template);
+                template.push_str(pseudo_class);
+                template.push_str(" ~ &");
+                template
+            } else if let Some((group_state, group_name)) = variant
+                .strip_prefix("group-")
+                .and_then(|group_variant| group_variant.split_once('/'))
+            {
+                // e.g. `group-focus/cluster_id`
+                let pseudo_class = pseudo_class(group_state)?;
+                let mut template = String::with_capacity(group_name.len() * 2 + 24);
+                template.push_str(".group\\/");
+                class_escape(group_name, &mut template);
+                template.push_str(pseudo_class);
+                template.push_str(" &");
+                template
+            } else {
+                return None;
+            }
+        }
+    };
+
+    Some(template)
+}
+
+fn pseudo_class(state: &str) -> Option<&'static str> {
+    match state {
+        "focus" => Some(":focus"),
+        "hover" => Some(":hover"),
+        "active" => Some(":active"),
+        "focus-within" => Some(":focus-within"),
+        "focus-visible" => Some(":focus-visible"),
+        "checked" => Some(":checked"),
+        _ => None,
+    }
+}
+
+/// Escapes characters in a class name so that it can be used in a CSS
+/// selector.
+pub(crate) fn class_escape(class: &str, buffer: &mut String) {
+    class.chars().for_each(|c| {
+        if c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() {
+            buffer.push(c);
+        } else if c.is_ascii_whitespace() || c.is_ascii_control() {
+            // Classes shouldn't contain these, but if they do, we use the code point
+            // escape.
+            let _ = write!(buffer, "\\{:x} ", c as u32);
+        } else {
+            buffer.push('\\');
+            buffer.push(c);
+        }
+    });
+}
@@ -0,0 +1,293 @@
+//! Tailwind CSS colour palette.
+//!
+//! These are the same colours listed in the [`ThemeAttr`] documentation.
+//!
+//! [`ThemeAttr`]: dot_ix_model::theme::ThemeAttr
+
+/// Shades in each colour palette, in the same order as the hex values in
+/// [`PALETTES`].
+const SHADES: [&str; 11] = [
+    "50", "100", "200", "300", "400", "500", "600", "700", "800", "900", "950",
+];
+
+/// Each colour name and its hex value for each shade.
+const PALETTES: &[(&str, [&str; 11])] = &[
+    (
+        "slate",
+        [
+            "#f8fafc", "#f1f5f9", "#e2e8f0", "#cbd5e1", "#94a3b8", "#64748b", "#475569", "#334155",
+            "#1e293b", "#0f172a", "#020617",
+        ],
+    ),
+    (
+        "gray",
+        [
+            "#f9fafb", "#f3f4f6", "#e5e7eb", "#d1d5db", "#9ca3af", "#6b7280", "#4b5563", "#374151",
+            "#1f2937", "#111827", "#030712",
+        ],
+    ),
+    (
+        "zinc",
+        [
+            "#fafafa", "#f4f4f5", "#e4e4e7", "#d4d4d8", "#a1a1aa", "#71717a", "#52525b", "#3f3f46",
+            "#27272a", "#18181b", "#09090b",
+        ],
+    ),
+    (
+        "neutral",
+        [
+            "#fafafa", "#f5f5f5", "#e5e5e5", "#d4d4d4", "#a3a3a3", "#737373", "#525252", "#404040",
+            "#262626", "#171717", "#0a0a0a",
+        ],
+    ),
+    (
+        "stone",
+        [
+            "#fafaf9", "#f5f5f4", "#e7e5e4", "#d6d3d1", "#a8a29e", "#78716c", "#57534e", "#44403c",
+            "#292524", "#1c1917", "#0c0a09",
+        ],
+    ),
+    (
+        "red",
+        [
+            "#fef2f2", "#fee2e2", "#fecaca", "#fca5a5", "#f87171", "#ef4444", "#dc2626", "#b91c1c",
+            "#991b1b", "#7f1d1d", "#450a0a",
+        ],
+    ),
+    (
+        "orange",
+        [
+            "#fff7ed", "#ffedd5", "#fed7aa", "#fdba74", "#fb923c", "#f97316", "#ea580c", "#c2410c",
+            "#9a3412", "#7c2d12", "#431407",
+        ],
+    ),
+    (
+        "amber",
+        [
+            "#fffbeb", "#fef3c7", "#fde68a", "#fcd34d", "#fbbf24", "#f59e0b", "#d97706", "#b45309",
+            "#92400e", "#78350f", "#451a03",
+        ],
+    ),
+    (
+        "yellow",
+        [
+            "#fefce8", "#fef9c3", "#fef08a", "#fde047", "#facc15", "#eab308", "#ca8a04", "#a16207",
+            "#854d0e", "#713f12", "#422006",
+        ],
+    ),
+    (
+        "lime",
+        [
+            "#f7fee7", "#ecfccb", "#d9f99d", "#bef264", "#a3e635", "#84cc16", "#65a30d", "#4d7c0f",
+            "#3f6212", "#365314", "#1a2e05",
+        ],
+    ),
+    (
+        "green",
+        [
+            "#f0fdf4", "#dcfce7", "#bbf7d0", "#86efac", "#4ade80", "#22c55e", "#16a34a", "#15803d",
+            "#166534", "#14532d", "#052e16",
+        ],
+    ),
+    (
+        "emerald",
+        [
+            "#ecfdf5", "#d1fae5", "#a7f3d0", "#6ee7b7", "#34d399", "#10b981", "#059669", "#047857",
+            "#065f46", "#064e3b", "#022c22",
+        ],
+    ),
+    (
+        "teal",
+        [
+            "#f0fdfa", "#ccfbf1", "#99f6e4", "#5eead4", "#2dd4bf", "#14b8a6", "#0d9488", "#0f766e",
+            "#115e59", "#134e4a", "#042f2e",
+        ],
+    ),
+    (
+        "cyan",
+        [
+            "#ecfeff", "#cffafe", "#a5f3fc", "#67e8f9", "#22d3ee", "#06b6d4", "#0891b2", "#0e7490",
+            "#155e75", "#164e63", "#083344",
+        ],
+    ),
+    (
+        "sky",
+        [
+            "#f0f9ff", "#e0f2fe", "#bae6fd", "#7dd3fc", "#38bdf8", "#0ea5e9", "#0284c7", "#0369a1",
+            "#075985", "#0c4a6e", "#082f49",
+        ],
+    ),
+    (
+        "blue",
+        [
+            "#eff6ff", "#dbeafe", "#bfdbfe", "#93c5fd", "#60a5fa", "#3b82f6", "#2563eb", "#1d4ed8",
+            "#1e40af", "#1e3a8a", "#172554",
+        ],
+    ),
+    (
+        "indigo",
+        [
+            "#eef2ff", "#e0e7ff", "#c7d2fe", "#a5b4fc", "#818cf8", "#6366f1", "#4f46e5", "#4338ca",
+            "#3730a3", "#312e81", "#1e1b4b",
+        ],
+    ),
+    (
+        "violet",
+        [
+            "#f5f3ff", "#ede9fe", "#ddd6fe", "#c4b5fd", "#a78bfa", "#8b5cf6", "#7c3aed", "#6d28d9",
+            "#5b21b6", "#4c1d95", "#2e1065",
+        ],
+    ),
+    (
+        "purple",
+        [
+            "#faf5ff", "#f3e8ff", "#e9d5ff", "#d8b4fe", "#c084fc", "#a855f7", "#9333ea", "#7e22ce",
+            "#6b21a8", "#581c87", "#3b0764",
+        ],
+    ),
+    (
+        "fuchsia",
+        [
+            "#fdf4ff", "#fae8ff", "#f5d0fe", "#f0abfc", "#e879f9", "#d946ef", "#c026d3", "#a21caf",
+            "#86198f", "#701a75", "#4a044e",
+        ],
+    ),
+    (
+        "pink",
+        [
+            "#fdf2f8", "#fce7f3", "#fbcfe8", "#f9a8d4", "#f472b6", "#ec4899", "#db2777", "#be185d",
+            "#9d174d", "#831843", "#500724",
+        ],
+    ),
+    (
+        "rose",
+        [
+            "#fff1f2", "#ffe4e6", "#fecdd3", "#fda4af", "#fb7185", "#f43f5e", "#e11d48", "#be123c",
+            "#9f1239", "#881337", "#4c0519",
+        ],
+    ),
+];
+
+/// Returns the hex value of a Tailwind colour, e.g. `"#475569"` for `"slate"`,
+/// `"600"`.
+pub(crate) fn color_hex(color: &str, shade: &str) -> Option<&'static str> {
+    let shade_index = SHADES
+        .iter()
+        .position(|shade_known| *shade_known == shade)?;
+
+    PALETTES
+        .iter()
+        .find(|(color_name, _hexes)| *color_name == color)
+        .map(|(_color_name, hexes)| hexes[shade_index])
+}
+
+/// Returns the CSS value for colours that don't have shades, e.g. `"white"`.
+pub(crate) fn color_keyword(color: &str) -> Option<&'static str> {
+    match color {
+        "inherit" => Some("inherit"),
+        "current" => Some("currentColor"),
+        "transparent" => Some("transparent"),
+        "black" => Some("#000"),
+        "white" => Some("#fff"),
+        _ => None,
+    }
+}
+
+/// Returns the Tailwind colour and shade closest to a CSS colour, e.g.
+/// `("red", "600")` for `"#dc2626"`.
+///
+/// Supports `#rgb`, `#rrggbb`, `#rrggbbaa`, `rgb(r, g, b)`, and common colour
+/// names. The alpha channel is ignored.
+pub(crate) fn color_nearest(css_color: &str) -> Option<(&'static str, &'static str)> {
+    let [r, g, b] = css_color_rgb(css_color.trim())?;
+
+    PALETTES
+        .iter()
+        .flat_map(|(color_name, hexes)| {
+            hexes
+                .iter()
+                .zip(SHADES.iter())
+                .map(move |(hex, shade)| (*color_name, *shade, *hex))
+        })
+        .filter_map(|(color_name, shade, hex)| {
+            let [pr, pg, pb] = css_color_rgb(hex)?;
+            let distance = [(r, pr), (g, pg), (b, pb)]
+                .iter()
+                .map(|(c, pc)| (i32::from(*c) - i32::from(*pc)).pow(2))
+                .sum::<i32>();
+            Some((distance, color_name, shade))
+        })
+        .min_by_key(|(distance, _color_name, _shade)| *distance)
+        .map(|(_distance, color_name, shade)| (color_name, shade))
+}
+
+/// Returns the red, green, and blue channels of a CSS colour.
+fn css_color_rgb(css_color: &str) -> Option<[u8; 3]> {
+    let css_color_lower = css_color.to_ascii_lowercase();
+    if let Some(hex) = css_color_lower.strip_prefix('#') {
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+        return match hex.len() {
+            3 | 4 => {
+                let mut channels = hex.chars().take(3).map(|digit| {
+                    let digit = digit.to_digit(16)? as u8;
+                    Some(digit * 16 + digit)
+                });
+                Some([channels.next()??, channels.next()??, channels.next()??])
+            }
+            6 | 8 => Some([
+                channel(hex.get(0..2)?)?,
+                channel(hex.get(2..4)?)?,
+                channel(hex.get(4..6)?)?,
+            ]),
+            _ => None,
+        };
+    }
+
+    if let Some(channels) = css_color_lower
+        .strip_prefix("rgb(")
+        .or_else(|| css_color_lower.strip_prefix("rgba("))
+        .and_then(|channels| channels.strip_suffix(')'))
+    {
+        let mut channels = channels
+            .split([',', ' ', '/'])
+            .filter(|channel| !channel.is_empty())
+            .map(|channel| {
+                channel
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .map(|c| c.clamp(0.0, 255.0) as u8)
+            });
+        return Some([channels.next()??, channels.next()??, channels.next()??]);
+    }
+
+    let hex = match css_color_lower.as_str() {
+        "black" => "#000000",
+        "white" => "#ffffff",
+        "gray" | "grey" => "#808080",
+        "silver" => "#c0c0c0",
+        "red" => "#ff0000",
+        "maroon" => "#800000",
+        "orange" => "#ffa500",
+        "yellow" => "#ffff00",
+        "gold" => "#ffd700",
+        "lime" => "#00ff00",
+        "green" => "#008000",
+        "olive" => "#808000",
+        "teal" => "#008080",
+        "cyan" | "aqua" => "#00ffff",
+        "blue" => "#0000ff",
+        "navy" => "#000080",
+        "purple" => "#800080",
+        "violet" => "#ee82ee",
+        "magenta" | "fuchsia" => "#ff00ff",
+        "pink" => "#ffc0cb",
+        "brown" => "#a52a2a",
+        "lightblue" => "#add8e6",
+        "lightgreen" => "#90ee90",
+        "lightgray" | "lightgrey" => "#d3d3d3",
+        "darkgray" | "darkgrey" => "#a9a9a9",
+        _ => return None,
+    };
+    css_color_rgb(hex)
+}
@@ -0,0 +1,359 @@
+use std::borrow::Cow;
+
+use crate::tailwind_css_gen::tailwind_colors;
+
+/// CSS declarations for a utility class, and the keyframes it depends on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct UtilityCss {
+    /// Declarations within the rule block, e.g. `"stroke: #475569;"`.
+    pub(crate) declarations: String,
+    /// Name and `@keyframes` rule that the declarations use, if any.
+    pub(crate) keyframes: Option<(&'static str, &'static str)>,
+}
+
+impl UtilityCss {
+    fn new(declarations: String) -> Self {
+        Self {
+            declarations,
+            keyframes: None,
+        }
+    }
+
+    fn with_keyframes(declarations: String, keyframes: (&'static str, &'static str)) -> Self {
+        Self {
+            declarations,
+            keyframes: Some(keyframes),
+        }
+    }
+}
+
+const KEYFRAMES_SPIN: (&str, &str) = (
+    "spin",
+    "@keyframes spin { to { transform: rotate(360deg); } }",
+);
+const KEYFRAMES_PING: (&str, &str) = (
+    "ping",
+    "@keyframes ping { 75%, 100% { transform: scale(2); opacity: 0; } }",
+);
+const KEYFRAMES_PULSE: (&str, &str) = ("pulse", "@keyframes pulse { 50% { opacity: .5; } }");
+const KEYFRAMES_BOUNCE: (&str, &str) = (
+    "bounce",
+    "@keyframes bounce { \
+        0%, 100% { transform: translateY(-25%); animation-timing-function: cubic-bezier(0.8,0,1,1); } \
+        50% { transform: none; animation-timing-function: cubic-bezier(0,0,0.2,1); } \
+    }",
+);
+
+/// Moves dashes by one repetition of the dash pattern, which is set through the
+/// `--dot-ix-dash-period` variable by edge flow classes.
+const KEYFRAMES_EDGE_DASH: (&str, &str) = (
+    "dot_ix_edge_dash",
+    "@keyframes dot_ix_edge_dash { \
+        from { stroke-dashoffset: 0; } \
+        to { stroke-dashoffset: calc(-1px * var(--dot-ix-dash-period, 8)); } \
+    }",
+);
+
+/// Returns the CSS declarations for a utility class without variants, e.g.
+/// `"stroke-slate-600"`, or `None` if the utility is not supported.
+pub(crate) fn utility_css(utility: &str) -> Option<UtilityCss> {
+    // Arbitrary properties, e.g. `[stroke-dasharray:3]`.
+    if let Some(property_and_value) = utility
+        .strip_prefix('[')
+        .and_then(|utility| utility.strip_suffix(']'))
+    {
+        let (property, value) = property_and_value.split_once(':')?;
+        let value = value.replace('_', " ");
+        return Some(UtilityCss::new(format!("{property}: {value};")));
+    }
+
+    let declarations = match utility {
+        "visible" => "visibility: visible;",
+        "invisible" => "visibility: hidden;",
+        "collapse" => "visibility: collapse;",
+        "outline" => "outline-style: solid;",
+        "outline-none" => "outline: 2px solid transparent; outline-offset: 2px;",
+        "outline-dashed" => "outline-style: dashed;",
+        "outline-dotted" => "outline-style: dotted;",
+        "outline-double" => "outline-style: double;",
+        "border" => "border-width: 1px;",
+        "border-solid" => "border-style: solid;",
+        "border-dashed" => "border-style: dashed;",
+        "border-dotted" => "border-style: dotted;",
+        "border-double" => "border-style: double;",
+        "border-none" => "border-style: none;",
+        "rounded" => "border-radius: 0.25rem;",
+        "rounded-none" => "border-radius: 0px;",
+        "rounded-sm" => "border-radius: 0.125rem;",
+        "rounded-md" => "border-radius: 0.375rem;",
+        "rounded-lg" => "border-radius: 0.5rem;",
+        "rounded-xl" => "border-radius: 0.75rem;",
+        "rounded-2xl" => "border-radius: 1rem;",
+        "rounded-3xl" => "border-radius: 1.5rem;",
+        "rounded-full" => "border-radius: 9999px;",
+        "shadow" => "box-shadow: 0 1px 3px 0 rgb(0 0 0 / 0.1), 0 1px 2px -1px rgb(0 0 0 / 0.1);",
+        "drop-shadow" => {
+            "filter: drop-shadow(0 1px 2px rgb(0 0 0 / 0.1)) drop-shadow(0 1px 1px rgb(0 0 0 / 0.06));"
+        }
+        "animate-none" => "animation: none;",
+        "animate-spin" => {
+            return Some(UtilityCss::with_keyframes(
+                String::from("animation: spin 1s linear infinite;"),
+                KEYFRAMES_SPIN,
+            ));
+        }
+        "animate-ping" => {
+            return Some(UtilityCss::with_keyframes(
+                String::from("animation: ping 1s cubic-bezier(0, 0, 0.2, 1) infinite;"),
+                KEYFRAMES_PING,
+            ));
+        }
+        "animate-pulse" => {
+            return Some(UtilityCss::with_keyframes(
+                String::from("animation: pulse 2s cubic-bezier(0.4, 0, 0.6, 1) infinite;"),
+                KEYFRAMES_PULSE,
+            ));
+        }
+        "animate-bounce" => {
+            return Some(UtilityCss::with_keyframes(
+                String::from("animation: bounce 1s infinite;"),
+                KEYFRAMES_BOUNCE,
+            ));
+        }
+        "animate-edge-march" => {
+            return Some(UtilityCss::with_keyframes(
+                String::from("animation: dot_ix_edge_dash 0.5s linear infinite;"),
+                KEYFRAMES_EDGE_DASH,
+            ));
+        }
+        "animate-edge-pulse" => {
+            return Some(UtilityCss::with_keyframes(
+                String::from("animation: dot_ix_edge_dash 1.5s linear infinite;"),
+                KEYFRAMES_EDGE_DASH,
+            ));
+        }
+        "animate-edge-flow" => {
+            return Some(UtilityCss::with_keyframes(
+                String::from("animation: dot_ix_edge_dash 1s linear infinite;"),
+                KEYFRAMES_EDGE_DASH,
+            ));
+        }
+        "animate-edge-flow-reverse" => {
+            return Some(UtilityCss::with_keyframes(
+                String::from("animation: dot_ix_edge_dash 1s linear infinite reverse;"),
+                KEYFRAMES_EDGE_DASH,
+            ));
+        }
+        "animate-edge-flow-alternate" => {
+            return Some(UtilityCss::with_keyframes(
+                String::from("animation: dot_ix_edge_dash 1s linear infinite alternate;"),
+                KEYFRAMES_EDGE_DASH,
+            ));
+        }
+        _ => return utility_css_prefixed(utility).map(UtilityCss::new),
+    };
+
+    Some(UtilityCss::new(String::from(declarations)))
+}
+
+/// Returns the CSS declarations for utilities that are a prefix followed by a
+/// value, e.g. `"stroke-2"`, `"px-1.5"`.
+fn utility_css_prefixed(utility: &str) -> Option<String> {
+    let (prefix, value) = utility.split_once('-')?;
+
+    match prefix {
+        "stroke" => color_declaration("stroke", value)
+            .or_else(|| width_declaration("stroke-width", value, "")),
+        "fill" => color_declaration("fill", value),
+        "bg" => color_declaration("background-color", value),
+        "text" => color_declaration("color", value).or_else(|| font_size_declaration(value)),
+        "font" => font_weight_declaration(value),
+        "shadow" => box_shadow(value).map(|box_shadow| format!("box-shadow: {box_shadow};")),
+        "drop" => value
+            .strip_prefix("shadow-")
+            .and_then(drop_shadow)
+            .map(|drop_shadow| format!("filter: {drop_shadow};")),
+        "outline" => color_declaration("outline-color", value)
+            .or_else(|| width_declaration("outline-width", value, "px")),
+        "border" => color_declaration("border-color", value)
+            .or_else(|| width_declaration("border-width", value, "px")),
+        "cursor" => Some(format!("cursor: {};", arbitrary_or(value)?)),
+        "opacity" => {
+            let opacity = if let Some(arbitrary) = arbitrary(value) {
+                arbitrary
+            } else {
+                let percent = value.parse::<u32>().ok()?;
+                format!("{}", f64::from(percent) / 100.0)
+            };
+            Some(format!("opacity: {opacity};"))
+        }
+        "animate" => arbitrary(value).map(|animation| format!("animation: {animation};")),
+        "p" => spacing_declaration(&["padding"], value),
+        "px" => spacing_declaration(&["padding-left", "padding-right"], value),
+        "py" => spacing_declaration(&["padding-top", "padding-bottom"], value),
+        "pt" => spacing_declaration(&["padding-top"], value),
+        "pr" => spacing_declaration(&["padding-right"], value),
+        "pb" => spacing_declaration(&["padding-bottom"], value),
+        "pl" => spacing_declaration(&["padding-left"], value),
+        "m" => spacing_declaration(&["margin"], value),
+        "mx" => spacing_declaration(&["margin-left", "margin-right"], value),
+        "my" => spacing_declaration(&["margin-top", "margin-bottom"], value),
+        "mt" => spacing_declaration(&["margin-top"], value),
+        "mr" => spacing_declaration(&["margin-right"], value),
+        "mb" => spacing_declaration(&["margin-bottom"], value),
+        "ml" => spacing_declaration(&["margin-left"], value),
+        _ => None,
+    }
+}
+
+/// Returns the declaration for colour utilities, e.g. `"slate-600"`,
+/// `"white"`, or `"[#abcdef]"`.
+fn color_declaration(property: &str, value: &str) -> Option<String> {
+    let color = if let Some(arbitrary) = arbitrary(value) {
+        if !arbitrary_is_color(&arbitrary) {
+            return None;
+        }
+        Cow::Owned(arbitrary)
+    } else if let Some(keyword) = tailwind_colors::color_keyword(value) {
+        Cow::Borrowed(keyword)
+    } else {
+        let (color, shade) = value.rsplit_once('-')?;
+        Cow::Borrowed(tailwind_colors::color_hex(color, shade)?)
+    };
+
+    Some(format!("{property}: {color};"))
+}
+
+/// Returns the declaration for line width utilities, e.g. `"2"` or `"[2px]"`.
+fn width_declaration(property: &str, value: &str, unit: &str) -> Option<String> {
+    if let Some(arbitrary) = arbitrary(value) {
+        Some(format!("{property}: {arbitrary};"))
+    } else {
+        let width = value.parse::<u32>().ok()?;
+        Some(format!("{property}: {width}{unit};"))
+    }
+}
+
+/// Returns the declaration for font weight utilities, e.g. `"bold"`.
+fn font_weight_declaration(value: &str) -> Option<String> {
+    let font_weight = match value {
+        "thin" => "100",
+        "extralight" => "200",
+        "light" => "300",
+        "normal" => "400",
+        "medium" => "500",
+        "semibold" => "600",
+        "bold" => "700",
+        "extrabold" => "800",
+        "black" => "900",
+        _ => return arbitrary(value).map(|arbitrary| format!("font-weight: {arbitrary};")),
+    };
+
+    Some(format!("font-weight: {font_weight};"))
+}
+
+/// Returns the declarations for font size utilities, e.g. `"sm"`.
+///
+/// Arbitrary values such as `"[13px]"` are only treated as a font size if they
+/// are not colours.
+fn font_size_declaration(value: &str) -> Option<String> {
+    let (font_size, line_height) = match value {
+        "xs" => ("0.75rem", "1rem"),
+        "sm" => ("0.875rem", "1.25rem"),
+        "base" => ("1rem", "1.5rem"),
+        "lg" => ("1.125rem", "1.75rem"),
+        "xl" => ("1.25rem", "1.75rem"),
+        "2xl" => ("1.5rem", "2rem"),
+        "3xl" => ("1.875rem", "2.25rem"),
+        "4xl" => ("2.25rem", "2.5rem"),
+        "5xl" => ("3rem", "1"),
+        "6xl" => ("3.75rem", "1"),
+        "7xl" => ("4.5rem", "1"),
+        "8xl" => ("6rem", "1"),
+        "9xl" => ("8rem", "1"),
+        _ => return arbitrary(value).map(|arbitrary| format!("font-size: {arbitrary};")),
+    };
+
+    Some(format!(
+        "font-size: {font_size}; line-height: {line_height};"
+    ))
+}
+
+/// Returns the `box-shadow` value for shadow utilities, e.g. `"md"`.
+fn box_shadow(value: &str) -> Option<&'static str> {
+    let box_shadow = match value {
+        "sm" => "0 1px 2px 0 rgb(0 0 0 / 0.05)",
+        "md" => "0 4px 6px -1px rgb(0 0 0 / 0.1), 0 2px 4px -2px rgb(0 0 0 / 0.1)",
+        "lg" => "0 10px 15px -3px rgb(0 0 0 / 0.1), 0 4px 6px -4px rgb(0 0 0 / 0.1)",
+        "xl" => "0 20px 25px -5px rgb(0 0 0 / 0.1), 0 8px 10px -6px rgb(0 0 0 / 0.1)",
+        "2xl" => "0 25px 50px -12px rgb(0 0 0 / 0.25)",
+        "inner" => "inset 0 2px 4px 0 rgb(0 0 0 / 0.05)",
+        "none" => "0 0 #0000",
+        _ => return None,
+    };
+
+    Some(box_shadow)
+}
+
+/// Returns the `filter` value for drop shadow utilities, e.g. `"md"`.
+///
+/// Drop shadows are used for SVG elements, which don't support `box-shadow`.
+fn drop_shadow(value: &str) -> Option<&'static str> {
+    let drop_shadow = match value {
+        "sm" => "drop-shadow(0 1px 1px rgb(0 0 0 / 0.05))",
+        "md" => "drop-shadow(0 4px 3px rgb(0 0 0 / 0.07)) drop-shadow(0 2px 2px rgb(0 0 0 / 0.06))",
+        "lg" => "drop-shadow(0 10px 8px rgb(0 0 0 / 0.04)) drop-shadow(0 4px 3px rgb(0 0 0 / 0.1))",
+        "xl" => {
+            "drop-shadow(0 20px 13px rgb(0 0 0 / 0.03)) drop-shadow(0 8px 5px rgb(0 0 0 / 0.08))"
+        }
+        "2xl" => "drop-shadow(0 25px 25px rgb(0 0 0 / 0.15))",
+        "none" => "drop-shadow(0 0 #0000)",
+        _ => return None,
+    };
+
+    Some(drop_shadow)
+}
+
+/// Returns the declarations for spacing utilities, e.g. `"1.5"` -> `0.375rem`.
+fn spacing_declaration(properties: &[&str], value: &str) -> Option<String> {
+    let spacing = if let Some(arbitrary) = arbitrary(value) {
+        arbitrary
+    } else if value == "px" {
+        String::from("1px")
+    } else if value == "0" {
+        String::from("0px")
+    } else {
+        let spacing = value.parse::<f64>().ok()?;
+        format!("{}rem", spacing * 0.25)
+    };
+
+    let declarations = properties
+        .iter()
+        .map(|property| format!("{property}: {spacing};"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some(declarations)
+}
+
+/// Returns the value within `[..]`, with `_` replaced with spaces.
+fn arbitrary(value: &str) -> Option<String> {
+    value
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+        .map(|value| value.replace('_', " "))
+}
+
+/// Returns the arbitrary value if it is within `[..]`, otherwise the value
+/// itself.
+fn arbitrary_or(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(arbitrary(value).unwrap_or_else(|| value.to_string()))
+    }
+}
+
+fn arbitrary_is_color(value: &str) -> bool {
+    value.starts_with('#') || value.starts_with("rgb") || value.starts_with("hsl")
+}
@@ -0,0 +1,25 @@
+use std::borrow::Cow;
+
+/// Returns the text with `<`, `>`, `&`, `'`, and `"` escaped, for use in XML
+/// or HTML text and attribute values.
+///
+/// Line breaks are escaped as well, as XML parsers replace line breaks in
+/// attribute values with spaces.
+pub(crate) fn xml_escape(text: &str) -> Cow<'_, str> {
+    if !text.contains(['<', '>', '&', '\'', '"', '\n', '\r']) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len() + 16);
+    text.chars().for_each(|c| match c {
+        '<' => escaped.push_str("&lt;"),
+        '>' => escaped.push_str("&gt;"),
+        '&' => escaped.push_str("&amp;"),
+        '\'' => escaped.push_str("&apos;"),
+        '"' => escaped.push_str("&quot;"),
+        '\n' => escaped.push_str("&#10;"),
+        '\r' => escaped.push_str("&#13;"),
+        c => escaped.push(c),
+    });
+    Cow::Owned(escaped)
+}
@@ -0,0 +1,154 @@
+/// Length of an arrowhead, in pixels.
+const ARROWHEAD_LENGTH = 8;
+/// Half of the width of an arrowhead, in pixels.
+const ARROWHEAD_HALF_WIDTH = 4;
+/// Distance between the edge label and the edge, in pixels.
+const LABEL_OFFSET = 4;
+
+/// Diagrams that are already redrawn when they are resized.
+const diagrams_observed = new WeakSet();
+
+/// Draws the edges of each `dot_ix_html` diagram within the container.
+///
+/// Edges are drawn after the browser lays out the nodes, and redrawn whenever
+/// the diagram is resized.
+///
+/// # Parameters
+///
+/// * `container`: Element that contains the HTML generated by `IntoHtmlSrc`.
+export function info_graph_html_edges(container) {
+    container.querySelectorAll(".dot_ix_html").forEach((diagram) => {
+        diagram_edges_draw(diagram);
+
+        if (!diagrams_observed.has(diagram) && typeof ResizeObserver !== "undefined") {
+            diagrams_observed.add(diagram);
+            new ResizeObserver(() => diagram_edges_draw(diagram)).observe(diagram);
+        }
+    });
+}
+
+function diagram_edges_draw(diagram) {
+    const svg = diagram.querySelector(":scope > svg.dot_ix_html_edges");
+    if (svg === null) {
+        return;
+    }
+
+    const graph_dir = diagram.dataset.graphDir;
+    const edge_connector = diagram.dataset.edgeConnector;
+    const diagram_rect = diagram.getBoundingClientRect();
+
+    svg.querySelectorAll(":scope > g.dot_ix_html_edge").forEach((edge) => {
+        const src = node_rect(diagram, diagram_rect, edge.dataset.edgeSrc);
+        const target = node_rect(diagram, diagram_rect, edge.dataset.edgeTarget);
+        if (src === null || target === null) {
+            return;
+        }
+
+        const points = edge_points(src, target, graph_dir, edge_connector);
+        edge_draw(edge, points);
+    });
+}
+
+/// Returns the node's bounds relative to the diagram.
+function node_rect(diagram, diagram_rect, node_id) {
+    const node = diagram.querySelector(`[id="${CSS.escape(node_id)}"]`);
+    if (node === null) {
+        return null;
+    }
+
+    const rect = node.getBoundingClientRect();
+    const x = rect.left - diagram_rect.left;
+    const y = rect.top - diagram_rect.top;
+
+    return {
+        left: x,
+        right: x + rect.width,
+        top: y,
+        bottom: y + rect.height,
+        x_mid: x + rect.width / 2,
+        y_mid: y + rect.height / 2,
+    };
+}
+
+/// Returns the points that the edge passes through, from the source node to
+/// the target node.
+function edge_points(src, target, graph_dir, edge_connector) {
+    const overlaps_x = src.left < target.right && target.left < src.right;
+    const overlaps_y = src.top < target.bottom && target.top < src.bottom;
+
+    // Connect the sides facing the graph direction, unless the nodes are stacked
+    // in the other direction.
+    const horizontal = graph_dir === "vertical"
+        ? overlaps_y && !overlaps_x
+        : !overlaps_x;
+    if (horizontal) {
+        const [x1, x2] = src.x_mid <= target.x_mid
+            ? [src.right, target.left]
+            : [src.left, target.right];
+        const [y1, y2] = [src.y_mid, target.y_mid];
+
+        if (edge_connector === "orthogonal") {
+            const x_mid = (x1 + x2) / 2;
+            return [[x1, y1], [x_mid, y1], [x_mid, y2], [x2, y2]];
+        }
+        return [[x1, y1], [x2, y2]];
+    } else {
+        const [y1, y2] = src.y_mid <= target.y_mid
+            ? [src.bottom, target.top]
+            : [src.top, target.bottom];
+        const [x1, x2] = [src.x_mid, target.x_mid];
+
+        if (edge_connector === "orthogonal") {
+            const y_mid = (y1 + y2) / 2;
+            return [[x1, y1], [x1, y_mid], [x2, y_mid], [x2, y2]];
+        }
+        return [[x1, y1], [x2, y2]];
+    }
+}
+
+function edge_draw(edge, points) {
+    const path = edge.querySelector(":scope > path");
+    const d = points
+        .map(([x, y], index) => `${index === 0 ? "M" : "L"}${x},${y}`)
+        .join(" ");
+    path.setAttribute("d", d);
+
+    const arrowheads = edge.querySelectorAll(":scope > polygon");
+    const last = points.length - 1;
+    const head = () => arrowhead_points(points[last - 1], points[last]);
+    const tail = () => arrowhead_points(points[1], points[0]);
+    switch (edge.dataset.edgeDir) {
+        case "forward":
+            arrowheads[0]?.setAttribute("points", head());
+            break;
+        case "back":
+            arrowheads[0]?.setAttribute("points", tail());
+            break;
+        case "both":
+            arrowheads[0]?.setAttribute("points", head());
+            arrowheads[1]?.setAttribute("points", tail());
+            break;
+    }
+
+    const text = edge.querySelector(":scope > text");
+    if (text !== null) {
+        // Place the label at the middle of the middle segment.
+        const segment_index = Math.floor(last / 2);
+        const [x1, y1] = points[segment_index];
+        const [x2, y2] = points[segment_index + 1];
+        text.setAttribute("x", (x1 + x2) / 2);
+        text.setAttribute("y", (y1 + y2) / 2 - LABEL_OFFSET);
+    }
+}
+
+/// Returns the `points` of an arrowhead pointing from `from` to `to`.
+function arrowhead_points([x_from, y_from], [x_to, y_to]) {
+    const length = Math.hypot(x_to - x_from, y_to - y_from) || 1;
+    const [dx, dy] = [(x_to - x_from) / length, (y_to - y_from) / length];
+    const [base_x, base_y] = [x_to - dx * ARROWHEAD_LENGTH, y_to - dy * ARROWHEAD_LENGTH];
+    const [normal_x, normal_y] = [-dy * ARROWHEAD_HALF_WIDTH, dx * ARROWHEAD_HALF_WIDTH];
+
+    return `${x_to},${y_to} \
+${base_x + normal_x},${base_y + normal_y} \
+${base_x - normal_x},${base_y - normal_y}`;
+}
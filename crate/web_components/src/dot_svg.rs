@@ -3,17 +3,23 @@ use leptos::{
     component,
     html::Div,
     prelude::{
-        ClassAttribute, Effect, ElementChild, Get, GlobalAttributes, GlobalOnAttributes, NodeRef,
-        NodeRefAttribute, Signal,
+        ClassAttribute, Effect, ElementChild, Get, GlobalAttributes, GlobalOnAttributes,
+        InnerHtmlAttribute, NodeRef, NodeRefAttribute, Signal,
     },
     view, IntoView,
 };
 use leptos_meta::Script;
 
-#[cfg(target_arch = "wasm32")]
-use dot_ix_model::common::{dot_src_and_styles::GraphvizImage, Images};
+#[cfg(any(target_arch = "wasm32", feature = "ssr"))]
+use dot_ix_model::common::{
+    dot_src_and_styles::{EdgeBundles, GraphvizImage},
+    Images,
+};
+#[cfg(feature = "ssr")]
+use dot_ix_rt::{DotRenderer, RenderError};
 
 cfg_if::cfg_if! { if #[cfg(target_arch = "wasm32")] {
+    use dot_ix_rt::Diagnostic;
     use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 
     #[wasm_bindgen(module = "/public/js/graphviz_dot_svg.js")]
@@ -25,12 +31,12 @@ cfg_if::cfg_if! { if #[cfg(target_arch = "wasm32")] {
 
 const SVG_WRITE_TO_CLIPBOARD: &str = include_str!("dot_svg/svg_write_to_clipboard.js");
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(any(target_arch = "wasm32", feature = "ssr"))]
 fn dot_svg_append_extra(dot_svg: &str, svg_extra: &str) -> String {
     dot_svg.replacen("<g", &format!("{svg_extra}\n<g"), 1)
 }
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(any(target_arch = "wasm32", feature = "ssr"))]
 fn svg_image_defs(images: &Images) -> String {
     images
         .iter()
@@ -60,7 +66,7 @@ fn svg_image_defs(images: &Images) -> String {
 /// * `dot_svg`: The SVG generated by `dot`.
 /// * `styles`: The CSS styles to include within the `<style>$styles</style>`
 ///   element.
-#[cfg(target_arch = "wasm32")]
+#[cfg(any(target_arch = "wasm32", feature = "ssr"))]
 fn dot_svg_sanitize(dot_svg: &str, styles: &str, images: &Images, images_defs: &str) -> String {
     let dot_svg = dot_svg
         .replacen("<g", &format!("<style>{styles}</style>\n<g"), 1)
@@ -70,7 +76,7 @@ fn dot_svg_sanitize(dot_svg: &str, styles: &str, images: &Images, images_defs: &
         .replace("stroke=\"black\"", "")
         .replace("&nbsp;", "&#160;");
 
-    let dot_svg = images
+    images
         .keys()
         .fold(dot_svg, |dot_svg, image_id| {
             dot_svg.replace(
@@ -81,47 +87,70 @@ fn dot_svg_sanitize(dot_svg: &str, styles: &str, images: &Images, images_defs: &
         .replace("</image>", "</use>")
         // Do this last, so all the previous replacements don't re-allocate the potentially large
         // string
-        .replacen("<g", &format!("<defs>{images_defs}</defs>\n<g"), 1);
+        .replacen("<g", &format!("<defs>{images_defs}</defs>\n<g"), 1)
+}
 
-    dot_svg
+/// Sanitizes the SVG generated by dot, and adds the styles, image
+/// definitions, bundled edge IDs, and extra SVG elements for the diagram.
+#[cfg(any(target_arch = "wasm32", feature = "ssr"))]
+fn dot_svg_postprocess(
+    dot_svg: &str,
+    info_graph: &InfoGraph,
+    styles: &str,
+    edge_bundles: &EdgeBundles,
+) -> String {
+    let styles = format!("\n/* TW_PLACEHOLDER */\n{styles}");
+    let images = info_graph.images();
+    let image_defs = svg_image_defs(images);
+    let dot_svg = edge_bundles.svg_edge_ids_insert(dot_svg);
+    let dot_svg = dot_svg_sanitize(&dot_svg, &styles, images, &image_defs);
+    dot_svg_append_extra(&dot_svg, info_graph.svg_extra())
 }
 
-/// Renders a graphviz graph as an SVG.
+/// Renders the diagram's SVG on the server, to pass to [`DotSvg`] as
+/// `dot_svg_prerendered`.
 ///
-/// TODO: Use `postcss` to generate styles on the client side via function.
+/// The SVG is sanitized and styled the same way as SVG that is rendered in the
+/// browser, so the diagram is shown before Graphviz is loaded.
+#[cfg(feature = "ssr")]
+pub fn dot_svg_prerender(
+    dot_renderer: &dyn DotRenderer,
+    info_graph: &InfoGraph,
+    dot_src_and_styles: &DotSrcAndStyles,
+) -> Result<String, RenderError> {
+    let dot_svg = dot_renderer.render(dot_src_and_styles)?;
+    Ok(dot_svg_postprocess(
+        &dot_svg,
+        info_graph,
+        dot_src_and_styles.styles(),
+        dot_src_and_styles.edge_bundles(),
+    ))
+}
+
+/// Renders a graphviz graph as an SVG.
 ///
-/// * https://stackoverflow.com/questions/75787942/given-html-string-generate-the-tailwindcss-programatically
-/// * https://tailwindcss.com/docs/content-configuration#configuring-raw-content
-/// * https://tailwindcss.com/docs/content-configuration#safelisting-classes
+/// The styles in [`DotSrcAndStyles::styles`] are plain CSS generated from the
+/// theme's Tailwind classes, so the rendered SVG is self-contained and does not
+/// need Tailwind to be run in the browser.
 ///
-/// ```js
-/// import postcss from "postcss";
-/// import tailwindcss from "tailwindcss"
-/// import autoprefixer from "autoprefixer"
-/// import cssnano from "cssnano"
+/// # Parameters
 ///
-/// async function generateTailwindCss(html) {
-///   return (await postcss([tailwindcss({
-///     content: [{ raw:html }],
-///     theme: {
-///       extend: {},
-///     },
-///     plugins: [],
-///   }), autoprefixer, cssnano])
-///   .process(`
-///     @tailwind base;
-///     @tailwind components;
-///     @tailwind utilities;
-///   `)).css
-/// }
+/// * `tailwind_cdn`: Whether to load Tailwind CSS from its CDN to compile
+///   classes in the browser. This is only needed for classes that the
+///   generated styles don't cover, such as arbitrary Tailwind classes in
+///   `ThemeAttr::Extra`. Defaults to `false`.
+/// * `dot_svg_prerendered`: SVG rendered on the server by
+///   `dot_svg_prerender`, shown until the diagram is rendered in the
+///   browser. Defaults to `None`.
 ///
-/// const css = generateTailwindCss('<div class="m-4">HTML content</div>')
-/// ```
+/// [`DotSrcAndStyles::styles`]: dot_ix_model::common::DotSrcAndStyles::styles
 #[component]
 pub fn DotSvg(
     info_graph: Signal<InfoGraph>,
     dot_src_and_styles: Signal<Option<DotSrcAndStyles>>,
     #[prop(default = Signal::from(false))] diagram_only: Signal<bool>,
+    #[prop(default = Signal::from(false))] tailwind_cdn: Signal<bool>,
+    #[prop(default = Signal::from(None))] dot_svg_prerendered: Signal<Option<String>>,
 ) -> impl IntoView {
     // DOM elements for the graph and error
     let svg_div_ref = NodeRef::<Div>::new();
@@ -146,7 +175,14 @@ pub fn DotSvg(
             if !dot_src_and_styles.dot_src.is_empty() {
                 use std::borrow::Cow;
 
-                let DotSrcAndStyles { dot_src, styles, opts, theme_warnings: _ } = dot_src_and_styles;
+                let DotSrcAndStyles {
+                    dot_src,
+                    styles,
+                    opts,
+                    theme_warnings: _,
+                    src_map,
+                    edge_bundles,
+                } = dot_src_and_styles;
 
                 let opts = &serde_wasm_bindgen::to_value(&opts).unwrap();
                 let (dot_svg, error) = match graphviz_dot_svg(dot_src, opts) {
@@ -154,18 +190,25 @@ pub fn DotSvg(
                     //       so that tailwind peer selectors work.
                     Ok(dot_svg) => {
                         let info_graph = info_graph.get();
-                        let styles = format!("\n/* TW_PLACEHOLDER */\n{styles}");
-                        let images = info_graph.images();
-                        let image_defs = svg_image_defs(images);
-                        let dot_svg = dot_svg_sanitize(&dot_svg, &styles, images, &image_defs);
-                        let dot_svg = dot_svg_append_extra(&dot_svg, info_graph.svg_extra());
+                        let dot_svg =
+                            dot_svg_postprocess(&dot_svg, &info_graph, &styles, &edge_bundles);
 
-                        (Cow::Owned(dot_svg), None)},
+                        (Cow::Owned(dot_svg), None)
+                    }
                     Err(error) => {
                         let error = js_sys::Error::from(error)
                             .to_string()
                             .as_string()
                             .unwrap_or_else(|| String::from("<unknown>"));
+                        let error = Diagnostic::graphviz_error_explain(&error, src_map.as_ref())
+                            .iter()
+                            .fold(error, |mut error, diagnostic| {
+                                error.push_str(&format!(
+                                    "\n• {diagnostic}\n  {}",
+                                    diagnostic.suggestion()
+                                ));
+                                error
+                            });
 
                         (Cow::Borrowed(""), Some(error))
                     }
@@ -227,8 +270,9 @@ pub fn DotSvg(
                 group
             "
         >
-            // Client side tailwind processing.
-            <Script src="https://cdn.tailwindcss.com" />
+            // Client side tailwind processing, only needed for classes that are not
+            // covered by the generated styles.
+            {move || tailwind_cdn.get().then(|| view! { <Script src="https://cdn.tailwindcss.com" /> })}
             // Button
             <div
                 class="
@@ -288,6 +332,7 @@ pub fn DotSvg(
                 id="svg_div"
                 node_ref=svg_div_ref
                 class="mt-6 overflow-auto"
+                inner_html=move || dot_svg_prerendered.get().unwrap_or_default()
             />
 
             // Errors
@@ -0,0 +1,62 @@
+use dot_ix_model::common::HtmlSrcAndStyles;
+use leptos::{
+    component,
+    html::Div,
+    prelude::{ClassAttribute, Effect, GlobalAttributes, NodeRef, NodeRefAttribute, Signal},
+    view, IntoView,
+};
+
+cfg_if::cfg_if! { if #[cfg(target_arch = "wasm32")] {
+    use wasm_bindgen::prelude::wasm_bindgen;
+
+    #[wasm_bindgen(module = "/public/js/info_graph_html_edges.js")]
+    extern "C" {
+        fn info_graph_html_edges(container: &web_sys::Element);
+    }
+}}
+
+/// Renders a diagram as HTML, without Graphviz.
+///
+/// Nodes are laid out by the browser, and edges are drawn over them once the
+/// layout is known. This is suited for simple nested diagrams, and does not
+/// load the Graphviz wasm module.
+///
+/// The `html_src_and_styles` is generated by `dot_ix_rt::IntoHtmlSrc`.
+#[component]
+pub fn HtmlDiagram(html_src_and_styles: Signal<Option<HtmlSrcAndStyles>>) -> impl IntoView {
+    let html_div_ref = NodeRef::<Div>::new();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _html_src_and_styles = html_src_and_styles;
+    }
+
+    Effect::new(move |_| {
+        #[cfg(not(target_arch = "wasm32"))]
+        let _html_div_ref = html_div_ref;
+
+        #[cfg(target_arch = "wasm32")]
+        use leptos::prelude::Get;
+        #[cfg(target_arch = "wasm32")]
+        if let Some(html_src_and_styles) = html_src_and_styles.get() {
+            let HtmlSrcAndStyles {
+                html_src,
+                styles,
+                theme_warnings: _,
+            } = html_src_and_styles;
+
+            if let Some(html_div) = html_div_ref.get() {
+                html_div.set_inner_html(&format!("<style>{styles}</style>\n{html_src}"));
+                info_graph_html_edges(&html_div);
+            }
+        }
+    });
+
+    view! {
+        <div
+            id="html_diagram_div"
+            node_ref=html_div_ref
+            class="overflow-auto"
+        />
+    }
+}
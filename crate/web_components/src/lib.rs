@@ -2,10 +2,18 @@
 
 //! Web components provided by the `dot_ix` library.
 
-pub use crate::{
-    dot_svg::DotSvg,
-    error_template::{AppError, ErrorTemplate},
-};
+pub use crate::error_template::{AppError, ErrorTemplate};
 
-mod dot_svg;
 mod error_template;
+
+#[cfg(all(feature = "dot_svg", feature = "ssr"))]
+pub use crate::dot_svg::dot_svg_prerender;
+#[cfg(feature = "dot_svg")]
+pub use crate::dot_svg::DotSvg;
+#[cfg(feature = "info_graph_html")]
+pub use crate::html_diagram::HtmlDiagram;
+
+#[cfg(feature = "dot_svg")]
+mod dot_svg;
+#[cfg(feature = "info_graph_html")]
+mod html_diagram;
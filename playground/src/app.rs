@@ -23,6 +23,9 @@ mod info_graph;
 mod tabs;
 mod text_editor;
 
+#[cfg(feature = "ssr")]
+pub use self::info_graph::DotSvgPrerendered;
+
 #[cfg(feature = "ssr")]
 use leptos::{
     hydration::{AutoReload, HydrationScripts},
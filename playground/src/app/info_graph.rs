@@ -3,11 +3,12 @@ use std::time::Duration;
 use dot_ix::{
     model::{
         common::{DotSrcAndStyles, GraphvizDotTheme},
-        info_graph::InfoGraph,
+        info_graph::{InfoGraph, Views},
     },
-    rt::IntoGraphvizDotSrc,
+    rt::{Diagnostic, IntoGraphvizDotSrc},
     web_components::DotSvg,
 };
+use indexmap::IndexSet;
 use leptos::{
     component, leptos_dom,
     prelude::{
@@ -23,7 +24,7 @@ use crate::app::{TabLabel, TextEditor};
 #[cfg(target_arch = "wasm32")]
 use super::QUERY_PARAM_DIAGRAM_ONLY;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(any(target_arch = "wasm32", feature = "ssr"))]
 const INFO_GRAPH_DEMO: &str = include_str!("../../public/examples/demo.yaml");
 
 /// User provided info graph source.
@@ -110,6 +111,46 @@ fn info_graph_src_init() -> String {
     }
 }
 
+/// The demo diagram's SVG, rendered by the server at startup so that it is
+/// shown before Graphviz is loaded in the browser.
+///
+/// This is provided as context when rendering pages on the server.
+#[cfg(feature = "ssr")]
+#[derive(Clone, Debug)]
+pub struct DotSvgPrerendered(std::sync::Arc<str>);
+
+#[cfg(feature = "ssr")]
+impl DotSvgPrerendered {
+    /// Renders the demo diagram using `dot`.
+    ///
+    /// Returns `None` if rendering fails.
+    pub fn demo(dot_renderer: &dot_ix::rt::DotProcessRenderer) -> Option<Self> {
+        use dot_ix::web_components::dot_svg_prerender;
+
+        let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_DEMO)
+            .inspect_err(|error| leptos::logging::error!("Failed to parse demo: {error}"))
+            .ok()?;
+        let dot_src_and_styles =
+            IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+        dot_svg_prerender(dot_renderer, &info_graph, &dot_src_and_styles)
+            .inspect_err(|error| leptos::logging::error!("Failed to pre-render demo: {error}"))
+            .ok()
+            .map(|dot_svg| Self(dot_svg.into()))
+    }
+}
+
+/// Returns the demo diagram's SVG that was rendered at server startup, if any.
+#[cfg(feature = "ssr")]
+fn info_graph_demo_prerender() -> Option<String> {
+    leptos::prelude::use_context::<DotSvgPrerendered>()
+        .map(|dot_svg_prerendered| dot_svg_prerendered.0.to_string())
+}
+
+#[cfg(not(feature = "ssr"))]
+fn info_graph_demo_prerender() -> Option<String> {
+    None
+}
+
 /// Requests and returns the given example.
 pub async fn example_load(example_name: &str) -> Option<String> {
     // Load the example source from static content.
@@ -258,7 +299,12 @@ pub fn InfoGraph(diagram_only: Signal<bool>) -> impl IntoView {
     let (dot_src, set_dot_src) = signal(None::<String>);
 
     let (info_graph, set_info_graph) = signal(InfoGraph::default());
+    let (views, views_set) = signal(Views::default());
+    // Empty when the whole graph is shown.
+    let (view_selection, view_selection_set) = signal(String::from(""));
     let (external_refresh_count, external_refresh_count_set) = signal(0);
+    // Replaced when the diagram is rendered in the browser.
+    let dot_svg_prerendered = Signal::stored(info_graph_demo_prerender());
 
     // Load an example when the selection box is changed.
     let _example_resource_load = LocalResource::new(move || async move {
@@ -274,6 +320,11 @@ pub fn InfoGraph(diagram_only: Signal<bool>) -> impl IntoView {
         let dot_src = dot_src.get();
         let dot_src_and_styles = dot_src_and_styles.get_untracked();
         if let Some((dot_src, mut dot_src_and_styles)) = dot_src.zip(dot_src_and_styles) {
+            // The dot source may have been edited, so its lines no longer map to the
+            // `InfoGraph`.
+            if dot_src_and_styles.dot_src != dot_src {
+                dot_src_and_styles.src_map = None;
+            }
             dot_src_and_styles.dot_src = dot_src;
             dot_src_and_styles_set.set(Some(dot_src_and_styles));
         }
@@ -281,6 +332,7 @@ pub fn InfoGraph(diagram_only: Signal<bool>) -> impl IntoView {
 
     Effect::new(move |_| {
         let info_graph_src = info_graph_src.get();
+        let view_selection = view_selection.get();
 
         let merge_key_exists = info_graph_src.lines().any(|line| {
             // skip whitespace, but ignore comments
@@ -302,42 +354,67 @@ pub fn InfoGraph(diagram_only: Signal<bool>) -> impl IntoView {
 
         match info_graph_result {
             Ok(info_graph) => {
+                views_set.set(info_graph.views().clone());
+
+                // Falls back to the whole graph if the view was removed from the source.
+                let info_graph = &info_graph
+                    .view(&view_selection)
+                    .unwrap_or_else(|| info_graph.clone());
                 set_info_graph.set(info_graph.clone());
 
                 let dot_src_and_styles =
-                    IntoGraphvizDotSrc::into(info_graph, &GraphvizDotTheme::default());
+                    IntoGraphvizDotSrc::into_with_src_map(info_graph, &GraphvizDotTheme::default());
                 dot_src_and_styles_set.set(Some(dot_src_and_styles.clone()));
                 let DotSrcAndStyles {
                     dot_src,
                     styles: _,
                     opts: _,
                     theme_warnings,
+                    src_map,
+                    edge_bundles: _,
                 } = dot_src_and_styles;
 
                 set_dot_src.set(Some(dot_src));
-                if theme_warnings.is_empty() {
+
+                // One line per distinct warning, as warnings for different highlight
+                // states may have the same text.
+                let theme_warning_lines = theme_warnings
+                    .iter()
+                    .map(|theme_warning| {
+                        let any_id = theme_warning.any_id();
+                        let yaml_line = src_map
+                            .as_ref()
+                            .and_then(|src_map| src_map.entries_for_id(any_id.as_str()).next())
+                            .and_then(|entry| entry.yaml_line(&info_graph_src));
+                        match yaml_line {
+                            Some(yaml_line) => {
+                                format!("• `{any_id}` (line {yaml_line}): {theme_warning}")
+                            }
+                            None => format!("• `{any_id}`: {theme_warning}"),
+                        }
+                    })
+                    .collect::<IndexSet<String>>();
+                // The playground renders with an up-to-date Graphviz, which doesn't have the
+                // short cluster label bug.
+                let warning_lines = Diagnostic::info_graph_check(info_graph)
+                    .iter()
+                    .filter(|diagnostic| {
+                        !matches!(diagnostic, Diagnostic::ClusterLabelShort { .. })
+                    })
+                    .fold(theme_warning_lines, |mut warning_lines, diagnostic| {
+                        warning_lines
+                            .insert(format!("• {diagnostic}\n  {}", diagnostic.suggestion()));
+                        warning_lines
+                    });
+                if warning_lines.is_empty() {
                     set_error_text.set(None);
                 } else {
-                    // TODO: format into a list.
-                    let theme_warnings_string = {
-                        let capacity = theme_warnings
-                            .iter()
-                            .map(|theme_warning| theme_warning.len())
-                            .sum::<usize>()
-                            + theme_warnings.len();
-                        let mut theme_warnings_string = String::with_capacity(capacity);
-                        let mut theme_warnings_iter = theme_warnings.iter();
-                        if let Some(theme_warning_first) = theme_warnings_iter.next() {
-                            theme_warnings_string.push_str(theme_warning_first);
-                        }
-                        theme_warnings.iter().for_each(|theme_warning| {
-                            theme_warnings_string.push('\n');
-                            theme_warnings_string.push_str(theme_warning);
-                        });
-
-                        theme_warnings_string
-                    };
-                    set_error_text.set(Some(theme_warnings_string));
+                    set_error_text.set(Some(
+                        warning_lines
+                            .into_iter()
+                            .collect::<Vec<String>>()
+                            .join("\n"),
+                    ));
                 }
                 #[cfg(target_arch = "wasm32")]
                 {
@@ -409,6 +486,10 @@ pub fn InfoGraph(diagram_only: Signal<bool>) -> impl IntoView {
                     diagram_only
                     info_graph
                     dot_src_and_styles
+                    dot_svg_prerendered
+                    views
+                    view_selection
+                    view_selection_set
                 />
             </div>
             <ErrorText error_text />
@@ -563,6 +644,10 @@ pub fn InfoGraphDiagram(
     diagram_only: Signal<bool>,
     info_graph: ReadSignal<InfoGraph>,
     dot_src_and_styles: ReadSignal<Option<DotSrcAndStyles>>,
+    dot_svg_prerendered: Signal<Option<String>>,
+    views: ReadSignal<Views>,
+    view_selection: ReadSignal<String>,
+    view_selection_set: WriteSignal<String>,
 ) -> impl IntoView {
     view! {
         <div
@@ -582,6 +667,11 @@ pub fn InfoGraphDiagram(
                 }
             }}
         >
+            <ViewSelect
+                views
+                view_selection
+                view_selection_set
+            />
             <div
                 class="\
                     w-dvw \
@@ -595,12 +685,81 @@ pub fn InfoGraphDiagram(
                     info_graph=info_graph.into()
                     dot_src_and_styles=dot_src_and_styles.into()
                     diagram_only=diagram_only
+                    dot_svg_prerendered
                 />
             </div>
         </div>
     }
 }
 
+/// Selects which of the `InfoGraph`'s views to render.
+///
+/// Hidden when the `InfoGraph` has no views.
+#[component]
+pub fn ViewSelect(
+    views: ReadSignal<Views>,
+    view_selection: ReadSignal<String>,
+    view_selection_set: WriteSignal<String>,
+) -> impl IntoView {
+    let view_select_div_classes = move || {
+        if views.get().is_empty() {
+            "hidden"
+        } else {
+            "\
+            flex \
+            gap-x-2 \
+            items-center \
+            h-7 \
+            lg:h-9 \
+            px-1 \
+            lg:px-2 \
+            "
+        }
+    };
+
+    view! {
+        <div class=view_select_div_classes>
+            <label class="" for="view_selection">"View:"</label>
+            <select
+                name="view_selection"
+                class="\
+                    border \
+                    border-slate-400 \
+                    rounded \
+                    focus:ring-2 \
+                    focus:ring-blue-500 \
+                    h-5 \
+                    lg:h-7 \
+                "
+                on:change=move |ev| {
+                    let new_value = event_target_value(&ev);
+                    view_selection_set.set(new_value);
+                }
+                prop:value=move || view_selection.get()
+            >
+                <option value="">"Whole graph"</option>
+                {
+                    move || {
+                        views
+                            .get()
+                            .iter()
+                            .map(|(view_id, view)| {
+                                let view_name = view
+                                    .name()
+                                    .map(String::from)
+                                    .unwrap_or_else(|| view_id.to_string());
+                                view! {
+                                    <option value={ view_id.to_string() }>{ view_name }</option>
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                }
+            </select>
+        </div>
+    }
+}
+
 #[component]
 pub fn ErrorText(error_text: ReadSignal<Option<String>>) -> impl IntoView {
     let error_text_classes = move || {
@@ -614,6 +773,7 @@ pub fn ErrorText(error_text: ReadSignal<Option<String>>) -> impl IntoView {
             border-amber-300
             bg-gradient-to-b from-amber-100 to-amber-200
             rounded
+            whitespace-pre-line
             "
         }
     };
@@ -5,7 +5,10 @@ mod app;
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() {
+    use std::path::PathBuf;
+
     use axum::{routing::post, Router};
+    use dot_ix::rt::DotProcessRenderer;
     use leptos::{logging::log, prelude::get_configuration};
     use leptos_axum::{generate_route_list, LeptosRoutes};
     use log4rs::{
@@ -14,7 +17,7 @@ async fn main() {
         filter::threshold::ThresholdFilter,
     };
 
-    use crate::app::{shell, App};
+    use crate::app::{shell, App, DotSvgPrerendered};
 
     let stderr = ConsoleAppender::builder().target(Target::Stderr).build();
     // Log Trace level output to file where trace is the default level
@@ -50,13 +53,37 @@ async fn main() {
     let socket_addr = leptos_options.site_addr;
     let routes = generate_route_list(App);
 
+    // `dot` is used to pre-render diagrams on the server. Set `DOT_IX_DOT_PATH`
+    // if it is not on the `PATH`.
+    let dot_renderer = std::env::var_os("DOT_IX_DOT_PATH")
+        .map(PathBuf::from)
+        .map(|dot_path| DotProcessRenderer::new().with_dot_path(dot_path))
+        .unwrap_or_default();
+    // The demo diagram doesn't change, so it is rendered once at startup instead
+    // of for each page.
+    let dot_svg_prerendered =
+        tokio::task::spawn_blocking(move || DotSvgPrerendered::demo(&dot_renderer))
+            .await
+            .ok()
+            .flatten();
+
     // build our application with a route
     let router = Router::new()
         .route("/api/*fn_name", post(leptos_axum::handle_server_fns))
-        .leptos_routes(&leptos_options, routes, {
-            let leptos_options = leptos_options.clone();
-            move || shell(leptos_options.clone())
-        })
+        .leptos_routes_with_context(
+            &leptos_options,
+            routes,
+            // Shown until the diagram is rendered in the browser.
+            move || {
+                if let Some(dot_svg_prerendered) = dot_svg_prerendered.clone() {
+                    leptos::prelude::provide_context(dot_svg_prerendered);
+                }
+            },
+            {
+                let leptos_options = leptos_options.clone();
+                move || shell(leptos_options.clone())
+            },
+        )
         .fallback(leptos_axum::file_and_error_handler(shell))
         .with_state(leptos_options);
 
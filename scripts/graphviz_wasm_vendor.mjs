@@ -0,0 +1,292 @@
+// Vendors the Graphviz wasm module that `graphviz_dot_svg.js` renders with, for
+// `GraphvizWasmRenderer`.
+//
+// Usage:
+//
+// ```bash
+// curl -fsSLO https://cdn.jsdelivr.net/npm/@hpcc-js/wasm@2.20.0/dist/graphviz.js
+// node scripts/graphviz_wasm_vendor.mjs graphviz.js target/graphviz_wasm
+// ```
+//
+// This writes:
+//
+// * `graphviz.wasm`: The module that `graphviz.js` instantiates.
+// * `graphviz.wasm.map`: The original names of the module's minified imports
+//   and exports, as `minified:original` lines.
+// * `graphviz.wasm.sha256`: The module's SHA-256 checksum, also printed.
+// * `<name>.svg`: The SVG that `graphviz.js` renders for each
+//   `workspace_tests/graphviz_wasm/<name>.dot`, which is the same output as in
+//   the browser.
+//
+// `graphviz.js` embeds the module, so the module is captured when it is
+// instantiated instead of decoded from the file.
+//
+// The published module's import and export names are minified, and
+// `@hpcc-js/wasm` does not publish emscripten's minification map, so the map is
+// recovered from the glue in `graphviz.js`:
+//
+// * Exports assigned to the emscripten module object keep their names, e.g.
+//   `c._malloc=a=>(c._malloc=Z.h)(a)` maps `h` to `malloc`.
+// * `memory` and `__indirect_function_table` are found by their type.
+// * `__wasm_call_ctors` is the first export that is called after the module is
+//   instantiated.
+// * Imports are named by the emscripten library function that `graphviz.js`
+//   passes for them, e.g. the function that throws `Infinity` is
+//   `_emscripten_throw_longjmp`.
+// * `invoke_*` imports name the `stackSave`, `stackRestore`, and `setThrew`
+//   exports that they call.
+//
+// The script fails if an export that `GraphvizWasmRenderer` needs is not found.
+// Imports that are not recognized are listed, and only fail if Graphviz calls
+// them.
+//
+// `workspace_tests/graphviz_wasm/fixture/graphviz.js` is a fixture in the same
+// shape, whose output is checked in next to it:
+//
+// ```bash
+// node scripts/graphviz_wasm_vendor.mjs \
+//     workspace_tests/graphviz_wasm/fixture/graphviz.js \
+//     workspace_tests/graphviz_wasm/fixture
+// ```
+
+import { createHash } from "node:crypto";
+import { mkdirSync, readdirSync, readFileSync, writeFileSync } from "node:fs";
+import { basename, join, resolve } from "node:path";
+import { fileURLToPath, pathToFileURL } from "node:url";
+
+const [graphvizJsPath, outDir] = process.argv.slice(2);
+if (graphvizJsPath === undefined || outDir === undefined) {
+    console.error("Usage: node scripts/graphviz_wasm_vendor.mjs <graphviz.js> <out_dir>");
+    process.exit(1);
+}
+
+const dotDir = fileURLToPath(new URL("../workspace_tests/graphviz_wasm/", import.meta.url));
+
+// Patterns in the source of emscripten library functions, and their names.
+const IMPORT_FINGERPRINTS = [
+    [/\bthrow\s+Infinity\b/, "_emscripten_throw_longjmp"],
+    [/Assertion failed/, "__assert_fail"],
+    [/\.copyWithin\(/, "_emscripten_memcpy_js"],
+    [/\b100663296\b/, "emscripten_resize_heap"],
+    [/^\(\)\s*=>\s*2147483648$/, "emscripten_get_heap_max"],
+    [/^\(\)\s*=>\s*\{?\s*[\w$]+\(\s*(?:""|'')\s*\)\s*;?\s*\}?$/, "_abort_js"],
+];
+
+let wasmBytes = null;
+let wasmImports = null;
+let wasmExports = null;
+// Names of the exports in the order that they are first called.
+const exportsCalled = [];
+
+function wasmBytesCapture(source) {
+    if (source instanceof ArrayBuffer) {
+        wasmBytes = new Uint8Array(source.slice(0));
+    } else if (ArrayBuffer.isView(source)) {
+        wasmBytes = new Uint8Array(source.buffer.slice(source.byteOffset, source.byteOffset + source.byteLength));
+    }
+}
+
+// Returns the instance with each exported function wrapped to record when it is
+// first called.
+function instanceTrace(instance) {
+    wasmExports = instance.exports;
+    const exportsTraced = Object.fromEntries(
+        Object.entries(instance.exports).map(([name, value]) => {
+            if (typeof value !== "function") {
+                return [name, value];
+            }
+            const fnTraced = (...args) => {
+                if (!exportsCalled.includes(name)) {
+                    exportsCalled.push(name);
+                }
+                return value(...args);
+            };
+            Object.defineProperty(fnTraced, "length", { value: value.length });
+            return [name, fnTraced];
+        }),
+    );
+    return { exports: exportsTraced };
+}
+
+const wasmInstantiate = WebAssembly.instantiate;
+WebAssembly.instantiate = async (source, imports) => {
+    wasmBytesCapture(source);
+    wasmImports = imports;
+    const result = await wasmInstantiate(source, imports);
+    if (result instanceof WebAssembly.Instance) {
+        return instanceTrace(result);
+    }
+    return { module: result.module, instance: instanceTrace(result.instance) };
+};
+WebAssembly.Module = new Proxy(WebAssembly.Module, {
+    construct(target, args) {
+        wasmBytesCapture(args[0]);
+        return Reflect.construct(target, args);
+    },
+});
+WebAssembly.Instance = new Proxy(WebAssembly.Instance, {
+    construct(target, args) {
+        wasmImports = args[1];
+        return instanceTrace(Reflect.construct(target, args));
+    },
+});
+
+const graphvizJs = readFileSync(graphvizJsPath, "utf8");
+const { Graphviz } = await import(pathToFileURL(resolve(graphvizJsPath)).href);
+const graphviz = await Graphviz.load();
+if (wasmBytes === null || wasmExports === null) {
+    console.error(`\`${graphvizJsPath}\` did not instantiate a wasm module from bytes.`);
+    process.exit(1);
+}
+
+const minificationMap = minificationMapBuild();
+
+mkdirSync(outDir, { recursive: true });
+const sha256 = createHash("sha256").update(wasmBytes).digest("hex");
+writeFileSync(join(outDir, "graphviz.wasm"), wasmBytes);
+writeFileSync(
+    join(outDir, "graphviz.wasm.map"),
+    [...minificationMap]
+        .filter(([minified, original]) => minified !== original)
+        .map(([minified, original]) => `${minified}:${original}\n`)
+        .join(""),
+);
+writeFileSync(join(outDir, "graphviz.wasm.sha256"), `${sha256}  graphviz.wasm\n`);
+console.log(`${sha256}  graphviz.wasm (Graphviz ${graphviz.version()})`);
+
+readdirSync(dotDir)
+    .filter((fileName) => fileName.endsWith(".dot"))
+    .forEach((fileName) => {
+        const dotSrc = readFileSync(join(dotDir, fileName), "utf8");
+        const svg = graphviz.layout(dotSrc, "svg", "dot");
+        writeFileSync(join(outDir, `${basename(fileName, ".dot")}.svg`), svg);
+    });
+
+// Returns the original name of each minified import and export.
+function minificationMapBuild() {
+    const minificationMap = new Map();
+    const nameInsert = (minified, original) => {
+        const originalExisting = minificationMap.get(minified);
+        if (originalExisting !== undefined && originalExisting !== original) {
+            console.error(`\`${minified}\` maps to both \`${originalExisting}\` and \`${original}\`.`);
+            process.exit(1);
+        }
+        minificationMap.set(minified, original);
+    };
+
+    // Exports.
+    Object.entries(wasmExports).forEach(([minified, value]) => {
+        if (value instanceof WebAssembly.Memory) {
+            nameInsert(minified, "memory");
+        } else if (value instanceof WebAssembly.Table) {
+            nameInsert(minified, "__indirect_function_table");
+        }
+    });
+    const moduleExportPattern = new RegExp(
+        String.raw`(?:\.|\[["'])_([A-Za-z_$][\w$]*)(?:["']\])?\s*=\s*` +
+            String.raw`(?:(?:\([^()]*\)|[\w$]+)\s*=>\s*\(\s*[\w$]+(?:\.|\[["'])_\1(?:["']\])?\s*=\s*)?` +
+            String.raw`[\w$]+(?:\.([\w$]+)|\[["']([\w$]+)["']\])`,
+        "g",
+    );
+    [...graphvizJs.matchAll(moduleExportPattern)].forEach(([, original, minifiedDot, minifiedIndex]) => {
+        const minified = minifiedDot ?? minifiedIndex;
+        if (Object.hasOwn(wasmExports, minified)) {
+            nameInsert(minified, original);
+        }
+    });
+    const wasmCallCtors = exportsCalled[0];
+    if (wasmCallCtors !== undefined && !minificationMap.has(wasmCallCtors) && wasmExports[wasmCallCtors].length === 0) {
+        nameInsert(wasmCallCtors, "__wasm_call_ctors");
+    }
+
+    // Imports.
+    const importsUnnamed = [];
+    Object.entries(wasmImports ?? {}).forEach(([importModule, imports]) => {
+        Object.entries(imports).forEach(([minified, value]) => {
+            const original = typeof value === "function" ? importOriginal(value, nameInsert) : undefined;
+            if (original === undefined) {
+                importsUnnamed.push(`${importModule}.${minified}`);
+            } else {
+                nameInsert(minified, original);
+            }
+        });
+    });
+    if (importsUnnamed.length !== 0) {
+        console.warn(`Imports not recognized, which fail if Graphviz calls them: ${importsUnnamed.join(", ")}`);
+    }
+
+    const exportsMissing = [
+        "memory",
+        "malloc",
+        "__wasm_call_ctors",
+        "emscripten_bind_CGraphviz_CGraphviz_2",
+        "emscripten_bind_CGraphviz_layout_3",
+        "emscripten_bind_CGraphviz___destroy___0",
+    ].filter((original) => ![...minificationMap.values()].includes(original));
+    if (exportsMissing.length !== 0) {
+        console.error(`Exports not found in \`${graphvizJsPath}\`: ${exportsMissing.join(", ")}`);
+        process.exit(1);
+    }
+
+    return new Map([...minificationMap].sort(([a], [b]) => a.localeCompare(b)));
+}
+
+// Returns the emscripten library function that an import is, from its source.
+///
+// For `invoke_*` functions, this also names the exports that it calls.
+function importOriginal(fn, nameInsert) {
+    const fnSrc = Function.prototype.toString.call(fn);
+
+    // `function invoke_vi(index, a1) { var sp = stackSave(); try { ... } catch (e) {
+    // stackRestore(sp); if (e !== e + 0) throw e; _setThrew(1, 0); } }`
+    const invoke = fnSrc.match(
+        new RegExp(
+            String.raw`=\s*([\w$]+)\(\)[\s\S]*\btry\s*\{([\s\S]*)\}\s*catch\s*\(\s*([\w$]+)\s*\)\s*\{` +
+                String.raw`[\s\S]*?([\w$]+)\(\s*[\w$]+\s*\)[\s\S]*?\3\s*!==\s*\3\s*\+\s*0[\s\S]*?([\w$]+)\(\s*1\s*,\s*0\s*\)`,
+        ),
+    );
+    if (invoke !== null) {
+        const [, stackSave, tryBody, , stackRestore, setThrew] = invoke;
+        [
+            [stackSave, "stackSave"],
+            [stackRestore, "stackRestore"],
+            [setThrew, "setThrew"],
+        ].forEach(([ident, original]) => {
+            const minified = identExport(ident);
+            if (minified !== undefined) {
+                nameInsert(minified, original);
+            }
+        });
+
+        // The signature only needs to start with `invoke_`, the import's type is
+        // read from the module.
+        const resultChar = /\breturn\b/.test(tryBody) ? "i" : "v";
+        return `invoke_${resultChar}${"i".repeat(Math.max(fn.length - 1, 0))}`;
+    }
+
+    return IMPORT_FINGERPRINTS.find(([pattern]) => pattern.test(fnSrc))?.[1];
+}
+
+// Returns the minified export that a function in `graphviz.js` calls, following
+// aliases such as `stackSave=()=>_emscripten_stack_get_current()`.
+function identExport(ident, depth = 0) {
+    if (depth > 4) {
+        return undefined;
+    }
+    const identPattern = ident.replace(/\$/g, "\\$");
+    const exportMatch = graphvizJs.match(
+        new RegExp(
+            String.raw`(?<![\w$.])${identPattern}\s*=\s*(?:(?:\([^()]*\)|[\w$]+)\s*=>\s*\(\s*${identPattern}\s*=\s*)?` +
+                String.raw`[\w$]+(?:\.([\w$]+)|\[["']([\w$]+)["']\])`,
+        ),
+    );
+    const minified = exportMatch?.[1] ?? exportMatch?.[2];
+    if (minified !== undefined && Object.hasOwn(wasmExports, minified)) {
+        return minified;
+    }
+
+    const aliasMatch = graphvizJs.match(
+        new RegExp(String.raw`(?<![\w$.])${identPattern}\s*=\s*(?:\([^()]*\)|[\w$]+)\s*=>\s*([\w$]+)\(`),
+    );
+    return aliasMatch === null ? undefined : identExport(aliasMatch[1], depth + 1);
+}
@@ -0,0 +1,194 @@
+//! Benchmarks for generating diagram sources from large `InfoGraph`s.
+//!
+//! Run with `cargo bench -p workspace_tests`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use dot_ix::{
+    model::{
+        common::{
+            AnyId, EdgeId, Edges, GraphvizDotTheme, NodeHierarchy, NodeId, NodeNames, TagId,
+            TagItems, TagNames,
+        },
+        info_graph::InfoGraph,
+        theme::Theme,
+    },
+    rt::{write_dot, IntoGraphvizDotSrc},
+};
+
+/// Number of elements -- nodes and edges -- in each benchmarked graph.
+const ELEMENT_COUNTS: [usize; 3] = [1_000, 10_000, 50_000];
+
+/// Number of nodes within each cluster.
+const CLUSTER_SIZE: usize = 9;
+
+/// Number of tags, each of which is associated with every `TAG_COUNT`th
+/// element.
+const TAG_COUNT: usize = 10;
+
+/// Returns an `InfoGraph` with approximately `element_count` nodes and edges.
+///
+/// Half of the elements are nodes, grouped into clusters of `CLUSTER_SIZE`
+/// nodes, and the other half are edges between consecutive nodes.
+fn info_graph(element_count: usize) -> InfoGraph {
+    let node_count = element_count / 2;
+    let cluster_count = node_count.div_ceil(CLUSTER_SIZE + 1);
+
+    let mut hierarchy = NodeHierarchy::new();
+    let mut node_names = NodeNames::with_capacity(node_count);
+    let mut node_ids = Vec::with_capacity(node_count);
+    (0..cluster_count).for_each(|cluster_index| {
+        let cluster_id = NodeId::try_from(format!("cluster_{cluster_index}"))
+            .expect("Expected cluster ID to be valid.");
+        let mut cluster_hierarchy = NodeHierarchy::new();
+        (0..CLUSTER_SIZE).for_each(|node_index| {
+            let node_id = NodeId::try_from(format!("node_{cluster_index}_{node_index}"))
+                .expect("Expected node ID to be valid.");
+            node_names.insert(
+                node_id.clone(),
+                format!("Node {cluster_index}.{node_index}"),
+            );
+            cluster_hierarchy.insert(node_id.clone(), NodeHierarchy::new());
+            node_ids.push(node_id);
+        });
+
+        node_names.insert(cluster_id.clone(), format!("Cluster {cluster_index}"));
+        hierarchy.insert(cluster_id, cluster_hierarchy);
+    });
+
+    let mut edges = Edges::with_capacity(node_ids.len());
+    let mut tag_items = TagItems::with_capacity(TAG_COUNT);
+    node_ids
+        .windows(2)
+        .enumerate()
+        .for_each(|(edge_index, node_id_pair)| {
+            let [src, target] = [&node_id_pair[0], &node_id_pair[1]];
+            let edge_id = EdgeId::try_from(format!("{src}__{target}"))
+                .expect("Expected edge ID to be valid.");
+
+            let tag_id = tag_id(edge_index % TAG_COUNT);
+            let tag_any_ids: &mut Vec<AnyId> = tag_items.entry(tag_id).or_default();
+            tag_any_ids.push(AnyId::from(src.clone()));
+            tag_any_ids.push(AnyId::from(edge_id.clone()));
+
+            edges.insert(edge_id, [src.clone(), target.clone()]);
+        });
+
+    let tags = (0..TAG_COUNT)
+        .map(|tag_index| (tag_id(tag_index), format!("Tag {tag_index}")))
+        .collect::<TagNames>();
+
+    InfoGraph::default()
+        .with_hierarchy(hierarchy)
+        .with_node_names(node_names)
+        .with_edges(edges)
+        .with_tags(tags)
+        .with_tag_items(tag_items)
+}
+
+/// Returns an `InfoGraph` from [`info_graph`], with a theme that styles
+/// elements using each kind of `ThemeSelector` as well as element IDs.
+fn info_graph_themed(element_count: usize) -> InfoGraph {
+    let theme = serde_yaml::from_str::<Theme>(INFO_GRAPH_THEME)
+        .expect("Expected benchmark theme to be valid.");
+
+    info_graph(element_count).with_theme(theme)
+}
+
+/// Theme with a key for each kind of `ThemeSelector`, and element IDs.
+const INFO_GRAPH_THEME: &str = r#"
+styles:
+  cluster:
+    fill_color: slate
+  leaf:
+    fill_color: blue
+  "depth:1":
+    stroke_style: dashed
+  "tag:tag_0":
+    stroke_color: red
+  "tag:tag_5":
+    stroke_color: green
+  "node:node_1*":
+    shape_color: emerald
+  "edge:node_2*":
+    stroke_width: "2"
+  "edge:from=node_3_*":
+    stroke_color: amber
+  "edge:to=node_4_*":
+    stroke_color: violet
+  cluster_0:
+    fill_color: yellow
+  node_0_0:
+    fill_color: red
+  node_0_0__node_0_1:
+    stroke_color: pink
+"#;
+
+fn tag_id(tag_index: usize) -> TagId {
+    TagId::try_from(format!("tag_{tag_index}")).expect("Expected tag ID to be valid.")
+}
+
+fn into_graphviz_dot_src(c: &mut Criterion) {
+    let theme = GraphvizDotTheme::default();
+    let mut group = c.benchmark_group("into_graphviz_dot_src");
+    group.sample_size(10);
+
+    ELEMENT_COUNTS.into_iter().for_each(|element_count| {
+        let info_graph = info_graph(element_count);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(element_count),
+            &info_graph,
+            |b, info_graph| b.iter(|| IntoGraphvizDotSrc::into(info_graph, &theme)),
+        );
+    });
+
+    group.finish();
+}
+
+fn into_graphviz_dot_src_themed(c: &mut Criterion) {
+    let theme = GraphvizDotTheme::default();
+    let mut group = c.benchmark_group("into_graphviz_dot_src_themed");
+    group.sample_size(10);
+
+    ELEMENT_COUNTS.into_iter().for_each(|element_count| {
+        let info_graph = info_graph_themed(element_count);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(element_count),
+            &info_graph,
+            |b, info_graph| b.iter(|| IntoGraphvizDotSrc::into(info_graph, &theme)),
+        );
+    });
+
+    group.finish();
+}
+
+fn write_dot_to_string(c: &mut Criterion) {
+    let theme = GraphvizDotTheme::default();
+    let mut group = c.benchmark_group("write_dot");
+    group.sample_size(10);
+
+    ELEMENT_COUNTS.into_iter().for_each(|element_count| {
+        let info_graph = info_graph(element_count);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(element_count),
+            &info_graph,
+            |b, info_graph| {
+                b.iter(|| {
+                    let mut dot_src = String::new();
+                    write_dot(info_graph, &theme, &mut dot_src)
+                        .expect("Expected writing to `String` to succeed.");
+                    dot_src
+                })
+            },
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    into_graphviz_dot_src,
+    into_graphviz_dot_src_themed,
+    write_dot_to_string
+);
+criterion_main!(benches);
@@ -0,0 +1,11 @@
+// Fixture in the shape of the `@hpcc-js/wasm` `graphviz.js` glue: an
+// emscripten module whose import and export names are minified, bundled and
+// minified, which embeds `graphviz.wat`.
+//
+// Used to check `scripts/graphviz_wasm_vendor.mjs` and `GraphvizWasmRenderer`
+// against each other without the published package.
+var W="AGFzbQEAAAABKghgA39/fwBgAX8AYAAAYAR/f39/AX9gAX8Bf2ACf38AYAABf2ACf38BfwIZBAFhAWEAAAFhAWIAAQFhAWMAAgFhAWQAAwMMCwIEAgQFBgEHAwQBBAQBcAABBQMBAAEGHQV/AUGAwAALfwFBgCALfwFBAAt/AUEAC38BQQALBy0LAWUCAAFmAQABZwAGAWgABwFpAAgBagAJAWsACgFsAAsBbQAMAW4ADQFvAA4JBwEAQQALAQQKvgELBAAQAgsgAQF/AkADQCAAIAFqLQAARQ0BIAFBAWohAQwACwsgAQsGAEEBJAMLEQEBfyMAIQEjACAAaiQAIAELBgAgACQCCwQAIwELBgAgACQBCwQAQRALXQEDfyMDRQRAQcAIJARBAA8LIAEQBSEEIARBIGoQByEFIAVBgAhBCRAAIAVBCWogASAEEABBACQCQQAQAUGQCEGwCCMCGyEGIAUgBEEJamogBiAGEAVBAWoQACAFCwQAIwQLAgALC2QEAEGACAsJPHN2Zz48IS0tAEGQCAsXLS0+PGc+Y2F1Z2h0PC9nPjwvc3ZnPgAAQbAICwotLT48L3N2Zz4AAEHACAshRXJyb3I6IGNvbnN0cnVjdG9ycyBkaWQgbm90IHJ1bgoAAOkBBG5hbWUBXQcAFGVtc2NyaXB0ZW5fbWVtY3B5X2pzAQhpbnZva2VfdgIYZW1zY3JpcHRlbl90aHJvd19sb25nam1wAwhmZF93cml0ZQQGdGhyb3dzBQZzdHJsZW4HBm1hbGxvYwJJAwUCAANwdHIBA2xlbgcCAARzaXplAQNwdHIMBwAEc2VsZgEDZG90AgZmb3JtYXQDBmVuZ2luZQQDbGVuBQNvdXQGBnN1ZmZpeAMOAQUCAANlbmQBBG5leHQHKAUABGhlYXABBXN0YWNrAgV0aHJldwMFY3RvcnMECmxhc3RfZXJyb3I=";
+var Ha=(()=>async function(c={}){var Z,F,Ea;function Ia(a){return Uint8Array.from(atob(a),b=>b.charCodeAt(0))}var Ja=()=>Ka(),Ka=()=>(Ka=Z.j)(),La=a=>Va(a),Va=a=>(Va=Z.k)(a),Ma=(a,b)=>(Ma=Z.i)(a,b),Ua=a=>Ea.get(a);var Na=(a,b,d)=>F.copyWithin(a,b,b+d);function Oa(a){var b=Ja();try{Ua(a)()}catch(d){if(La(b),d!==d+0)throw d;Ma(1,0)}}var Pa=()=>{throw Infinity},Qa=(a,b,d,e)=>{try{return Xa(a,b,d,e)}catch(f){return f.errno}},Xa=()=>{throw{errno:52}};var Ra={a:Na,b:Oa,c:Pa,d:Qa};c._malloc=a=>(c._malloc=Z.h)(a);c._emscripten_bind_CGraphviz_CGraphviz_2=(a,b)=>(c._emscripten_bind_CGraphviz_CGraphviz_2=Z.l)(a,b);c._emscripten_bind_CGraphviz_layout_3=(a,b,d,e)=>(c._emscripten_bind_CGraphviz_layout_3=Z.m)(a,b,d,e);c._emscripten_bind_CGraphviz_lastError_0=a=>(c._emscripten_bind_CGraphviz_lastError_0=Z.n)(a);c._emscripten_bind_CGraphviz___destroy___0=a=>(c._emscripten_bind_CGraphviz___destroy___0=Z.o)(a);var{instance:Sa}=await WebAssembly.instantiate(Ia(W),{a:Ra});Z=Sa.exports;F=new Uint8Array(Z.e.buffer);Ea=Z.f;Z.g();c.HEAPU8=F;return c})();
+function Ta(c,a){var b=new TextEncoder().encode(a+"\0"),d=c._malloc(b.length);return c.HEAPU8.set(b,d),d}
+function Ya(c,a){if(a===0)return"";var b=c.HEAPU8.indexOf(0,a);return new TextDecoder().decode(c.HEAPU8.subarray(a,b))}
+export class Graphviz{constructor(c){this._module=c}static async load(){return new Graphviz(await Ha())}version(){return"fixture"}layout(a,b="svg",d="dot"){var c=this._module,e=c._emscripten_bind_CGraphviz_CGraphviz_2(0,0),f=Ya(c,c._emscripten_bind_CGraphviz_layout_3(e,Ta(c,a),Ta(c,b),Ta(c,d))),g=Ya(c,c._emscripten_bind_CGraphviz_lastError_0(e));if(c._emscripten_bind_CGraphviz___destroy___0(e),!f&&g)throw new Error(g);return f}}
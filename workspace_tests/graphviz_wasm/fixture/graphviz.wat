@@ -0,0 +1,93 @@
+;; Module embedded in `graphviz.js`, with the imports and exports that
+;; `GraphvizWasmRenderer` uses, under minified names.
+;;
+;; `layout` returns `<svg><!--{dot_src}--><g>caught</g></svg>` when the
+;; constructors have run, and a `longjmp` through `invoke_v` is caught.
+(module
+  (import "a" "a" (func $emscripten_memcpy_js (param i32 i32 i32)))
+  (import "a" "b" (func $invoke_v (param i32)))
+  (import "a" "c" (func $emscripten_throw_longjmp))
+  (import "a" "d" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+
+  (memory (export "e") 1)
+  (table (export "f") 1 funcref)
+  (elem (i32.const 0) $throws)
+
+  (global $heap (mut i32) (i32.const 8192))
+  (global $stack (mut i32) (i32.const 4096))
+  (global $threw (mut i32) (i32.const 0))
+  (global $ctors (mut i32) (i32.const 0))
+  (global $last_error (mut i32) (i32.const 0))
+
+  (data (i32.const 1024) "<svg><!--")
+  (data (i32.const 1040) "--><g>caught</g></svg>\00")
+  (data (i32.const 1072) "--></svg>\00")
+  (data (i32.const 1088) "Error: constructors did not run\0a\00")
+
+  (func $throws (call $emscripten_throw_longjmp))
+
+  (func $strlen (param $ptr i32) (result i32) (local $len i32)
+    (block $end
+      (loop $next
+        (br_if $end (i32.eqz (i32.load8_u (i32.add (local.get $ptr) (local.get $len)))))
+        (local.set $len (i32.add (local.get $len) (i32.const 1)))
+        (br $next)))
+    (local.get $len))
+
+  ;; `__wasm_call_ctors`
+  (func (export "g")
+    (global.set $ctors (i32.const 1)))
+
+  ;; `malloc`
+  (func $malloc (export "h") (param $size i32) (result i32) (local $ptr i32)
+    (local.set $ptr (global.get $heap))
+    (global.set $heap (i32.add (global.get $heap) (local.get $size)))
+    (local.get $ptr))
+
+  ;; `setThrew`
+  (func (export "i") (param i32 i32)
+    (global.set $threw (local.get 0)))
+
+  ;; `emscripten_stack_get_current`
+  (func (export "j") (result i32)
+    (global.get $stack))
+
+  ;; `_emscripten_stack_restore`
+  (func (export "k") (param i32)
+    (global.set $stack (local.get 0)))
+
+  ;; `emscripten_bind_CGraphviz_CGraphviz_2`
+  (func (export "l") (param i32 i32) (result i32)
+    (i32.const 16))
+
+  ;; `emscripten_bind_CGraphviz_layout_3`
+  (func (export "m")
+    (param $self i32) (param $dot i32) (param $format i32) (param $engine i32) (result i32)
+    (local $len i32) (local $out i32) (local $suffix i32)
+    (if (i32.eqz (global.get $ctors))
+      (then
+        (global.set $last_error (i32.const 1088))
+        (return (i32.const 0))))
+
+    (local.set $len (call $strlen (local.get $dot)))
+    (local.set $out (call $malloc (i32.add (local.get $len) (i32.const 32))))
+    (call $emscripten_memcpy_js (local.get $out) (i32.const 1024) (i32.const 9))
+    (call $emscripten_memcpy_js
+      (i32.add (local.get $out) (i32.const 9)) (local.get $dot) (local.get $len))
+
+    (global.set $threw (i32.const 0))
+    (call $invoke_v (i32.const 0))
+    (local.set $suffix (select (i32.const 1040) (i32.const 1072) (global.get $threw)))
+    (call $emscripten_memcpy_js
+      (i32.add (local.get $out) (i32.add (local.get $len) (i32.const 9)))
+      (local.get $suffix)
+      (i32.add (call $strlen (local.get $suffix)) (i32.const 1)))
+    (local.get $out))
+
+  ;; `emscripten_bind_CGraphviz_lastError_0`
+  (func (export "n") (param i32) (result i32)
+    (global.get $last_error))
+
+  ;; `emscripten_bind_CGraphviz___destroy___0`
+  (func (export "o") (param i32))
+)
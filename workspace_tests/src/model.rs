@@ -1,2 +1,4 @@
+mod common;
 mod edge;
+mod info_graph;
 mod theme;
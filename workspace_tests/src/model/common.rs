@@ -0,0 +1,2 @@
+mod dot_src_and_styles;
+mod graphviz_attrs;
@@ -0,0 +1,17 @@
+use dot_ix::model::{common::dot_src_and_styles::EdgeBundles, edge_id};
+
+#[test]
+fn svg_edge_ids_insert_annotates_bundled_edge_elements() {
+    let edge_bundles = [(edge_id!("a__b"), vec![edge_id!("a__b"), edge_id!("a__b_2")])]
+        .into_iter()
+        .collect::<EdgeBundles>();
+    let dot_svg = "<g id=\"a__b\" class=\"edge\">\n<g id=\"a__c\" class=\"edge\">";
+
+    let dot_svg = edge_bundles.svg_edge_ids_insert(dot_svg);
+
+    assert_eq!(
+        "<g id=\"a__b\" data-edge-ids=\"a__b a__b_2\" class=\"edge\">\n\
+        <g id=\"a__c\" class=\"edge\">",
+        dot_svg
+    );
+}
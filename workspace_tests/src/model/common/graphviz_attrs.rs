@@ -0,0 +1 @@
+mod arrow_type;
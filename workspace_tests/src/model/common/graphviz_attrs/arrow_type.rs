@@ -0,0 +1,68 @@
+use dot_ix::model::common::graphviz_attrs::{ArrowPrimitive, ArrowShape, ArrowSide, ArrowType};
+
+#[test]
+fn from_str_parses_single_primitive() {
+    let arrow_type = "diamond".parse::<ArrowType>().unwrap();
+
+    assert_eq!(ArrowType::from(ArrowPrimitive::Diamond), arrow_type);
+}
+
+#[test]
+fn from_str_parses_modifiers_and_multiple_shapes() {
+    let arrow_type = "crowodot".parse::<ArrowType>().unwrap();
+
+    assert_eq!(
+        &[
+            ArrowShape::new(ArrowPrimitive::Crow),
+            ArrowShape::new(ArrowPrimitive::Dot).with_open(true),
+        ],
+        arrow_type.shapes()
+    );
+
+    let arrow_type = "lteeoldiamond".parse::<ArrowType>().unwrap();
+
+    assert_eq!(
+        &[
+            ArrowShape::new(ArrowPrimitive::Tee).with_side(Some(ArrowSide::Left)),
+            ArrowShape::new(ArrowPrimitive::Diamond)
+                .with_open(true)
+                .with_side(Some(ArrowSide::Left)),
+        ],
+        arrow_type.shapes()
+    );
+}
+
+#[test]
+fn display_round_trips() {
+    [
+        "normal",
+        "odiamond",
+        "rvee",
+        "teetee",
+        "crowodot",
+        "icurveinvboxnone",
+    ]
+    .into_iter()
+    .for_each(|s| {
+        let arrow_type = s.parse::<ArrowType>().unwrap();
+        assert_eq!(s, arrow_type.to_string());
+    });
+}
+
+#[test]
+fn from_str_returns_err_for_invalid_shapes() {
+    assert!("".parse::<ArrowType>().is_err());
+    assert!("arrow".parse::<ArrowType>().is_err());
+    assert!("ocrowx".parse::<ArrowType>().is_err());
+    assert!("teeteeteeteetee".parse::<ArrowType>().is_err());
+}
+
+#[test]
+fn deserialize_arrow_type() {
+    let arrow_type = serde_yaml::from_str::<ArrowType>("odiamond").unwrap();
+
+    assert_eq!(
+        ArrowType::from(ArrowShape::new(ArrowPrimitive::Diamond).with_open(true)),
+        arrow_type
+    );
+}
@@ -0,0 +1,328 @@
+use dot_ix::model::{
+    info_graph::{GraphDir, InfoGraph},
+    theme::AnyIdOrDefaults,
+};
+
+const INFO_GRAPH_WITH_VIEWS: &str = r#"
+direction: horizontal
+hierarchy:
+  infra:
+    db: {}
+    cache: {}
+  app:
+    web: {}
+    worker: {}
+  user: {}
+node_names:
+  db: "Database"
+  user: "User"
+edges:
+  user__web: [user, web]
+  web__db: [web, "db:w"]
+  web__cache: [web, cache]
+  worker__db: [worker, db]
+  db__cache: [db, cache]
+edge_descs:
+  db__cache: "replicates"
+tags:
+  tag_data: "Data"
+  tag_ops: "Operations"
+tag_items:
+  tag_data: [web__db, worker__db]
+  tag_ops: [worker]
+views:
+  data_flow:
+    name: "Data Flow"
+    tags_include: [tag_data]
+    direction: vertical
+  overview:
+    clusters_collapsed: [infra]
+    tags_exclude: [tag_ops]
+    theme:
+      styles:
+        edge_defaults:
+          stroke_style: dashed
+"#;
+
+#[test]
+fn view_returns_none_for_unknown_view() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_WITH_VIEWS).unwrap();
+
+    assert!(info_graph.view("unknown").is_none());
+}
+
+#[test]
+fn view_includes_nodes_of_tagged_edges_and_their_clusters() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_WITH_VIEWS).unwrap();
+
+    let info_graph_view = info_graph.view("data_flow").unwrap();
+
+    let mut node_ids = info_graph_view
+        .hierarchy_flat()
+        .into_keys()
+        .map(|node_id| node_id.as_str())
+        .collect::<Vec<_>>();
+    node_ids.sort_unstable();
+    assert_eq!(vec!["app", "db", "infra", "web", "worker"], node_ids);
+    assert_eq!(
+        vec!["web__db", "worker__db"],
+        info_graph_view
+            .edges()
+            .keys()
+            .map(|edge_id| edge_id.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert!(!info_graph_view.node_names().contains_key("user"));
+    assert!(info_graph_view.edge_descs().is_empty());
+    assert_eq!(GraphDir::Vertical, info_graph_view.direction());
+    assert_eq!(info_graph.views(), info_graph_view.views());
+}
+
+#[test]
+fn view_collapses_clusters_and_redirects_edges() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_WITH_VIEWS).unwrap();
+
+    let info_graph_view = info_graph.view("overview").unwrap();
+
+    assert!(info_graph_view
+        .hierarchy()
+        .get("infra")
+        .expect("Expected `infra` to be in the view.")
+        .is_empty());
+    // `worker` is excluded by `tag_ops`, and `db__cache` is within `infra`.
+    assert_eq!(
+        vec![
+            ("user__web", ["user", "web"]),
+            ("web__db", ["web", "infra"]),
+            ("web__cache", ["web", "infra"]),
+        ],
+        info_graph_view
+            .edges()
+            .iter()
+            .map(|(edge_id, [src, target])| (edge_id.as_str(), [src.as_str(), target.as_str()]))
+            .collect::<Vec<_>>()
+    );
+    assert!(!info_graph_view.tags().contains_key("tag_ops"));
+    assert!(!info_graph_view.node_names().contains_key("db"));
+    assert_eq!(GraphDir::Horizontal, info_graph_view.direction());
+    assert!(info_graph_view
+        .theme()
+        .styles
+        .get(&AnyIdOrDefaults::EdgeDefaults)
+        .is_some());
+}
+
+const INFO_GRAPH_WITH_VIEW_THEMES: &str = r#"
+hierarchy:
+  a: {}
+theme:
+  cascade: true
+views:
+  without_base:
+    theme:
+      merge_with_base: false
+  without_cascade:
+    theme:
+      cascade: false
+  unchanged: {}
+"#;
+
+#[test]
+fn view_theme_merge_with_base_overrides_graph_theme() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_WITH_VIEW_THEMES).unwrap();
+
+    let info_graph_view = info_graph.view("without_base").unwrap();
+
+    assert!(!info_graph_view.theme().merge_with_base());
+    // Not set by the view, so the graph theme's value is kept.
+    assert!(info_graph_view.theme().cascade());
+}
+
+#[test]
+fn view_theme_cascade_overrides_graph_theme() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_WITH_VIEW_THEMES).unwrap();
+
+    let info_graph_view = info_graph.view("without_cascade").unwrap();
+
+    assert!(!info_graph_view.theme().cascade());
+    assert!(info_graph_view.theme().merge_with_base());
+}
+
+#[test]
+fn view_theme_keeps_graph_theme_settings_when_not_set() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_WITH_VIEW_THEMES).unwrap();
+
+    let info_graph_view = info_graph.view("unchanged").unwrap();
+
+    assert!(info_graph_view.theme().cascade());
+    assert!(info_graph_view.theme().merge_with_base());
+}
+
+#[test]
+fn view_excludes_nodes_with_descendants_and_connected_edges() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a:
+    a0: {}
+  b: {}
+edges:
+  a0__b: [a0, b]
+views:
+  without_a:
+    nodes_exclude: [a]
+"#,
+    )
+    .unwrap();
+
+    let info_graph_view = info_graph.view("without_a").unwrap();
+
+    assert_eq!(
+        vec!["b"],
+        info_graph_view
+            .hierarchy_flat()
+            .into_keys()
+            .map(|node_id| node_id.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert!(info_graph_view.edges().is_empty());
+}
+
+const INFO_GRAPH_NESTED: &str = r#"
+hierarchy:
+  region:
+    vpc:
+      subnet_a: {}
+      subnet_b: {}
+    bucket: {}
+  user: {}
+node_descs:
+  region: "Main region"
+edges:
+  user__subnet_a: [user, subnet_a]
+  user__subnet_b: [user, "subnet_b:n"]
+  user__bucket: [user, bucket]
+  subnet_a__subnet_b: [subnet_a, subnet_b]
+tags:
+  tag_network: "Network"
+tag_items:
+  tag_network: [user__subnet_b]
+"#;
+
+#[test]
+fn depth_limited_collapses_clusters_at_max_depth() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_NESTED).unwrap();
+
+    let info_graph_depth_limited = info_graph.depth_limited(0);
+
+    let region_hierarchy = info_graph_depth_limited
+        .hierarchy()
+        .get("region")
+        .expect("Expected `region` to be rendered.");
+    assert!(region_hierarchy.is_empty());
+    assert_eq!(
+        Some("Main region\n4 nodes"),
+        info_graph_depth_limited
+            .node_descs()
+            .get("region")
+            .map(String::as_str)
+    );
+    // All edges from `user` are merged into the first edge.
+    assert_eq!(
+        vec![("user__subnet_a", ["user", "region"])],
+        info_graph_depth_limited
+            .edges()
+            .iter()
+            .map(|(edge_id, [src, target])| (edge_id.as_str(), [src.as_str(), target.as_str()]))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some("3 edges"),
+        info_graph_depth_limited
+            .edge_descs()
+            .get("user__subnet_a")
+            .map(String::as_str)
+    );
+    assert_eq!(
+        vec!["user__subnet_a"],
+        info_graph_depth_limited.tag_items()["tag_network"]
+            .iter()
+            .map(|any_id| any_id.as_str())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn depth_limited_keeps_nodes_above_max_depth() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_NESTED).unwrap();
+
+    let info_graph_depth_limited = info_graph.depth_limited(1);
+
+    let mut node_ids = info_graph_depth_limited
+        .hierarchy_flat()
+        .into_keys()
+        .map(|node_id| node_id.as_str())
+        .collect::<Vec<_>>();
+    node_ids.sort_unstable();
+    assert_eq!(vec!["bucket", "region", "user", "vpc"], node_ids);
+    assert_eq!(
+        vec![
+            ("user__subnet_a", ["user", "vpc"]),
+            ("user__bucket", ["user", "bucket"]),
+        ],
+        info_graph_depth_limited
+            .edges()
+            .iter()
+            .map(|(edge_id, [src, target])| (edge_id.as_str(), [src.as_str(), target.as_str()]))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some("2 nodes"),
+        info_graph_depth_limited
+            .node_descs()
+            .get("vpc")
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some("2 edges"),
+        info_graph_depth_limited
+            .edge_descs()
+            .get("user__subnet_a")
+            .map(String::as_str)
+    );
+}
+
+#[test]
+fn depth_limited_lifts_tags_of_hidden_items_onto_collapsed_cluster() {
+    let info_graph_src = format!(
+        "{INFO_GRAPH_NESTED}  \
+        tag_subnet: [user, subnet_b, subnet_a__subnet_b]\n"
+    );
+    let info_graph = serde_yaml::from_str::<InfoGraph>(&info_graph_src).unwrap();
+
+    let info_graph_depth_limited = info_graph.depth_limited(1);
+
+    assert_eq!(
+        vec!["user", "vpc"],
+        info_graph_depth_limited.tag_items()["tag_subnet"]
+            .iter()
+            .map(|any_id| any_id.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["user__subnet_a"],
+        info_graph_depth_limited.tag_items()["tag_network"]
+            .iter()
+            .map(|any_id| any_id.as_str())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn depth_limited_does_not_change_graph_shallower_than_max_depth() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_NESTED).unwrap();
+
+    let info_graph_depth_limited = info_graph.depth_limited(2);
+
+    assert_eq!(info_graph, info_graph_depth_limited);
+}
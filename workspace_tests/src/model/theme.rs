@@ -2,14 +2,20 @@ use std::collections::HashMap;
 
 use dot_ix::{
     model::{
-        common::NodeHierarchy,
-        info_graph::GraphStyle,
+        common::{AnyId, Edges, GraphvizAttrs, NodeHierarchy, TagItems},
+        edge_id,
+        info_graph::{GraphStyle, InfoGraph},
         node_id, tag_id,
-        theme::{AnyIdOrDefaults, CssClassPartials, CssClasses, Theme, ThemeAttr},
+        theme::{
+            AnyIdOrDefaults, CssClassPartials, CssClasses, HighlightState, StyleFor, Theme,
+            ThemeAttr, ThemeSelector, ThemeSource, ThemeWarning, ThemeWarningSeverity,
+        },
     },
     rt::InfoGraphDot,
 };
 
+mod theme_selector;
+
 #[test]
 fn theme_default_is_empty() {
     let theme = Theme::default();
@@ -63,10 +69,15 @@ fn tag_theme_default_contains_peer_focus_lime() {
         node_id_to_hierarchy
     };
     let node_id_to_hierarchy = &node_id_to_hierarchy;
+    let node_id_to_parent = &HashMap::new();
     let diagram_theme = Theme::new();
     let info_graph_dot = InfoGraphDot {
         graph_style: GraphStyle::Box,
         node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
         node_ids: vec![&test_node_id],
         edge_ids: vec![],
     };
@@ -111,10 +122,15 @@ fn tag_theme_merge_resolves_node_outline() {
         node_id_to_hierarchy
     };
     let node_id_to_hierarchy = &node_id_to_hierarchy;
+    let node_id_to_parent = &HashMap::new();
     let diagram_theme = Theme::new();
     let info_graph_dot = InfoGraphDot {
         graph_style: GraphStyle::Circle,
         node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
         node_ids: vec![&test_node_id],
         edge_ids: vec![],
     };
@@ -141,3 +157,670 @@ fn tag_theme_merge_resolves_node_outline() {
         "Theme warnings: `{theme_warnings:?}`",
     );
 }
+
+#[test]
+fn cascade_inherits_cluster_styles_in_descendants() {
+    let info_graph = info_graph_nested();
+    let theme = cascade_theme().with_cascade(true);
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: node_id_to_hierarchy.keys().copied().collect(),
+        edge_ids: vec![],
+    };
+
+    let (el_css_classes, _theme_warnings) = theme.el_css_classes(&info_graph_dot);
+
+    let b_classes = el_css_classes
+        .get("b")
+        .expect("Expected `b` to have classes.");
+    assert!(b_classes.contains("[&>path]:fill-red-"), "{b_classes:?}");
+    assert!(b_classes.contains("[&>path]:stroke-blue-"), "{b_classes:?}");
+    let c_classes = el_css_classes
+        .get("c")
+        .expect("Expected `c` to have classes.");
+    assert!(c_classes.contains("[&>path]:fill-red-"), "{c_classes:?}");
+    assert!(
+        c_classes.contains("[&>path]:stroke-amber-"),
+        "{c_classes:?}"
+    );
+    assert!(!c_classes.contains("stroke-blue-"), "{c_classes:?}");
+}
+
+#[test]
+fn cascade_node_attr_overrides_more_specific_inherited_attr() {
+    let info_graph = info_graph_nested();
+    let mut theme = Theme::new().with_cascade(true);
+    theme.insert(
+        AnyIdOrDefaults::AnyId(AnyId::from(node_id!("a"))),
+        CssClassPartials::from_iter([(ThemeAttr::FillColorNormal, "red".to_string())]),
+    );
+    theme.insert(
+        AnyIdOrDefaults::AnyId(AnyId::from(node_id!("c"))),
+        CssClassPartials::from_iter([(ThemeAttr::FillColor, "amber".to_string())]),
+    );
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: node_id_to_hierarchy.keys().copied().collect(),
+        edge_ids: vec![],
+    };
+
+    let (el_css_classes, _theme_warnings) = theme.el_css_classes(&info_graph_dot);
+
+    let b_classes = el_css_classes
+        .get("b")
+        .expect("Expected `b` to have classes.");
+    assert!(b_classes.contains("[&>path]:fill-red-"), "{b_classes:?}");
+    let c_classes = el_css_classes
+        .get("c")
+        .expect("Expected `c` to have classes.");
+    assert!(c_classes.contains("[&>path]:fill-amber-"), "{c_classes:?}");
+    assert!(!c_classes.contains("fill-red-"), "{c_classes:?}");
+
+    let theme_explanation = theme
+        .explain(&info_graph_dot, &AnyId::from(node_id!("c")))
+        .expect("Expected `c` to be explained.");
+    assert_eq!(c_classes, &theme_explanation.css_classes);
+    assert!(theme_explanation
+        .theme_attr_explanation(ThemeAttr::FillColorNormal)
+        .is_none());
+}
+
+#[test]
+fn cascade_disabled_does_not_inherit_cluster_styles() {
+    let info_graph = info_graph_nested();
+    let theme = cascade_theme();
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: node_id_to_hierarchy.keys().copied().collect(),
+        edge_ids: vec![],
+    };
+
+    let (el_css_classes, _theme_warnings) = theme.el_css_classes(&info_graph_dot);
+
+    assert!(!theme.cascade());
+    let c_classes = el_css_classes
+        .get("c")
+        .expect("Expected `c` to have classes.");
+    assert!(!c_classes.contains("fill-red-"), "{c_classes:?}");
+    assert!(
+        c_classes.contains("[&>path]:stroke-amber-"),
+        "{c_classes:?}"
+    );
+}
+
+#[test]
+fn node_parents_maps_nested_nodes_to_parent() {
+    let info_graph = info_graph_nested();
+
+    let node_parents = info_graph.node_parents();
+
+    assert_eq!(2, node_parents.len());
+    assert_eq!(Some(&&node_id!("a")), node_parents.get(&node_id!("b")));
+    assert_eq!(Some(&&node_id!("b")), node_parents.get(&node_id!("c")));
+}
+
+#[test]
+fn el_tag_ids_maps_nodes_and_edges_to_their_tags() {
+    let mut tag_items = TagItems::new();
+    tag_items.insert(
+        tag_id!("tag_x"),
+        vec![
+            AnyId::from(node_id!("b")),
+            AnyId::from(edge_id!("a__c")),
+            AnyId::from(node_id!("b")),
+        ],
+    );
+    tag_items.insert(tag_id!("tag_y"), vec![AnyId::from(node_id!("b"))]);
+    let info_graph = info_graph_nested().with_tag_items(tag_items);
+
+    let el_tag_ids = info_graph.el_tag_ids();
+
+    assert_eq!(2, el_tag_ids.len());
+    assert_eq!(
+        vec![&tag_id!("tag_x"), &tag_id!("tag_y")],
+        el_tag_ids["b"].iter().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![&tag_id!("tag_x")],
+        el_tag_ids["a__c"].iter().copied().collect::<Vec<_>>()
+    );
+    assert!(!el_tag_ids.contains_key("a"));
+}
+
+#[test]
+fn typography_and_effect_attrs_produce_classes() {
+    let info_graph = info_graph_nested();
+    let mut theme = Theme::base();
+    theme.insert(
+        AnyIdOrDefaults::AnyId(AnyId::from(node_id!("c"))),
+        CssClassPartials::from_iter([
+            (ThemeAttr::TextColor, "slate".to_string()),
+            (ThemeAttr::TextShade, "700".to_string()),
+            (ThemeAttr::TextColorHover, "blue".to_string()),
+            (ThemeAttr::FontWeight, "bold".to_string()),
+            (ThemeAttr::FontSize, "sm".to_string()),
+            (ThemeAttr::Opacity, "80".to_string()),
+            (ThemeAttr::OpacityHover, "100".to_string()),
+            (ThemeAttr::Shadow, "md".to_string()),
+            (ThemeAttr::CornerRadius, "lg".to_string()),
+        ]),
+    );
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: node_id_to_hierarchy.keys().copied().collect(),
+        edge_ids: vec![],
+    };
+
+    let (el_css_classes, _theme_warnings) = theme.el_css_classes(&info_graph_dot);
+
+    let c_classes = el_css_classes
+        .get("c")
+        .expect("Expected `c` to have classes.");
+    [
+        "[&>text]:fill-slate-700",
+        "[&>text]:hover:fill-blue-700",
+        "[&>text]:font-bold",
+        "[&>text]:text-sm",
+        "opacity-80",
+        "hover:opacity-100",
+        "drop-shadow-md",
+        "rounded-lg",
+    ]
+    .into_iter()
+    .for_each(|class| {
+        assert!(
+            c_classes.split_whitespace().any(|c| c == class),
+            "Expected `{class}` in {c_classes:?}"
+        )
+    });
+    let b_classes = el_css_classes
+        .get("b")
+        .expect("Expected `b` to have classes.");
+    assert!(!b_classes.contains("[&>text]:"), "{b_classes:?}");
+    assert!(!b_classes.contains("opacity-"), "{b_classes:?}");
+}
+
+#[test]
+fn explain_records_source_and_classes_of_each_attr() {
+    let info_graph = info_graph_nested();
+    let theme = cascade_theme().with_cascade(true);
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: node_id_to_hierarchy.keys().copied().collect(),
+        edge_ids: vec![],
+    };
+
+    let theme_explanation = theme
+        .explain(&info_graph_dot, &AnyId::from(node_id!("c")))
+        .expect("Expected `c` to be explained.");
+
+    let (el_css_classes, _theme_warnings) = theme.el_css_classes(&info_graph_dot);
+    assert_eq!(
+        el_css_classes.get("c"),
+        Some(&theme_explanation.css_classes)
+    );
+
+    let fill_color = theme_explanation
+        .theme_attr_explanation(ThemeAttr::FillColor)
+        .expect("Expected `fill_color` to be explained.");
+    assert_eq!("red", fill_color.value);
+    assert_eq!(ThemeSource::Diagram, fill_color.theme_source);
+    assert_eq!(
+        AnyIdOrDefaults::AnyId(AnyId::from(node_id!("a"))),
+        fill_color.theme_key
+    );
+    assert!(fill_color
+        .css_classes
+        .contains(&String::from("[&>path]:fill-red-300")));
+
+    let stroke_color = theme_explanation
+        .theme_attr_explanation(ThemeAttr::StrokeColor)
+        .expect("Expected `stroke_color` to be explained.");
+    assert_eq!(
+        AnyIdOrDefaults::AnyId(AnyId::from(node_id!("c"))),
+        stroke_color.theme_key
+    );
+
+    let fill_shade_normal = theme_explanation
+        .theme_attr_explanation(ThemeAttr::FillShadeNormal)
+        .expect("Expected `fill_shade_normal` to be explained.");
+    assert_eq!(ThemeSource::Base, fill_shade_normal.theme_source);
+    assert_eq!(AnyIdOrDefaults::NodeDefaults, fill_shade_normal.theme_key);
+    assert_eq!(
+        vec![String::from("[&>path]:fill-red-300")],
+        fill_shade_normal.css_classes
+    );
+
+    // `shape_color` is overridden by `fill_color` and `stroke_color`.
+    assert!(theme_explanation
+        .theme_attr_explanation(ThemeAttr::ShapeColor)
+        .is_none());
+}
+
+#[test]
+fn tag_explain_records_tag_sources() {
+    let info_graph = info_graph_nested();
+    let diagram_theme = Theme::new();
+    let mut tag_theme = Theme::new();
+    tag_theme.insert(
+        AnyIdOrDefaults::NodeDefaults,
+        CssClassPartials::from_iter([(ThemeAttr::FillShade, "100".to_string())]),
+    );
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: node_id_to_hierarchy.keys().copied().collect(),
+        edge_ids: vec![],
+    };
+    let tag_id = tag_id!("tag_0");
+
+    let theme_explanation = tag_theme
+        .tag_explain(
+            &info_graph_dot,
+            &diagram_theme,
+            &tag_id,
+            &AnyId::from(node_id!("b")),
+        )
+        .expect("Expected `b` to be explained.");
+
+    assert_eq!(Some(&tag_id), theme_explanation.tag_id.as_ref());
+    let fill_shade = theme_explanation
+        .theme_attr_explanation(ThemeAttr::FillShade)
+        .expect("Expected `fill_shade` to be explained.");
+    assert_eq!(
+        ThemeSource::TagFocus(tag_id.clone()),
+        fill_shade.theme_source
+    );
+    assert_eq!("100", fill_shade.value);
+    let shape_color = theme_explanation
+        .theme_attr_explanation(ThemeAttr::ShapeColor)
+        .expect("Expected `shape_color` to be explained.");
+    assert_eq!(ThemeSource::TagBase, shape_color.theme_source);
+    assert!(
+        shape_color
+            .css_classes
+            .iter()
+            .all(|css_class| css_class.contains("peer-focus/tag_0:")),
+        "{:?}",
+        shape_color.css_classes
+    );
+}
+
+#[test]
+fn explain_returns_none_for_unknown_id() {
+    let info_graph = info_graph_nested();
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: node_id_to_hierarchy.keys().copied().collect(),
+        edge_ids: vec![],
+    };
+
+    assert!(Theme::new()
+        .explain(&info_graph_dot, &AnyId::from(node_id!("z")))
+        .is_none());
+}
+
+/// Returns a theme where cluster `a` is red and blue, and node `c` is amber.
+fn cascade_theme() -> Theme {
+    let mut theme = Theme::new();
+    theme.insert(
+        AnyIdOrDefaults::AnyId(AnyId::from(node_id!("a"))),
+        CssClassPartials::from_iter([
+            (ThemeAttr::FillColor, "red".to_string()),
+            (ThemeAttr::StrokeColor, "blue".to_string()),
+        ]),
+    );
+    theme.insert(
+        AnyIdOrDefaults::AnyId(AnyId::from(node_id!("c"))),
+        CssClassPartials::from_iter([(ThemeAttr::StrokeColor, "amber".to_string())]),
+    );
+    theme
+}
+
+/// Returns an `InfoGraph` with the hierarchy `a: { b: { c: {} } }`.
+fn info_graph_nested() -> InfoGraph {
+    let mut hierarchy_b = NodeHierarchy::new();
+    hierarchy_b.insert(node_id!("c"), NodeHierarchy::new());
+    let mut hierarchy_a = NodeHierarchy::new();
+    hierarchy_a.insert(node_id!("b"), hierarchy_b);
+    let mut hierarchy = NodeHierarchy::new();
+    hierarchy.insert(node_id!("a"), hierarchy_a);
+
+    InfoGraph::default().with_hierarchy(hierarchy)
+}
+
+#[test]
+fn selectors_apply_styles_in_precedence_order() {
+    let mut edges = Edges::new();
+    edges.insert(
+        edge_id!("api_to_db"),
+        [node_id!("api"), node_id!("db_main")],
+    );
+    let mut tag_items = TagItems::new();
+    tag_items.insert(tag_id!("critical"), vec![AnyId::from(node_id!("db_main"))]);
+    let mut hierarchy = NodeHierarchy::new();
+    hierarchy.insert(node_id!("api"), NodeHierarchy::new());
+    hierarchy.insert(node_id!("db_main"), NodeHierarchy::new());
+    let info_graph = InfoGraph::default()
+        .with_hierarchy(hierarchy)
+        .with_edges(edges)
+        .with_tag_items(tag_items);
+
+    let mut theme = Theme::new();
+    [
+        ("node:db_*", "amber"),
+        ("tag:critical", "red"),
+        ("leaf", "blue"),
+        ("edge:from=api*", "lime"),
+    ]
+    .into_iter()
+    .for_each(|(selector, color)| {
+        theme.insert(
+            AnyIdOrDefaults::Selector(selector.parse::<ThemeSelector>().unwrap()),
+            CssClassPartials::from_iter([(ThemeAttr::StrokeColor, color.to_string())]),
+        );
+    });
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: info_graph.edges(),
+        graphviz_attrs: info_graph.graphviz_attrs(),
+        el_tag_ids: &info_graph.el_tag_ids(),
+        node_ids: node_id_to_hierarchy.keys().copied().collect(),
+        edge_ids: info_graph.edges().keys().collect(),
+    };
+
+    let (el_css_classes, _theme_warnings) = theme.el_css_classes(&info_graph_dot);
+
+    let api_classes = el_css_classes.get("api").unwrap();
+    assert!(
+        api_classes.contains("[&>path]:stroke-blue-"),
+        "{api_classes:?}"
+    );
+    let db_main_classes = el_css_classes.get("db_main").unwrap();
+    assert!(
+        db_main_classes.contains("[&>path]:stroke-amber-"),
+        "{db_main_classes:?}"
+    );
+    let edge_classes = el_css_classes.get("api_to_db").unwrap();
+    assert!(
+        edge_classes.contains("[&>path]:stroke-lime-"),
+        "{edge_classes:?}"
+    );
+}
+
+#[test]
+fn id_styles_override_selectors_and_defaults() {
+    let mut hierarchy = NodeHierarchy::new();
+    hierarchy.insert(node_id!("api"), NodeHierarchy::new());
+    hierarchy.insert(node_id!("db_main"), NodeHierarchy::new());
+    let info_graph = InfoGraph::default().with_hierarchy(hierarchy);
+
+    let mut theme = Theme::new();
+    theme.insert(
+        AnyIdOrDefaults::NodeDefaults,
+        CssClassPartials::from_iter([(ThemeAttr::StrokeColor, "slate".to_string())]),
+    );
+    theme.insert(
+        AnyIdOrDefaults::AnyId(AnyId::from(node_id!("db_main"))),
+        CssClassPartials::from_iter([(ThemeAttr::StrokeColor, "red".to_string())]),
+    );
+    theme.insert(
+        AnyIdOrDefaults::Selector("leaf".parse::<ThemeSelector>().unwrap()),
+        CssClassPartials::from_iter([(ThemeAttr::StrokeColor, "blue".to_string())]),
+    );
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent: &HashMap::new(),
+        edges: info_graph.edges(),
+        graphviz_attrs: info_graph.graphviz_attrs(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: node_id_to_hierarchy.keys().copied().collect(),
+        edge_ids: Vec::new(),
+    };
+
+    let (el_css_classes, _theme_warnings) = theme.el_css_classes(&info_graph_dot);
+
+    let api_classes = el_css_classes.get("api").unwrap();
+    assert!(
+        api_classes.contains("[&>path]:stroke-blue-"),
+        "{api_classes:?}"
+    );
+    let db_main_classes = el_css_classes.get("db_main").unwrap();
+    assert!(
+        db_main_classes.contains("[&>path]:stroke-red-"),
+        "{db_main_classes:?}"
+    );
+}
+
+#[test]
+fn selector_warnings_name_selector() {
+    let info_graph = info_graph_nested();
+    let mut theme = Theme::new();
+    theme.insert(
+        AnyIdOrDefaults::Selector(ThemeSelector::Cluster),
+        CssClassPartials::from_iter([(ThemeAttr::OutlineStyle, "dotted".to_string())]),
+    );
+    theme.insert(
+        AnyIdOrDefaults::NodeDefaults,
+        CssClassPartials::from_iter([(ThemeAttr::OutlineColor, "red".to_string())]),
+    );
+    let theme = Theme {
+        merge_with_base: false,
+        ..theme
+    };
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: node_id_to_hierarchy.keys().copied().collect(),
+        edge_ids: vec![],
+    };
+
+    let (_el_css_classes, theme_warnings) = theme.el_css_classes(&info_graph_dot);
+
+    assert!(
+        theme_warnings.iter().any(|theme_warning| theme_warning
+            .to_string()
+            .ends_with("Selectors applied to `a`: `cluster`.")),
+        "{theme_warnings:?}"
+    );
+    assert!(
+        theme_warnings
+            .iter()
+            .filter(|theme_warning| theme_warning.any_id().as_str() == "c")
+            .all(|theme_warning| theme_warning
+                .theme_attrs_partial()
+                .is_some_and(|theme_attrs_partial| theme_attrs_partial.theme_selectors.is_empty())),
+        "{theme_warnings:?}"
+    );
+}
+
+#[test]
+fn theme_warnings_record_element_and_missing_attrs() {
+    let info_graph = info_graph_nested();
+    let mut theme = Theme::new();
+    theme.insert(
+        AnyIdOrDefaults::AnyId(AnyId::from(node_id!("c"))),
+        CssClassPartials::from_iter([(ThemeAttr::FillColor, "red".to_string())]),
+    );
+    let theme = Theme {
+        merge_with_base: false,
+        ..theme
+    };
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let node_id_c = node_id!("c");
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: vec![&node_id_c],
+        edge_ids: vec![],
+    };
+
+    let (_el_css_classes, theme_warnings) = theme.el_css_classes(&info_graph_dot);
+
+    let theme_warning = theme_warnings
+        .iter()
+        .find(|theme_warning| {
+            theme_warning
+                .theme_attrs_partial()
+                .is_some_and(|theme_attrs_partial| {
+                    theme_attrs_partial.highlight_state == HighlightState::Normal
+                })
+        })
+        .expect("Expected a warning for the normal highlight state.");
+    assert!(
+        matches!(theme_warning, ThemeWarning::FillAttrsPartial(_)),
+        "{theme_warning:?}"
+    );
+    assert_eq!("c", theme_warning.any_id().as_str());
+    assert_eq!(StyleFor::Regular, theme_warning.style_for());
+    assert_eq!(ThemeWarningSeverity::Warning, theme_warning.severity());
+    assert_eq!(
+        vec![ThemeAttr::FillShade],
+        theme_warning.attrs_missing().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "Fill attributes partially specified, so fill classes will not be applied. \
+        fill_color: `red`, fill_shade: `<none>`.",
+        theme_warning.to_string()
+    );
+    assert_eq!(
+        1,
+        theme_warnings
+            .with_severity_min(ThemeWarningSeverity::Warning)
+            .filter(
+                |theme_warning| theme_warning.theme_attrs_partial().is_some_and(
+                    |theme_attrs_partial| {
+                        theme_attrs_partial.highlight_state == HighlightState::Normal
+                    }
+                )
+            )
+            .count()
+    );
+}
+
+#[test]
+fn theme_warnings_record_attrs_conflicting_between_selectors() {
+    let info_graph = info_graph_nested();
+    let mut theme = Theme::new();
+    [
+        ("node:c", "red"),
+        ("node:*", "blue"),
+        ("tag:tag_a", "amber"),
+    ]
+    .into_iter()
+    .for_each(|(selector, color)| {
+        theme.insert(
+            AnyIdOrDefaults::Selector(selector.parse::<ThemeSelector>().unwrap()),
+            CssClassPartials::from_iter([(ThemeAttr::FillColor, color.to_string())]),
+        );
+    });
+    let node_id_to_hierarchy = &info_graph.hierarchy_flat();
+    let node_id_to_parent = &info_graph.node_parents();
+    let node_id_c = node_id!("c");
+    let info_graph_dot = InfoGraphDot {
+        graph_style: GraphStyle::Box,
+        node_id_to_hierarchy,
+        node_id_to_parent,
+        edges: &Edges::new(),
+        graphviz_attrs: &GraphvizAttrs::default(),
+        el_tag_ids: &HashMap::new(),
+        node_ids: vec![&node_id_c],
+        edge_ids: vec![],
+    };
+
+    let (el_css_classes, theme_warnings) = theme.el_css_classes(&info_graph_dot);
+
+    let theme_warnings_conflict = theme_warnings
+        .iter()
+        .filter(|theme_warning| matches!(theme_warning, ThemeWarning::AttrConflict(_)))
+        .collect::<Vec<_>>();
+    let [theme_warning] = theme_warnings_conflict.as_slice() else {
+        panic!("Expected one conflict warning, got: {theme_warnings:?}");
+    };
+    assert_eq!("c", theme_warning.any_id().as_str());
+    assert_eq!(ThemeWarningSeverity::Info, theme_warning.severity());
+    assert_eq!(
+        vec![ThemeAttr::FillColor],
+        theme_warning.attrs_conflicting().collect::<Vec<_>>()
+    );
+    assert_eq!(None, theme_warning.attrs_missing().next());
+    assert_eq!(
+        "`fill_color` specified with different values by selectors with the same precedence \
+        for `c`, so only the last value is applied. `node:c`: `red`, `node:*`: `blue`.",
+        theme_warning.to_string()
+    );
+    assert!(
+        el_css_classes
+            .get("c")
+            .is_some_and(|css_classes| css_classes.contains("fill-blue-")),
+        "{el_css_classes:?}"
+    );
+    assert!(theme_warnings
+        .with_severity_min(ThemeWarningSeverity::Warning)
+        .all(|theme_warning| !matches!(theme_warning, ThemeWarning::AttrConflict(_))));
+}
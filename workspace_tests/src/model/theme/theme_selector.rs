@@ -0,0 +1,62 @@
+use dot_ix::model::{
+    tag_id,
+    theme::{AnyIdOrDefaults, ThemeSelector},
+};
+
+#[test]
+fn from_str_parses_selectors() {
+    [
+        ("cluster", ThemeSelector::Cluster),
+        ("leaf", ThemeSelector::Leaf),
+        ("depth:2", ThemeSelector::Depth(2)),
+        ("tag:critical", ThemeSelector::Tag(tag_id!("critical"))),
+        ("node:db_*", ThemeSelector::Node("db_*".to_string())),
+        ("edge:api_*", ThemeSelector::Edge("api_*".to_string())),
+        (
+            "edge:from=api_*",
+            ThemeSelector::EdgeFrom("api_*".to_string()),
+        ),
+        ("edge:to=db_?", ThemeSelector::EdgeTo("db_?".to_string())),
+    ]
+    .into_iter()
+    .for_each(|(s, theme_selector_expected)| {
+        let theme_selector = s.parse::<ThemeSelector>().unwrap();
+
+        assert_eq!(theme_selector_expected, theme_selector);
+        assert_eq!(s, theme_selector.to_string());
+    });
+}
+
+#[test]
+fn from_str_returns_err_for_invalid_selectors() {
+    ["depth:two", "tag:not-an-id", "node:", "unknown:a", "a"]
+        .into_iter()
+        .for_each(|s| {
+            assert!(
+                s.parse::<ThemeSelector>().is_err(),
+                "Expected `{s}` to fail."
+            );
+        });
+}
+
+#[test]
+fn precedence_orders_specific_selectors_last() {
+    assert!(ThemeSelector::Leaf.precedence() < ThemeSelector::Depth(0).precedence());
+    assert!(
+        ThemeSelector::Depth(0).precedence() < ThemeSelector::Tag(tag_id!("critical")).precedence()
+    );
+    assert!(
+        ThemeSelector::Tag(tag_id!("critical")).precedence()
+            < ThemeSelector::Node("db_*".to_string()).precedence()
+    );
+}
+
+#[test]
+fn any_id_or_defaults_deserializes_selector_keys() {
+    let any_id_or_defaults = serde_yaml::from_str::<AnyIdOrDefaults>("node:db_*").unwrap();
+
+    assert_eq!(
+        Some(&ThemeSelector::Node("db_*".to_string())),
+        any_id_or_defaults.selector()
+    );
+}
@@ -0,0 +1,13 @@
+mod diagnostics;
+mod dot_parser;
+mod dot_renderer;
+mod graphviz_wasm_renderer;
+mod interchange;
+mod into_drawio_src;
+mod into_graphviz_dot_src;
+mod into_html_src;
+mod into_mermaid_src;
+mod into_svg_src;
+mod into_text_src;
+mod mermaid_parser;
+mod tailwind_css_gen;
@@ -0,0 +1,166 @@
+use dot_ix::{
+    model::{
+        common::{DotSrcAndStyles, GraphvizDotTheme},
+        edge_id,
+        info_graph::InfoGraph,
+        node_id,
+    },
+    rt::{Diagnostic, DotRenderer, FakeDotRenderer, IntoGraphvizDotSrc},
+};
+
+#[test]
+fn info_graph_check_detects_risky_edges() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  ab:
+    a0: {}
+  cluster:
+    c0: {}
+  d: {}
+node_names:
+  cluster: "Cluster"
+edges:
+  a0__ab: [a0, ab]
+  d__cluster: [d, "cluster:n"]
+  d__c0: [d, "c0:up"]
+  d__e: [d, e]
+"#,
+    )
+    .unwrap();
+
+    let diagnostics = Diagnostic::info_graph_check(&info_graph);
+
+    assert_eq!(
+        vec![
+            Diagnostic::ClusterLabelShort {
+                edge_id: edge_id!("a0__ab"),
+                cluster_node_id: node_id!("ab"),
+                label: String::from("ab"),
+            },
+            Diagnostic::EdgeWithinCluster {
+                edge_id: Some(edge_id!("a0__ab")),
+                cluster_node_id: String::from("ab"),
+                node_id: String::from("a0"),
+            },
+            Diagnostic::ClusterCompassPoint {
+                edge_id: edge_id!("d__cluster"),
+                cluster_node_id: node_id!("cluster"),
+                compass_point: String::from("n"),
+            },
+            Diagnostic::CompassPointInvalid {
+                edge_id: Some(edge_id!("d__c0")),
+                node_id: String::from("c0"),
+                compass_point: String::from("up"),
+            },
+            Diagnostic::EdgeNodeUnknown {
+                edge_id: edge_id!("d__e"),
+                node_id: String::from("e"),
+            },
+        ],
+        diagnostics
+    );
+}
+
+#[test]
+fn info_graph_check_detects_edge_descs_with_ortho_splines() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a: {}
+  b: {}
+edges:
+  a__b: [a, b]
+edge_descs:
+  a__b: "connects"
+graphviz_attrs:
+  splines: ortho
+"#,
+    )
+    .unwrap();
+
+    let diagnostics = Diagnostic::info_graph_check(&info_graph);
+
+    assert_eq!(
+        vec![Diagnostic::OrthoEdgeDescs {
+            edge_ids: vec![edge_id!("a__b")],
+        }],
+        diagnostics
+    );
+    assert!(diagnostics[0].suggestion().contains("polyline"));
+}
+
+#[test]
+fn graphviz_error_explain_maps_known_errors() {
+    let diagnostics = Diagnostic::graphviz_error_explain(
+        "Warning: node a, port x unrecognized\n\
+        Warning: a0 -> a0: tail is inside head cluster cluster_a\n\
+        dot: compound.c:452: makeCompoundEdge: Assertion `bez->sflag' failed.\n\
+        dot: compound.c:452: makeCompoundEdge: Assertion `bez->sflag' failed.\n\
+        Warning: unexplained\n",
+        None,
+    );
+
+    assert_eq!(
+        vec![
+            Diagnostic::CompassPointInvalid {
+                edge_id: None,
+                node_id: String::from("a"),
+                compass_point: String::from("x"),
+            },
+            Diagnostic::EdgeWithinCluster {
+                edge_id: None,
+                cluster_node_id: String::from("a"),
+                node_id: String::from("a0"),
+            },
+            Diagnostic::CompoundEdgeAssertion,
+        ],
+        diagnostics
+    );
+    assert!(Diagnostic::CompoundEdgeAssertion
+        .suggestion()
+        .contains("8.1.0"));
+}
+
+#[test]
+fn graphviz_error_explain_traces_syntax_error_to_element() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>("hierarchy: { a: {}, b: {} }").unwrap();
+    let DotSrcAndStyles { src_map, .. } =
+        IntoGraphvizDotSrc::into_with_src_map(&info_graph, &GraphvizDotTheme::default());
+    let src_map = src_map.expect("Expected `src_map` to be recorded.");
+    let entry_b = src_map.entries_for_id("b").next().unwrap().clone();
+    let error = format!(
+        "Error: <stdin>: syntax error in line {} near '<'",
+        entry_b.line_start() + 1
+    );
+
+    let diagnostics = Diagnostic::graphviz_error_explain(&error, Some(&src_map));
+
+    assert_eq!(
+        vec![Diagnostic::SyntaxError {
+            message: format!("syntax error in line {} near '<'", entry_b.line_start() + 1),
+            dot_src_map_entry: Some(entry_b),
+        }],
+        diagnostics
+    );
+    assert_eq!("b", diagnostics[0].any_id().unwrap().as_str());
+    assert!(diagnostics[0].suggestion().contains("`hierarchy.b`"));
+}
+
+#[test]
+fn render_error_explain_explains_dot_failed() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>("hierarchy: { a: {} }").unwrap();
+    let dot_src_and_styles = IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+    let fake_dot_renderer =
+        FakeDotRenderer::new().with_stderr(String::from("Error: trouble in init_rank\n\ta 0\n"));
+
+    let render_error = fake_dot_renderer.render(&dot_src_and_styles).unwrap_err();
+    let diagnostics = Diagnostic::render_error_explain(&render_error, None);
+
+    assert_eq!(
+        vec![Diagnostic::InitRank {
+            message: String::from("trouble in init_rank\n\ta 0"),
+        }],
+        diagnostics
+    );
+}
@@ -0,0 +1,215 @@
+use dot_ix::{
+    model::{
+        common::{
+            graphviz_attrs::{EdgeDir, Margin, Splines},
+            EdgeId, NodeId,
+        },
+        info_graph::GraphDir,
+    },
+    rt::{DotParseError, DotParseWarning, DotParser},
+};
+
+#[test]
+fn parses_clusters_labels_and_edges() {
+    let dot_parse_output = DotParser::parse(
+        r#"
+digraph G {
+    // comment
+    subgraph cluster_app {
+        label = "App";
+        web [label = "Web\nServes pages"];
+        subgraph cluster_storage { db; }
+    }
+    web -> db [label = "queries"];
+    db -> web;
+    cli;
+}
+"#,
+    )
+    .unwrap();
+    let info_graph = dot_parse_output.info_graph();
+
+    assert!(dot_parse_output.dot_parse_warnings().is_empty());
+    assert_eq!(GraphDir::Vertical, info_graph.direction());
+
+    let app = NodeId::try_from(String::from("app")).unwrap();
+    let web = NodeId::try_from(String::from("web")).unwrap();
+    let storage = NodeId::try_from(String::from("storage")).unwrap();
+    let db = NodeId::try_from(String::from("db")).unwrap();
+    let cli = NodeId::try_from(String::from("cli")).unwrap();
+    let app_hierarchy = info_graph.hierarchy().get(&app).unwrap();
+    assert_eq!(
+        vec![&app, &cli],
+        info_graph.hierarchy().keys().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![&web, &storage],
+        app_hierarchy.keys().collect::<Vec<_>>()
+    );
+    assert!(app_hierarchy.get(&storage).unwrap().contains_key(&db));
+
+    assert_eq!(
+        Some("App"),
+        info_graph.node_names().get(&app).map(String::as_str)
+    );
+    assert_eq!(
+        Some("Web"),
+        info_graph.node_names().get(&web).map(String::as_str)
+    );
+    assert_eq!(
+        Some("Serves pages"),
+        info_graph.node_descs().get(&web).map(String::as_str)
+    );
+
+    let edge_web_db = EdgeId::try_from(String::from("web__db")).unwrap();
+    let edge_db_web = EdgeId::try_from(String::from("db__web")).unwrap();
+    assert_eq!(
+        vec![&edge_web_db, &edge_db_web],
+        info_graph.edges().keys().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some(&[web.clone(), db.clone()]),
+        info_graph.edges().get(&edge_web_db)
+    );
+    assert_eq!(
+        Some("queries"),
+        info_graph
+            .edge_descs()
+            .get(&edge_web_db)
+            .map(String::as_str)
+    );
+}
+
+#[test]
+fn maps_known_attributes_to_graphviz_attrs() {
+    let dot_parse_output = DotParser::parse(
+        r#"
+digraph {
+    rankdir = LR
+    graph [nodesep = 0.5, ranksep = "1.5", splines = ortho]
+    node [width = 1.2, height = 0.4, margin = "0.1,0.05"]
+    edge [minlen = 3]
+    a [width = 2]
+    a -> b [constraint = false, minlen = 1, dir = both]
+}
+"#,
+    )
+    .unwrap();
+    let info_graph = dot_parse_output.info_graph();
+    let graphviz_attrs = info_graph.graphviz_attrs();
+
+    assert!(dot_parse_output.dot_parse_warnings().is_empty());
+    assert_eq!(GraphDir::Horizontal, info_graph.direction());
+    assert_eq!(0.5, graphviz_attrs.nodesep());
+    assert_eq!(1.5, graphviz_attrs.ranksep());
+    assert_eq!(Splines::Ortho, graphviz_attrs.splines());
+    assert_eq!(1.2, graphviz_attrs.node_width_default());
+    assert_eq!(0.4, graphviz_attrs.node_height_default());
+    assert_eq!(
+        Margin::Different(0.1, 0.05),
+        graphviz_attrs.margin_node_default().0
+    );
+    assert_eq!(3, graphviz_attrs.edge_minlen_default());
+
+    let a = NodeId::try_from(String::from("a")).unwrap();
+    let edge_a_b = EdgeId::try_from(String::from("a__b")).unwrap();
+    assert_eq!(Some(&2.0), graphviz_attrs.node_widths().get(&a));
+    assert_eq!(
+        Some(&false),
+        graphviz_attrs.edge_constraints().get(&edge_a_b)
+    );
+    assert_eq!(Some(&1), graphviz_attrs.edge_minlens().get(&edge_a_b));
+    assert_eq!(
+        Some(&EdgeDir::Both),
+        graphviz_attrs.edge_dirs().get(&edge_a_b)
+    );
+}
+
+#[test]
+fn reports_unknown_attributes_and_renamed_ids() {
+    let dot_parse_output = DotParser::parse(
+        r#"digraph {
+    bgcolor = white
+    "my node" [shape = box]
+    "my node" -> 2:n
+}"#,
+    )
+    .unwrap();
+    let info_graph = dot_parse_output.info_graph();
+
+    assert_eq!(
+        &[
+            DotParseWarning::AttrUnknown {
+                el: String::from("graph"),
+                attr_name: String::from("bgcolor"),
+                line: 2,
+            },
+            DotParseWarning::IdRenamed {
+                dot_id: String::from("my node"),
+                id: String::from("my_node"),
+                line: 3,
+            },
+            DotParseWarning::AttrUnknown {
+                el: String::from("node `my node`"),
+                attr_name: String::from("shape"),
+                line: 3,
+            },
+            DotParseWarning::PortIgnored {
+                dot_id: String::from("2"),
+                port: String::from("n"),
+                line: 4,
+            },
+            DotParseWarning::IdRenamed {
+                dot_id: String::from("2"),
+                id: String::from("_2"),
+                line: 4,
+            },
+        ],
+        dot_parse_output.dot_parse_warnings()
+    );
+
+    let my_node = NodeId::try_from(String::from("my_node")).unwrap();
+    assert_eq!(
+        Some("my node"),
+        info_graph.node_names().get(&my_node).map(String::as_str)
+    );
+    assert!(info_graph
+        .edges()
+        .contains_key(&EdgeId::try_from(String::from("my_node___2")).unwrap()));
+}
+
+#[test]
+fn expands_subgraph_operands_and_undirected_edges() {
+    let dot_parse_output = DotParser::parse("strict graph { a -- { b c } a -- b }").unwrap();
+    let info_graph = dot_parse_output.info_graph();
+
+    assert_eq!(
+        vec!["a__b", "a__c"],
+        info_graph
+            .edges()
+            .keys()
+            .map(EdgeId::as_str)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        EdgeDir::None,
+        info_graph.graphviz_attrs().edge_dir_default()
+    );
+}
+
+#[test]
+fn returns_syntax_error_with_position() {
+    let error = DotParser::parse("digraph {\n    a -> ;\n}").unwrap_err();
+
+    assert!(
+        matches!(
+            error,
+            DotParseError::Syntax {
+                line: 2,
+                column: 10,
+                ..
+            }
+        ),
+        "{error:?}"
+    );
+}
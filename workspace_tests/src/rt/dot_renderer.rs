@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+
+use dot_ix::{
+    model::{
+        common::{DotSrcAndStyles, GraphvizDotTheme},
+        info_graph::InfoGraph,
+    },
+    rt::{
+        DotDiagnostic, DotDiagnosticSeverity, DotProcessRenderer, DotRenderer, FakeDotRenderer,
+        IntoGraphvizDotSrc, RenderError, Svg,
+    },
+};
+
+fn dot_src_and_styles() -> DotSrcAndStyles {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a: {}
+  b: {}
+edges:
+  a__b: [a, b]
+"#,
+    )
+    .unwrap();
+
+    IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default())
+}
+
+#[test]
+fn fake_dot_renderer_returns_svg_and_records_dot_src() {
+    let dot_src_and_styles = dot_src_and_styles();
+    let fake_dot_renderer = FakeDotRenderer::new().with_svg(Svg::new(String::from("<svg/>")));
+
+    let svg = fake_dot_renderer.render(&dot_src_and_styles).unwrap();
+
+    assert_eq!("<svg/>", svg.as_str());
+    assert_eq!(
+        vec![dot_src_and_styles.dot_src().to_string()],
+        fake_dot_renderer.dot_srcs()
+    );
+}
+
+#[test]
+fn fake_dot_renderer_fails_with_parsed_stderr() {
+    let fake_dot_renderer = FakeDotRenderer::new().with_stderr(String::from(
+        "Error: <stdin>: syntax error in line 3 near '->'\n",
+    ));
+
+    let error = fake_dot_renderer.render(&dot_src_and_styles()).unwrap_err();
+
+    let RenderError::DotFailed {
+        exit_code,
+        dot_diagnostics,
+    } = &error
+    else {
+        panic!("Expected `RenderError::DotFailed`, but got: {error:?}");
+    };
+    assert_eq!(&Some(1), exit_code);
+    assert_eq!(
+        &vec![DotDiagnostic {
+            severity: DotDiagnosticSeverity::Error,
+            line: Some(3),
+            message: String::from("syntax error in line 3 near '->'"),
+        }],
+        dot_diagnostics
+    );
+    assert!(error.to_string().contains("syntax error in line 3"));
+}
+
+#[test]
+fn dot_diagnostics_parse_stderr_joins_continuation_lines() {
+    let dot_diagnostics = DotDiagnostic::parse_stderr(
+        "Warning: node a, port x unrecognized\n\
+        Error: <stdin>: syntax error in line 12 near 'b'\n\
+        context: a -> >>> b <<< \n",
+    );
+
+    assert_eq!(
+        vec![
+            DotDiagnostic {
+                severity: DotDiagnosticSeverity::Warning,
+                line: None,
+                message: String::from("node a, port x unrecognized"),
+            },
+            DotDiagnostic {
+                severity: DotDiagnosticSeverity::Error,
+                line: Some(12),
+                message: String::from("syntax error in line 12 near 'b'\ncontext: a -> >>> b <<<"),
+            },
+        ],
+        dot_diagnostics
+    );
+}
+
+#[test]
+fn dot_process_renderer_returns_spawn_error_when_dot_missing() {
+    let dot_process_renderer =
+        DotProcessRenderer::new().with_dot_path(PathBuf::from("/nonexistent/dot_ix/dot"));
+
+    let error = dot_process_renderer
+        .render(&dot_src_and_styles())
+        .unwrap_err();
+
+    assert!(
+        matches!(error, RenderError::DotSpawn { .. }),
+        "Expected `RenderError::DotSpawn`, but got: {error:?}"
+    );
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::{os::unix::fs::PermissionsExt, path::PathBuf, time::Duration};
+
+    use dot_ix::rt::{DotDiagnosticSeverity, DotProcessRenderer, DotRenderer, RenderError};
+    use tempfile::TempDir;
+
+    use super::dot_src_and_styles;
+
+    /// Writes an executable shell script that stands in for `dot`.
+    fn dot_script(temp_dir: &TempDir, script: &str) -> PathBuf {
+        let dot_path = temp_dir.path().join("dot");
+        std::fs::write(&dot_path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        std::fs::set_permissions(&dot_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        dot_path
+    }
+
+    #[test]
+    fn dot_process_renderer_returns_stdout_as_svg() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dot_path = dot_script(
+            &temp_dir,
+            r#"cat > /dev/null; echo "Warning: ignored" >&2; echo "<svg>$1</svg>""#,
+        );
+        let dot_process_renderer = DotProcessRenderer::new().with_dot_path(dot_path);
+
+        let svg = dot_process_renderer.render(&dot_src_and_styles()).unwrap();
+
+        assert_eq!("<svg>-Tsvg</svg>\n", svg.as_str());
+    }
+
+    #[test]
+    fn dot_process_renderer_returns_stderr_on_failure() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dot_path = dot_script(
+            &temp_dir,
+            r#"cat > /dev/null; echo "Error: <stdin>: syntax error in line 2 near 'x'" >&2; exit 1"#,
+        );
+        let dot_process_renderer = DotProcessRenderer::new().with_dot_path(dot_path);
+
+        let error = dot_process_renderer
+            .render(&dot_src_and_styles())
+            .unwrap_err();
+
+        let RenderError::DotFailed {
+            exit_code,
+            dot_diagnostics,
+        } = &error
+        else {
+            panic!("Expected `RenderError::DotFailed`, but got: {error:?}");
+        };
+        assert_eq!(&Some(1), exit_code);
+        assert_eq!(1, dot_diagnostics.len());
+        assert_eq!(DotDiagnosticSeverity::Error, dot_diagnostics[0].severity);
+        assert_eq!(Some(2), dot_diagnostics[0].line);
+    }
+
+    #[test]
+    fn dot_process_renderer_kills_dot_after_timeout() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dot_path = dot_script(&temp_dir, "exec sleep 5");
+        let dot_process_renderer = DotProcessRenderer::new()
+            .with_dot_path(dot_path)
+            .with_timeout(Duration::from_millis(100));
+
+        let error = dot_process_renderer
+            .render(&dot_src_and_styles())
+            .unwrap_err();
+
+        assert!(
+            matches!(
+                error,
+                RenderError::DotTimeout { timeout, .. } if timeout == Duration::from_millis(100)
+            ),
+            "Expected `RenderError::DotTimeout`, but got: {error:?}"
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use std::path::{Path, PathBuf};
+
+use dot_ix::{
+    model::{
+        common::{
+            dot_src_and_styles::{GraphvizImage, GraphvizOpts},
+            DotSrcAndStyles,
+        },
+        theme::ThemeWarnings,
+    },
+    rt::{DotDiagnosticSeverity, DotRenderer, GraphvizWasmRenderer, RenderError},
+};
+
+/// Module with the same exports and imports that `CGraphviz` uses, whose
+/// `layout` behaves based on the first character of the dot source:
+///
+/// * `/`: Returns the contents of the file at that path.
+/// * `x`: Fails with a syntax error.
+/// * `l`: Calls a function through `invoke_v` which `longjmp`s.
+/// * `t`: Writes to `stderr`, then traps.
+/// * Otherwise: Returns `<svg></svg>`.
+const GRAPHVIZ_WAT: &str = r#"
+(module
+  (import "env" "emscripten_longjmp" (func $longjmp (param i32 i32)))
+  (import "env" "invoke_v" (func $invoke_v (param i32)))
+  (import "env" "__syscall_openat" (func $openat (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_read" (func $fd_read (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (import "env" "unused_import" (func $unused_import))
+
+  (memory (export "memory") 1)
+  (table (export "__indirect_function_table") 1 funcref)
+  (elem (i32.const 0) $throws)
+
+  (global $heap (mut i32) (i32.const 8192))
+  (global $threw (mut i32) (i32.const 0))
+  (global $last_error (mut i32) (i32.const 0))
+
+  (data (i32.const 1024) "<svg></svg>\00")
+  (data (i32.const 1040) "Error: syntax error in line 1 near 'x'\0a\00")
+  (data (i32.const 1100) "caught\00")
+  (data (i32.const 1120) "oops\0a")
+
+  (func $throws (call $longjmp (i32.const 0) (i32.const 1)))
+
+  (func (export "malloc") (param $size i32) (result i32) (local $ptr i32)
+    (local.set $ptr (global.get $heap))
+    (global.set $heap (i32.add (global.get $heap) (local.get $size)))
+    (local.get $ptr))
+
+  (func (export "setThrew") (param i32 i32)
+    (global.set $threw (local.get 0)))
+
+  (func (export "emscripten_bind_CGraphviz_CGraphviz_2") (param i32 i32) (result i32)
+    (i32.const 16))
+
+  (func (export "emscripten_bind_CGraphviz___destroy___0") (param i32))
+
+  (func (export "emscripten_bind_CGraphviz_lastError_0") (param i32) (result i32)
+    (global.get $last_error))
+
+  (func (export "emscripten_bind_CGraphviz_layout_3")
+    (param $self i32) (param $dot i32) (param $format i32) (param $engine i32) (result i32)
+    (local $c i32) (local $fd i32)
+    (local.set $c (i32.load8_u (local.get $dot)))
+
+    ;; `/`
+    (if (i32.eq (local.get $c) (i32.const 47))
+      (then
+        (local.set $fd (call $openat (i32.const -100) (local.get $dot) (i32.const 0) (i32.const 0)))
+        (i32.store (i32.const 2048) (i32.const 4096))
+        (i32.store (i32.const 2052) (i32.const 1024))
+        (drop (call $fd_read (local.get $fd) (i32.const 2048) (i32.const 1) (i32.const 2056)))
+        (return (i32.const 4096))))
+
+    ;; `x`
+    (if (i32.eq (local.get $c) (i32.const 120))
+      (then
+        (global.set $last_error (i32.const 1040))
+        (return (i32.const 0))))
+
+    ;; `l`
+    (if (i32.eq (local.get $c) (i32.const 108))
+      (then
+        (call $invoke_v (i32.const 0))
+        (if (i32.eq (global.get $threw) (i32.const 1))
+          (then (return (i32.const 1100))))
+        (return (i32.const 0))))
+
+    ;; `t`
+    (if (i32.eq (local.get $c) (i32.const 116))
+      (then
+        (i32.store (i32.const 2064) (i32.const 1120))
+        (i32.store (i32.const 2068) (i32.const 5))
+        (drop (call $fd_write (i32.const 2) (i32.const 2064) (i32.const 1) (i32.const 2072)))
+        (unreachable)))
+
+    (i32.const 1024))
+)
+"#;
+
+/// Module whose imports and exports are minified, whose `layout` calls a
+/// function through `invoke_v` which `longjmp`s.
+const GRAPHVIZ_WAT_MINIFIED: &str = r#"
+(module
+  (import "a" "a" (func $longjmp (param i32 i32)))
+  (import "a" "b" (func $invoke_v (param i32)))
+
+  (memory (export "c") 1)
+  (table (export "d") 1 funcref)
+  (elem (i32.const 0) $throws)
+
+  (global $heap (mut i32) (i32.const 8192))
+  (global $threw (mut i32) (i32.const 0))
+
+  (data (i32.const 1100) "caught\00")
+
+  (func $throws (call $longjmp (i32.const 0) (i32.const 1)))
+
+  (func (export "e") (param $size i32) (result i32) (local $ptr i32)
+    (local.set $ptr (global.get $heap))
+    (global.set $heap (i32.add (global.get $heap) (local.get $size)))
+    (local.get $ptr))
+
+  (func (export "f") (param i32 i32)
+    (global.set $threw (local.get 0)))
+
+  (func (export "g") (param i32 i32) (result i32)
+    (i32.const 16))
+
+  (func (export "h") (param i32))
+
+  (func (export "i") (param i32 i32 i32 i32) (result i32)
+    (call $invoke_v (i32.const 0))
+    (if (i32.eq (global.get $threw) (i32.const 1))
+      (then (return (i32.const 1100))))
+    (i32.const 0))
+)
+"#;
+
+fn graphviz_wasm_renderer() -> GraphvizWasmRenderer {
+    let wasm_bytes = wat::parse_str(GRAPHVIZ_WAT).unwrap();
+    GraphvizWasmRenderer::from_bytes(&wasm_bytes).unwrap()
+}
+
+fn dot_src_and_styles(dot_src: &str, opts: GraphvizOpts) -> DotSrcAndStyles {
+    DotSrcAndStyles::new(
+        dot_src.to_string(),
+        String::new(),
+        opts,
+        ThemeWarnings::new(),
+    )
+}
+
+#[test]
+fn render_returns_svg_from_layout() {
+    let dot_src_and_styles = dot_src_and_styles("digraph {}", GraphvizOpts::default());
+
+    let svg = graphviz_wasm_renderer()
+        .render(&dot_src_and_styles)
+        .unwrap();
+
+    assert_eq!("<svg></svg>", svg.as_str());
+}
+
+#[test]
+fn render_writes_images_as_svg_files_with_size() {
+    let dot_src_and_styles = dot_src_and_styles(
+        "/image.png",
+        GraphvizOpts::new(vec![GraphvizImage::new(
+            String::from("/image.png"),
+            String::from("50px"),
+            String::from("40px"),
+        )]),
+    );
+
+    let svg = graphviz_wasm_renderer()
+        .render(&dot_src_and_styles)
+        .unwrap();
+
+    assert_eq!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n\
+        <svg width=\"50px\" height=\"40px\"></svg>",
+        svg.as_str()
+    );
+}
+
+#[test]
+fn render_returns_dot_failed_with_last_error() {
+    let dot_src_and_styles = dot_src_and_styles("x", GraphvizOpts::default());
+
+    let error = graphviz_wasm_renderer()
+        .render(&dot_src_and_styles)
+        .unwrap_err();
+
+    match error {
+        RenderError::DotFailed {
+            exit_code,
+            dot_diagnostics,
+        } => {
+            assert_eq!(None, exit_code);
+            assert_eq!(1, dot_diagnostics.len());
+            assert_eq!(DotDiagnosticSeverity::Error, dot_diagnostics[0].severity);
+            assert_eq!(Some(1), dot_diagnostics[0].line);
+        }
+        error => panic!("Expected `RenderError::DotFailed`, but got: {error:?}"),
+    }
+}
+
+#[test]
+fn render_catches_longjmp_in_invoke() {
+    let dot_src_and_styles = dot_src_and_styles("l", GraphvizOpts::default());
+
+    let svg = graphviz_wasm_renderer()
+        .render(&dot_src_and_styles)
+        .unwrap();
+
+    assert_eq!("caught", svg.as_str());
+}
+
+#[test]
+fn render_returns_trap_with_stderr() {
+    let graphviz_wasm_renderer = graphviz_wasm_renderer();
+    let error = graphviz_wasm_renderer
+        .render(&dot_src_and_styles("t", GraphvizOpts::default()))
+        .unwrap_err();
+
+    match error {
+        RenderError::GraphvizWasmTrap { stderr, .. } => assert_eq!("oops\n", stderr),
+        error => panic!("Expected `RenderError::GraphvizWasmTrap`, but got: {error:?}"),
+    }
+
+    // Each render uses a new instance, so later renders are unaffected.
+    let svg = graphviz_wasm_renderer
+        .render(&dot_src_and_styles("digraph {}", GraphvizOpts::default()))
+        .unwrap();
+    assert_eq!("<svg></svg>", svg.as_str());
+}
+
+#[test]
+fn from_bytes_returns_error_when_export_missing() {
+    let wasm_bytes = wat::parse_str(r#"(module (memory (export "memory") 1))"#).unwrap();
+
+    let error = GraphvizWasmRenderer::from_bytes(&wasm_bytes).unwrap_err();
+
+    match error {
+        RenderError::GraphvizWasmExportMissing { export_name } => {
+            assert_eq!("emscripten_bind_CGraphviz_CGraphviz_2", export_name)
+        }
+        error => panic!("Expected `RenderError::GraphvizWasmExportMissing`, but got: {error:?}"),
+    }
+}
+
+#[test]
+fn from_bytes_with_minification_map_resolves_minified_names() {
+    let wasm_bytes = wat::parse_str(GRAPHVIZ_WAT_MINIFIED).unwrap();
+    let minification_map_src = "\
+        a:emscripten_longjmp\n\
+        b:invoke_v\n\
+        c:memory\n\
+        d:__indirect_function_table\n\
+        e:malloc\n\
+        f:setThrew\n\
+        g:emscripten_bind_CGraphviz_CGraphviz_2\n\
+        h:emscripten_bind_CGraphviz___destroy___0\n\
+        i:emscripten_bind_CGraphviz_layout_3\n\
+    ";
+
+    let graphviz_wasm_renderer =
+        GraphvizWasmRenderer::from_bytes_with_minification_map(&wasm_bytes, minification_map_src)
+            .unwrap();
+    let svg = graphviz_wasm_renderer
+        .render(&dot_src_and_styles("digraph {}", GraphvizOpts::default()))
+        .unwrap();
+
+    // `longjmp` is caught by the `invoke_v` import, which needs the minified
+    // `setThrew` and `__indirect_function_table` exports.
+    assert_eq!("caught", svg.as_str());
+}
+
+#[test]
+fn from_bytes_returns_error_when_export_minified_without_map() {
+    let wasm_bytes = wat::parse_str(GRAPHVIZ_WAT_MINIFIED).unwrap();
+
+    let error = GraphvizWasmRenderer::from_bytes(&wasm_bytes).unwrap_err();
+
+    assert!(matches!(
+        error,
+        RenderError::GraphvizWasmExportMissing { .. }
+    ));
+}
+
+#[test]
+fn from_bytes_with_minification_map_returns_error_when_line_invalid() {
+    let wasm_bytes = wat::parse_str(GRAPHVIZ_WAT_MINIFIED).unwrap();
+
+    let error =
+        GraphvizWasmRenderer::from_bytes_with_minification_map(&wasm_bytes, "a:malloc\nb\n")
+            .unwrap_err();
+
+    match error {
+        RenderError::GraphvizWasmMinificationMapInvalid { line_number } => {
+            assert_eq!(2, line_number)
+        }
+        error => {
+            panic!("Expected `RenderError::GraphvizWasmMinificationMapInvalid`, but got: {error:?}")
+        }
+    }
+}
+
+/// Compares the SVG rendered from each vendored module with the SVG that
+/// `graphviz.js` rendered when it was vendored.
+///
+/// `graphviz_wasm/fixture` is vendored by `scripts/graphviz_wasm_vendor.mjs`
+/// from a `graphviz.js` fixture whose import and export names are minified, so
+/// this checks the minification map that the script recovers from the glue.
+///
+/// To also check the `@hpcc-js/wasm` module, vendor it with the same script and
+/// set `DOT_IX_GRAPHVIZ_WASM_DIR` to the output directory.
+#[test]
+fn render_matches_graphviz_js_svg() {
+    let fixture_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("graphviz_wasm/fixture");
+
+    std::iter::once(fixture_dir)
+        .chain(std::env::var_os("DOT_IX_GRAPHVIZ_WASM_DIR").map(PathBuf::from))
+        .for_each(|wasm_dir| render_matches_vendored_svg(&wasm_dir));
+}
+
+fn render_matches_vendored_svg(wasm_dir: &Path) {
+    let wasm_path = wasm_dir.join("graphviz.wasm");
+    let minification_map_path = wasm_dir.join("graphviz.wasm.map");
+    let graphviz_wasm_renderer = if minification_map_path.exists() {
+        GraphvizWasmRenderer::from_path_with_minification_map(&wasm_path, &minification_map_path)
+    } else {
+        GraphvizWasmRenderer::from_path(&wasm_path)
+    }
+    .unwrap();
+
+    let dot_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("graphviz_wasm");
+    let dot_paths = std::fs::read_dir(&dot_dir)
+        .unwrap()
+        .map(|dir_entry| dir_entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "dot"))
+        .collect::<Vec<PathBuf>>();
+    assert!(!dot_paths.is_empty());
+
+    dot_paths.iter().for_each(|dot_path| {
+        let dot_src = std::fs::read_to_string(dot_path).unwrap();
+        let svg_path = wasm_dir
+            .join(dot_path.file_name().unwrap())
+            .with_extension("svg");
+        let svg_expected = std::fs::read_to_string(&svg_path)
+            .unwrap_or_else(|error| panic!("Failed to read `{}`: {error}", svg_path.display()));
+
+        let svg = graphviz_wasm_renderer
+            .render(&dot_src_and_styles(&dot_src, GraphvizOpts::default()))
+            .unwrap();
+
+        assert_eq!(
+            svg_expected,
+            svg.as_str(),
+            "SVG for `{}` differs from `graphviz.js` in `{}`.",
+            dot_path.display(),
+            wasm_dir.display()
+        );
+    });
+}
@@ -0,0 +1,232 @@
+use dot_ix::{
+    model::{
+        common::{AnyId, EdgeId, NodeId, TagId},
+        info_graph::InfoGraph,
+    },
+    rt::{CytoscapeJson, Gexf, Graphml, InterchangeError},
+};
+
+fn info_graph() -> InfoGraph {
+    serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  app:
+    web: {}
+    db: {}
+  cli: {}
+node_names:
+  app: "App"
+  web: "Web <Server> & \"Proxy\""
+  db: "Database"
+node_descs:
+  web: "line 1\nline 2"
+node_emojis:
+  db: "🗄️"
+edges:
+  web__db: [web, db]
+  cli__web: [cli, web]
+edge_descs:
+  web__db: "queries"
+tags:
+  tag_storage: "Storage"
+  tag_unused: "Unused"
+tag_items:
+  tag_storage: [db, web__db]
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn graphml_round_trips_info_graph() {
+    let info_graph = info_graph();
+
+    let graphml = Graphml::export(&info_graph);
+
+    assert!(
+        graphml.contains(
+            "    <node id=\"app\">\n\
+            \x20     <data key=\"name\">App</data>\n\
+            \x20     <graph id=\"app:\" edgedefault=\"directed\">\n"
+        ),
+        "{graphml}"
+    );
+    assert!(
+        graphml.contains(r#"<data key="desc">line 1&#10;line 2</data>"#),
+        "{graphml}"
+    );
+    assert_eq!(info_graph, Graphml::import(&graphml).unwrap());
+}
+
+#[test]
+fn gexf_round_trips_info_graph() {
+    let info_graph = info_graph();
+
+    let gexf = Gexf::export(&info_graph);
+
+    assert!(
+        gexf.contains(r#"<attribute id="tag:tag_storage" title="Storage" type="boolean"/>"#),
+        "{gexf}"
+    );
+    assert!(
+        gexf.contains(r#"<edge id="web__db" source="web" target="db" label="queries">"#),
+        "{gexf}"
+    );
+    assert_eq!(info_graph, Gexf::import(&gexf).unwrap());
+}
+
+#[test]
+fn cytoscape_json_round_trips_info_graph() {
+    let info_graph = info_graph();
+
+    let cytoscape_json = CytoscapeJson::export(&info_graph);
+
+    let value = serde_json::from_str::<serde_json::Value>(&cytoscape_json).unwrap();
+    assert_eq!(
+        Some("app"),
+        value["elements"]["nodes"][1]["data"]["parent"].as_str()
+    );
+    assert_eq!(info_graph, CytoscapeJson::import(&cytoscape_json).unwrap());
+}
+
+#[test]
+fn round_trips_edge_ports() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a1: {}
+  b1: {}
+edges:
+  a1__b1: ["a1:n", "b1:s"]
+  b1__a1: [b1, "a1:e"]
+"#,
+    )
+    .unwrap();
+
+    let graphml = Graphml::export(&info_graph);
+    assert!(
+        graphml.contains(
+            r#"<edge id="a1__b1" source="a1" target="b1" sourceport="n" targetport="s">"#
+        ),
+        "{graphml}"
+    );
+    assert_eq!(info_graph, Graphml::import(&graphml).unwrap());
+
+    let gexf = Gexf::export(&info_graph);
+    assert!(
+        gexf.contains(r#"<attvalue for="target_port" value="e"/>"#),
+        "{gexf}"
+    );
+    assert_eq!(info_graph, Gexf::import(&gexf).unwrap());
+
+    let cytoscape_json = CytoscapeJson::export(&info_graph);
+    let value = serde_json::from_str::<serde_json::Value>(&cytoscape_json).unwrap();
+    assert_eq!(
+        Some("a1"),
+        value["elements"]["edges"][0]["data"]["source"].as_str()
+    );
+    assert_eq!(
+        Some("n"),
+        value["elements"]["edges"][0]["data"]["source_port"].as_str()
+    );
+    assert_eq!(info_graph, CytoscapeJson::import(&cytoscape_json).unwrap());
+}
+
+#[test]
+fn imports_ids_and_labels_from_other_tools() {
+    let info_graph = Graphml::import(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="node" attr.name="label" attr.type="string"/>
+  <key id="d1" for="edge" attr.name="label" attr.type="string"/>
+  <graph edgedefault="undirected">
+    <node id="n-0"><data key="d0">Zero</data></node>
+    <node id="1"/>
+    <edge source="n-0" target="1"><data key="d1">links</data></edge>
+  </graph>
+</graphml>"#,
+    )
+    .unwrap();
+
+    let n_0 = NodeId::try_from(String::from("n_0")).unwrap();
+    let node_1 = NodeId::try_from(String::from("_1")).unwrap();
+    assert_eq!(
+        Some("Zero"),
+        info_graph.node_names().get(&n_0).map(String::as_str)
+    );
+    assert_eq!(
+        Some("1"),
+        info_graph.node_names().get(&node_1).map(String::as_str)
+    );
+    let edge_id = EdgeId::try_from(String::from("n_0___1")).unwrap();
+    assert_eq!(Some(&[n_0, node_1]), info_graph.edges().get(&edge_id));
+    assert_eq!(
+        Some("links"),
+        info_graph.edge_descs().get(&edge_id).map(String::as_str)
+    );
+
+    let info_graph = CytoscapeJson::import(
+        r#"[
+            { "group": "nodes", "data": { "id": "a", "tags": ["hot"] } },
+            { "group": "nodes", "data": { "id": "b", "parent": "a" } },
+            { "group": "edges", "data": { "source": "b", "target": "a" } }
+        ]"#,
+    )
+    .unwrap();
+    let hot = TagId::try_from(String::from("hot")).unwrap();
+    assert_eq!(
+        Some(&vec![AnyId::try_from(String::from("a")).unwrap()]),
+        info_graph.tag_items().get(&hot)
+    );
+    assert!(
+        info_graph.hierarchy()[&NodeId::try_from(String::from("a")).unwrap()]
+            .contains_key(&NodeId::try_from(String::from("b")).unwrap())
+    );
+}
+
+#[test]
+fn returns_error_for_unknown_nodes_and_parent_cycles() {
+    assert_eq!(
+        InterchangeError::NodeUnknown {
+            referrer: String::from("a -> b"),
+            node_id: String::from("b"),
+        },
+        Gexf::import(
+            r#"<gexf version="1.3"><graph>
+                <nodes><node id="a"/></nodes>
+                <edges><edge source="a" target="b"/></edges>
+            </graph></gexf>"#
+        )
+        .unwrap_err()
+    );
+    assert_eq!(
+        InterchangeError::NodeParentCycle {
+            node_id: String::from("a"),
+        },
+        CytoscapeJson::import(
+            r#"{ "elements": { "nodes": [
+                { "data": { "id": "a", "parent": "b" } },
+                { "data": { "id": "b", "parent": "a" } }
+            ] } }"#
+        )
+        .unwrap_err()
+    );
+    assert_eq!(
+        InterchangeError::PortInvalid {
+            referrer: String::from("a -> a"),
+            node_id: String::from("a"),
+            port: String::from("n e"),
+        },
+        Graphml::import(
+            r#"<graphml><graph>
+                <node id="a"/>
+                <edge source="a" target="a" sourceport="n e"/>
+            </graph></graphml>"#
+        )
+        .unwrap_err()
+    );
+    assert_eq!(
+        InterchangeError::GraphMissing { element: "graphml" },
+        Graphml::import("<gexf/>").unwrap_err()
+    );
+}
@@ -0,0 +1,137 @@
+use dot_ix::{
+    model::{common::GraphvizDotTheme, info_graph::InfoGraph},
+    rt::IntoDrawioSrc,
+};
+
+fn info_graph() -> InfoGraph {
+    serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  app:
+    web: {}
+    db: {}
+  cli: {}
+node_names:
+  app: "App"
+  web: "Web <Server> & \"Proxy\""
+  db: "Database"
+node_descs:
+  web: "line 1\nline 2"
+node_emojis:
+  db: "🗄️"
+edges:
+  cli__web: ["cli:e", web]
+  web__db: [web, db]
+edge_descs:
+  web__db: "queries"
+graphviz_attrs:
+  edge_dirs:
+    cli__web: both
+theme:
+  styles:
+    web__db:
+      stroke_style: dashed
+      stroke_color: red
+      stroke_width: "2"
+    db:
+      fill_color: sky
+images:
+  img_db:
+    path: "data:image/png;base64,AAAA"
+    width: "32px"
+    height: "32px"
+node_images:
+  db: img_db
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn writes_clusters_as_containers_with_children_positioned_within() {
+    let drawio_src = IntoDrawioSrc::into(&info_graph(), &GraphvizDotTheme::default());
+
+    assert!(
+        drawio_src.starts_with("<mxfile host=\"dot_ix\">\n"),
+        "{drawio_src}"
+    );
+    assert!(
+        drawio_src.contains(
+            "<mxCell id=\"app\" value=\"App\" style=\"rounded=1;arcSize=4;absoluteArcSize=1;\
+            whiteSpace=wrap;html=0;container=1;collapsible=0;"
+        ),
+        "{drawio_src}"
+    );
+    assert!(
+        drawio_src.contains(
+            "<mxCell id=\"web\" \
+            value=\"Web &lt;Server&gt; &amp; &quot;Proxy&quot;&#10;line 1&#10;line 2\""
+        ),
+        "{drawio_src}"
+    );
+    assert!(
+        drawio_src.contains(
+            "vertex=\"1\" parent=\"app\">\n          \
+            <mxGeometry x=\"8\" y=\"20\" width=\"148\" height=\"44\" as=\"geometry\"/>"
+        ),
+        "{drawio_src}"
+    );
+    assert!(
+        drawio_src.contains("<mxCell id=\"cli\" value=\"cli\""),
+        "{drawio_src}"
+    );
+}
+
+#[test]
+fn writes_edges_with_labels_arrows_compass_points_and_waypoints() {
+    let drawio_src = IntoDrawioSrc::into(&info_graph(), &GraphvizDotTheme::default());
+
+    assert!(
+        drawio_src.contains(
+            "<mxCell id=\"cli__web\" value=\"\" style=\"rounded=1;curved=1;html=0;\
+            startArrow=classic;endArrow=classic;exitX=1;exitY=0.5;exitPerimeter=0;"
+        ),
+        "{drawio_src}"
+    );
+    assert!(
+        drawio_src.contains(
+            "edge=\"1\" parent=\"1\" source=\"web\" target=\"db\">\n          \
+            <mxGeometry relative=\"1\" as=\"geometry\">\n            \
+            <Array as=\"points\">\n              \
+            <mxPoint x=\"244\" y=\"42\"/>\n"
+        ),
+        "{drawio_src}"
+    );
+    assert!(
+        drawio_src.contains("<mxCell id=\"web__db\" value=\"queries\""),
+        "{drawio_src}"
+    );
+}
+
+#[test]
+fn writes_theme_colours_and_dashes_as_styles() {
+    let drawio_src = IntoDrawioSrc::into(&info_graph(), &GraphvizDotTheme::default());
+
+    assert!(
+        drawio_src.contains("strokeColor=#7f1d1d;strokeWidth=2;dashed=1;dashPattern=3;"),
+        "{drawio_src}"
+    );
+    assert!(
+        drawio_src.contains("strokeColor=#475569;strokeWidth=1;fillColor=#7dd3fc;\""),
+        "{drawio_src}"
+    );
+}
+
+#[test]
+fn writes_images_as_label_images() {
+    let drawio_src = IntoDrawioSrc::into(&info_graph(), &GraphvizDotTheme::default());
+
+    assert!(
+        drawio_src.contains(
+            "<mxCell id=\"db\" value=\"🗄️ Database\" style=\"rounded=1;arcSize=4;\
+            absoluteArcSize=1;whiteSpace=wrap;html=0;fontSize=10;shape=label;\
+            image=data:image/png,AAAA;imageWidth=32;imageHeight=32;"
+        ),
+        "{drawio_src}"
+    );
+}
@@ -0,0 +1,595 @@
+use std::fmt;
+
+use dot_ix::{
+    model::{
+        common::{
+            graphviz_attrs::{ArrowPrimitive, ArrowType, EdgeArrowheads, EdgeArrowsizes},
+            DotSrcAndStyles, Edges, GraphvizAttrs, GraphvizDotTheme, NodeHierarchy,
+        },
+        edge_id,
+        info_graph::InfoGraph,
+        node_id,
+        theme::{ThemeWarning, ThemeWarningSeverity},
+    },
+    rt::{write_dot, IntoGraphvizDotSrc},
+};
+
+#[test]
+fn edge_arrows_are_emitted_as_defaults_and_per_edge() {
+    let mut hierarchy = NodeHierarchy::new();
+    hierarchy.insert(node_id!("a"), NodeHierarchy::new());
+    hierarchy.insert(node_id!("b"), NodeHierarchy::new());
+    let mut edges = Edges::new();
+    edges.insert(edge_id!("a__b"), [node_id!("a"), node_id!("b")]);
+    let graphviz_attrs = GraphvizAttrs::new()
+        .with_edge_arrowhead_default(ArrowType::from(ArrowPrimitive::Vee))
+        .with_edge_arrowsize_default(0.5)
+        .with_edge_arrowheads(EdgeArrowheads::from_iter([(
+            edge_id!("a__b"),
+            "crowodot".parse::<ArrowType>().unwrap(),
+        )]))
+        .with_edge_arrowsizes(EdgeArrowsizes::from_iter([(edge_id!("a__b"), 1.2)]));
+    let info_graph = InfoGraph::default()
+        .with_hierarchy(hierarchy)
+        .with_edges(edges)
+        .with_graphviz_attrs(graphviz_attrs);
+
+    let DotSrcAndStyles { dot_src, .. } =
+        IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    assert!(dot_src.contains("arrowhead  = vee"), "{dot_src}");
+    assert!(dot_src.contains("arrowtail  = normal"), "{dot_src}");
+    assert!(dot_src.contains("arrowsize  = 0.5"), "{dot_src}");
+    assert!(dot_src.contains("arrowhead = crowodot"), "{dot_src}");
+    assert!(dot_src.contains("arrowsize = 1.2"), "{dot_src}");
+}
+
+#[test]
+fn edge_flow_animates_dashes_in_edge_direction() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a: {}
+  b: {}
+  c: {}
+edges:
+  a__b: [a, b]
+  b__c: [b, c]
+tags:
+  tag_deploy: "Deploy"
+tag_items:
+  tag_deploy: [b__c]
+graphviz_attrs:
+  edge_dirs:
+    a__b: back
+theme:
+  styles:
+    a__b:
+      edge_flow: flow
+      stroke_style: "dasharray:4,2"
+tag_styles_focus:
+  tag_deploy:
+    edge_defaults:
+      edge_flow: marching_ants
+"#,
+    )
+    .unwrap();
+
+    let DotSrcAndStyles {
+        dot_src, styles, ..
+    } = IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    assert!(
+        dot_src.contains(
+            "[&>path]:[--dot-ix-dash-period:6] \
+            [&>path]:animate-edge-flow-reverse"
+        ),
+        "{dot_src}"
+    );
+    assert!(
+        dot_src.contains(
+            "peer-focus/tag_deploy:[&>path]:[stroke-dasharray:5,3] \
+            peer-focus/tag_deploy:[&>path]:[--dot-ix-dash-period:8] \
+            peer-focus/tag_deploy:[&>path]:animate-edge-march"
+        ),
+        "{dot_src}"
+    );
+    assert!(styles.contains("@keyframes dot_ix_edge_dash"), "{styles}");
+    assert!(
+        styles.contains(
+            ".\\[\\&\\>path\\]\\:animate-edge-flow-reverse>path { \
+            animation: dot_ix_edge_dash 1s linear infinite reverse; }"
+        ),
+        "{styles}"
+    );
+}
+
+#[test]
+fn edge_flow_unknown_value_produces_theme_warning() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a: {}
+  b: {}
+edges:
+  a__b: [a, b]
+theme:
+  styles:
+    a__b:
+      edge_flow: zoom
+"#,
+    )
+    .unwrap();
+
+    let DotSrcAndStyles {
+        dot_src,
+        theme_warnings,
+        ..
+    } = IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    assert!(!dot_src.contains("animate-edge-"), "{dot_src}");
+    let theme_warning = theme_warnings
+        .iter()
+        .find(|theme_warning| matches!(theme_warning, ThemeWarning::AttrValueInvalid(_)))
+        .expect("Expected a warning for the invalid `edge_flow`.");
+    assert_eq!("a__b", theme_warning.any_id().as_str());
+    assert_eq!(ThemeWarningSeverity::Warning, theme_warning.severity());
+    assert_eq!(
+        "`edge_flow` value `zoom` is invalid, so edge_flow classes will not be applied. \
+        Unable to map `zoom` to an `EdgeFlow`. \
+        Valid strings are: \"marching_ants\", \"pulse\", \"flow\".",
+        theme_warning.to_string()
+    );
+}
+
+#[test]
+fn write_dot_writes_same_dot_src_as_into_graphviz_dot_src() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a:
+    a0: {}
+    a1: {}
+  b: {}
+node_descs:
+  a0: "first\nline"
+node_emojis:
+  b: "🐙"
+edges:
+  a__b: [a, b]
+  a0__a1: [a0, a1]
+edge_descs:
+  a0__a1: "next"
+tags:
+  tag_a: "Tag A"
+tag_items:
+  tag_a: [a0, a0__a1]
+graphviz_attrs:
+  edge_constraints:
+    a__b: false
+"#,
+    )
+    .unwrap();
+    let theme = GraphvizDotTheme::default();
+
+    let mut dot_src = String::new();
+    write_dot(&info_graph, &theme, &mut dot_src).unwrap();
+
+    let DotSrcAndStyles {
+        dot_src: dot_src_expected,
+        ..
+    } = IntoGraphvizDotSrc::into(&info_graph, &theme);
+    assert_eq!(dot_src_expected, dot_src);
+}
+
+#[test]
+fn write_dot_streams_to_writer_in_chunks() {
+    let mut hierarchy = NodeHierarchy::new();
+    hierarchy.insert(node_id!("a"), NodeHierarchy::new());
+    hierarchy.insert(node_id!("b"), NodeHierarchy::new());
+    let mut edges = Edges::new();
+    edges.insert(edge_id!("a__b"), [node_id!("a"), node_id!("b")]);
+    let info_graph = InfoGraph::default()
+        .with_hierarchy(hierarchy)
+        .with_edges(edges);
+
+    let mut chunk_writer = ChunkWriter::default();
+    write_dot(&info_graph, &GraphvizDotTheme::default(), &mut chunk_writer).unwrap();
+
+    let dot_src = chunk_writer.chunks.concat();
+    assert!(chunk_writer.chunks.len() > 1);
+    assert!(dot_src.starts_with("digraph G {"), "{dot_src}");
+    assert!(dot_src.contains("a -> b ["), "{dot_src}");
+    assert!(dot_src.ends_with('}'), "{dot_src}");
+}
+
+#[test]
+fn write_dot_propagates_writer_errors() {
+    let info_graph = InfoGraph::default();
+
+    let result = write_dot(
+        &info_graph,
+        &GraphvizDotTheme::default(),
+        &mut FailingWriter,
+    );
+
+    assert_eq!(Err(fmt::Error), result);
+}
+
+#[test]
+fn node_and_edge_classes_include_tag_focus_classes() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a:
+    a0: {}
+  b: {}
+edges:
+  a0__b: [a0, b]
+tags:
+  tag_x: "X"
+  tag_y: "Y"
+tag_items:
+  tag_x: [a0, a0__b]
+  tag_y: [a0]
+"#,
+    )
+    .unwrap();
+
+    let DotSrcAndStyles { dot_src, .. } =
+        IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    let a0_classes = dot_src
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("a0 ["))
+        .find(|line| line.trim_start().starts_with("class"))
+        .unwrap();
+    assert!(a0_classes.contains("peer-focus/tag_x:"), "{a0_classes}");
+    assert!(a0_classes.contains("peer-focus/tag_y:"), "{a0_classes}");
+    let b_classes = dot_src
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("b ["))
+        .find(|line| line.trim_start().starts_with("class"))
+        .unwrap();
+    assert!(!b_classes.contains("peer-focus/tag_"), "{b_classes}");
+    let edge_classes = dot_src
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("a0 -> b ["))
+        .find(|line| line.trim_start().starts_with("class"))
+        .unwrap();
+    assert!(edge_classes.contains("peer-focus/tag_x:"), "{edge_classes}");
+    assert!(
+        !edge_classes.contains("peer-focus/tag_y:"),
+        "{edge_classes}"
+    );
+}
+
+#[derive(Default)]
+struct ChunkWriter {
+    chunks: Vec<String>,
+}
+
+impl fmt::Write for ChunkWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.chunks.push(s.to_string());
+        Ok(())
+    }
+}
+
+struct FailingWriter;
+
+impl fmt::Write for FailingWriter {
+    fn write_str(&mut self, _s: &str) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
+
+#[test]
+fn into_with_src_map_maps_dot_src_lines_to_info_graph_elements() {
+    let info_graph_src = r#"
+hierarchy:
+  a:
+    b: {}
+  c: {}
+edges:
+  a_to_c: [a, c]
+tags:
+  tag_0: "Tag 0"
+tag_items:
+  tag_0: [c]
+"#;
+    let info_graph = serde_yaml::from_str::<InfoGraph>(info_graph_src).unwrap();
+
+    let DotSrcAndStyles {
+        dot_src, src_map, ..
+    } = IntoGraphvizDotSrc::into_with_src_map(&info_graph, &GraphvizDotTheme::default());
+    let src_map = src_map.expect("Expected `src_map` to be recorded.");
+    let dot_src_lines = dot_src.lines().collect::<Vec<_>>();
+
+    let doc_paths = src_map
+        .iter()
+        .map(|entry| entry.doc_path())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "tags.tag_0",
+            "hierarchy.a.b",
+            "hierarchy.a",
+            "hierarchy.c",
+            "edges.a_to_c",
+        ],
+        doc_paths
+    );
+
+    let entry_b = src_map.entries_for_id("b").next().unwrap();
+    let entry_a = src_map.entries_for_id("a").next().unwrap();
+    assert!(dot_src_lines[entry_b.line_start() as usize - 1].contains("b ["));
+    assert!(dot_src_lines[entry_a.line_start() as usize - 1].contains("subgraph cluster_a"));
+    assert!(entry_a.line_start() < entry_b.line_start());
+    assert!(entry_b.line_end() <= entry_a.line_end());
+
+    // Lines within `b` map to `b` rather than the enclosing cluster `a`.
+    assert_eq!(
+        Some(entry_b),
+        src_map.entry_at_line(entry_b.line_start() + 1)
+    );
+    assert_eq!(
+        Some(entry_b),
+        src_map.entry_for_error(&format!(
+            "Error: syntax error in line {} near '['",
+            entry_b.line_start() + 1
+        ))
+    );
+
+    let entry_edge = src_map.entries_for_id("a_to_c").next().unwrap();
+    assert!(dot_src_lines[entry_edge.line_start() as usize - 1].contains("->"));
+}
+
+#[test]
+fn src_map_entry_yaml_line_finds_element_in_info_graph_src() {
+    let info_graph_src = r#"
+hierarchy:
+  # comment
+  a:
+    a0: {}
+    a1: {}
+  b: {}
+edges:
+  a_to_b: [a, b]
+"#;
+    let info_graph = serde_yaml::from_str::<InfoGraph>(info_graph_src).unwrap();
+
+    let DotSrcAndStyles { src_map, .. } =
+        IntoGraphvizDotSrc::into_with_src_map(&info_graph, &GraphvizDotTheme::default());
+    let src_map = src_map.expect("Expected `src_map` to be recorded.");
+    let yaml_lines = src_map
+        .iter()
+        .map(|entry| (entry.doc_path(), entry.yaml_line(info_graph_src)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            ("hierarchy.a.a1", Some(6)),
+            ("hierarchy.a.a0", Some(5)),
+            ("hierarchy.a", Some(4)),
+            ("hierarchy.b", Some(7)),
+            ("edges.a_to_b", Some(9)),
+        ],
+        yaml_lines
+    );
+}
+
+#[test]
+fn into_does_not_record_src_map() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>("hierarchy: { a: {} }").unwrap();
+
+    let dot_src_and_styles = IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    assert_eq!(None, dot_src_and_styles.src_map());
+}
+
+#[test]
+fn max_depth_renders_clusters_at_max_depth_as_nodes() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  region:
+    subnet_a: {}
+    subnet_b: {}
+  user: {}
+edges:
+  user__subnet_a: [user, subnet_a]
+  user__subnet_b: [user, subnet_b]
+graphviz_attrs:
+  max_depth: 0
+"#,
+    )
+    .unwrap();
+
+    let DotSrcAndStyles { dot_src, .. } =
+        IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+    let mut dot_src_written = String::new();
+    write_dot(
+        &info_graph,
+        &GraphvizDotTheme::default(),
+        &mut dot_src_written,
+    )
+    .unwrap();
+
+    assert!(!dot_src.contains("cluster_region"));
+    assert!(!dot_src.contains("subnet_a ["));
+    assert!(dot_src.contains("2 nodes"));
+    assert!(dot_src.contains("user -> region"));
+    assert!(dot_src.contains("2 edges"));
+    assert!(!dot_src.contains("user__subnet_b"));
+    assert_eq!(dot_src, dot_src_written);
+}
+
+const INFO_GRAPH_PARALLEL_EDGES: &str = r#"
+hierarchy:
+  a: {}
+  b: {}
+  c:
+    c0: {}
+    c1: {}
+edges:
+  a__b_read: [a, b]
+  a__b_write: [a, b]
+  a__c_deploy: [a, c]
+  a__c_monitor: [a, c]
+  a__c0: [a, c0]
+edge_descs:
+  a__b_read: "reads \"cfg\""
+tags:
+  tag_io: "IO"
+tag_items:
+  tag_io: [a__b_write]
+"#;
+
+#[test]
+fn edge_bundling_draws_edges_with_same_ends_as_one_edge() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_PARALLEL_EDGES)
+        .unwrap()
+        .with_graphviz_attrs(GraphvizAttrs::new().with_edge_bundling(true));
+
+    let DotSrcAndStyles {
+        dot_src,
+        edge_bundles,
+        ..
+    } = IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    assert_eq!(1, dot_src.matches("a -> b [").count(), "{dot_src}");
+    assert!(!dot_src.contains("id     = \"a__b_write\""), "{dot_src}");
+    assert!(!dot_src.contains("id     = \"a__c_monitor\""), "{dot_src}");
+    // `a__c0` is drawn to the same node, but without `lhead`.
+    assert!(dot_src.contains("id     = \"a__c0\""), "{dot_src}");
+    assert!(
+        dot_src
+            .contains("label = <2 edges> tooltip = \"a__b_read: reads \\\"cfg\\\"\na__b_write\""),
+        "{dot_src}"
+    );
+    assert_eq!(
+        vec![
+            (
+                edge_id!("a__b_read"),
+                vec![edge_id!("a__b_read"), edge_id!("a__b_write")]
+            ),
+            (
+                edge_id!("a__c_deploy"),
+                vec![edge_id!("a__c_deploy"), edge_id!("a__c_monitor")]
+            ),
+        ],
+        edge_bundles.into_inner().into_iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn edge_bundling_draws_edges_with_different_attributes_separately() {
+    let graphviz_attrs = serde_yaml::from_str::<GraphvizAttrs>(
+        r#"
+edge_bundling: true
+edge_dirs:
+  a__b_write: back
+edge_arrowheads:
+  a__c_monitor: dot
+"#,
+    )
+    .unwrap();
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_PARALLEL_EDGES)
+        .unwrap()
+        .with_graphviz_attrs(graphviz_attrs);
+
+    let DotSrcAndStyles {
+        dot_src,
+        edge_bundles,
+        ..
+    } = IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    assert_eq!(2, dot_src.matches("a -> b [").count(), "{dot_src}");
+    assert!(dot_src.contains("id     = \"a__b_write\""), "{dot_src}");
+    assert!(dot_src.contains("id     = \"a__c_monitor\""), "{dot_src}");
+    assert!(!dot_src.contains("edges>"), "{dot_src}");
+    assert!(edge_bundles.is_empty());
+}
+
+#[test]
+fn edge_bundling_draws_edges_with_different_theme_classes_separately() {
+    let info_graph_src = format!(
+        "{INFO_GRAPH_PARALLEL_EDGES}\
+theme:
+  styles:
+    a__b_write:
+      stroke_color: red
+"
+    );
+    let info_graph = serde_yaml::from_str::<InfoGraph>(&info_graph_src)
+        .unwrap()
+        .with_graphviz_attrs(GraphvizAttrs::new().with_edge_bundling(true));
+
+    let DotSrcAndStyles {
+        dot_src,
+        edge_bundles,
+        ..
+    } = IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    assert_eq!(2, dot_src.matches("a -> b [").count(), "{dot_src}");
+    assert!(dot_src.contains("stroke-red-"), "{dot_src}");
+    assert_eq!(
+        vec![(
+            edge_id!("a__c_deploy"),
+            vec![edge_id!("a__c_deploy"), edge_id!("a__c_monitor")]
+        )],
+        edge_bundles.into_inner().into_iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn edge_bundling_applies_tag_classes_of_each_bundled_edge() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_PARALLEL_EDGES)
+        .unwrap()
+        .with_graphviz_attrs(GraphvizAttrs::new().with_edge_bundling(true));
+
+    let DotSrcAndStyles { dot_src, .. } =
+        IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    let edge_classes = dot_src
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("a -> b ["))
+        .find(|line| line.trim_start().starts_with("class"))
+        .unwrap();
+    assert!(
+        edge_classes.contains("peer-focus/tag_io:"),
+        "{edge_classes}"
+    );
+}
+
+#[test]
+fn edge_bundling_disabled_draws_each_edge() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_PARALLEL_EDGES).unwrap();
+
+    let DotSrcAndStyles {
+        dot_src,
+        edge_bundles,
+        ..
+    } = IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    assert_eq!(2, dot_src.matches("a -> b [").count(), "{dot_src}");
+    assert!(!dot_src.contains("tooltip"), "{dot_src}");
+    assert!(edge_bundles.is_empty());
+}
+
+#[test]
+fn edge_bundling_records_src_map_entry_for_each_bundled_edge() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(INFO_GRAPH_PARALLEL_EDGES)
+        .unwrap()
+        .with_graphviz_attrs(GraphvizAttrs::new().with_edge_bundling(true));
+
+    let DotSrcAndStyles { src_map, .. } =
+        IntoGraphvizDotSrc::into_with_src_map(&info_graph, &GraphvizDotTheme::default());
+    let src_map = src_map.expect("Expected `src_map` to be recorded.");
+
+    let entry_read = src_map.entries_for_id("a__b_read").next().unwrap();
+    let entry_write = src_map.entries_for_id("a__b_write").next().unwrap();
+    assert_eq!(entry_read.line_start(), entry_write.line_start());
+    assert_eq!("edges.a__b_write", entry_write.doc_path());
+}
@@ -0,0 +1,137 @@
+use dot_ix::{
+    model::{
+        common::{
+            graphviz_attrs::{EdgeDir, EdgeDirs},
+            Edges, GraphvizAttrs, HtmlSrcAndStyles, NodeHierarchy, NodeNames,
+        },
+        edge_id,
+        info_graph::{GraphDir, InfoGraph},
+        node_id,
+    },
+    rt::{HtmlEdgeConnector, IntoHtmlSrc},
+};
+
+#[test]
+fn renders_nested_nodes_in_graph_direction() {
+    let info_graph = info_graph_nested().with_direction(GraphDir::Vertical);
+
+    let HtmlSrcAndStyles { html_src, .. } =
+        IntoHtmlSrc::into(&info_graph, HtmlEdgeConnector::Straight);
+
+    assert!(html_src.starts_with(
+        "<div class=\"dot_ix_html\" \
+        data-graph-dir=\"vertical\" \
+        data-edge-connector=\"straight\">\
+        <div class=\"dot_ix_html_nodes dot_ix_html_vertical\">\
+        <div id=\"a\" tabindex=\"0\" class=\"dot_ix_html_node outline-none "
+    ));
+    let b_index = html_src
+        .find("id=\"b\"")
+        .expect("Expected `b` to be rendered.");
+    let a_close_index = html_src
+        .find("</div></div><div id=\"c\"")
+        .expect("Expected `c` to be rendered after `a` closes.");
+    assert!(b_index < a_close_index, "{html_src}");
+    assert!(html_src.contains("<div class=\"dot_ix_html_label\">Node A</div>"));
+}
+
+#[test]
+fn renders_edges_in_svg_overlay() {
+    let info_graph = info_graph_nested();
+
+    let HtmlSrcAndStyles {
+        html_src, styles, ..
+    } = IntoHtmlSrc::into(&info_graph, HtmlEdgeConnector::Orthogonal);
+
+    assert!(html_src.contains("data-edge-connector=\"orthogonal\""));
+    assert!(html_src.contains(
+        "data-edge-src=\"b\" \
+        data-edge-target=\"c\" \
+        data-edge-dir=\"both\">\
+        <path d=\"\" />\
+        <polygon points=\"\" />\
+        <polygon points=\"\" />\
+        </g></svg></div>"
+    ));
+    assert!(
+        html_src.contains(" [&amp;&gt;path]:stroke-slate-900 [&amp;&gt;polygon]:stroke-slate-900 "),
+        "{html_src}"
+    );
+    assert!(styles.starts_with(".dot_ix_html {"));
+    assert!(styles.contains(">path { stroke: "));
+}
+
+#[test]
+fn escapes_names_descriptions_and_edge_descriptions() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a: {}
+  b: {}
+node_names:
+  a: "<script>alert(1)</script>"
+  b: "R&D \"lab\""
+node_descs:
+  a: "x < y\ny & z"
+edges:
+  a__b: [a, "b:n"]
+edge_descs:
+  a__b: "<b>\"sends\" & receives</b>"
+"#,
+    )
+    .unwrap();
+
+    let HtmlSrcAndStyles { html_src, .. } =
+        IntoHtmlSrc::into(&info_graph, HtmlEdgeConnector::Straight);
+
+    assert!(!html_src.contains("<script>"), "{html_src}");
+    assert!(!html_src.contains("<b>"), "{html_src}");
+    assert!(
+        html_src.contains(
+            "<div class=\"dot_ix_html_label\">&lt;script&gt;alert(1)&lt;/script&gt;</div>"
+        ),
+        "{html_src}"
+    );
+    assert!(
+        html_src.contains("<div class=\"dot_ix_html_label\">R&amp;D &quot;lab&quot;</div>"),
+        "{html_src}"
+    );
+    assert!(
+        html_src.contains("<div class=\"dot_ix_html_desc\">x &lt; y<br />y &amp; z</div>"),
+        "{html_src}"
+    );
+    assert!(
+        html_src.contains(
+            "<text text-anchor=\"middle\">\
+            &lt;b&gt;&quot;sends&quot; &amp; receives&lt;/b&gt;\
+            </text>"
+        ),
+        "{html_src}"
+    );
+    assert!(html_src.contains("data-edge-target=\"b\""), "{html_src}");
+}
+
+/// Returns an `InfoGraph` with the hierarchy `a: { b: {} }, c: {}`, and an edge
+/// from `b` to `c`.
+fn info_graph_nested() -> InfoGraph {
+    let mut hierarchy_a = NodeHierarchy::new();
+    hierarchy_a.insert(node_id!("b"), NodeHierarchy::new());
+    let mut hierarchy = NodeHierarchy::new();
+    hierarchy.insert(node_id!("a"), hierarchy_a);
+    hierarchy.insert(node_id!("c"), NodeHierarchy::new());
+
+    let mut edges = Edges::new();
+    edges.insert(edge_id!("b__c"), [node_id!("b"), node_id!("c")]);
+
+    let graphviz_attrs = GraphvizAttrs::new()
+        .with_edge_dirs(EdgeDirs::from_iter([(edge_id!("b__c"), EdgeDir::Both)]));
+
+    InfoGraph::default()
+        .with_hierarchy(hierarchy)
+        .with_node_names(NodeNames::from_iter([(
+            node_id!("a"),
+            String::from("Node A"),
+        )]))
+        .with_edges(edges)
+        .with_graphviz_attrs(graphviz_attrs)
+}
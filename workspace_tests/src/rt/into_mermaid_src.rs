@@ -0,0 +1,128 @@
+use dot_ix::{model::info_graph::InfoGraph, rt::IntoMermaidSrc};
+
+#[test]
+fn converts_hierarchy_labels_and_edges_to_flowchart() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+direction: vertical
+hierarchy:
+  a:
+    a0: {}
+    end: {}
+  b: {}
+node_names:
+  a: "Group A"
+  a0: "A \"Zero\""
+node_emojis:
+  a0: "📦"
+node_descs:
+  a0: "line 1\nline 2"
+edges:
+  a0__end: [a0, end]
+  end__b: [end, "b:n"]
+  b__a0: [b, a0]
+  a0__b: [a0, b]
+edge_descs:
+  a0__end: "calls"
+graphviz_attrs:
+  edge_dirs:
+    end__b: both
+    b__a0: none
+    a0__b: back
+"#,
+    )
+    .unwrap();
+
+    let mermaid_src = IntoMermaidSrc::into(&info_graph);
+
+    assert!(mermaid_src.starts_with("flowchart TB\n"), "{mermaid_src}");
+    assert!(
+        mermaid_src.contains(
+            "    subgraph a [\"Group A\"]\n\
+            \x20       a0(\"📦 A #quot;Zero#quot;<br/>line 1<br/>line 2\")\n\
+            \x20       end_(\"end\")\n\
+            \x20   end\n\
+            \x20   b(\"b\")\n"
+        ),
+        "{mermaid_src}"
+    );
+    assert!(mermaid_src.contains("    a0 -->|\"calls\"| end_\n"));
+    assert!(mermaid_src.contains("    end_ <--> b\n"));
+    assert!(mermaid_src.contains("    b --- a0\n"));
+    assert!(mermaid_src.contains("    b --> a0\n"));
+}
+
+#[test]
+fn converts_theme_colours_to_class_defs_and_link_styles() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a: {}
+  b: {}
+  c: {}
+edges:
+  a__b: [a, b]
+theme:
+  merge_with_base: true
+  styles:
+    b:
+      shape_color: amber
+      stroke_style: dashed
+    a__b:
+      stroke_color: red
+"#,
+    )
+    .unwrap();
+
+    let mermaid_src = IntoMermaidSrc::into(&info_graph);
+
+    assert!(mermaid_src.starts_with("flowchart LR\n"), "{mermaid_src}");
+    assert!(
+        mermaid_src.contains(
+            "    classDef theme_0 stroke:#475569,stroke-width:1px,fill:#cbd5e1\n\
+            \x20   class a,c theme_0\n"
+        ),
+        "{mermaid_src}"
+    );
+    assert!(
+        mermaid_src.contains("class b theme_1\n")
+            && mermaid_src.contains("fill:#fcd34d")
+            && mermaid_src.contains("stroke-dasharray:"),
+        "{mermaid_src}"
+    );
+    assert!(
+        mermaid_src.contains("    linkStyle 0 stroke:#7f1d1d,stroke-width:1px"),
+        "{mermaid_src}"
+    );
+}
+
+#[test]
+fn escapes_label_markup_and_keyword_ids_without_collisions() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  end: {}
+  end_: {}
+node_names:
+  end: "<b>R&D</b> #1"
+edges:
+  end__end_: [end, end_]
+"#,
+    )
+    .unwrap();
+
+    let mermaid_src = IntoMermaidSrc::into(&info_graph);
+
+    assert!(
+        mermaid_src.contains("    end_(\"#lt;b#gt;R#amp;D#lt;/b#gt; #35;1\")\n"),
+        "{mermaid_src}"
+    );
+    assert!(
+        mermaid_src.contains("    end__(\"end_\")\n"),
+        "{mermaid_src}"
+    );
+    assert!(
+        mermaid_src.contains("    end_ --> end__\n"),
+        "{mermaid_src}"
+    );
+}
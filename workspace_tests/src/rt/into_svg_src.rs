@@ -0,0 +1,125 @@
+use dot_ix::{
+    model::{common::GraphvizDotTheme, info_graph::InfoGraph},
+    rt::IntoSvgSrc,
+};
+
+#[test]
+fn renders_nodes_clusters_and_edges_with_graphviz_ids_and_classes() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  a:
+    a0: {}
+    a1: {}
+  b: {}
+node_names:
+  a0: "A Zero"
+edges:
+  a0__a1: [a0, a1]
+  a1__b: [a1, b]
+edge_descs:
+  a1__b: "calls"
+tags:
+  tag_0: "Tag Zero"
+tag_items:
+  tag_0: [a0, a1__b]
+"#,
+    )
+    .unwrap();
+
+    let svg_src_and_styles = IntoSvgSrc::into(&info_graph, &GraphvizDotTheme::default());
+    let svg_src = svg_src_and_styles.svg_src();
+
+    assert!(svg_src.starts_with("<svg "), "{svg_src}");
+    assert!(svg_src.contains(r#"<g id="graph0" class="graph">"#));
+    assert!(svg_src.contains("<title>cluster_a</title>"));
+    assert!(svg_src.contains("<title>a0</title>"));
+    assert!(svg_src.contains(">A Zero</text>"));
+    assert!(svg_src.contains(r#"<g id="a0__a1" class="edge "#));
+    assert!(svg_src.contains("<title>a1-&gt;b</title>"));
+    assert!(svg_src.contains(">calls</text>"));
+    assert!(svg_src.contains("peer/tag_0"));
+    assert!(svg_src.contains("peer-focus/tag_0"), "{svg_src}");
+    assert!(svg_src_and_styles.styles().contains(".outline-none"));
+}
+
+#[test]
+fn ranks_nodes_along_graph_direction() {
+    let info_graph = |direction: &str| {
+        serde_yaml::from_str::<InfoGraph>(&format!(
+            r#"
+direction: {direction}
+hierarchy:
+  a: {{}}
+  b: {{}}
+edges:
+  a__b: [a, b]
+"#
+        ))
+        .unwrap()
+    };
+
+    let svg_src = IntoSvgSrc::into(&info_graph("vertical"), &GraphvizDotTheme::default())
+        .svg_src()
+        .to_string();
+    let (a_x, a_y) = node_position(&svg_src, "a");
+    let (b_x, b_y) = node_position(&svg_src, "b");
+    assert!(b_y > a_y, "{svg_src}");
+    assert_eq!(a_x, b_x, "{svg_src}");
+
+    let svg_src = IntoSvgSrc::into(&info_graph("horizontal"), &GraphvizDotTheme::default())
+        .svg_src()
+        .to_string();
+    let (a_x, a_y) = node_position(&svg_src, "a");
+    let (b_x, b_y) = node_position(&svg_src, "b");
+    assert!(b_x > a_x, "{svg_src}");
+    assert_eq!(a_y, b_y, "{svg_src}");
+}
+
+#[test]
+fn edge_minlens_and_constraints_affect_ranks() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+direction: vertical
+hierarchy:
+  a: {}
+  b: {}
+  c: {}
+  d: {}
+edges:
+  a__b: [a, b]
+  a__c: [a, c]
+  c__d: [c, d]
+graphviz_attrs:
+  edge_minlen_default: 1
+  edge_minlens:
+    a__c: 3
+  edge_constraints:
+    c__d: false
+"#,
+    )
+    .unwrap();
+
+    let svg_src = IntoSvgSrc::into(&info_graph, &GraphvizDotTheme::default())
+        .svg_src()
+        .to_string();
+    let (_a_x, a_y) = node_position(&svg_src, "a");
+    let (_b_x, b_y) = node_position(&svg_src, "b");
+    let (_c_x, c_y) = node_position(&svg_src, "c");
+    let (_d_x, d_y) = node_position(&svg_src, "d");
+
+    assert!(c_y - a_y > 2.0 * (b_y - a_y), "{svg_src}");
+    assert!(d_y < c_y, "{svg_src}");
+}
+
+/// Returns the top left corner of a node's shape.
+fn node_position(svg_src: &str, node_id: &str) -> (f64, f64) {
+    let title = format!("<title>{node_id}</title>");
+    let node_start = svg_src.find(&title).unwrap_or_else(|| panic!("{svg_src}"));
+    let path_d = &svg_src[node_start..];
+    let path_d = &path_d[path_d.find(" d=\"M").unwrap() + 5..];
+    let (x, rest) = path_d.split_once(',').unwrap();
+    let y = rest.split_once(' ').unwrap().0;
+
+    (x.parse().unwrap(), y.parse().unwrap())
+}
@@ -0,0 +1,208 @@
+use dot_ix::{
+    model::{
+        common::GraphvizAttrs,
+        info_graph::{GraphDir, InfoGraph},
+    },
+    rt::{IntoTextSrc, TextCharset, TextOptions},
+};
+
+fn info_graph() -> InfoGraph {
+    serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  app:
+    web: {}
+    db: {}
+  cli: {}
+node_names:
+  app: "App"
+  web: "Web"
+  db: "Database"
+  cli: "CLI"
+node_emojis:
+  db: "🗄️"
+edges:
+  cli__web: [cli, web]
+  web__db: [web, db]
+edge_descs:
+  web__db: "queries"
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn draws_vertical_graph_with_unicode() {
+    let info_graph = info_graph().with_direction(GraphDir::Vertical);
+
+    let text = IntoTextSrc::into(&info_graph, &TextOptions::new());
+
+    assert_eq!(
+        r#"
+      ╭─────╮
+      │ CLI │
+      ╰─────╯
+         │
+         │
+         │
+         │
+         │
+         │
+┌─ App ──┼────────┐
+│        ▼        │
+│     ╭─────╮     │
+│     │ Web │     │
+│     ╰─────╯     │
+│        │        │
+│        │        │
+│        │        │
+│        │ queries│
+│        │        │
+│        ▼        │
+│ ╭─────────────╮ │
+│ │ 🗄️ Database │ │
+│ ╰─────────────╯ │
+└─────────────────┘
+"#
+        .trim_start_matches('\n'),
+        text
+    );
+}
+
+#[test]
+fn draws_horizontal_graph_with_ascii() {
+    let info_graph = info_graph().with_direction(GraphDir::Horizontal);
+
+    let text = IntoTextSrc::into(
+        &info_graph,
+        &TextOptions::new().with_charset(TextCharset::Ascii),
+    );
+
+    assert_eq!(
+        r#"
+                         +- App ------------------------------------+
+                         |                                          |
++-----+                  | +-----+      queries     +-------------+ |
+| CLI |------------------+>| Web |----------------->| 🗄️ Database | |
++-----+                  | +-----+                  +-------------+ |
+                         +------------------------------------------+
+"#
+        .trim_start_matches('\n'),
+        text
+    );
+}
+
+#[test]
+fn draws_arrows_for_edge_dirs() {
+    let graphviz_attrs = serde_yaml::from_str::<GraphvizAttrs>(
+        r#"
+edge_dirs:
+  cli__web: back
+  web__db: both
+"#,
+    )
+    .unwrap();
+    let info_graph = info_graph()
+        .with_direction(GraphDir::Horizontal)
+        .with_graphviz_attrs(graphviz_attrs);
+
+    let text = IntoTextSrc::into(&info_graph, &TextOptions::new());
+
+    assert!(
+        text.contains("│ CLI │◀─────────────────┼─│ Web │◀────────────────▶│ 🗄️ Database │"),
+        "{text}"
+    );
+}
+
+#[test]
+fn falls_back_to_tree_when_wider_than_width_max() {
+    let text = IntoTextSrc::into(&info_graph(), &TextOptions::new().with_width_max(16));
+
+    assert_eq!(
+        r#"
+App
+├── Web
+└── 🗄️ Database
+CLI
+
+CLI ──▶ Web
+Web ──▶ 🗄️ Data…
+"#
+        .trim_start_matches('\n'),
+        text
+    );
+}
+
+#[test]
+fn routes_edges_around_other_edges() {
+    let info_graph = serde_yaml::from_str::<InfoGraph>(
+        r#"
+hierarchy:
+  app:
+    web: {}
+    api: {}
+  db: {}
+edges:
+  web__api: [web, api]
+  api__db: [api, db]
+  db__web: [db, web]
+edge_descs:
+  db__web: "back"
+"#,
+    )
+    .unwrap();
+
+    let text_horizontal = IntoTextSrc::into(
+        &info_graph.clone().with_direction(GraphDir::Horizontal),
+        &TextOptions::new(),
+    );
+    let text_vertical = IntoTextSrc::into(
+        &info_graph.with_direction(GraphDir::Vertical),
+        &TextOptions::new(),
+    );
+
+    assert_eq!(
+        r#"
+┌─ app ──────────────────────┐
+│                            │
+│ ╭─────╮            ╭─────╮ │            ╭────╮
+│ │ web │───────────▶│ api │─┼───────────▶│ db │
+│ ╰─────╯            ╰─────╯ │            ╰────╯
+└──────▲─────────────────────┘             │
+       └───────────────────────────────────┘
+                       back
+"#
+        .trim_start_matches('\n'),
+        text_horizontal
+    );
+    assert_eq!(
+        r#"
+┌─ app ───┐
+│         │
+│ ╭─────╮ │
+│ │ web │ │
+│ ╰─────╯ │
+│   │  ▲  │
+│   └─┐└─┐│
+│     │  ││
+│     │  ││
+│    ┌┘  ││
+│    ▼   ││
+│ ╭─────╮││
+│ │ api │││back
+│ ╰─────╯││
+└────┼───┼┘
+     │ ┌─┘
+     │ │
+     │ │
+     │ │
+     │ │
+     ▼ │
+   ╭────╮
+   │ db │
+   ╰────╯
+"#
+        .trim_start_matches('\n'),
+        text_vertical
+    );
+}
@@ -0,0 +1,271 @@
+use dot_ix::{
+    model::{
+        common::{graphviz_attrs::EdgeDir, AnyId, EdgeId, NodeId, TagId},
+        info_graph::{GraphDir, GraphStyle},
+        theme::{AnyIdOrDefaults, ThemeAttr, ThemeSelector},
+    },
+    rt::{MermaidParseError, MermaidParseWarning, MermaidParser},
+};
+
+#[test]
+fn parses_subgraphs_labels_and_links() {
+    let mermaid_parse_output = MermaidParser::parse(
+        r#"---
+title: Deploy
+---
+flowchart LR
+    %% comment
+    subgraph app [App]
+        web("Web<br/>Serves #quot;pages#quot;")
+        subgraph storage
+            db[(Database)]
+        end
+    end
+    web -- queries --> db
+    db -.->|rows| web
+    cli & web ==> api; api --- db
+"#,
+    )
+    .unwrap();
+    let info_graph = mermaid_parse_output.info_graph();
+
+    assert_eq!(GraphDir::Horizontal, info_graph.direction());
+    assert_eq!(
+        &[MermaidParseWarning::ShapeUnsupported {
+            mermaid_id: String::from("db"),
+            shape: String::from("cylinder"),
+            line: 9,
+        }],
+        mermaid_parse_output.mermaid_parse_warnings()
+    );
+
+    let app = NodeId::try_from(String::from("app")).unwrap();
+    let storage = NodeId::try_from(String::from("storage")).unwrap();
+    let web = NodeId::try_from(String::from("web")).unwrap();
+    let db = NodeId::try_from(String::from("db")).unwrap();
+    let app_hierarchy = info_graph.hierarchy().get(&app).unwrap();
+    assert_eq!(
+        vec!["app", "cli", "api"],
+        info_graph
+            .hierarchy()
+            .keys()
+            .map(NodeId::as_str)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![&web, &storage],
+        app_hierarchy.keys().collect::<Vec<_>>()
+    );
+    assert!(app_hierarchy.get(&storage).unwrap().contains_key(&db));
+
+    assert_eq!(
+        Some("App"),
+        info_graph.node_names().get(&app).map(String::as_str)
+    );
+    assert_eq!(
+        Some("Web"),
+        info_graph.node_names().get(&web).map(String::as_str)
+    );
+    assert_eq!(
+        Some("Serves \"pages\""),
+        info_graph.node_descs().get(&web).map(String::as_str)
+    );
+
+    assert_eq!(
+        vec!["web__db", "db__web", "cli__api", "web__api", "api__db"],
+        info_graph
+            .edges()
+            .keys()
+            .map(EdgeId::as_str)
+            .collect::<Vec<_>>()
+    );
+    let edge_id = |id: &str| EdgeId::try_from(id.to_string()).unwrap();
+    assert_eq!(
+        Some("queries"),
+        info_graph
+            .edge_descs()
+            .get(&edge_id("web__db"))
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some("rows"),
+        info_graph
+            .edge_descs()
+            .get(&edge_id("db__web"))
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some(&EdgeDir::None),
+        info_graph
+            .graphviz_attrs()
+            .edge_dirs()
+            .get(&edge_id("api__db"))
+    );
+
+    let theme = info_graph.theme();
+    let edge_styles = |id: &str| {
+        theme
+            .get(&AnyIdOrDefaults::AnyId(AnyId::from(edge_id(id))))
+            .unwrap()
+    };
+    assert_eq!(
+        Some("dashed"),
+        edge_styles("db__web")
+            .get(&ThemeAttr::StrokeStyle)
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some("2"),
+        edge_styles("cli__api")
+            .get(&ThemeAttr::StrokeWidth)
+            .map(String::as_str)
+    );
+}
+
+#[test]
+fn maps_classes_to_tags_and_styles_to_theme() {
+    let mermaid_parse_output = MermaidParser::parse(
+        "graph TD
+    a:::critical --> b
+    b e1@---> c
+    classDef critical fill:#dc2626,stroke:#333,stroke-width:4px,rx:5
+    class b,e1 critical
+    style c color:white
+    linkStyle 1 stroke:blue",
+    )
+    .unwrap();
+    let info_graph = mermaid_parse_output.info_graph();
+
+    assert_eq!(
+        &[MermaidParseWarning::StyleUnsupported {
+            el: String::from("classDef `critical`"),
+            style: String::from("rx:5"),
+            line: 4,
+        }],
+        mermaid_parse_output.mermaid_parse_warnings()
+    );
+
+    let critical = TagId::try_from(String::from("critical")).unwrap();
+    let e1 = EdgeId::try_from(String::from("e1")).unwrap();
+    assert_eq!(
+        Some("critical"),
+        info_graph.tags().get(&critical).map(String::as_str)
+    );
+    assert_eq!(
+        Some(&vec![
+            AnyId::try_from(String::from("a")).unwrap(),
+            AnyId::try_from(String::from("b")).unwrap(),
+            AnyId::from(e1.clone()),
+        ]),
+        info_graph.tag_items().get(&critical)
+    );
+    assert_eq!(
+        Some(&4),
+        info_graph.graphviz_attrs().edge_minlens().get(&e1)
+    );
+
+    let theme = info_graph.theme();
+    let critical_styles = theme
+        .get(&AnyIdOrDefaults::Selector(ThemeSelector::Tag(critical)))
+        .unwrap();
+    assert_eq!(
+        Some("red"),
+        critical_styles
+            .get(&ThemeAttr::FillColor)
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some("600"),
+        critical_styles
+            .get(&ThemeAttr::FillShadeNormal)
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some("4"),
+        critical_styles
+            .get(&ThemeAttr::StrokeWidth)
+            .map(String::as_str)
+    );
+    assert!(theme
+        .get(&AnyIdOrDefaults::AnyId(
+            AnyId::try_from(String::from("c")).unwrap()
+        ))
+        .unwrap()
+        .contains_key(&ThemeAttr::TextColor));
+    assert_eq!(
+        Some("blue"),
+        theme
+            .get(&AnyIdOrDefaults::AnyId(AnyId::from(e1)))
+            .and_then(|styles| styles.get(&ThemeAttr::StrokeColor))
+            .map(String::as_str)
+    );
+}
+
+#[test]
+fn renames_invalid_ids_and_keeps_mermaid_ids() {
+    let mermaid_parse_output =
+        MermaidParser::parse("flowchart TB\n    my-node((One)) --> 2((Two))").unwrap();
+    let info_graph = mermaid_parse_output.info_graph();
+
+    assert_eq!(GraphStyle::Circle, info_graph.graph_style());
+    assert_eq!(
+        &[
+            MermaidParseWarning::IdRenamed {
+                mermaid_id: String::from("my-node"),
+                id: String::from("my_node"),
+                line: 2,
+            },
+            MermaidParseWarning::IdRenamed {
+                mermaid_id: String::from("2"),
+                id: String::from("_2"),
+                line: 2,
+            },
+        ],
+        mermaid_parse_output.mermaid_parse_warnings()
+    );
+    assert_eq!(
+        Some("my-node"),
+        mermaid_parse_output
+            .mermaid_ids()
+            .get(&AnyId::try_from(String::from("my_node")).unwrap())
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some("One"),
+        info_graph
+            .node_names()
+            .get(&NodeId::try_from(String::from("my_node")).unwrap())
+            .map(String::as_str)
+    );
+}
+
+#[test]
+fn returns_error_for_unsupported_diagram_and_syntax() {
+    assert_eq!(
+        MermaidParseError::DiagramTypeUnsupported {
+            diagram_type: String::from("sequenceDiagram"),
+            line: 1,
+        },
+        MermaidParser::parse("sequenceDiagram\n    a->>b: hi").unwrap_err()
+    );
+    assert_eq!(
+        MermaidParseError::SubgraphNotClosed {
+            subgraph_id: String::from("one"),
+            line: 2,
+        },
+        MermaidParser::parse("flowchart\n    subgraph one\n    a").unwrap_err()
+    );
+
+    let error = MermaidParser::parse("flowchart LR\n    a --> [b]").unwrap_err();
+    assert!(
+        matches!(
+            error,
+            MermaidParseError::Syntax {
+                line: 2,
+                column: 11,
+                ..
+            }
+        ),
+        "{error:?}"
+    );
+}
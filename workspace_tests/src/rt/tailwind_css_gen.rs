@@ -0,0 +1,153 @@
+use dot_ix::{
+    model::{
+        common::{DotSrcAndStyles, GraphvizDotTheme},
+        info_graph::InfoGraph,
+    },
+    rt::{IntoGraphvizDotSrc, TailwindCssGen},
+};
+
+#[test]
+fn generates_path_color_class() {
+    let css = TailwindCssGen::generate(["[&>path]:stroke-slate-600"]);
+
+    assert_eq!(
+        ".\\[\\&\\>path\\]\\:stroke-slate-600>path { stroke: #475569; }\n",
+        css
+    );
+}
+
+#[test]
+fn generates_focus_and_hover_variants_inside_element_variant() {
+    let css = TailwindCssGen::generate(["[&>path]:focus:hover:fill-lime-200"]);
+
+    assert_eq!(
+        ".\\[\\&\\>path\\]\\:focus\\:hover\\:fill-lime-200:hover:focus>path { fill: #d9f99d; }\n",
+        css
+    );
+}
+
+#[test]
+fn orders_rules_by_variant_instead_of_insertion() {
+    let css = TailwindCssGen::generate([
+        "[&>path]:focus:fill-lime-200 [&>path]:hover:fill-slate-300 [&>path]:fill-blue-500",
+    ]);
+
+    assert_eq!(
+        ".\\[\\&\\>path\\]\\:fill-blue-500>path { fill: #3b82f6; }\n\
+        .\\[\\&\\>path\\]\\:hover\\:fill-slate-300:hover>path { fill: #cbd5e1; }\n\
+        .\\[\\&\\>path\\]\\:focus\\:fill-lime-200:focus>path { fill: #d9f99d; }\n",
+        css
+    );
+}
+
+#[test]
+fn generates_peer_focus_selector() {
+    let css = TailwindCssGen::generate(["peer-focus/tag_0:[&>path]:stroke-2 peer/tag_0"]);
+
+    assert_eq!(
+        ".peer\\/tag_0:focus ~ \
+        .peer-focus\\/tag_0\\:\\[\\&\\>path\\]\\:stroke-2>path { stroke-width: 2; }\n",
+        css
+    );
+}
+
+#[test]
+fn generates_arbitrary_property_and_values() {
+    let css = TailwindCssGen::generate([
+        "[&>path]:[stroke-dasharray:3] outline-[2px] animate-[move_2s_linear_infinite]",
+    ]);
+
+    assert_eq!(
+        "\
+        .outline-\\[2px\\] { outline-width: 2px; }\n\
+        .animate-\\[move_2s_linear_infinite\\] { animation: move 2s linear infinite; }\n\
+        .\\[\\&\\>path\\]\\:\\[stroke-dasharray\\:3\\]>path { stroke-dasharray: 3; }\n",
+        css
+    );
+}
+
+#[test]
+fn generates_spacing_and_keyframes() {
+    let css = TailwindCssGen::generate(["px-1.5 animate-ping"]);
+
+    assert_eq!(
+        "\
+        @keyframes ping { 75%, 100% { transform: scale(2); opacity: 0; } }\n\
+        .px-1\\.5 { padding-left: 0.375rem; padding-right: 0.375rem; }\n\
+        .animate-ping { animation: ping 1s cubic-bezier(0, 0, 0.2, 1) infinite; }\n",
+        css
+    );
+}
+
+#[test]
+fn generates_typography_and_shadows() {
+    let css = TailwindCssGen::generate([
+        "[&>text]:font-semibold [&>text]:text-sm text-slate-700 shadow-sm drop-shadow-2xl",
+    ]);
+
+    assert_eq!(
+        "\
+        .text-slate-700 { color: #334155; }\n\
+        .shadow-sm { box-shadow: 0 1px 2px 0 rgb(0 0 0 / 0.05); }\n\
+        .drop-shadow-2xl { filter: drop-shadow(0 25px 25px rgb(0 0 0 / 0.15)); }\n\
+        .\\[\\&\\>text\\]\\:font-semibold>text { font-weight: 600; }\n\
+        .\\[\\&\\>text\\]\\:text-sm>text { font-size: 0.875rem; line-height: 1.25rem; }\n",
+        css
+    );
+}
+
+#[test]
+fn generates_edge_flow_keyframes_once() {
+    let css = TailwindCssGen::generate([
+        "[&>path]:[--dot-ix-dash-period:8] [&>path]:animate-edge-march",
+        "peer-focus/tag_0:[&>path]:animate-edge-flow-alternate",
+    ]);
+
+    assert_eq!(
+        "\
+        @keyframes dot_ix_edge_dash { \
+            from { stroke-dashoffset: 0; } \
+            to { stroke-dashoffset: calc(-1px * var(--dot-ix-dash-period, 8)); } \
+        }\n\
+        .\\[\\&\\>path\\]\\:\\[--dot-ix-dash-period\\:8\\]>path { --dot-ix-dash-period: 8; }\n\
+        .\\[\\&\\>path\\]\\:animate-edge-march>path { \
+            animation: dot_ix_edge_dash 0.5s linear infinite; }\n\
+        .peer\\/tag_0:focus ~ .peer-focus\\/tag_0\\:\\[\\&\\>path\\]\\:animate-edge-flow-alternate>path { \
+            animation: dot_ix_edge_dash 1s linear infinite alternate; }\n",
+        css
+    );
+}
+
+#[test]
+fn classes_unsupported_returns_unknown_classes() {
+    let tailwind_css_gen =
+        TailwindCssGen::from_iter(["fill-slate-300 fill-unknown-300 peer/tag_0 unknown"]);
+
+    assert_eq!(
+        vec!["fill-unknown-300", "unknown"],
+        tailwind_css_gen.classes_unsupported().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn info_graph_styles_contains_generated_css_and_user_css() {
+    let info_graph = info_graph_single_node();
+
+    let DotSrcAndStyles { styles, .. } =
+        IntoGraphvizDotSrc::into(&info_graph, &GraphvizDotTheme::default());
+
+    assert!(styles.contains(".\\[\\&\\>path\\]\\:fill-slate-300>path { fill: #cbd5e1; }"));
+    assert!(styles.contains(".outline-none { outline: 2px solid transparent;"));
+    assert!(styles.ends_with("/* user css */"));
+}
+
+fn info_graph_single_node() -> InfoGraph {
+    use dot_ix::model::{common::NodeHierarchy, node_id};
+
+    let mut hierarchy = NodeHierarchy::new();
+    hierarchy.insert(node_id!("a"), NodeHierarchy::new());
+
+    InfoGraph::default()
+        .with_hierarchy(hierarchy)
+        .with_css(String::from("/* user css */"))
+}